@@ -0,0 +1,171 @@
+use crate::commands::llm_commands::{current_client, ensure_enabled};
+use crate::llm::{ActionPlanner, ActionProposal, Priority, ProposedActionKind, ProposedActionResult, ProposedActionStatus};
+use crate::models::audit::{AuditEntry, EventType};
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::monitor::current_ports;
+use crate::services::security::confirmation::ConfirmationGuard;
+use crate::services::security::AuditLogger;
+use crate::services::system_stats::HostInfo;
+use crate::services::user_scope::UserScope;
+use crate::services::{ServiceManager, ServiceMonitor};
+use crate::services::port::port_owner;
+use super::service_commands::{disable_service_autostart, service_type_name, stop_discovered_service, stop_port_owner};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// How long proposed actions can be executed after they were proposed
+const PROPOSAL_TTL_MINUTES: i64 = 15;
+
+static PROPOSALS: OnceLock<Mutex<HashMap<String, ActionProposal>>> = OnceLock::new();
+
+fn proposals() -> &'static Mutex<HashMap<String, ActionProposal>> {
+    PROPOSALS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Let the model propose actions for the current services. Nothing is
+/// changed until `execute_proposed_actions`.
+#[tauri::command]
+pub async fn propose_service_actions() -> Result<ActionProposal, String> {
+    ensure_enabled()?;
+    let client = current_client().await.with_priority(Priority::High);
+    if !client.can_generate().await {
+        return Err("Ollama ist nicht verfügbar. Starte Ollama für KI-Aktionsvorschläge.".into());
+    }
+
+    let mut services = ServiceManager::new().discover_all().await;
+    UserScope::retain_services(&mut services);
+    let mut ports = tokio::task::spawn_blocking(current_ports).await.map_err(|e| e.to_string())?;
+    UserScope::retain_ports(&mut ports);
+
+    let proposal = ActionPlanner::new(client)
+        .propose(&services, &ports, &HostInfo::current().prompt_context())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut stored = proposals().lock().unwrap_or_else(|e| e.into_inner());
+    let cutoff = Utc::now() - chrono::Duration::minutes(PROPOSAL_TTL_MINUTES);
+    stored.retain(|_, p| p.created_at > cutoff);
+    stored.insert(proposal.id.clone(), proposal.clone());
+
+    Ok(proposal)
+}
+
+/// Run the actions the user accepted. Every action is audit logged as
+/// proposed by the assistant; a failed action doesn't stop the others.
+#[tauri::command]
+pub async fn execute_proposed_actions(
+    proposal_id: String,
+    accepted_actions: Vec<String>,
+) -> Result<Vec<ProposedActionResult>, String> {
+    let proposal = {
+        let mut stored = proposals().lock().unwrap_or_else(|e| e.into_inner());
+        stored.remove(&proposal_id)
+    }
+    .ok_or("Die Vorschläge sind unbekannt oder wurden bereits ausgeführt")?;
+
+    if proposal.created_at < Utc::now() - chrono::Duration::minutes(PROPOSAL_TTL_MINUTES) {
+        return Err("Die Vorschläge sind abgelaufen. Bitte neu anfordern.".into());
+    }
+    if let Some(unknown) = accepted_actions.iter().find(|id| !proposal.actions.iter().any(|a| &a.id == *id)) {
+        return Err(format!("Aktion {} gehört nicht zu diesen Vorschlägen", unknown));
+    }
+
+    let actions: Vec<_> = proposal.actions.iter().filter(|a| accepted_actions.contains(&a.id)).collect();
+
+    // Simulated services carry made-up ids and PIDs that may exist on the host
+    let skipped = if ConfirmationGuard::is_dry_run() {
+        Some(ProposedActionStatus::DryRun)
+    } else if MockDiscovery::is_enabled() {
+        Some(ProposedActionStatus::Simulated)
+    } else {
+        None
+    };
+    if let Some(status) = skipped {
+        return Ok(actions
+            .iter()
+            .map(|a| ProposedActionResult {
+                action_id: a.id.clone(),
+                status: status.clone(),
+                error: None,
+            })
+            .collect());
+    }
+
+    let logger = AuditLogger::new();
+    let mut results = Vec::with_capacity(actions.len());
+
+    for action in actions {
+        let result = execute_action(&action.action).await;
+
+        let mut entry = AuditEntry::new(event_type(&action.action), format!("assistant: {}", action.title));
+        entry.service_id = match &action.action {
+            ProposedActionKind::StopService { service_id } | ProposedActionKind::DisableAutostart { service_id, .. } => {
+                Some(service_id.clone())
+            }
+            ProposedActionKind::FreePort { .. } => None,
+        };
+        entry.success = result.is_ok();
+        entry.error_message = result.as_ref().err().cloned();
+        entry.details = serde_json::json!({
+            "source": "llm",
+            "proposal_id": proposal.id,
+            "action_id": action.id,
+            "action": action.action,
+            "reason": action.reason,
+        });
+        let _ = logger.log(&entry);
+
+        results.push(ProposedActionResult {
+            action_id: action.id.clone(),
+            status: if result.is_ok() { ProposedActionStatus::Executed } else { ProposedActionStatus::Failed },
+            error: result.err(),
+        });
+    }
+
+    ServiceMonitor::request_refresh(None);
+    Ok(results)
+}
+
+async fn execute_action(action: &ProposedActionKind) -> Result<(), String> {
+    match action {
+        ProposedActionKind::StopService { service_id } => {
+            let service = ServiceManager::new()
+                .get_service(service_id)
+                .await
+                .ok_or_else(|| format!("Service {} not found", service_id))?;
+            stop_discovered_service(&service).await.map_err(|e| e.to_string())
+        }
+        ProposedActionKind::DisableAutostart { service_id, service_type } => {
+            disable_service_autostart(service_id.clone(), service_type_name(service_type).to_string()).await
+        }
+        ProposedActionKind::FreePort { port, service_id, pid: proposed_pid } => {
+            // Only the owner the user saw in the proposal is stopped, not
+            // whatever took the port since
+            let port = *port;
+            let listeners = tokio::task::spawn_blocking(current_ports).await.map_err(|e| e.to_string())?;
+            let pid = listeners.iter().filter(|p| p.port == port).find_map(|p| p.pid);
+            let services = ServiceManager::new().discover_all().await;
+            let service = port_owner(port, pid, &services);
+            let unchanged = match (service_id, service) {
+                (Some(id), Some(service)) => *id == service.id,
+                (Some(_), None) => false,
+                (None, _) => proposed_pid.is_some() && pid == *proposed_pid,
+            };
+            if !unchanged {
+                return Err(format!(
+                    "Port {} gehört nicht mehr dem vorgeschlagenen Prozess; die Aktion wurde nicht ausgeführt",
+                    port
+                ));
+            }
+            stop_port_owner(port, service, pid).await
+        }
+    }
+}
+
+fn event_type(action: &ProposedActionKind) -> EventType {
+    match action {
+        ProposedActionKind::StopService { .. } | ProposedActionKind::FreePort { .. } => EventType::ServiceStop,
+        ProposedActionKind::DisableAutostart { .. } => EventType::ConfigChange,
+    }
+}
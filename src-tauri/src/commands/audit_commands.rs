@@ -1,3 +1,6 @@
+use crate::commands::llm_commands::{current_client, ensure_enabled};
+use crate::llm::{AuditAnswer, AuditAssistant, Priority};
+use crate::services::export::{ExportDocument, ExportFormat, Exporter};
 use crate::services::security::AuditLogger;
 use std::sync::OnceLock;
 
@@ -24,29 +27,33 @@ pub async fn get_audit_logs(limit: Option<u32>) -> Result<Vec<serde_json::Value>
 
 #[tauri::command]
 pub async fn export_audit_logs(format: String) -> Result<String, String> {
-    let logger = get_logger();
-    let entries = logger
+    let format = match format.as_str() {
+        "json" => ExportFormat::Json,
+        "csv" => ExportFormat::Csv,
+        "markdown" | "md" => ExportFormat::Markdown,
+        "html" => ExportFormat::Html,
+        _ => return Err(format!("Unsupported format: {}", format)),
+    };
+    let entries = get_logger()
         .get_entries(10000)
         .map_err(|e| e.to_string())?;
 
-    match format.as_str() {
-        "json" => serde_json::to_string_pretty(&entries).map_err(|e| e.to_string()),
-        "csv" => {
-            let mut csv = String::from("timestamp,event_type,user,operation,service_id,success,error_message\n");
-            for entry in entries {
-                csv.push_str(&format!(
-                    "{},{:?},{},{},{},{},{}\n",
-                    entry.timestamp,
-                    entry.event_type,
-                    entry.user,
-                    entry.operation,
-                    entry.service_id.unwrap_or_default(),
-                    entry.success,
-                    entry.error_message.unwrap_or_default()
-                ));
-            }
-            Ok(csv)
-        }
-        _ => Err(format!("Unsupported format: {}", format)),
+    Exporter::new()
+        .render(&ExportDocument::audit(entries), format)
+        .map_err(|e| e.to_string())
+}
+
+/// Answer a question like "what stopped postgres last Tuesday?" from the
+/// audit entries matching it
+#[tauri::command]
+pub async fn ask_audit_log(question: String) -> Result<AuditAnswer, String> {
+    if question.trim().is_empty() {
+        return Err("Bitte eine Frage eingeben".into());
+    }
+    ensure_enabled()?;
+    let client = current_client().await.with_priority(Priority::High);
+    if !client.can_generate().await {
+        return Err("Ollama ist nicht verfügbar. Starte Ollama, um Fragen zum Audit-Log zu stellen.".into());
     }
+    AuditAssistant::new(client).answer(&question).await.map_err(|e| e.to_string())
 }
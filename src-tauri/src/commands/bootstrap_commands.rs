@@ -0,0 +1,24 @@
+use crate::commands::llm_commands::current_client;
+use crate::services::bootstrap::{BaselineSummary, Bootstrap, BootstrapStatus};
+
+// First-run setup, driven step by step by the setup screen:
+// get_bootstrap_status -> pull_ollama_model -> create_baseline_snapshot -> complete_bootstrap
+
+/// Missing tools with install commands, model and baseline state
+#[tauri::command]
+pub async fn get_bootstrap_status() -> Result<BootstrapStatus, String> {
+    Ok(Bootstrap::status(&current_client().await).await)
+}
+
+#[tauri::command]
+pub async fn create_baseline_snapshot() -> Result<BaselineSummary, String> {
+    Bootstrap::create_baseline().await.map_err(|e| e.to_string())
+}
+
+/// Mark setup as done (or skipped) so it isn't shown on the next start
+#[tauri::command]
+pub async fn complete_bootstrap() -> Result<(), String> {
+    tokio::task::spawn_blocking(Bootstrap::complete)
+        .await
+        .map_err(|e| e.to_string())
+}
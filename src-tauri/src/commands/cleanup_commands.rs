@@ -0,0 +1,212 @@
+use crate::models::audit::{AuditEntry, EventType};
+use crate::services::cleanup::{
+    CleanupAction, CleanupPlan, CleanupPlanner, CleanupReport, CleanupRisk, CleanupStep, CleanupStepResult,
+    CleanupStepStatus,
+};
+use crate::services::docker_inventory::DockerInventory;
+use crate::services::security::confirmation::{ActionOutcome, ConfirmationGuard, ImpactPreview};
+use crate::services::security::AuditLogger;
+use crate::services::ServiceManager;
+use super::service_commands::{disable_service_autostart, service_type_name, stop_discovered_service};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// How long a plan can be executed after it was created
+const PLAN_TTL_MINUTES: i64 = 30;
+
+static PLANS: OnceLock<Mutex<HashMap<String, CleanupPlan>>> = OnceLock::new();
+
+fn plans() -> &'static Mutex<HashMap<String, CleanupPlan>> {
+    PLANS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Build an ordered cleanup plan. Nothing is changed until `execute_cleanup`.
+#[tauri::command]
+pub async fn plan_cleanup() -> Result<CleanupPlan, String> {
+    let services = ServiceManager::new().discover_all().await;
+
+    let inventory = DockerInventory::new();
+    let (images, volumes) = if inventory.is_available() {
+        (
+            inventory.list_images().await.unwrap_or_default(),
+            inventory.list_volumes().await.unwrap_or_default(),
+        )
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let plan = CleanupPlanner::new().plan(&services, &images, &volumes);
+
+    let mut stored = plans().lock().unwrap_or_else(|e| e.into_inner());
+    let cutoff = Utc::now() - chrono::Duration::minutes(PLAN_TTL_MINUTES);
+    stored.retain(|_, p| p.created_at > cutoff);
+    stored.insert(plan.id.clone(), plan.clone());
+
+    Ok(plan)
+}
+
+/// Apply the accepted steps of a plan in plan order. Every step is audit
+/// logged; a failed step doesn't stop the remaining ones.
+///
+/// Stopping services and removing volumes goes through the confirmation
+/// protocol: the token is bound to the plan and the accepted steps. Plans
+/// with only low-risk steps run right away.
+#[tauri::command]
+pub async fn execute_cleanup(
+    plan_id: String,
+    accepted_steps: Vec<String>,
+    confirmation_token: Option<String>,
+) -> Result<CleanupReport, String> {
+    let plan = {
+        let stored = plans().lock().unwrap_or_else(|e| e.into_inner());
+        stored.get(&plan_id).cloned()
+    }
+    .ok_or("Der Bereinigungsplan ist unbekannt oder wurde bereits ausgeführt")?;
+
+    if plan.created_at < Utc::now() - chrono::Duration::minutes(PLAN_TTL_MINUTES) {
+        return Err("Der Bereinigungsplan ist abgelaufen. Bitte neu erstellen.".into());
+    }
+    if let Some(unknown) = accepted_steps.iter().find(|id| !plan.steps.iter().any(|s| &s.id == *id)) {
+        return Err(format!("Schritt {} gehört nicht zu diesem Plan", unknown));
+    }
+
+    let steps: Vec<&CleanupStep> = plan.steps.iter().filter(|s| accepted_steps.contains(&s.id)).collect();
+
+    if ConfirmationGuard::is_dry_run() || steps.iter().any(|s| s.risk != CleanupRisk::Low) {
+        let target = format!(
+            "{}:{}",
+            plan.id,
+            steps.iter().map(|s| s.id.as_str()).collect::<Vec<_>>().join(",")
+        );
+        let guard = ConfirmationGuard::check("execute_cleanup", &target, confirmation_token.as_deref(), || {
+            cleanup_impact(&plan, &steps)
+        })
+        .map_err(|e| e.to_string())?;
+        if let Some(outcome) = guard {
+            let dry_run = matches!(outcome, ActionOutcome::DryRun { .. });
+            return Ok(CleanupReport {
+                plan_id,
+                outcome,
+                results: steps
+                    .iter()
+                    .filter(|_| dry_run)
+                    .map(|s| CleanupStepResult {
+                        step_id: s.id.clone(),
+                        status: CleanupStepStatus::DryRun,
+                        error: None,
+                    })
+                    .collect(),
+                freed_bytes: 0,
+            });
+        }
+    }
+
+    // A plan runs once, even if the same token was used twice in parallel
+    if plans().lock().unwrap_or_else(|e| e.into_inner()).remove(&plan_id).is_none() {
+        return Err("Der Bereinigungsplan wurde bereits ausgeführt".into());
+    }
+
+    let logger = AuditLogger::new();
+    let mut results = Vec::with_capacity(steps.len());
+    let mut freed_bytes = 0;
+
+    for step in steps {
+        let result = execute_step(&step.action).await;
+
+        let mut entry = AuditEntry::new(event_type(&step.action), format!("cleanup: {}", step.title));
+        entry.service_id = match &step.action {
+            CleanupAction::StopService { service_id } | CleanupAction::DisableAutostart { service_id, .. } => {
+                Some(service_id.clone())
+            }
+            _ => None,
+        };
+        entry.success = result.is_ok();
+        entry.error_message = result.as_ref().err().cloned();
+        entry.details = serde_json::json!({
+            "plan_id": plan.id,
+            "step_id": step.id,
+            "category": step.category,
+            "action": step.action,
+        });
+        let _ = logger.log(&entry);
+
+        results.push(match result {
+            Ok(()) => {
+                freed_bytes += step.expected_savings_bytes.unwrap_or(0);
+                CleanupStepResult {
+                    step_id: step.id.clone(),
+                    status: CleanupStepStatus::Executed,
+                    error: None,
+                }
+            }
+            Err(error) => CleanupStepResult {
+                step_id: step.id.clone(),
+                status: CleanupStepStatus::Failed,
+                error: Some(error),
+            },
+        });
+    }
+
+    Ok(CleanupReport {
+        plan_id,
+        outcome: ActionOutcome::Executed,
+        results,
+        freed_bytes,
+    })
+}
+
+/// The accepted steps, riskiest first, as shown in the confirmation
+fn cleanup_impact(plan: &CleanupPlan, steps: &[&CleanupStep]) -> ImpactPreview {
+    let mut affected: Vec<&CleanupStep> = steps.to_vec();
+    affected.sort_by_key(|s| match s.risk {
+        CleanupRisk::High => 0,
+        CleanupRisk::Medium => 1,
+        CleanupRisk::Low => 2,
+    });
+    ImpactPreview {
+        action: "execute_cleanup".to_string(),
+        target: plan.id.clone(),
+        target_name: Some(format!("{} Bereinigungsschritte", steps.len())),
+        ports_freed: Vec::new(),
+        dependents_affected: affected
+            .iter()
+            .map(|s| match s.risk {
+                CleanupRisk::High => format!("{} (Datenverlust möglich)", s.title),
+                _ => s.title.clone(),
+            })
+            .collect(),
+    }
+}
+
+async fn execute_step(action: &CleanupAction) -> Result<(), String> {
+    match action {
+        CleanupAction::StopService { service_id } => {
+            let service = ServiceManager::new()
+                .get_service(service_id)
+                .await
+                .ok_or_else(|| format!("Service {} not found", service_id))?;
+            stop_discovered_service(&service).await.map_err(|e| e.to_string())
+        }
+        CleanupAction::DisableAutostart { service_id, service_type } => {
+            disable_service_autostart(service_id.clone(), service_type_name(service_type).to_string()).await
+        }
+        CleanupAction::RemoveImage { image_id } => DockerInventory::new()
+            .remove_image(image_id, false)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        CleanupAction::RemoveVolume { name } => DockerInventory::new()
+            .remove_volume(name)
+            .await
+            .map_err(|e| e.to_string()),
+    }
+}
+
+fn event_type(action: &CleanupAction) -> EventType {
+    match action {
+        CleanupAction::StopService { .. } => EventType::ServiceStop,
+        CleanupAction::DisableAutostart { .. } => EventType::ConfigChange,
+        CleanupAction::RemoveImage { .. } | CleanupAction::RemoveVolume { .. } => EventType::Cleanup,
+    }
+}
@@ -1,4 +1,24 @@
-use crate::models::config::Config;
+use crate::models::audit::{AuditEntry, EventType};
+use crate::models::config::{AcknowledgedIssue, Config, PortReservation, TunnelSpec};
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::security::confirmation::ConfirmationGuard;
+use crate::services::alerts::AlertEngine;
+use crate::services::digest::DigestScheduler;
+use crate::services::hash_reputation::HashReputation;
+use crate::services::history::MonitoringHistory;
+use crate::services::remote::RemoteViewer;
+use crate::services::{DiskWatcher, FlapDetector, MemoryWatcher, PortWatcher, ResourceTracker};
+use crate::services::dependency_map::DependencyMapper;
+use crate::services::notifier::Notifier;
+use crate::services::powermetrics::PowermetricsSampler;
+use crate::services::port::{NmapScanner, PortHistory, PortReservations, ServiceNames};
+use crate::services::security::{AuditLogger, SecurityAcknowledgements};
+use crate::services::storage::{Storage, StoragePaths};
+use crate::services::tunnel::TunnelManager;
+use crate::services::user_scope::UserScope;
+use crate::services::webhooks::WebhookDispatcher;
+use crate::commands::llm_commands::configure_client;
+use crate::llm::{CloudLlm, LlmQueue, ResponseLanguage};
 use std::sync::OnceLock;
 use tokio::sync::RwLock;
 
@@ -6,7 +26,30 @@ use tokio::sync::RwLock;
 static CONFIG: OnceLock<RwLock<Config>> = OnceLock::new();
 
 fn get_config_store() -> &'static RwLock<Config> {
-    CONFIG.get_or_init(|| RwLock::new(Config::default()))
+    CONFIG.get_or_init(|| {
+        RwLock::new(Config {
+            port_reservations: PortReservations::list(),
+            acknowledged_issues: SecurityAcknowledgements::list(),
+            storage: Storage::overrides(),
+            tunnels: TunnelManager::specs(),
+            ..Config::default()
+        })
+    })
+}
+
+/// Keep the config in step after reservations changed through the port commands
+pub(crate) async fn set_port_reservations(reservations: Vec<PortReservation>) {
+    get_config_store().write().await.port_reservations = reservations;
+}
+
+/// Same for findings acknowledged or revoked through the security commands
+pub(crate) async fn set_acknowledged_issues(acknowledged: Vec<AcknowledgedIssue>) {
+    get_config_store().write().await.acknowledged_issues = acknowledged;
+}
+
+/// Same for tunnels saved or removed through the tunnel commands
+pub(crate) async fn set_tunnels(tunnels: Vec<TunnelSpec>) {
+    get_config_store().write().await.tunnels = tunnels;
 }
 
 #[tauri::command]
@@ -18,6 +61,64 @@ pub async fn get_config() -> Result<Config, String> {
 #[tauri::command]
 pub async fn update_config(config: Config) -> Result<(), String> {
     let mut current = get_config_store().write().await;
-    *current = config;
+    // Before anything else writes to the old locations
+    let migration = Storage::configure(&config.storage).map_err(|e| e.to_string())?;
+    if !migration.moved.is_empty() || !migration.skipped.is_empty() {
+        let mut entry = AuditEntry::new(EventType::ConfigChange, "migrate_storage".to_string());
+        entry.details = serde_json::json!({ "moved": migration.moved, "skipped": migration.skipped });
+        let _ = AuditLogger::new().log(&entry);
+    }
+    MockDiscovery::set_enabled(config.simulation_mode);
+    ConfirmationGuard::configure(&config.security);
+    UserScope::configure(&config.security);
+    LlmQueue::global().configure(&config.ollama);
+    if current.ollama != config.ollama {
+        configure_client(&config.ollama).await;
+    }
+    ResponseLanguage::configure(&config.ollama);
+    DigestScheduler::configure(&config);
+    RemoteViewer::configure(&config.remote_viewer);
+    PortWatcher::configure(&config.port_watch);
+    DiskWatcher::configure(&config.disk_watch);
+    MemoryWatcher::configure(&config.memory_watch);
+    FlapDetector::configure(&config.flapping);
+    ResourceTracker::configure(&config.resource_events);
+    PortHistory::configure(&config.port_history);
+    MonitoringHistory::configure(&config.monitoring_history);
+    AlertEngine::configure(&config.alerts);
+    Notifier::configure(&config.notifications);
+    ServiceNames::configure(&config.service_names);
+    NmapScanner::configure(&config.nmap);
+    DependencyMapper::configure(&config);
+    WebhookDispatcher::configure(&config.webhooks);
+    PowermetricsSampler::configure(&config.powermetrics);
+    HashReputation::configure(&config.hash_reputation);
+    if current.cloud_llm.enabled != config.cloud_llm.enabled {
+        let state = if config.cloud_llm.enabled { "enabled" } else { "disabled" };
+        let mut entry = AuditEntry::new(EventType::ConfigChange, format!("cloud_llm: {}", state));
+        entry.details = serde_json::json!({ "provider": config.cloud_llm.provider });
+        let _ = AuditLogger::new().log(&entry);
+    }
+    CloudLlm::configure(&config.cloud_llm);
+    // Reservations, acknowledgements and tunnels only change through their
+    // own commands; the copies the frontend sends back may be stale
+    *current = Config {
+        port_reservations: PortReservations::list(),
+        acknowledged_issues: SecurityAcknowledgements::list(),
+        tunnels: TunnelManager::specs(),
+        ..config
+    };
     Ok(())
 }
+
+/// Effective config, data and log directories
+#[tauri::command]
+pub async fn get_storage_paths() -> Result<StoragePaths, String> {
+    Ok(Storage::paths())
+}
+
+/// Send a test event to a saved webhook
+#[tauri::command]
+pub async fn test_webhook(id: String) -> Result<(), String> {
+    WebhookDispatcher::test(&id).await.map_err(|e| e.to_string())
+}
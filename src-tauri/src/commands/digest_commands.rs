@@ -0,0 +1,11 @@
+use crate::services::digest::{DigestGenerator, DigestReport, DigestScheduler};
+
+/// Generate the weekly digest now instead of waiting for the schedule
+#[tauri::command]
+pub async fn generate_digest(llm_summary: Option<bool>) -> Result<DigestReport, String> {
+    let client = llm_summary.unwrap_or(false).then(DigestScheduler::llm_client).flatten();
+    DigestGenerator::new()
+        .generate(client.as_ref())
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,55 @@
+use crate::services::docker_inventory::{DockerImage, DockerInventory, DockerNetwork, DockerVolume};
+use tauri::{AppHandle, Emitter};
+
+#[tauri::command]
+pub async fn list_images(dangling_only: Option<bool>) -> Result<Vec<DockerImage>, String> {
+    let images = DockerInventory::new()
+        .list_images()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if dangling_only.unwrap_or(false) {
+        Ok(images.into_iter().filter(|i| i.dangling).collect())
+    } else {
+        Ok(images)
+    }
+}
+
+/// Pull an image, emitting `image-pull-progress` events while downloading
+#[tauri::command]
+pub async fn pull_image(app: AppHandle, image: String) -> Result<(), String> {
+    if image.trim().is_empty() {
+        return Err("Es muss ein Image angegeben werden".into());
+    }
+
+    DockerInventory::new()
+        .pull_image(&image, |progress| {
+            let _ = app.emit("image-pull-progress", progress);
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_image(image: String, force: Option<bool>) -> Result<Vec<String>, String> {
+    DockerInventory::new()
+        .remove_image(&image, force.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_docker_volumes() -> Result<Vec<DockerVolume>, String> {
+    DockerInventory::new()
+        .list_volumes()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_docker_networks() -> Result<Vec<DockerNetwork>, String> {
+    DockerInventory::new()
+        .list_networks()
+        .await
+        .map_err(|e| e.to_string())
+}
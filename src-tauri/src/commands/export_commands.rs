@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+use crate::services::export::{ExportDocument, ExportFormat, ExportKind, ExportResult, Exporter};
+use crate::services::security_report::{ReportFormat, SecurityReport};
+
+/// Write the audit log, security report, inventory or metrics to `path`, or
+/// to the exports directory when no path is given
+#[tauri::command]
+pub async fn export(kind: ExportKind, format: ExportFormat, path: Option<String>) -> Result<ExportResult, String> {
+    let document = ExportDocument::collect(kind).await.map_err(|e| e.to_string())?;
+    let path = path.filter(|p| !p.trim().is_empty()).map(PathBuf::from);
+    tokio::task::spawn_blocking(move || Exporter::new().write(&document, format, path.as_deref()))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Render the latest stored security scan with the trend of earlier scans
+/// as a styled HTML or PDF report, or as SARIF for other tools
+#[tauri::command]
+pub async fn export_security_report(format: ReportFormat, path: Option<String>) -> Result<ExportResult, String> {
+    let report = SecurityReport::collect().await.map_err(|e| e.to_string())?;
+    let path = path.filter(|p| !p.trim().is_empty()).map(PathBuf::from);
+    tokio::task::spawn_blocking(move || report.write(format, path.as_deref()))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Where user templates for Markdown and HTML exports are looked up
+#[tauri::command]
+pub async fn get_export_template_dir() -> Result<String, String> {
+    Ok(Exporter::new().template_dir().to_string_lossy().to_string())
+}
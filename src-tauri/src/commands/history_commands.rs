@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use crate::models::alert::Alert;
+use crate::services::history::{HistoryStore, PortCountPoint, SecurityScanSummary, ServicePoint, SystemPoint};
+use crate::services::security_scanner::{SecurityScanDiff, SecurityScanResult};
+
+/// `from`/`to` with the defaults shared by all history commands: up to now,
+/// starting 24 hours earlier
+fn time_range(from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> Result<(DateTime<Utc>, DateTime<Utc>), String> {
+    let to = to.unwrap_or_else(Utc::now);
+    let from = from.unwrap_or(to - chrono::Duration::hours(24));
+    if from > to {
+        return Err("Der Beginn muss vor dem Ende liegen".into());
+    }
+    Ok((from, to))
+}
+
+/// Recorded CPU and memory usage of the whole system
+#[tauri::command]
+pub async fn get_system_history(
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<Vec<SystemPoint>, String> {
+    let (from, to) = time_range(from, to)?;
+    HistoryStore::system_series(from, to).await.map_err(|e| e.to_string())
+}
+
+/// Recorded status, CPU and memory of one service
+#[tauri::command]
+pub async fn get_service_history(
+    service_id: String,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<Vec<ServicePoint>, String> {
+    let (from, to) = time_range(from, to)?;
+    HistoryStore::service_series(&service_id, from, to).await.map_err(|e| e.to_string())
+}
+
+/// Number of listening and publicly reachable ports over time
+#[tauri::command]
+pub async fn get_port_count_history(
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<Vec<PortCountPoint>, String> {
+    let (from, to) = time_range(from, to)?;
+    HistoryStore::port_series(from, to).await.map_err(|e| e.to_string())
+}
+
+/// Fired alerts, newest first
+#[tauri::command]
+pub async fn get_alert_history(limit: Option<u32>) -> Result<Vec<Alert>, String> {
+    HistoryStore::alerts(limit.unwrap_or(100)).await.map_err(|e| e.to_string())
+}
+
+/// Stored security scans, newest first
+#[tauri::command]
+pub async fn get_security_scans(limit: Option<u32>) -> Result<Vec<SecurityScanSummary>, String> {
+    HistoryStore::security_scans(limit.unwrap_or(50)).await.map_err(|e| e.to_string())
+}
+
+/// All findings of a stored security scan
+#[tauri::command]
+pub async fn get_security_scan(id: i64) -> Result<SecurityScanResult, String> {
+    HistoryStore::security_scan(Some(id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|(_, result)| result)
+        .ok_or_else(|| format!("Scan {} nicht gefunden", id))
+}
+
+/// New, fixed and persistent findings between two stored scans; `to`
+/// defaults to the latest scan
+#[tauri::command]
+pub async fn diff_security_scans(from: i64, to: Option<i64>) -> Result<SecurityScanDiff, String> {
+    let (_, before) = HistoryStore::security_scan(Some(from))
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Scan {} nicht gefunden", from))?;
+    let (_, after) = HistoryStore::security_scan(to)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Keine gespeicherten Scans".to_string())?;
+    Ok(before.diff(&after))
+}
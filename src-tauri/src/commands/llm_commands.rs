@@ -1,349 +1,84 @@
-use crate::llm::{OllamaClient, LogAnalyzer, ServiceRecommendation};
+use crate::llm::{
+    AnalysisStore, ChatSession, ChatSessionMessage, ChatSessionSummary, ChatStore, EndpointHealth, LlmQueue, LogAnalysisProgress,
+    LlmDiagnostics, LlmTask, LogAnalysisResult, LogAnalysisStage, LogClusterReport, LogClusterer, OllamaClient, LogAnalyzer, Priority,
+    ProcessExplanation, PullProgress, QueueStatus, ServiceRecommendation, StoredAnalysis, TaskModels,
+};
 use crate::llm::analyzer::AnalysisType;
+use crate::models::audit::{AuditEntry, EventType};
+use crate::models::config::OllamaConfig;
+use crate::services::hash_reputation::HashReputation;
+use crate::services::process_knowledge::{ProcessKnowledge, ProcessKnowledgeEntry};
+use crate::services::security::AuditLogger;
+use crate::services::port::ServiceNames;
+use crate::services::system_stats::{HostInfo, SystemMonitor};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
 use tokio::sync::RwLock;
 
 static OLLAMA_CLIENT: OnceLock<RwLock<OllamaClient>> = OnceLock::new();
+static OLLAMA_ENABLED: AtomicBool = AtomicBool::new(true);
 
 fn get_client() -> &'static RwLock<OllamaClient> {
-    OLLAMA_CLIENT.get_or_init(|| RwLock::new(OllamaClient::new()))
-}
-
-/// Common process explanations cache for known processes - comprehensive list
-fn get_known_process_explanation(name: &str) -> Option<String> {
-    let name_lower = name.to_lowercase();
-
-    // ===== Adobe =====
-    if name_lower.contains("adobe") {
-        if name_lower.contains("arm") || name_lower.contains("armdchelper") {
-            return Some("Adobe ARM (Application Resource Manager) - Verwaltet automatische Updates für Adobe-Programme. Sicher, aber optional wenn Sie keine Adobe-Produkte nutzen.".to_string());
-        }
-        if name_lower.contains("cef") || name_lower.contains("cefhelper") {
-            return Some("Adobe CEF Helper - Chromium-basierter Prozess für Web-Inhalte in Adobe Creative Cloud. Sicher, optional bei Nichtnutzung.".to_string());
-        }
-        if name_lower.contains("creative") || name_lower.contains("cc") {
-            return Some("Adobe Creative Cloud - Verwaltungs-App für Adobe-Produkte wie Photoshop, Illustrator etc.".to_string());
-        }
-        if name_lower.contains("ipc") {
-            return Some("Adobe IPC Broker - Kommunikationsprozess zwischen Adobe-Anwendungen. Sicher.".to_string());
-        }
-        return Some("Adobe-Prozess - Gehört zur Adobe-Software-Suite. In der Regel sicher.".to_string());
-    }
-
-    // ===== Apple macOS System =====
-    if name_lower.contains("windowserver") {
-        return Some("WindowServer - Essentieller macOS-Prozess für die grafische Oberfläche. NIEMALS beenden!".to_string());
-    }
-    if name_lower.contains("kernel_task") || name_lower == "kernel" {
-        return Some("kernel_task - Kern des macOS-Betriebssystems. Verwaltet Hardware und Ressourcen. Essentiell.".to_string());
-    }
-    if name_lower.contains("spotlight") || name_lower.contains("mds") || name_lower.contains("mdworker") {
-        return Some("Spotlight - macOS Suchindizierung. Durchsucht und indiziert Dateien für schnelle Suche. Sicher.".to_string());
-    }
-    if name_lower.contains("launchd") {
-        return Some("launchd - Zentraler macOS-Prozessmanager. Startet und verwaltet alle Dienste. Essentiell.".to_string());
-    }
-    if name_lower.contains("loginwindow") {
-        return Some("loginwindow - macOS Anmeldeprozess und Benutzersitzungsverwaltung. Essentiell.".to_string());
-    }
-    if name_lower.contains("finder") {
-        return Some("Finder - macOS Dateimanager und Desktop-Verwaltung. Standard-App von Apple.".to_string());
-    }
-    if name_lower.contains("dock") && !name_lower.contains("docker") {
-        return Some("Dock - macOS Anwendungsleiste am unteren Bildschirmrand. System-App von Apple.".to_string());
-    }
-    if name_lower.contains("systemuiserver") {
-        return Some("SystemUIServer - Verwaltet die macOS-Menüleiste und Systemsymbole. Essentiell.".to_string());
-    }
-    if name_lower.contains("coreaudio") {
-        return Some("CoreAudio - macOS Audiosystem. Verwaltet alle Audioein- und -ausgaben. Essentiell.".to_string());
-    }
-    if name_lower.contains("airplay") {
-        return Some("AirPlay - Apple-Dienst für drahtloses Streaming zu Apple TV und kompatiblen Geräten.".to_string());
-    }
-    if name_lower.contains("bluetooth") {
-        return Some("Bluetooth-Dienst - Verwaltet Bluetooth-Verbindungen zu Geräten wie Kopfhörern, Tastaturen etc.".to_string());
-    }
-    if name_lower.contains("wifi") || name_lower.contains("wlan") {
-        return Some("WLAN/WiFi-Dienst - Verwaltet drahtlose Netzwerkverbindungen. Essentiell für Internet.".to_string());
-    }
-    if name_lower.contains("cfprefsd") {
-        return Some("cfprefsd - macOS Einstellungs-Daemon. Verwaltet App-Einstellungen und Preferences. Essentiell.".to_string());
-    }
-    if name_lower.contains("distnoted") {
-        return Some("distnoted - Distributed Notification Server. Verwaltet System-Benachrichtigungen zwischen Apps.".to_string());
-    }
-    if name_lower.contains("notificationcenter") || name_lower.contains("usernoted") {
-        return Some("Notification Center - macOS Benachrichtigungszentrale für App-Mitteilungen.".to_string());
-    }
-    if name_lower.contains("coreservices") {
-        return Some("CoreServices - Zentrale macOS-Systemdienste. Verschiedene Hintergrundprozesse.".to_string());
-    }
-    if name_lower.contains("imagent") || name_lower.contains("imessage") {
-        return Some("iMessage-Dienst - Apple Nachrichtendienst für iMessage und SMS-Weiterleitung.".to_string());
-    }
-    if name_lower.contains("facetime") {
-        return Some("FaceTime - Apple Video- und Audioanrufdienst.".to_string());
-    }
-    if name_lower.contains("icloud") || name_lower.contains("bird") {
-        return Some("iCloud-Dienst - Synchronisiert Dateien, Fotos und Daten mit Apple iCloud.".to_string());
-    }
-    if name_lower.contains("photoanalysis") || name_lower.contains("photolibrary") {
-        return Some("Fotos-Analyse - Analysiert Bilder für Gesichtserkennung und intelligente Alben.".to_string());
-    }
-    if name_lower.contains("backupd") || name_lower.contains("timemachine") {
-        return Some("Time Machine - macOS Backup-System. Erstellt automatische Sicherungen.".to_string());
-    }
-    if name_lower.contains("softwareupdate") {
-        return Some("Software Update - macOS Aktualisierungsdienst für System- und App-Updates.".to_string());
-    }
-    if name_lower.contains("siri") || name_lower.contains("assistant") {
-        return Some("Siri - Apple Sprachassistent. Verarbeitet Sprachbefehle und -anfragen.".to_string());
-    }
-    if name_lower.contains("securityd") || name_lower.contains("trustd") {
-        return Some("Sicherheitsdienst - Verwaltet Zertifikate, Schlüsselbund und Sicherheitsrichtlinien. Essentiell.".to_string());
-    }
-    if name_lower.contains("opendirectory") || name_lower.contains("dscacheutil") {
-        return Some("Directory Service - Verwaltet Benutzer, Gruppen und Netzwerkverzeichnisse.".to_string());
-    }
-
-    // ===== Browsers =====
-    if name_lower.contains("chrome") {
-        if name_lower.contains("helper") {
-            return Some("Chrome Helper - Unterprozess von Google Chrome für Tabs, Erweiterungen und Plugins. Isoliert für Sicherheit.".to_string());
-        }
-        return Some("Google Chrome - Webbrowser von Google. Verwendet mehrere Prozesse für Stabilität.".to_string());
-    }
-    if name_lower.contains("firefox") {
-        return Some("Mozilla Firefox - Open-Source Webbrowser. Verwendet Multiprozess-Architektur.".to_string());
-    }
-    if name_lower.contains("safari") {
-        if name_lower.contains("networking") {
-            return Some("Safari Networking - Netzwerk-Prozess für Safari-Webbrowser.".to_string());
-        }
-        return Some("Safari - Apple Webbrowser. Standard-Browser auf macOS und iOS.".to_string());
-    }
-    if name_lower.contains("edge") {
-        return Some("Microsoft Edge - Chromium-basierter Webbrowser von Microsoft.".to_string());
-    }
-    if name_lower.contains("brave") {
-        return Some("Brave Browser - Datenschutzorientierter Webbrowser mit integriertem Werbeblocker.".to_string());
-    }
-    if name_lower.contains("opera") {
-        return Some("Opera - Webbrowser mit integriertem VPN und Werbeblocker.".to_string());
-    }
-    if name_lower.contains("webkit") {
-        return Some("WebKit - Browser-Engine für Safari und andere Apps. Rendert Webseiten.".to_string());
-    }
-
-    // ===== Development Tools =====
-    if name_lower.contains("docker") {
-        return Some("Docker - Container-Virtualisierung. Führt Anwendungen in isolierten Containern aus.".to_string());
-    }
-    if name_lower.contains("node") && !name_lower.contains("notification") {
-        return Some("Node.js - JavaScript-Laufzeitumgebung für Webentwicklung und Server.".to_string());
-    }
-    if name_lower.contains("code") && (name_lower.contains("helper") || name_lower.contains("visual")) {
-        return Some("Visual Studio Code - Microsoft Code-Editor. Helper-Prozesse für Erweiterungen.".to_string());
-    }
-    if name_lower.contains("xcode") {
-        return Some("Xcode - Apple Entwicklungsumgebung für macOS, iOS und andere Apple-Plattformen.".to_string());
-    }
-    if name_lower.contains("simulator") {
-        return Some("iOS Simulator - Emuliert iPhone/iPad für App-Entwicklung und Tests.".to_string());
-    }
-    if name_lower.contains("git") {
-        return Some("Git - Versionskontrollsystem für Softwareentwicklung.".to_string());
-    }
-    if name_lower.contains("npm") {
-        return Some("npm - Node Package Manager. Verwaltet JavaScript-Pakete und Abhängigkeiten.".to_string());
-    }
-    if name_lower.contains("yarn") {
-        return Some("Yarn - Alternativer JavaScript-Paketmanager, oft schneller als npm.".to_string());
-    }
-    if name_lower.contains("python") {
-        return Some("Python - Programmiersprache. Weit verbreitet für Scripting, KI und Webentwicklung.".to_string());
-    }
-    if name_lower.contains("ruby") {
-        return Some("Ruby - Programmiersprache. Bekannt für Ruby on Rails Webframework.".to_string());
-    }
-    if name_lower.contains("java") && !name_lower.contains("javascript") {
-        return Some("Java - Programmiersprache und Laufzeitumgebung. Weit verbreitet in Unternehmen.".to_string());
-    }
-    if name_lower.contains("rust") {
-        return Some("Rust - Systemprogrammiersprache. Bekannt für Sicherheit und Performance.".to_string());
-    }
-    if name_lower.contains("cargo") {
-        return Some("Cargo - Rust Paketmanager und Build-System.".to_string());
-    }
-    if name_lower.contains("go") && name_lower.len() < 10 {
-        return Some("Go/Golang - Programmiersprache von Google. Bekannt für Einfachheit und Performance.".to_string());
-    }
-    if name_lower.contains("postgres") || name_lower.contains("psql") {
-        return Some("PostgreSQL - Leistungsstarke Open-Source Datenbank.".to_string());
-    }
-    if name_lower.contains("mysql") {
-        return Some("MySQL - Populäre relationale Datenbank.".to_string());
-    }
-    if name_lower.contains("redis") {
-        return Some("Redis - In-Memory Datenbank für Caching und Nachrichtenwarteschlangen.".to_string());
-    }
-    if name_lower.contains("mongo") {
-        return Some("MongoDB - NoSQL-Dokumentendatenbank.".to_string());
-    }
-    if name_lower.contains("ollama") {
-        return Some("Ollama - Lokale KI/LLM-Laufzeitumgebung. Führt Sprachmodelle auf Ihrem Computer aus.".to_string());
-    }
-    if name_lower.contains("tauri") {
-        return Some("Tauri - Framework für Desktop-Anwendungen mit Webtechnologien.".to_string());
-    }
-    if name_lower.contains("electron") {
-        return Some("Electron - Framework für Desktop-Apps (z.B. VS Code, Slack, Discord).".to_string());
-    }
-    if name_lower.contains("jetbrains") || name_lower.contains("intellij") || name_lower.contains("pycharm") || name_lower.contains("webstorm") {
-        return Some("JetBrains IDE - Professionelle Entwicklungsumgebung für verschiedene Programmiersprachen.".to_string());
-    }
-
-    // ===== Communication =====
-    if name_lower.contains("slack") {
-        return Some("Slack - Team-Kommunikationsplattform für Unternehmen.".to_string());
-    }
-    if name_lower.contains("discord") {
-        return Some("Discord - Voice-, Video- und Text-Chat-Plattform.".to_string());
-    }
-    if name_lower.contains("zoom") {
-        return Some("Zoom - Videokonferenz-Software für Meetings und Webinare.".to_string());
-    }
-    if name_lower.contains("teams") {
-        return Some("Microsoft Teams - Kommunikationsplattform für Unternehmen.".to_string());
-    }
-    if name_lower.contains("telegram") {
-        return Some("Telegram - Cloud-basierter Messenger mit Fokus auf Geschwindigkeit und Sicherheit.".to_string());
-    }
-    if name_lower.contains("whatsapp") {
-        return Some("WhatsApp - Messenger von Meta für Text, Sprach- und Videoanrufe.".to_string());
-    }
-    if name_lower.contains("signal") {
-        return Some("Signal - Sicherer Messenger mit Ende-zu-Ende-Verschlüsselung.".to_string());
-    }
-    if name_lower.contains("skype") {
-        return Some("Skype - Video- und Sprachanrufdienst von Microsoft.".to_string());
-    }
-
-    // ===== Productivity =====
-    if name_lower.contains("spotify") {
-        return Some("Spotify - Musik-Streaming-Dienst.".to_string());
-    }
-    if name_lower.contains("dropbox") {
-        return Some("Dropbox - Cloud-Speicher und Dateisynchronisation.".to_string());
-    }
-    if name_lower.contains("onedrive") {
-        return Some("OneDrive - Microsoft Cloud-Speicher, integriert in Windows und Office.".to_string());
-    }
-    if name_lower.contains("notion") {
-        return Some("Notion - All-in-One Workspace für Notizen, Dokumente und Projektmanagement.".to_string());
-    }
-    if name_lower.contains("obsidian") {
-        return Some("Obsidian - Wissensmanagement-App mit Markdown-Notizen und Verknüpfungen.".to_string());
-    }
-    if name_lower.contains("1password") || name_lower.contains("onepassword") {
-        return Some("1Password - Passwort-Manager für sichere Speicherung von Zugangsdaten.".to_string());
-    }
-    if name_lower.contains("bitwarden") {
-        return Some("Bitwarden - Open-Source Passwort-Manager.".to_string());
-    }
-    if name_lower.contains("lastpass") {
-        return Some("LastPass - Cloud-basierter Passwort-Manager.".to_string());
-    }
-
-    // ===== Security & VPN =====
-    if name_lower.contains("vpn") {
-        return Some("VPN-Client - Stellt sichere, verschlüsselte Netzwerkverbindungen her.".to_string());
-    }
-    if name_lower.contains("wireguard") {
-        return Some("WireGuard - Modernes, schnelles VPN-Protokoll.".to_string());
-    }
-    if name_lower.contains("openvpn") {
-        return Some("OpenVPN - Open-Source VPN-Lösung.".to_string());
-    }
-    if name_lower.contains("antivir") || name_lower.contains("avast") || name_lower.contains("norton") || name_lower.contains("kaspersky") || name_lower.contains("malware") {
-        return Some("Antivirus/Sicherheitssoftware - Schützt vor Malware und Bedrohungen.".to_string());
-    }
-    if name_lower.contains("littlesnitch") {
-        return Some("Little Snitch - macOS Firewall zur Kontrolle ausgehender Verbindungen.".to_string());
-    }
+    OLLAMA_CLIENT.get_or_init(|| RwLock::new(OllamaClient::from_config(&OllamaConfig::default())))
+}
 
-    // ===== Media =====
-    if name_lower.contains("vlc") {
-        return Some("VLC - Open-Source Mediaplayer für fast alle Audio- und Videoformate.".to_string());
-    }
-    if name_lower.contains("quicktime") {
-        return Some("QuickTime - Apple Mediaplayer und Framework.".to_string());
-    }
-    if name_lower.contains("handbrake") {
-        return Some("HandBrake - Open-Source Videokonverter.".to_string());
-    }
-    if name_lower.contains("obs") {
-        return Some("OBS Studio - Open-Source Software für Streaming und Aufnahme.".to_string());
-    }
+/// The client the analysis commands use, with the models set through them
+pub(crate) async fn current_client() -> OllamaClient {
+    get_client().read().await.clone()
+}
 
-    // ===== System Utilities =====
-    if name_lower.contains("alfred") {
-        return Some("Alfred - Produktivitäts-App für macOS mit Spotlight-Alternative und Workflows.".to_string());
-    }
-    if name_lower.contains("raycast") {
-        return Some("Raycast - Produktivitäts-Tool und Launcher für macOS.".to_string());
-    }
-    if name_lower.contains("rectangle") || name_lower.contains("magnet") {
-        return Some("Fenster-Manager - Organisiert Fenster auf dem Desktop mit Tastenkombinationen.".to_string());
-    }
-    if name_lower.contains("bartender") {
-        return Some("Bartender - Organisiert und versteckt Menüleistensymbole auf macOS.".to_string());
-    }
-    if name_lower.contains("cleanmymac") || name_lower.contains("ccleaner") {
-        return Some("System-Cleaner - Bereinigt temporäre Dateien und Cache.".to_string());
-    }
+/// Apply the Ollama settings. The client is rebuilt from them, replacing
+/// models set through `set_ollama_model` and friends.
+pub(crate) async fn configure_client(config: &OllamaConfig) {
+    OLLAMA_ENABLED.store(config.enabled, Ordering::Relaxed);
+    *get_client().write().await = OllamaClient::from_config(config);
+}
 
-    // ===== Generic patterns =====
-    if name_lower.contains("helper") {
-        return Some("Helper-Prozess - Unterprozess einer Anwendung für spezielle Aufgaben.".to_string());
-    }
-    if name_lower.contains("agent") {
-        return Some("Agent-Prozess - Hintergrundprozess einer Anwendung oder des Systems.".to_string());
-    }
-    if name_lower.contains("daemon") || name_lower.ends_with("d") && name_lower.len() < 15 {
-        return Some("Daemon - Hintergrunddienst, der ohne Benutzerinteraktion läuft.".to_string());
-    }
-    if name_lower.contains("service") {
-        return Some("Systemdienst - Hintergrundprozess für bestimmte Funktionen.".to_string());
-    }
-    if name_lower.contains("updater") || name_lower.contains("update") {
-        return Some("Update-Dienst - Prüft und installiert Software-Aktualisierungen.".to_string());
-    }
-    if name_lower.starts_with("com.apple.") {
-        return Some("Apple-Systemdienst - Interner macOS-Prozess. In der Regel essentiell.".to_string());
+/// LLM commands fail early while Ollama is disabled in the settings
+pub(crate) fn ensure_enabled() -> Result<(), String> {
+    if OLLAMA_ENABLED.load(Ordering::Relaxed) {
+        Ok(())
+    } else {
+        Err("Die KI-Funktionen sind in den Einstellungen deaktiviert".into())
     }
-
-    None
 }
 
+/// Whether the AI features can be used: Ollama answers or a cloud provider
+/// is enabled
 #[tauri::command]
 pub async fn check_ollama_status() -> Result<bool, String> {
+    if ensure_enabled().is_err() {
+        return Ok(false);
+    }
     let client = get_client().read().await;
-    Ok(client.is_available().await)
+    Ok(client.can_generate().await)
 }
 
 #[tauri::command]
 pub async fn list_ollama_models() -> Result<Vec<String>, String> {
+    ensure_enabled()?;
     let client = get_client().read().await;
     let models = client.list_models().await.map_err(|e| e.to_string())?;
     Ok(models.into_iter().map(|m| m.name).collect())
 }
 
+/// Characters of the sanitized input used for the embedding
+const EMBEDDING_INPUT_CHARS: usize = 4000;
+
+/// Analyze logs with the model. With `cluster`, similar lines are grouped
+/// by their embeddings first and the model only sees the groups.
 #[tauri::command]
-pub async fn analyze_logs(logs: String, analysis_type: String) -> Result<String, String> {
-    let client = get_client().read().await;
-    let analyzer = LogAnalyzer::new(client.clone());
+pub async fn analyze_logs(
+    app: AppHandle,
+    logs: String,
+    analysis_type: String,
+    cluster: Option<bool>,
+) -> Result<LogAnalysisResult, String> {
+    ensure_enabled()?;
+    let client = get_client().read().await.clone();
+    let analyzer = LogAnalyzer::new(client.clone().with_priority(Priority::Low));
 
     let analysis = match analysis_type.as_str() {
         "errors" => AnalysisType::ErrorDetection,
@@ -354,7 +89,166 @@ pub async fn analyze_logs(logs: String, analysis_type: String) -> Result<String,
         _ => return Err(format!("Unknown analysis type: {}", analysis_type)),
     };
 
-    analyzer.analyze(&logs, analysis).await.map_err(|e| e.to_string())
+    let input = if cluster.unwrap_or(false) {
+        let _ = app.emit(
+            "log-analysis-progress",
+            LogAnalysisProgress { stage: LogAnalysisStage::Clustering, completed: 0, total: 1 },
+        );
+        cluster_sanitized(&client, &analyzer.sanitize_logs(&logs)).await?.digest()
+    } else {
+        logs.clone()
+    };
+
+    // Long logs take several model calls, progress is reported per chunk
+    let report = analyzer
+        .analyze_with_progress(&input, analysis, |progress| {
+            let _ = app.emit("log-analysis-progress", &progress);
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Similarity search is best effort: without an embedding model the
+    // report is returned as is and not stored
+    let sanitized = analyzer.sanitize_logs(&logs);
+    let Ok(embedding) = client.embed(&embedding_input(&sanitized)).await else {
+        return Ok(LogAnalysisResult { report, analysis_id: None, similar: Vec::new() });
+    };
+
+    let store = AnalysisStore::new();
+    let similar = store.find_similar(&embedding);
+    let analysis_id = store
+        .save(&analysis_type, &summary_line(&sanitized), &sanitized, &report, embedding)
+        .ok();
+
+    Ok(LogAnalysisResult { report, analysis_id, similar })
+}
+
+/// Group similar log lines and flag unusual ones using embeddings only,
+/// without the generation model
+#[tauri::command]
+pub async fn cluster_logs(logs: String) -> Result<LogClusterReport, String> {
+    ensure_enabled()?;
+    let client = current_client().await;
+    let sanitized = LogAnalyzer::new(client.clone()).sanitize_logs(&logs);
+    cluster_sanitized(&client, &sanitized).await
+}
+
+async fn cluster_sanitized(client: &OllamaClient, logs: &str) -> Result<LogClusterReport, String> {
+    LogClusterer::new(client.clone()).cluster(logs).await.map_err(|e| {
+        format!(
+            "Das Gruppieren braucht das Embedding-Modell {} ({}). Installiere es mit `ollama pull {}`.",
+            client.embedding_model(),
+            e,
+            client.embedding_model()
+        )
+    })
+}
+
+/// Record an incident so later analyses of similar logs link to it
+#[tauri::command]
+pub async fn record_incident(title: String, details: String) -> Result<String, String> {
+    if title.trim().is_empty() {
+        return Err("Der Vorfall benötigt einen Titel".into());
+    }
+    ensure_enabled()?;
+
+    let client = get_client().read().await.clone();
+    let sanitized = LogAnalyzer::new(client.clone()).sanitize_logs(&details);
+    let embedding = client
+        .embed(&embedding_input(&format!("{}\n{}", title.trim(), sanitized)))
+        .await
+        .map_err(|e| format!("Embedding konnte nicht erstellt werden: {}", e))?;
+
+    AnalysisStore::new()
+        .save("incident", title.trim(), &sanitized, &sanitized, embedding)
+        .map_err(|e| e.to_string())
+}
+
+/// Stored report behind a similarity match
+#[tauri::command]
+pub async fn get_stored_analysis(id: String) -> Result<StoredAnalysis, String> {
+    AnalysisStore::new()
+        .get(&id)
+        .ok_or_else(|| format!("Analyse {} nicht gefunden", id))
+}
+
+/// Start a conversation; without a title the first question names it
+#[tauri::command]
+pub async fn create_chat_session(title: Option<String>) -> Result<ChatSession, String> {
+    ChatStore::new().create(title.as_deref()).map_err(|e| e.to_string())
+}
+
+/// Ask a question within a conversation and return the answer
+#[tauri::command]
+pub async fn send_chat_message(session_id: String, message: String) -> Result<ChatSessionMessage, String> {
+    if message.trim().is_empty() {
+        return Err("Die Nachricht ist leer".into());
+    }
+
+    ensure_enabled()?;
+    let store = ChatStore::new();
+    let mut session = store
+        .get(&session_id)
+        .ok_or_else(|| format!("Unterhaltung {} nicht gefunden", session_id))?;
+
+    let client = current_client().await.with_priority(Priority::High);
+    if !client.can_generate().await {
+        return Err("Ollama ist nicht verfügbar. Starte Ollama, um mit dem Assistenten zu sprechen.".into());
+    }
+
+    let reply = session.ask(&client, message.trim()).await.map_err(|e| e.to_string())?;
+    store.save(&session).map_err(|e| e.to_string())?;
+    Ok(reply)
+}
+
+#[tauri::command]
+pub async fn list_chat_sessions() -> Result<Vec<ChatSessionSummary>, String> {
+    Ok(ChatStore::new().list())
+}
+
+#[tauri::command]
+pub async fn get_chat_session(session_id: String) -> Result<ChatSession, String> {
+    ChatStore::new()
+        .get(&session_id)
+        .ok_or_else(|| format!("Unterhaltung {} nicht gefunden", session_id))
+}
+
+#[tauri::command]
+pub async fn delete_chat_session(session_id: String) -> Result<(), String> {
+    if ChatStore::new().delete(&session_id) {
+        Ok(())
+    } else {
+        Err(format!("Unterhaltung {} nicht gefunden", session_id))
+    }
+}
+
+#[tauri::command]
+pub async fn set_ollama_embedding_model(model: String) -> Result<(), String> {
+    if model.trim().is_empty() {
+        return Err("Es muss ein Modell angegeben werden".into());
+    }
+    let mut client = get_client().write().await;
+    client.set_embedding_model(model.trim());
+    Ok(())
+}
+
+/// The most recent part of the logs, like the analysis prompt
+fn embedding_input(text: &str) -> String {
+    let skip = text.chars().count().saturating_sub(EMBEDDING_INPUT_CHARS);
+    text.chars().skip(skip).collect()
+}
+
+/// First line that looks like an error, otherwise the first non-empty line
+fn summary_line(logs: &str) -> String {
+    let mut lines = logs.lines().map(str::trim).filter(|l| !l.is_empty());
+    let first = lines.clone().next().unwrap_or_default();
+    let line = lines
+        .find(|l| {
+            let lower = l.to_lowercase();
+            lower.contains("error") || lower.contains("fail") || lower.contains("exception")
+        })
+        .unwrap_or(first);
+    line.chars().take(120).collect()
 }
 
 #[tauri::command]
@@ -364,24 +258,128 @@ pub async fn set_ollama_model(model: String) -> Result<(), String> {
     Ok(())
 }
 
+#[tauri::command]
+pub async fn set_ollama_fast_model(model: String) -> Result<(), String> {
+    if model.trim().is_empty() {
+        return Err("Es muss ein Modell angegeben werden".into());
+    }
+    let mut client = get_client().write().await;
+    client.set_fast_model(model.trim());
+    Ok(())
+}
+
+/// The models each task is sent to, in order, with its timeout
+#[tauri::command]
+pub async fn get_task_models() -> Result<Vec<TaskModels>, String> {
+    ensure_enabled()?;
+    let client = current_client().await;
+    let mut tasks = Vec::with_capacity(LlmTask::ALL.len());
+    for task in LlmTask::ALL {
+        tasks.push(TaskModels {
+            task,
+            models: client.model_chain(task).await,
+            timeout_seconds: client.timeout(task).map(|t| t.as_secs()),
+        });
+    }
+    Ok(tasks)
+}
+
+/// Ollama version, installed and loaded models against the free memory,
+/// queue state and, unless `probe` is false, the timing of a tiny
+/// generation, with hints why AI analysis is slow or unavailable
+#[tauri::command]
+pub async fn get_llm_diagnostics(probe: Option<bool>) -> Result<LlmDiagnostics, String> {
+    ensure_enabled()?;
+    let client = current_client().await.with_priority(Priority::High);
+    let stats = SystemMonitor::shared().lock().await.get_stats();
+    Ok(LlmDiagnostics::collect(&client, &stats.memory, &stats.gpus, probe.unwrap_or(true)).await)
+}
+
+/// Download `model`, or the analysis model, emitting `model-pull-progress`
+/// events until it is complete
+#[tauri::command]
+pub async fn pull_ollama_model(app: AppHandle, model: Option<String>) -> Result<(), String> {
+    ensure_enabled()?;
+    let client = current_client().await;
+    let model = model
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| client.model().to_string());
+
+    // Ollama reports every few kilobytes; the UI only needs a few updates per second
+    let mut last_status = String::new();
+    let mut last_emit = Instant::now();
+    let result = client
+        .pull_model(&model, |progress: PullProgress| {
+            if progress.status != last_status || last_emit.elapsed() >= Duration::from_millis(250) {
+                last_status = progress.status.clone();
+                last_emit = Instant::now();
+                let _ = app.emit("model-pull-progress", &progress);
+            }
+        })
+        .await;
+
+    let mut entry = AuditEntry::new(EventType::ConfigChange, format!("pull_ollama_model: {}", model));
+    entry.success = result.is_ok();
+    entry.error_message = result.as_ref().err().map(|e| e.to_string());
+    let _ = AuditLogger::new().log(&entry);
+
+    result.map_err(|e| e.to_string())
+}
+
+/// Running and waiting generations
+#[tauri::command]
+pub async fn get_llm_queue_status() -> Result<QueueStatus, String> {
+    Ok(LlmQueue::global().status())
+}
+
+/// Configure the primary endpoint followed by fallback endpoints
+#[tauri::command]
+pub async fn set_ollama_endpoints(endpoints: Vec<String>) -> Result<(), String> {
+    if endpoints.iter().all(|e| e.trim().is_empty()) {
+        return Err("Es muss mindestens ein Ollama-Endpunkt angegeben werden".into());
+    }
+    let mut client = get_client().write().await;
+    client.set_endpoints(&endpoints);
+    Ok(())
+}
+
+/// Health of every configured endpoint; the first healthy one becomes active
+#[tauri::command]
+pub async fn check_ollama_endpoints() -> Result<Vec<EndpointHealth>, String> {
+    let client = get_client().read().await;
+    Ok(client.check_endpoints().await)
+}
+
 #[tauri::command]
 pub async fn explain_process(
     process_name: String,
     process_path: Option<String>,
     description: Option<String>,
-) -> Result<String, String> {
-    // First check if we have a cached explanation for known processes
-    if let Some(explanation) = get_known_process_explanation(&process_name) {
-        return Ok(explanation);
+) -> Result<ProcessExplanation, String> {
+    let reputation = match &process_path {
+        Some(path) => HashReputation::check_file(path).await,
+        None => None,
+    };
+
+    // Known processes are answered from the knowledge base
+    if let Some(known) = ProcessKnowledge::lookup(&process_name) {
+        return Ok(ProcessExplanation {
+            explanation: known.describe(),
+            safety: Some(known.safety),
+            category: Some(known.category),
+            reputation,
+        });
     }
 
     // Fall back to LLM for unknown processes
+    ensure_enabled()?;
     let client = get_client().read().await;
-    if !client.is_available().await {
+    if !client.can_generate().await {
         return Err("Ollama ist nicht verfügbar. Bitte starten Sie Ollama, um Prozess-Erklärungen zu erhalten.".to_string());
     }
 
-    let analyzer = LogAnalyzer::new(client.clone());
+    let analyzer = LogAnalyzer::new(client.clone().with_priority(Priority::High));
     analyzer
         .explain_process(
             &process_name,
@@ -389,45 +387,36 @@ pub async fn explain_process(
             description.as_deref(),
         )
         .await
+        .map(|explanation| ProcessExplanation { explanation, safety: None, category: None, reputation })
         .map_err(|e| e.to_string())
 }
 
+/// Add or replace a custom knowledge base entry; it is matched before the
+/// bundled entries
+#[tauri::command]
+pub async fn add_process_knowledge_entry(entry: ProcessKnowledgeEntry) -> Result<ProcessKnowledgeEntry, String> {
+    ProcessKnowledge::add(entry).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_process_knowledge_entries() -> Result<Vec<ProcessKnowledgeEntry>, String> {
+    Ok(ProcessKnowledge::custom_entries())
+}
+
 #[tauri::command]
 pub async fn get_service_recommendations(services_json: String) -> Result<Vec<ServiceRecommendation>, String> {
+    ensure_enabled()?;
     let client = get_client().read().await;
 
-    if !client.is_available().await {
+    if !client.can_generate().await {
         // Return mock recommendations when Ollama is not available
         return Ok(get_default_recommendations());
     }
 
-    let analyzer = LogAnalyzer::new(client.clone());
-    let response = analyzer
-        .generate_recommendations(&services_json)
+    LogAnalyzer::new(client.clone())
+        .generate_recommendations(&ServiceNames::annotate(&services_json), &HostInfo::current().prompt_context())
         .await
-        .map_err(|e| e.to_string())?;
-
-    // Try to parse the JSON response
-    // First, try to extract JSON from the response (LLM might add extra text)
-    let json_str = extract_json_array(&response).unwrap_or(&response);
-
-    match serde_json::from_str::<Vec<ServiceRecommendation>>(json_str) {
-        Ok(recommendations) => Ok(recommendations),
-        Err(_) => {
-            // If parsing fails, return default recommendations
-            Ok(get_default_recommendations())
-        }
-    }
-}
-
-fn extract_json_array(text: &str) -> Option<&str> {
-    let start = text.find('[')?;
-    let end = text.rfind(']')?;
-    if start < end {
-        Some(&text[start..=end])
-    } else {
-        None
-    }
+        .map_err(|e| e.to_string())
 }
 
 fn get_default_recommendations() -> Vec<ServiceRecommendation> {
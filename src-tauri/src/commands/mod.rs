@@ -6,6 +6,17 @@ pub mod config_commands;
 pub mod audit_commands;
 pub mod llm_commands;
 pub mod system_commands;
+pub mod docker_commands;
+pub mod digest_commands;
+pub mod cleanup_commands;
+pub mod action_commands;
+pub mod remote_commands;
+pub mod network_commands;
+pub mod tunnel_commands;
+pub mod export_commands;
+pub mod bootstrap_commands;
+pub mod history_commands;
+pub mod prompt_commands;
 
 // Re-export commands for easier registration
 pub use service_commands::*;
@@ -14,3 +25,14 @@ pub use config_commands::*;
 pub use audit_commands::*;
 pub use llm_commands::*;
 pub use system_commands::*;
+pub use docker_commands::*;
+pub use digest_commands::*;
+pub use cleanup_commands::*;
+pub use action_commands::*;
+pub use remote_commands::*;
+pub use network_commands::*;
+pub use tunnel_commands::*;
+pub use export_commands::*;
+pub use bootstrap_commands::*;
+pub use history_commands::*;
+pub use prompt_commands::*;
@@ -0,0 +1,35 @@
+use std::time::Duration;
+use crate::services::mdns::{MdnsBrowser, NetworkDiscovery};
+use crate::services::ssdp::{SsdpDiscovery, SsdpScanner};
+
+/// Browse mDNS/DNS-SD services (web servers, SSH, printers, media devices)
+/// on the local network. `service_types` replaces the default list, e.g.
+/// `["_ssh._tcp"]`; types announced in the meta query are always browsed.
+#[tauri::command]
+pub async fn discover_network_services(
+    timeout_ms: Option<u64>,
+    service_types: Option<Vec<String>>,
+) -> Result<NetworkDiscovery, String> {
+    let mut browser = MdnsBrowser::new();
+    if let Some(timeout_ms) = timeout_ms {
+        browser = browser.with_timeout(Duration::from_millis(timeout_ms.clamp(250, 10_000)));
+    }
+    if let Some(service_types) = service_types {
+        browser = browser.with_service_types(service_types);
+    }
+
+    tokio::task::spawn_blocking(move || browser.browse().map_err(|e| e.to_string()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Find UPnP devices via SSDP and list the port mappings of Internet
+/// gateways that support IGD, with a finding per active forwarding
+#[tauri::command]
+pub async fn discover_upnp_devices(timeout_ms: Option<u64>) -> Result<SsdpDiscovery, String> {
+    let mut scanner = SsdpScanner::new();
+    if let Some(timeout_ms) = timeout_ms {
+        scanner = scanner.with_timeout(Duration::from_millis(timeout_ms.clamp(250, 10_000)));
+    }
+    scanner.scan().await.map_err(|e| e.to_string())
+}
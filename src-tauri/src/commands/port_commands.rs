@@ -1,17 +1,121 @@
-use crate::models::port::PortInfo;
-use crate::services::port::{PortScanner, PortResolver};
+use crate::models::audit::{AuditEntry, EventType};
+use crate::models::config::PortReservation;
+use crate::models::port::{Connection, IpcEndpoint, PortInfo};
+use crate::parsers::{sockets::SocketRecord, ParseReport};
+use crate::services::port::{NmapScanner, PortBaseline, PortScanner, PortResolver, PortReservations};
+use crate::services::port::baseline::ListenerBaseline;
+use crate::services::port::nmap::NmapStatus;
+use crate::services::port::history::{PortHistory, PortHistoryDiff, PortOccupancy, PortSnapshot};
+use crate::services::port::scanner::{parse_targets, ScanProgress, MAX_SCAN_PROBES};
+use crate::services::port::tls::{TlsInspection, TlsInspector};
+use crate::services::resolvers::{ResolverInspector, ResolverStatus};
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::security::AuditLogger;
+use crate::services::monitor::current_ports;
+use crate::services::user_scope::UserScope;
+use crate::commands::config_commands::set_port_reservations;
+use chrono::{DateTime, Utc};
+use tauri::{AppHandle, Emitter};
 
+/// Scan localhost, or `target` (IP, hostname or CIDR range such as
+/// `192.168.1.0/24`). `ports` replaces the `start..=end` range. Emits
+/// `port-scan-progress` events while scanning. Goes through nmap when it is
+/// enabled and installed, and falls back to the built-in scanner if it fails.
 #[tauri::command]
-pub async fn scan_ports(start: u16, end: u16) -> Result<Vec<PortInfo>, String> {
-    let scanner = PortScanner::new();
-    let ports = scanner.scan_range("127.0.0.1", start, end).await;
-    Ok(ports)
+pub async fn scan_ports(
+    app: AppHandle,
+    start: u16,
+    end: u16,
+    fingerprint: Option<bool>,
+    target: Option<String>,
+    ports: Option<Vec<u16>>,
+) -> Result<Vec<PortInfo>, String> {
+    let scanner = PortScanner::new().with_fingerprinting(fingerprint.unwrap_or(false));
+    let port_list = ports.unwrap_or_else(|| (start..=end).collect());
+    if port_list.is_empty() {
+        return Err("Es muss mindestens ein Port angegeben werden".into());
+    }
+
+    let target = target.filter(|t| !t.trim().is_empty());
+    let hosts = match &target {
+        Some(target) => {
+            let target = target.clone();
+            tokio::task::spawn_blocking(move || parse_targets(&target))
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string())?
+        }
+        // Dual-stack services may only listen on one of the loopback addresses
+        None => vec!["127.0.0.1".parse().unwrap(), "::1".parse().unwrap()],
+    };
+    let probes = hosts.len().saturating_mul(port_list.len());
+    if probes > MAX_SCAN_PROBES {
+        return Err(format!(
+            "Der Scan umfasst {} Host/Port-Kombinationen, maximal {} sind erlaubt. Bitte weniger Hosts oder Ports wählen.",
+            probes, MAX_SCAN_PROBES
+        ));
+    }
+
+    let nmap = match NmapScanner::from_config() {
+        Some(nmap) => {
+            let (hosts, port_list) = (hosts.clone(), port_list.clone());
+            tokio::task::spawn_blocking(move || nmap.scan(&hosts, &port_list))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result.map_err(|e| e.to_string()))
+                .map(Some)
+        }
+        None => Ok(None),
+    };
+
+    if let Some(target) = &target {
+        let mut entry = AuditEntry::new(EventType::PortScan, format!("scan_ports: {}", target));
+        entry.details = serde_json::json!({
+            "hosts": hosts.len(),
+            "ports": port_list.len(),
+            "backend": if matches!(nmap, Ok(Some(_))) { "nmap" } else { "builtin" },
+            "nmap_error": nmap.as_ref().err(),
+        });
+        let _ = AuditLogger::new().log(&entry);
+    }
+
+    let mut results = match nmap {
+        Ok(Some(results)) => {
+            let total = hosts.len() * port_list.len();
+            let _ = app.emit("port-scan-progress", ScanProgress { scanned: total, total, open: results.len() });
+            results
+        }
+        _ => {
+            scanner
+                .scan_targets(&hosts, &port_list, move |progress| {
+                    let _ = app.emit("port-scan-progress", progress);
+                })
+                .await
+        }
+    };
+    if target.is_none() {
+        results.sort_by_key(|p| p.port);
+    }
+    Ok(results)
+}
+
+/// Whether nmap is installed and used for scans
+#[tauri::command]
+pub async fn get_nmap_status() -> Result<NmapStatus, String> {
+    tokio::task::spawn_blocking(NmapScanner::status)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn get_port_usage() -> Result<Vec<PortInfo>, String> {
-    let resolver = PortResolver::new();
-    Ok(resolver.get_port_usage())
+    let mut ports = if MockDiscovery::is_enabled() {
+        MockDiscovery::new().port_usage()
+    } else {
+        PortResolver::new().get_port_usage()
+    };
+    UserScope::retain_ports(&mut ports);
+    Ok(ports)
 }
 
 #[tauri::command]
@@ -19,3 +123,166 @@ pub async fn find_free_ports(count: u16) -> Result<Vec<u16>, String> {
     let resolver = PortResolver::new();
     Ok(resolver.find_free_ports(1024, 65535, count as usize))
 }
+
+/// Reserve `port` for `label`. With `owner` set, only that process may
+/// listen on it without a warning.
+#[tauri::command]
+pub async fn reserve_port(port: u16, label: String, owner: Option<String>) -> Result<PortReservation, String> {
+    let reservation = PortReservations::reserve(port, &label, owner).map_err(|e| e.to_string())?;
+    set_port_reservations(PortReservations::list()).await;
+    Ok(reservation)
+}
+
+#[tauri::command]
+pub async fn release_port(port: u16) -> Result<(), String> {
+    if !PortReservations::release(port) {
+        return Err(format!("Port {} ist nicht reserviert", port));
+    }
+    set_port_reservations(PortReservations::list()).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_port_reservations() -> Result<Vec<PortReservation>, String> {
+    Ok(PortReservations::list())
+}
+
+/// Snapshot the current listeners as the expected state. Later security
+/// scans and port watch ticks report every listener that deviates from it.
+#[tauri::command]
+pub async fn record_port_baseline() -> Result<ListenerBaseline, String> {
+    tokio::task::spawn_blocking(|| PortBaseline::record(&current_ports()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_port_baseline() -> Result<Option<ListenerBaseline>, String> {
+    Ok(PortBaseline::get())
+}
+
+#[tauri::command]
+pub async fn clear_port_baseline() -> Result<(), String> {
+    if !PortBaseline::clear() {
+        return Err("Es ist keine Port-Baseline aufgenommen".to_string());
+    }
+    Ok(())
+}
+
+/// Recorded port table at `time` (the last snapshot before it)
+#[tauri::command]
+pub async fn get_ports_at(time: DateTime<Utc>) -> Result<PortSnapshot, String> {
+    let mut snapshot = tokio::task::spawn_blocking(move || PortHistory::at(time))
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Für {} liegt keine Port-Aufzeichnung vor", time.format("%d.%m.%Y %H:%M UTC")))?;
+    UserScope::retain_ports(&mut snapshot.ports);
+    Ok(snapshot)
+}
+
+/// Who listened on `port` between `from` and `to` (default: the last 24 hours)
+#[tauri::command]
+pub async fn get_port_history(
+    port: u16,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<Vec<PortOccupancy>, String> {
+    let to = to.unwrap_or_else(Utc::now);
+    let from = from.unwrap_or(to - chrono::Duration::hours(24));
+    if from > to {
+        return Err("Der Beginn muss vor dem Ende liegen".into());
+    }
+    let mut history = tokio::task::spawn_blocking(move || PortHistory::port_timeline(port, from, to))
+        .await
+        .map_err(|e| e.to_string())?;
+    history.retain(|o| UserScope::is_visible(o.port.user.as_deref()));
+    Ok(history)
+}
+
+/// Listeners opened, closed or taken over between two recorded times;
+/// `to` defaults to the latest snapshot
+#[tauri::command]
+pub async fn diff_port_usage(from: DateTime<Utc>, to: Option<DateTime<Utc>>) -> Result<PortHistoryDiff, String> {
+    let to = to.unwrap_or_else(Utc::now);
+    let mut diff = tokio::task::spawn_blocking(move || PortHistory::diff(from, to))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    UserScope::retain_ports(&mut diff.opened);
+    UserScope::retain_ports(&mut diff.closed);
+    diff.owner_changed.retain(|c| {
+        UserScope::is_visible(c.before.user.as_deref()) || UserScope::is_visible(c.after.user.as_deref())
+    });
+    Ok(diff)
+}
+
+/// Certificate chain and protocol/cipher support of a TLS endpoint
+#[tauri::command]
+pub async fn inspect_tls(host: String, port: u16) -> Result<TlsInspection, String> {
+    tokio::task::spawn_blocking(move || TlsInspector::new().inspect(&host, port).map_err(|e| e.to_string()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Inspect all local listening ports that answer a TLS handshake
+#[tauri::command]
+pub async fn scan_tls_ports() -> Result<Vec<TlsInspection>, String> {
+    let ports = PortResolver::new().get_port_usage();
+    tokio::task::spawn_blocking(move || TlsInspector::new().scan_ports(&ports))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Local DNS/mDNS resolvers, the ports they hold and conflicts between them
+#[tauri::command]
+pub async fn get_resolver_status() -> Result<ResolverStatus, String> {
+    if MockDiscovery::is_enabled() {
+        let ports = MockDiscovery::new().port_usage();
+        return Ok(ResolverInspector::new().with_unit_states(false).inspect(&ports));
+    }
+
+    let ports = PortResolver::new().get_port_usage();
+    tokio::task::spawn_blocking(move || ResolverInspector::new().inspect(&ports))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Unix domain sockets and Windows named pipes with their owning processes,
+/// for services that don't listen on a port (docker, mysql.sock, gpg-agent)
+#[tauri::command]
+pub async fn get_ipc_endpoints() -> Result<ParseReport<IpcEndpoint>, String> {
+    let mut report = if MockDiscovery::is_enabled() {
+        ParseReport {
+            items: MockDiscovery::new().ipc_endpoints(),
+            issues: Vec::new(),
+        }
+    } else {
+        tokio::task::spawn_blocking(|| PortResolver::new().ipc_endpoints())
+            .await
+            .map_err(|e| e.to_string())?
+    };
+    report.items.retain(|e| UserScope::is_visible(e.user.as_deref()));
+    Ok(report)
+}
+
+/// What processes are talking to: TCP connections in any state but LISTEN
+/// and connected UDP sockets, optionally only those of `pid`
+#[tauri::command]
+pub async fn get_connections(pid: Option<u32>) -> Result<Vec<Connection>, String> {
+    let mut connections = if MockDiscovery::is_enabled() {
+        MockDiscovery::new().connections()
+    } else {
+        tokio::task::spawn_blocking(|| PortResolver::new().active_connections())
+            .await
+            .map_err(|e| e.to_string())?
+    };
+    connections.retain(|c| pid.is_none_or(|pid| c.pid == Some(pid)) && UserScope::is_visible(c.user.as_deref()));
+    Ok(connections)
+}
+
+/// Raw socket table with any lines the parser had to skip
+#[tauri::command]
+pub async fn get_socket_report() -> Result<ParseReport<SocketRecord>, String> {
+    let resolver = PortResolver::new();
+    Ok(resolver.socket_report())
+}
@@ -0,0 +1,24 @@
+use crate::llm::{PromptKind, PromptTemplate, PromptTemplates};
+
+/// All prompt templates with their current content and placeholders
+#[tauri::command]
+pub async fn list_prompt_templates() -> Result<Vec<PromptTemplate>, String> {
+    Ok(PromptTemplates::new().list())
+}
+
+#[tauri::command]
+pub async fn get_prompt_template(kind: PromptKind) -> Result<PromptTemplate, String> {
+    Ok(PromptTemplates::new().get(kind))
+}
+
+/// Store `content` as template file; it is used from the next prompt on
+#[tauri::command]
+pub async fn update_prompt_template(kind: PromptKind, content: String) -> Result<PromptTemplate, String> {
+    PromptTemplates::new().save(kind, &content).map_err(|e| e.to_string())
+}
+
+/// Go back to the built-in prompt
+#[tauri::command]
+pub async fn reset_prompt_template(kind: PromptKind) -> Result<PromptTemplate, String> {
+    PromptTemplates::new().reset(kind).map_err(|e| e.to_string())
+}
@@ -0,0 +1,30 @@
+use crate::services::remote::{PairingInfo, RemoteViewer, RemoteViewerStatus};
+
+/// Start the read-only viewer if needed and create a pairing QR code.
+/// Every call issues an additional one-time code; existing devices stay paired.
+#[tauri::command]
+pub async fn start_remote_pairing() -> Result<PairingInfo, String> {
+    RemoteViewer::start_pairing().await.map_err(|e| e.to_string())
+}
+
+/// Stop the viewer and unpair all devices
+#[tauri::command]
+pub async fn stop_remote_viewer() -> Result<(), String> {
+    RemoteViewer::stop();
+    Ok(())
+}
+
+/// Unpair a single device
+#[tauri::command]
+pub async fn revoke_remote_device(device_id: String) -> Result<(), String> {
+    if RemoteViewer::revoke(&device_id) {
+        Ok(())
+    } else {
+        Err(format!("Gerät {} ist nicht gekoppelt", device_id))
+    }
+}
+
+#[tauri::command]
+pub async fn get_remote_viewer_status() -> Result<RemoteViewerStatus, String> {
+    Ok(RemoteViewer::status())
+}
@@ -1,6 +1,19 @@
-use crate::models::service::Service;
-use crate::services::ServiceManager;
-use crate::services::control::{docker_control::DockerControl, process_control::ProcessControl};
+use crate::models::port::{PortCollision, PortInfo, PortMapping, Protocol};
+use crate::models::audit::{AuditEntry, EventType};
+use crate::models::service::{CustomServiceSpec, DiscoveryResult, Service, ServiceDependencies, ServiceStatus, ServiceType};
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::monitor::current_ports;
+use crate::services::port::{port_owner, PortResolver};
+use crate::services::{FlapDetector, ServiceManager, ServiceMonitor};
+use crate::services::dependency_map::{DependencyMapper, ServiceRuntimeDependencies};
+use crate::services::control::{docker_control::DockerControl, process_control::ProcessControl, custom_service::CustomServiceInstaller};
+use crate::services::control::unit_file::{UnitFileEditor, UnitOverrideDiff};
+use crate::services::security::confirmation::{ActionOutcome, ConfirmationGuard, ImpactPreview};
+use crate::services::security::AuditLogger;
+use crate::services::user_scope::{UserScope, UserScopeStatus};
+use crate::services::session::{
+    SessionChange, SessionChangeKind, SessionJournal, SessionRestoreReport, SessionRestoreResult,
+};
 
 #[cfg(target_os = "macos")]
 use crate::services::control::launchd_control::LaunchdControl;
@@ -23,86 +36,163 @@ fn get_manager() -> &'static Mutex<ServiceManager> {
 }
 
 #[tauri::command]
-pub async fn discover_services() -> Result<Vec<Service>, String> {
+pub async fn discover_services() -> Result<DiscoveryResult, String> {
     let manager = get_manager().lock().await;
-    Ok(manager.discover_all().await)
+    let mut result = manager.discover().await;
+    UserScope::retain_services(&mut result.services);
+    FlapDetector::annotate(&mut result.services);
+    Ok(result)
+}
+
+/// Current user and whether other users' services and ports are listed
+#[tauri::command]
+pub async fn get_user_scope() -> Result<UserScopeStatus, String> {
+    Ok(UserScope::status())
+}
+
+/// Switch to listing all users' items. The mode is audited as a privilege
+/// escalation and expires after `privilege_cache_ttl_minutes`.
+#[tauri::command]
+pub async fn set_all_users_mode(enabled: bool) -> Result<UserScopeStatus, String> {
+    Ok(if enabled {
+        UserScope::enable_all_users()
+    } else {
+        UserScope::disable_all_users()
+    })
 }
 
 #[tauri::command]
 pub async fn get_service_details(service_id: String) -> Result<Option<Service>, String> {
     let manager = get_manager().lock().await;
-    Ok(manager.get_service(&service_id).await)
+    let mut service = manager.get_service(&service_id).await;
+    if let Some(service) = service.as_mut() {
+        FlapDetector::annotate(std::slice::from_mut(service));
+    }
+    Ok(service)
 }
 
+/// Start a service. Containers are checked for host ports that are already
+/// taken first, since Docker only reports a bare bind error.
 #[tauri::command]
 pub async fn start_service(service_id: String) -> Result<(), String> {
-    let manager = get_manager().lock().await;
+    let service = get_manager().lock().await.get_service(&service_id).await;
 
-    if let Some(service) = manager.get_service(&service_id).await {
-        let result = match service.service_type {
-            crate::models::service::ServiceType::Docker => {
-                DockerControl::new().start(&service_id).await
-            }
-            #[cfg(target_os = "macos")]
-            crate::models::service::ServiceType::Launchd => {
-                LaunchdControl::new().start(&service_id).await
-            }
-            #[cfg(target_os = "linux")]
-            crate::models::service::ServiceType::Systemd => {
-                SystemdControl::new().start(&service_id).await
-            }
-            #[cfg(target_os = "windows")]
-            crate::models::service::ServiceType::WindowsService => {
-                WindowsControl::new().start(&service_id).await
+    if let Some(service) = service {
+        // The simulated fleet has made-up ids and PIDs; never act on the host
+        if MockDiscovery::is_enabled() {
+            return Ok(());
+        }
+        if service.service_type == ServiceType::Docker {
+            let mappings = DockerControl::new().port_bindings(&service_id).await.unwrap_or_default();
+            let collisions = port_collisions(&mappings).await?;
+            if !collisions.is_empty() {
+                return Err(collision_error(&collisions));
             }
-            _ => Err("Cannot start this type of service".into()),
-        };
-
-        result.map_err(|e| e.to_string())
+        }
+        start_service_of_type(&service_id, &service.service_type)
+            .await
+            .map_err(|e| e.to_string())?;
+        SessionJournal::forget(&service_id, SessionChangeKind::Stopped);
+        ServiceMonitor::request_refresh(Some(&service_id));
+        Ok(())
     } else {
         Err(format!("Service {} not found", service_id))
     }
 }
 
+async fn start_service_of_type(
+    service_id: &str,
+    service_type: &ServiceType,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    match service_type {
+        ServiceType::Docker => {
+            DockerControl::new().start(service_id).await
+        }
+        #[cfg(target_os = "macos")]
+        ServiceType::Launchd => {
+            LaunchdControl::new().start(service_id).await
+        }
+        #[cfg(target_os = "linux")]
+        ServiceType::Systemd => {
+            SystemdControl::new().start(service_id).await
+        }
+        #[cfg(target_os = "windows")]
+        ServiceType::WindowsService => {
+            WindowsControl::new().start(service_id).await
+        }
+        _ => Err("Cannot start this type of service".into()),
+    }
+}
+
+/// Stop a service. Subject to the confirmation protocol and dry-run mode,
+/// see `ConfirmationGuard`.
 #[tauri::command]
-pub async fn stop_service(service_id: String) -> Result<(), String> {
+pub async fn stop_service(service_id: String, confirmation_token: Option<String>) -> Result<ActionOutcome, String> {
     let manager = get_manager().lock().await;
 
     if let Some(service) = manager.get_service(&service_id).await {
-        let result = match service.service_type {
-            crate::models::service::ServiceType::Docker => {
-                DockerControl::new().stop(&service_id).await
-            }
-            #[cfg(target_os = "macos")]
-            crate::models::service::ServiceType::Launchd => {
-                LaunchdControl::new().stop(&service_id).await
-            }
-            #[cfg(target_os = "linux")]
-            crate::models::service::ServiceType::Systemd => {
-                SystemdControl::new().stop(&service_id).await
-            }
-            #[cfg(target_os = "windows")]
-            crate::models::service::ServiceType::WindowsService => {
-                WindowsControl::new().stop(&service_id).await
-            }
-            crate::models::service::ServiceType::Process => {
-                ProcessControl::new().stop(&service_id).await
-            }
-            #[allow(unreachable_patterns)]
-            _ => Err("Cannot stop this type of service".into()),
-        };
+        let guard = ConfirmationGuard::check("stop_service", &service_id, confirmation_token.as_deref(), || {
+            service_impact("stop_service", &service)
+        })
+        .map_err(|e| e.to_string())?;
+        if let Some(outcome) = guard {
+            return Ok(outcome);
+        }
+        if MockDiscovery::is_enabled() {
+            return Ok(ActionOutcome::Simulated);
+        }
 
-        result.map_err(|e| e.to_string())
+        stop_discovered_service(&service).await.map_err(|e| e.to_string())?;
+        ServiceMonitor::request_refresh(Some(&service_id));
+        Ok(ActionOutcome::Executed)
     } else {
         Err(format!("Service {} not found", service_id))
     }
 }
 
+/// Stop a discovered service with the controller for its type, without the
+/// confirmation protocol. Callers are responsible for confirming the action.
+/// Stopped services are recorded for `restore_session_state`.
+pub(crate) async fn stop_discovered_service(service: &Service) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let result = match service.service_type {
+        crate::models::service::ServiceType::Docker => {
+            DockerControl::new().stop(&service.id).await
+        }
+        #[cfg(target_os = "macos")]
+        crate::models::service::ServiceType::Launchd => {
+            LaunchdControl::new().stop(&service.id).await
+        }
+        #[cfg(target_os = "linux")]
+        crate::models::service::ServiceType::Systemd => {
+            SystemdControl::new().stop(&service.id).await
+        }
+        #[cfg(target_os = "windows")]
+        crate::models::service::ServiceType::WindowsService => {
+            WindowsControl::new().stop(&service.id).await
+        }
+        // Ids of port-holding processes are `process-<pid>`, so stop by PID
+        crate::models::service::ServiceType::Process => match service.pid {
+            Some(pid) => ProcessControl::new().stop(&pid.to_string()).await,
+            None => Err(format!("Process {} has no PID", service.id).into()),
+        },
+        #[allow(unreachable_patterns)]
+        _ => Err("Cannot stop this type of service".into()),
+    };
+
+    if result.is_ok() && service.status == crate::models::service::ServiceStatus::Running {
+        SessionJournal::record(&service.id, service.service_type.clone(), SessionChangeKind::Stopped);
+    }
+    result
+}
+
 #[tauri::command]
 pub async fn restart_service(service_id: String) -> Result<(), String> {
     let manager = get_manager().lock().await;
 
     if let Some(service) = manager.get_service(&service_id).await {
+        if MockDiscovery::is_enabled() {
+            return Ok(());
+        }
         let result = match service.service_type {
             crate::models::service::ServiceType::Docker => {
                 DockerControl::new().restart(&service_id).await
@@ -122,20 +212,108 @@ pub async fn restart_service(service_id: String) -> Result<(), String> {
             _ => Err("Cannot restart this type of service".into()),
         };
 
-        result.map_err(|e| e.to_string())
+        result.map_err(|e| e.to_string())?;
+        ServiceMonitor::request_refresh(Some(&service_id));
+        Ok(())
     } else {
         Err(format!("Service {} not found", service_id))
     }
 }
 
+/// Kill a process. Requires an echoed confirmation token when
+/// `require_confirmation_for_kill` is set; nothing is killed in dry-run mode.
 #[tauri::command]
-pub async fn kill_process(pid: u32) -> Result<(), String> {
+pub async fn kill_process(pid: u32, confirmation_token: Option<String>) -> Result<ActionOutcome, String> {
+    let target = pid.to_string();
+    let guard = ConfirmationGuard::check("kill_process", &target, confirmation_token.as_deref(), || {
+        process_impact(pid)
+    })
+    .map_err(|e| e.to_string())?;
+    if let Some(outcome) = guard {
+        return Ok(outcome);
+    }
+    // Simulated PIDs can belong to real host processes
+    if MockDiscovery::is_enabled() {
+        return Ok(ActionOutcome::Simulated);
+    }
+
     let control = ProcessControl::new();
-    control.kill(&pid.to_string()).await.map_err(|e| e.to_string())
+    control.kill(&target).await.map_err(|e| e.to_string())?;
+    ServiceMonitor::request_refresh(None);
+    Ok(ActionOutcome::Executed)
+}
+
+/// Stop whatever listens on `port`: the owning service through its controller,
+/// or the bare process. Same confirmation protocol as `kill_process`; the
+/// preview names the owner.
+#[tauri::command]
+pub async fn free_port(port: u16, confirmation_token: Option<String>) -> Result<ActionOutcome, String> {
+    let listeners = tokio::task::spawn_blocking(current_ports)
+        .await
+        .map_err(|e| e.to_string())?;
+    let listeners: Vec<_> = listeners.into_iter().filter(|p| p.port == port).collect();
+    if listeners.is_empty() {
+        return Err(format!("Port {} ist nicht belegt", port));
+    }
+    let pid = listeners.iter().find_map(|p| p.pid);
+
+    let services = get_manager().lock().await.discover_all().await;
+    let service = port_owner(port, pid, &services);
+    let Some(target) = service.map(|s| s.id.clone()).or_else(|| pid.map(|p| p.to_string())) else {
+        return Err(format!(
+            "Der Prozess hinter Port {} ist nicht ermittelbar, eventuell fehlen Rechte",
+            port
+        ));
+    };
+
+    // Bound to the current owner, so a token can't stop whatever takes the port next
+    let guard_target = format!("{}:{}", port, target);
+    let guard = ConfirmationGuard::check("free_port", &guard_target, confirmation_token.as_deref(), || {
+        let mut preview = match (service, pid) {
+            (Some(service), _) => {
+                let mut preview = service_impact("free_port", service);
+                preview.target_name = Some(format!("{} ({})", service.name, service_type_name(&service.service_type)));
+                preview
+            }
+            (None, Some(pid)) => process_impact(pid),
+            (None, None) => unreachable!("target requires a service or a PID"),
+        };
+        preview.action = "free_port".to_string();
+        if !preview.ports_freed.contains(&port) {
+            preview.ports_freed.insert(0, port);
+        }
+        preview
+    })
+    .map_err(|e| e.to_string())?;
+    if let Some(outcome) = guard {
+        return Ok(outcome);
+    }
+    if MockDiscovery::is_enabled() {
+        return Ok(ActionOutcome::Simulated);
+    }
+
+    stop_port_owner(port, service, pid).await?;
+    ServiceMonitor::request_refresh(None);
+    Ok(ActionOutcome::Executed)
+}
+
+/// Stop what `port_owner` resolved for a port: the service through its
+/// controller, or the bare process by PID. Shared by `free_port` and the
+/// analyzer's confirmed free_port proposals.
+pub(crate) async fn stop_port_owner(port: u16, service: Option<&Service>, pid: Option<u32>) -> Result<(), String> {
+    match (service, pid) {
+        (Some(service), _) => stop_discovered_service(service).await,
+        (None, Some(pid)) => ProcessControl::new().kill(&pid.to_string()).await,
+        (None, None) => return Err(format!("Der Prozess hinter Port {} ist nicht ermittelbar", port)),
+    }
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn enable_service_autostart(service_id: String, service_type: String) -> Result<(), String> {
+    if MockDiscovery::is_enabled() {
+        return Ok(());
+    }
     let result = match service_type.as_str() {
         #[cfg(target_os = "macos")]
         "launchd" => {
@@ -155,11 +333,16 @@ pub async fn enable_service_autostart(service_id: String, service_type: String)
         _ => Err("Autostart wird für diesen Service-Typ nicht unterstützt".into()),
     };
 
-    result.map_err(|e| e.to_string())
+    result.map_err(|e| e.to_string())?;
+    SessionJournal::forget(&service_id, SessionChangeKind::AutostartDisabled);
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn disable_service_autostart(service_id: String, service_type: String) -> Result<(), String> {
+    if MockDiscovery::is_enabled() {
+        return Ok(());
+    }
     let result = match service_type.as_str() {
         #[cfg(target_os = "macos")]
         "launchd" => {
@@ -179,5 +362,500 @@ pub async fn disable_service_autostart(service_id: String, service_type: String)
         _ => Err("Autostart wird für diesen Service-Typ nicht unterstützt".into()),
     };
 
-    result.map_err(|e| e.to_string())
+    result.map_err(|e| e.to_string())?;
+    if let Some(service_type) = service_type_from_name(&service_type) {
+        SessionJournal::record(&service_id, service_type, SessionChangeKind::AutostartDisabled);
+    }
+    Ok(())
+}
+
+/// Name of a service type as accepted by the autostart commands
+pub(crate) fn service_type_name(service_type: &ServiceType) -> &'static str {
+    match service_type {
+        ServiceType::Docker => "docker",
+        ServiceType::Systemd => "systemd",
+        ServiceType::Launchd => "launchd",
+        ServiceType::WindowsService => "windows_service",
+        ServiceType::Process => "process",
+    }
+}
+
+fn service_type_from_name(name: &str) -> Option<ServiceType> {
+    match name {
+        "docker" => Some(ServiceType::Docker),
+        "systemd" => Some(ServiceType::Systemd),
+        "launchd" => Some(ServiceType::Launchd),
+        "windows_service" => Some(ServiceType::WindowsService),
+        _ => None,
+    }
+}
+
+/// Services stopped or removed from autostart by the app in this session
+#[tauri::command]
+pub async fn get_session_changes() -> Result<Vec<SessionChange>, String> {
+    Ok(SessionJournal::list())
+}
+
+/// Undo this session's changes: re-enable autostart and restart stopped
+/// services, newest change first. Failed restores stay recorded so they
+/// can be retried.
+#[tauri::command]
+pub async fn restore_session_state() -> Result<SessionRestoreReport, String> {
+    let logger = AuditLogger::new();
+    let mut results = Vec::new();
+
+    for change in SessionJournal::list().into_iter().rev() {
+        let (event_type, result) = match change.kind {
+            SessionChangeKind::Stopped => (
+                EventType::ServiceStart,
+                start_service_of_type(&change.service_id, &change.service_type)
+                    .await
+                    .map_err(|e| e.to_string()),
+            ),
+            SessionChangeKind::AutostartDisabled => (
+                EventType::ConfigChange,
+                enable_service_autostart(
+                    change.service_id.clone(),
+                    service_type_name(&change.service_type).to_string(),
+                )
+                .await,
+            ),
+        };
+
+        if result.is_ok() {
+            SessionJournal::forget(&change.service_id, change.kind);
+            ServiceMonitor::request_refresh(Some(&change.service_id));
+        }
+
+        let mut entry = AuditEntry::new(event_type, "restore_session_state".to_string());
+        entry.service_id = Some(change.service_id.clone());
+        entry.success = result.is_ok();
+        entry.error_message = result.as_ref().err().cloned();
+        entry.details = serde_json::json!({ "change": change.kind });
+        let _ = logger.log(&entry);
+
+        results.push(SessionRestoreResult {
+            change,
+            success: result.is_ok(),
+            error: result.err(),
+        });
+    }
+
+    let restored = results.iter().filter(|r| r.success).count();
+    Ok(SessionRestoreReport {
+        failed: results.len() - restored,
+        restored,
+        results,
+    })
+}
+
+/// Generate and install a launchd plist / systemd user unit for an ad-hoc process.
+/// Returns the path of the installed file.
+#[tauri::command]
+pub async fn register_custom_service(spec: CustomServiceSpec) -> Result<String, String> {
+    let path = CustomServiceInstaller::new()
+        .install(&spec)
+        .map_err(|e| e.to_string())?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn get_unit_file(unit: String) -> Result<String, String> {
+    UnitFileEditor::new().cat_unit(&unit).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_unit_override(unit: String) -> Result<String, String> {
+    UnitFileEditor::new().read_override(&unit).map_err(|e| e.to_string())
+}
+
+/// Validate and diff proposed override content without writing it
+#[tauri::command]
+pub async fn preview_unit_override(unit: String, content: String) -> Result<UnitOverrideDiff, String> {
+    UnitFileEditor::new().diff_override(&unit, &content).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn write_unit_override(
+    unit: String,
+    content: String,
+    expected_previous: String,
+) -> Result<UnitOverrideDiff, String> {
+    UnitFileEditor::new()
+        .write_override(&unit, &content, &expected_previous)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn systemd_daemon_reload() -> Result<(), String> {
+    let editor = UnitFileEditor::new();
+    if !editor.is_available() {
+        return Err("systemd ist nur unter Linux verfügbar".into());
+    }
+    editor.daemon_reload().map_err(|e| e.to_string())
+}
+
+/// Dependencies and running dependents of a Windows service
+#[tauri::command]
+pub async fn get_windows_service_dependencies(service_id: String) -> Result<ServiceDependencies, String> {
+    #[cfg(target_os = "windows")]
+    {
+        WindowsControl::new().dependencies(&service_id).map_err(|e| e.to_string())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = service_id;
+        Err("Windows-Dienste sind nur unter Windows verfügbar".into())
+    }
+}
+
+/// What a running service currently talks to: other local services by port and
+/// remote hosts by reverse DNS (and GeoIP, if configured)
+#[tauri::command]
+pub async fn get_service_dependencies_runtime(service_id: String) -> Result<ServiceRuntimeDependencies, String> {
+    let services = get_manager().lock().await.discover_all().await;
+    let service = services
+        .iter()
+        .find(|s| s.id == service_id)
+        .cloned()
+        .ok_or_else(|| format!("Dienst {} nicht gefunden", service_id))?;
+    if service.pid.is_none() {
+        return Err(format!("{} läuft nicht", service.name));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let resolver = PortResolver::new();
+        let listeners = resolver.get_port_usage();
+        let connections = resolver.connections();
+        DependencyMapper::new().map(&service, &services, &listeners, &connections)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Stop a Windows service together with its running dependents. The
+/// confirmation preview lists the dependents that will be stopped.
+#[tauri::command]
+pub async fn stop_service_with_dependents(
+    service_id: String,
+    confirmation_token: Option<String>,
+) -> Result<ActionOutcome, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let service = get_manager()
+            .lock()
+            .await
+            .get_service(&service_id)
+            .await
+            .ok_or_else(|| format!("Service {} not found", service_id))?;
+        let guard = ConfirmationGuard::check(
+            "stop_service_with_dependents",
+            &service_id,
+            confirmation_token.as_deref(),
+            || service_impact("stop_service_with_dependents", &service),
+        )
+        .map_err(|e| e.to_string())?;
+        if let Some(outcome) = guard {
+            return Ok(outcome);
+        }
+        if MockDiscovery::is_enabled() {
+            return Ok(ActionOutcome::Simulated);
+        }
+
+        let dependents = WindowsControl::new()
+            .stop_with_dependents(&service_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        SessionJournal::record(&service_id, ServiceType::WindowsService, SessionChangeKind::Stopped);
+        for dependent in &dependents {
+            SessionJournal::record(dependent, ServiceType::WindowsService, SessionChangeKind::Stopped);
+        }
+        ServiceMonitor::request_refresh(Some(&service_id));
+        Ok(ActionOutcome::Executed)
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (service_id, confirmation_token);
+        Err("Windows-Dienste sind nur unter Windows verfügbar".into())
+    }
+}
+
+/// Create and start a new Docker container. Returns the container ID.
+#[tauri::command]
+pub async fn docker_run(
+    image: String,
+    name: Option<String>,
+    ports: Vec<PortMapping>,
+    env: Option<std::collections::HashMap<String, String>>,
+    volumes: Option<Vec<String>>,
+) -> Result<String, String> {
+    if image.trim().is_empty() {
+        return Err("Es muss ein Image angegeben werden".into());
+    }
+
+    // Fail early instead of letting Docker report a bind error
+    let collisions = port_collisions(&ports).await?;
+    if !collisions.is_empty() {
+        return Err(collision_error(&collisions));
+    }
+
+    DockerControl::new()
+        .run(
+            &image,
+            name.as_deref().filter(|n| !n.is_empty()),
+            &ports,
+            &env.unwrap_or_default(),
+            &volumes.unwrap_or_default(),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Host ports a stopped container (`service_id`) or a planned one (`ports`)
+/// would publish that are already in use, with the service or process
+/// holding each of them
+#[tauri::command]
+pub async fn check_port_collisions(
+    service_id: Option<String>,
+    ports: Option<Vec<PortMapping>>,
+) -> Result<Vec<PortCollision>, String> {
+    let mut mappings = ports.unwrap_or_default();
+    if let Some(service_id) = service_id {
+        mappings.extend(
+            DockerControl::new()
+                .port_bindings(&service_id)
+                .await
+                .map_err(|e| e.to_string())?,
+        );
+    }
+    port_collisions(&mappings).await
+}
+
+/// Mappings whose host port already has a listener with the same protocol
+async fn port_collisions(mappings: &[PortMapping]) -> Result<Vec<PortCollision>, String> {
+    if mappings.is_empty() {
+        return Ok(Vec::new());
+    }
+    let listeners = tokio::task::spawn_blocking(|| PortResolver::new().get_port_usage())
+        .await
+        .map_err(|e| e.to_string())?;
+    let taken: Vec<_> = mappings
+        .iter()
+        .filter_map(|mapping| listener_for(mapping, &listeners).map(|listener| (mapping.clone(), listener.clone())))
+        .collect();
+    if taken.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Discovery is slow, only attribute owners once something collides
+    let services = get_manager().lock().await.discover_all().await;
+    let running: Vec<Service> = services.into_iter().filter(|s| s.status == ServiceStatus::Running).collect();
+    Ok(taken
+        .into_iter()
+        .map(|(mapping, listener)| {
+            let service = port_owner(listener.port, listener.pid, &running);
+            let owner = match (service, &listener.process_name, listener.pid) {
+                (Some(service), _, _) => format!("{} ({})", service.name, service_type_name(&service.service_type)),
+                (None, Some(name), Some(pid)) => format!("{} (PID {})", name, pid),
+                (None, None, Some(pid)) => format!("PID {}", pid),
+                (None, Some(name), None) => name.clone(),
+                (None, None, None) => "einen unbekannten Prozess".to_string(),
+            };
+            PortCollision {
+                mapping,
+                listener,
+                service_id: service.map(|s| s.id.clone()),
+                owner,
+            }
+        })
+        .collect())
+}
+
+/// The listener holding `mapping`'s host port; TCP and UDP ports don't collide
+fn listener_for<'a>(mapping: &PortMapping, listeners: &'a [PortInfo]) -> Option<&'a PortInfo> {
+    listeners
+        .iter()
+        .find(|l| l.port == mapping.host_port && l.protocol == mapping.protocol)
+}
+
+fn collision_error(collisions: &[PortCollision]) -> String {
+    collisions
+        .iter()
+        .map(|c| {
+            let protocol = match c.mapping.protocol {
+                Protocol::Tcp => "tcp",
+                Protocol::Udp => "udp",
+            };
+            format!("Port {}/{} ist bereits belegt durch {}", c.mapping.host_port, protocol, c.owner)
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Ports and child processes affected by killing `pid`
+fn process_impact(pid: u32) -> ImpactPreview {
+    let system = sysinfo::System::new_all();
+    let target_pid = sysinfo::Pid::from_u32(pid);
+
+    ImpactPreview {
+        action: "kill_process".to_string(),
+        target: pid.to_string(),
+        target_name: system
+            .process(target_pid)
+            .map(|p| p.name().to_string_lossy().to_string()),
+        ports_freed: ports_of_pid(pid),
+        dependents_affected: child_processes(&system, target_pid),
+    }
+}
+
+/// Ports, child processes and dependent services affected by stopping `service`
+fn service_impact(action: &str, service: &Service) -> ImpactPreview {
+    let mut dependents = Vec::new();
+    let mut ports = service.ports.clone();
+
+    if let Some(pid) = service.pid {
+        let system = sysinfo::System::new_all();
+        dependents.extend(child_processes(&system, sysinfo::Pid::from_u32(pid)));
+        ports.extend(ports_of_pid(pid));
+    }
+
+    #[cfg(target_os = "windows")]
+    if service.service_type == crate::models::service::ServiceType::WindowsService {
+        if let Ok(dependencies) = WindowsControl::new().dependencies(&service.id) {
+            dependents.extend(dependencies.running_dependents);
+        }
+    }
+
+    ports.sort_unstable();
+    ports.dedup();
+
+    ImpactPreview {
+        action: action.to_string(),
+        target: service.id.clone(),
+        target_name: Some(service.name.clone()),
+        ports_freed: ports,
+        dependents_affected: dependents,
+    }
+}
+
+fn ports_of_pid(pid: u32) -> Vec<u16> {
+    let mut ports: Vec<u16> = PortResolver::new()
+        .get_port_usage()
+        .into_iter()
+        .filter(|p| p.pid == Some(pid))
+        .map(|p| p.port)
+        .collect();
+    ports.sort_unstable();
+    ports.dedup();
+    ports
+}
+
+fn child_processes(system: &sysinfo::System, parent: sysinfo::Pid) -> Vec<String> {
+    system
+        .processes()
+        .iter()
+        .filter(|(_, process)| process.parent() == Some(parent))
+        .map(|(pid, process)| format!("{} ({})", process.name().to_string_lossy(), pid))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::port::{AddressFamily, PortStatus};
+
+    fn listener(port: u16, protocol: Protocol) -> PortInfo {
+        PortInfo {
+            port,
+            protocol,
+            family: AddressFamily::Ipv4,
+            bind_address: "0.0.0.0".to_string(),
+            status: PortStatus::Occupied,
+            process_name: Some("dnsmasq".to_string()),
+            pid: Some(42),
+            detected_service: None,
+            version: None,
+            user: None,
+            service_name: None,
+            latency_ms: None,
+            os_guess: None,
+        }
+    }
+
+    #[test]
+    fn collisions_need_the_same_protocol() {
+        let listeners = [listener(53, Protocol::Udp)];
+        let tcp = PortMapping { host_port: 53, container_port: 53, protocol: Protocol::Tcp };
+        let udp = PortMapping { host_port: 53, container_port: 53, protocol: Protocol::Udp };
+
+        assert!(listener_for(&tcp, &listeners).is_none());
+        assert_eq!(listener_for(&udp, &listeners).and_then(|l| l.pid), Some(42));
+    }
+
+    /// Child side of `free_port_stops_a_bare_listener`: holds a port until killed
+    #[test]
+    #[ignore = "spawned as a child process by free_port_stops_a_bare_listener"]
+    fn bare_listener() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        println!("port={}", listener.local_addr().unwrap().port());
+        std::thread::sleep(std::time::Duration::from_secs(60));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn free_port_stops_a_bare_listener() {
+        use std::io::{BufRead, BufReader};
+
+        let mut child = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "commands::service_commands::tests::bare_listener", "--ignored", "--nocapture"])
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let port = BufReader::new(child.stdout.take().unwrap())
+            .lines()
+            .map_while(Result::ok)
+            .find_map(|line| line.split("port=").nth(1)?.trim().parse::<u16>().ok())
+            .expect("listener reports its port");
+        let pid = child.id();
+
+        // What discovery reports for a process no provider knows
+        let services = [crate::services::manager::port_process_service(
+            pid,
+            "bare-listener".to_string(),
+            vec![port],
+            format!("Port {}", port),
+        )];
+        let owner = port_owner(port, Some(pid), &services).expect("the listener owns the port");
+        assert_eq!(owner.id, format!("process-{}", pid));
+
+        stop_port_owner(port, Some(owner), Some(pid)).await.unwrap();
+        assert!(!child.wait().unwrap().success());
+        assert!(std::net::TcpListener::bind(("127.0.0.1", port)).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn simulation_mode_never_kills_host_processes() {
+        // A simulated PID that happens to exist on the host
+        let mut child = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "commands::service_commands::tests::bare_listener", "--ignored"])
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+
+        MockDiscovery::set_enabled(true);
+        let mut outcome = kill_process(pid, None).await;
+        if let Ok(ActionOutcome::ConfirmationRequired { token, .. }) = outcome {
+            outcome = kill_process(pid, Some(token)).await;
+        }
+        MockDiscovery::set_enabled(false);
+
+        assert!(matches!(outcome, Ok(ActionOutcome::Simulated)), "{:?}", outcome);
+        // Give a wrongly sent signal time to land
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert!(child.try_wait().unwrap().is_none());
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
 }
@@ -1,78 +1,375 @@
-use crate::services::system_stats::{SystemMonitor, SystemStats, GpuProvider};
+use crate::commands::llm_commands::{current_client, ensure_enabled};
+use crate::llm::{LogAnalyzer, RemediationPlan, SecurityAnalysis};
+use crate::services::compliance::{Compliance, ComplianceProfile, ComplianceReport, ProfileInfo};
+use crate::services::disk_health::{DiskHealthChecker, DiskHealthReport};
+use crate::services::docker_hardening::DockerHardening;
+use crate::services::hash_reputation::HashReputation;
+use crate::services::history::HistoryStore;
+use crate::services::security::SecurityAcknowledgements;
+use crate::commands::config_commands::set_acknowledged_issues;
+use crate::services::firewall::{FirewallChecker, FirewallStatus};
+use crate::services::powermetrics::{PowermetricsSampler, PowermetricsStatus};
+use crate::services::stats_history::{StatsResolution, StatsSample};
+use crate::services::system_stats::{sort_processes, GpuProviderInfo, HostInfo, ProcessSort, ProcessUsage, SystemMonitor, SystemStats, GpuProvider};
 use crate::services::security_scanner::{SecurityScanner, SecurityScanResult};
 use crate::services::ServiceManager;
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::discovery::traits::ServiceDiscovery;
+use crate::services::time_sync::{TimeSyncChecker, TimeSyncStatus};
+use crate::services::ssdp::SsdpScanner;
+use crate::services::port::{PortResolver, ServiceNames};
+use crate::services::notifier::{NotificationCategory, Notifier};
+use crate::services::security_scanner::SecuritySeverity;
+use crate::services::webhooks::WebhookDispatcher;
+use crate::models::config::{AcknowledgedIssue, WebhookEvent};
+use chrono::{DateTime, Utc};
+use tauri::AppHandle;
 use std::sync::OnceLock;
 use tokio::sync::Mutex;
 
-static SYSTEM_MONITOR: OnceLock<Mutex<SystemMonitor>> = OnceLock::new();
-static SECURITY_SCANNER: OnceLock<SecurityScanner> = OnceLock::new();
 static SERVICE_MANAGER: OnceLock<Mutex<ServiceManager>> = OnceLock::new();
 
-fn get_system_monitor() -> &'static Mutex<SystemMonitor> {
-    SYSTEM_MONITOR.get_or_init(|| Mutex::new(SystemMonitor::new()))
-}
-
-fn get_security_scanner() -> &'static SecurityScanner {
-    SECURITY_SCANNER.get_or_init(SecurityScanner::new)
-}
-
 fn get_service_manager() -> &'static Mutex<ServiceManager> {
     SERVICE_MANAGER.get_or_init(|| Mutex::new(ServiceManager::new()))
 }
 
 #[tauri::command]
 pub async fn get_system_stats() -> Result<SystemStats, String> {
-    let mut monitor = get_system_monitor().lock().await;
+    if MockDiscovery::is_enabled() {
+        let tick = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        return Ok(MockDiscovery::new().system_stats(tick));
+    }
+
+    let mut monitor = SystemMonitor::shared().lock().await;
     Ok(monitor.get_stats())
 }
 
+/// CPU/memory/GPU samples of the last `range_seconds` from the in-memory
+/// history, at `resolution` or the finest one that covers the range
+#[tauri::command]
+pub async fn get_stats_history(
+    range_seconds: u64,
+    resolution: Option<StatsResolution>,
+) -> Result<Vec<StatsSample>, String> {
+    if MockDiscovery::is_enabled() {
+        let resolution = resolution.unwrap_or_else(|| StatsResolution::for_range(range_seconds));
+        let step = resolution.step_seconds();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let range = range_seconds.min(resolution.retention_seconds());
+        let mock = MockDiscovery::new();
+        return Ok((0..range / step)
+            .rev()
+            .map(|i| {
+                let timestamp = (now - i * step) / step * step;
+                let stats = mock.system_stats(timestamp);
+                StatsSample {
+                    timestamp,
+                    cpu_percent: stats.cpu.usage_percent,
+                    memory_percent: stats.memory.usage_percent,
+                    gpu_percent: stats.gpus.first().and_then(|gpu| gpu.usage_percent),
+                }
+            })
+            .collect());
+    }
+
+    Ok(SystemMonitor::shared().lock().await.stats_history(range_seconds, resolution))
+}
+
+/// The `n` (default 10) heaviest processes by CPU or memory
+#[tauri::command]
+pub async fn get_top_processes(by: ProcessSort, n: Option<usize>) -> Result<Vec<ProcessUsage>, String> {
+    let n = n.unwrap_or(10);
+    if MockDiscovery::is_enabled() && by != ProcessSort::Network {
+        let services = MockDiscovery::new().discover().await.map_err(|e| e.to_string())?;
+        let mut processes: Vec<ProcessUsage> = services
+            .into_iter()
+            .filter_map(|s| {
+                Some(ProcessUsage {
+                    pid: s.pid?,
+                    name: s.name,
+                    path: s.path,
+                    user: s.user,
+                    cpu_usage: s.cpu_usage.unwrap_or(0.0),
+                    memory_bytes: s.memory_bytes.unwrap_or(0),
+                    memory_percent: s.memory_percent.unwrap_or(0.0),
+                })
+            })
+            .collect();
+        sort_processes(&mut processes, by);
+        processes.truncate(n);
+        return Ok(processes);
+    }
+
+    SystemMonitor::shared().lock().await.top_processes(by, n)
+}
+
+/// Hostname, OS, kernel, uptime and load, without sampling CPU usage
+#[tauri::command]
+pub async fn get_host_info() -> Result<HostInfo, String> {
+    if MockDiscovery::is_enabled() {
+        let tick = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        return Ok(MockDiscovery::host_info(tick));
+    }
+    Ok(HostInfo::current())
+}
+
+/// Use one provider, all detected ones ("auto") or none
 #[tauri::command]
 pub async fn set_gpu_provider(provider: String) -> Result<(), String> {
-    let gpu_provider = match provider.to_lowercase().as_str() {
-        "auto" => GpuProvider::Auto,
-        "apple" => GpuProvider::Apple,
-        "nvidia" => GpuProvider::Nvidia,
-        "amd" => GpuProvider::Amd,
-        "none" => GpuProvider::None,
-        _ => return Err(format!("Unknown GPU provider: {}", provider)),
-    };
-
-    let mut monitor = get_system_monitor().lock().await;
-    *monitor = SystemMonitor::new().with_gpu_provider(gpu_provider);
+    let gpu_provider = parse_gpu_provider(&provider)?;
+    SystemMonitor::shared().lock().await.set_gpu_provider(gpu_provider);
     Ok(())
 }
 
+/// Detected and enabled GPU providers with their GPUs
 #[tauri::command]
-pub async fn scan_security() -> Result<SecurityScanResult, String> {
-    let scanner = get_security_scanner();
-    let manager = get_service_manager().lock().await;
-    let services = manager.discover_all().await;
-    Ok(scanner.scan(&services))
+pub async fn list_gpu_providers() -> Result<Vec<GpuProviderInfo>, String> {
+    if MockDiscovery::is_enabled() {
+        return Ok(Vec::new());
+    }
+    Ok(SystemMonitor::shared().lock().await.gpu_providers())
 }
 
 #[tauri::command]
-pub async fn get_security_analysis(services_json: String) -> Result<String, String> {
+pub async fn set_gpu_provider_enabled(provider: String, enabled: bool) -> Result<(), String> {
+    let gpu_provider = parse_gpu_provider(&provider)?;
+    SystemMonitor::shared().lock().await.set_gpu_provider_enabled(gpu_provider, enabled);
+    Ok(())
+}
+
+/// Show or hide a single GPU by the name its provider reports
+#[tauri::command]
+pub async fn set_gpu_enabled(name: String, enabled: bool) -> Result<(), String> {
+    SystemMonitor::shared().lock().await.set_gpu_enabled(&name, enabled);
+    Ok(())
+}
+
+fn parse_gpu_provider(provider: &str) -> Result<GpuProvider, String> {
+    match provider.to_lowercase().as_str() {
+        "auto" => Ok(GpuProvider::Auto),
+        "apple" => Ok(GpuProvider::Apple),
+        "nvidia" => Ok(GpuProvider::Nvidia),
+        "amd" => Ok(GpuProvider::Amd),
+        "windows" => Ok(GpuProvider::Windows),
+        "none" => Ok(GpuProvider::None),
+        _ => Err(format!("Unknown GPU provider: {}", provider)),
+    }
+}
+
+/// Whether the Apple GPU sampler delivers, and why not
+#[tauri::command]
+pub async fn get_powermetrics_status() -> Result<PowermetricsStatus, String> {
+    Ok(PowermetricsSampler::status())
+}
+
+/// Sync daemon status and clock offset against `server` (default: the daemon's
+/// server, or pool.ntp.org)
+#[tauri::command]
+pub async fn check_time_sync(server: Option<String>) -> Result<TimeSyncStatus, String> {
+    tokio::task::spawn_blocking(move || TimeSyncChecker::new().with_server(server).check())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// SMART health of all drives
+#[tauri::command]
+pub async fn check_disk_health() -> Result<DiskHealthReport, String> {
+    if MockDiscovery::is_enabled() {
+        return Ok(DiskHealthReport::new(MockDiscovery::new().disk_health(), Vec::new()));
+    }
+    tokio::task::spawn_blocking(|| DiskHealthChecker::new().check())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Accept a finding as a known risk. It moves to the `acknowledged` section
+/// of later scans and stops counting towards the severities until `expires`.
+#[tauri::command]
+pub async fn acknowledge_security_issue(
+    id: String,
+    reason: String,
+    expires: Option<DateTime<Utc>>,
+) -> Result<AcknowledgedIssue, String> {
+    let acknowledgement = SecurityAcknowledgements::acknowledge(&id, &reason, expires).map_err(|e| e.to_string())?;
+    set_acknowledged_issues(SecurityAcknowledgements::list()).await;
+    Ok(acknowledgement)
+}
+
+#[tauri::command]
+pub async fn revoke_security_acknowledgement(id: String) -> Result<(), String> {
+    if !SecurityAcknowledgements::revoke(&id) {
+        return Err(format!("Befund {} ist nicht akzeptiert", id));
+    }
+    set_acknowledged_issues(SecurityAcknowledgements::list()).await;
+    Ok(())
+}
+
+/// All acknowledgements, including expired ones
+#[tauri::command]
+pub async fn list_security_acknowledgements() -> Result<Vec<AcknowledgedIssue>, String> {
+    Ok(SecurityAcknowledgements::list())
+}
+
+/// Whether ufw/firewalld/nftables/iptables, ALF/pf or the Windows firewall
+/// filters incoming connections
+#[tauri::command]
+pub async fn check_firewall() -> Result<FirewallStatus, String> {
+    tokio::task::spawn_blocking(|| FirewallChecker::new().check())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// `upnp` also asks the router for port forwardings to the LAN; `auth_probe`
+/// sends read-only commands to local Redis, MongoDB, Elasticsearch and
+/// Memcached to confirm whether they accept clients without credentials
+#[tauri::command]
+pub async fn scan_security(
+    app: AppHandle,
+    fingerprint: Option<bool>,
+    upnp: Option<bool>,
+    auth_probe: Option<bool>,
+) -> Result<SecurityScanResult, String> {
+    let scanner = SecurityScanner::new()
+        .with_fingerprinting(fingerprint.unwrap_or(false))
+        .with_auth_probing(auth_probe.unwrap_or(false));
+    let discovery = get_service_manager().lock().await.discover().await;
+    let mut result = scanner.scan(&discovery.services);
+
+    if upnp.unwrap_or(false) {
+        let upnp = SsdpScanner::new().scan().await.map_err(|e| e.to_string())?;
+        result.add_issues(upnp.issues);
+    }
+    // Container isolation comes from `docker inspect`, which the sync scanner can't call
+    if !MockDiscovery::is_enabled() {
+        if let Ok(findings) = DockerHardening::new().findings().await {
+            result.add_issues(findings);
+        }
+        // Known-bad executables behind listening ports, opt-in
+        if HashReputation::is_enabled() {
+            let ports = tokio::task::spawn_blocking(|| PortResolver::new().get_port_usage())
+                .await
+                .unwrap_or_default();
+            result.add_issues(HashReputation::findings(ports).await);
+        }
+    }
+    // Failing drives are reported with the security findings
+    if let Ok(disks) = check_disk_health().await {
+        result.add_issues(DiskHealthChecker::findings(&disks));
+    }
+
+    // The scanner reports its own port warnings; add the remaining provider gaps
+    for warning in discovery.warnings {
+        let duplicate = result
+            .warnings
+            .iter()
+            .any(|w| w.provider == warning.provider && w.kind == warning.kind);
+        if !duplicate {
+            result.warnings.push(warning);
+        }
+    }
+
+    // Kept for diffing later scans; simulated findings would only add noise
+    if !MockDiscovery::is_enabled() {
+        let _ = HistoryStore::record_security_scan(&result).await;
+    }
+
+    if result.critical_count > 0 {
+        let titles: Vec<&str> = result
+            .issues
+            .iter()
+            .filter(|i| i.severity == SecuritySeverity::Critical)
+            .map(|i| i.title.as_str())
+            .collect();
+        Notifier::send(
+            &app,
+            NotificationCategory::SecurityCritical,
+            format!("{} kritische Sicherheitsprobleme", result.critical_count),
+            titles.join(", "),
+        );
+    }
+    if !result.issues.is_empty() {
+        WebhookDispatcher::dispatch(
+            WebhookEvent::SecurityFinding,
+            serde_json::json!({
+                "critical_count": result.critical_count,
+                "high_count": result.high_count,
+                "medium_count": result.medium_count,
+                "low_count": result.low_count,
+                "issues": result.issues,
+            }),
+        );
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_compliance_profiles() -> Result<Vec<ProfileInfo>, String> {
+    Ok(Compliance::profiles())
+}
+
+/// Score the current system against a compliance profile. Runs its own
+/// security scan without notifications, webhooks or history.
+#[tauri::command]
+pub async fn run_compliance_scan(profile: ComplianceProfile) -> Result<ComplianceReport, String> {
+    let discovery = get_service_manager().lock().await.discover().await;
+    let mut result = SecurityScanner::new().scan(&discovery.services);
+    if !MockDiscovery::is_enabled() {
+        if let Ok(findings) = DockerHardening::new().findings().await {
+            result.add_issues(findings);
+        }
+        if HashReputation::is_enabled() {
+            let ports = tokio::task::spawn_blocking(|| PortResolver::new().get_port_usage())
+                .await
+                .unwrap_or_default();
+            result.add_issues(HashReputation::findings(ports).await);
+        }
+    }
+    if let Ok(disks) = check_disk_health().await {
+        result.add_issues(DiskHealthChecker::findings(&disks));
+    }
+    tokio::task::spawn_blocking(move || Compliance::evaluate(profile, &result))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_security_analysis(services_json: String) -> Result<SecurityAnalysis, String> {
     // Use LLM for security analysis if available
-    let client = crate::llm::client::OllamaClient::new();
+    ensure_enabled()?;
+    let client = current_client().await.with_priority(crate::llm::Priority::Low);
 
-    if !client.is_available().await {
+    if !client.can_generate().await {
         return Err("Ollama ist nicht verfügbar. Starte Ollama für KI-Sicherheitsanalyse.".into());
     }
 
-    let prompt = format!(
-        r#"Analysiere diese Services auf Sicherheitsprobleme. Antworte auf Deutsch.
-Prüfe auf:
-- Unverschlüsselte Verbindungen
-- Öffentlich erreichbare Datenbanken
-- Fehlende Authentifizierung
-- Bekannte Schwachstellen
+    LogAnalyzer::new(client)
+        .analyze_security(&ServiceNames::annotate(&services_json), &HostInfo::current().prompt_context())
+        .await
+        .map_err(|e| e.to_string())
+}
 
-Services:
-{}
+/// Prioritized remediation plan for the findings of a scan, each step
+/// linked to the IDs of the findings it fixes
+#[tauri::command]
+pub async fn get_remediation_plan(scan: SecurityScanResult) -> Result<RemediationPlan, String> {
+    ensure_enabled()?;
+    let client = current_client().await.with_priority(crate::llm::Priority::Low);
 
-Gib eine kurze Zusammenfassung der wichtigsten Sicherheitsrisiken und Empfehlungen."#,
-        services_json
-    );
+    if !client.can_generate().await {
+        return Err("Ollama ist nicht verfügbar. Starte Ollama für einen KI-Maßnahmenplan.".into());
+    }
 
-    client.generate(&prompt).await.map_err(|e| e.to_string())
+    LogAnalyzer::new(client)
+        .remediation_plan(&scan, &HostInfo::current().prompt_context())
+        .await
+        .map_err(|e| e.to_string())
 }
@@ -0,0 +1,57 @@
+use crate::commands::config_commands::set_tunnels;
+use crate::models::audit::{AuditEntry, EventType};
+use crate::models::config::TunnelSpec;
+use crate::services::security::AuditLogger;
+use crate::services::tunnel::{TunnelManager, TunnelStatus};
+use tauri::AppHandle;
+
+#[tauri::command]
+pub async fn list_tunnels() -> Result<Vec<TunnelStatus>, String> {
+    Ok(TunnelManager::list())
+}
+
+/// Add a tunnel, or update the one with the same id. Returns it with its id.
+#[tauri::command]
+pub async fn save_tunnel(spec: TunnelSpec) -> Result<TunnelSpec, String> {
+    let spec = TunnelManager::save(spec).map_err(|e| e.to_string())?;
+    set_tunnels(TunnelManager::specs()).await;
+    Ok(spec)
+}
+
+#[tauri::command]
+pub async fn remove_tunnel(id: String) -> Result<(), String> {
+    if !TunnelManager::remove(&id) {
+        return Err(format!("Tunnel {} nicht gefunden", id));
+    }
+    set_tunnels(TunnelManager::specs()).await;
+    Ok(())
+}
+
+/// Start the ssh process; state changes arrive as `TunnelStateChanged` events
+#[tauri::command]
+pub async fn start_tunnel(app: AppHandle, id: String) -> Result<TunnelStatus, String> {
+    let status = TunnelManager::start(&app, &id).map_err(|e| e.to_string())?;
+    audit_tunnel(EventType::ServiceStart, "start_tunnel", &status);
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn stop_tunnel(id: String) -> Result<TunnelStatus, String> {
+    let status = TunnelManager::stop(&id).map_err(|e| e.to_string())?;
+    audit_tunnel(EventType::ServiceStop, "stop_tunnel", &status);
+    Ok(status)
+}
+
+/// Tunnels open ports on this machine or the server, so keep a record
+fn audit_tunnel(event_type: EventType, operation: &str, status: &TunnelStatus) {
+    let spec = &status.spec;
+    let mut entry = AuditEntry::new(event_type, operation.to_string());
+    entry.details = serde_json::json!({
+        "tunnel": spec.name,
+        "kind": spec.kind,
+        "bind_port": spec.bind_port,
+        "target": format!("{}:{}", spec.target_host, spec.target_port),
+        "ssh_host": spec.ssh_host,
+    });
+    let _ = AuditLogger::new().log(&entry);
+}
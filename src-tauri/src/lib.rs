@@ -5,67 +5,251 @@ pub mod commands;
 pub mod models;
 pub mod services;
 pub mod llm;
+pub mod parsers;
+mod tray;
 
 use commands::{
-    discover_services, get_service_details, start_service, stop_service, restart_service, kill_process,
-    enable_service_autostart, disable_service_autostart,
-    scan_ports, get_port_usage, find_free_ports,
-    get_config, update_config,
-    get_audit_logs, export_audit_logs,
-    check_ollama_status, list_ollama_models, analyze_logs, set_ollama_model,
-    explain_process, get_service_recommendations,
-    get_system_stats, set_gpu_provider, scan_security, get_security_analysis,
+    discover_services, get_user_scope, set_all_users_mode, get_service_details, start_service, stop_service, restart_service, kill_process, free_port,
+    enable_service_autostart, disable_service_autostart, register_custom_service,
+    get_unit_file, get_unit_override, preview_unit_override, write_unit_override, systemd_daemon_reload,
+    get_windows_service_dependencies, stop_service_with_dependents, get_service_dependencies_runtime,
+    get_session_changes, restore_session_state,
+    docker_run, check_port_collisions, list_images, pull_image, remove_image, list_docker_volumes, list_docker_networks,
+    scan_ports, get_nmap_status, get_port_usage, find_free_ports, get_socket_report, get_ipc_endpoints, get_connections, get_resolver_status,
+    reserve_port, release_port, list_port_reservations, record_port_baseline, get_port_baseline, clear_port_baseline, get_ports_at, get_port_history, diff_port_usage,
+    get_config, update_config, get_storage_paths, test_webhook,
+    get_audit_logs, export_audit_logs, ask_audit_log,
+    check_ollama_status, list_ollama_models, pull_ollama_model, analyze_logs, set_ollama_model,
+    set_ollama_endpoints, check_ollama_endpoints, get_llm_queue_status, set_ollama_fast_model, get_task_models, get_llm_diagnostics,
+    record_incident, get_stored_analysis, set_ollama_embedding_model,
+    create_chat_session, send_chat_message, list_chat_sessions, get_chat_session, delete_chat_session,
+    cluster_logs, explain_process, add_process_knowledge_entry, list_process_knowledge_entries, get_service_recommendations,
+    get_system_stats, get_stats_history, get_host_info, get_top_processes, set_gpu_provider, list_gpu_providers, set_gpu_provider_enabled, set_gpu_enabled, get_powermetrics_status, check_time_sync, check_disk_health, check_firewall, acknowledge_security_issue, revoke_security_acknowledgement, list_security_acknowledgements, scan_security, get_compliance_profiles, run_compliance_scan, get_security_analysis, get_remediation_plan,
+    inspect_tls, scan_tls_ports,
+    generate_digest, plan_cleanup, execute_cleanup, propose_service_actions, execute_proposed_actions,
+    start_remote_pairing, stop_remote_viewer, revoke_remote_device, get_remote_viewer_status,
+    discover_network_services, discover_upnp_devices,
+    list_tunnels, save_tunnel, remove_tunnel, start_tunnel, stop_tunnel,
+    export, export_security_report, get_export_template_dir,
+    list_prompt_templates, get_prompt_template, update_prompt_template, reset_prompt_template,
+    get_bootstrap_status, create_baseline_snapshot, complete_bootstrap,
+    get_system_history, get_service_history, get_port_count_history, get_alert_history, get_security_scans, get_security_scan, diff_security_scans,
 };
 
-use services::{MonitorState, set_monitor_interval, enable_monitor};
+use services::{DiskWatcher, MemoryWatcher, MonitorState, PortWatcher, set_monitor_interval, enable_monitor, start_monitor, stop_monitor, watch_service, unwatch_service, get_watched_services, get_recent_events};
+use services::digest::DigestScheduler;
+use services::port::PortHistory;
+use services::history::MonitoringHistory;
+use services::alerts::AlertEngine;
+use services::notifier::Notifier;
+use services::storage::Storage;
+use services::SystemMonitor;
+use services::tunnel::TunnelManager;
+use services::process_knowledge::ProcessKnowledge;
+use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // `--portable` keeps config, data and logs next to the executable
+    Storage::init(std::env::args().any(|arg| arg == "--portable"));
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(MonitorState::default())
-        .setup(|_app| {
+        .setup(|app| {
             // Service manager is initialized lazily in commands
-            // Monitor disabled - frontend handles refresh via polling
+            // The monitor pushes service events; start_monitor/stop_monitor control it at runtime
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                handle.state::<MonitorState>().start(handle.clone()).await;
+            });
+            tray::init(app)?;
+            DigestScheduler::start(app.handle().clone());
+            PortWatcher::start(app.handle().clone());
+            DiskWatcher::start(app.handle().clone());
+            MemoryWatcher::start(app.handle().clone());
+            PortHistory::start();
+            MonitoringHistory::start();
+            SystemMonitor::start_sampler();
+            AlertEngine::start(app.handle().clone());
+            TunnelManager::start_autostart(app.handle());
+            ProcessKnowledge::load();
             Ok(())
         })
+        .on_window_event(|window, event| {
+            // Keep monitoring and notifications running; the tray brings the window back
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                if Notifier::run_in_background() {
+                    api.prevent_close();
+                    let _ = window.hide();
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             // Service commands
             discover_services,
+            get_user_scope,
+            set_all_users_mode,
             get_service_details,
             start_service,
             stop_service,
             restart_service,
             kill_process,
+            free_port,
             enable_service_autostart,
             disable_service_autostart,
+            register_custom_service,
+            get_unit_file,
+            get_unit_override,
+            preview_unit_override,
+            write_unit_override,
+            systemd_daemon_reload,
+            get_windows_service_dependencies,
+            stop_service_with_dependents,
+            get_service_dependencies_runtime,
+            get_session_changes,
+            restore_session_state,
+            docker_run,
+            check_port_collisions,
+            // Docker commands
+            list_images,
+            pull_image,
+            remove_image,
+            list_docker_volumes,
+            list_docker_networks,
             // Port commands
             scan_ports,
+            get_nmap_status,
             get_port_usage,
             find_free_ports,
+            get_socket_report,
+            get_ipc_endpoints,
+            get_connections,
+            get_resolver_status,
+            reserve_port,
+            release_port,
+            list_port_reservations,
+            record_port_baseline,
+            get_port_baseline,
+            clear_port_baseline,
+            get_ports_at,
+            get_port_history,
+            diff_port_usage,
             // Config commands
             get_config,
             update_config,
+            get_storage_paths,
+            test_webhook,
             // Audit commands
             get_audit_logs,
             export_audit_logs,
+            ask_audit_log,
             // LLM commands
             check_ollama_status,
             list_ollama_models,
+            pull_ollama_model,
             analyze_logs,
             set_ollama_model,
+            set_ollama_endpoints,
+            check_ollama_endpoints,
+            get_llm_queue_status,
+            set_ollama_fast_model,
+            get_task_models,
+            get_llm_diagnostics,
+            record_incident,
+            get_stored_analysis,
+            set_ollama_embedding_model,
+            create_chat_session,
+            send_chat_message,
+            list_chat_sessions,
+            get_chat_session,
+            delete_chat_session,
+            cluster_logs,
             explain_process,
+            add_process_knowledge_entry,
+            list_process_knowledge_entries,
             get_service_recommendations,
             // Monitor commands
             set_monitor_interval,
             enable_monitor,
+            start_monitor,
+            stop_monitor,
+            watch_service,
+            unwatch_service,
+            get_watched_services,
+            get_recent_events,
             // System stats commands
             get_system_stats,
+            get_stats_history,
+            get_host_info,
+            get_top_processes,
             set_gpu_provider,
+            list_gpu_providers,
+            set_gpu_provider_enabled,
+            set_gpu_enabled,
+            get_powermetrics_status,
+            check_time_sync,
+            check_disk_health,
+            check_firewall,
+            acknowledge_security_issue,
+            revoke_security_acknowledgement,
+            list_security_acknowledgements,
             // Security commands
             scan_security,
+            get_compliance_profiles,
+            run_compliance_scan,
             get_security_analysis,
+            get_remediation_plan,
+            inspect_tls,
+            scan_tls_ports,
+            generate_digest,
+            plan_cleanup,
+            execute_cleanup,
+            propose_service_actions,
+            execute_proposed_actions,
+            // Remote viewer commands
+            start_remote_pairing,
+            stop_remote_viewer,
+            revoke_remote_device,
+            get_remote_viewer_status,
+            // Network commands
+            discover_network_services,
+            discover_upnp_devices,
+            // Tunnel commands
+            list_tunnels,
+            save_tunnel,
+            remove_tunnel,
+            start_tunnel,
+            stop_tunnel,
+            // Export commands
+            export,
+            export_security_report,
+            get_export_template_dir,
+            // Prompt templates
+            list_prompt_templates,
+            get_prompt_template,
+            update_prompt_template,
+            reset_prompt_template,
+            // Setup commands
+            get_bootstrap_status,
+            create_baseline_snapshot,
+            complete_bootstrap,
+            // Monitoring history
+            get_system_history,
+            get_service_history,
+            get_port_count_history,
+            get_alert_history,
+            get_security_scans,
+            get_security_scan,
+            diff_security_scans,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            // Don't leave ssh processes behind
+            if let tauri::RunEvent::Exit = event {
+                TunnelManager::stop_all();
+            }
+        });
 }
@@ -0,0 +1,292 @@
+use std::collections::HashSet;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use super::client::{ChatMessage, ChatRole, OllamaClient, ToolCall};
+use super::language::ResponseLanguage;
+use crate::models::port::PortInfo;
+use crate::models::service::{Service, ServiceStatus, ServiceType};
+use crate::services::cleanup::is_protected;
+use crate::services::port::port_owner;
+
+/// Services listed in the prompt; running and autostart services first
+const MAX_PROMPT_SERVICES: usize = 80;
+const MAX_ACTIONS: usize = 5;
+
+/// What a proposed action changes; executed through the service controllers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProposedActionKind {
+    StopService { service_id: String },
+    DisableAutostart { service_id: String, service_type: ServiceType },
+    /// Stop the service or process listening on the port. The owner seen at
+    /// proposal time is kept; the action is refused if it changed since.
+    FreePort {
+        port: u16,
+        service_id: Option<String>,
+        pid: Option<u32>,
+    },
+}
+
+/// A tool call of the model, checked against the discovered services
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposedAction {
+    /// Stable within a proposal, e.g. `stop:nginx.service`
+    pub id: String,
+    pub action: ProposedActionKind,
+    pub title: String,
+    /// The model's justification
+    pub reason: String,
+}
+
+/// Actions the model proposes; nothing runs until the user accepts them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionProposal {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub actions: Vec<ProposedAction>,
+    /// Tool calls that were dropped, e.g. for unknown or protected services
+    pub rejected: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposedActionStatus {
+    Executed,
+    Failed,
+    /// Dry-run mode is enabled, nothing was changed
+    DryRun,
+    /// Simulation mode is enabled, nothing on the host was changed
+    Simulated,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposedActionResult {
+    pub action_id: String,
+    pub status: ProposedActionStatus,
+    pub error: Option<String>,
+}
+
+/// Lets the model propose stop, autostart and port actions as tool calls.
+/// Calls are only accepted for services and ports that exist, aren't
+/// protected and are in a state where the action changes something.
+pub struct ActionPlanner {
+    client: OllamaClient,
+}
+
+impl ActionPlanner {
+    pub fn new(client: OllamaClient) -> Self {
+        Self { client }
+    }
+
+    pub async fn propose(
+        &self,
+        services: &[Service],
+        ports: &[PortInfo],
+        host_context: &str,
+    ) -> Result<ActionProposal, Box<dyn std::error::Error + Send + Sync>> {
+        let messages = [
+            ChatMessage {
+                role: ChatRole::System,
+                content: format!(
+                    "Du bist ein vorsichtiger Systemadministrator. Schlage mit den verfügbaren Werkzeugen höchstens {} Aktionen vor, \
+                     die Ressourcen sparen oder die Angriffsfläche verkleinern. Der Benutzer bestätigt jede Aktion, bevor sie ausgeführt wird. \
+                     Schlage nichts vor, was das Betriebssystem, die Anmeldung oder den Netzwerkzugang braucht. \
+                     Begründe jede Aktion in einem Satz im Feld reason. Wenn nichts sinnvoll ist, rufe kein Werkzeug auf. {}",
+                    MAX_ACTIONS,
+                    ResponseLanguage::instruction()
+                ),
+            },
+            ChatMessage {
+                role: ChatRole::User,
+                content: format!(
+                    "Host: {}\n\nServices (JSON):\n```json\n{}\n```\n\nLauschende Ports:\n{}",
+                    host_context,
+                    services_json(services),
+                    ports_list(ports)
+                ),
+            },
+        ];
+
+        let calls = self.client.chat_with_tools(&messages, &tools()).await?;
+
+        let mut actions: Vec<ProposedAction> = Vec::new();
+        let mut rejected = Vec::new();
+        for call in calls {
+            match validate(&call, services, ports) {
+                Ok(action) if actions.iter().any(|a| a.id == action.id) => {}
+                Ok(action) if actions.len() < MAX_ACTIONS => actions.push(action),
+                Ok(action) => rejected.push(format!("{}: mehr als {} Aktionen", action.title, MAX_ACTIONS)),
+                Err(reason) => rejected.push(format!("{}: {}", call.function.name, reason)),
+            }
+        }
+
+        Ok(ActionProposal {
+            id: uuid::Uuid::new_v4().to_string(),
+            created_at: Utc::now(),
+            actions,
+            rejected,
+        })
+    }
+}
+
+fn tools() -> Vec<serde_json::Value> {
+    let function = |name: &str, description: &str, target: (&str, &str, &str)| {
+        serde_json::json!({
+            "type": "function",
+            "function": {
+                "name": name,
+                "description": description,
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        target.0: { "type": target.1, "description": target.2 },
+                        "reason": { "type": "string", "description": "Begründung in einem Satz" }
+                    },
+                    "required": [target.0, "reason"]
+                }
+            }
+        })
+    };
+    vec![
+        function(
+            "stop_service",
+            "Stoppt einen laufenden Service",
+            ("service_id", "string", "id des Service aus der Liste"),
+        ),
+        function(
+            "disable_autostart",
+            "Verhindert, dass ein Service beim Systemstart startet",
+            ("service_id", "string", "id des Service aus der Liste"),
+        ),
+        function(
+            "free_port",
+            "Beendet den Service oder Prozess, der auf dem Port lauscht",
+            ("port", "integer", "Portnummer"),
+        ),
+    ]
+}
+
+fn validate(call: &ToolCall, services: &[Service], ports: &[PortInfo]) -> Result<ProposedAction, String> {
+    // Some models send the arguments as a JSON string
+    let arguments = match &call.function.arguments {
+        serde_json::Value::String(text) => serde_json::from_str(text).unwrap_or_default(),
+        other => other.clone(),
+    };
+    let reason = arguments["reason"].as_str().unwrap_or_default().trim().to_string();
+
+    let service = || {
+        let id = arguments["service_id"].as_str().ok_or("service_id fehlt")?;
+        let service = services
+            .iter()
+            .find(|s| s.id == id)
+            .ok_or_else(|| format!("Service {} existiert nicht", id))?;
+        if is_protected(&service.name) {
+            return Err(format!("{} ist geschützt", service.name));
+        }
+        Ok(service)
+    };
+
+    match call.function.name.as_str() {
+        "stop_service" => {
+            let service = service()?;
+            if service.status != ServiceStatus::Running {
+                return Err(format!("{} läuft nicht", service.name));
+            }
+            Ok(ProposedAction {
+                id: format!("stop:{}", service.id),
+                action: ProposedActionKind::StopService { service_id: service.id.clone() },
+                title: format!("{} stoppen", service.name),
+                reason,
+            })
+        }
+        "disable_autostart" => {
+            let service = service()?;
+            if !service.auto_start || service.service_type == ServiceType::Process {
+                return Err(format!("{} hat keinen Autostart", service.name));
+            }
+            Ok(ProposedAction {
+                id: format!("autostart:{}", service.id),
+                action: ProposedActionKind::DisableAutostart {
+                    service_id: service.id.clone(),
+                    service_type: service.service_type.clone(),
+                },
+                title: format!("Autostart von {} deaktivieren", service.name),
+                reason,
+            })
+        }
+        "free_port" => {
+            let port = arguments["port"]
+                .as_u64()
+                .or_else(|| arguments["port"].as_str().and_then(|p| p.parse().ok()))
+                .and_then(|p| u16::try_from(p).ok())
+                .ok_or("port fehlt")?;
+            let listener = ports
+                .iter()
+                .find(|p| p.port == port)
+                .ok_or_else(|| format!("Port {} ist nicht belegt", port))?;
+            let service = port_owner(port, listener.pid, services);
+            if service.is_none() && listener.pid.is_none() {
+                return Err(format!("Der Prozess hinter Port {} ist nicht ermittelbar", port));
+            }
+            let owner = service
+                .map(|s| s.name.clone())
+                .or_else(|| listener.process_name.clone())
+                .unwrap_or_else(|| "unbekannter Prozess".to_string());
+            if is_protected(&owner) || listener.process_name.as_deref().is_some_and(is_protected) {
+                return Err(format!("{} ist geschützt", owner));
+            }
+            Ok(ProposedAction {
+                id: format!("port:{}", port),
+                action: ProposedActionKind::FreePort {
+                    port,
+                    service_id: service.map(|s| s.id.clone()),
+                    pid: listener.pid,
+                },
+                title: format!("Port {} freigeben ({})", port, owner),
+                reason,
+            })
+        }
+        other => Err(format!("Unbekanntes Werkzeug {}", other)),
+    }
+}
+
+fn services_json(services: &[Service]) -> String {
+    let mut relevant: Vec<&Service> = services
+        .iter()
+        .filter(|s| s.status == ServiceStatus::Running || s.auto_start)
+        .collect();
+    relevant.truncate(MAX_PROMPT_SERVICES);
+    let entries: Vec<serde_json::Value> = relevant
+        .iter()
+        .map(|s| {
+            serde_json::json!({
+                "id": s.id,
+                "name": s.name,
+                "type": s.service_type,
+                "status": s.status,
+                "ports": s.ports,
+                "auto_start": s.auto_start,
+                "cpu_percent": s.cpu_usage,
+                "memory_mb": s.memory_bytes.map(|b| b / 1024 / 1024),
+            })
+        })
+        .collect();
+    serde_json::to_string(&entries).unwrap_or_default()
+}
+
+fn ports_list(ports: &[PortInfo]) -> String {
+    let mut seen = HashSet::new();
+    ports
+        .iter()
+        .filter(|p| seen.insert(p.port))
+        .map(|p| {
+            format!(
+                "- {} auf {} ({})",
+                p.port,
+                p.bind_address,
+                p.process_name.as_deref().unwrap_or("unbekannt")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
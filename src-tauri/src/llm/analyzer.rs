@@ -1,7 +1,25 @@
 use super::client::OllamaClient;
+use super::prompts::{PromptKind, PromptTemplates};
+use crate::services::hash_reputation::HashVerdict;
+use crate::services::process_knowledge::{ProcessCategory, ProcessSafety};
+use crate::services::security_scanner::{SecurityIssue, SecurityScanResult, SecuritySeverity};
 use regex::Regex;
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
+/// Logs up to this size are analyzed in one prompt; longer logs are split
+/// into chunks of this size
+const CHUNK_CHARS: usize = 8000;
+/// Chunks summarized at most; beyond that the chunks with the most errors
+/// and warnings are kept
+const MAX_CHUNKS: usize = 32;
+const MAX_REDUCE_ROUNDS: usize = 3;
+/// Findings given to the model for a remediation plan, most severe first
+const MAX_PLAN_FINDINGS: usize = 60;
+/// Characters of a finding's details shown to the model
+const FINDING_DETAILS_CHARS: usize = 200;
+
 pub struct LogAnalyzer {
     client: OllamaClient,
 }
@@ -15,6 +33,45 @@ pub enum AnalysisType {
     SecurityAnalysis,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogAnalysisStage {
+    /// Similar lines are grouped by their embeddings
+    Clustering,
+    /// Chunks of a long log are summarized
+    Summarizing,
+    /// Summaries too long for one prompt are summarized again
+    Combining,
+    /// The final analysis is generated
+    Analyzing,
+    Done,
+}
+
+/// Emitted as `log-analysis-progress` while logs are analyzed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogAnalysisProgress {
+    pub stage: LogAnalysisStage,
+    pub completed: usize,
+    pub total: usize,
+}
+
+impl LogAnalysisProgress {
+    fn new(stage: LogAnalysisStage, completed: usize, total: usize) -> Self {
+        Self { stage, completed, total }
+    }
+}
+
+/// What a process does, plus the reputation of its executable when hash
+/// lookups are enabled. Safety and category are set for processes from the
+/// knowledge base.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessExplanation {
+    pub explanation: String,
+    pub safety: Option<ProcessSafety>,
+    pub category: Option<ProcessCategory>,
+    pub reputation: Option<HashVerdict>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceRecommendation {
     pub service_id: String,
@@ -25,6 +82,44 @@ pub struct ServiceRecommendation {
     pub action: Option<String>,
 }
 
+/// Result of the AI security analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityAnalysis {
+    pub summary: String,
+    pub risks: Vec<SecurityRisk>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityRisk {
+    pub service_name: Option<String>,
+    pub severity: SecuritySeverity,
+    pub title: String,
+    pub recommendation: String,
+}
+
+/// Prioritized remediation plan for the findings of a security scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemediationPlan {
+    pub summary: String,
+    /// Most urgent first
+    pub steps: Vec<RemediationStep>,
+    /// Findings no step refers to, including those left out of the prompt
+    pub unaddressed_finding_ids: Vec<String>,
+}
+
+/// One paragraph of a remediation plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemediationStep {
+    /// Position in the plan, starting at 1
+    pub priority: usize,
+    pub title: String,
+    pub text: String,
+    /// IDs of the `SecurityIssue`s this step fixes
+    pub finding_ids: Vec<String>,
+    /// Highest severity of those findings
+    pub severity: Option<SecuritySeverity>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RecommendationType {
@@ -43,23 +138,7 @@ impl LogAnalyzer {
 
     /// Sanitize logs by removing sensitive information
     pub fn sanitize_logs(&self, logs: &str) -> String {
-        let patterns = vec![
-            (r"password\s*[=:]\s*\S+", "password=***"),
-            (r"api[_-]?key\s*[=:]\s*\S+", "api_key=***"),
-            (r"token\s*[=:]\s*\S+", "token=***"),
-            (r"secret\s*[=:]\s*\S+", "secret=***"),
-            (r"bearer\s+\S+", "bearer ***"),
-            (r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b", "email@***"),
-            (r"\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}\b", "x.x.x.x"),
-        ];
-
-        let mut sanitized = logs.to_string();
-        for (pattern, replacement) in patterns {
-            if let Ok(re) = Regex::new(pattern) {
-                sanitized = re.replace_all(&sanitized, replacement).to_string();
-            }
-        }
-        sanitized
+        sanitize(logs)
     }
 
     /// Analyze logs with a specific analysis type
@@ -67,66 +146,111 @@ impl LogAnalyzer {
         &self,
         logs: &str,
         analysis_type: AnalysisType,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        self.analyze_with_progress(logs, analysis_type, |_| {}).await
+    }
+
+    /// Analyze logs of any size. Logs longer than one chunk are reduced
+    /// map-reduce style: repeated lines are collapsed, every chunk is
+    /// summarized, and the summaries are combined into the final analysis.
+    pub async fn analyze_with_progress(
+        &self,
+        logs: &str,
+        analysis_type: AnalysisType,
+        on_progress: impl Fn(LogAnalysisProgress),
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         // Sanitize logs before sending to LLM
         let sanitized_logs = self.sanitize_logs(logs);
+        let task = analysis_task(analysis_type);
 
-        // Truncate if too long
-        let max_chars = 8000;
-        let logs_to_analyze = if sanitized_logs.len() > max_chars {
-            &sanitized_logs[sanitized_logs.len() - max_chars..]
-        } else {
-            &sanitized_logs
-        };
+        if sanitized_logs.len() <= CHUNK_CHARS {
+            on_progress(LogAnalysisProgress::new(LogAnalysisStage::Analyzing, 0, 1));
+            let prompt = self.build_prompt(&sanitized_logs, analysis_type);
+            let response = self.client.generate(&prompt).await?;
+            on_progress(LogAnalysisProgress::new(LogAnalysisStage::Done, 1, 1));
+            return Ok(response);
+        }
 
-        let prompt = self.build_prompt(logs_to_analyze, analysis_type);
-        let response = self.client.generate(&prompt).await?;
+        let collapsed = collapse_repeated_lines(&sanitized_logs);
+        let mut chunks = split_chunks(&collapsed, CHUNK_CHARS);
+        let total_chunks = chunks.len();
+        if total_chunks > MAX_CHUNKS {
+            chunks = most_relevant_chunks(chunks, MAX_CHUNKS);
+        }
+
+        // Map: one summary per chunk
+        let mut summaries = Vec::with_capacity(chunks.len());
+        for (index, chunk) in chunks.iter().enumerate() {
+            on_progress(LogAnalysisProgress::new(LogAnalysisStage::Summarizing, index, chunks.len()));
+            let summary = self.summarize_chunk(task, chunk, index + 1, chunks.len()).await?;
+            summaries.push(format!("Part {}/{}:\n{}", index + 1, chunks.len(), summary.trim()));
+        }
+
+        // Reduce: summaries that don't fit into one prompt are summarized again
+        let mut round = 0;
+        while summaries.join("\n\n").len() > CHUNK_CHARS && summaries.len() > 1 && round < MAX_REDUCE_ROUNDS {
+            let groups = split_chunks(&summaries.join("\n\n"), CHUNK_CHARS);
+            let mut combined = Vec::with_capacity(groups.len());
+            for (index, group) in groups.iter().enumerate() {
+                on_progress(LogAnalysisProgress::new(LogAnalysisStage::Combining, index, groups.len()));
+                combined.push(self.summarize_chunk(task, group, index + 1, groups.len()).await?.trim().to_string());
+            }
+            summaries = combined;
+            round += 1;
+        }
 
+        on_progress(LogAnalysisProgress::new(LogAnalysisStage::Analyzing, 0, 1));
+        let mut note = format!(
+            "The log is {} characters long and is given as summaries of its {} parts, in order.",
+            sanitized_logs.len(),
+            chunks.len()
+        );
+        if total_chunks > chunks.len() {
+            note.push_str(&format!(
+                " {} of {} parts with the fewest errors and warnings were skipped.",
+                total_chunks - chunks.len(),
+                total_chunks
+            ));
+        }
+        let prompt = PromptTemplates::new().render(
+            PromptKind::LogAnalysis,
+            &[("task", &format!("{}\n\n{}", task, note)), ("logs", &summaries.join("\n\n"))],
+        );
+        let response = self.client.generate(&prompt).await?;
+        on_progress(LogAnalysisProgress::new(LogAnalysisStage::Done, 1, 1));
         Ok(response)
     }
 
+    async fn summarize_chunk(
+        &self,
+        task: &str,
+        chunk: &str,
+        part: usize,
+        parts: usize,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let prompt = PromptTemplates::new().render(
+            PromptKind::LogChunkSummary,
+            &[
+                ("task", task),
+                ("part", &part.to_string()),
+                ("parts", &parts.to_string()),
+                ("logs", chunk),
+            ],
+        );
+        self.client.generate(&prompt).await
+    }
+
     /// Build analysis prompt based on type
     fn build_prompt(&self, logs: &str, analysis_type: AnalysisType) -> String {
-        let instruction = match analysis_type {
-            AnalysisType::ErrorDetection => {
-                "Analyze these logs and identify all errors, exceptions, and failures. \
-                 For each issue found, explain what went wrong and suggest potential fixes."
-            }
-            AnalysisType::PatternAnalysis => {
-                "Analyze these logs and identify recurring patterns, common operations, \
-                 and typical behavior. Highlight any unusual deviations from the norm."
-            }
-            AnalysisType::AnomalyDetection => {
-                "Analyze these logs and identify any anomalies, unusual behavior, \
-                 or suspicious activities that deviate from normal operation patterns."
-            }
-            AnalysisType::PerformanceAnalysis => {
-                "Analyze these logs for performance issues. Look for slow operations, \
-                 timeouts, resource exhaustion, or bottlenecks. Suggest optimizations."
-            }
-            AnalysisType::SecurityAnalysis => {
-                "Analyze these logs for potential security concerns. Look for failed \
-                 authentication attempts, suspicious access patterns, or potential attacks."
-            }
-        };
-
-        format!(
-            "You are a log analysis assistant. Your task is to analyze service logs and provide insights.\n\n\
-             IMPORTANT: This is a READ-ONLY analysis. Do not suggest running commands or making changes to services.\n\n\
-             {}\n\n\
-             LOGS:\n```\n{}\n```\n\n\
-             Provide a concise analysis with:\n\
-             1. Summary of findings\n\
-             2. Key issues or patterns identified\n\
-             3. Recommendations (informational only)",
-            instruction,
-            logs
+        PromptTemplates::new().render(
+            PromptKind::LogAnalysis,
+            &[("task", analysis_task(analysis_type)), ("logs", logs)],
         )
     }
 
     /// Check if LLM analysis is available
     pub async fn is_available(&self) -> bool {
-        self.client.is_available().await
+        self.client.can_generate().await
     }
 
     /// Explain what a process/service does in natural language
@@ -148,10 +272,8 @@ impl LogAnalyzer {
             })
             .unwrap_or_default();
 
-        let prompt = format!(
-            "Was macht der Prozess '{}{}'? Antworte auf Deutsch in 1-2 Sätzen. Nur Fakten, keine Einleitung.",
-            process_name, path_hint
-        );
+        let process = format!("{}{}", process_name, path_hint);
+        let prompt = PromptTemplates::new().render(PromptKind::ProcessExplanation, &[("process", &process)]);
 
         self.client.generate_fast(&prompt).await
     }
@@ -160,30 +282,343 @@ impl LogAnalyzer {
     pub async fn generate_recommendations(
         &self,
         services_json: &str,
-    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        let prompt = format!(
-            "You are a system optimization assistant. Analyze the following list of running services and provide recommendations for optimization.\n\n\
-             Services (JSON):\n```json\n{}\n```\n\n\
-             Focus on identifying:\n\
-             1. Services that may be consuming unnecessary resources\n\
-             2. Auto-start services that might not be needed\n\
-             3. Duplicate or redundant services\n\
-             4. Services that could be safely disabled\n\n\
-             IMPORTANT: Respond ONLY with valid JSON array. Each recommendation must be an object with these exact fields:\n\
-             - service_id: string (the service ID)\n\
-             - service_name: string (the service name)\n\
-             - recommendation_type: one of \"stop_service\", \"disable_autostart\", \"reduce_resources\", \"performance_impact\", \"info\"\n\
-             - title: string (short title, 5-10 words)\n\
-             - description: string (explanation in German, 1-2 sentences)\n\
-             - action: string or null (suggested action if applicable)\n\n\
-             Provide 3-5 recommendations. Be conservative - only suggest stopping services that are truly optional.\n\n\
-             Response format example:\n\
-             [\n\
-               {{\"service_id\": \"123\", \"service_name\": \"ExampleService\", \"recommendation_type\": \"disable_autostart\", \"title\": \"Autostart nicht nötig\", \"description\": \"Dieser Dienst wird selten verwendet.\", \"action\": \"Autostart deaktivieren\"}}\n\
-             ]",
-            services_json
+        host_context: &str,
+    ) -> Result<Vec<ServiceRecommendation>, Box<dyn std::error::Error + Send + Sync>> {
+        let prompt = PromptTemplates::new().render(
+            PromptKind::Recommendations,
+            &[("host", host_context), ("services", services_json)],
         );
 
-        self.client.generate(&prompt).await
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "recommendations": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "service_id": { "type": "string" },
+                            "service_name": { "type": "string" },
+                            "recommendation_type": {
+                                "type": "string",
+                                "enum": ["stop_service", "disable_autostart", "reduce_resources", "security_concern", "performance_impact", "info"]
+                            },
+                            "title": { "type": "string" },
+                            "description": { "type": "string" },
+                            "action": { "type": ["string", "null"] }
+                        },
+                        "required": ["service_id", "service_name", "recommendation_type", "title", "description", "action"]
+                    }
+                }
+            },
+            "required": ["recommendations"]
+        });
+
+        #[derive(Deserialize)]
+        struct Recommendations {
+            recommendations: Vec<ServiceRecommendation>,
+        }
+
+        let response = self.client.generate_json(&prompt, &schema).await?;
+        Ok(parse_json::<Recommendations>(&response)?.recommendations)
+    }
+
+    /// Security risks of the given services with recommendations
+    pub async fn analyze_security(
+        &self,
+        services_json: &str,
+        host_context: &str,
+    ) -> Result<SecurityAnalysis, Box<dyn std::error::Error + Send + Sync>> {
+        let prompt = PromptTemplates::new().render(
+            PromptKind::SecurityAnalysis,
+            &[("host", host_context), ("services", services_json)],
+        );
+
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "summary": { "type": "string" },
+                "risks": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "service_name": { "type": ["string", "null"] },
+                            "severity": { "type": "string", "enum": ["critical", "high", "medium", "low", "info"] },
+                            "title": { "type": "string" },
+                            "recommendation": { "type": "string" }
+                        },
+                        "required": ["service_name", "severity", "title", "recommendation"]
+                    }
+                }
+            },
+            "required": ["summary", "risks"]
+        });
+
+        let response = self.client.generate_json(&prompt, &schema).await?;
+        parse_json(&response)
+    }
+
+    /// Plain-language remediation plan for the findings of `scan`. Each step
+    /// names the findings it fixes; steps are ordered by their most severe
+    /// finding, keeping the model's order within a severity.
+    pub async fn remediation_plan(
+        &self,
+        scan: &SecurityScanResult,
+        host_context: &str,
+    ) -> Result<RemediationPlan, Box<dyn std::error::Error + Send + Sync>> {
+        if scan.issues.is_empty() {
+            return Ok(RemediationPlan {
+                summary: "Der Scan hat keine offenen Sicherheitsprobleme gefunden.".to_string(),
+                steps: Vec::new(),
+                unaddressed_finding_ids: Vec::new(),
+            });
+        }
+
+        let mut issues: Vec<&SecurityIssue> = scan.issues.iter().collect();
+        issues.sort_by_key(|i| severity_rank(&i.severity));
+        issues.truncate(MAX_PLAN_FINDINGS);
+
+        // Findings are referenced as F1, F2, ...: IDs can contain paths and
+        // addresses, which are sanitized and would be hard to repeat exactly
+        let refs: Vec<String> = (1..=issues.len()).map(|n| format!("F{}", n)).collect();
+        let findings: Vec<serde_json::Value> = issues
+            .iter()
+            .zip(&refs)
+            .map(|(i, reference)| {
+                serde_json::json!({
+                    "id": reference,
+                    "severity": i.severity,
+                    "category": i.category,
+                    "title": i.title,
+                    "description": i.description,
+                    "recommendation": i.recommendation,
+                    "service": i.service_name,
+                    "port": i.port,
+                    "details": i.details.as_deref().map(|d| d.chars().take(FINDING_DETAILS_CHARS).collect::<String>()),
+                })
+            })
+            .collect();
+        let findings = self.sanitize_logs(&serde_json::to_string_pretty(&findings)?);
+        let prompt = PromptTemplates::new().render(
+            PromptKind::RemediationPlan,
+            &[("host", host_context), ("findings", &findings)],
+        );
+
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "summary": { "type": "string" },
+                "steps": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "title": { "type": "string" },
+                            "text": { "type": "string" },
+                            "finding_ids": { "type": "array", "items": { "type": "string", "enum": refs } }
+                        },
+                        "required": ["title", "text", "finding_ids"]
+                    }
+                }
+            },
+            "required": ["summary", "steps"]
+        });
+
+        #[derive(Deserialize)]
+        struct PlanResponse {
+            summary: String,
+            steps: Vec<StepResponse>,
+        }
+        #[derive(Deserialize)]
+        struct StepResponse {
+            title: String,
+            text: String,
+            #[serde(default)]
+            finding_ids: Vec<String>,
+        }
+
+        let response = self.client.generate_json(&prompt, &schema).await?;
+        let response: PlanResponse = parse_json(&response)?;
+
+        // Older Ollama versions ignore the schema, so references are checked again
+        let mut steps: Vec<RemediationStep> = response
+            .steps
+            .into_iter()
+            .filter(|s| !s.text.trim().is_empty())
+            .map(|s| {
+                let mut finding_ids: Vec<String> = Vec::new();
+                for reference in s.finding_ids {
+                    let Some(index) = refs.iter().position(|r| r.eq_ignore_ascii_case(reference.trim())) else {
+                        continue;
+                    };
+                    if !finding_ids.contains(&issues[index].id) {
+                        finding_ids.push(issues[index].id.clone());
+                    }
+                }
+                let severity = issues
+                    .iter()
+                    .filter(|i| finding_ids.contains(&i.id))
+                    .map(|i| i.severity.clone())
+                    .min_by_key(severity_rank);
+                RemediationStep {
+                    priority: 0,
+                    title: s.title.trim().to_string(),
+                    text: s.text.trim().to_string(),
+                    finding_ids,
+                    severity,
+                }
+            })
+            .collect();
+        steps.sort_by_key(|s| s.severity.as_ref().map_or(usize::MAX, severity_rank));
+        for (index, step) in steps.iter_mut().enumerate() {
+            step.priority = index + 1;
+        }
+
+        let unaddressed_finding_ids = scan
+            .issues
+            .iter()
+            .filter(|i| !steps.iter().any(|s| s.finding_ids.contains(&i.id)))
+            .map(|i| i.id.clone())
+            .collect();
+
+        Ok(RemediationPlan {
+            summary: response.summary.trim().to_string(),
+            steps,
+            unaddressed_finding_ids,
+        })
+    }
+}
+
+/// 0 for critical findings, 4 for informational ones
+fn severity_rank(severity: &SecuritySeverity) -> usize {
+    match severity {
+        SecuritySeverity::Critical => 0,
+        SecuritySeverity::High => 1,
+        SecuritySeverity::Medium => 2,
+        SecuritySeverity::Low => 3,
+        SecuritySeverity::Info => 4,
+    }
+}
+
+/// `text` with passwords, keys, tokens, email and IP addresses masked
+pub(crate) fn sanitize(text: &str) -> String {
+    let patterns = vec![
+        (r"password\s*[=:]\s*\S+", "password=***"),
+        (r"api[_-]?key\s*[=:]\s*\S+", "api_key=***"),
+        (r"token\s*[=:]\s*\S+", "token=***"),
+        (r"secret\s*[=:]\s*\S+", "secret=***"),
+        (r"bearer\s+\S+", "bearer ***"),
+        (r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b", "email@***"),
+        (r"\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}\b", "x.x.x.x"),
+    ];
+
+    let mut sanitized = text.to_string();
+    for (pattern, replacement) in patterns {
+        if let Ok(re) = Regex::new(pattern) {
+            sanitized = re.replace_all(&sanitized, replacement).to_string();
+        }
+    }
+    sanitized
+}
+
+fn analysis_task(analysis_type: AnalysisType) -> &'static str {
+    match analysis_type {
+        AnalysisType::ErrorDetection => {
+            "Analyze these logs and identify all errors, exceptions, and failures. \
+             For each issue found, explain what went wrong and suggest potential fixes."
+        }
+        AnalysisType::PatternAnalysis => {
+            "Analyze these logs and identify recurring patterns, common operations, \
+             and typical behavior. Highlight any unusual deviations from the norm."
+        }
+        AnalysisType::AnomalyDetection => {
+            "Analyze these logs and identify any anomalies, unusual behavior, \
+             or suspicious activities that deviate from normal operation patterns."
+        }
+        AnalysisType::PerformanceAnalysis => {
+            "Analyze these logs for performance issues. Look for slow operations, \
+             timeouts, resource exhaustion, or bottlenecks. Suggest optimizations."
+        }
+        AnalysisType::SecurityAnalysis => {
+            "Analyze these logs for potential security concerns. Look for failed \
+             authentication attempts, suspicious access patterns, or potential attacks."
+        }
+    }
+}
+
+/// Lines that differ only in numbers (timestamps, PIDs, counters) are kept
+/// once, at their first occurrence, with the number of repetitions
+fn collapse_repeated_lines(logs: &str) -> String {
+    let digits = Regex::new(r"\d+").expect("valid regex");
+    let mut order: Vec<(String, &str)> = Vec::new();
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for line in logs.lines().filter(|l| !l.trim().is_empty()) {
+        let key = digits.replace_all(line, "#").to_string();
+        let count = counts.entry(key.clone()).or_insert(0);
+        if *count == 0 {
+            order.push((key, line));
+        }
+        *count += 1;
+    }
+    order
+        .iter()
+        .map(|(key, line)| match counts[key] {
+            1 => line.to_string(),
+            n => format!("{} [repeated {}x]", line, n),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Split at line boundaries into chunks of at most `max_chars`; longer
+/// lines are cut
+fn split_chunks(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for line in text.lines() {
+        let line = match line.char_indices().nth(max_chars - 1) {
+            Some((index, _)) => &line[..index],
+            None => line,
+        };
+        if !current.is_empty() && current.len() + line.len() + 1 > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
     }
+    chunks
+}
+
+/// The `max` chunks with the most error and warning lines, in log order
+fn most_relevant_chunks(chunks: Vec<String>, max: usize) -> Vec<String> {
+    let relevant = Regex::new(r"(?i)error|fail|exception|fatal|panic|crit|denied|refused|timeout|timed out|warn")
+        .expect("valid regex");
+    let mut scored: Vec<(usize, usize)> = chunks
+        .iter()
+        .enumerate()
+        .map(|(index, chunk)| (index, chunk.lines().filter(|l| relevant.is_match(l)).count()))
+        .collect();
+    scored.sort_by_key(|(index, score)| (Reverse(*score), *index));
+    let mut keep: Vec<usize> = scored.into_iter().take(max).map(|(index, _)| index).collect();
+    keep.sort_unstable();
+    let mut chunks: Vec<Option<String>> = chunks.into_iter().map(Some).collect();
+    keep.into_iter().filter_map(|index| chunks[index].take()).collect()
+}
+
+/// Models sometimes wrap the JSON in a code fence even in JSON mode
+pub(crate) fn parse_json<T: serde::de::DeserializeOwned>(response: &str) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
+    let trimmed = response.trim();
+    let json = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .unwrap_or(trimmed);
+    serde_json::from_str(json)
+        .map_err(|e| format!("Die Antwort des Modells hat nicht das erwartete JSON-Format: {}", e).into())
 }
@@ -0,0 +1,216 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use super::client::OllamaClient;
+use super::language::ResponseLanguage;
+use super::memory::cosine_similarity;
+use crate::models::audit::{AuditEntry, AuditQuery, EventType};
+use crate::services::security::AuditLogger;
+
+/// Matching entries considered at most, newest first
+const MAX_CANDIDATES: usize = 300;
+/// Entries given to the model as context
+const MAX_CONTEXT_ENTRIES: usize = 40;
+/// Characters of an entry's details shown to the model
+const DETAILS_CHARS: usize = 300;
+
+/// Answer to a question about the audit log with the entries it is based on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditAnswer {
+    pub answer: String,
+    /// Filter derived from the question
+    pub query: AuditQuery,
+    /// Entries given to the model, numbered from 1 as cited in the answer
+    pub entries: Vec<AuditEntry>,
+}
+
+/// What the model extracts from the question, before dates are parsed
+#[derive(Deserialize)]
+struct QueryResponse {
+    since: Option<String>,
+    until: Option<String>,
+    service: Option<String>,
+    #[serde(default)]
+    event_types: Vec<EventType>,
+    success: Option<bool>,
+    #[serde(default)]
+    keywords: Vec<String>,
+}
+
+/// Answers questions like "what stopped postgres last Tuesday?" from the
+/// audit log: the model turns the question into an `AuditQuery`, matching
+/// entries are ranked by embedding similarity, and the answer is generated
+/// from the best ones only.
+pub struct AuditAssistant {
+    client: OllamaClient,
+    logger: AuditLogger,
+}
+
+impl AuditAssistant {
+    pub fn new(client: OllamaClient) -> Self {
+        Self { client, logger: AuditLogger::new() }
+    }
+
+    pub async fn answer(&self, question: &str) -> Result<AuditAnswer, Box<dyn std::error::Error + Send + Sync>> {
+        let query = self.extract_query(question).await?;
+
+        // A wrong keyword guess shouldn't hide entries the other fields found
+        let mut candidates = self.search(&query)?;
+        if candidates.is_empty() && !query.keywords.is_empty() {
+            candidates = self.search(&AuditQuery { keywords: Vec::new(), ..query.clone() })?;
+        }
+        let entries = self.rank(question, candidates).await;
+
+        let context = if entries.is_empty() {
+            "(no matching entries)".to_string()
+        } else {
+            entries
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| format!("[{}] {}", i + 1, describe(entry)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let prompt = format!(
+            "You answer questions about the audit log of a service manager. The log records who started, stopped or \
+             changed services, killed processes, scanned ports and so on.\n\n\
+             Current local time: {}\n\n\
+             Audit entries matching the question, newest first:\n{}\n\n\
+             Question: {}\n\n\
+             Answer only from these entries and cite them by number, e.g. [2]. Name the time, the user and whether the \
+             action succeeded. If the entries don't answer the question, say so. {}",
+            Local::now().format("%A, %Y-%m-%d %H:%M"),
+            context,
+            question.trim(),
+            ResponseLanguage::instruction()
+        );
+        let answer = self.client.generate(&prompt).await?;
+
+        Ok(AuditAnswer { answer, query, entries })
+    }
+
+    async fn extract_query(&self, question: &str) -> Result<AuditQuery, Box<dyn std::error::Error + Send + Sync>> {
+        let prompt = format!(
+            "Turn this question about an audit log into a search filter.\n\n\
+             Current local time: {}\n\
+             Question: {}\n\n\
+             Fields:\n\
+             - since, until: local date (YYYY-MM-DD) or date and time (YYYY-MM-DDTHH:MM) limiting the period, until is \
+             inclusive for dates; null if the question names no period. Resolve relative dates like \"last Tuesday\" or \
+             \"yesterday\" against the current time.\n\
+             - service: name of the service, container or process asked about, or null\n\
+             - event_types: the kinds of events asked about, empty for all. service_stop also covers kills of services.\n\
+             - success: false when asking about failures, true when asking about successful actions, otherwise null\n\
+             - keywords: further words that must occur in matching entries, usually empty",
+            Local::now().format("%A, %Y-%m-%d %H:%M"),
+            question.trim()
+        );
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "since": { "type": ["string", "null"] },
+                "until": { "type": ["string", "null"] },
+                "service": { "type": ["string", "null"] },
+                "event_types": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "enum": [
+                            "service_start", "service_stop", "service_restart", "process_kill", "config_change",
+                            "privilege_escalation", "llm_analysis", "port_scan", "cleanup", "alert"
+                        ]
+                    }
+                },
+                "success": { "type": ["boolean", "null"] },
+                "keywords": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["since", "until", "service", "event_types", "success", "keywords"]
+        });
+
+        let response = self.client.generate_json(&prompt, &schema).await?;
+        let response: QueryResponse = super::analyzer::parse_json(&response)?;
+        Ok(AuditQuery {
+            since: response.since.as_deref().and_then(|s| parse_local_time(s, false)),
+            until: response.until.as_deref().and_then(|s| parse_local_time(s, true)),
+            service: response.service.filter(|s| !s.trim().is_empty()),
+            event_types: response.event_types,
+            success: response.success,
+            keywords: response.keywords,
+        })
+    }
+
+    fn search(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, Box<dyn std::error::Error + Send + Sync>> {
+        self.logger
+            .query(query, MAX_CANDIDATES)
+            .map_err(|e| format!("Das Audit-Log konnte nicht gelesen werden: {}", e).into())
+    }
+
+    /// The entries most similar to the question, newest first. Without an
+    /// embedding model the newest entries are used.
+    async fn rank(&self, question: &str, mut candidates: Vec<AuditEntry>) -> Vec<AuditEntry> {
+        if candidates.len() <= MAX_CONTEXT_ENTRIES {
+            return candidates;
+        }
+
+        let mut texts = vec![question.to_string()];
+        texts.extend(candidates.iter().map(describe));
+        let Ok(embeddings) = self.client.embed_batch(&texts).await else {
+            candidates.truncate(MAX_CONTEXT_ENTRIES);
+            return candidates;
+        };
+
+        let mut scored: Vec<(f32, AuditEntry)> = candidates
+            .into_iter()
+            .zip(&embeddings[1..])
+            .map(|(entry, embedding)| (cosine_similarity(&embeddings[0], embedding).unwrap_or(0.0), entry))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.truncate(MAX_CONTEXT_ENTRIES);
+        let mut entries: Vec<AuditEntry> = scored.into_iter().map(|(_, entry)| entry).collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+        entries
+    }
+}
+
+/// One line per entry, in local time
+fn describe(entry: &AuditEntry) -> String {
+    let mut line = format!(
+        "{} | {} | user {} | {}",
+        entry.timestamp.with_timezone(&Local).format("%A, %Y-%m-%d %H:%M:%S"),
+        serde_json::to_value(entry.event_type)
+            .ok()
+            .and_then(|v| v.as_str().map(String::from))
+            .unwrap_or_default(),
+        entry.user,
+        entry.operation
+    );
+    if let Some(service) = &entry.service_id {
+        line.push_str(&format!(" | service {}", service));
+    }
+    line.push_str(if entry.success { " | succeeded" } else { " | failed" });
+    if let Some(error) = &entry.error_message {
+        line.push_str(&format!(": {}", error));
+    }
+    if entry.details.as_object().is_some_and(|d| !d.is_empty()) {
+        let details: String = entry.details.to_string().chars().take(DETAILS_CHARS).collect();
+        line.push_str(&format!(" | {}", details));
+    }
+    line
+}
+
+/// `YYYY-MM-DD` (the whole day when `end_of_period`), local date and time,
+/// or RFC 3339
+fn parse_local_time(value: &str, end_of_period: bool) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    let local = if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let date = if end_of_period { date.succ_opt()? } else { date };
+        date.and_hms_opt(0, 0, 0)?
+    } else {
+        ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())?
+    };
+    Local.from_local_datetime(&local).earliest().map(|t| t.with_timezone(&Utc))
+}
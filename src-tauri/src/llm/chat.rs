@@ -0,0 +1,268 @@
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use super::client::{ChatMessage, ChatRole, OllamaClient};
+use super::language::ResponseLanguage;
+use crate::services::history::HistoryStore;
+use crate::services::manager::ServiceManager;
+use crate::services::monitor::current_ports;
+use crate::services::storage::Storage;
+use crate::services::system_stats::HostInfo;
+use crate::services::user_scope::UserScope;
+
+const DEFAULT_TITLE: &str = "Neue Unterhaltung";
+const TITLE_CHARS: usize = 60;
+/// Messages of the history sent with each question; older ones drop out of
+/// the model's view but stay in the session
+const HISTORY_MESSAGES: usize = 20;
+/// Limits for the context block, so it doesn't crowd out the conversation
+const MAX_CONTEXT_SERVICES: usize = 60;
+const MAX_CONTEXT_PORTS: usize = 80;
+const MAX_CONTEXT_ISSUES: usize = 30;
+
+/// A message of a stored conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSessionMessage {
+    pub role: ChatRole,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A conversation with the assistant, stored as one JSON file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSession {
+    pub id: String,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub messages: Vec<ChatSessionMessage>,
+}
+
+/// Session list entry without the messages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSessionSummary {
+    pub id: String,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub message_count: usize,
+}
+
+impl ChatSession {
+    /// Ask `question` and append it together with the answer. The current
+    /// services, listening ports and the latest security scan are sent as a
+    /// fresh system message with every question, so follow-ups like "and
+    /// which of those can I disable?" refer to the live state; the context
+    /// itself isn't stored.
+    pub async fn ask(
+        &mut self,
+        client: &OllamaClient,
+        question: &str,
+    ) -> Result<ChatSessionMessage, Box<dyn std::error::Error + Send + Sync>> {
+        let mut messages = vec![ChatMessage {
+            role: ChatRole::System,
+            content: system_prompt().await,
+        }];
+        let skip = self.messages.len().saturating_sub(HISTORY_MESSAGES);
+        messages.extend(self.messages.iter().skip(skip).map(|m| ChatMessage {
+            role: m.role,
+            content: m.content.clone(),
+        }));
+        messages.push(ChatMessage {
+            role: ChatRole::User,
+            content: question.to_string(),
+        });
+
+        let answer = client.chat(&messages).await?;
+
+        if self.messages.is_empty() && self.title == DEFAULT_TITLE {
+            self.title = title_from(question);
+        }
+        self.messages.push(ChatSessionMessage {
+            role: ChatRole::User,
+            content: question.to_string(),
+            created_at: Utc::now(),
+        });
+        let reply = ChatSessionMessage {
+            role: ChatRole::Assistant,
+            content: answer.trim().to_string(),
+            created_at: Utc::now(),
+        };
+        self.messages.push(reply.clone());
+        self.updated_at = reply.created_at;
+        Ok(reply)
+    }
+
+    fn summary(&self) -> ChatSessionSummary {
+        ChatSessionSummary {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            message_count: self.messages.len(),
+        }
+    }
+}
+
+/// Chat sessions in the data directory, one file per session
+pub struct ChatStore {
+    dir: PathBuf,
+}
+
+impl ChatStore {
+    pub fn new() -> Self {
+        Self {
+            dir: Storage::data_dir().join("chats"),
+        }
+    }
+
+    pub fn create(&self, title: Option<&str>) -> Result<ChatSession, Box<dyn std::error::Error + Send + Sync>> {
+        let now = Utc::now();
+        let session = ChatSession {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: title
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(title_from)
+                .unwrap_or_else(|| DEFAULT_TITLE.to_string()),
+            created_at: now,
+            updated_at: now,
+            messages: Vec::new(),
+        };
+        self.save(&session)?;
+        Ok(session)
+    }
+
+    pub fn get(&self, id: &str) -> Option<ChatSession> {
+        let path = self.path(id)?;
+        std::fs::read(path).ok().and_then(|data| serde_json::from_slice(&data).ok())
+    }
+
+    pub fn save(&self, session: &ChatSession) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let path = self.path(&session.id).ok_or("Ungültige Sitzungs-ID")?;
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(path, serde_json::to_vec_pretty(session)?)?;
+        Ok(())
+    }
+
+    /// Returns false if the session doesn't exist
+    pub fn delete(&self, id: &str) -> bool {
+        self.path(id).is_some_and(|path| std::fs::remove_file(path).is_ok())
+    }
+
+    /// All sessions, most recently used first
+    pub fn list(&self) -> Vec<ChatSessionSummary> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut sessions: Vec<ChatSessionSummary> = entries
+            .flatten()
+            .filter_map(|entry| std::fs::read(entry.path()).ok())
+            .filter_map(|data| serde_json::from_slice::<ChatSession>(&data).ok())
+            .map(|session| session.summary())
+            .collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.updated_at));
+        sessions
+    }
+
+    /// `None` for anything but a UUID, so IDs can't point outside the directory
+    fn path(&self, id: &str) -> Option<PathBuf> {
+        let id = uuid::Uuid::parse_str(id).ok()?;
+        Some(self.dir.join(format!("{}.json", id)))
+    }
+}
+
+impl Default for ChatStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn title_from(text: &str) -> String {
+    let line = text.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or(DEFAULT_TITLE);
+    if line.chars().count() > TITLE_CHARS {
+        format!("{}…", line.chars().take(TITLE_CHARS).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+async fn system_prompt() -> String {
+    format!(
+        r#"Du bist ein Assistent für die Verwaltung von Diensten und Ports auf diesem Rechner. Antworte knapp und konkret. {}
+Beziehe dich auf den folgenden aktuellen Zustand. Wenn die Frage sich auf frühere Antworten bezieht ("davon", "diese"), nutze den Gesprächsverlauf.
+Empfiehl das Deaktivieren von Diensten nur, wenn sie nicht für das Betriebssystem benötigt werden, und nenne mögliche Folgen.
+
+Host: {}
+
+{}"#,
+        ResponseLanguage::instruction(),
+        HostInfo::current().prompt_context(),
+        context().await
+    )
+}
+
+/// Services, listening ports and findings of the latest stored scan
+async fn context() -> String {
+    let mut services = ServiceManager::new().discover_all().await;
+    UserScope::retain_services(&mut services);
+    services.sort_by_cached_key(|s| s.name.to_lowercase());
+
+    let mut ports = tokio::task::spawn_blocking(current_ports).await.unwrap_or_default();
+    UserScope::retain_ports(&mut ports);
+    ports.sort_by_key(|p| p.port);
+
+    let mut sections = Vec::new();
+
+    let mut lines = vec![format!("Dienste ({}):", services.len())];
+    lines.extend(services.iter().take(MAX_CONTEXT_SERVICES).map(|s| {
+        let mut parts = vec![format!("{:?}", s.status).to_lowercase()];
+        if !s.ports.is_empty() {
+            parts.push(format!("Ports {}", s.ports.iter().map(u16::to_string).collect::<Vec<_>>().join(", ")));
+        }
+        parts.extend(s.cpu_usage.map(|cpu| format!("CPU {:.1} %", cpu)));
+        parts.extend(s.memory_bytes.map(|bytes| format!("{} MB", bytes / 1024 / 1024)));
+        if s.auto_start {
+            parts.push("Autostart".to_string());
+        }
+        format!("- {} ({})", s.name, parts.join(", "))
+    }));
+    if services.len() > MAX_CONTEXT_SERVICES {
+        lines.push(format!("… und {} weitere", services.len() - MAX_CONTEXT_SERVICES));
+    }
+    sections.push(lines.join("\n"));
+
+    let mut lines = vec![format!("Lauschende Ports ({}):", ports.len())];
+    lines.extend(ports.iter().take(MAX_CONTEXT_PORTS).map(|p| {
+        format!(
+            "- {}:{}/{:?} {}{}",
+            p.bind_address,
+            p.port,
+            p.protocol,
+            p.process_name.as_deref().unwrap_or("unbekannt"),
+            p.pid.map(|pid| format!(" (PID {})", pid)).unwrap_or_default()
+        )
+    }));
+    if ports.len() > MAX_CONTEXT_PORTS {
+        lines.push(format!("… und {} weitere", ports.len() - MAX_CONTEXT_PORTS));
+    }
+    sections.push(lines.join("\n"));
+
+    match HistoryStore::security_scan(None).await {
+        Ok(Some((_, scan))) => {
+            let mut lines = vec![format!("Befunde des letzten Sicherheitsscans ({}):", scan.issues.len())];
+            lines.extend(scan.issues.iter().take(MAX_CONTEXT_ISSUES).map(|i| {
+                format!(
+                    "- [{:?}] {}{}",
+                    i.severity,
+                    i.title,
+                    i.service_name.as_ref().map(|s| format!(" ({})", s)).unwrap_or_default()
+                )
+            }));
+            sections.push(lines.join("\n"));
+        }
+        _ => sections.push("Es liegt noch kein Sicherheitsscan vor.".to_string()),
+    }
+
+    sections.join("\n\n")
+}
@@ -1,16 +1,81 @@
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use super::cloud::CloudLlm;
+use super::queue::{LlmQueue, Priority};
+use super::routing::{self, LlmTask};
+use crate::models::config::{OllamaConfig, TaskTimeouts};
 
 const DEFAULT_ENDPOINT: &str = "http://localhost:11434";
 const DEFAULT_MODEL: &str = "mistral:7b-instruct";
+const DEFAULT_FAST_MODEL: &str = "llama3.2:1b";
+const DEFAULT_EMBEDDING_MODEL: &str = "nomic-embed-text";
+/// Token limit when a larger model stands in for a missing fast model
+const FALLBACK_NUM_PREDICT: u32 = 256;
+/// Tokens generated by `probe_generation`, enough for a speed estimate
+const PROBE_NUM_PREDICT: u32 = 8;
+/// Models tried per generation before giving up
+const MAX_MODEL_ATTEMPTS: usize = 3;
+/// How long the installed models are cached for model selection
+const MODEL_LIST_TTL: Duration = Duration::from_secs(60);
 const DEFAULT_TIMEOUT: u64 = 30;
+/// Texts per `/api/embed` request
+const EMBED_BATCH_SIZE: usize = 64;
+/// Unreachable LAN hosts should fail over quickly instead of waiting for the request timeout
+const CONNECT_TIMEOUT: u64 = 3;
+const HEALTH_CHECK_TIMEOUT: u64 = 3;
 
 #[derive(Clone)]
 pub struct OllamaClient {
     client: Client,
-    endpoint: String,
+    /// Configured endpoints in order of preference
+    endpoints: Vec<String>,
+    /// Index of the endpoint that answered last, shared between clones
+    active: Arc<AtomicUsize>,
     model: String,
+    /// Small model for quick explanations, see `generate_fast`
+    fast_model: String,
+    /// Model used by `embed` for similarity search over past analyses
+    embedding_model: String,
+    /// Request timeouts per task, see `timeout`
+    task_timeouts: TaskTimeouts,
+    /// Fall back to other installed models, see `routing::model_chain`
+    auto_select_models: bool,
+    /// Models installed on the active endpoint and when they were listed,
+    /// shared between clones
+    installed: Arc<Mutex<Option<InstalledModels>>>,
+    /// Queue priority for generations issued by this client
+    priority: Priority,
+}
+
+/// When the models were listed, and the list
+type InstalledModels = (Instant, Vec<ModelInfo>);
+
+/// Every endpoint failed; `timed_out` when all of them ran into the timeout
+#[derive(Debug)]
+struct EndpointsFailed {
+    errors: Vec<String>,
+    timed_out: bool,
+}
+
+impl std::fmt::Display for EndpointsFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Kein Ollama-Endpunkt erreichbar ({})", self.errors.join("; "))
+    }
+}
+
+impl std::error::Error for EndpointsFailed {}
+
+/// Result of probing a single endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointHealth {
+    pub endpoint: String,
+    pub healthy: bool,
+    pub active: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -18,6 +83,17 @@ struct GenerateRequest {
     model: String,
     prompt: String,
     stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    options: Option<GenerateOptions>,
+    /// `"json"` or a JSON schema the response must follow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
+}
+
+#[derive(Serialize)]
+struct GenerateOptions {
+    /// Maximum number of tokens to generate
+    num_predict: u32,
 }
 
 #[derive(Deserialize)]
@@ -25,15 +101,176 @@ struct GenerateResponse {
     response: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    System,
+    User,
+    Assistant,
+}
+
+/// Message of a conversation, as sent to and returned by `/api/chat`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+/// Function call the model asked for (`message.tool_calls` of `/api/chat`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub function: ToolFunctionCall,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolFunctionCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: &'a [ChatMessage],
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<&'a [serde_json::Value]>,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    message: ChatResponseMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatResponseMessage {
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    tool_calls: Vec<ToolCall>,
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest {
+    model: String,
+    prompt: String,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    embedding: Vec<f32>,
+}
+
+/// `/api/embed` takes several inputs at once (Ollama 0.3 and later)
+#[derive(Serialize)]
+struct BatchEmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct BatchEmbeddingResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
 #[derive(Deserialize)]
 struct TagsResponse {
     models: Vec<ModelInfo>,
 }
 
 #[derive(Deserialize)]
+struct VersionResponse {
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct RunningModelsResponse {
+    models: Vec<RunningModel>,
+}
+
+/// A model loaded into memory (`/api/ps`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningModel {
+    pub name: String,
+    /// Memory the loaded model occupies
+    pub size: u64,
+    /// Part of `size` in GPU memory; the rest runs on the CPU
+    #[serde(default)]
+    pub size_vram: u64,
+    /// When Ollama unloads the model if it isn't used again
+    pub expires_at: Option<String>,
+}
+
+/// Timings of a generation, in nanoseconds as reported by Ollama
+#[derive(Deserialize)]
+struct ProbeResponse {
+    #[serde(default)]
+    load_duration: u64,
+    #[serde(default)]
+    prompt_eval_duration: u64,
+    #[serde(default)]
+    eval_count: u64,
+    #[serde(default)]
+    eval_duration: u64,
+}
+
+/// Latency of a tiny generation, see `probe_generation`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationProbe {
+    pub model: String,
+    /// Wall-clock time of the request, including waiting for the model to load
+    pub total_ms: u64,
+    /// Time Ollama spent loading the model into memory
+    pub load_ms: u64,
+    pub prompt_eval_ms: u64,
+    pub tokens_per_second: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct PullRequest<'a> {
+    /// Older Ollama versions read `name`, newer ones `model`
+    name: &'a str,
+    model: &'a str,
+    stream: bool,
+}
+
+#[derive(Deserialize)]
+struct PullLine {
+    #[serde(default)]
+    status: String,
+    total: Option<u64>,
+    completed: Option<u64>,
+    error: Option<String>,
+}
+
+/// Progress of a model download (`/api/pull`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullProgress {
+    pub model: String,
+    /// `pulling manifest`, `pulling <digest>`, `verifying sha256 digest`, `success`, ...
+    pub status: String,
+    /// Bytes of the layer currently downloading
+    pub completed: Option<u64>,
+    pub total: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct ModelInfo {
     pub name: String,
     pub size: u64,
+    #[serde(default)]
+    pub details: ModelDetails,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ModelDetails {
+    /// Architecture, e.g. `llama` or `nomic-bert`
+    #[serde(default)]
+    pub family: String,
+    /// e.g. `7.2B`
+    #[serde(default)]
+    pub parameter_size: String,
 }
 
 impl OllamaClient {
@@ -41,33 +278,99 @@ impl OllamaClient {
         Self::with_config(DEFAULT_ENDPOINT, DEFAULT_MODEL, DEFAULT_TIMEOUT)
     }
 
+    /// Client for the endpoints, models and timeout of the settings
+    pub fn from_config(config: &OllamaConfig) -> Self {
+        let mut endpoints = vec![config.endpoint.clone()];
+        endpoints.extend(config.fallback_endpoints.iter().cloned());
+        let mut client = Self::with_endpoints(&endpoints, &config.model, (config.timeout_seconds as u64).max(1));
+        if !config.fast_model.trim().is_empty() {
+            client.set_fast_model(config.fast_model.trim());
+        }
+        if !config.embedding_model.trim().is_empty() {
+            client.set_embedding_model(config.embedding_model.trim());
+        }
+        client.task_timeouts = config.task_timeouts.clone();
+        client.auto_select_models = config.auto_select_models;
+        client
+    }
+
     pub fn with_config(endpoint: &str, model: &str, timeout_secs: u64) -> Self {
+        Self::with_endpoints(&[endpoint.to_string()], model, timeout_secs)
+    }
+
+    /// Client with failover: `endpoints[0]` is preferred, the rest are tried in order
+    pub fn with_endpoints(endpoints: &[String], model: &str, timeout_secs: u64) -> Self {
         let client = Client::builder()
             .timeout(Duration::from_secs(timeout_secs))
+            .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT))
             .build()
             .expect("Failed to create HTTP client");
 
-        Self {
+        let mut client = Self {
             client,
-            endpoint: endpoint.to_string(),
+            endpoints: Vec::new(),
+            active: Arc::new(AtomicUsize::new(0)),
             model: model.to_string(),
-        }
+            fast_model: DEFAULT_FAST_MODEL.to_string(),
+            embedding_model: DEFAULT_EMBEDDING_MODEL.to_string(),
+            task_timeouts: TaskTimeouts::default(),
+            auto_select_models: true,
+            installed: Arc::new(Mutex::new(None)),
+            priority: Priority::default(),
+        };
+        client.set_endpoints(endpoints);
+        client
     }
 
-    /// Check if Ollama is available
+    /// Check if any endpoint is available. Endpoints are probed in order of
+    /// preference, so this also fails back to the primary once it returns.
     pub async fn is_available(&self) -> bool {
-        self.client
-            .get(format!("{}/api/tags", self.endpoint))
-            .send()
-            .await
-            .is_ok()
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            if self.probe(endpoint).await.is_ok() {
+                self.active.store(index, Ordering::Relaxed);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Whether generations can run: a cloud provider is enabled or an
+    /// Ollama endpoint answers
+    pub async fn can_generate(&self) -> bool {
+        CloudLlm::is_enabled() || self.is_available().await
+    }
+
+    /// Probe every configured endpoint
+    pub async fn check_endpoints(&self) -> Vec<EndpointHealth> {
+        let mut results = Vec::with_capacity(self.endpoints.len());
+        let mut first_healthy = None;
+
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            let started = Instant::now();
+            let result = self.probe(endpoint).await;
+            if result.is_ok() && first_healthy.is_none() {
+                first_healthy = Some(index);
+            }
+            results.push(EndpointHealth {
+                endpoint: endpoint.clone(),
+                healthy: result.is_ok(),
+                active: false,
+                latency_ms: result.is_ok().then(|| started.elapsed().as_millis() as u64),
+                error: result.err(),
+            });
+        }
+
+        if let Some(index) = first_healthy {
+            self.active.store(index, Ordering::Relaxed);
+            results[index].active = true;
+        }
+        results
     }
 
     /// List available models
     pub async fn list_models(&self) -> Result<Vec<ModelInfo>, Box<dyn std::error::Error + Send + Sync>> {
-        let response: TagsResponse = self.client
-            .get(format!("{}/api/tags", self.endpoint))
-            .send()
+        let response: TagsResponse = self
+            .send_with_failover(|endpoint| self.client.get(format!("{}/api/tags", endpoint)))
             .await?
             .json()
             .await?;
@@ -75,52 +378,324 @@ impl OllamaClient {
         Ok(response.models)
     }
 
-    /// Generate a response from the model
-    pub async fn generate(&self, prompt: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    /// Version of the Ollama server (`/api/version`)
+    pub async fn version(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let response: VersionResponse = self
+            .send_with_failover(|endpoint| self.client.get(format!("{}/api/version", endpoint)))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response.version)
+    }
+
+    /// Models currently loaded into memory (`/api/ps`)
+    pub async fn running_models(&self) -> Result<Vec<RunningModel>, Box<dyn std::error::Error + Send + Sync>> {
+        let response: RunningModelsResponse = self
+            .send_with_failover(|endpoint| self.client.get(format!("{}/api/ps", endpoint)))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response.models)
+    }
+
+    /// Generate a few tokens with `model` and measure how long it takes.
+    /// Always goes to Ollama, also when a cloud provider is enabled.
+    pub async fn probe_generation(&self, model: &str) -> Result<GenerationProbe, Box<dyn std::error::Error + Send + Sync>> {
+        let _permit = LlmQueue::global().acquire(self.priority).await?;
+
         let request = GenerateRequest {
-            model: self.model.clone(),
-            prompt: prompt.to_string(),
+            model: model.to_string(),
+            prompt: "Reply with OK.".to_string(),
             stream: false,
+            options: Some(GenerateOptions { num_predict: PROBE_NUM_PREDICT }),
+            format: None,
         };
+        let started = Instant::now();
+        let response: ProbeResponse = self
+            .send_with_failover(|endpoint| {
+                let request = self.client.post(format!("{}/api/generate", endpoint)).json(&request);
+                match self.timeout(LlmTask::Analysis) {
+                    Some(timeout) => request.timeout(timeout),
+                    None => request,
+                }
+            })
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
 
-        let response: GenerateResponse = self.client
-            .post(format!("{}/api/generate", self.endpoint))
-            .json(&request)
-            .send()
+        let millis = |nanos: u64| nanos / 1_000_000;
+        Ok(GenerationProbe {
+            model: model.to_string(),
+            total_ms: started.elapsed().as_millis() as u64,
+            load_ms: millis(response.load_duration),
+            prompt_eval_ms: millis(response.prompt_eval_duration),
+            tokens_per_second: (response.eval_count > 0 && response.eval_duration > 0)
+                .then(|| response.eval_count as f64 / (response.eval_duration as f64 / 1e9)),
+        })
+    }
+
+    /// Same client, scheduling generations with the given queue priority
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Generate a response from the model. This and the other generation
+    /// methods go to the cloud provider instead when one is enabled.
+    pub async fn generate(&self, prompt: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let _permit = LlmQueue::global().acquire(self.priority).await?;
+        if CloudLlm::is_enabled() {
+            return CloudLlm::generate(prompt, None, LlmTask::Analysis, self.timeout(LlmTask::Analysis)).await;
+        }
+
+        let response: GenerateResponse = self
+            .send_with_models(LlmTask::Analysis, |endpoint, model| {
+                let request = GenerateRequest {
+                    model: model.to_string(),
+                    prompt: prompt.to_string(),
+                    stream: false,
+                    options: None,
+                    format: None,
+                };
+                self.client.post(format!("{}/api/generate", endpoint)).json(&request)
+            })
             .await?
+            .error_for_status()?
             .json()
             .await?;
 
         Ok(response.response)
     }
 
-    /// Generate a quick response with a fast model (for process explanations)
-    pub async fn generate_fast(&self, prompt: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        // Use a smaller, faster model for quick explanations
-        let fast_model = "llama3.2:1b";
+    /// Generate a JSON response following `schema`.
+    ///
+    /// Ollama before 0.5 only knows `format: "json"` and rejects schemas with
+    /// HTTP 400; the request is then repeated with plain JSON mode, and the
+    /// prompt has to describe the structure.
+    pub async fn generate_json(
+        &self,
+        prompt: &str,
+        schema: &serde_json::Value,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let _permit = LlmQueue::global().acquire(self.priority).await?;
+        if CloudLlm::is_enabled() {
+            return CloudLlm::generate(prompt, Some(schema), LlmTask::Structured, self.timeout(LlmTask::Structured)).await;
+        }
 
-        let request = GenerateRequest {
-            model: fast_model.to_string(),
-            prompt: prompt.to_string(),
-            stream: false,
+        let send = |format: serde_json::Value| {
+            self.send_with_models(LlmTask::Structured, move |endpoint, model| {
+                let request = GenerateRequest {
+                    model: model.to_string(),
+                    prompt: prompt.to_string(),
+                    stream: false,
+                    options: None,
+                    format: Some(format.clone()),
+                };
+                self.client.post(format!("{}/api/generate", endpoint)).json(&request)
+            })
         };
 
-        // Create a client with shorter timeout for fast responses
-        let fast_client = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()?;
+        let mut response = send(schema.clone()).await?;
+        if response.status() == reqwest::StatusCode::BAD_REQUEST {
+            response = send(serde_json::Value::String("json".to_string())).await?;
+        }
 
-        let response: GenerateResponse = fast_client
-            .post(format!("{}/api/generate", self.endpoint))
-            .json(&request)
-            .send()
+        let response: GenerateResponse = response.error_for_status()?.json().await?;
+        Ok(response.response)
+    }
+
+    /// Generate a quick response for process explanations, with the fast
+    /// model or the smallest installed one.
+    ///
+    /// Other models than the fast model get a reduced token limit.
+    pub async fn generate_fast(&self, prompt: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let _permit = LlmQueue::global().acquire(self.priority).await?;
+        if CloudLlm::is_enabled() {
+            return CloudLlm::generate(prompt, None, LlmTask::Explanation, self.timeout(LlmTask::Explanation)).await;
+        }
+
+        let response: GenerateResponse = self
+            .send_with_models(LlmTask::Explanation, |endpoint, model| {
+                let request = GenerateRequest {
+                    model: model.to_string(),
+                    prompt: prompt.to_string(),
+                    stream: false,
+                    options: (!routing::same_model(model, &self.fast_model)).then_some(GenerateOptions {
+                        num_predict: FALLBACK_NUM_PREDICT,
+                    }),
+                    format: None,
+                };
+                self.client.post(format!("{}/api/generate", endpoint)).json(&request)
+            })
             .await?
+            .error_for_status()?
             .json()
             .await?;
 
         Ok(response.response)
     }
 
+    /// Next assistant message of a conversation (`/api/chat`)
+    pub async fn chat(&self, messages: &[ChatMessage]) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.send_chat(messages, None).await?.content)
+    }
+
+    /// Function calls the model makes with the given tool definitions
+    /// (OpenAI-style `{"type": "function", "function": {...}}`). Needs a
+    /// model with tool support; others answer without calls.
+    pub async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+    ) -> Result<Vec<ToolCall>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.send_chat(messages, Some(tools)).await?.tool_calls)
+    }
+
+    async fn send_chat(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[serde_json::Value]>,
+    ) -> Result<ChatResponseMessage, Box<dyn std::error::Error + Send + Sync>> {
+        let _permit = LlmQueue::global().acquire(self.priority).await?;
+        if CloudLlm::is_enabled() {
+            let (content, tool_calls) =
+                CloudLlm::chat(messages, tools, None, LlmTask::Chat, self.timeout(LlmTask::Chat)).await?;
+            return Ok(ChatResponseMessage { content, tool_calls });
+        }
+
+        let response: ChatResponse = self
+            .send_with_models(LlmTask::Chat, |endpoint, model| {
+                let request = ChatRequest {
+                    model,
+                    messages,
+                    stream: false,
+                    tools,
+                };
+                self.client.post(format!("{}/api/chat", endpoint)).json(&request)
+            })
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(response.message)
+    }
+
+    /// Embedding vector for `text` (`/api/embeddings`).
+    /// Embeddings are cheap, so they don't wait for a generation slot.
+    pub async fn embed(&self, text: &str) -> Result<Vec<f32>, Box<dyn std::error::Error + Send + Sync>> {
+        let request = EmbeddingRequest {
+            model: self.embedding_model.clone(),
+            prompt: text.to_string(),
+        };
+
+        let response = self
+            .send_with_failover(|endpoint| {
+                self.client.post(format!("{}/api/embeddings", endpoint)).json(&request)
+            })
+            .await?
+            .error_for_status()?;
+        let response: EmbeddingResponse = response.json().await?;
+
+        if response.embedding.is_empty() {
+            return Err(format!("Model {} returned an empty embedding", self.embedding_model).into());
+        }
+        Ok(response.embedding)
+    }
+
+    /// Embedding vectors for `texts`, in order, with one request per batch
+    /// (`/api/embed`). Older servers without that endpoint get one
+    /// `/api/embeddings` request per text.
+    pub async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(EMBED_BATCH_SIZE) {
+            let request = BatchEmbeddingRequest {
+                model: &self.embedding_model,
+                input: batch,
+            };
+            let response = self
+                .send_with_failover(|endpoint| self.client.post(format!("{}/api/embed", endpoint)).json(&request))
+                .await?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND && embeddings.is_empty() {
+                let mut single = Vec::with_capacity(texts.len());
+                for text in texts {
+                    single.push(self.embed(text).await?);
+                }
+                return Ok(single);
+            }
+
+            let response: BatchEmbeddingResponse = response.error_for_status()?.json().await?;
+            if response.embeddings.len() != batch.len() || response.embeddings.iter().any(Vec::is_empty) {
+                return Err(format!("Model {} returned incomplete embeddings", self.embedding_model).into());
+            }
+            embeddings.extend(response.embeddings);
+        }
+        Ok(embeddings)
+    }
+
+    /// Whether `model` is available on the active endpoint (`/api/tags`).
+    /// Names without a tag match `:latest`.
+    pub async fn has_model(&self, model: &str) -> bool {
+        let latest = format!("{}:latest", model);
+        match self.list_models().await {
+            Ok(models) => models.iter().any(|m| m.name == model || m.name == latest),
+            Err(_) => false,
+        }
+    }
+
+    /// Download `model` on the active endpoint, reporting each progress line.
+    /// Pulls take minutes, so this uses a client without a request timeout
+    /// and doesn't occupy a generation slot.
+    pub async fn pull_model<F: FnMut(PullProgress)>(
+        &self,
+        model: &str,
+        mut on_progress: F,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let pull_client = Client::builder()
+            .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT))
+            .build()?;
+        let request = PullRequest { name: model, model, stream: true };
+
+        let mut response = self
+            .send_with_failover(|endpoint| pull_client.post(format!("{}/api/pull", endpoint)).json(&request))
+            .await?
+            .error_for_status()?;
+
+        let mut buffer = Vec::new();
+        let mut succeeded = false;
+        while let Some(chunk) = response.chunk().await? {
+            buffer.extend_from_slice(&chunk);
+            // One JSON object per line
+            while let Some(newline) = buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=newline).collect();
+                let Ok(line) = serde_json::from_slice::<PullLine>(&line) else {
+                    continue;
+                };
+                if let Some(error) = line.error {
+                    return Err(format!("Failed to pull {}: {}", model, error).into());
+                }
+                succeeded |= line.status == "success";
+                on_progress(PullProgress {
+                    model: model.to_string(),
+                    status: line.status,
+                    completed: line.completed,
+                    total: line.total,
+                });
+            }
+        }
+
+        if !succeeded {
+            return Err(format!("Failed to pull {}: download ended early", model).into());
+        }
+        // The new model is a candidate for the next generation
+        *self.installed.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        Ok(())
+    }
+
     /// Set the model to use
     pub fn set_model(&mut self, model: &str) {
         self.model = model.to_string();
@@ -130,4 +705,177 @@ impl OllamaClient {
     pub fn model(&self) -> &str {
         &self.model
     }
+
+    /// Set the model used by `generate_fast`
+    pub fn set_fast_model(&mut self, model: &str) {
+        self.fast_model = model.to_string();
+    }
+
+    pub fn fast_model(&self) -> &str {
+        &self.fast_model
+    }
+
+    /// Set the model used by `embed`
+    pub fn set_embedding_model(&mut self, model: &str) {
+        self.embedding_model = model.to_string();
+    }
+
+    pub fn embedding_model(&self) -> &str {
+        &self.embedding_model
+    }
+
+    /// Replace the endpoint list; empty entries are ignored
+    pub fn set_endpoints(&mut self, endpoints: &[String]) {
+        self.endpoints = endpoints
+            .iter()
+            .map(|e| e.trim().trim_end_matches('/').to_string())
+            .filter(|e| !e.is_empty())
+            .collect();
+        if self.endpoints.is_empty() {
+            self.endpoints.push(DEFAULT_ENDPOINT.to_string());
+        }
+        self.active = Arc::new(AtomicUsize::new(0));
+    }
+
+    /// Configured endpoints in order of preference
+    pub fn endpoints(&self) -> &[String] {
+        &self.endpoints
+    }
+
+    /// Endpoint currently used for requests
+    pub fn endpoint(&self) -> &str {
+        &self.endpoints[self.active_index()]
+    }
+
+    fn active_index(&self) -> usize {
+        self.active.load(Ordering::Relaxed).min(self.endpoints.len() - 1)
+    }
+
+    /// Models a `task` is sent to, in order of preference; see
+    /// `routing::model_chain`
+    pub async fn model_chain(&self, task: LlmTask) -> Vec<String> {
+        let installed = self.installed_models().await;
+        routing::model_chain(
+            task,
+            &self.model,
+            &self.fast_model,
+            installed.as_deref(),
+            &self.embedding_model,
+            self.auto_select_models,
+        )
+    }
+
+    /// Request timeout for `task`; `None` keeps the client timeout
+    pub fn timeout(&self, task: LlmTask) -> Option<Duration> {
+        let seconds = match task {
+            LlmTask::Explanation => self.task_timeouts.explanation_seconds,
+            LlmTask::Analysis => self.task_timeouts.analysis_seconds,
+            LlmTask::Structured => self.task_timeouts.structured_seconds,
+            LlmTask::Chat => self.task_timeouts.chat_seconds,
+        };
+        seconds.map(|s| Duration::from_secs(s.max(1) as u64))
+    }
+
+    /// Installed models, listed at most once per `MODEL_LIST_TTL`; `None`
+    /// when no endpoint answers
+    async fn installed_models(&self) -> Option<Vec<ModelInfo>> {
+        let cached = {
+            let installed = self.installed.lock().unwrap_or_else(|e| e.into_inner());
+            installed
+                .as_ref()
+                .filter(|(listed, _)| listed.elapsed() < MODEL_LIST_TTL)
+                .map(|(_, models)| models.clone())
+        };
+        if cached.is_some() {
+            return cached;
+        }
+
+        let models = self.list_models().await.ok()?;
+        *self.installed.lock().unwrap_or_else(|e| e.into_inner()) = Some((Instant::now(), models.clone()));
+        Some(models)
+    }
+
+    /// Send a generation to the models of `task` in turn. A model that isn't
+    /// installed (HTTP 404) or runs into the task timeout on every endpoint
+    /// is skipped; other errors are returned right away.
+    async fn send_with_models<F>(&self, task: LlmTask, build: F) -> Result<Response, Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: Fn(&str, &str) -> RequestBuilder,
+    {
+        let timeout = self.timeout(task);
+        let mut skipped = Vec::new();
+
+        for model in self.model_chain(task).await.iter().take(MAX_MODEL_ATTEMPTS) {
+            let result = self
+                .send_with_failover(|endpoint| {
+                    let request = build(endpoint, model);
+                    match timeout {
+                        Some(timeout) => request.timeout(timeout),
+                        None => request,
+                    }
+                })
+                .await;
+            match result {
+                Ok(response) if response.status() == reqwest::StatusCode::NOT_FOUND => {
+                    skipped.push(format!("{}: nicht installiert", model));
+                }
+                Ok(response) => return Ok(response),
+                Err(e) if e.downcast_ref::<EndpointsFailed>().is_some_and(|e| e.timed_out) => {
+                    skipped.push(format!("{}: Zeitüberschreitung", model));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(format!("Kein Modell hat geantwortet ({})", skipped.join("; ")).into())
+    }
+
+    async fn probe(&self, endpoint: &str) -> Result<(), String> {
+        let response = self
+            .client
+            .get(format!("{}/api/tags", endpoint))
+            .timeout(Duration::from_secs(HEALTH_CHECK_TIMEOUT))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("HTTP {}", response.status()))
+        }
+    }
+
+    /// Send a request to the active endpoint, falling over to the others on
+    /// connection errors, timeouts and server errors
+    async fn send_with_failover<F>(&self, build: F) -> Result<Response, Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: Fn(&str) -> RequestBuilder,
+    {
+        let start = self.active_index();
+        let mut errors = Vec::new();
+        let mut timed_out = true;
+
+        for offset in 0..self.endpoints.len() {
+            let index = (start + offset) % self.endpoints.len();
+            let endpoint = &self.endpoints[index];
+
+            match build(endpoint).send().await {
+                Ok(response) if !response.status().is_server_error() => {
+                    self.active.store(index, Ordering::Relaxed);
+                    return Ok(response);
+                }
+                Ok(response) => {
+                    timed_out = false;
+                    errors.push(format!("{}: HTTP {}", endpoint, response.status()));
+                }
+                Err(e) => {
+                    timed_out &= e.is_timeout();
+                    errors.push(format!("{}: {}", endpoint, e));
+                }
+            }
+        }
+
+        Err(Box::new(EndpointsFailed { errors, timed_out }))
+    }
 }
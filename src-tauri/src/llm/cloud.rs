@@ -0,0 +1,291 @@
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use serde::Deserialize;
+use super::analyzer::sanitize;
+use super::client::{ChatMessage, ChatRole, ToolCall, ToolFunctionCall};
+use super::routing::LlmTask;
+use crate::models::audit::{AuditEntry, EventType};
+use crate::models::config::{CloudLlmConfig, CloudProvider};
+use crate::services::security::AuditLogger;
+
+const ANTHROPIC_URL: &str = "https://api.anthropic.com/v1/messages";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const OPENAI_URL: &str = "https://api.openai.com/v1/chat/completions";
+const DEFAULT_ANTHROPIC_MODEL: &str = "claude-3-5-haiku-latest";
+const DEFAULT_OPENAI_MODEL: &str = "gpt-4o-mini";
+/// Anthropic requires a token limit
+const MAX_TOKENS: u32 = 4096;
+/// Used when the task has no timeout of its own
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+static SETTINGS: OnceLock<Mutex<CloudLlmConfig>> = OnceLock::new();
+
+fn settings() -> &'static Mutex<CloudLlmConfig> {
+    SETTINGS.get_or_init(|| Mutex::new(CloudLlmConfig::default()))
+}
+
+/// Anthropic or OpenAI in place of Ollama, for machines without the memory
+/// or GPU for local models. Only used after opting in with an API key.
+/// Every message is sanitized before it is sent, embeddings stay local, and
+/// each request is audit logged as data that left the machine.
+pub struct CloudLlm;
+
+impl CloudLlm {
+    pub fn configure(config: &CloudLlmConfig) {
+        *settings().lock().unwrap_or_else(|e| e.into_inner()) = config.clone();
+    }
+
+    /// Opted in with an API key
+    pub fn is_enabled() -> bool {
+        let config = settings().lock().unwrap_or_else(|e| e.into_inner());
+        config.enabled && config.api_key.as_deref().is_some_and(|key| !key.trim().is_empty())
+    }
+
+    /// Model requests go to, `None` while disabled
+    pub fn model() -> Option<String> {
+        Self::is_enabled().then(|| model(&settings().lock().unwrap_or_else(|e| e.into_inner())))
+    }
+
+    /// Answer to a single prompt; with `schema` the answer is JSON following it
+    pub(crate) async fn generate(
+        prompt: &str,
+        schema: Option<&serde_json::Value>,
+        task: LlmTask,
+        timeout: Option<Duration>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let messages = [ChatMessage { role: ChatRole::User, content: prompt.to_string() }];
+        Ok(Self::chat(&messages, None, schema, task, timeout).await?.0)
+    }
+
+    /// Next assistant message and the tool calls in it
+    pub(crate) async fn chat(
+        messages: &[ChatMessage],
+        tools: Option<&[serde_json::Value]>,
+        schema: Option<&serde_json::Value>,
+        task: LlmTask,
+        timeout: Option<Duration>,
+    ) -> Result<(String, Vec<ToolCall>), Box<dyn std::error::Error + Send + Sync>> {
+        let config = settings().lock().unwrap_or_else(|e| e.into_inner()).clone();
+        if !Self::is_enabled() {
+            return Err("Der Cloud-Anbieter ist nicht freigegeben".into());
+        }
+
+        let messages: Vec<ChatMessage> = messages
+            .iter()
+            .map(|m| ChatMessage { role: m.role, content: sanitize(&m.content) })
+            .collect();
+        let client = reqwest::Client::builder()
+            .timeout(timeout.unwrap_or(REQUEST_TIMEOUT))
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()?;
+        let api_key = config.api_key.as_deref().unwrap_or_default().trim();
+        let model = model(&config);
+
+        let result = match config.provider {
+            CloudProvider::Anthropic => send_anthropic(&client, api_key, &model, &messages, tools, schema).await,
+            CloudProvider::OpenAi => send_openai(&client, api_key, &model, &messages, tools, schema).await,
+        };
+
+        let mut entry = AuditEntry::new(EventType::LlmAnalysis, format!("cloud_llm: {}", provider_name(config.provider)));
+        entry.success = result.is_ok();
+        entry.error_message = result.as_ref().err().cloned();
+        entry.details = serde_json::json!({
+            "data_left_machine": true,
+            "provider": config.provider,
+            "model": model,
+            "task": task,
+            "sanitized": true,
+            "characters_sent": messages.iter().map(|m| m.content.chars().count()).sum::<usize>(),
+        });
+        let _ = AuditLogger::new().log(&entry);
+
+        Ok(result?)
+    }
+}
+
+fn model(config: &CloudLlmConfig) -> String {
+    match config.model.trim() {
+        "" => match config.provider {
+            CloudProvider::Anthropic => DEFAULT_ANTHROPIC_MODEL.to_string(),
+            CloudProvider::OpenAi => DEFAULT_OPENAI_MODEL.to_string(),
+        },
+        model => model.to_string(),
+    }
+}
+
+fn provider_name(provider: CloudProvider) -> &'static str {
+    match provider {
+        CloudProvider::Anthropic => "Anthropic",
+        CloudProvider::OpenAi => "OpenAI",
+    }
+}
+
+#[derive(Deserialize)]
+struct AnthropicResponse {
+    content: Vec<AnthropicBlock>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicBlock {
+    Text { text: String },
+    ToolUse { name: String, input: serde_json::Value },
+    #[serde(other)]
+    Other,
+}
+
+/// `POST /v1/messages`. System messages go into `system`, consecutive
+/// messages of the same role are merged since roles have to alternate, and
+/// JSON answers are requested in the prompt as there is no JSON mode.
+async fn send_anthropic(
+    client: &reqwest::Client,
+    api_key: &str,
+    model: &str,
+    messages: &[ChatMessage],
+    tools: Option<&[serde_json::Value]>,
+    schema: Option<&serde_json::Value>,
+) -> Result<(String, Vec<ToolCall>), String> {
+    let system: Vec<&str> = messages
+        .iter()
+        .filter(|m| m.role == ChatRole::System)
+        .map(|m| m.content.as_str())
+        .collect();
+    let mut turns: Vec<(ChatRole, String)> = Vec::new();
+    for message in messages.iter().filter(|m| m.role != ChatRole::System) {
+        match turns.last_mut() {
+            Some((role, content)) if *role == message.role => {
+                content.push_str("\n\n");
+                content.push_str(&message.content);
+            }
+            _ => turns.push((message.role, message.content.clone())),
+        }
+    }
+    if let (Some(schema), Some((_, content))) = (schema, turns.last_mut()) {
+        content.push_str(&format!(
+            "\n\nRespond only with a JSON object following this JSON schema, without any other text:\n{}",
+            schema
+        ));
+    }
+
+    let mut body = serde_json::json!({
+        "model": model,
+        "max_tokens": MAX_TOKENS,
+        "messages": turns
+            .iter()
+            .map(|(role, content)| serde_json::json!({ "role": role, "content": content }))
+            .collect::<Vec<_>>(),
+    });
+    if !system.is_empty() {
+        body["system"] = serde_json::Value::String(system.join("\n\n"));
+    }
+    if let Some(tools) = tools {
+        // Ollama uses the OpenAI format {"type": "function", "function": {...}}
+        body["tools"] = tools
+            .iter()
+            .map(|tool| {
+                serde_json::json!({
+                    "name": tool["function"]["name"],
+                    "description": tool["function"]["description"],
+                    "input_schema": tool["function"]["parameters"],
+                })
+            })
+            .collect();
+    }
+
+    let response = client
+        .post(ANTHROPIC_URL)
+        .header("x-api-key", api_key)
+        .header("anthropic-version", ANTHROPIC_VERSION)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Anthropic ist nicht erreichbar: {}", e))?;
+    let response: AnthropicResponse = parse_response(response, "Anthropic").await?;
+
+    let mut text = String::new();
+    let mut calls = Vec::new();
+    for block in response.content {
+        match block {
+            AnthropicBlock::Text { text: part } => text.push_str(&part),
+            AnthropicBlock::ToolUse { name, input } => calls.push(ToolCall {
+                function: ToolFunctionCall { name, arguments: input },
+            }),
+            AnthropicBlock::Other => {}
+        }
+    }
+    Ok((text, calls))
+}
+
+#[derive(Deserialize)]
+struct OpenAiResponse {
+    choices: Vec<OpenAiChoice>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiChoice {
+    message: OpenAiMessage,
+}
+
+#[derive(Deserialize)]
+struct OpenAiMessage {
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ToolCall>,
+}
+
+/// `POST /v1/chat/completions`; tools already have the OpenAI format.
+/// Tool call arguments arrive as a JSON string, which the callers accept.
+async fn send_openai(
+    client: &reqwest::Client,
+    api_key: &str,
+    model: &str,
+    messages: &[ChatMessage],
+    tools: Option<&[serde_json::Value]>,
+    schema: Option<&serde_json::Value>,
+) -> Result<(String, Vec<ToolCall>), String> {
+    let mut body = serde_json::json!({
+        "model": model,
+        "messages": messages,
+    });
+    if let Some(tools) = tools {
+        body["tools"] = serde_json::json!(tools);
+    }
+    if let Some(schema) = schema {
+        body["response_format"] = serde_json::json!({
+            "type": "json_schema",
+            "json_schema": { "name": "response", "schema": schema },
+        });
+    }
+
+    let response = client
+        .post(OPENAI_URL)
+        .bearer_auth(api_key)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("OpenAI ist nicht erreichbar: {}", e))?;
+    let response: OpenAiResponse = parse_response(response, "OpenAI").await?;
+
+    let message = response
+        .choices
+        .into_iter()
+        .next()
+        .ok_or("OpenAI hat keine Antwort geliefert")?
+        .message;
+    Ok((message.content.unwrap_or_default(), message.tool_calls))
+}
+
+/// The body of a successful response, or the provider's error message
+async fn parse_response<T: for<'de> Deserialize<'de>>(response: reqwest::Response, provider: &str) -> Result<T, String> {
+    let status = response.status();
+    let text = response.text().await.map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        let message = serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .and_then(|v| v["error"]["message"].as_str().map(String::from))
+            .unwrap_or_else(|| status.to_string());
+        return Err(format!("{} hat die Anfrage abgelehnt: {}", provider, message));
+    }
+    serde_json::from_str(&text).map_err(|e| format!("Unerwartete Antwort von {}: {}", provider, e))
+}
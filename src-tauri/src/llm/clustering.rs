@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use super::client::OllamaClient;
+use super::memory::cosine_similarity;
+
+/// Distinct lines embedded at most; later ones are only counted
+const MAX_EMBEDDED_LINES: usize = 1000;
+/// Characters of a line used for its embedding
+const EMBEDDING_LINE_CHARS: usize = 500;
+/// Minimum cosine similarity for a line to join a group
+const CLUSTER_THRESHOLD: f32 = 0.88;
+/// Groups scoring this many standard deviations above the mean are unusual
+const ANOMALY_Z_SCORE: f32 = 2.0;
+const MAX_EXAMPLES: usize = 3;
+
+/// Similar log lines, e.g. the same error with different hosts or IDs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogCluster {
+    /// First line of the group
+    pub representative: String,
+    /// Other variants of the line
+    pub examples: Vec<String>,
+    /// Lines in the log that belong to the group
+    pub occurrences: usize,
+    /// Lines that differ in more than numbers and IDs
+    pub variants: usize,
+    pub contains_errors: bool,
+    /// How far the group is from all others, weighted by how rare it is
+    pub anomaly_score: f32,
+    /// The score is well above that of the other groups
+    pub anomalous: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogClusterReport {
+    pub total_lines: usize,
+    /// Groups, most frequent first
+    pub clusters: Vec<LogCluster>,
+    /// Lines beyond the embedding limit; they are not part of any group
+    pub skipped_lines: usize,
+}
+
+impl LogClusterReport {
+    /// Compact form of the log for the generation model: unusual lines
+    /// first, then every group once with its count
+    pub fn digest(&self) -> String {
+        let line = |cluster: &LogCluster| {
+            let mut line = format!("[{}x", cluster.occurrences);
+            if cluster.variants > 1 {
+                line.push_str(&format!(", {} variants", cluster.variants));
+            }
+            format!("{}] {}", line, cluster.representative)
+        };
+
+        let mut digest = format!(
+            "{} log lines in {} groups of similar lines.\n",
+            self.total_lines,
+            self.clusters.len()
+        );
+        let anomalies: Vec<String> = self.clusters.iter().filter(|c| c.anomalous).map(line).collect();
+        if !anomalies.is_empty() {
+            digest.push_str(&format!("\nStatistically unusual lines:\n{}\n", anomalies.join("\n")));
+        }
+        digest.push_str(&format!(
+            "\nAll groups, most frequent first:\n{}",
+            self.clusters.iter().map(line).collect::<Vec<_>>().join("\n")
+        ));
+        if self.skipped_lines > 0 {
+            digest.push_str(&format!("\n\n{} further lines were not grouped.", self.skipped_lines));
+        }
+        digest
+    }
+}
+
+/// Lines with the same text apart from numbers and IDs
+struct LineGroup<'a> {
+    first: &'a str,
+    variants: Vec<&'a str>,
+    occurrences: usize,
+    template: String,
+}
+
+/// Groups log lines by the similarity of their embeddings instead of
+/// prompting the generation model, so repeated errors collapse into one
+/// entry and lines unlike the rest of the log stand out.
+pub struct LogClusterer {
+    client: OllamaClient,
+}
+
+impl LogClusterer {
+    pub fn new(client: OllamaClient) -> Self {
+        Self { client }
+    }
+
+    pub async fn cluster(&self, logs: &str) -> Result<LogClusterReport, Box<dyn std::error::Error + Send + Sync>> {
+        let masks = Regex::new(r"(?i)\b0x[0-9a-f]+\b|\b[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\b|\b[0-9a-f]{16,}\b|\d+")
+            .expect("valid regex");
+
+        // Lines differing only in numbers and IDs are embedded once
+        let mut groups: Vec<LineGroup> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut total_lines = 0;
+        let mut skipped_lines = 0;
+        for line in logs.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            total_lines += 1;
+            let template = masks.replace_all(line, "#").to_string();
+            match index.get(&template) {
+                Some(&i) => {
+                    let group = &mut groups[i];
+                    group.occurrences += 1;
+                    if group.variants.len() < MAX_EXAMPLES && !group.variants.contains(&line) && group.first != line {
+                        group.variants.push(line);
+                    }
+                }
+                None if groups.len() < MAX_EMBEDDED_LINES => {
+                    index.insert(template.clone(), groups.len());
+                    groups.push(LineGroup { first: line, variants: Vec::new(), occurrences: 1, template });
+                }
+                None => skipped_lines += 1,
+            }
+        }
+        if groups.is_empty() {
+            return Ok(LogClusterReport { total_lines, clusters: Vec::new(), skipped_lines });
+        }
+
+        let texts: Vec<String> = groups
+            .iter()
+            .map(|g| g.template.chars().take(EMBEDDING_LINE_CHARS).collect())
+            .collect();
+        let embeddings = self.client.embed_batch(&texts).await?;
+
+        // Leader clustering: each line joins the most similar group above
+        // the threshold or starts a new one
+        let mut clusters: Vec<(Vec<f32>, Vec<usize>)> = Vec::new();
+        for (i, embedding) in embeddings.iter().enumerate() {
+            let best = clusters
+                .iter()
+                .enumerate()
+                .filter_map(|(c, (centroid, _))| cosine_similarity(embedding, centroid).map(|s| (c, s)))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            match best {
+                Some((c, similarity)) if similarity >= CLUSTER_THRESHOLD => {
+                    let (centroid, members) = &mut clusters[c];
+                    let n = members.len() as f32;
+                    for (value, x) in centroid.iter_mut().zip(embedding) {
+                        *value = (*value * n + x) / (n + 1.0);
+                    }
+                    members.push(i);
+                }
+                _ => clusters.push((embedding.clone(), vec![i])),
+            }
+        }
+
+        let errors = Regex::new(r"(?i)error|fail|exception|fatal|panic|crit|denied|refused").expect("valid regex");
+        let mut result: Vec<LogCluster> = clusters
+            .iter()
+            .enumerate()
+            .map(|(c, (centroid, members))| {
+                // Distance to the closest other group, weighted by rarity
+                let nearest = clusters
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != c)
+                    .filter_map(|(_, (other, _))| cosine_similarity(centroid, other))
+                    .fold(f32::MIN, f32::max);
+                let isolation = if nearest == f32::MIN { 0.0 } else { 1.0 - nearest };
+                let occurrences: usize = members.iter().map(|&m| groups[m].occurrences).sum();
+                let rarity = 1.0 - occurrences as f32 / total_lines as f32;
+
+                let lines: Vec<&str> = members
+                    .iter()
+                    .flat_map(|&m| std::iter::once(groups[m].first).chain(groups[m].variants.iter().copied()))
+                    .collect();
+                LogCluster {
+                    representative: lines[0].to_string(),
+                    examples: lines.iter().skip(1).take(MAX_EXAMPLES).map(|l| l.to_string()).collect(),
+                    occurrences,
+                    variants: members.len(),
+                    contains_errors: lines.iter().any(|l| errors.is_match(l)),
+                    anomaly_score: isolation * rarity,
+                    anomalous: false,
+                }
+            })
+            .collect();
+
+        // Unusual means well above the typical score of this log
+        if result.len() >= 3 {
+            let n = result.len() as f32;
+            let mean = result.iter().map(|c| c.anomaly_score).sum::<f32>() / n;
+            let deviation = (result.iter().map(|c| (c.anomaly_score - mean).powi(2)).sum::<f32>() / n).sqrt();
+            for cluster in &mut result {
+                cluster.anomalous = deviation > 0.0 && cluster.anomaly_score > mean + ANOMALY_Z_SCORE * deviation;
+            }
+        }
+
+        result.sort_by_key(|c| std::cmp::Reverse(c.occurrences));
+        Ok(LogClusterReport { total_lines, clusters: result, skipped_lines })
+    }
+}
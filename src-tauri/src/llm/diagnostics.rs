@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use super::client::{GenerationProbe, OllamaClient, RunningModel};
+use super::cloud::CloudLlm;
+use super::queue::{LlmQueue, QueueStatus};
+use super::routing::{same_model, LlmTask, TaskModels};
+use crate::services::system_stats::{GpuStats, MemoryStats};
+
+/// Loading a model slower than this is worth a hint
+const SLOW_LOAD_MS: u64 = 5000;
+/// Generation slower than this feels stuck for longer answers
+const SLOW_TOKENS_PER_SECOND: f64 = 5.0;
+
+/// An installed model and whether it fits into memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelFit {
+    pub name: String,
+    /// Size of the weights on disk
+    pub size_bytes: u64,
+    /// e.g. `7.2B`
+    pub parameter_size: Option<String>,
+    /// Estimated memory while loaded: the weights plus about 20% for the context
+    pub required_bytes: u64,
+    pub fits_in_ram: bool,
+    /// `None` when the GPU memory is unknown, e.g. with unified memory
+    pub fits_in_vram: Option<bool>,
+    pub loaded: bool,
+}
+
+/// Why AI analysis is slow or unavailable, as far as the app can tell
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmDiagnostics {
+    pub endpoint: String,
+    pub reachable: bool,
+    pub version: Option<String>,
+    /// Model of the cloud provider when one replaces Ollama
+    pub cloud_model: Option<String>,
+    pub models: Vec<ModelFit>,
+    pub loaded_models: Vec<RunningModel>,
+    pub task_models: Vec<TaskModels>,
+    pub memory_total_bytes: u64,
+    pub memory_available_bytes: u64,
+    /// Sum over the GPUs that report their memory
+    pub vram_total_bytes: Option<u64>,
+    pub queue: QueueStatus,
+    /// Tiny generation with the analysis model
+    pub probe: Option<GenerationProbe>,
+    pub probe_error: Option<String>,
+    /// Findings in plain language, most important first
+    pub hints: Vec<String>,
+}
+
+impl LlmDiagnostics {
+    /// Ask Ollama for its version and models, compare the model sizes with
+    /// the free memory and, with `probe`, time a tiny generation
+    pub async fn collect(client: &OllamaClient, memory: &MemoryStats, gpus: &[GpuStats], probe: bool) -> Self {
+        let reachable = client.is_available().await;
+        let version = if reachable { client.version().await.ok() } else { None };
+        let installed = if reachable { client.list_models().await.unwrap_or_default() } else { Vec::new() };
+        let loaded_models = if reachable { client.running_models().await.unwrap_or_default() } else { Vec::new() };
+
+        let vram: Vec<u64> = gpus.iter().filter_map(|g| g.memory_total_bytes).collect();
+        let vram_total_bytes = (!vram.is_empty()).then(|| vram.iter().sum());
+
+        let models: Vec<ModelFit> = installed
+            .iter()
+            .map(|m| {
+                let loaded = loaded_models.iter().any(|l| same_model(&l.name, &m.name));
+                let required_bytes = m.size + m.size / 5;
+                ModelFit {
+                    name: m.name.clone(),
+                    size_bytes: m.size,
+                    parameter_size: Some(m.details.parameter_size.clone()).filter(|p| !p.is_empty()),
+                    required_bytes,
+                    // A loaded model is already part of the used memory
+                    fits_in_ram: loaded || required_bytes <= memory.available_bytes,
+                    fits_in_vram: vram_total_bytes.map(|v| required_bytes <= v),
+                    loaded,
+                }
+            })
+            .collect();
+
+        let mut task_models = Vec::with_capacity(LlmTask::ALL.len());
+        for task in LlmTask::ALL {
+            task_models.push(TaskModels {
+                task,
+                models: client.model_chain(task).await,
+                timeout_seconds: client.timeout(task).map(|t| t.as_secs()),
+            });
+        }
+        let analysis_model = task_models
+            .iter()
+            .find(|t| t.task == LlmTask::Analysis)
+            .and_then(|t| t.models.first().cloned())
+            .unwrap_or_else(|| client.model().to_string());
+
+        let (probe, probe_error) = if probe && reachable {
+            match client.probe_generation(&analysis_model).await {
+                Ok(result) => (Some(result), None),
+                Err(e) => (None, Some(e.to_string())),
+            }
+        } else {
+            (None, None)
+        };
+
+        let mut diagnostics = Self {
+            endpoint: client.endpoint().to_string(),
+            reachable,
+            version,
+            cloud_model: CloudLlm::model(),
+            models,
+            loaded_models,
+            task_models,
+            memory_total_bytes: memory.total_bytes,
+            memory_available_bytes: memory.available_bytes,
+            vram_total_bytes,
+            queue: LlmQueue::global().status(),
+            probe,
+            probe_error,
+            hints: Vec::new(),
+        };
+        diagnostics.hints = diagnostics.hints(client, &analysis_model);
+        diagnostics
+    }
+
+    fn hints(&self, client: &OllamaClient, analysis_model: &str) -> Vec<String> {
+        let mut hints = Vec::new();
+        if let Some(model) = &self.cloud_model {
+            hints.push(format!(
+                "Generierungen gehen an den Cloud-Anbieter ({}); Ollama wird nur noch für Embeddings genutzt.",
+                model
+            ));
+        }
+        if !self.reachable {
+            hints.push(format!("Ollama ist unter {} nicht erreichbar. Läuft `ollama serve`?", self.endpoint));
+            return hints;
+        }
+
+        for model in [client.model(), client.fast_model()] {
+            if !self.models.iter().any(|m| same_model(&m.name, model)) {
+                hints.push(format!("Das Modell {} ist nicht installiert (`ollama pull {}`).", model, model));
+            }
+        }
+
+        if let Some(fit) = self.models.iter().find(|m| same_model(&m.name, analysis_model)) {
+            if !fit.fits_in_ram {
+                hints.push(format!(
+                    "{} braucht etwa {}, frei sind nur {}. Das System lagert aus und die Analyse wird sehr langsam; ein kleineres Modell hilft.",
+                    fit.name,
+                    gigabytes(fit.required_bytes),
+                    gigabytes(self.memory_available_bytes)
+                ));
+            } else if fit.fits_in_vram == Some(false) && !fit.loaded {
+                hints.push(format!(
+                    "{} ({}) passt nicht in den Grafikspeicher ({}) und wird teilweise auf der CPU laufen.",
+                    fit.name,
+                    gigabytes(fit.required_bytes),
+                    gigabytes(self.vram_total_bytes.unwrap_or_default())
+                ));
+            }
+        }
+        for loaded in &self.loaded_models {
+            if loaded.size > 0 && loaded.size_vram < loaded.size {
+                hints.push(format!(
+                    "{} liegt nur zu {}% im Grafikspeicher, der Rest läuft auf der CPU.",
+                    loaded.name,
+                    loaded.size_vram * 100 / loaded.size
+                ));
+            }
+        }
+
+        if let Some(probe) = &self.probe {
+            if probe.load_ms > SLOW_LOAD_MS {
+                hints.push(format!(
+                    "Das Laden von {} dauerte {:.1} s. Ollama entlädt Modelle nach einigen Minuten ohne Anfrage, die erste Analyse danach wartet erneut.",
+                    probe.model,
+                    probe.load_ms as f64 / 1000.0
+                ));
+            }
+            if let Some(speed) = probe.tokens_per_second.filter(|s| *s < SLOW_TOKENS_PER_SECOND) {
+                hints.push(format!(
+                    "{} erzeugt nur {:.1} Tokens pro Sekunde; eine Analyse mit einigen hundert Tokens dauert entsprechend lange.",
+                    probe.model, speed
+                ));
+            }
+        }
+        if let Some(error) = &self.probe_error {
+            hints.push(format!("Die Testanfrage an {} ist fehlgeschlagen: {}", analysis_model, error));
+        }
+
+        if self.queue.waiting > 0 {
+            hints.push(format!(
+                "{} Anfragen warten auf einen freien Platz ({} gleichzeitig erlaubt).",
+                self.queue.waiting, self.queue.max_concurrent
+            ));
+        }
+        hints
+    }
+}
+
+fn gigabytes(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / 1024f64.powi(3))
+}
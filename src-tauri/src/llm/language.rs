@@ -0,0 +1,57 @@
+use std::sync::{Mutex, OnceLock};
+use crate::models::config::OllamaConfig;
+
+static LANGUAGE: OnceLock<Mutex<String>> = OnceLock::new();
+
+/// Languages offered in the settings; other values are passed to the model as written
+const KNOWN_LANGUAGES: &[(&str, &str)] = &[
+    ("de", "German"),
+    ("en", "English"),
+    ("fr", "French"),
+    ("es", "Spanish"),
+    ("it", "Italian"),
+    ("nl", "Dutch"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+];
+
+/// Language the models answer in, from `OllamaConfig::language`
+pub struct ResponseLanguage;
+
+impl ResponseLanguage {
+    pub fn configure(config: &OllamaConfig) {
+        *language().lock().unwrap_or_else(|e| e.into_inner()) = config.language.trim().to_string();
+    }
+
+    /// English name of the language for prompts, e.g. `German`
+    pub fn name() -> String {
+        let code = language().lock().unwrap_or_else(|e| e.into_inner()).clone();
+        if code.is_empty() {
+            return "German".to_string();
+        }
+        KNOWN_LANGUAGES
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(&code))
+            .map(|(_, name)| name.to_string())
+            .unwrap_or(code)
+    }
+
+    /// Sentence appended to every prompt
+    pub fn instruction() -> String {
+        format!("Respond in {}.", Self::name())
+    }
+
+    /// Whether answers are expected in the language with `code`, e.g. `de`
+    /// for built-in texts like the bundled process explanations
+    pub fn is(code: &str) -> bool {
+        let name = KNOWN_LANGUAGES
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(code))
+            .map_or(code, |(_, name)| name);
+        Self::name().eq_ignore_ascii_case(name)
+    }
+}
+
+fn language() -> &'static Mutex<String> {
+    LANGUAGE.get_or_init(|| Mutex::new(OllamaConfig::default().language))
+}
@@ -0,0 +1,172 @@
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::services::storage::Storage;
+
+/// Minimum cosine similarity for a past analysis to be surfaced
+const SIMILARITY_THRESHOLD: f32 = 0.8;
+const MAX_SIMILAR: usize = 3;
+/// Characters of the input kept with a stored report
+const EXCERPT_CHARS: usize = 500;
+
+/// A past log analysis or incident that can be looked up again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredAnalysis {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    /// Analysis type (e.g. `errors`) or `incident`
+    pub kind: String,
+    pub title: String,
+    pub input_excerpt: String,
+    pub report: String,
+}
+
+/// A stored analysis that resembles the current one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarAnalysis {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub kind: String,
+    pub title: String,
+    pub similarity: f32,
+}
+
+/// Result of a log analysis with links to similar past analyses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogAnalysisResult {
+    pub report: String,
+    /// ID of the stored report; `None` if no embedding could be created
+    pub analysis_id: Option<String>,
+    pub similar: Vec<SimilarAnalysis>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AnalysisRecord {
+    #[serde(flatten)]
+    analysis: StoredAnalysis,
+    embedding: Vec<f32>,
+}
+
+/// Past analyses with their embeddings, stored as JSON lines
+pub struct AnalysisStore {
+    path: PathBuf,
+}
+
+impl AnalysisStore {
+    pub fn new() -> Self {
+        let dir = Storage::data_dir().join("analyses");
+
+        Self::with_path(dir.join("analyses.jsonl"))
+    }
+
+    pub fn with_path(path: PathBuf) -> Self {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        Self { path }
+    }
+
+    /// Store an analysis and return its ID
+    pub fn save(
+        &self,
+        kind: &str,
+        title: &str,
+        input: &str,
+        report: &str,
+        embedding: Vec<f32>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let record = AnalysisRecord {
+            analysis: StoredAnalysis {
+                id: uuid::Uuid::new_v4().to_string(),
+                created_at: Utc::now(),
+                kind: kind.to_string(),
+                title: title.to_string(),
+                input_excerpt: input.chars().take(EXCERPT_CHARS).collect(),
+                report: report.to_string(),
+            },
+            embedding,
+        };
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "{}", serde_json::to_string(&record)?)?;
+        writer.flush()?;
+
+        Ok(record.analysis.id)
+    }
+
+    /// Stored analyses most similar to `embedding`, best match first
+    pub fn find_similar(&self, embedding: &[f32]) -> Vec<SimilarAnalysis> {
+        let mut matches: Vec<SimilarAnalysis> = self
+            .records()
+            .into_iter()
+            .filter_map(|record| {
+                let similarity = cosine_similarity(embedding, &record.embedding)?;
+                (similarity >= SIMILARITY_THRESHOLD).then_some(SimilarAnalysis {
+                    id: record.analysis.id,
+                    created_at: record.analysis.created_at,
+                    kind: record.analysis.kind,
+                    title: record.analysis.title,
+                    similarity,
+                })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        matches.truncate(MAX_SIMILAR);
+        matches
+    }
+
+    pub fn get(&self, id: &str) -> Option<StoredAnalysis> {
+        self.records()
+            .into_iter()
+            .find(|record| record.analysis.id == id)
+            .map(|record| record.analysis)
+    }
+
+    /// Analyses and incidents stored since `since`, oldest first
+    pub fn since(&self, since: DateTime<Utc>) -> Vec<StoredAnalysis> {
+        self.records()
+            .into_iter()
+            .map(|record| record.analysis)
+            .filter(|analysis| analysis.created_at >= since)
+            .collect()
+    }
+
+    fn records(&self) -> Vec<AnalysisRecord> {
+        let Ok(content) = std::fs::read_to_string(&self.path) else {
+            return Vec::new();
+        };
+        content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+}
+
+impl Default for AnalysisStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `None` if the vectors don't match, e.g. after switching the embedding model
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() || a.is_empty() {
+        return None;
+    }
+
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return None;
+    }
+    Some(dot / (norm_a * norm_b))
+}
@@ -2,6 +2,28 @@
 
 pub mod client;
 pub mod analyzer;
+pub mod queue;
+pub mod memory;
+pub mod chat;
+pub mod actions;
+pub mod language;
+pub mod prompts;
+pub mod clustering;
+pub mod audit_qa;
+pub mod routing;
+pub mod cloud;
+pub mod diagnostics;
 
-pub use client::OllamaClient;
-pub use analyzer::{LogAnalyzer, ServiceRecommendation, RecommendationType};
+pub use audit_qa::{AuditAnswer, AuditAssistant};
+pub use actions::{ActionPlanner, ActionProposal, ProposedAction, ProposedActionKind, ProposedActionResult, ProposedActionStatus};
+pub use chat::{ChatSession, ChatSessionMessage, ChatSessionSummary, ChatStore};
+pub use cloud::CloudLlm;
+pub use clustering::{LogCluster, LogClusterReport, LogClusterer};
+pub use client::{ChatMessage, ChatRole, EndpointHealth, GenerationProbe, OllamaClient, PullProgress, RunningModel, ToolCall};
+pub use diagnostics::{LlmDiagnostics, ModelFit};
+pub use language::ResponseLanguage;
+pub use prompts::{PromptKind, PromptTemplate, PromptTemplates};
+pub use memory::{AnalysisStore, LogAnalysisResult, SimilarAnalysis, StoredAnalysis};
+pub use queue::{LlmQueue, Priority, QueueStatus};
+pub use routing::{LlmTask, TaskModels};
+pub use analyzer::{LogAnalysisProgress, LogAnalysisStage, LogAnalyzer, ProcessExplanation, RemediationPlan, RemediationStep, SecurityAnalysis, SecurityRisk, ServiceRecommendation, RecommendationType};
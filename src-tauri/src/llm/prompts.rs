@@ -0,0 +1,270 @@
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use super::language::ResponseLanguage;
+use crate::services::storage::Storage;
+
+/// Prompts that can be overridden by a template file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptKind {
+    LogAnalysis,
+    /// Summary of one part of a log too long for a single prompt
+    LogChunkSummary,
+    ProcessExplanation,
+    Recommendations,
+    /// Remediation plan for the findings of a security scan
+    RemediationPlan,
+    SecurityAnalysis,
+}
+
+impl PromptKind {
+    pub const ALL: [PromptKind; 6] = [
+        PromptKind::LogAnalysis,
+        PromptKind::LogChunkSummary,
+        PromptKind::ProcessExplanation,
+        PromptKind::Recommendations,
+        PromptKind::RemediationPlan,
+        PromptKind::SecurityAnalysis,
+    ];
+
+    /// File name of the template without extension
+    pub fn name(self) -> &'static str {
+        match self {
+            PromptKind::LogAnalysis => "log_analysis",
+            PromptKind::LogChunkSummary => "log_chunk_summary",
+            PromptKind::ProcessExplanation => "process_explanation",
+            PromptKind::Recommendations => "recommendations",
+            PromptKind::RemediationPlan => "remediation_plan",
+            PromptKind::SecurityAnalysis => "security_analysis",
+        }
+    }
+
+    /// Placeholders filled by the caller; `{{language}}` and
+    /// `{{language_instruction}}` are available in every template
+    pub fn placeholders(self) -> &'static [&'static str] {
+        match self {
+            PromptKind::LogAnalysis => &["task", "logs"],
+            PromptKind::LogChunkSummary => &["task", "part", "parts", "logs"],
+            PromptKind::ProcessExplanation => &["process"],
+            PromptKind::Recommendations | PromptKind::SecurityAnalysis => &["host", "services"],
+            PromptKind::RemediationPlan => &["host", "findings"],
+        }
+    }
+
+    /// A template without this placeholder would send no data to the model
+    fn required_placeholder(self) -> &'static str {
+        match self {
+            PromptKind::LogAnalysis | PromptKind::LogChunkSummary => "logs",
+            PromptKind::ProcessExplanation => "process",
+            PromptKind::Recommendations | PromptKind::SecurityAnalysis => "services",
+            PromptKind::RemediationPlan => "findings",
+        }
+    }
+
+    fn default_template(self) -> &'static str {
+        match self {
+            PromptKind::LogAnalysis => LOG_ANALYSIS_TEMPLATE,
+            PromptKind::LogChunkSummary => LOG_CHUNK_SUMMARY_TEMPLATE,
+            PromptKind::ProcessExplanation => PROCESS_EXPLANATION_TEMPLATE,
+            PromptKind::Recommendations => RECOMMENDATIONS_TEMPLATE,
+            PromptKind::RemediationPlan => REMEDIATION_PLAN_TEMPLATE,
+            PromptKind::SecurityAnalysis => SECURITY_ANALYSIS_TEMPLATE,
+        }
+    }
+}
+
+/// A prompt template as shown in the settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub kind: PromptKind,
+    pub content: String,
+    pub default_content: String,
+    /// Whether a template file overrides the built-in prompt
+    pub customized: bool,
+    pub path: String,
+    pub placeholders: Vec<String>,
+}
+
+/// Prompt templates with `{{placeholder}}` substitution. A `<name>.txt` in
+/// the template directory replaces the built-in prompt of that name.
+pub struct PromptTemplates {
+    template_dir: PathBuf,
+}
+
+impl PromptTemplates {
+    pub fn new() -> Self {
+        Self {
+            template_dir: Storage::config_dir().join("prompt_templates"),
+        }
+    }
+
+    pub fn template_dir(&self) -> &Path {
+        &self.template_dir
+    }
+
+    pub fn list(&self) -> Vec<PromptTemplate> {
+        PromptKind::ALL.iter().map(|kind| self.get(*kind)).collect()
+    }
+
+    pub fn get(&self, kind: PromptKind) -> PromptTemplate {
+        let user = self.user_template(kind);
+        PromptTemplate {
+            kind,
+            customized: user.is_some(),
+            content: user.unwrap_or_else(|| kind.default_template().to_string()),
+            default_content: kind.default_template().to_string(),
+            path: self.path(kind).to_string_lossy().to_string(),
+            placeholders: ["language", "language_instruction"]
+                .iter()
+                .chain(kind.placeholders())
+                .map(|p| format!("{{{{{}}}}}", p))
+                .collect(),
+        }
+    }
+
+    /// Write a template file for `kind`
+    pub fn save(&self, kind: PromptKind, content: &str) -> Result<PromptTemplate, Box<dyn std::error::Error + Send + Sync>> {
+        let required = format!("{{{{{}}}}}", kind.required_placeholder());
+        if !content.contains(&required) {
+            return Err(format!("Die Vorlage muss den Platzhalter {} enthalten", required).into());
+        }
+        std::fs::create_dir_all(&self.template_dir)
+            .map_err(|e| format!("Failed to create {}: {}", self.template_dir.display(), e))?;
+        let path = self.path(kind);
+        std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(self.get(kind))
+    }
+
+    /// Remove the template file so the built-in prompt is used again
+    pub fn reset(&self, kind: PromptKind) -> Result<PromptTemplate, Box<dyn std::error::Error + Send + Sync>> {
+        let path = self.path(kind);
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to remove {}: {}", path.display(), e).into()),
+        }
+        Ok(self.get(kind))
+    }
+
+    /// The prompt for `kind` with `values` and the response language filled in
+    pub fn render(&self, kind: PromptKind, values: &[(&str, &str)]) -> String {
+        let template = self.user_template(kind).unwrap_or_else(|| kind.default_template().to_string());
+        let template = template
+            .replace("{{language_instruction}}", &ResponseLanguage::instruction())
+            .replace("{{language}}", &ResponseLanguage::name());
+
+        // Values are substituted in order, so data placeholders like logs go
+        // last and placeholders inside the data are not expanded
+        values
+            .iter()
+            .fold(template, |prompt, (name, value)| prompt.replace(&format!("{{{{{}}}}}", name), value))
+    }
+
+    fn path(&self, kind: PromptKind) -> PathBuf {
+        self.template_dir.join(format!("{}.txt", kind.name()))
+    }
+
+    fn user_template(&self, kind: PromptKind) -> Option<String> {
+        std::fs::read_to_string(self.path(kind))
+            .ok()
+            .filter(|template| !template.trim().is_empty())
+    }
+}
+
+impl Default for PromptTemplates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const LOG_ANALYSIS_TEMPLATE: &str = "You are a log analysis assistant. Your task is to analyze service logs and provide insights.
+
+IMPORTANT: This is a READ-ONLY analysis. Do not suggest running commands or making changes to services.
+
+{{task}}
+
+LOGS:
+```
+{{logs}}
+```
+
+Provide a concise analysis with:
+1. Summary of findings
+2. Key issues or patterns identified
+3. Recommendations (informational only)
+
+{{language_instruction}}";
+
+const LOG_CHUNK_SUMMARY_TEMPLATE: &str = "You are a log analysis assistant. A long log is analyzed in parts; this is part {{part}} of {{parts}}.
+
+The overall task is: {{task}}
+
+Summarize only what is relevant for this task in this part: errors, warnings and unusual events with their timestamps, affected components and how often they occur. Lines ending in [repeated Nx] occurred N times. Skip routine entries. Use at most 15 short bullet points and do not draw conclusions about other parts.
+
+LOG PART:
+```
+{{logs}}
+```
+
+Write the summary in English.";
+
+const PROCESS_EXPLANATION_TEMPLATE: &str =
+    "What does the process '{{process}}' do? Answer in 1-2 sentences, facts only, no introduction. {{language_instruction}}";
+
+const RECOMMENDATIONS_TEMPLATE: &str = r#"You are a system optimization assistant. Analyze the following list of running services and provide recommendations for optimization.
+
+Host: {{host}}
+
+Services (JSON):
+```json
+{{services}}
+```
+
+Focus on identifying:
+1. Services that may be consuming unnecessary resources
+2. Auto-start services that might not be needed
+3. Duplicate or redundant services
+4. Services that could be safely disabled
+
+Respond with a JSON object {"recommendations": [...]}. Each recommendation has these fields:
+- service_id: string (the service ID)
+- service_name: string (the service name)
+- recommendation_type: one of "stop_service", "disable_autostart", "reduce_resources", "security_concern", "performance_impact", "info"
+- title: string (short title, 5-10 words, in {{language}})
+- description: string (explanation in {{language}}, 1-2 sentences)
+- action: string or null (suggested action if applicable)
+
+Provide 3-5 recommendations. Be conservative - only suggest stopping services that are truly optional."#;
+
+const REMEDIATION_PLAN_TEMPLATE: &str = r#"You are a security advisor for the owner of this machine, who is not a security expert. Turn the findings of a security scan into a remediation plan.
+
+Host: {{host}}
+
+Findings (JSON, most severe first):
+```json
+{{findings}}
+```
+
+Respond with a JSON object with these fields:
+- summary: 2-3 sentences on the overall state and what to do first
+- steps: the plan, most urgent step first. Each step has a title (5-10 words), text (one paragraph in plain language: what the risk is, why it matters here and how to fix it, without jargon) and finding_ids (the ids of the findings the step fixes, such as "F1")
+
+Group findings that are fixed together, e.g. several open ports of the same database, into one step. Every finding should belong to a step. Only describe fixes the findings support and don't invent findings.
+
+{{language_instruction}}"#;
+
+const SECURITY_ANALYSIS_TEMPLATE: &str = r#"Analysiere diese Services auf Sicherheitsprobleme. {{language_instruction}}
+Host: {{host}}
+
+Prüfe auf:
+- Unverschlüsselte Verbindungen
+- Öffentlich erreichbare Datenbanken
+- Fehlende Authentifizierung
+- Bekannte Schwachstellen
+
+Services:
+{{services}}
+
+Antworte mit einem JSON-Objekt mit den Feldern:
+- summary: kurze Zusammenfassung der wichtigsten Sicherheitsrisiken (2-4 Sätze)
+- risks: Liste der Risiken, jeweils mit service_name (string oder null), severity ("critical", "high", "medium", "low" oder "info"), title (kurz) und recommendation (konkrete Empfehlung)"#;
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::BinaryHeap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use crate::models::config::OllamaConfig;
+
+static QUEUE: OnceLock<LlmQueue> = OnceLock::new();
+
+/// Scheduling priority of a generation request.
+/// Waiting requests with a higher priority are served first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    /// Long-running analyses (logs, security)
+    Low,
+    #[default]
+    Normal,
+    /// Interactive requests such as process explanations
+    High,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueStatus {
+    pub max_concurrent: usize,
+    pub running: usize,
+    pub waiting: usize,
+}
+
+/// Limits concurrent generations so a slow model doesn't pile up timeouts
+pub struct LlmQueue {
+    state: Mutex<QueueState>,
+}
+
+struct QueueState {
+    max_concurrent: usize,
+    max_waiting: usize,
+    wait_timeout: Duration,
+    running: usize,
+    next_seq: u64,
+    waiting: BinaryHeap<Waiter>,
+}
+
+struct Waiter {
+    priority: Priority,
+    seq: u64,
+    wake: oneshot::Sender<()>,
+}
+
+// Highest priority first, FIFO within the same priority
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == CmpOrdering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+/// A running generation slot, released on drop
+pub struct QueuePermit {
+    queue: &'static LlmQueue,
+}
+
+impl Drop for QueuePermit {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+/// A queued request. If it is dropped (timeout or cancelled command) right
+/// after a slot was handed to it, the slot is passed on instead of leaking.
+struct PendingSlot {
+    queue: &'static LlmQueue,
+    receiver: oneshot::Receiver<()>,
+}
+
+impl Drop for PendingSlot {
+    fn drop(&mut self) {
+        self.receiver.close();
+        if self.receiver.try_recv().is_ok() {
+            self.queue.release();
+        }
+    }
+}
+
+impl LlmQueue {
+    pub fn new(max_concurrent: usize, max_waiting: usize, wait_timeout: Duration) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                max_concurrent: max_concurrent.max(1),
+                max_waiting,
+                wait_timeout,
+                running: 0,
+                next_seq: 0,
+                waiting: BinaryHeap::new(),
+            }),
+        }
+    }
+
+    pub fn global() -> &'static LlmQueue {
+        let defaults = OllamaConfig::default();
+        QUEUE.get_or_init(|| {
+            LlmQueue::new(
+                defaults.max_concurrent_generations as usize,
+                defaults.max_queued_generations as usize,
+                Duration::from_secs(defaults.queue_timeout_seconds as u64),
+            )
+        })
+    }
+
+    /// Apply limits from the config; takes effect for the next request
+    pub fn configure(&self, config: &OllamaConfig) {
+        let mut state = self.lock();
+        state.max_concurrent = (config.max_concurrent_generations as usize).max(1);
+        state.max_waiting = config.max_queued_generations as usize;
+        state.wait_timeout = Duration::from_secs(config.queue_timeout_seconds as u64);
+        drop(state);
+
+        // A raised limit may allow waiting requests to start
+        self.dispatch();
+    }
+
+    pub fn status(&self) -> QueueStatus {
+        let mut state = self.lock();
+        state.waiting.retain(|w| !w.wake.is_closed());
+        QueueStatus {
+            max_concurrent: state.max_concurrent,
+            running: state.running,
+            waiting: state.waiting.len(),
+        }
+    }
+
+    /// Wait for a generation slot
+    pub async fn acquire(&'static self, priority: Priority) -> Result<QueuePermit, Box<dyn std::error::Error + Send + Sync>> {
+        let (receiver, wait_timeout) = {
+            let mut state = self.lock();
+            // Drop waiters that timed out or were cancelled
+            state.waiting.retain(|w| !w.wake.is_closed());
+
+            let higher_waiting = state.waiting.peek().is_some_and(|w| w.priority >= priority);
+            if state.running < state.max_concurrent && !higher_waiting {
+                state.running += 1;
+                return Ok(QueuePermit { queue: self });
+            }
+
+            // Low priority work is rejected first when the queue is full
+            if state.waiting.len() >= state.max_waiting && priority != Priority::High {
+                return Err("Das Sprachmodell ist ausgelastet. Bitte später erneut versuchen.".into());
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            state.waiting.push(Waiter { priority, seq, wake: sender });
+            (receiver, state.wait_timeout)
+        };
+
+        let mut pending = PendingSlot { queue: self, receiver };
+        match tokio::time::timeout(wait_timeout, &mut pending.receiver).await {
+            Ok(Ok(())) => Ok(QueuePermit { queue: self }),
+            _ => Err(format!(
+                "Zeitüberschreitung: Keine freie Kapazität beim Sprachmodell nach {}s",
+                wait_timeout.as_secs()
+            )
+            .into()),
+        }
+    }
+
+    fn release(&self) {
+        self.lock().running -= 1;
+        self.dispatch();
+    }
+
+    /// Hand free slots to the highest-priority waiters that are still waiting
+    fn dispatch(&self) {
+        let mut state = self.lock();
+        while state.running < state.max_concurrent {
+            let Some(waiter) = state.waiting.pop() else {
+                break;
+            };
+            // Fails if the waiter gave up in the meantime
+            if waiter.wake.send(()).is_ok() {
+                state.running += 1;
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use super::client::ModelInfo;
+
+/// What a generation is for; decides which model is tried first and which
+/// timeout applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmTask {
+    /// Short process explanations (`generate_fast`)
+    Explanation,
+    /// Log and security analyses (`generate`)
+    Analysis,
+    /// JSON answers (`generate_json`)
+    Structured,
+    /// Conversations and tool calls (`chat`, `chat_with_tools`)
+    Chat,
+}
+
+impl LlmTask {
+    pub const ALL: [LlmTask; 4] = [LlmTask::Explanation, LlmTask::Analysis, LlmTask::Structured, LlmTask::Chat];
+
+    /// Explanations want a quick answer, everything else the best one
+    fn prefers_small(self) -> bool {
+        self == LlmTask::Explanation
+    }
+}
+
+/// Model chain and timeout of a task, as shown in the settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskModels {
+    pub task: LlmTask,
+    /// Models in the order they are tried; only the first few are attempted
+    pub models: Vec<String>,
+    /// `None` when the general request timeout applies
+    pub timeout_seconds: Option<u64>,
+}
+
+/// Models to try for `task`, in order.
+///
+/// The configured models come first: the fast model and then the primary
+/// model for explanations, the primary model for everything else. With
+/// `auto_select` the other installed models follow, sorted by size. When
+/// the installed models are unknown, the configured ones are returned
+/// unchecked so Ollama reports what is missing.
+pub(crate) fn model_chain(
+    task: LlmTask,
+    model: &str,
+    fast_model: &str,
+    installed: Option<&[ModelInfo]>,
+    embedding_model: &str,
+    auto_select: bool,
+) -> Vec<String> {
+    let configured: Vec<&str> = if task.prefers_small() { vec![fast_model, model] } else { vec![model] };
+
+    let Some(installed) = installed else {
+        let mut chain: Vec<String> = Vec::new();
+        for name in configured {
+            if !chain.iter().any(|c| same_model(c, name)) {
+                chain.push(name.to_string());
+            }
+        }
+        return chain;
+    };
+
+    let mut chain: Vec<String> = configured
+        .iter()
+        .filter_map(|name| installed.iter().find(|m| same_model(&m.name, name)))
+        .map(|m| m.name.clone())
+        .collect();
+    chain.dedup();
+
+    if auto_select {
+        let mut others: Vec<&ModelInfo> = installed
+            .iter()
+            .filter(|m| !is_embedding_model(m, embedding_model) && !chain.contains(&m.name))
+            .collect();
+        others.sort_by_key(|m| m.size);
+        if !task.prefers_small() {
+            others.reverse();
+        }
+        chain.extend(others.into_iter().map(|m| m.name.clone()));
+    }
+
+    if chain.is_empty() {
+        chain.push(configured[0].to_string());
+    }
+    chain
+}
+
+/// Names without a tag match `:latest`
+pub(crate) fn same_model(a: &str, b: &str) -> bool {
+    a == b || a.strip_suffix(":latest") == Some(b) || b.strip_suffix(":latest") == Some(a)
+}
+
+/// Embedding models can't generate text; they are recognized by name or by
+/// their BERT model family
+fn is_embedding_model(model: &ModelInfo, embedding_model: &str) -> bool {
+    let name = model.name.to_lowercase();
+    same_model(&model.name, embedding_model)
+        || name.contains("embed")
+        || name.contains("minilm")
+        || name.starts_with("bge")
+        || model.details.family.to_lowercase().contains("bert")
+}
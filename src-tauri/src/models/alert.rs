@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// User-defined alert, checked on every tick of the `AlertEngine`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    /// Limits the rule to one service; `None` watches the whole system
+    /// (CPU/memory) or every service
+    #[serde(default)]
+    pub service_id: Option<String>,
+    pub condition: AlertCondition,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// CPU usage above `percent` for at least `minutes`
+    CpuAbove { percent: f32, minutes: u32 },
+    /// Memory use above `bytes` for at least `minutes`
+    MemoryAbove { bytes: u64, minutes: u32 },
+    /// A running service stopped or failed
+    ServiceStopped,
+    /// A listener appeared on a non-loopback address
+    NewPublicPort,
+}
+
+/// A rule that fired. Raised once per breach; the rule fires again only
+/// after the condition cleared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    pub id: String,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub service_id: Option<String>,
+    pub message: String,
+    /// Measured value for threshold rules (percent or bytes)
+    pub value: Option<f64>,
+    pub triggered_at: DateTime<Utc>,
+}
@@ -14,7 +14,7 @@ pub struct AuditEntry {
     pub details: serde_json::Value,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EventType {
     ServiceStart,
@@ -25,6 +25,10 @@ pub enum EventType {
     PrivilegeEscalation,
     LlmAnalysis,
     PortScan,
+    /// Removal of Docker images or volumes
+    Cleanup,
+    /// An alert rule fired
+    Alert,
 }
 
 impl AuditEntry {
@@ -42,3 +46,57 @@ impl AuditEntry {
         }
     }
 }
+
+/// Filter for audit entries; empty fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditQuery {
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive
+    pub until: Option<DateTime<Utc>>,
+    /// Matched case-insensitively against the service ID, operation and details
+    pub service: Option<String>,
+    #[serde(default)]
+    pub event_types: Vec<EventType>,
+    pub success: Option<bool>,
+    /// At least one has to occur in the entry
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+impl AuditQuery {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        if self.since.is_some_and(|since| entry.timestamp < since)
+            || self.until.is_some_and(|until| entry.timestamp >= until)
+            || self.success.is_some_and(|success| entry.success != success)
+            || (!self.event_types.is_empty() && !self.event_types.contains(&entry.event_type))
+        {
+            return false;
+        }
+
+        let text = || {
+            format!(
+                "{} {} {} {}",
+                entry.service_id.as_deref().unwrap_or_default(),
+                entry.operation,
+                entry.error_message.as_deref().unwrap_or_default(),
+                entry.details
+            )
+            .to_lowercase()
+        };
+        if let Some(service) = self.service.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            if !text().contains(&service.to_lowercase()) {
+                return false;
+            }
+        }
+        let keywords: Vec<String> = self
+            .keywords
+            .iter()
+            .map(|k| k.trim().to_lowercase())
+            .filter(|k| !k.is_empty())
+            .collect();
+        keywords.is_empty() || {
+            let text = text();
+            keywords.iter().any(|k| text.contains(k))
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use crate::models::alert::AlertRule;
+use crate::models::port::Protocol;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
@@ -6,6 +9,62 @@ pub struct Config {
     pub refresh_interval_ms: u32,
     pub ollama: OllamaConfig,
     pub security: SecurityConfig,
+    /// Serve simulated services, ports and metrics instead of real data
+    #[serde(default)]
+    pub simulation_mode: bool,
+    #[serde(default)]
+    pub digest: DigestConfig,
+    #[serde(default)]
+    pub remote_viewer: RemoteViewerConfig,
+    #[serde(default)]
+    pub port_watch: PortWatchConfig,
+    #[serde(default)]
+    pub port_history: PortHistoryConfig,
+    /// MaxMind/DB-IP country database (`.mmdb`) for locating remote peers
+    #[serde(default)]
+    pub geoip_database: Option<String>,
+    /// Ports held back for team conventions. Read-only here; changed through
+    /// the reservation commands, see `PortReservations`
+    #[serde(default)]
+    pub port_reservations: Vec<PortReservation>,
+    /// Security findings accepted as known risks. Read-only here; changed
+    /// through the acknowledgement commands, see `SecurityAcknowledgements`
+    #[serde(default)]
+    pub acknowledged_issues: Vec<AcknowledgedIssue>,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    /// SSH port forwards. Read-only here; changed through the tunnel
+    /// commands, see `TunnelManager`
+    #[serde(default)]
+    pub tunnels: Vec<TunnelSpec>,
+    /// Names for ports, replacing the IANA registration, see `ServiceNames`
+    #[serde(default)]
+    pub service_names: Vec<ServiceNameOverride>,
+    #[serde(default)]
+    pub nmap: NmapConfig,
+    #[serde(default)]
+    pub monitoring_history: MonitoringHistoryConfig,
+    #[serde(default)]
+    pub alerts: AlertConfig,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default)]
+    pub flapping: FlappingConfig,
+    #[serde(default)]
+    pub resource_events: ResourceEventsConfig,
+    /// Outbound webhooks for service, alert and security events
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+    #[serde(default)]
+    pub disk_watch: DiskWatchConfig,
+    #[serde(default)]
+    pub powermetrics: PowermetricsConfig,
+    #[serde(default)]
+    pub memory_watch: MemoryWatchConfig,
+    #[serde(default)]
+    pub hash_reputation: HashReputationConfig,
+    #[serde(default)]
+    pub cloud_llm: CloudLlmConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -22,21 +81,115 @@ pub enum ThemeMode {
     Dark,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct OllamaConfig {
+    /// With `false` the LLM commands fail without contacting Ollama
     pub enabled: bool,
     pub endpoint: String,
+    /// Additional endpoints (e.g. a GPU box on the LAN) used when `endpoint` is down
+    #[serde(default)]
+    pub fallback_endpoints: Vec<String>,
     pub model: String,
+    /// Small model for quick process explanations
+    #[serde(default = "default_fast_model")]
+    pub fast_model: String,
+    /// Model for embeddings of past analyses (similarity search)
+    #[serde(default = "default_embedding_model")]
+    pub embedding_model: String,
     pub timeout_seconds: u32,
+    /// Request timeouts per task; tasks without one use `timeout_seconds`
+    #[serde(default)]
+    pub task_timeouts: TaskTimeouts,
+    /// Try other installed models when the configured one is missing or
+    /// times out: the smallest first for explanations, the largest first
+    /// for analyses
+    #[serde(default = "default_auto_select_models")]
+    pub auto_select_models: bool,
+    /// Generations allowed to run at the same time
+    #[serde(default = "default_max_concurrent_generations")]
+    pub max_concurrent_generations: u32,
+    /// Requests allowed to wait for a slot before new ones are rejected
+    #[serde(default = "default_max_queued_generations")]
+    pub max_queued_generations: u32,
+    /// How long a request waits for a slot
+    #[serde(default = "default_queue_timeout_seconds")]
+    pub queue_timeout_seconds: u32,
+    /// Language of AI answers, as ISO 639-1 code (`de`, `en`, ...) or name
+    #[serde(default = "default_language")]
+    pub language: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskTimeouts {
+    /// Short process explanations
+    #[serde(default)]
+    pub explanation_seconds: Option<u32>,
+    /// Log and security analyses
+    #[serde(default)]
+    pub analysis_seconds: Option<u32>,
+    /// JSON answers such as recommendations and search filters
+    #[serde(default)]
+    pub structured_seconds: Option<u32>,
+    /// Chat answers and action proposals
+    #[serde(default)]
+    pub chat_seconds: Option<u32>,
+}
+
+impl Default for TaskTimeouts {
+    fn default() -> Self {
+        Self {
+            explanation_seconds: Some(10),
+            analysis_seconds: None,
+            structured_seconds: None,
+            chat_seconds: None,
+        }
+    }
+}
+
+fn default_fast_model() -> String {
+    "llama3.2:1b".to_string()
+}
+
+fn default_language() -> String {
+    "de".to_string()
+}
+
+fn default_embedding_model() -> String {
+    "nomic-embed-text".to_string()
+}
+
+fn default_auto_select_models() -> bool {
+    true
+}
+
+fn default_max_concurrent_generations() -> u32 {
+    1
+}
+
+fn default_max_queued_generations() -> u32 {
+    8
+}
+
+fn default_queue_timeout_seconds() -> u32 {
+    60
 }
 
 impl Default for OllamaConfig {
     fn default() -> Self {
         Self {
-            enabled: false,
+            enabled: true,
             endpoint: "http://localhost:11434".to_string(),
+            fallback_endpoints: Vec::new(),
             model: "mistral:7b-instruct".to_string(),
+            fast_model: default_fast_model(),
+            embedding_model: default_embedding_model(),
             timeout_seconds: 30,
+            task_timeouts: TaskTimeouts::default(),
+            auto_select_models: default_auto_select_models(),
+            max_concurrent_generations: default_max_concurrent_generations(),
+            max_queued_generations: default_max_queued_generations(),
+            queue_timeout_seconds: default_queue_timeout_seconds(),
+            language: default_language(),
         }
     }
 }
@@ -46,6 +199,355 @@ pub struct SecurityConfig {
     pub audit_logging: bool,
     pub require_confirmation_for_kill: bool,
     pub privilege_cache_ttl_minutes: u32,
+    /// Destructive commands only return an impact preview and change nothing
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Scheduled weekly summary report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestConfig {
+    pub enabled: bool,
+    /// Day of the week the digest is generated on (`monday`, `tue`, ...)
+    pub weekday: String,
+    /// Local hour (0-23) from which the digest is generated
+    pub hour: u32,
+    /// Add an LLM-written summary paragraph to the report
+    pub llm_summary: bool,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            weekday: "monday".to_string(),
+            hour: 8,
+            llm_summary: false,
+        }
+    }
+}
+
+/// Read-only status page for paired devices on the LAN
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteViewerConfig {
+    /// Port of the viewer's HTTP server, 0 picks a free one
+    pub port: u16,
+    /// Paired devices have to pair again after this many hours
+    #[serde(default = "default_viewer_session_hours")]
+    pub session_hours: u32,
+}
+
+fn default_viewer_session_hours() -> u32 {
+    24
+}
+
+impl Default for RemoteViewerConfig {
+    fn default() -> Self {
+        Self {
+            port: 7421,
+            session_hours: default_viewer_session_hours(),
+        }
+    }
+}
+
+/// Diff listening ports between monitor ticks and report changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortWatchConfig {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    /// Desktop notification when a port opens on a non-loopback address
+    pub notify_public_ports: bool,
+}
+
+impl Default for PortWatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_seconds: 5,
+            notify_public_ports: true,
+        }
+    }
+}
+
+/// Periodic port usage snapshots for looking back in time, see `PortHistory`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortHistoryConfig {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    /// Days of snapshots to keep
+    pub retention_days: u32,
+}
+
+impl Default for PortHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_seconds: 60,
+            retention_days: 14,
+        }
+    }
+}
+
+/// Service, CPU/memory and port samples in SQLite for charts, see `MonitoringHistory`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitoringHistoryConfig {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    /// Days of samples to keep
+    pub retention_days: u32,
+}
+
+impl Default for MonitoringHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_seconds: 60,
+            retention_days: 30,
+        }
+    }
+}
+
+/// When restarts count as a crash loop, see `FlapDetector`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlappingConfig {
+    /// A service is flapping with more restarts than this within the window
+    pub max_restarts: u32,
+    pub window_minutes: u32,
+}
+
+impl Default for FlappingConfig {
+    fn default() -> Self {
+        Self {
+            max_restarts: 3,
+            window_minutes: 10,
+        }
+    }
+}
+
+/// How much CPU or memory usage must change before the monitor emits
+/// `ServiceResourceChanged`, see `ResourceTracker`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceEventsConfig {
+    pub enabled: bool,
+    /// Percentage points of CPU usage
+    pub cpu_delta_percent: f32,
+    pub memory_delta_bytes: u64,
+}
+
+impl Default for ResourceEventsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cpu_delta_percent: 5.0,
+            memory_delta_bytes: 50 * 1024 * 1024,
+        }
+    }
+}
+
+/// An HTTP endpoint that receives events as JSON, see `WebhookDispatcher`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    /// Signs the body with HMAC-SHA256 when set
+    #[serde(default)]
+    pub secret: Option<String>,
+    pub enabled: bool,
+    /// Events to deliver; empty delivers all of them
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    ServiceStatus,
+    Alert,
+    SecurityFinding,
+}
+
+/// Desktop notifications per event category, see `Notifier`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// A running service went into the error state
+    pub service_crashed: bool,
+    /// A security scan found critical issues
+    pub security_critical: bool,
+    /// Watched ports: new public listeners and taken reservations
+    pub port_opened: bool,
+    /// Fired alert rules
+    pub alerts: bool,
+    /// A disk crossed the fill threshold of `DiskWatchConfig`
+    pub disk_space: bool,
+    /// Memory is about to run out, see `MemoryWatchConfig`
+    #[serde(default = "default_memory_pressure")]
+    pub memory_pressure: bool,
+    /// Closing the window hides it to the tray, so monitoring and
+    /// notifications keep running
+    pub run_in_background: bool,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            service_crashed: true,
+            security_critical: true,
+            port_opened: true,
+            alerts: true,
+            disk_space: true,
+            memory_pressure: true,
+            run_in_background: true,
+        }
+    }
+}
+
+fn default_memory_pressure() -> bool {
+    true
+}
+
+/// Warn when a filesystem fills up, see `DiskWatcher`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskWatchConfig {
+    pub enabled: bool,
+    pub threshold_percent: f32,
+    pub interval_seconds: u64,
+}
+
+impl Default for DiskWatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_percent: 90.0,
+            interval_seconds: 60,
+        }
+    }
+}
+
+/// Warn before memory runs out, see `MemoryWatcher`. Any one condition
+/// counts as pressure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryWatchConfig {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    /// Available memory below this share of the total
+    pub available_percent: f32,
+    /// Swap grew faster than this over the trend window
+    pub swap_growth_mb_per_minute: u64,
+    /// Memory runs out within this many minutes at the current trend
+    pub exhaustion_minutes: u32,
+    /// Processes attached to the event, by resident memory
+    pub top_consumers: usize,
+}
+
+impl Default for MemoryWatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_seconds: 10,
+            available_percent: 10.0,
+            swap_growth_mb_per_minute: 256,
+            exhaustion_minutes: 5,
+            top_consumers: 5,
+        }
+    }
+}
+
+/// Apple GPU utilization from a background `powermetrics` process, see
+/// `PowermetricsSampler`. Needs a sudoers rule for passwordless
+/// `/usr/bin/powermetrics`, so it is off by default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowermetricsConfig {
+    pub enabled: bool,
+    pub interval_ms: u64,
+}
+
+impl Default for PowermetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_ms: 1000,
+        }
+    }
+}
+
+/// Online lookup of executable hashes, see `HashReputation`. Only the
+/// SHA-256 leaves the machine, never the file, but it is still off by default.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HashReputationConfig {
+    pub enabled: bool,
+    pub provider: ReputationProvider,
+    /// VirusTotal API key or abuse.ch Auth-Key; both services require one
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReputationProvider {
+    #[default]
+    MalwareBazaar,
+    VirusTotal,
+}
+
+/// Cloud model used instead of Ollama for generations, see `CloudLlm`.
+/// Prompts are sanitized and every request is audit logged, but they still
+/// leave the machine, so it is off by default.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct CloudLlmConfig {
+    pub enabled: bool,
+    pub provider: CloudProvider,
+    pub api_key: Option<String>,
+    /// Empty for the provider's default model
+    #[serde(default)]
+    pub model: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CloudProvider {
+    #[default]
+    Anthropic,
+    OpenAi,
+}
+
+/// Alert rules and how often they are checked, see `AlertEngine`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertConfig {
+    pub interval_seconds: u64,
+    pub rules: Vec<AlertRule>,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 30,
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// Opt-in nmap backend for port scans, see `NmapScanner`. The built-in
+/// scanner is used when disabled or when nmap is missing or fails.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NmapConfig {
+    pub enabled: bool,
+    /// Probe open ports for product and version (`-sV --version-light`)
+    pub version_detection: bool,
+    /// Guess the operating system (`-O`); needs root
+    pub os_detection: bool,
+    /// Timing template (`-T0`..`-T4`); `-T5` is not offered
+    pub timing: u8,
+}
+
+impl Default for NmapConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            version_detection: true,
+            os_detection: false,
+            timing: 3,
+        }
+    }
 }
 
 impl Default for SecurityConfig {
@@ -54,6 +556,100 @@ impl Default for SecurityConfig {
             audit_logging: true,
             require_confirmation_for_kill: true,
             privilege_cache_ttl_minutes: 15,
+            dry_run: false,
         }
     }
 }
+
+/// A port kept free for a specific use, e.g. `3000` for the frontend dev server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortReservation {
+    pub port: u16,
+    pub label: String,
+    /// Process expected on the port; any other listener counts as a conflict
+    #[serde(default)]
+    pub owner: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A security finding accepted as a known risk, e.g. plain HTTP on port 80
+/// behind a reverse proxy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcknowledgedIssue {
+    /// `SecurityIssue::id`
+    pub issue_id: String,
+    pub reason: String,
+    pub acknowledged_at: DateTime<Utc>,
+    /// The finding counts again from this time on
+    #[serde(default)]
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl AcknowledgedIssue {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_none_or(|expires| expires > now)
+    }
+}
+
+/// User-defined name for a port, e.g. `3000` → `frontend`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceNameOverride {
+    pub port: u16,
+    /// `None` names the port for TCP and UDP
+    #[serde(default)]
+    pub protocol: Option<Protocol>,
+    pub name: String,
+}
+
+/// Directory overrides; `None` keeps the OS default (or the portable directory)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct StorageConfig {
+    pub config_dir: Option<String>,
+    pub data_dir: Option<String>,
+    pub log_dir: Option<String>,
+}
+
+/// An SSH port forward, e.g. `localhost:5432` to `prod-db:5432` via a bastion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TunnelSpec {
+    /// Generated when empty
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub kind: TunnelKind,
+    /// Port opened on this machine (local) or on the SSH server (remote)
+    pub bind_port: u16,
+    /// Defaults to the loopback interface
+    #[serde(default)]
+    pub bind_address: Option<String>,
+    /// Destination as seen from the SSH server (local) or from this machine (remote)
+    pub target_host: String,
+    pub target_port: u16,
+    /// SSH server as `user@host` or a `Host` alias from `~/.ssh/config`
+    pub ssh_host: String,
+    #[serde(default)]
+    pub ssh_port: Option<u16>,
+    /// Bastion for `ssh -J`
+    #[serde(default)]
+    pub jump_host: Option<String>,
+    #[serde(default)]
+    pub identity_file: Option<String>,
+    #[serde(default = "default_auto_reconnect")]
+    pub auto_reconnect: bool,
+    /// Start together with the app
+    #[serde(default)]
+    pub autostart: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TunnelKind {
+    /// `ssh -L`: a local port reaches a host behind the SSH server
+    Local,
+    /// `ssh -R`: a port on the SSH server reaches a host on this side
+    Remote,
+}
+
+fn default_auto_reconnect() -> bool {
+    true
+}
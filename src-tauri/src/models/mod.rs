@@ -4,6 +4,7 @@ pub mod service;
 pub mod port;
 pub mod config;
 pub mod audit;
+pub mod alert;
 
 // Re-export main types
 pub use service::Service;
@@ -4,21 +4,144 @@ use serde::{Deserialize, Serialize};
 pub struct PortInfo {
     pub port: u16,
     pub protocol: Protocol,
+    pub family: AddressFamily,
+    /// Local address the socket is bound to (`127.0.0.1`, `0.0.0.0`, `::`, `192.168.1.5`, ...)
+    pub bind_address: String,
     pub status: PortStatus,
     pub process_name: Option<String>,
     pub pid: Option<u32>,
+    /// Protocol identified by the scanner's fingerprint pass (`ssh`, `http`, `redis`, ...)
+    pub detected_service: Option<String>,
+    /// Product/version announced by the service, e.g. `OpenSSH_9.6p1`
+    pub version: Option<String>,
+    /// Account of the owning process
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Registered or user-assigned name of the port, e.g. `postgresql`
+    #[serde(default)]
+    pub service_name: Option<String>,
+    /// TCP connect round-trip time, only set for scan results
+    #[serde(default)]
+    pub latency_ms: Option<f64>,
+    /// Operating system of the host, only set by nmap scans with OS detection
+    #[serde(default)]
+    pub os_guess: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl PortInfo {
+    /// Bound to all interfaces (`0.0.0.0`, `::`, `*`)
+    pub fn is_wildcard(&self) -> bool {
+        matches!(self.bind_address.as_str(), "*" | "0.0.0.0" | "::" | "")
+    }
+
+    /// Only reachable from this machine
+    pub fn is_loopback(&self) -> bool {
+        let address = self.bind_address.strip_prefix("::ffff:").unwrap_or(&self.bind_address);
+        match address.parse::<std::net::IpAddr>() {
+            Ok(ip) => ip.is_loopback(),
+            Err(_) => address == "localhost",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Protocol {
     Tcp,
     Udp,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressFamily {
+    #[default]
+    Ipv4,
+    Ipv6,
+}
+
+impl AddressFamily {
+    /// Guess the family from a local address (`::`, `fe80::1`, `0.0.0.0`, `*`)
+    pub fn from_address(address: &str) -> Self {
+        if address.contains(':') {
+            AddressFamily::Ipv6
+        } else {
+            AddressFamily::Ipv4
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum PortStatus {
     Occupied,
     Free,
 }
+
+/// A socket with a remote peer: TCP in any state but LISTEN, or a connected
+/// UDP socket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Connection {
+    pub protocol: Protocol,
+    pub family: AddressFamily,
+    pub local_address: String,
+    pub local_port: u16,
+    pub remote_address: String,
+    pub remote_port: u16,
+    /// `ESTABLISHED`, `SYN_SENT`, `TIME_WAIT`, `CLOSE_WAIT`, ...
+    pub state: String,
+    pub process_name: Option<String>,
+    pub pid: Option<u32>,
+    /// Account of the owning process
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Registered name of the remote port, e.g. `https`
+    #[serde(default)]
+    pub remote_service: Option<String>,
+}
+
+/// A local IPC endpoint that services listen on instead of (or besides) a port
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpcEndpoint {
+    pub kind: IpcKind,
+    /// Socket path (`/run/docker.sock`, `@abstract` on Linux) or `\\.\pipe\name`
+    pub path: String,
+    /// `LISTEN` for stream sockets accepting connections, `BOUND` for datagram sockets
+    pub state: Option<String>,
+    pub process_name: Option<String>,
+    pub pid: Option<u32>,
+    /// Account of the owning process
+    #[serde(default)]
+    pub user: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IpcKind {
+    UnixSocket,
+    NamedPipe,
+}
+
+/// Host-to-container port mapping for new containers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortMapping {
+    pub host_port: u16,
+    pub container_port: u16,
+    #[serde(default = "default_mapping_protocol")]
+    pub protocol: Protocol,
+}
+
+fn default_mapping_protocol() -> Protocol {
+    Protocol::Tcp
+}
+
+/// A host port a container wants to publish that something else already holds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortCollision {
+    pub mapping: PortMapping,
+    /// The conflicting listener
+    pub listener: PortInfo,
+    /// Service holding the port, if it could be attributed to one
+    pub service_id: Option<String>,
+    /// `postgres (docker)` or `nginx (PID 812)`
+    pub owner: String,
+}
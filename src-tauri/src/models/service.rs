@@ -17,6 +17,73 @@ pub struct Service {
     pub memory_bytes: Option<u64>,
     /// Memory usage as percentage of total system memory
     pub memory_percent: Option<f32>,
+    /// CPU usage of the process and all its descendants, e.g. nginx workers
+    #[serde(default)]
+    pub cpu_usage_total: Option<f32>,
+    /// Memory of the process and all its descendants in bytes
+    #[serde(default)]
+    pub memory_total: Option<u64>,
+    /// GPU memory and utilization of the main process, `None` if it doesn't use the GPU
+    #[serde(default)]
+    pub gpu_usage: Option<GpuProcessUsage>,
+    /// Energy and QoS of the main process, macOS only
+    #[serde(default)]
+    pub energy: Option<EnergyUsage>,
+    /// Bytes received since the container started, Docker only
+    #[serde(default)]
+    pub network_rx_bytes: Option<u64>,
+    /// Bytes sent since the container started, Docker only
+    #[serde(default)]
+    pub network_tx_bytes: Option<u64>,
+    /// Account the process runs as, `None` if not running or not readable
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Restarts the service monitor saw within the flapping window
+    #[serde(default)]
+    pub recent_restarts: u32,
+    /// Restarted more often than `FlappingConfig` allows
+    #[serde(default)]
+    pub flapping: bool,
+    /// Docker Compose project from the container labels
+    #[serde(default)]
+    pub compose_project: Option<String>,
+    /// Service within the Compose project, shared by its replicas
+    #[serde(default)]
+    pub compose_service: Option<String>,
+}
+
+/// What one process uses of the GPUs, summed over all of them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GpuProcessUsage {
+    pub memory_bytes: Option<u64>,
+    /// Busiest engine (NVIDIA: streaming multiprocessors)
+    pub utilization_percent: Option<f32>,
+}
+
+/// Energy use of one process
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnergyUsage {
+    /// Activity Monitor's "Energy Impact" score, only while the powermetrics
+    /// sampler runs
+    pub energy_impact: Option<f32>,
+    /// Energy the kernel billed to the process since the previous sample
+    pub power_milliwatts: Option<f32>,
+    /// CPU time per QoS class since the previous sample
+    pub qos: Option<QosCpuTime>,
+}
+
+/// CPU time in ms per second by quality-of-service class. Background work
+/// running at `default` or above keeps the performance cores busy, while
+/// `background` and `maintenance` go to the efficiency cores.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QosCpuTime {
+    pub user_interactive: f32,
+    pub user_initiated: f32,
+    /// Includes threads without a QoS class (legacy)
+    pub default: f32,
+    pub utility: f32,
+    pub background: f32,
+    pub maintenance: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -37,3 +104,95 @@ pub enum ServiceType {
     WindowsService,
     Process,
 }
+
+/// User-supplied definition for promoting an ad-hoc process into a managed service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomServiceSpec {
+    /// Service name, used as the launchd label or systemd unit name
+    pub name: String,
+    pub description: Option<String>,
+    /// Executable to run (absolute path recommended)
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: std::collections::HashMap<String, String>,
+    pub working_directory: Option<String>,
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
+    /// Start the service automatically at login
+    #[serde(default)]
+    pub auto_start: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartPolicy {
+    Never,
+    #[default]
+    OnFailure,
+    Always,
+}
+
+/// Services a Windows service depends on and services depending on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceDependencies {
+    pub service: String,
+    /// Services that must run before this one can start
+    pub dependencies: Vec<String>,
+    /// Running services that must be stopped before this one can stop,
+    /// in the order they can be stopped
+    pub running_dependents: Vec<String>,
+}
+
+/// Why a provider's data may be incomplete
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderWarningKind {
+    /// Access was denied (e.g. Docker socket, other users' processes)
+    PermissionDenied,
+    /// Provider could not be queried at all
+    Unavailable,
+    /// Provider returned data, but some entries are missing details
+    Partial,
+}
+
+/// Explains a gap in discovery or scan results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderWarning {
+    pub provider: String,
+    pub kind: ProviderWarningKind,
+    pub message: String,
+}
+
+impl ProviderWarning {
+    pub fn new(provider: &str, kind: ProviderWarningKind, message: impl Into<String>) -> Self {
+        Self {
+            provider: provider.to_string(),
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Classify a provider error as permission problem or general failure
+    pub fn from_error(provider: &str, error: &(dyn std::error::Error + Send + Sync)) -> Self {
+        let message = error.to_string();
+        let lower = message.to_lowercase();
+        let kind = if lower.contains("permission denied")
+            || lower.contains("access is denied")
+            || lower.contains("operation not permitted")
+        {
+            ProviderWarningKind::PermissionDenied
+        } else {
+            ProviderWarningKind::Unavailable
+        };
+        Self::new(provider, kind, message)
+    }
+}
+
+/// Discovered services together with warnings about incomplete providers
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiscoveryResult {
+    pub services: Vec<Service>,
+    pub warnings: Vec<ProviderWarning>,
+}
@@ -0,0 +1,368 @@
+use serde::{Deserialize, Serialize};
+use super::ParseReport;
+
+/// One GPU as reported by nvidia-smi or rocm-smi
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuRecord {
+    pub name: String,
+    pub usage_percent: Option<f32>,
+    pub memory_used_bytes: Option<u64>,
+    pub memory_total_bytes: Option<u64>,
+    pub temperature_celsius: Option<f32>,
+    pub power_watts: Option<f32>,
+    pub frequency_mhz: Option<u32>,
+}
+
+/// One `gpu_power` sample from `powermetrics`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PowermetricsGpu {
+    pub usage_percent: Option<f32>,
+    pub frequency_mhz: Option<u32>,
+    pub power_watts: Option<f32>,
+}
+
+/// Parse `nvidia-smi --query-gpu=name,utilization.gpu,memory.used,memory.total,temperature.gpu,power.draw --format=csv,noheader,nounits`
+pub fn parse_nvidia_smi(output: &str) -> ParseReport<GpuRecord> {
+    let mut report = ParseReport::new();
+
+    for (index, line) in output.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
+        if parts.len() < 6 {
+            report.push_issue(index + 1, line, "expected 6 comma-separated fields");
+            continue;
+        }
+
+        // Unsupported fields are reported as "[N/A]" and simply become None;
+        // anything else that isn't a number means the line is garbled
+        let unsupported = |value: &str| value.trim_matches(|c| c == '[' || c == ']') == "N/A";
+        if let Some(value) = parts[1..6].iter().find(|v| !unsupported(v) && v.parse::<f64>().is_err()) {
+            report.push_issue(index + 1, line, format!("invalid number '{}'", value));
+            continue;
+        }
+
+        let mib = |value: &str| value.parse::<u64>().ok().map(|m| m * 1024 * 1024);
+        report.items.push(GpuRecord {
+            name: parts[0].to_string(),
+            usage_percent: parts[1].parse().ok(),
+            memory_used_bytes: mib(parts[2]),
+            memory_total_bytes: mib(parts[3]),
+            temperature_celsius: parts[4].parse().ok(),
+            power_watts: parts[5].parse().ok(),
+            frequency_mhz: None,
+        });
+    }
+
+    report
+}
+
+/// Parse `rocm-smi --showuse --showmeminfo vram --showtemp --json`
+pub fn parse_rocm_smi(output: &str) -> ParseReport<GpuRecord> {
+    let mut report = ParseReport::new();
+
+    let json = match serde_json::from_str::<serde_json::Value>(output) {
+        Ok(json) => json,
+        Err(e) => {
+            report.push_issue(e.line(), "", format!("invalid JSON: {}", e));
+            return report;
+        }
+    };
+
+    let Some(cards) = json.as_object() else {
+        report.push_issue(1, "", "expected a JSON object");
+        return report;
+    };
+
+    let number = |info: &serde_json::Value, key: &str| {
+        info.get(key).and_then(|v| v.as_str()).and_then(|s| s.trim().parse::<f64>().ok())
+    };
+
+    for (name, info) in cards.iter().filter(|(name, _)| name.starts_with("card")) {
+        // JSON has no useful line numbers, like the cases above
+        if !info.is_object() {
+            report.push_issue(1, name, "expected an object per card");
+            continue;
+        }
+        report.items.push(GpuRecord {
+            name: format!("AMD GPU {}", name),
+            usage_percent: number(info, "GPU use (%)").map(|v| v as f32),
+            memory_used_bytes: number(info, "VRAM Total Used Memory (B)").map(|v| v as u64),
+            memory_total_bytes: number(info, "VRAM Total Memory (B)").map(|v| v as u64),
+            temperature_celsius: number(info, "Temperature (Sensor edge) (C)").map(|v| v as f32),
+            power_watts: None,
+            frequency_mhz: None,
+        });
+    }
+
+    report
+}
+
+/// Parse the text output of `powermetrics --samplers gpu_power`, one item per
+/// `*** Sampled system activity` block:
+///
+/// ```text
+/// **** GPU usage ****
+///
+/// GPU HW active frequency: 444 MHz
+/// GPU HW active residency:  12.85% (389 MHz:  11% 486 MHz: .47% ...)
+/// GPU idle residency:  87.15%
+/// GPU Power: 36 mW
+/// ```
+///
+/// macOS 12 and older print "GPU active ..." without "HW".
+pub fn parse_powermetrics_gpu(output: &str) -> ParseReport<PowermetricsGpu> {
+    let mut report = ParseReport::new();
+    let mut current: Option<PowermetricsGpu> = None;
+
+    for (index, line) in output.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with("*** Sampled system activity") {
+            report.items.extend(current.take());
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        // The leading number, ignoring the unit and the per-frequency breakdown
+        let mut words = value.split_whitespace();
+        let number = words
+            .next()
+            .map(|v| v.trim_end_matches('%'))
+            .and_then(|v| v.parse::<f32>().ok());
+        let unit = words.next().unwrap_or_default();
+
+        match key.trim() {
+            "GPU HW active frequency" | "GPU active frequency" => {
+                current.get_or_insert_with(PowermetricsGpu::default).frequency_mhz = number.map(|mhz| mhz.round() as u32);
+            }
+            "GPU HW active residency" | "GPU active residency" => {
+                current.get_or_insert_with(PowermetricsGpu::default).usage_percent = number;
+            }
+            "GPU idle residency" => {
+                let sample = current.get_or_insert_with(PowermetricsGpu::default);
+                sample.usage_percent = sample.usage_percent.or(number.map(|idle| 100.0 - idle));
+            }
+            "GPU Power" => {
+                let watts = match unit {
+                    "mW" => number.map(|mw| mw / 1000.0),
+                    "W" => number,
+                    _ => None,
+                };
+                if watts.is_none() {
+                    report.push_issue(index + 1, line, "expected a power value in mW or W");
+                }
+                current.get_or_insert_with(PowermetricsGpu::default).power_watts = watts;
+            }
+            _ => {}
+        }
+    }
+    report.items.extend(current);
+
+    report
+}
+
+/// One process in `nvidia-smi pmon`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuProcessRecord {
+    pub pid: u32,
+    /// Share of the streaming multiprocessors
+    pub sm_percent: Option<f32>,
+    /// Framebuffer memory
+    pub memory_bytes: Option<u64>,
+}
+
+/// Parse `nvidia-smi pmon -c 1 -s um`. The columns differ between driver
+/// versions, so they are located through the first header line:
+///
+/// ```text
+/// # gpu         pid   type     sm    mem    enc    dec    jpg    ofa     fb   command
+/// # Idx           #    C/G      %      %      %      %      %      %     MB   name
+///     0        1712     G      3      1      -      -      -      -    245   Xorg
+/// ```
+pub fn parse_nvidia_pmon(output: &str) -> ParseReport<GpuProcessRecord> {
+    let mut report = ParseReport::new();
+    let mut columns: Option<Vec<String>> = None;
+
+    for (index, line) in output.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('#') {
+            if columns.is_none() {
+                columns = Some(header.split_whitespace().map(str::to_lowercase).collect());
+            }
+            continue;
+        }
+        let Some(columns) = &columns else {
+            report.push_issue(index + 1, line, "data before the header line");
+            continue;
+        };
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let field = |name: &str| {
+            columns
+                .iter()
+                .position(|c| c == name)
+                .and_then(|i| fields.get(i))
+                .filter(|v| **v != "-")
+        };
+        // Idle GPUs print a single row with "-" as the PID
+        let Some(pid) = field("pid") else {
+            continue;
+        };
+        let Ok(pid) = pid.parse::<u32>() else {
+            report.push_issue(index + 1, line, "invalid PID");
+            continue;
+        };
+        report.items.push(GpuProcessRecord {
+            pid,
+            sm_percent: field("sm").and_then(|v| v.parse().ok()),
+            memory_bytes: field("fb").and_then(|v| v.parse::<u64>().ok()).map(|mb| mb * 1024 * 1024),
+        });
+    }
+
+    report
+}
+
+/// A DRM client from `/proc/<pid>/fdinfo/<fd>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrmClient {
+    /// Shared by all file descriptors of the same client
+    pub client_id: String,
+    /// Busy time per engine in nanoseconds since the client was opened
+    pub engines_ns: Vec<(String, u64)>,
+    /// Dedicated memory (VRAM, or "local" on Intel discrete GPUs)
+    pub memory_bytes: Option<u64>,
+}
+
+/// Parse the DRM usage stats of one fdinfo file
+/// (https://docs.kernel.org/gpu/drm-usage-stats.html), `None` for file
+/// descriptors that are not DRM clients:
+///
+/// ```text
+/// drm-driver:     amdgpu
+/// drm-client-id:  42
+/// drm-engine-gfx: 193447520 ns
+/// drm-memory-vram:        65536 KiB
+/// ```
+pub fn parse_drm_fdinfo(content: &str) -> Option<DrmClient> {
+    let mut client_id = None;
+    let mut engines_ns = Vec::new();
+    // Newer kernels print drm-total-* next to the legacy drm-memory-*
+    let mut total_bytes: Option<u64> = None;
+    let mut legacy_bytes: Option<u64> = None;
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim();
+        let mut words = value.split_whitespace();
+        let number = words.next().and_then(|v| v.parse::<u64>().ok());
+        let unit = words.next().unwrap_or_default();
+
+        if key == "drm-client-id" {
+            client_id = Some(value.trim().to_string());
+        } else if let Some(engine) = key.strip_prefix("drm-engine-") {
+            // drm-engine-capacity-<engine> is the number of engines, not a time
+            if !engine.starts_with("capacity-") {
+                engines_ns.extend(number.map(|ns| (engine.to_string(), ns)));
+            }
+        } else {
+            let (sum, region) = if let Some(region) = key.strip_prefix("drm-total-") {
+                (&mut total_bytes, region)
+            } else if let Some(region) = key.strip_prefix("drm-memory-") {
+                (&mut legacy_bytes, region)
+            } else {
+                continue;
+            };
+            if !(region.starts_with("vram") || region.starts_with("local")) {
+                continue;
+            }
+            if let Some(n) = number {
+                let bytes = match unit {
+                    "KiB" => n * 1024,
+                    "MiB" => n * 1024 * 1024,
+                    _ => n,
+                };
+                *sum = Some(sum.unwrap_or(0) + bytes);
+            }
+        }
+    }
+
+    Some(DrmClient {
+        client_id: client_id?,
+        engines_ns,
+        memory_bytes: total_bytes.or(legacy_bytes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nvidia_smi_fixture() {
+        let output = "\
+NVIDIA GeForce RTX 3080, 12, 2048, 10240, 45, 115.32
+Tesla T4, [N/A], 0, 15360, 38, [N/A]
+NVIDIA A100-SXM4-40GB, 97, 39000
+Quadro P2000, 5 %, 300, 5120, 40, 20.1
+";
+        let report = parse_nvidia_smi(output);
+        assert_eq!(report.items.len(), 2);
+
+        let rtx = &report.items[0];
+        assert_eq!(rtx.name, "NVIDIA GeForce RTX 3080");
+        assert_eq!(rtx.usage_percent, Some(12.0));
+        assert_eq!(rtx.memory_used_bytes, Some(2048 * 1024 * 1024));
+        assert_eq!(rtx.memory_total_bytes, Some(10240 * 1024 * 1024));
+        assert_eq!((rtx.temperature_celsius, rtx.power_watts), (Some(45.0), Some(115.32)));
+
+        // Unsupported fields
+        assert_eq!((report.items[1].usage_percent, report.items[1].power_watts), (None, None));
+
+        let lines: Vec<usize> = report.issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, [3, 4]);
+        assert!(report.issues[1].reason.contains("5 %"));
+    }
+
+    #[test]
+    fn rocm_smi_fixture() {
+        let output = r#"{
+            "card0": {
+                "GPU use (%)": "37",
+                "VRAM Total Memory (B)": "17163091968",
+                "VRAM Total Used Memory (B)": "1073741824",
+                "Temperature (Sensor edge) (C)": "52.0"
+            },
+            "card1": "unavailable",
+            "system": {"Driver version": "6.7.0"}
+        }"#;
+        let report = parse_rocm_smi(output);
+        assert_eq!(report.items.len(), 1);
+        let card = &report.items[0];
+        assert_eq!(card.name, "AMD GPU card0");
+        assert_eq!(card.usage_percent, Some(37.0));
+        assert_eq!((card.memory_used_bytes, card.memory_total_bytes), (Some(1073741824), Some(17163091968)));
+        assert_eq!(card.temperature_celsius, Some(52.0));
+
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].content, "card1");
+    }
+
+    #[test]
+    fn truncated_rocm_smi_output_is_an_issue() {
+        let report = parse_rocm_smi("{\n  \"card0\": {\n    \"GPU use (%)\": \"3");
+        assert!(report.items.is_empty());
+        assert_eq!(report.issues.len(), 1);
+        assert!(report.issues[0].reason.starts_with("invalid JSON"));
+
+        let report = parse_rocm_smi("[1, 2]");
+        assert_eq!(report.issues[0].reason, "expected a JSON object");
+    }
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use super::ParseReport;
+
+/// One row of `launchctl list`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchctlEntry {
+    pub pid: Option<u32>,
+    pub last_exit_status: Option<i32>,
+    pub label: String,
+}
+
+/// Parse `launchctl list`
+///
+/// PID Status Label
+pub fn parse_launchctl_list(output: &str) -> ParseReport<LaunchctlEntry> {
+    let mut report = ParseReport::new();
+
+    for (index, line) in output.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with("PID") {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 3 {
+            report.push_issue(index + 1, line, "expected 3 columns");
+            continue;
+        }
+
+        let pid = match parts[0] {
+            "-" => None,
+            value => match value.parse::<u32>() {
+                Ok(pid) => Some(pid),
+                Err(_) => {
+                    report.push_issue(index + 1, line, format!("invalid pid '{}'", value));
+                    continue;
+                }
+            },
+        };
+
+        let last_exit_status = match parts[1] {
+            "-" => None,
+            value => match value.parse::<i32>() {
+                Ok(status) => Some(status),
+                Err(_) => {
+                    report.push_issue(index + 1, line, format!("invalid status '{}'", value));
+                    continue;
+                }
+            },
+        };
+
+        report.items.push(LaunchctlEntry {
+            pid,
+            last_exit_status,
+            label: parts[2..].join(" "),
+        });
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launchctl_list_fixture() {
+        let output = "\
+PID\tStatus\tLabel
+-\t0\tcom.apple.SafariHistoryServiceAgent
+612\t0\tcom.apple.Finder
+-\t-9\tcom.example.crashy
+7781\t-\thomebrew.mxcl.postgresql@16
+abc\t0\tcom.example.garbled
+-\t0
+-\tOK\tcom.example.badstatus
+";
+        let report = parse_launchctl_list(output);
+        let labels: Vec<&str> = report.items.iter().map(|e| e.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            ["com.apple.SafariHistoryServiceAgent", "com.apple.Finder", "com.example.crashy", "homebrew.mxcl.postgresql@16"]
+        );
+        assert_eq!((report.items[1].pid, report.items[1].last_exit_status), (Some(612), Some(0)));
+        assert_eq!((report.items[2].pid, report.items[2].last_exit_status), (None, Some(-9)));
+        assert_eq!((report.items[3].pid, report.items[3].last_exit_status), (Some(7781), None));
+
+        let lines: Vec<usize> = report.issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, [6, 7, 8]);
+        assert!(report.issues[0].reason.contains("pid"));
+        assert!(report.issues[1].reason.contains("columns"));
+        assert!(report.issues[2].reason.contains("status"));
+    }
+}
@@ -0,0 +1,72 @@
+// Parsers for external tool output (lsof, ss, netstat, lsof -U, launchctl, systemctl, sc, nvidia-smi, rocm-smi,
+// powermetrics, DRM fdinfo, smartctl)
+//
+// All parsers are platform-independent pure functions. Lines that cannot be
+// parsed are reported as `ParseIssue`s instead of being silently dropped.
+
+pub mod sockets;
+pub mod launchctl;
+pub mod systemctl;
+pub mod sc;
+pub mod gpu;
+pub mod powermetrics;
+pub mod smartctl;
+
+use serde::{Deserialize, Serialize};
+
+/// A line of tool output that could not be parsed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParseIssue {
+    /// 1-based line number in the tool output
+    pub line: usize,
+    pub content: String,
+    pub reason: String,
+}
+
+/// Typed parse result with the lines that were skipped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParseReport<T> {
+    pub items: Vec<T>,
+    pub issues: Vec<ParseIssue>,
+}
+
+impl<T> ParseReport<T> {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            issues: Vec::new(),
+        }
+    }
+
+    pub fn push_issue(&mut self, line: usize, content: &str, reason: impl Into<String>) {
+        self.issues.push(ParseIssue {
+            line,
+            content: content.to_string(),
+            reason: reason.into(),
+        });
+    }
+
+    /// True if every non-ignored line was parsed
+    pub fn is_complete(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl<T> Default for ParseReport<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Split `host:port`, `[v6]:port` or `:::port` style addresses into host and port.
+/// Scope IDs (`[fe80::1]%eth0:123`, `fe80::1%4`) are dropped from the host.
+pub fn split_host_port(addr: &str) -> Option<(String, u16)> {
+    let (host, port) = addr.rsplit_once(':')?;
+    let port = port.parse::<u16>().ok()?;
+    let host = match host.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next()?,
+        None => host,
+    };
+    let host = host.split('%').next().unwrap_or_default();
+    Some((host.to_string(), port))
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use super::ParseReport;
+
+/// One process in the `tasks` sampler of `powermetrics`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowermetricsTask {
+    pub pid: u32,
+    pub name: String,
+    pub energy_impact: f32,
+}
+
+/// Parse the "Running tasks" table of `powermetrics --samplers tasks --show-process-energy`
+///
+/// Name                               ID     CPU ms/s  User%  Deadlines (<2 ms, 2-5 ms)  Wakeups (Intr, Pkg idle)  Energy Impact
+/// kernel_task                        0      22.68     0.00   0.00    0.00               354.92  32.96              4.71
+///
+/// Names can contain spaces, so a row is split at the header's ID column.
+/// Energy Impact is the last column. Summary rows with negative IDs
+/// (`ALL_TASKS`, `DEAD_TASKS`) are skipped.
+pub fn parse_powermetrics_tasks(output: &str) -> ParseReport<PowermetricsTask> {
+    let mut report = ParseReport::new();
+    let mut id_column: Option<usize> = None;
+
+    for (index, line) in output.lines().enumerate() {
+        if line.starts_with("*** Running tasks") {
+            id_column = None;
+            continue;
+        }
+        if line.starts_with("Name") {
+            id_column = line.find(" ID ").map(|i| i + 1);
+            continue;
+        }
+        // The table ends with the first blank line
+        let Some(column) = id_column else {
+            continue;
+        };
+        if line.trim().is_empty() {
+            id_column = None;
+            continue;
+        }
+
+        // The ID may be right-aligned and start before the column
+        let Some(split) = line.get(..=column).and_then(|head| head.rfind(' ')) else {
+            report.push_issue(index + 1, line, "row shorter than the header");
+            continue;
+        };
+        let (name, values) = line.split_at(split);
+        let values: Vec<&str> = values.split_whitespace().collect();
+        let Some(Ok(id)) = values.first().map(|v| v.parse::<i64>()) else {
+            report.push_issue(index + 1, line, "expected a process ID");
+            continue;
+        };
+        if id < 0 {
+            continue;
+        }
+        let Some(Ok(energy_impact)) = values[1..].last().map(|v| v.parse::<f32>()) else {
+            report.push_issue(index + 1, line, "expected an energy impact value");
+            continue;
+        };
+
+        report.items.push(PowermetricsTask {
+            pid: id as u32,
+            name: name.trim().to_string(),
+            energy_impact,
+        });
+    }
+
+    report
+}
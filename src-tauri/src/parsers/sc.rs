@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use super::ParseReport;
+
+/// One service block of `sc enumdepend`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScServiceEntry {
+    pub name: String,
+    pub display_name: Option<String>,
+    /// State name as printed by sc, e.g. `RUNNING` or `STOPPED`
+    pub state: Option<String>,
+}
+
+/// Parse the `DEPENDENCIES` field of `sc qc <service>`
+///
+/// ```text
+///         DEPENDENCIES       : RPCSS
+///                            : http
+/// ```
+///
+/// Load order groups (prefixed with `+`) are skipped since they can't be
+/// started by name.
+pub fn parse_qc_dependencies(output: &str) -> Vec<String> {
+    let mut dependencies = Vec::new();
+    let mut in_dependencies = false;
+
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            in_dependencies = false;
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+
+        if key == "DEPENDENCIES" {
+            in_dependencies = true;
+        } else if !key.is_empty() {
+            in_dependencies = false;
+        }
+
+        if in_dependencies && !value.is_empty() && !value.starts_with('+') {
+            dependencies.push(value.to_string());
+        }
+    }
+
+    dependencies
+}
+
+/// Parse `sc enumdepend <service> <bufsize>`
+///
+/// Each dependent starts with `SERVICE_NAME:`, followed by `DISPLAY_NAME:`
+/// and the indented status fields of `sc query`.
+pub fn parse_enumdepend(output: &str) -> ParseReport<ScServiceEntry> {
+    let mut report = ParseReport::new();
+
+    for (index, line) in output.lines().enumerate() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        match key.trim() {
+            "SERVICE_NAME" => {
+                if value.is_empty() {
+                    report.push_issue(index + 1, line, "empty service name");
+                    continue;
+                }
+                report.items.push(ScServiceEntry {
+                    name: value.to_string(),
+                    display_name: None,
+                    state: None,
+                });
+            }
+            "DISPLAY_NAME" => {
+                if let Some(entry) = report.items.last_mut() {
+                    entry.display_name = Some(value.to_string());
+                }
+            }
+            "STATE" => match (report.items.last_mut(), state_name(value)) {
+                (Some(entry), Some(state)) => entry.state = Some(state),
+                _ => report.push_issue(index + 1, line, "state without service"),
+            },
+            _ => {}
+        }
+    }
+
+    report
+}
+
+/// Parse the `STATE` field of `sc query <service>`
+///
+/// ```text
+///         STATE              : 4  RUNNING
+/// ```
+pub fn parse_query_state(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "STATE").then(|| state_name(value.trim())).flatten()
+    })
+}
+
+/// `4  RUNNING` -> `RUNNING`
+fn state_name(value: &str) -> Option<String> {
+    value
+        .split_whitespace()
+        .find(|part| part.parse::<u32>().is_err())
+        .map(|part| part.to_string())
+}
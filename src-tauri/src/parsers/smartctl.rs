@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use super::ParseReport;
+
+/// ATA attributes whose normalized value is the remaining SSD life in percent
+/// (Remaining_Lifetime_Perc, Wear_Leveling_Count, Percent_Lifetime_Remain,
+/// SSD_Life_Left, Media_Wearout_Indicator)
+const ATA_LIFE_LEFT_ATTRIBUTES: &[u64] = &[169, 177, 202, 231, 233];
+const ATA_REALLOCATED_SECTORS: u64 = 5;
+const ATA_PENDING_SECTORS: u64 = 197;
+
+/// Exit status bit: the device could not be opened, usually missing privileges
+pub const SMARTCTL_OPEN_FAILED: i64 = 1 << 1;
+
+/// One device from `smartctl --scan`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmartctlDevice {
+    /// `/dev/sda`, `/dev/nvme0`, `/dev/disk0`
+    pub name: String,
+    /// Value for `-d`, e.g. `sat` or `nvme`
+    pub device_type: Option<String>,
+}
+
+/// Health data of one drive from `smartctl --all`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmartRecord {
+    pub model: Option<String>,
+    pub serial: Option<String>,
+    /// The drive's overall self-assessment
+    pub passed: Option<bool>,
+    pub reallocated_sectors: Option<u64>,
+    pub pending_sectors: Option<u64>,
+    /// NVMe media and data integrity errors
+    pub media_errors: Option<u64>,
+    /// Share of the rated endurance used up
+    pub wear_percent: Option<u8>,
+    pub temperature_celsius: Option<f32>,
+    pub power_on_hours: Option<u64>,
+    /// Bit mask, see `SMARTCTL_OPEN_FAILED`
+    pub exit_status: i64,
+    /// First error message smartctl reported
+    pub error: Option<String>,
+}
+
+/// Parse `smartctl --scan --json`
+pub fn parse_smartctl_scan(output: &str) -> ParseReport<SmartctlDevice> {
+    let mut report = ParseReport::new();
+    let json: Value = match serde_json::from_str(output) {
+        Ok(json) => json,
+        Err(e) => {
+            report.push_issue(1, output.lines().next().unwrap_or_default(), format!("invalid JSON: {}", e));
+            return report;
+        }
+    };
+
+    for device in json["devices"].as_array().into_iter().flatten() {
+        let Some(name) = device["name"].as_str() else {
+            report.push_issue(1, &device.to_string(), "device without a name");
+            continue;
+        };
+        report.items.push(SmartctlDevice {
+            name: name.to_string(),
+            device_type: device["type"].as_str().map(String::from),
+        });
+    }
+
+    report
+}
+
+/// Parse `smartctl --all --json <device>` for ATA and NVMe drives. The output
+/// is still parsed when the exit status reports a failing drive; only a
+/// device that could not be opened has no data.
+pub fn parse_smartctl_info(output: &str) -> ParseReport<SmartRecord> {
+    let mut report = ParseReport::new();
+    let json: Value = match serde_json::from_str(output) {
+        Ok(json) => json,
+        Err(e) => {
+            report.push_issue(1, output.lines().next().unwrap_or_default(), format!("invalid JSON: {}", e));
+            return report;
+        }
+    };
+
+    let mut record = SmartRecord {
+        model: json["model_name"].as_str().map(String::from),
+        serial: json["serial_number"].as_str().map(String::from),
+        passed: json["smart_status"]["passed"].as_bool(),
+        temperature_celsius: json["temperature"]["current"].as_f64().map(|t| t as f32),
+        power_on_hours: json["power_on_time"]["hours"].as_u64(),
+        exit_status: json["smartctl"]["exit_status"].as_i64().unwrap_or_default(),
+        error: json["smartctl"]["messages"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|m| m["severity"].as_str() == Some("error"))
+            .find_map(|m| m["string"].as_str().map(String::from)),
+        ..SmartRecord::default()
+    };
+
+    let attributes = json["ata_smart_attributes"]["table"].as_array();
+    for attribute in attributes.into_iter().flatten() {
+        let Some(id) = attribute["id"].as_u64() else {
+            continue;
+        };
+        match id {
+            ATA_REALLOCATED_SECTORS => record.reallocated_sectors = attribute["raw"]["value"].as_u64(),
+            ATA_PENDING_SECTORS => record.pending_sectors = attribute["raw"]["value"].as_u64(),
+            _ if ATA_LIFE_LEFT_ATTRIBUTES.contains(&id) => {
+                // Keep the most worn reading if a drive reports several
+                let worn = attribute["value"].as_u64().map(|left| 100 - left.min(100) as u8);
+                record.wear_percent = record.wear_percent.max(worn);
+            }
+            _ => {}
+        }
+    }
+
+    let nvme = &json["nvme_smart_health_information_log"];
+    if nvme.is_object() {
+        record.wear_percent = nvme["percentage_used"].as_u64().map(|used| used.min(100) as u8);
+        record.media_errors = nvme["media_errors"].as_u64();
+        // Bit 0: spare capacity below threshold, 2: reliability degraded, 3: read-only
+        if nvme["critical_warning"].as_u64().is_some_and(|w| w & 0b1101 != 0) {
+            record.passed = Some(false);
+        }
+    }
+
+    report.items.push(record);
+    report
+}
@@ -0,0 +1,560 @@
+use serde::{Deserialize, Serialize};
+use crate::models::port::{AddressFamily, IpcEndpoint, IpcKind, Protocol};
+use super::{split_host_port, ParseReport};
+
+/// One row of a socket table, independent of the tool it came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SocketRecord {
+    pub protocol: Protocol,
+    pub family: AddressFamily,
+    /// Local address without port (`*`, `0.0.0.0`, `::`, `127.0.0.1`, ...)
+    pub local_address: String,
+    pub local_port: u16,
+    pub remote: Option<String>,
+    pub state: Option<String>,
+    pub process_name: Option<String>,
+    pub pid: Option<u32>,
+}
+
+fn parse_protocol(value: &str) -> Option<Protocol> {
+    let lower = value.to_lowercase();
+    if lower.starts_with("tcp") {
+        Some(Protocol::Tcp)
+    } else if lower.starts_with("udp") {
+        Some(Protocol::Udp)
+    } else {
+        None
+    }
+}
+
+/// Parse `lsof -i -P -n`
+///
+/// COMMAND PID USER FD TYPE DEVICE SIZE/OFF NODE NAME [(STATE)]
+pub fn parse_lsof(output: &str) -> ParseReport<SocketRecord> {
+    let mut report = ParseReport::new();
+
+    for (index, line) in output.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with("COMMAND") {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 9 {
+            report.push_issue(index + 1, line, "expected at least 9 columns");
+            continue;
+        }
+
+        let Some(protocol) = parse_protocol(parts[7]) else {
+            report.push_issue(index + 1, line, format!("unknown protocol '{}'", parts[7]));
+            continue;
+        };
+
+        let Ok(pid) = parts[1].parse::<u32>() else {
+            report.push_issue(index + 1, line, format!("invalid pid '{}'", parts[1]));
+            continue;
+        };
+
+        let mut name = parts[8].split("->");
+        let local = name.next().unwrap_or_default();
+        let remote = name.next().map(String::from);
+
+        let Some((local_address, local_port)) = split_host_port(local) else {
+            report.push_issue(index + 1, line, format!("invalid address '{}'", local));
+            continue;
+        };
+
+        let family = match parts[4] {
+            "IPv6" => AddressFamily::Ipv6,
+            "IPv4" => AddressFamily::Ipv4,
+            _ => AddressFamily::from_address(&local_address),
+        };
+
+        report.items.push(SocketRecord {
+            protocol,
+            family,
+            local_address,
+            local_port,
+            remote,
+            state: parts.get(9).map(|s| s.trim_matches(|c| c == '(' || c == ')').to_string()),
+            process_name: Some(parts[0].to_string()),
+            pid: Some(pid),
+        });
+    }
+
+    report
+}
+
+/// Parse `lsof -U -n -P`, keeping sockets bound to a path. Unnamed socket
+/// pairs and the client ends of connections (`->0x...`) are skipped.
+///
+/// COMMAND PID USER FD TYPE DEVICE SIZE/OFF [NODE] NAME [type=STREAM] [(LISTEN)]
+pub fn parse_lsof_unix(output: &str) -> ParseReport<IpcEndpoint> {
+    let mut report = ParseReport::new();
+    let mut seen = std::collections::HashSet::new();
+
+    for (index, line) in output.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with("COMMAND") {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 7 {
+            report.push_issue(index + 1, line, "expected at least 7 columns");
+            continue;
+        }
+        if parts[4] != "unix" {
+            continue;
+        }
+
+        // macOS leaves NODE empty, so the name isn't at a fixed column
+        let Some(path) = parts[7.min(parts.len())..]
+            .iter()
+            .find(|part| part.starts_with('/') || part.starts_with('@'))
+        else {
+            continue;
+        };
+        let pid = parts[1].parse::<u32>().ok();
+        // Every fd of a forked server shows up
+        if !seen.insert((pid, path.to_string())) {
+            continue;
+        }
+
+        report.items.push(IpcEndpoint {
+            kind: IpcKind::UnixSocket,
+            path: path.to_string(),
+            state: line.contains("(LISTEN)").then(|| "LISTEN".to_string()),
+            process_name: Some(parts[0].to_string()),
+            pid,
+            user: Some(parts[2].to_string()),
+        });
+    }
+
+    report
+}
+
+/// Parse `ss -tulnp`
+///
+/// Netid State Recv-Q Send-Q Local:Port Peer:Port Process
+pub fn parse_ss(output: &str) -> ParseReport<SocketRecord> {
+    let mut report = ParseReport::new();
+
+    for (index, line) in output.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with("Netid") {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 5 {
+            report.push_issue(index + 1, line, "expected at least 5 columns");
+            continue;
+        }
+
+        let Some(protocol) = parse_protocol(parts[0]) else {
+            report.push_issue(index + 1, line, format!("unknown protocol '{}'", parts[0]));
+            continue;
+        };
+
+        // Interface-scoped addresses look like `0.0.0.0%lo:53` or `[::]%lo:53`
+        let local = parts[4];
+        let Some((local_address, local_port)) = split_host_port(local) else {
+            report.push_issue(index + 1, line, format!("invalid address '{}'", local));
+            continue;
+        };
+
+        // users:(("process",pid=1234,fd=3))
+        let (process_name, pid) = match parts.get(6) {
+            Some(process_info) => (
+                process_info.split('"').nth(1).map(String::from),
+                process_info
+                    .split("pid=")
+                    .nth(1)
+                    .and_then(|s| s.split(',').next())
+                    .and_then(|s| s.parse().ok()),
+            ),
+            None => (None, None),
+        };
+
+        // ss prints dual-stack IPv6 sockets (`[::]` without IPV6_V6ONLY) as `*`
+        let family = if local_address == "*" {
+            AddressFamily::Ipv6
+        } else {
+            AddressFamily::from_address(&local_address)
+        };
+
+        report.items.push(SocketRecord {
+            protocol,
+            family,
+            local_address,
+            local_port,
+            remote: parts.get(5).map(|s| s.to_string()),
+            state: Some(parts[1].to_string()),
+            process_name,
+            pid,
+        });
+    }
+
+    report
+}
+
+/// Parse Linux `netstat -tulpn`
+///
+/// Proto Recv-Q Send-Q Local Foreign [State] PID/Program
+///
+/// Title and header lines are recognized by lacking both a protocol and an
+/// address, so translated ones ("Aktive Internetverbindungen") are skipped
+/// too if the C locale didn't take effect.
+pub fn parse_netstat_linux(output: &str) -> ParseReport<SocketRecord> {
+    let mut report = ParseReport::new();
+
+    for (index, line) in output.lines().enumerate() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let Some(protocol) = parts.first().and_then(|p| parse_protocol(p)) else {
+            if parts.get(3).and_then(|a| split_host_port(a)).is_some() {
+                report.push_issue(index + 1, line, format!("unknown protocol '{}'", parts[0]));
+            }
+            continue;
+        };
+        if parts.len() < 4 {
+            report.push_issue(index + 1, line, "expected at least 4 columns");
+            continue;
+        }
+
+        let Some((local_address, local_port)) = split_host_port(parts[3]) else {
+            report.push_issue(index + 1, line, format!("invalid address '{}'", parts[3]));
+            continue;
+        };
+
+        // UDP rows have no State column unless the socket is connected
+        let program_column = match protocol {
+            Protocol::Tcp => 6,
+            Protocol::Udp if parts.get(5).is_some_and(|c| *c != "-" && !c.contains('/')) => 6,
+            Protocol::Udp => 5,
+        };
+        let state = match program_column {
+            6 => parts.get(5).map(|s| s.to_string()),
+            _ => None,
+        };
+
+        // `pid/program name`, where the name may contain spaces ("nginx: master"),
+        // or `-` without privileges
+        let program = parts.get(program_column..).unwrap_or_default().join(" ");
+        let (pid, process_name) = match program.split_once('/') {
+            Some((pid, name)) => match pid.parse::<u32>() {
+                Ok(pid) => (Some(pid), Some(name.to_string())),
+                Err(_) => {
+                    report.push_issue(index + 1, line, format!("invalid pid '{}'", pid));
+                    continue;
+                }
+            },
+            None => (None, None),
+        };
+
+        // tcp6/udp6 sockets may also show IPv4-style addresses
+        let family = if parts[0].ends_with('6') {
+            AddressFamily::Ipv6
+        } else {
+            AddressFamily::from_address(&local_address)
+        };
+
+        report.items.push(SocketRecord {
+            protocol,
+            family,
+            local_address,
+            local_port,
+            remote: parts.get(4).map(|s| s.to_string()),
+            state,
+            process_name,
+            pid,
+        });
+    }
+
+    report
+}
+
+/// Parse Windows `netstat -ano`
+///
+/// Proto Local Foreign [State] PID
+///
+/// Windows ignores `LC_ALL`, so headers and states are localized
+/// ("Aktive Verbindungen", "ABHÖREN"). Rows are recognized by their
+/// protocol column instead of matching English header text.
+pub fn parse_netstat_windows(output: &str) -> ParseReport<SocketRecord> {
+    let mut report = ParseReport::new();
+
+    for (index, line) in output.lines().enumerate() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let Some(protocol) = parts.first().and_then(|p| parse_protocol(p)) else {
+            // Title, blank and column header lines in any language
+            continue;
+        };
+        // UDP rows have no state column
+        let columns = match protocol {
+            Protocol::Tcp => 5,
+            Protocol::Udp => 4,
+        };
+        if parts.len() < columns {
+            report.push_issue(index + 1, line, format!("expected {} columns", columns));
+            continue;
+        }
+
+        let Some((local_address, local_port)) = split_host_port(parts[1]) else {
+            report.push_issue(index + 1, line, format!("invalid address '{}'", parts[1]));
+            continue;
+        };
+        let Ok(pid) = parts[columns - 1].parse::<u32>() else {
+            report.push_issue(index + 1, line, format!("invalid pid '{}'", parts[columns - 1]));
+            continue;
+        };
+
+        report.items.push(SocketRecord {
+            protocol,
+            family: AddressFamily::from_address(&local_address),
+            local_address,
+            local_port,
+            remote: Some(parts[2].to_string()),
+            state: (columns == 5).then(|| parts[3].to_string()),
+            process_name: None,
+            pid: Some(pid),
+        });
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LSOF: &str = "\
+COMMAND     PID   USER   FD   TYPE             DEVICE SIZE/OFF NODE NAME
+rapportd    512 alice    4u  IPv4 0x1a2b3c4d5e6f7a8b      0t0  TCP *:49152 (LISTEN)
+rapportd    512 alice    5u  IPv6 0x1a2b3c4d5e6f7a8c      0t0  TCP *:49152 (LISTEN)
+node       4242 alice   23u  IPv4 0x1a2b3c4d5e6f7a8d      0t0  TCP 127.0.0.1:3000 (LISTEN)
+node       4242 alice   24u  IPv4 0x1a2b3c4d5e6f7a8e      0t0  TCP 127.0.0.1:3000->127.0.0.1:52044 (ESTABLISHED)
+mDNSRespo   301 _mdnsresponder 8u IPv6 0x1a2b3c4d5e6f7a8f 0t0  UDP *:5353
+postgres    777 alice    7u  IPv6 0x1a2b3c4d5e6f7a90      0t0  TCP [::1]:5432 (LISTEN)
+truncated   999 alice    9u  IPv4 0x1a2b3c4d5e6f7a91
+garbled    abc alice   10u  IPv4 0x1a2b3c4d5e6f7a92      0t0  TCP *:8080 (LISTEN)
+weird       100 alice   11u  IPv4 0x1a2b3c4d5e6f7a93      0t0  SCTP *:9999
+noport      101 alice   12u  IPv4 0x1a2b3c4d5e6f7a94      0t0  TCP *:http (LISTEN)
+";
+
+    #[test]
+    fn lsof_fixture() {
+        let report = parse_lsof(LSOF);
+        assert_eq!(report.items.len(), 6);
+
+        let node = &report.items[3];
+        assert_eq!((node.local_address.as_str(), node.local_port), ("127.0.0.1", 3000));
+        assert_eq!(node.remote.as_deref(), Some("127.0.0.1:52044"));
+        assert_eq!(node.state.as_deref(), Some("ESTABLISHED"));
+        assert_eq!((node.process_name.as_deref(), node.pid), (Some("node"), Some(4242)));
+
+        assert_eq!(report.items[1].family, AddressFamily::Ipv6);
+        assert_eq!(report.items[4].protocol, Protocol::Udp);
+        assert_eq!(report.items[4].state, None);
+        assert_eq!((report.items[5].local_address.as_str(), report.items[5].local_port), ("::1", 5432));
+
+        let lines: Vec<usize> = report.issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, [8, 9, 10, 11]);
+        assert!(report.issues[0].reason.contains("columns"));
+        assert!(report.issues[1].reason.contains("pid"));
+        assert!(report.issues[2].reason.contains("protocol"));
+        assert!(report.issues[3].reason.contains("address"));
+    }
+
+    #[test]
+    fn lsof_unix_fixture() {
+        let output = "\
+COMMAND   PID  USER   FD   TYPE             DEVICE SIZE/OFF                NODE NAME
+dockerd   880  root   6u  unix 0xffff8881035ac000      0t0               23456 /run/docker.sock type=STREAM (LISTEN)
+dockerd   880  root   7u  unix 0xffff8881035ac400      0t0               23457 /run/docker.sock type=STREAM (LISTEN)
+systemd     1  root  40u  unix 0xffff8881035ac800      0t0               12000 type=DGRAM
+Xorg      999 alice   3u  unix 0xffff8881035acc00      0t0               34000 @/tmp/.X11-unix/X0 type=STREAM (LISTEN)
+ssh-agent 123 alice   3u  unix 0x1234567890abcdef      0t0                     /tmp/ssh-XXXX/agent.122
+broken    1
+";
+        let report = parse_lsof_unix(output);
+        let paths: Vec<&str> = report.items.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["/run/docker.sock", "@/tmp/.X11-unix/X0", "/tmp/ssh-XXXX/agent.122"]);
+        assert_eq!(report.items[0].state.as_deref(), Some("LISTEN"));
+        assert_eq!(report.items[2].state, None);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].line, 7);
+    }
+
+    #[test]
+    fn ss_fixture() {
+        let output = "\
+Netid State  Recv-Q Send-Q  Local Address:Port   Peer Address:Port Process
+udp   UNCONN 0      0       127.0.0.53%lo:53          0.0.0.0:*     users:((\"systemd-resolve\",pid=612,fd=13))
+tcp   LISTEN 0      4096          0.0.0.0:22          0.0.0.0:*     users:((\"sshd\",pid=901,fd=3))
+tcp   LISTEN 0      511              [::]:80             [::]:*     users:((\"nginx\",pid=1200,fd=7),(\"nginx\",pid=1201,fd=7))
+tcp   LISTEN 0      128                 *:8080              *:*
+tcp   LISTEN 0
+sctp  LISTEN 0      128           0.0.0.0:9899        0.0.0.0:*
+tcp   LISTEN 0      128           0.0.0.0:ssh         0.0.0.0:*
+";
+        let report = parse_ss(output);
+        assert_eq!(report.items.len(), 4);
+
+        let resolver = &report.items[0];
+        assert_eq!((resolver.local_address.as_str(), resolver.local_port), ("127.0.0.53", 53));
+        assert_eq!((resolver.process_name.as_deref(), resolver.pid), (Some("systemd-resolve"), Some(612)));
+        assert_eq!(resolver.protocol, Protocol::Udp);
+
+        assert_eq!(report.items[2].local_address, "::");
+        assert_eq!(report.items[2].pid, Some(1200));
+        // Dual-stack wildcard
+        assert_eq!(report.items[3].family, AddressFamily::Ipv6);
+        assert_eq!(report.items[3].pid, None);
+
+        let lines: Vec<usize> = report.issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, [6, 7, 8]);
+    }
+
+    #[test]
+    fn netstat_linux_fixture() {
+        let output = "\
+Active Internet connections (only servers)
+Proto Recv-Q Send-Q Local Address           Foreign Address         State       PID/Program name
+tcp        0      0 0.0.0.0:22              0.0.0.0:*               LISTEN      901/sshd
+tcp        0      0 127.0.0.1:5432          0.0.0.0:*               LISTEN      -
+tcp6       0      0 :::80                   :::*                    LISTEN      1200/nginx: master
+udp        0      0 0.0.0.0:68              0.0.0.0:*                           640/dhclient
+udp6       0      0 ::1:323                 :::*                                -
+tcp        0
+raw        0      0 0.0.0.0:1               0.0.0.0:*               7           -
+tcp        0      0 0.0.0.0:ssh             0.0.0.0:*               LISTEN      901/sshd
+udp6       0      0 ::1:52000               ::1:53                  ESTABLISHED 777/systemd-resolve
+tcp        0      0 0.0.0.0:8080            0.0.0.0:*               LISTEN      x1/java
+";
+        let report = parse_netstat_linux(output);
+        assert_eq!(report.items.len(), 6);
+
+        let sshd = &report.items[0];
+        assert_eq!((sshd.local_port, sshd.state.as_deref(), sshd.pid), (22, Some("LISTEN"), Some(901)));
+        assert_eq!(sshd.process_name.as_deref(), Some("sshd"));
+        // No privileges for the owner
+        assert_eq!((report.items[1].pid, report.items[1].process_name.as_deref()), (None, None));
+        let nginx = &report.items[2];
+        assert_eq!((nginx.local_address.as_str(), nginx.family), ("::", AddressFamily::Ipv6));
+        // The program name contains a space
+        assert_eq!((nginx.pid, nginx.process_name.as_deref()), (Some(1200), Some("nginx: master")));
+        assert_eq!((report.items[3].state.as_deref(), report.items[3].pid), (None, Some(640)));
+        assert_eq!(report.items[3].process_name.as_deref(), Some("dhclient"));
+        assert_eq!(report.items[4].local_port, 323);
+        // Connected UDP sockets do show a state
+        let resolver = &report.items[5];
+        assert_eq!((resolver.state.as_deref(), resolver.pid), (Some("ESTABLISHED"), Some(777)));
+        assert_eq!(resolver.process_name.as_deref(), Some("systemd-resolve"));
+
+        // Cut-off row, unknown protocol, named port, garbled PID
+        let lines: Vec<usize> = report.issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, [8, 9, 10, 12]);
+    }
+
+    #[test]
+    fn netstat_windows_fixture() {
+        // netstat.exe prints CRLF and starts with a blank line
+        let output = [
+            "",
+            "Active Connections",
+            "",
+            "  Proto  Local Address          Foreign Address        State           PID",
+            "  TCP    0.0.0.0:135            0.0.0.0:0              LISTENING       1044",
+            "  TCP    127.0.0.1:5432         0.0.0.0:0              LISTENING       4321",
+            "  TCP    [::]:445               [::]:0                 LISTENING       4",
+            "  TCP    192.168.1.20:50112     140.82.112.4:443       ESTABLISHED     7788",
+            "  UDP    0.0.0.0:5353           *:*                                    2210",
+            "  UDP    [fe80::1%4]:1900       *:*                                    3300",
+            "  TCP    0.0.0.0:3389           0.0.0.0:0              LISTENING",
+            "  TCP    0.0.0.0:7680",
+            "  UDP    0.0.0.0:500            *:*                                    System",
+            "  TCP    0.0.0.0:x              0.0.0.0:0              LISTENING       4",
+        ]
+        .join("\r\n");
+        let report = parse_netstat_windows(&output);
+        assert_eq!(report.items.len(), 6);
+
+        let rpc = &report.items[0];
+        assert_eq!((rpc.local_port, rpc.state.as_deref(), rpc.pid), (135, Some("LISTENING"), Some(1044)));
+        assert_eq!((report.items[2].local_address.as_str(), report.items[2].family), ("::", AddressFamily::Ipv6));
+        assert_eq!(report.items[3].remote.as_deref(), Some("140.82.112.4:443"));
+        assert_eq!((report.items[4].protocol.clone(), report.items[4].state.as_deref()), (Protocol::Udp, None));
+        assert_eq!((report.items[5].local_address.as_str(), report.items[5].pid), ("fe80::1", Some(3300)));
+
+        // Missing PID, cut-off row, non-numeric PID, bad port
+        let lines: Vec<usize> = report.issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, [11, 12, 13, 14]);
+    }
+
+    #[test]
+    fn localized_netstat_windows() {
+        let german = [
+            "",
+            "Aktive Verbindungen",
+            "",
+            "  Proto  Lokale Adresse         Remoteadresse          Status           PID",
+            "  TCP    0.0.0.0:135            0.0.0.0:0              ABHÖREN          1044",
+            "  TCP    192.168.178.20:50112   140.82.112.4:443       HERGESTELLT      7788",
+            "  UDP    0.0.0.0:5353           *:*                                     2210",
+        ]
+        .join("\r\n");
+        let report = parse_netstat_windows(&german);
+        assert!(report.is_complete(), "{:?}", report.issues);
+        assert_eq!(report.items.len(), 3);
+        assert_eq!((report.items[0].state.as_deref(), report.items[0].pid), (Some("ABHÖREN"), Some(1044)));
+        assert_eq!(report.items[1].state.as_deref(), Some("HERGESTELLT"));
+
+        let french = [
+            "",
+            "Connexions actives",
+            "",
+            "  Proto  Adresse locale         Adresse distante       État           PID",
+            "  TCP    0.0.0.0:445            0.0.0.0:0              ÉCOUTE         4",
+            "  TCP    [::1]:5432             [::]:0                 ÉCOUTE         4321",
+            "  UDP    [::]:500               *:*                                   3300",
+        ]
+        .join("\r\n");
+        let report = parse_netstat_windows(&french);
+        assert!(report.is_complete(), "{:?}", report.issues);
+        let ports: Vec<u16> = report.items.iter().map(|r| r.local_port).collect();
+        assert_eq!(ports, [445, 5432, 500]);
+        assert_eq!(report.items[1].state.as_deref(), Some("ÉCOUTE"));
+    }
+
+    #[test]
+    fn localized_netstat_linux() {
+        // net-tools output when the C locale didn't take effect
+        let german = "\
+Aktive Internetverbindungen (Nur Server)
+Proto Recv-Q Send-Q Lokale Adresse          Gegenstelle             Zustand     PID/Programmname
+tcp        0      0 0.0.0.0:22              0.0.0.0:*               LISTEN      901/sshd
+tcp6       0      0 :::80                   :::*                    LISTEN      1200/nginx
+udp        0      0 0.0.0.0:68              0.0.0.0:*                           640/dhclient
+";
+        let report = parse_netstat_linux(german);
+        assert!(report.is_complete(), "{:?}", report.issues);
+        assert_eq!(report.items.len(), 3);
+        assert_eq!(report.items[0].process_name.as_deref(), Some("sshd"));
+
+        let french = "\
+Connexions Internet actives (seulement serveurs)
+Proto Recv-Q Send-Q Adresse locale          Adresse distante        Etat        PID/Program name
+tcp        0      0 127.0.0.1:5432          0.0.0.0:*               LISTEN      777/postgres
+";
+        let report = parse_netstat_linux(french);
+        assert!(report.is_complete(), "{:?}", report.issues);
+        assert_eq!((report.items[0].local_port, report.items[0].pid), (5432, Some(777)));
+    }
+
+    #[test]
+    fn split_host_port_variants() {
+        assert_eq!(split_host_port("0.0.0.0:22"), Some(("0.0.0.0".to_string(), 22)));
+        assert_eq!(split_host_port("[::1]:5432"), Some(("::1".to_string(), 5432)));
+        assert_eq!(split_host_port(":::80"), Some(("::".to_string(), 80)));
+        assert_eq!(split_host_port("*:*"), None);
+        assert_eq!(split_host_port("fe80::1%4:123"), Some(("fe80::1".to_string(), 123)));
+    }
+}
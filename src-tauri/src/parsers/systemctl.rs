@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use super::ParseReport;
+
+/// One row of `systemctl list-units --plain`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemctlUnit {
+    pub unit: String,
+    pub load: String,
+    pub active: String,
+    pub sub: String,
+    pub description: Option<String>,
+}
+
+/// Parse `systemctl list-units --type=service --all --no-pager --plain [--no-legend]`
+///
+/// UNIT LOAD ACTIVE SUB DESCRIPTION
+pub fn parse_list_units(output: &str) -> ParseReport<SystemctlUnit> {
+    let mut report = ParseReport::new();
+
+    for (index, line) in output.lines().enumerate() {
+        // Header, legend and summary lines don't reference a unit
+        if !line.contains(".service") {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 4 {
+            report.push_issue(index + 1, line, "expected at least 4 columns");
+            continue;
+        }
+        if !parts[0].ends_with(".service") {
+            report.push_issue(index + 1, line, format!("unexpected unit column '{}'", parts[0]));
+            continue;
+        }
+
+        report.items.push(SystemctlUnit {
+            unit: parts[0].to_string(),
+            load: parts[1].to_string(),
+            active: parts[2].to_string(),
+            sub: parts[3].to_string(),
+            description: (parts.len() > 4).then(|| parts[4..].join(" ")),
+        });
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_units_fixture() {
+        let output = "\
+UNIT                          LOAD      ACTIVE   SUB     DESCRIPTION
+cron.service                  loaded    active   running Regular background program processing daemon
+docker.service                loaded    active   running Docker Application Container Engine
+getty@tty1.service            loaded    active   running Getty on tty1
+nginx.service                 not-found inactive dead    nginx.service
+postgresql.service            loaded    failed   failed  PostgreSQL RDBMS
+truncated.service             loaded
+Description of a.service in the wrong column
+rsyslog.service               loaded    active   running
+
+LOAD   = Reflects whether the unit definition was properly loaded.
+ACTIVE = The high-level unit activation state, i.e. generalization of SUB.
+
+7 loaded units listed.
+";
+        let report = parse_list_units(output);
+        let units: Vec<&str> = report.items.iter().map(|u| u.unit.as_str()).collect();
+        assert_eq!(
+            units,
+            ["cron.service", "docker.service", "getty@tty1.service", "nginx.service", "postgresql.service", "rsyslog.service"]
+        );
+        let nginx = &report.items[3];
+        assert_eq!((nginx.load.as_str(), nginx.active.as_str(), nginx.sub.as_str()), ("not-found", "inactive", "dead"));
+        assert_eq!(report.items[4].description.as_deref(), Some("PostgreSQL RDBMS"));
+        assert_eq!(report.items[5].description, None);
+
+        let lines: Vec<usize> = report.issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, [7, 8]);
+    }
+
+    #[test]
+    fn localized_list_units() {
+        // Header, legend and summary are translated, unit states are not
+        let output = "\
+EINHEIT                LADEN   AKTIV    UNTER   BESCHREIBUNG
+cron.service           loaded  active   running Regelmäßige Hintergrundprogrammverarbeitung
+ssh.service            loaded  inactive dead    OpenBSD Secure Shell Server
+
+LADEN  = Zeigt an, ob die Unit-Definition korrekt geladen wurde.
+AKTIV  = Der übergeordnete Aktivierungsstatus der Unit.
+
+2 geladene Units aufgelistet.
+";
+        let report = parse_list_units(output);
+        assert!(report.is_complete(), "{:?}", report.issues);
+        assert_eq!(report.items.len(), 2);
+        assert_eq!(report.items[1].active, "inactive");
+        assert_eq!(report.items[0].description.as_deref(), Some("Regelmäßige Hintergrundprogrammverarbeitung"));
+    }
+}
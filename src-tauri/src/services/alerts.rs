@@ -0,0 +1,264 @@
+use crate::models::alert::{Alert, AlertCondition, AlertRule};
+use crate::models::audit::{AuditEntry, EventType};
+use crate::models::config::AlertConfig;
+use crate::models::port::PortInfo;
+use crate::models::service::{Service, ServiceStatus};
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::history::HistoryStore;
+use crate::services::monitor::{current_ports, emit_service_event, port_key, ServiceEvent};
+use crate::services::notifier::{NotificationCategory, Notifier};
+use crate::services::security::AuditLogger;
+use crate::services::system_stats::{SystemMonitor, SystemStats};
+use crate::services::ServiceManager;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::AppHandle;
+
+const MIN_INTERVAL_SECONDS: u64 = 5;
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+static SETTINGS: OnceLock<Mutex<AlertConfig>> = OnceLock::new();
+
+/// One tick's worth of measurements
+struct Sample {
+    services: Vec<Service>,
+    stats: SystemStats,
+    /// Only collected when a `NewPublicPort` rule is enabled
+    ports: Option<Vec<PortInfo>>,
+}
+
+/// What the previous ticks saw, to tell sustained breaches and changes apart
+#[derive(Default)]
+struct EvaluationState {
+    /// First tick a threshold was exceeded, per rule and subject
+    breached_since: HashMap<(String, String), DateTime<Utc>>,
+    /// Rule/subject pairs that already fired and haven't cleared since
+    firing: HashSet<(String, String)>,
+    last_status: Option<HashMap<String, ServiceStatus>>,
+    known_public: Option<HashSet<String>>,
+}
+
+/// Checks the configured `AlertRule`s against services, system stats and the
+/// port table on a fixed interval. Fired alerts are stored in the history
+/// database, written to the audit log and emitted as `AlertTriggered`.
+pub struct AlertEngine;
+
+impl AlertEngine {
+    pub fn configure(config: &AlertConfig) {
+        *settings().lock().unwrap_or_else(|e| e.into_inner()) = config.clone();
+    }
+
+    pub fn start(app_handle: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut state = EvaluationState::default();
+            loop {
+                let config = settings().lock().unwrap_or_else(|e| e.into_inner()).clone();
+                let rules: Vec<AlertRule> = config.rules.into_iter().filter(|r| r.enabled).collect();
+                if rules.is_empty() {
+                    // Re-enabled rules start from a clean slate
+                    state = EvaluationState::default();
+                } else {
+                    let sample = collect(&rules).await;
+                    let alerts = match &sample {
+                        Some(sample) => evaluate(&rules, sample, &mut state, Utc::now()),
+                        None => Vec::new(),
+                    };
+                    for alert in alerts {
+                        let _ = HistoryStore::record_alert(&alert).await;
+                        audit(&alert);
+                        Notifier::send(&app_handle, NotificationCategory::Alert, alert.rule_name.clone(), alert.message.clone());
+                        emit_service_event(&app_handle, ServiceEvent::AlertTriggered(alert));
+                    }
+                }
+
+                tokio::time::sleep(Duration::from_secs(config.interval_seconds.max(MIN_INTERVAL_SECONDS))).await;
+            }
+        });
+    }
+}
+
+fn settings() -> &'static Mutex<AlertConfig> {
+    SETTINGS.get_or_init(|| Mutex::new(AlertConfig::default()))
+}
+
+async fn collect(rules: &[AlertRule]) -> Option<Sample> {
+    let services = ServiceManager::new().discover_all().await;
+    let want_ports = rules.iter().any(|r| matches!(r.condition, AlertCondition::NewPublicPort));
+    let (stats, ports) = tokio::task::spawn_blocking(move || {
+        let stats = if MockDiscovery::is_enabled() {
+            MockDiscovery::new().system_stats(Utc::now().timestamp() as u64)
+        } else {
+            SystemMonitor::new().get_stats()
+        };
+        (stats, want_ports.then(current_ports))
+    })
+    .await
+    .ok()?;
+    Some(Sample { services, stats, ports })
+}
+
+fn evaluate(rules: &[AlertRule], sample: &Sample, state: &mut EvaluationState, now: DateTime<Utc>) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+    let services: HashMap<&str, &Service> = sample.services.iter().map(|s| (s.id.as_str(), s)).collect();
+
+    for rule in rules {
+        match &rule.condition {
+            AlertCondition::CpuAbove { percent, minutes } => {
+                let (subject, label, value) = match &rule.service_id {
+                    None => ("system".to_string(), "System".to_string(), Some(sample.stats.cpu.usage_percent)),
+                    Some(id) => match services.get(id.as_str()) {
+                        Some(service) => (id.clone(), service.name.clone(), service.cpu_usage),
+                        None => (id.clone(), id.clone(), None),
+                    },
+                };
+                let breached = value.filter(|v| v > percent);
+                if sustained(state, rule, &subject, breached.is_some(), *minutes, now) {
+                    let value = breached.unwrap_or_default();
+                    alerts.push(alert(
+                        rule,
+                        format!(
+                            "{}: CPU-Auslastung {:.1} % seit {} Minuten über {:.0} %",
+                            label, value, minutes, percent
+                        ),
+                        Some(value as f64),
+                        now,
+                    ));
+                }
+            }
+            AlertCondition::MemoryAbove { bytes, minutes } => {
+                let (subject, label, value) = match &rule.service_id {
+                    None => ("system".to_string(), "System".to_string(), Some(sample.stats.memory.used_bytes)),
+                    Some(id) => match services.get(id.as_str()) {
+                        Some(service) => (id.clone(), service.name.clone(), service.memory_bytes),
+                        None => (id.clone(), id.clone(), None),
+                    },
+                };
+                let breached = value.filter(|v| v > bytes);
+                if sustained(state, rule, &subject, breached.is_some(), *minutes, now) {
+                    let value = breached.unwrap_or_default();
+                    alerts.push(alert(
+                        rule,
+                        format!(
+                            "{}: Speicherverbrauch {:.1} GB über {:.1} GB",
+                            label,
+                            value as f64 / GIB,
+                            *bytes as f64 / GIB
+                        ),
+                        Some(value as f64),
+                        now,
+                    ));
+                }
+            }
+            AlertCondition::ServiceStopped => {
+                // Nothing to compare against on the first tick
+                let Some(last_status) = &state.last_status else {
+                    continue;
+                };
+                let was_running: Vec<&String> = last_status
+                    .iter()
+                    .filter(|(id, status)| {
+                        **status == ServiceStatus::Running && rule.service_id.as_ref().is_none_or(|s| s == *id)
+                    })
+                    .map(|(id, _)| id)
+                    .collect();
+                for id in was_running {
+                    let message = match services.get(id.as_str()) {
+                        Some(service) if service.status == ServiceStatus::Error => {
+                            format!("Dienst {} ist fehlgeschlagen", service.name)
+                        }
+                        Some(service) if service.status == ServiceStatus::Stopped => {
+                            format!("Dienst {} wurde gestoppt", service.name)
+                        }
+                        // Process-based services disappear when they exit;
+                        // only report that for an explicitly watched service
+                        None if rule.service_id.is_some() => format!("Dienst {} läuft nicht mehr", id),
+                        _ => continue,
+                    };
+                    let mut alert = alert(rule, message, None, now);
+                    alert.service_id = Some(id.clone());
+                    alerts.push(alert);
+                }
+            }
+            AlertCondition::NewPublicPort => {
+                let (Some(known), Some(ports)) = (&state.known_public, &sample.ports) else {
+                    continue;
+                };
+                // A watched service that isn't running owns no ports
+                let owner_service = rule.service_id.as_ref().map(|id| services.get(id.as_str()).copied());
+                for port in ports.iter().filter(|p| !p.is_loopback() && !known.contains(&port_key(p))) {
+                    if let Some(service) = owner_service {
+                        let owned = service.is_some_and(|s| {
+                            s.ports.contains(&port.port) || (s.pid.is_some() && s.pid == port.pid)
+                        });
+                        if !owned {
+                            continue;
+                        }
+                    }
+                    let owner = match (&port.process_name, port.pid) {
+                        (Some(name), Some(pid)) => format!("{} (PID {})", name, pid),
+                        (Some(name), None) => name.clone(),
+                        _ => "unbekannter Prozess".to_string(),
+                    };
+                    alerts.push(alert(
+                        rule,
+                        format!(
+                            "Neuer öffentlicher Port {}: {} lauscht auf {} ({:?})",
+                            port.port, owner, port.bind_address, port.protocol
+                        ),
+                        Some(port.port as f64),
+                        now,
+                    ));
+                }
+            }
+        }
+    }
+
+    state.last_status = Some(sample.services.iter().map(|s| (s.id.clone(), s.status.clone())).collect());
+    if let Some(ports) = &sample.ports {
+        state.known_public = Some(ports.iter().filter(|p| !p.is_loopback()).map(port_key).collect());
+    }
+    alerts
+}
+
+/// Whether a threshold rule fires now: breached for `minutes` and not
+/// already reported. A cleared breach re-arms the rule.
+fn sustained(state: &mut EvaluationState, rule: &AlertRule, subject: &str, breached: bool, minutes: u32, now: DateTime<Utc>) -> bool {
+    let key = (rule.id.clone(), subject.to_string());
+    if !breached {
+        state.breached_since.remove(&key);
+        state.firing.remove(&key);
+        return false;
+    }
+    let since = *state.breached_since.entry(key.clone()).or_insert(now);
+    if now - since < chrono::Duration::minutes(minutes as i64) || state.firing.contains(&key) {
+        return false;
+    }
+    state.firing.insert(key);
+    true
+}
+
+fn alert(rule: &AlertRule, message: String, value: Option<f64>, now: DateTime<Utc>) -> Alert {
+    Alert {
+        id: uuid::Uuid::new_v4().to_string(),
+        rule_id: rule.id.clone(),
+        rule_name: rule.name.clone(),
+        service_id: rule.service_id.clone(),
+        message,
+        value,
+        triggered_at: now,
+    }
+}
+
+fn audit(alert: &Alert) {
+    let mut entry = AuditEntry::new(EventType::Alert, "alert_triggered".to_string());
+    entry.service_id = alert.service_id.clone();
+    entry.details = serde_json::json!({
+        "rule_id": alert.rule_id,
+        "rule_name": alert.rule_name,
+        "message": alert.message,
+        "value": alert.value,
+    });
+    let _ = AuditLogger::new().log(&entry);
+}
@@ -0,0 +1,301 @@
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::llm::OllamaClient;
+use crate::services::cmd::{self, find_in_path};
+use crate::services::digest::DigestGenerator;
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::port::PortResolver;
+use crate::services::security_scanner::SecurityScanner;
+use crate::services::storage::Storage;
+use crate::services::system_stats::SystemMonitor;
+use crate::services::ServiceManager;
+
+/// An optional external tool and how to get it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolStatus {
+    pub tool: String,
+    /// What the app uses it for
+    pub purpose: String,
+    pub installed: bool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    /// Whether the daemon answers (docker, ollama); `None` for plain CLIs
+    pub running: Option<bool>,
+    /// Shell commands that install the tool on this platform, in order
+    pub install_commands: Vec<String>,
+    pub docs_url: String,
+}
+
+/// The state recorded by `create_baseline`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineSummary {
+    pub created_at: DateTime<Utc>,
+    pub services: usize,
+    pub ports: usize,
+    pub security_issues: usize,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootstrapStatus {
+    /// Setup has neither been completed nor skipped
+    pub first_run: bool,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// `macos`, `linux` or `windows`
+    pub platform: String,
+    pub tools: Vec<ToolStatus>,
+    /// Model used for analyses
+    pub model: String,
+    pub model_installed: bool,
+    pub baseline: Option<BaselineSummary>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BootstrapState {
+    completed_at: Option<DateTime<Utc>>,
+    baseline: Option<BaselineSummary>,
+}
+
+/// First-run setup: checks for the optional tools, knows how to install
+/// them, and records a baseline of services, ports and findings. The setup
+/// UI calls the steps one by one; the model download lives with the LLM
+/// commands.
+pub struct Bootstrap;
+
+impl Bootstrap {
+    pub async fn status(ollama: &OllamaClient) -> BootstrapStatus {
+        let state = load_state();
+
+        let mut tools = tokio::task::spawn_blocking(detect_tools).await.unwrap_or_default();
+        let ollama_running = ollama.is_available().await;
+        let docker_running = docker_running().await;
+        for tool in &mut tools {
+            match tool.tool.as_str() {
+                "docker" => tool.running = Some(docker_running),
+                "ollama" => tool.running = Some(ollama_running),
+                _ => {}
+            }
+        }
+        let model_installed = ollama_running && ollama.has_model(ollama.model()).await;
+
+        BootstrapStatus {
+            first_run: state.completed_at.is_none(),
+            completed_at: state.completed_at,
+            platform: std::env::consts::OS.to_string(),
+            tools,
+            model: ollama.model().to_string(),
+            model_installed,
+            baseline: state.baseline,
+        }
+    }
+
+    /// Save the current services, listening ports and security findings to
+    /// `baseline.json` and use them as the starting point of the first digest
+    pub async fn create_baseline() -> Result<BaselineSummary, Box<dyn std::error::Error + Send + Sync>> {
+        let services = ServiceManager::new().discover_all().await;
+        let security = SecurityScanner::new().scan(&services);
+        let (ports, stats) = if MockDiscovery::is_enabled() {
+            let mock = MockDiscovery::new();
+            (mock.port_usage(), mock.system_stats(Utc::now().timestamp() as u64))
+        } else {
+            tokio::task::spawn_blocking(|| (PortResolver::new().get_port_usage(), SystemMonitor::new().get_stats())).await?
+        };
+
+        let created_at = Utc::now();
+        let path = Storage::data_dir().join("baseline.json");
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        let content = serde_json::json!({
+            "created_at": created_at,
+            "services": services,
+            "ports": ports,
+            "security_issues": security.issues,
+            "system": stats,
+        });
+        std::fs::write(&path, serde_json::to_string_pretty(&content)?)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+        DigestGenerator::new().seed_baseline(&services, &security, Some(&stats))?;
+
+        let summary = BaselineSummary {
+            created_at,
+            services: services.len(),
+            ports: ports.len(),
+            security_issues: security.issues.len(),
+            path: path.to_string_lossy().to_string(),
+        };
+        let mut state = load_state();
+        state.baseline = Some(summary.clone());
+        save_state(&state);
+        Ok(summary)
+    }
+
+    /// Finish or skip setup; it is not offered again on start
+    pub fn complete() {
+        let mut state = load_state();
+        state.completed_at = Some(Utc::now());
+        save_state(&state);
+    }
+}
+
+fn detect_tools() -> Vec<ToolStatus> {
+    let mut tools = vec![
+        tool(
+            "docker",
+            "Container erkennen, starten und stoppen",
+            &["--version"],
+            "https://docs.docker.com/get-docker/",
+        ),
+        tool(
+            "ollama",
+            "Lokale KI für Log-Analyse und Prozess-Erklärungen",
+            &["--version"],
+            "https://ollama.com/download",
+        ),
+        tool(
+            "nmap",
+            "Gründlichere Port-Scans mit Versionserkennung (optional)",
+            &["--version"],
+            "https://nmap.org/download",
+        ),
+    ];
+    if cfg!(unix) {
+        tools.push(tool(
+            "lsof",
+            "Ports und Sockets den Prozessen zuordnen",
+            &["-v"],
+            "https://github.com/lsof-org/lsof",
+        ));
+    }
+    if cfg!(target_os = "linux") {
+        tools.push(tool(
+            "ss",
+            "Schnelle Abfrage offener Ports ohne lsof",
+            &["-V"],
+            "https://wiki.linuxfoundation.org/networking/iproute2",
+        ));
+    }
+    tools
+}
+
+fn tool(name: &str, purpose: &str, version_args: &[&str], docs_url: &str) -> ToolStatus {
+    let path = find_in_path(name);
+    let version = path.as_deref().and_then(|path| version(path, version_args));
+    ToolStatus {
+        tool: name.to_string(),
+        purpose: purpose.to_string(),
+        installed: path.is_some(),
+        path: path.map(|p| p.to_string_lossy().to_string()),
+        version,
+        running: None,
+        install_commands: install_commands(name),
+        docs_url: docs_url.to_string(),
+    }
+}
+
+/// The line of `--version` style output that carries the version
+fn version(path: &Path, args: &[&str]) -> Option<String> {
+    let output = cmd::command(&path.to_string_lossy()).args(args).output().ok()?;
+    // lsof and ollama (without a running server) print to stderr
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<&str> = text.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    lines
+        .iter()
+        .find(|l| l.starts_with("revision:"))
+        .or_else(|| lines.iter().rev().find(|l| l.to_lowercase().contains("version") && !l.ends_with(':')))
+        .or_else(|| lines.first())
+        .map(|l| l.to_string())
+}
+
+async fn docker_running() -> bool {
+    match bollard::Docker::connect_with_local_defaults() {
+        Ok(docker) => docker.ping().await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn install_commands(tool: &str) -> Vec<String> {
+    match tool {
+        "docker" => vec!["brew install --cask docker".to_string(), "open -a Docker".to_string()],
+        "ollama" => vec!["brew install ollama".to_string(), "brew services start ollama".to_string()],
+        "nmap" => vec!["brew install nmap".to_string()],
+        // Part of macOS; missing only in stripped-down images
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn install_commands(tool: &str) -> Vec<String> {
+    match tool {
+        "docker" => vec![
+            "curl -fsSL https://get.docker.com | sh".to_string(),
+            "sudo usermod -aG docker $USER".to_string(),
+        ],
+        "ollama" => vec!["curl -fsSL https://ollama.com/install.sh | sh".to_string()],
+        "nmap" => package_install("nmap", "nmap"),
+        "lsof" => package_install("lsof", "lsof"),
+        "ss" => package_install("iproute2", "iproute"),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(windows)]
+fn install_commands(tool: &str) -> Vec<String> {
+    match tool {
+        "docker" => vec!["winget install -e --id Docker.DockerDesktop".to_string()],
+        "ollama" => vec!["winget install -e --id Ollama.Ollama".to_string()],
+        "nmap" => vec!["winget install -e --id Insecure.Nmap".to_string()],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn install_commands(_tool: &str) -> Vec<String> {
+    Vec::new()
+}
+
+/// Install command for the distribution's package manager. Fedora and
+/// RHEL name some packages differently (`rpm_name`).
+#[cfg(target_os = "linux")]
+fn package_install(package: &str, rpm_name: &str) -> Vec<String> {
+    let managers: &[(&str, String)] = &[
+        ("apt-get", format!("sudo apt-get install -y {}", package)),
+        ("dnf", format!("sudo dnf install -y {}", rpm_name)),
+        ("zypper", format!("sudo zypper install -y {}", package)),
+        ("pacman", format!("sudo pacman -S --needed {}", package)),
+        ("apk", format!("sudo apk add {}", package)),
+    ];
+    managers
+        .iter()
+        .find(|(manager, _)| find_in_path(manager).is_some())
+        .map(|(_, command)| vec![command.clone()])
+        .unwrap_or_default()
+}
+
+fn state_path() -> PathBuf {
+    Storage::config_dir().join("bootstrap.json")
+}
+
+fn load_state() -> BootstrapState {
+    std::fs::read_to_string(state_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &BootstrapState) {
+    let path = state_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+    if let Ok(content) = serde_json::to_string_pretty(state) {
+        std::fs::write(path, content).ok();
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::service::{Service, ServiceStatus, ServiceType};
+use crate::services::docker_inventory::{DockerImage, DockerVolume};
+use crate::services::security::confirmation::ActionOutcome;
+
+/// Below this CPU usage a running service without ports counts as idle
+const IDLE_CPU_PERCENT: f32 = 0.5;
+/// Idle services using less memory aren't worth a step
+const IDLE_MIN_MEMORY_BYTES: u64 = 50 * 1024 * 1024;
+/// Autostart services using more memory slow down the boot noticeably
+const STARTUP_MIN_MEMORY_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Services that must never be suggested for cleanup
+const PROTECTED_PREFIXES: &[&str] = &[
+    "com.apple.", "systemd", "launchd", "kernel", "init", "dbus", "sshd", "networkmanager",
+    "windowserver", "loginwindow", "svchost", "lsass", "csrss", "winlogon", "network-manager",
+    "network_manager",
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupCategory {
+    /// Dangling images and orphaned volumes
+    DockerPrune,
+    /// Several instances of the same service
+    Duplicate,
+    IdleService,
+    StartupImpact,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CleanupRisk {
+    Low,
+    Medium,
+    /// Data may be lost (e.g. volumes)
+    High,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CleanupAction {
+    StopService { service_id: String },
+    DisableAutostart { service_id: String, service_type: ServiceType },
+    RemoveImage { image_id: String },
+    RemoveVolume { name: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupStep {
+    /// Stable within a plan, e.g. `stop:nginx.service`
+    pub id: String,
+    pub category: CleanupCategory,
+    pub action: CleanupAction,
+    pub title: String,
+    pub reason: String,
+    pub risk: CleanupRisk,
+    /// Memory or disk space freed, if it can be estimated
+    pub expected_savings_bytes: Option<u64>,
+}
+
+/// Ordered cleanup suggestions, safest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupPlan {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub steps: Vec<CleanupStep>,
+    pub total_savings_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupStepStatus {
+    Executed,
+    Failed,
+    /// Dry-run mode is enabled, nothing was changed
+    DryRun,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupStepResult {
+    pub step_id: String,
+    pub status: CleanupStepStatus,
+    pub error: Option<String>,
+}
+
+/// Outcome of `execute_cleanup`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupReport {
+    pub plan_id: String,
+    /// `confirmation_required` until the token is echoed back; nothing was
+    /// changed unless `executed`
+    #[serde(flatten)]
+    pub outcome: ActionOutcome,
+    pub results: Vec<CleanupStepResult>,
+    /// Estimated savings of the executed steps
+    pub freed_bytes: u64,
+}
+
+/// Combines idle-service, duplicate, startup and Docker prune checks into one plan
+pub struct CleanupPlanner;
+
+impl CleanupPlanner {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn plan(&self, services: &[Service], images: &[DockerImage], volumes: &[DockerVolume]) -> CleanupPlan {
+        let candidates: Vec<&Service> = services
+            .iter()
+            .filter(|s| s.service_type != ServiceType::Process && !is_protected(&s.name))
+            .collect();
+
+        let mut steps = Vec::new();
+        steps.extend(prune_steps(images, volumes));
+        steps.extend(duplicate_steps(&candidates));
+        steps.extend(idle_steps(&candidates));
+        steps.extend(startup_steps(&candidates));
+
+        // A service is only stopped once, even if several checks flag it
+        let mut seen = HashSet::new();
+        steps.retain(|step| seen.insert(step.id.clone()));
+        steps.sort_by(|a, b| {
+            a.category
+                .cmp(&b.category)
+                .then_with(|| b.expected_savings_bytes.cmp(&a.expected_savings_bytes))
+        });
+
+        CleanupPlan {
+            id: uuid::Uuid::new_v4().to_string(),
+            created_at: Utc::now(),
+            total_savings_bytes: steps.iter().filter_map(|s| s.expected_savings_bytes).sum(),
+            steps,
+        }
+    }
+}
+
+impl Default for CleanupPlanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn is_protected(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    PROTECTED_PREFIXES.iter().any(|prefix| lower.starts_with(prefix))
+}
+
+fn prune_steps(images: &[DockerImage], volumes: &[DockerVolume]) -> Vec<CleanupStep> {
+    let mut steps: Vec<CleanupStep> = images
+        .iter()
+        .filter(|image| image.dangling && image.containers.unwrap_or(0) == 0)
+        .map(|image| CleanupStep {
+            id: format!("image:{}", image.id),
+            category: CleanupCategory::DockerPrune,
+            action: CleanupAction::RemoveImage { image_id: image.id.clone() },
+            title: format!("Ungenutztes Image {} entfernen", short_id(&image.id)),
+            reason: "Image ohne Tag, das von keinem Container verwendet wird".to_string(),
+            risk: CleanupRisk::Low,
+            expected_savings_bytes: Some(image.size_bytes),
+        })
+        .collect();
+
+    steps.extend(volumes.iter().filter(|volume| volume.orphaned).map(|volume| CleanupStep {
+        id: format!("volume:{}", volume.name),
+        category: CleanupCategory::DockerPrune,
+        action: CleanupAction::RemoveVolume { name: volume.name.clone() },
+        title: format!("Verwaistes Volume {} entfernen", volume.name),
+        reason: "Volume wird von keinem Container eingebunden. Enthaltene Daten gehen verloren.".to_string(),
+        risk: CleanupRisk::High,
+        expected_savings_bytes: None,
+    }));
+
+    steps
+}
+
+/// Running instances of the same service; the one serving the most ports is kept
+fn duplicate_steps(services: &[&Service]) -> Vec<CleanupStep> {
+    let mut groups: HashMap<String, Vec<&Service>> = HashMap::new();
+    for service in services.iter().filter(|s| s.status == ServiceStatus::Running) {
+        if let Some(key) = duplicate_key(service) {
+            groups.entry(key).or_default().push(service);
+        }
+    }
+
+    let mut steps = Vec::new();
+    for group in groups.values_mut().filter(|g| g.len() > 1) {
+        group.sort_by(|a, b| {
+            b.ports
+                .len()
+                .cmp(&a.ports.len())
+                .then_with(|| b.cpu_usage.unwrap_or(0.0).total_cmp(&a.cpu_usage.unwrap_or(0.0)))
+        });
+        let kept = group[0];
+        for duplicate in &group[1..] {
+            steps.push(CleanupStep {
+                id: format!("stop:{}", duplicate.id),
+                category: CleanupCategory::Duplicate,
+                action: CleanupAction::StopService { service_id: duplicate.id.clone() },
+                title: format!("Doppelten Dienst {} stoppen", duplicate.name),
+                reason: format!("{} läuft bereits als {}", duplicate.name, kept.name),
+                risk: if duplicate.ports.is_empty() { CleanupRisk::Low } else { CleanupRisk::Medium },
+                expected_savings_bytes: duplicate.memory_bytes,
+            });
+        }
+    }
+    steps
+}
+
+fn idle_steps(services: &[&Service]) -> Vec<CleanupStep> {
+    services
+        .iter()
+        .filter(|s| {
+            s.status == ServiceStatus::Running
+                && s.ports.is_empty()
+                && s.cpu_usage.is_some_and(|cpu| cpu < IDLE_CPU_PERCENT)
+                && s.memory_bytes.unwrap_or(0) >= IDLE_MIN_MEMORY_BYTES
+        })
+        .map(|s| CleanupStep {
+            id: format!("stop:{}", s.id),
+            category: CleanupCategory::IdleService,
+            action: CleanupAction::StopService { service_id: s.id.clone() },
+            title: format!("Inaktiven Dienst {} stoppen", s.name),
+            reason: "Läuft ohne offene Ports und nahezu ohne CPU-Last".to_string(),
+            risk: CleanupRisk::Medium,
+            expected_savings_bytes: s.memory_bytes,
+        })
+        .collect()
+}
+
+fn startup_steps(services: &[&Service]) -> Vec<CleanupStep> {
+    services
+        .iter()
+        .filter(|s| s.auto_start && s.ports.is_empty() && s.memory_bytes.unwrap_or(0) >= STARTUP_MIN_MEMORY_BYTES)
+        .map(|s| CleanupStep {
+            id: format!("autostart:{}", s.id),
+            category: CleanupCategory::StartupImpact,
+            action: CleanupAction::DisableAutostart {
+                service_id: s.id.clone(),
+                service_type: s.service_type.clone(),
+            },
+            title: format!("Autostart von {} deaktivieren", s.name),
+            reason: "Startet mit dem System, belegt viel Arbeitsspeicher und bietet keine Ports an".to_string(),
+            risk: CleanupRisk::Low,
+            expected_savings_bytes: s.memory_bytes,
+        })
+        .collect()
+}
+
+/// What two services have to share to be the same service: replicas of one
+/// Compose service, or the same unit including its template instance, so
+/// `getty@tty1` and `getty@tty2` stay apart. Containers outside of Compose
+/// are never grouped, their names say nothing about what they run.
+fn duplicate_key(service: &Service) -> Option<String> {
+    match service.service_type {
+        ServiceType::Docker => Some(format!(
+            "compose:{}/{}",
+            service.compose_project.as_deref()?,
+            service.compose_service.as_deref()?
+        )),
+        _ => Some(format!("unit:{}", service.name.to_lowercase().trim_end_matches(".service"))),
+    }
+}
+
+fn short_id(id: &str) -> &str {
+    let id = id.trim_start_matches("sha256:");
+    &id[..id.len().min(12)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running(id: &str, service_type: ServiceType) -> Service {
+        Service {
+            id: id.to_string(),
+            name: id.trim_start_matches('/').to_string(),
+            status: ServiceStatus::Running,
+            service_type,
+            ports: Vec::new(),
+            pid: None,
+            path: None,
+            description: None,
+            auto_start: false,
+            cpu_usage: Some(1.0),
+            memory_bytes: Some(10 * 1024 * 1024),
+            memory_percent: None,
+            cpu_usage_total: None,
+            memory_total: None,
+            gpu_usage: None,
+            energy: None,
+            network_rx_bytes: None,
+            network_tx_bytes: None,
+            user: None,
+            recent_restarts: 0,
+            flapping: false,
+            compose_project: None,
+            compose_service: None,
+        }
+    }
+
+    fn container(name: &str, project: &str, service: &str) -> Service {
+        Service {
+            compose_project: Some(project.to_string()),
+            compose_service: Some(service.to_string()),
+            ..running(name, ServiceType::Docker)
+        }
+    }
+
+    fn duplicates(services: &[Service]) -> Vec<String> {
+        let services: Vec<&Service> = services.iter().collect();
+        let mut ids: Vec<String> = duplicate_steps(&services).into_iter().map(|s| s.id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn services_of_one_compose_project_are_not_duplicates() {
+        let services = [container("/app-db-1", "app", "db"), container("/app-web-1", "app", "web")];
+        assert!(duplicates(&services).is_empty());
+    }
+
+    #[test]
+    fn replicas_of_a_compose_service_are_duplicates() {
+        let mut busy = container("/app-web-1", "app", "web");
+        busy.ports = vec![8080];
+        let services = [busy, container("/app-web-2", "app", "web"), container("/app-db-1", "app", "db")];
+        assert_eq!(duplicates(&services), ["stop:/app-web-2"]);
+    }
+
+    #[test]
+    fn containers_without_compose_labels_are_not_grouped() {
+        let services = [running("/postgres", ServiceType::Docker), running("/postgres-test", ServiceType::Docker)];
+        assert!(duplicates(&services).is_empty());
+    }
+
+    #[test]
+    fn template_instances_are_not_duplicates() {
+        let services = [
+            running("getty@tty1.service", ServiceType::Systemd),
+            running("getty@tty2.service", ServiceType::Systemd),
+            running("user@1000.service", ServiceType::Systemd),
+            running("user-runtime-dir@1000.service", ServiceType::Systemd),
+            running("snap.lxd.daemon.service", ServiceType::Systemd),
+            running("snap.docker.dockerd.service", ServiceType::Systemd),
+        ];
+        assert!(duplicates(&services).is_empty());
+    }
+
+    #[test]
+    fn same_unit_twice_is_a_duplicate() {
+        let system = running("nginx.service", ServiceType::Systemd);
+        let user = Service { id: "user:nginx.service".to_string(), ..system.clone() };
+        assert_eq!(duplicates(&[system, user]).len(), 1);
+    }
+}
@@ -0,0 +1,62 @@
+// Helpers for running external tools
+
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Apps started from the macOS Finder or a desktop launcher get a minimal
+/// PATH; Homebrew and sbin tools live outside of it
+#[cfg(unix)]
+const EXTRA_DIRS: &[&str] = &["/usr/local/bin", "/opt/homebrew/bin", "/usr/sbin", "/sbin"];
+#[cfg(not(unix))]
+const EXTRA_DIRS: &[&str] = &[];
+
+/// Build a `Command` pinned to the C locale.
+///
+/// Tools like `ps`, `ss`, `netstat` and `systemctl` translate headers, states
+/// and number formats on non-English systems, which the parsers can't handle.
+pub fn command(program: &str) -> Command {
+    let mut command = Command::new(program);
+    command
+        .env("LC_ALL", "C")
+        .env("LANG", "C")
+        .env("LANGUAGE", "C");
+    command
+}
+
+/// Locate an executable in PATH and the usual install directories
+pub fn find_in_path(program: &str) -> Option<PathBuf> {
+    let file_name = if cfg!(windows) {
+        format!("{}.exe", program)
+    } else {
+        program.to_string()
+    };
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    std::env::split_paths(&path)
+        .chain(EXTRA_DIRS.iter().map(PathBuf::from))
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn commands_run_under_the_c_locale() {
+        let command = command("ps");
+        let envs: Vec<_> = command.get_envs().collect();
+        for name in ["LC_ALL", "LANG", "LANGUAGE"] {
+            assert!(envs.contains(&(OsStr::new(name), Some(OsStr::new("C")))), "{} not set", name);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn child_sees_the_c_locale_whatever_the_parent_uses() {
+        let output = command("env").env("LC_MESSAGES", "de_DE.UTF-8").output().unwrap();
+        let env = String::from_utf8_lossy(&output.stdout);
+        assert!(env.lines().any(|l| l == "LC_ALL=C"));
+        assert!(env.lines().any(|l| l == "LANG=C"));
+    }
+}
@@ -0,0 +1,199 @@
+// Code signatures of the executables behind listening ports on macOS
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use crate::models::port::PortInfo;
+use crate::services::cmd;
+use crate::services::security_scanner::{SecurityCategory, SecurityIssue, SecuritySeverity};
+
+/// Executable path and its modification time
+type CacheKey = (PathBuf, Option<SystemTime>);
+
+/// Verified signatures; codesign takes a moment per binary and the
+/// executables rarely change
+static CACHE: OnceLock<Mutex<HashMap<CacheKey, CodeSignature>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// Signed with a certificate and intact
+    Signed,
+    /// Signed without an identity, e.g. by the linker on Apple silicon
+    AdHoc,
+    Unsigned,
+    /// The signature does not match the binary anymore
+    Invalid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeSignature {
+    pub path: String,
+    pub status: SignatureStatus,
+    /// Leaf certificate, e.g. `Developer ID Application: Docker Inc (9BNSXJN65R)`
+    pub authority: Option<String>,
+    pub team_id: Option<String>,
+    /// Gatekeeper would allow the binary to run
+    pub gatekeeper_accepted: bool,
+    /// First line codesign printed on failure
+    pub error: Option<String>,
+}
+
+/// Runs `codesign --verify` and `spctl --assess` on the executables of
+/// listening processes. Ad-hoc signed and unsigned binaries can't be tied
+/// to a developer, so nothing vouches for what they do with the port.
+pub struct CodeSigning;
+
+impl CodeSigning {
+    /// Blocking: runs codesign for every executable not checked before
+    pub fn verify(path: &Path) -> CodeSignature {
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let key = (path.to_path_buf(), modified);
+        let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+        if let Some(signature) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(&key) {
+            return signature.clone();
+        }
+        let signature = verify_uncached(path);
+        cache.lock().unwrap_or_else(|e| e.into_inner()).insert(key, signature.clone());
+        signature
+    }
+
+    /// One finding per executable that is unsigned, ad-hoc signed or whose
+    /// signature is broken
+    pub fn findings(port_usage: &[PortInfo]) -> Vec<SecurityIssue> {
+        let mut pids: Vec<u32> = port_usage.iter().filter_map(|p| p.pid).collect();
+        pids.sort_unstable();
+        pids.dedup();
+        if pids.is_empty() {
+            return Vec::new();
+        }
+
+        let mut system = System::new();
+        let refresh: Vec<Pid> = pids.iter().map(|&pid| Pid::from_u32(pid)).collect();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&refresh),
+            true,
+            ProcessRefreshKind::nothing().with_exe(UpdateKind::Always),
+        );
+
+        // Several processes and ports can share one executable
+        let mut executables: HashMap<PathBuf, Vec<&PortInfo>> = HashMap::new();
+        for port in port_usage {
+            let exe = port.pid.and_then(|pid| system.process(Pid::from_u32(pid))).and_then(|p| p.exe());
+            if let Some(exe) = exe {
+                executables.entry(exe.to_path_buf()).or_default().push(port);
+            }
+        }
+
+        let mut issues: Vec<SecurityIssue> = executables
+            .into_iter()
+            .filter_map(|(path, ports)| {
+                let signature = Self::verify(&path);
+                let (severity, title, description) = match signature.status {
+                    SignatureStatus::Signed => return None,
+                    SignatureStatus::Invalid => (
+                        SecuritySeverity::High,
+                        "hat eine ungültige Signatur",
+                        "Die Signatur passt nicht mehr zur Datei; das Programm wurde nach dem Signieren verändert",
+                    ),
+                    SignatureStatus::Unsigned => (
+                        SecuritySeverity::Medium,
+                        "ist nicht signiert",
+                        "Ein unsigniertes Programm lässt sich keinem Entwickler zuordnen",
+                    ),
+                    SignatureStatus::AdHoc => (
+                        SecuritySeverity::Low,
+                        "ist nur ad-hoc signiert",
+                        "Ad-hoc-Signaturen enthalten keine Identität; typisch für selbst kompilierte oder per Homebrew installierte Programme",
+                    ),
+                };
+                let mut port_numbers: Vec<u16> = ports.iter().map(|p| p.port).collect();
+                port_numbers.sort_unstable();
+                port_numbers.dedup();
+                let name = ports
+                    .iter()
+                    .find_map(|p| p.process_name.clone())
+                    .unwrap_or_else(|| path.file_name().unwrap_or_default().to_string_lossy().to_string());
+
+                let mut details = vec![signature.path.clone()];
+                details.push(format!(
+                    "Ports: {}",
+                    port_numbers.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", ")
+                ));
+                if !signature.gatekeeper_accepted {
+                    details.push("Von Gatekeeper abgelehnt".to_string());
+                }
+                if let Some(error) = &signature.error {
+                    details.push(error.clone());
+                }
+
+                Some(SecurityIssue {
+                    id: format!("code-signature-{}", signature.path),
+                    service_id: None,
+                    service_name: Some(name.clone()),
+                    category: SecurityCategory::UntrustedBinary,
+                    severity,
+                    title: format!("{} {} und lauscht auf Port {}", name, title, port_numbers[0]),
+                    description: description.to_string(),
+                    recommendation: "Prüfe die Herkunft des Programms; installiere es aus einer signierten Quelle oder beschränke den Port auf localhost".to_string(),
+                    port: port_numbers.first().copied(),
+                    details: Some(details.join(", ")),
+                })
+            })
+            .collect();
+        issues.sort_by(|a, b| a.id.cmp(&b.id));
+        issues
+    }
+}
+
+fn verify_uncached(path: &Path) -> CodeSignature {
+    let path_str = path.to_string_lossy().to_string();
+    let mut signature = CodeSignature {
+        path: path_str.clone(),
+        status: SignatureStatus::Signed,
+        authority: None,
+        team_id: None,
+        gatekeeper_accepted: false,
+        error: None,
+    };
+
+    // codesign reports on stderr, also on success
+    match cmd::command("codesign").args(["--verify", "--strict", &path_str]).output() {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            signature.status = if stderr.contains("not signed at all") {
+                SignatureStatus::Unsigned
+            } else {
+                SignatureStatus::Invalid
+            };
+            signature.error = stderr.lines().next().map(|line| line.trim().to_string());
+        }
+        Err(e) => signature.error = Some(e.to_string()),
+    }
+
+    // `Signature=adhoc`, `Authority=Developer ID Application: ...`, `TeamIdentifier=...`
+    if signature.status != SignatureStatus::Unsigned {
+        if let Ok(output) = cmd::command("codesign").args(["-dv", "--verbose=2", &path_str]).output() {
+            let info = String::from_utf8_lossy(&output.stderr);
+            if signature.status == SignatureStatus::Signed && info.lines().any(|line| line.trim() == "Signature=adhoc") {
+                signature.status = SignatureStatus::AdHoc;
+            }
+            signature.authority = info.lines().find_map(|line| line.strip_prefix("Authority=")).map(String::from);
+            signature.team_id = info
+                .lines()
+                .find_map(|line| line.strip_prefix("TeamIdentifier="))
+                .filter(|team| *team != "not set")
+                .map(String::from);
+        }
+    }
+
+    signature.gatekeeper_accepted = cmd::command("spctl")
+        .args(["--assess", "--type", "execute", &path_str])
+        .output()
+        .is_ok_and(|output| output.status.success());
+    signature
+}
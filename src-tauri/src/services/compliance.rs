@@ -0,0 +1,516 @@
+// Compliance profiles: baseline controls scored against a security scan
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::services::firewall::{FirewallChecker, FirewallState};
+use crate::services::port::PortResolver;
+use crate::services::security_scanner::{SecurityCategory, SecurityIssue, SecurityScanResult, SecuritySeverity};
+use crate::services::time_sync::{TimeSyncChecker, TimeSyncHealth};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceProfile {
+    MacosWorkstation,
+    LinuxServer,
+}
+
+impl ComplianceProfile {
+    pub const ALL: [ComplianceProfile; 2] = [Self::MacosWorkstation, Self::LinuxServer];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MacosWorkstation => "macOS-Arbeitsplatz",
+            Self::LinuxServer => "Linux-Server",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::MacosWorkstation => "Verschlüsselung, Gatekeeper, SIP, Firewall und signierte Dienste, angelehnt an den CIS-Benchmark für macOS",
+            Self::LinuxServer => "SSH-Härtung, Firewall, Updates, SUID-Programme und Datenbankzugriff, angelehnt an die CIS-Benchmarks für Linux",
+        }
+    }
+
+    fn controls(&self) -> &'static [Control] {
+        match self {
+            Self::MacosWorkstation => MACOS_WORKSTATION,
+            Self::LinuxServer => LINUX_SERVER,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlStatus {
+    Pass,
+    Fail,
+    /// Failed only by findings acknowledged as accepted risks
+    Accepted,
+    /// The state could not be read, e.g. without root
+    Unknown,
+    /// Not available on this operating system
+    NotApplicable,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlResult {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub severity: SecuritySeverity,
+    pub status: ControlStatus,
+    /// What the check saw, e.g. the failing findings
+    pub evidence: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileInfo {
+    pub profile: ComplianceProfile,
+    pub name: String,
+    pub description: String,
+    pub controls: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceReport {
+    pub profile: ComplianceProfile,
+    pub name: String,
+    pub controls: Vec<ControlResult>,
+    pub passed: usize,
+    pub failed: usize,
+    /// Unknown and not applicable controls, left out of the score
+    pub skipped: usize,
+    /// Passed and accepted controls in percent of all scored controls
+    pub score: u8,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// How a control is decided
+enum Check {
+    /// Fails on any finding of the scan the predicate matches
+    Findings(fn(&SecurityIssue) -> bool),
+    Firewall,
+    TimeSync,
+    AutomaticUpdates,
+    /// Nothing listens on the port on a non-loopback address
+    PortClosed(u16),
+    DiskEncryption,
+    Gatekeeper,
+    SystemIntegrity,
+}
+
+struct Control {
+    id: &'static str,
+    title: &'static str,
+    description: &'static str,
+    severity: SecuritySeverity,
+    check: Check,
+}
+
+fn id_prefix(issue: &SecurityIssue, prefixes: &[&str]) -> bool {
+    prefixes.iter().any(|prefix| issue.id.starts_with(prefix))
+}
+
+const FIREWALL: Control = Control {
+    id: "firewall",
+    title: "Firewall ist aktiv",
+    description: "Eingehende Verbindungen werden von einer Host-Firewall gefiltert",
+    severity: SecuritySeverity::High,
+    check: Check::Firewall,
+};
+const TIME_SYNC: Control = Control {
+    id: "time-sync",
+    title: "Uhrzeit wird synchronisiert",
+    description: "Die Systemzeit weicht nicht merklich ab; Logs und Zertifikate bleiben gültig",
+    severity: SecuritySeverity::Low,
+    check: Check::TimeSync,
+};
+const AUTOMATIC_UPDATES: Control = Control {
+    id: "automatic-updates",
+    title: "Sicherheitsupdates werden automatisch installiert",
+    description: "unattended-upgrades, dnf-automatic oder die automatische Softwareaktualisierung von macOS ist aktiv",
+    severity: SecuritySeverity::Medium,
+    check: Check::AutomaticUpdates,
+};
+const NO_INSECURE_PORTS: Control = Control {
+    id: "insecure-ports",
+    title: "Keine unverschlüsselten Protokolle",
+    description: "Telnet, FTP und ähnliche Dienste ohne Verschlüsselung lauschen nicht",
+    severity: SecuritySeverity::High,
+    check: Check::Findings(|i| id_prefix(i, &["port-"])),
+};
+const DATASTORE_ACCESS: Control = Control {
+    id: "datastore-access",
+    title: "Datenbanken verlangen eine Anmeldung",
+    description: "Keine Datenbank ist öffentlich erreichbar oder ohne Passwort zugänglich",
+    severity: SecuritySeverity::Critical,
+    check: Check::Findings(|i| {
+        id_prefix(i, &["public-db-", "unauthenticated-", "config-postgres-hba-trust", "config-redis-protected-mode"])
+    }),
+};
+const TLS: Control = Control {
+    id: "tls",
+    title: "TLS-Dienste sind korrekt konfiguriert",
+    description: "Keine abgelaufenen Zertifikate, veralteten Protokolle oder schwachen Cipher Suites",
+    severity: SecuritySeverity::Medium,
+    check: Check::Findings(|i| id_prefix(i, &["tls-expired-", "tls-legacy-protocol-", "tls-weak-cipher-", "config-nginx-ssl-protocols"])),
+};
+const ENV_SECRETS: Control = Control {
+    id: "env-secrets",
+    title: "Keine Geheimnisse in Umgebungsvariablen",
+    description: "Laufende Dienste bekommen keine Schlüssel oder Tokens über ihre Umgebung",
+    severity: SecuritySeverity::Medium,
+    check: Check::Findings(|i| id_prefix(i, &["env-secret-"]) && i.severity != SecuritySeverity::Low),
+};
+const SETID: Control = Control {
+    id: "setid-binaries",
+    title: "Keine ungewöhnlichen SUID/SGID-Programme",
+    description: "Nur die von der Distribution ausgelieferten Programme laufen mit erhöhten Rechten",
+    severity: SecuritySeverity::High,
+    check: Check::Findings(|i| id_prefix(i, &["setid-"]) && i.severity != SecuritySeverity::Low),
+};
+
+const LINUX_SERVER: &[Control] = &[
+    FIREWALL,
+    AUTOMATIC_UPDATES,
+    TIME_SYNC,
+    Control {
+        id: "ssh-root-login",
+        title: "SSH verbietet die Anmeldung als root",
+        description: "PermitRootLogin ist nicht auf yes gesetzt",
+        severity: SecuritySeverity::High,
+        check: Check::Findings(|i| i.id == "config-sshd-permit-root-login"),
+    },
+    Control {
+        id: "ssh-empty-passwords",
+        title: "SSH verbietet leere Passwörter",
+        description: "PermitEmptyPasswords ist nicht auf yes gesetzt",
+        severity: SecuritySeverity::Critical,
+        check: Check::Findings(|i| i.id == "config-sshd-permit-empty-passwords"),
+    },
+    Control {
+        id: "ssh-password-authentication",
+        title: "SSH nur mit Schlüsseln",
+        description: "PasswordAuthentication ist auf no gesetzt",
+        severity: SecuritySeverity::Medium,
+        check: Check::Findings(|i| i.id == "config-sshd-password-authentication"),
+    },
+    Control {
+        id: "config-permissions",
+        title: "Konfigurationsdateien sind geschützt",
+        description: "Keine Konfiguration ist für alle beschreibbar, keine Datei mit Geheimnissen für alle lesbar",
+        severity: SecuritySeverity::High,
+        check: Check::Findings(|i| i.id.starts_with("config-") && (i.id.contains("-writable-") || i.id.contains("-readable-"))),
+    },
+    SETID,
+    Control {
+        id: "file-capabilities",
+        title: "Keine gefährlichen Datei-Capabilities",
+        description: "Kein Programm erhält über Capabilities root-gleiche Rechte",
+        severity: SecuritySeverity::High,
+        check: Check::Findings(|i| id_prefix(i, &["file-capability-"]) && i.severity != SecuritySeverity::Low),
+    },
+    Control {
+        id: "root-services",
+        title: "Dienste laufen ohne root",
+        description: "Nicht-System-Dienste haben eigene, unprivilegierte Benutzer",
+        severity: SecuritySeverity::Medium,
+        check: Check::Findings(|i| id_prefix(i, &["root-"])),
+    },
+    Control {
+        id: "container-isolation",
+        title: "Container sind vom Host isoliert",
+        description: "Kein Container läuft privilegiert, mit Docker-Socket oder mit gefährlichen Capabilities",
+        severity: SecuritySeverity::Critical,
+        check: Check::Findings(|i| {
+            id_prefix(i, &["docker-privileged-", "docker-docker-socket-"])
+                || (i.id.starts_with("docker-capabilities-") && i.severity != SecuritySeverity::Low)
+        }),
+    },
+    NO_INSECURE_PORTS,
+    DATASTORE_ACCESS,
+    TLS,
+    ENV_SECRETS,
+    Control {
+        id: "drive-health",
+        title: "Laufwerke sind gesund",
+        description: "SMART meldet keine Fehler oder verbrauchte Reserven",
+        severity: SecuritySeverity::Medium,
+        check: Check::Findings(|i| matches!(i.category, SecurityCategory::Reliability)),
+    },
+];
+
+const MACOS_WORKSTATION: &[Control] = &[
+    Control {
+        id: "filevault",
+        title: "FileVault ist aktiv",
+        description: "Die Startfestplatte ist verschlüsselt",
+        severity: SecuritySeverity::High,
+        check: Check::DiskEncryption,
+    },
+    Control {
+        id: "gatekeeper",
+        title: "Gatekeeper ist aktiv",
+        description: "Programme aus dem Internet werden vor dem Start geprüft",
+        severity: SecuritySeverity::High,
+        check: Check::Gatekeeper,
+    },
+    Control {
+        id: "sip",
+        title: "Der Systemintegritätsschutz ist aktiv",
+        description: "SIP schützt Systemdateien auch vor root",
+        severity: SecuritySeverity::High,
+        check: Check::SystemIntegrity,
+    },
+    FIREWALL,
+    AUTOMATIC_UPDATES,
+    TIME_SYNC,
+    Control {
+        id: "remote-login",
+        title: "Entfernte Anmeldung ist aus",
+        description: "Kein SSH-Server lauscht im Netzwerk (Freigaben > Entfernte Anmeldung)",
+        severity: SecuritySeverity::Medium,
+        check: Check::PortClosed(22),
+    },
+    Control {
+        id: "signed-services",
+        title: "Netzwerkdienste sind signiert",
+        description: "Kein Programm hinter einem offenen Port ist unsigniert oder verändert",
+        severity: SecuritySeverity::Medium,
+        check: Check::Findings(|i| id_prefix(i, &["code-signature-"]) && i.severity != SecuritySeverity::Low),
+    },
+    Control {
+        id: "known-malware",
+        title: "Keine bekannte Schadsoftware",
+        description: "Kein Programm hinter einem offenen Port ist bei MalwareBazaar oder VirusTotal gemeldet",
+        severity: SecuritySeverity::Critical,
+        check: Check::Findings(|i| id_prefix(i, &["hash-reputation-"])),
+    },
+    SETID,
+    NO_INSECURE_PORTS,
+    DATASTORE_ACCESS,
+    ENV_SECRETS,
+];
+
+/// Scores a security scan against a profile. Controls backed by scan
+/// findings pass when no finding matches; the remaining controls read the
+/// system state directly.
+pub struct Compliance;
+
+impl Compliance {
+    pub fn profiles() -> Vec<ProfileInfo> {
+        ComplianceProfile::ALL
+            .iter()
+            .map(|profile| ProfileInfo {
+                profile: *profile,
+                name: profile.name().to_string(),
+                description: profile.description().to_string(),
+                controls: profile.controls().len(),
+            })
+            .collect()
+    }
+
+    /// Blocking: runs the firewall, time sync and platform checks
+    pub fn evaluate(profile: ComplianceProfile, scan: &SecurityScanResult) -> ComplianceReport {
+        let controls: Vec<ControlResult> = profile
+            .controls()
+            .iter()
+            .map(|control| {
+                let (status, evidence) = evaluate_control(&control.check, scan);
+                ControlResult {
+                    id: control.id.to_string(),
+                    title: control.title.to_string(),
+                    description: control.description.to_string(),
+                    severity: control.severity.clone(),
+                    status,
+                    evidence,
+                }
+            })
+            .collect();
+
+        let passed = controls
+            .iter()
+            .filter(|c| matches!(c.status, ControlStatus::Pass | ControlStatus::Accepted))
+            .count();
+        let failed = controls.iter().filter(|c| c.status == ControlStatus::Fail).count();
+        let scored = passed + failed;
+        ComplianceReport {
+            profile,
+            name: profile.name().to_string(),
+            skipped: controls.len() - scored,
+            controls,
+            passed,
+            failed,
+            score: (passed * 100).checked_div(scored).unwrap_or(0) as u8,
+            checked_at: Utc::now(),
+        }
+    }
+}
+
+fn evaluate_control(check: &Check, scan: &SecurityScanResult) -> (ControlStatus, Option<String>) {
+    match check {
+        Check::Findings(matches) => {
+            let failing: Vec<&str> = scan.issues.iter().filter(|i| matches(i)).map(|i| i.title.as_str()).collect();
+            if !failing.is_empty() {
+                return (ControlStatus::Fail, Some(failing.join("; ")));
+            }
+            let accepted: Vec<&str> = scan
+                .acknowledged
+                .iter()
+                .filter(|a| matches(&a.issue))
+                .map(|a| a.issue.title.as_str())
+                .collect();
+            if accepted.is_empty() {
+                (ControlStatus::Pass, None)
+            } else {
+                (ControlStatus::Accepted, Some(accepted.join("; ")))
+            }
+        }
+        Check::Firewall => {
+            let status = FirewallChecker::new().check();
+            let backends: Vec<&str> = status.backends.iter().map(|b| b.name.as_str()).collect();
+            let evidence = (!backends.is_empty()).then(|| backends.join(", "));
+            match status.state {
+                FirewallState::Enabled => (ControlStatus::Pass, evidence),
+                FirewallState::Disabled => (ControlStatus::Fail, evidence),
+                FirewallState::Unknown => (ControlStatus::Unknown, status.warnings.first().cloned()),
+            }
+        }
+        Check::TimeSync => {
+            let status = TimeSyncChecker::new().check();
+            let evidence = status.issues.first().cloned().or(status.service);
+            match status.health {
+                TimeSyncHealth::Ok => (ControlStatus::Pass, evidence),
+                TimeSyncHealth::Warning | TimeSyncHealth::Critical => (ControlStatus::Fail, evidence),
+                TimeSyncHealth::Unknown => (ControlStatus::Unknown, evidence),
+            }
+        }
+        Check::PortClosed(port) => {
+            let listening = PortResolver::new()
+                .get_port_usage()
+                .iter()
+                .any(|p| p.port == *port && !p.is_loopback());
+            if listening {
+                (ControlStatus::Fail, Some(format!("Port {} ist geöffnet", port)))
+            } else {
+                (ControlStatus::Pass, None)
+            }
+        }
+        Check::AutomaticUpdates => automatic_updates(),
+        Check::DiskEncryption => disk_encryption(),
+        Check::Gatekeeper => gatekeeper(),
+        Check::SystemIntegrity => system_integrity(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn automatic_updates() -> (ControlStatus, Option<String>) {
+    use crate::services::cmd;
+
+    // Debian/Ubuntu: `APT::Periodic::Unattended-Upgrade "1";` in apt.conf.d
+    if let Ok(entries) = std::fs::read_dir("/etc/apt/apt.conf.d") {
+        let enabled = entries.flatten().any(|entry| {
+            std::fs::read_to_string(entry.path()).is_ok_and(|content| {
+                content
+                    .lines()
+                    .any(|line| line.trim().starts_with("APT::Periodic::Unattended-Upgrade") && line.contains("\"1\""))
+            })
+        });
+        return if enabled {
+            (ControlStatus::Pass, Some("unattended-upgrades".to_string()))
+        } else {
+            (ControlStatus::Fail, Some("unattended-upgrades ist nicht aktiviert".to_string()))
+        };
+    }
+    // Fedora/RHEL
+    if cmd::find_in_path("dnf").is_some() {
+        let timers = ["dnf-automatic.timer", "dnf-automatic-install.timer", "dnf5-automatic.timer"];
+        let enabled = timers.iter().find(|timer| {
+            cmd::command("systemctl")
+                .args(["is-enabled", timer])
+                .output()
+                .is_ok_and(|o| o.status.success())
+        });
+        return match enabled {
+            Some(timer) => (ControlStatus::Pass, Some(timer.to_string())),
+            None => (ControlStatus::Fail, Some("dnf-automatic ist nicht aktiviert".to_string())),
+        };
+    }
+    (ControlStatus::Unknown, Some("Weder apt noch dnf gefunden".to_string()))
+}
+
+/// Missing keys mean the default, which is on
+#[cfg(target_os = "macos")]
+fn automatic_updates() -> (ControlStatus, Option<String>) {
+    let disabled: Vec<&str> = ["AutomaticCheckEnabled", "CriticalUpdateInstall"]
+        .into_iter()
+        .filter(|key| {
+            crate::services::cmd::command("defaults")
+                .args(["read", "/Library/Preferences/com.apple.SoftwareUpdate", key])
+                .output()
+                .is_ok_and(|o| o.status.success() && String::from_utf8_lossy(&o.stdout).trim() == "0")
+        })
+        .collect();
+    if disabled.is_empty() {
+        (ControlStatus::Pass, None)
+    } else {
+        (ControlStatus::Fail, Some(format!("Deaktiviert: {}", disabled.join(", "))))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn automatic_updates() -> (ControlStatus, Option<String>) {
+    (ControlStatus::NotApplicable, None)
+}
+
+/// First line of a macOS status tool and whether it contains `expected`
+#[cfg(target_os = "macos")]
+fn macos_status(program: &str, args: &[&str], expected: &str) -> (ControlStatus, Option<String>) {
+    let Ok(output) = crate::services::cmd::command(program).args(args).output() else {
+        return (ControlStatus::Unknown, None);
+    };
+    // spctl writes its status to stderr on some releases
+    let text = [output.stdout, output.stderr].concat();
+    let text = String::from_utf8_lossy(&text);
+    let line = text.lines().next().unwrap_or_default().trim().to_string();
+    if line.is_empty() {
+        (ControlStatus::Unknown, None)
+    } else if line.contains(expected) {
+        (ControlStatus::Pass, Some(line))
+    } else {
+        (ControlStatus::Fail, Some(line))
+    }
+}
+
+/// `FileVault is On.`
+#[cfg(target_os = "macos")]
+fn disk_encryption() -> (ControlStatus, Option<String>) {
+    macos_status("fdesetup", &["status"], "FileVault is On")
+}
+
+/// `assessments enabled`
+#[cfg(target_os = "macos")]
+fn gatekeeper() -> (ControlStatus, Option<String>) {
+    macos_status("spctl", &["--status"], "assessments enabled")
+}
+
+/// `System Integrity Protection status: enabled.`
+#[cfg(target_os = "macos")]
+fn system_integrity() -> (ControlStatus, Option<String>) {
+    macos_status("csrutil", &["status"], "status: enabled")
+}
+
+#[cfg(not(target_os = "macos"))]
+fn disk_encryption() -> (ControlStatus, Option<String>) {
+    (ControlStatus::NotApplicable, None)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn gatekeeper() -> (ControlStatus, Option<String>) {
+    (ControlStatus::NotApplicable, None)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn system_integrity() -> (ControlStatus, Option<String>) {
+    (ControlStatus::NotApplicable, None)
+}
@@ -0,0 +1,428 @@
+// Permissions and risky settings in the config files of known services
+
+use std::collections::HashSet;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use crate::models::service::Service;
+use crate::services::security_scanner::{SecurityCategory, SecurityIssue, SecuritySeverity};
+
+const SSHD_CONFIGS: &[&str] = &["/etc/ssh/sshd_config", "/private/etc/ssh/sshd_config"];
+const REDIS_CONFIGS: &[&str] = &[
+    "/etc/redis/redis.conf", "/etc/redis.conf", "/usr/local/etc/redis.conf", "/opt/homebrew/etc/redis.conf",
+];
+const NGINX_CONFIGS: &[&str] = &[
+    "/etc/nginx/nginx.conf", "/usr/local/etc/nginx/nginx.conf", "/opt/homebrew/etc/nginx/nginx.conf",
+];
+/// Data directories holding `pg_hba.conf`; Debian keeps it in `/etc/postgresql/<version>/main`
+const POSTGRES_DIRS: &[&str] = &[
+    "/etc/postgresql", "/var/lib/pgsql/data", "/var/lib/postgres/data", "/usr/local/var/postgres", "/opt/homebrew/var",
+    "/usr/local/var",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ConfigKind {
+    Sshd,
+    Redis,
+    Nginx,
+    Postgres,
+}
+
+impl ConfigKind {
+    fn of(service: &Service) -> Option<Self> {
+        let name = service.name.to_lowercase();
+        if name.contains("sshd") || name == "ssh" || name.ends_with(".sshd") {
+            Some(Self::Sshd)
+        } else if name.contains("redis") {
+            Some(Self::Redis)
+        } else if name.contains("nginx") {
+            Some(Self::Nginx)
+        } else if name.contains("postgres") {
+            Some(Self::Postgres)
+        } else {
+            None
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        match self {
+            Self::Sshd => "sshd",
+            Self::Redis => "redis",
+            Self::Nginx => "nginx",
+            Self::Postgres => "postgres",
+        }
+    }
+}
+
+/// Finds the main config files of recognized services (sshd, Redis, nginx,
+/// PostgreSQL), flags files that other users can modify or, if they hold
+/// credentials or keys, read, and checks a few settings known to open the
+/// service up.
+pub struct ConfigAudit;
+
+impl ConfigAudit {
+    /// Blocking: reads the config files and the services' command lines
+    pub fn findings(services: &[Service]) -> Vec<SecurityIssue> {
+        let recognized: Vec<(&Service, ConfigKind)> = services
+            .iter()
+            .filter_map(|service| Some((service, ConfigKind::of(service)?)))
+            .collect();
+        if recognized.is_empty() {
+            return Vec::new();
+        }
+
+        // `redis-server /path/redis.conf`, `postgres -D /path`
+        let pids: Vec<Pid> = recognized.iter().filter_map(|(s, _)| s.pid).map(Pid::from_u32).collect();
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&pids),
+            true,
+            ProcessRefreshKind::nothing().with_cmd(UpdateKind::Always),
+        );
+
+        let mut checked = HashSet::new();
+        let mut issues = Vec::new();
+        for (service, kind) in recognized {
+            if !checked.insert(kind) {
+                continue;
+            }
+            let args: Vec<String> = service
+                .pid
+                .and_then(|pid| system.process(Pid::from_u32(pid)))
+                .map(|p| p.cmd().iter().map(|arg| arg.to_string_lossy().to_string()).collect())
+                .unwrap_or_default();
+            match kind {
+                ConfigKind::Sshd => check_sshd(service, &mut issues),
+                ConfigKind::Redis => check_redis(service, &args, &mut issues),
+                ConfigKind::Nginx => check_nginx(service, &args, &mut issues),
+                ConfigKind::Postgres => check_postgres(service, &args, &mut issues),
+            }
+        }
+        issues
+    }
+}
+
+fn issue(
+    service: &Service,
+    check: &str,
+    severity: SecuritySeverity,
+    title: String,
+    description: &str,
+    recommendation: String,
+    path: &Path,
+) -> SecurityIssue {
+    SecurityIssue {
+        id: format!("config-{}", check),
+        service_id: Some(service.id.clone()),
+        service_name: Some(service.name.clone()),
+        category: SecurityCategory::InsecureConfiguration,
+        severity,
+        title,
+        description: description.to_string(),
+        recommendation,
+        port: service.ports.first().copied(),
+        details: Some(path.to_string_lossy().to_string()),
+    }
+}
+
+/// World-writable files always; world-readable ones only if they hold secrets
+fn check_permissions(service: &Service, kind: ConfigKind, path: &Path, secret: bool, issues: &mut Vec<SecurityIssue>) {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return;
+    };
+    let mode = metadata.mode() & 0o777;
+    let file = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    if mode & 0o002 != 0 {
+        issues.push(issue(
+            service,
+            &format!("{}-writable-{}", kind.id(), file),
+            SecuritySeverity::High,
+            format!("{} ist für alle Benutzer beschreibbar", path.display()),
+            "Jeder lokale Benutzer kann die Konfiguration ändern und damit den Dienst übernehmen",
+            format!("sudo chmod o-w {}", path.display()),
+            path,
+        ));
+    } else if secret && mode & 0o004 != 0 {
+        issues.push(issue(
+            service,
+            &format!("{}-readable-{}", kind.id(), file),
+            SecuritySeverity::Medium,
+            format!("{} ist für alle Benutzer lesbar", path.display()),
+            "Die Datei enthält Passwörter oder private Schlüssel",
+            format!("sudo chmod o-r {}", path.display()),
+            path,
+        ));
+    }
+}
+
+/// Non-comment lines as (lowercase keyword, rest)
+fn directives(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once(|c: char| c.is_whitespace() || c == '=')?;
+            Some((key.to_lowercase(), value.trim().trim_matches(['=', ' ', '\t']).to_string()))
+        })
+        .collect()
+}
+
+/// sshd uses the first value it reads, including from `Include`d files,
+/// and everything after `Match` applies only to some connections
+fn sshd_directives(path: &Path, depth: u32, out: &mut Vec<(String, String)>) -> bool {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return false;
+    };
+    for (key, value) in directives(&content) {
+        match key.as_str() {
+            "match" => return true,
+            "include" if depth < 3 => {
+                for pattern in value.split_whitespace() {
+                    for included in expand_glob(pattern) {
+                        if sshd_directives(&included, depth + 1, out) {
+                            return true;
+                        }
+                    }
+                }
+            }
+            _ => out.push((key, value)),
+        }
+    }
+    false
+}
+
+/// `/etc/ssh/sshd_config.d/*.conf`; only a `*` in the file name is supported
+fn expand_glob(pattern: &str) -> Vec<PathBuf> {
+    let path = if pattern.starts_with('/') {
+        PathBuf::from(pattern)
+    } else {
+        Path::new("/etc/ssh").join(pattern)
+    };
+    let file = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let Some((prefix, suffix)) = file.split_once('*') else {
+        return vec![path];
+    };
+    let Some(dir) = path.parent() else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|p| {
+                    let name = p.file_name().unwrap_or_default().to_string_lossy();
+                    name.starts_with(prefix) && name.ends_with(suffix)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn first<'a>(directives: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    directives.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+}
+
+/// All values of an nginx directive, without the trailing `;`
+fn values<'a>(directives: &'a [(String, String)], key: &'a str) -> impl Iterator<Item = &'a str> {
+    directives
+        .iter()
+        .filter(move |(k, _)| k == key)
+        .map(|(_, v)| v.trim_end_matches(';').trim())
+}
+
+fn check_sshd(service: &Service, issues: &mut Vec<SecurityIssue>) {
+    let Some(path) = SSHD_CONFIGS.iter().map(Path::new).find(|p| p.exists()) else {
+        return;
+    };
+    check_permissions(service, ConfigKind::Sshd, path, false, issues);
+    let mut settings = Vec::new();
+    sshd_directives(path, 0, &mut settings);
+
+    if first(&settings, "permitrootlogin").is_some_and(|v| v.eq_ignore_ascii_case("yes")) {
+        issues.push(issue(
+            service,
+            "sshd-permit-root-login",
+            SecuritySeverity::High,
+            "SSH erlaubt die Anmeldung als root".to_string(),
+            "Mit 'PermitRootLogin yes' reicht ein erratenes root-Passwort für vollen Zugriff",
+            "Setze 'PermitRootLogin no' oder 'prohibit-password' in sshd_config".to_string(),
+            path,
+        ));
+    }
+    if first(&settings, "permitemptypasswords").is_some_and(|v| v.eq_ignore_ascii_case("yes")) {
+        issues.push(issue(
+            service,
+            "sshd-permit-empty-passwords",
+            SecuritySeverity::Critical,
+            "SSH erlaubt leere Passwörter".to_string(),
+            "Konten ohne Passwort können sich ohne jede Anmeldung verbinden",
+            "Setze 'PermitEmptyPasswords no' in sshd_config".to_string(),
+            path,
+        ));
+    }
+    // The default is yes, so only an explicit `no` disables it
+    if !first(&settings, "passwordauthentication").is_some_and(|v| v.eq_ignore_ascii_case("no")) {
+        issues.push(issue(
+            service,
+            "sshd-password-authentication",
+            SecuritySeverity::Low,
+            "SSH erlaubt die Anmeldung mit Passwort".to_string(),
+            "Passwörter lassen sich erraten; Schlüssel nicht",
+            "Hinterlege SSH-Schlüssel und setze 'PasswordAuthentication no' in sshd_config".to_string(),
+            path,
+        ));
+    }
+}
+
+/// The config is the first argument ending in `.conf`, e.g. `redis-server /etc/redis/redis.conf`
+fn argument_config(args: &[String]) -> Option<PathBuf> {
+    args.iter().skip(1).find(|arg| arg.ends_with(".conf")).map(PathBuf::from)
+}
+
+fn check_redis(service: &Service, args: &[String], issues: &mut Vec<SecurityIssue>) {
+    let path = argument_config(args)
+        .filter(|p| p.exists())
+        .or_else(|| REDIS_CONFIGS.iter().map(PathBuf::from).find(|p| p.exists()));
+    let Some(path) = path else {
+        return;
+    };
+    let settings = std::fs::read_to_string(&path).map(|c| directives(&c)).unwrap_or_default();
+    let password = first(&settings, "requirepass").is_some() || first(&settings, "masterauth").is_some();
+    check_permissions(service, ConfigKind::Redis, &path, password, issues);
+
+    if first(&settings, "protected-mode").is_some_and(|v| v.eq_ignore_ascii_case("no")) && !password {
+        issues.push(issue(
+            service,
+            "redis-protected-mode",
+            SecuritySeverity::High,
+            "Redis läuft ohne Protected Mode und ohne Passwort".to_string(),
+            "Ohne Protected Mode beantwortet Redis Anfragen von jeder Adresse, an die es gebunden ist",
+            format!("Setze 'protected-mode yes' oder 'requirepass' in {}", path.display()),
+            &path,
+        ));
+    }
+}
+
+fn check_nginx(service: &Service, args: &[String], issues: &mut Vec<SecurityIssue>) {
+    // `nginx -c /path/nginx.conf`
+    let path = args
+        .windows(2)
+        .find(|pair| pair[0] == "-c")
+        .map(|pair| PathBuf::from(&pair[1]))
+        .filter(|p| p.exists())
+        .or_else(|| NGINX_CONFIGS.iter().map(PathBuf::from).find(|p| p.exists()));
+    let Some(path) = path else {
+        return;
+    };
+    check_permissions(service, ConfigKind::Nginx, &path, false, issues);
+
+    // Directives end with `;` and may be spread over included files; the main
+    // file and its direct includes cover typical setups
+    let mut content = std::fs::read_to_string(&path).unwrap_or_default();
+    let base = path.parent().unwrap_or(Path::new("/"));
+    for include in directives(&content).into_iter().filter(|(k, _)| k == "include").map(|(_, v)| v) {
+        let pattern = include.trim_end_matches(';');
+        let pattern = if pattern.starts_with('/') { PathBuf::from(pattern) } else { base.join(pattern) };
+        for file in expand_glob(&pattern.to_string_lossy()) {
+            if let Ok(included) = std::fs::read_to_string(&file) {
+                content.push('\n');
+                content.push_str(&included);
+            }
+        }
+    }
+    let settings = directives(&content);
+
+    if values(&settings, "autoindex").any(|v| v == "on") {
+        issues.push(issue(
+            service,
+            "nginx-autoindex",
+            SecuritySeverity::Low,
+            "nginx listet Verzeichnisinhalte auf".to_string(),
+            "Mit 'autoindex on' sind alle Dateien eines Verzeichnisses ohne Index-Datei sichtbar",
+            "Entferne 'autoindex on' oder beschränke es auf einzelne Locations".to_string(),
+            &path,
+        ));
+    }
+    let legacy: Vec<&str> = values(&settings, "ssl_protocols")
+        .flat_map(str::split_whitespace)
+        .filter(|p| matches!(*p, "SSLv2" | "SSLv3" | "TLSv1" | "TLSv1.1"))
+        .collect();
+    if !legacy.is_empty() {
+        issues.push(issue(
+            service,
+            "nginx-ssl-protocols",
+            SecuritySeverity::Medium,
+            format!("nginx erlaubt veraltete Protokolle ({})", legacy.join(", ")),
+            "SSLv3, TLS 1.0 und TLS 1.1 gelten als unsicher",
+            "Setze 'ssl_protocols TLSv1.2 TLSv1.3;'".to_string(),
+            &path,
+        ));
+    }
+    for key in values(&settings, "ssl_certificate_key").map(PathBuf::from).collect::<HashSet<_>>() {
+        check_permissions(service, ConfigKind::Nginx, &key, true, issues);
+    }
+}
+
+fn check_postgres(service: &Service, args: &[String], issues: &mut Vec<SecurityIssue>) {
+    let data_dir = args
+        .windows(2)
+        .find(|pair| pair[0] == "-D")
+        .map(|pair| PathBuf::from(&pair[1]));
+    let hba = data_dir
+        .map(|dir| dir.join("pg_hba.conf"))
+        .filter(|p| p.exists())
+        .or_else(|| POSTGRES_DIRS.iter().find_map(|dir| find_file(Path::new(dir), "pg_hba.conf", 2)));
+    let Some(hba) = hba else {
+        return;
+    };
+    check_permissions(service, ConfigKind::Postgres, &hba, false, issues);
+
+    // `host all all 0.0.0.0/0 trust`: TYPE DATABASE USER ADDRESS METHOD
+    let content = std::fs::read_to_string(&hba).unwrap_or_default();
+    let trusted: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // The address may also be written as `IP MASK`
+            let trust = fields.iter().skip(4).take(2).any(|f| *f == "trust");
+            (fields.first()?.starts_with("host") && trust).then(|| fields[3].to_string())
+        })
+        .filter(|address| !matches!(address.as_str(), "127.0.0.1/32" | "::1/128" | "localhost"))
+        .collect();
+    if !trusted.is_empty() {
+        issues.push(issue(
+            service,
+            "postgres-hba-trust",
+            SecuritySeverity::Critical,
+            format!("PostgreSQL vertraut Verbindungen von {} ohne Passwort", trusted.join(", ")),
+            "Mit der Methode 'trust' kann sich jeder aus diesem Netz als beliebiger Benutzer anmelden",
+            format!("Ersetze 'trust' durch 'scram-sha-256' in {}", hba.display()),
+            &hba,
+        ));
+    }
+}
+
+/// Breadth-first search, e.g. for `/etc/postgresql/16/main/pg_hba.conf`
+fn find_file(dir: &Path, name: &str, depth: u32) -> Option<PathBuf> {
+    let candidate = dir.join(name);
+    if candidate.is_file() {
+        return Some(candidate);
+    }
+    if depth == 0 {
+        return None;
+    }
+    let mut subdirs: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| entry.path())
+        .collect();
+    subdirs.sort();
+    subdirs.iter().find_map(|sub| find_file(sub, name, depth - 1))
+}
@@ -0,0 +1,272 @@
+use std::path::PathBuf;
+use crate::models::service::{CustomServiceSpec, RestartPolicy};
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use crate::services::cmd;
+
+/// Generates launchd plists / systemd user units from a `CustomServiceSpec`
+/// and installs them for the current user
+pub struct CustomServiceInstaller;
+
+impl CustomServiceInstaller {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Validate the spec before generating any files
+    pub fn validate(&self, spec: &CustomServiceSpec) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if spec.name.is_empty()
+            || !spec.name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        {
+            return Err("Ungültiger Service-Name. Erlaubt sind Buchstaben, Ziffern, '.', '-' und '_'.".into());
+        }
+        if spec.command.trim().is_empty() {
+            return Err("Es muss ein Befehl angegeben werden".into());
+        }
+        if spec.env.keys().any(|k| k.is_empty() || k.contains('=') || k.contains(char::is_whitespace)) {
+            return Err("Ungültiger Name für Umgebungsvariable".into());
+        }
+        // A line break would start a new directive in the unit file; control
+        // characters are not representable in plist XML either
+        let mut written = std::iter::once(&spec.command)
+            .chain(spec.description.iter())
+            .chain(spec.working_directory.iter())
+            .chain(spec.args.iter())
+            .chain(spec.env.keys())
+            .chain(spec.env.values());
+        if let Some(value) = written.find(|v| v.contains(char::is_control)) {
+            return Err(format!("Steuerzeichen sind nicht erlaubt: {:?}", value).into());
+        }
+        Ok(())
+    }
+
+    /// Generate a launchd property list for the spec
+    pub fn generate_launchd_plist(&self, spec: &CustomServiceSpec) -> String {
+        let mut plist = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+             <plist version=\"1.0\">\n<dict>\n",
+        );
+
+        plist.push_str(&format!("    <key>Label</key>\n    <string>{}</string>\n", xml_escape(&spec.name)));
+
+        plist.push_str("    <key>ProgramArguments</key>\n    <array>\n");
+        for arg in std::iter::once(&spec.command).chain(spec.args.iter()) {
+            plist.push_str(&format!("        <string>{}</string>\n", xml_escape(arg)));
+        }
+        plist.push_str("    </array>\n");
+
+        if !spec.env.is_empty() {
+            let mut env: Vec<_> = spec.env.iter().collect();
+            env.sort();
+            plist.push_str("    <key>EnvironmentVariables</key>\n    <dict>\n");
+            for (key, value) in env {
+                plist.push_str(&format!(
+                    "        <key>{}</key>\n        <string>{}</string>\n",
+                    xml_escape(key),
+                    xml_escape(value)
+                ));
+            }
+            plist.push_str("    </dict>\n");
+        }
+
+        if let Some(dir) = &spec.working_directory {
+            plist.push_str(&format!("    <key>WorkingDirectory</key>\n    <string>{}</string>\n", xml_escape(dir)));
+        }
+
+        plist.push_str(&format!("    <key>RunAtLoad</key>\n    <{}/>\n", spec.auto_start));
+
+        match spec.restart_policy {
+            RestartPolicy::Never => plist.push_str("    <key>KeepAlive</key>\n    <false/>\n"),
+            RestartPolicy::Always => plist.push_str("    <key>KeepAlive</key>\n    <true/>\n"),
+            RestartPolicy::OnFailure => plist.push_str(
+                "    <key>KeepAlive</key>\n    <dict>\n        <key>SuccessfulExit</key>\n        <false/>\n    </dict>\n",
+            ),
+        }
+
+        plist.push_str("</dict>\n</plist>\n");
+        plist
+    }
+
+    /// Generate a systemd user unit for the spec
+    pub fn generate_systemd_unit(&self, spec: &CustomServiceSpec) -> String {
+        let description = spec.description.clone().unwrap_or_else(|| spec.name.clone());
+        let exec_start = std::iter::once(&spec.command)
+            .chain(spec.args.iter())
+            .map(|arg| systemd_quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut unit = format!("[Unit]\nDescription={}\n\n[Service]\nExecStart={}\n", description, exec_start);
+
+        if let Some(dir) = &spec.working_directory {
+            unit.push_str(&format!("WorkingDirectory={}\n", dir));
+        }
+
+        let mut env: Vec<_> = spec.env.iter().collect();
+        env.sort();
+        for (key, value) in env {
+            unit.push_str(&format!("Environment={}\n", systemd_quote(&format!("{}={}", key, value))));
+        }
+
+        let restart = match spec.restart_policy {
+            RestartPolicy::Never => "no",
+            RestartPolicy::OnFailure => "on-failure",
+            RestartPolicy::Always => "always",
+        };
+        unit.push_str(&format!("Restart={}\n", restart));
+
+        unit.push_str("\n[Install]\nWantedBy=default.target\n");
+        unit
+    }
+
+    /// Write the plist to ~/Library/LaunchAgents and load it
+    #[cfg(target_os = "macos")]
+    pub fn install(&self, spec: &CustomServiceSpec) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+        self.validate(spec)?;
+
+        let agents_dir = dirs::home_dir()
+            .ok_or("Home-Verzeichnis nicht gefunden")?
+            .join("Library")
+            .join("LaunchAgents");
+        std::fs::create_dir_all(&agents_dir)?;
+
+        let plist_path = agents_dir.join(format!("{}.plist", spec.name));
+        if plist_path.exists() {
+            return Err(format!("Service {} existiert bereits", spec.name).into());
+        }
+        std::fs::write(&plist_path, self.generate_launchd_plist(spec))?;
+
+        let mut load = cmd::command("launchctl");
+        load.arg("load");
+        if spec.auto_start {
+            load.arg("-w");
+        }
+        // Don't leave a plist behind that launchd refused; it would block a retry
+        if let Err(e) = run(load.arg(&plist_path), "Failed to load service") {
+            let _ = std::fs::remove_file(&plist_path);
+            return Err(e);
+        }
+        Ok(plist_path)
+    }
+
+    /// Write the unit to ~/.config/systemd/user and reload the user manager
+    #[cfg(target_os = "linux")]
+    pub fn install(&self, spec: &CustomServiceSpec) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+        self.validate(spec)?;
+
+        let unit_dir = dirs::config_dir()
+            .ok_or("Konfigurationsverzeichnis nicht gefunden")?
+            .join("systemd")
+            .join("user");
+        std::fs::create_dir_all(&unit_dir)?;
+
+        let unit_name = format!("{}.service", spec.name);
+        let unit_path = unit_dir.join(&unit_name);
+        if unit_path.exists() {
+            return Err(format!("Service {} existiert bereits", spec.name).into());
+        }
+        std::fs::write(&unit_path, self.generate_systemd_unit(spec))?;
+
+        // Don't leave a unit behind that systemd never picked up; it would block a retry
+        let mut reload = cmd::command("systemctl");
+        if let Err(e) = run(reload.args(["--user", "daemon-reload"]), "Failed to reload systemd") {
+            let _ = std::fs::remove_file(&unit_path);
+            return Err(e);
+        }
+
+        if spec.auto_start {
+            let output = cmd::command("systemctl")
+                .args(["--user", "enable", "--now", &unit_name])
+                .output()?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("Failed to enable service: {}", stderr).into());
+            }
+        }
+        Ok(unit_path)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    pub fn install(&self, _spec: &CustomServiceSpec) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+        Err("Eigene Services werden nur unter macOS (launchd) und Linux (systemd) unterstützt".into())
+    }
+}
+
+impl Default for CustomServiceInstaller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run a command, turning a non-zero exit into an error with its stderr
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn run(command: &mut std::process::Command, context: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let output = command.output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{}: {}", context, stderr).into());
+    }
+    Ok(())
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn systemd_quote(value: &str) -> String {
+    // '%' introduces unit specifiers in systemd and must be doubled
+    let value = &value.replace('%', "%%");
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> CustomServiceSpec {
+        CustomServiceSpec {
+            name: "my-app".to_string(),
+            description: Some("My app".to_string()),
+            command: "/usr/bin/my-app".to_string(),
+            args: vec!["--port".to_string(), "8080".to_string()],
+            env: [("MODE".to_string(), "prod".to_string())].into_iter().collect(),
+            working_directory: Some("/srv/my-app".to_string()),
+            restart_policy: RestartPolicy::OnFailure,
+            auto_start: false,
+        }
+    }
+
+    #[test]
+    fn accepts_a_plain_spec() {
+        assert!(CustomServiceInstaller::new().validate(&spec()).is_ok());
+    }
+
+    #[test]
+    fn rejects_line_breaks_in_anything_written_to_the_unit() {
+        let installer = CustomServiceInstaller::new();
+        let injected = "x\nExecStartPre=/bin/sh -c evil";
+
+        let mut with_description = spec();
+        with_description.description = Some(injected.to_string());
+        let mut with_directory = spec();
+        with_directory.working_directory = Some(injected.to_string());
+        let mut with_arg = spec();
+        with_arg.args.push(injected.to_string());
+        let mut with_env = spec();
+        with_env.env.insert("MODE".to_string(), "a\rb".to_string());
+
+        for spec in [with_description, with_directory, with_arg, with_env] {
+            assert!(installer.validate(&spec).is_err(), "{:?}", spec);
+        }
+    }
+}
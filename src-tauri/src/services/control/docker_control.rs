@@ -2,7 +2,11 @@ use async_trait::async_trait;
 use bollard::Docker;
 #[allow(deprecated)]
 use bollard::container::{StartContainerOptions, StopContainerOptions, RestartContainerOptions, KillContainerOptions, UpdateContainerOptions};
-use bollard::models::RestartPolicy;
+use bollard::models::{ContainerCreateBody, HostConfig, PortBinding, RestartPolicy};
+use bollard::query_parameters::{CreateContainerOptionsBuilder, CreateImageOptionsBuilder};
+use futures::TryStreamExt;
+use std::collections::HashMap;
+use crate::models::port::{PortMapping, Protocol};
 use super::traits::ServiceControl;
 
 pub struct DockerControl {
@@ -14,6 +18,103 @@ impl DockerControl {
         let docker = Docker::connect_with_local_defaults().ok();
         Self { docker }
     }
+
+    /// Create and start a new container, pulling the image first if needed.
+    /// Returns the ID of the new container.
+    pub async fn run(
+        &self,
+        image: &str,
+        name: Option<&str>,
+        ports: &[PortMapping],
+        env: &HashMap<String, String>,
+        volumes: &[String],
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let docker = self.docker.as_ref().ok_or("Docker not available")?;
+        let image = &image_reference(image);
+
+        if docker.inspect_image(image).await.is_err() {
+            let options = CreateImageOptionsBuilder::new().from_image(image).build();
+            docker
+                .create_image(Some(options), None, None)
+                .try_collect::<Vec<_>>()
+                .await?;
+        }
+
+        let mut exposed_ports = HashMap::new();
+        let mut port_bindings = HashMap::new();
+        for mapping in ports {
+            let protocol = match mapping.protocol {
+                Protocol::Tcp => "tcp",
+                Protocol::Udp => "udp",
+            };
+            let key = format!("{}/{}", mapping.container_port, protocol);
+            exposed_ports.insert(key.clone(), HashMap::new());
+            port_bindings
+                .entry(key)
+                .or_insert_with(|| Some(Vec::new()))
+                .get_or_insert_with(Vec::new)
+                .push(PortBinding {
+                    host_ip: None,
+                    host_port: Some(mapping.host_port.to_string()),
+                });
+        }
+
+        let mut env: Vec<String> = env.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        env.sort();
+
+        let config = ContainerCreateBody {
+            image: Some(image.to_string()),
+            env: Some(env),
+            exposed_ports: Some(exposed_ports),
+            labels: Some(HashMap::from([("managed-by".to_string(), "network_manager".to_string())])),
+            host_config: Some(HostConfig {
+                port_bindings: Some(port_bindings),
+                binds: (!volumes.is_empty()).then(|| volumes.to_vec()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let options = name.map(|n| CreateContainerOptionsBuilder::new().name(n).build());
+        let created = docker.create_container(options, config).await?;
+
+        docker.start_container(&created.id, None::<bollard::query_parameters::StartContainerOptions>).await?;
+        Ok(created.id)
+    }
+
+    /// Host port bindings from the container's configuration, so they're
+    /// known before it starts. Random host ports (`-p 80`) are left out.
+    pub async fn port_bindings(&self, container_id: &str) -> Result<Vec<PortMapping>, Box<dyn std::error::Error + Send + Sync>> {
+        let docker = self.docker.as_ref().ok_or("Docker not available")?;
+        let info = docker
+            .inspect_container(container_id, None::<bollard::query_parameters::InspectContainerOptions>)
+            .await?;
+
+        let bindings = info.host_config.and_then(|hc| hc.port_bindings).unwrap_or_default();
+        let mut mappings = Vec::new();
+        for (key, bindings) in bindings {
+            // `5432/tcp`
+            let (container_port, protocol) = key.split_once('/').unwrap_or((&key, "tcp"));
+            let Ok(container_port) = container_port.parse::<u16>() else {
+                continue;
+            };
+            let protocol = if protocol == "udp" { Protocol::Udp } else { Protocol::Tcp };
+            for binding in bindings.unwrap_or_default() {
+                let Some(host_port) = binding.host_port.and_then(|p| p.parse::<u16>().ok()) else {
+                    continue;
+                };
+                mappings.push(PortMapping {
+                    host_port,
+                    container_port,
+                    protocol: protocol.clone(),
+                });
+            }
+        }
+        // Bindings on 0.0.0.0 and :: show up twice
+        mappings.sort_by_key(|m| (m.host_port, m.protocol == Protocol::Udp));
+        mappings.dedup_by(|a, b| a.host_port == b.host_port && a.protocol == b.protocol);
+        Ok(mappings)
+    }
 }
 
 #[async_trait]
@@ -76,3 +177,35 @@ impl ServiceControl for DockerControl {
         service_type == "docker"
     }
 }
+
+/// `image` with `:latest` added when it has neither a tag nor a digest. The
+/// image API pulls every tag of a repository given without one.
+pub fn image_reference(image: &str) -> String {
+    let image = image.trim();
+    // A registry port (`registry:5000/app`) is not a tag
+    let name = image.rsplit('/').next().unwrap_or(image);
+    if image.contains('@') || name.contains(':') {
+        image.to_string()
+    } else {
+        format!("{}:latest", image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untagged_images_default_to_latest() {
+        assert_eq!(image_reference("nginx"), "nginx:latest");
+        assert_eq!(image_reference(" library/redis "), "library/redis:latest");
+        assert_eq!(image_reference("registry.local:5000/team/app"), "registry.local:5000/team/app:latest");
+    }
+
+    #[test]
+    fn tags_and_digests_are_kept() {
+        assert_eq!(image_reference("postgres:16"), "postgres:16");
+        assert_eq!(image_reference("registry.local:5000/app:1.2"), "registry.local:5000/app:1.2");
+        assert_eq!(image_reference("alpine@sha256:abc"), "alpine@sha256:abc");
+    }
+}
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use std::process::Command;
+use crate::services::cmd;
 use super::traits::ServiceControl;
 
 pub struct LaunchdControl;
@@ -13,7 +13,7 @@ impl LaunchdControl {
 #[async_trait]
 impl ServiceControl for LaunchdControl {
     async fn start(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("launchctl")
+        let output = cmd::command("launchctl")
             .args(["start", service_id])
             .output()?;
 
@@ -25,7 +25,7 @@ impl ServiceControl for LaunchdControl {
     }
 
     async fn stop(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("launchctl")
+        let output = cmd::command("launchctl")
             .args(["stop", service_id])
             .output()?;
 
@@ -44,7 +44,7 @@ impl ServiceControl for LaunchdControl {
     }
 
     async fn kill(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("launchctl")
+        let output = cmd::command("launchctl")
             .args(["kill", "SIGKILL", service_id])
             .output()?;
 
@@ -57,12 +57,12 @@ impl ServiceControl for LaunchdControl {
 
     async fn enable_autostart(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         // Get current user ID for user-level services
-        let uid_output = Command::new("id").arg("-u").output()?;
+        let uid_output = cmd::command("id").arg("-u").output()?;
         let uid = String::from_utf8_lossy(&uid_output.stdout).trim().to_string();
         let domain_target = format!("gui/{}", uid);
 
         // Try to enable the service
-        let output = Command::new("launchctl")
+        let output = cmd::command("launchctl")
             .args(["enable", &format!("{}/{}", domain_target, service_id)])
             .output()?;
 
@@ -76,7 +76,7 @@ impl ServiceControl for LaunchdControl {
 
             for plist_path in plist_paths {
                 if std::path::Path::new(&plist_path).exists() {
-                    let load_output = Command::new("launchctl")
+                    let load_output = cmd::command("launchctl")
                         .args(["load", "-w", &plist_path])
                         .output()?;
 
@@ -94,12 +94,12 @@ impl ServiceControl for LaunchdControl {
 
     async fn disable_autostart(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         // Get current user ID for user-level services
-        let uid_output = Command::new("id").arg("-u").output()?;
+        let uid_output = cmd::command("id").arg("-u").output()?;
         let uid = String::from_utf8_lossy(&uid_output.stdout).trim().to_string();
         let domain_target = format!("gui/{}", uid);
 
         // Try to disable the service
-        let output = Command::new("launchctl")
+        let output = cmd::command("launchctl")
             .args(["disable", &format!("{}/{}", domain_target, service_id)])
             .output()?;
 
@@ -113,7 +113,7 @@ impl ServiceControl for LaunchdControl {
 
             for plist_path in plist_paths {
                 if std::path::Path::new(&plist_path).exists() {
-                    let unload_output = Command::new("launchctl")
+                    let unload_output = cmd::command("launchctl")
                         .args(["unload", "-w", &plist_path])
                         .output()?;
 
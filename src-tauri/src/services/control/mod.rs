@@ -3,6 +3,8 @@
 pub mod traits;
 pub mod docker_control;
 pub mod process_control;
+pub mod custom_service;
+pub mod unit_file;
 
 #[cfg(target_os = "macos")]
 pub mod launchd_control;
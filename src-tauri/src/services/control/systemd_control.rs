@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use std::process::Command;
+use crate::services::cmd;
 use super::traits::ServiceControl;
 
 pub struct SystemdControl;
@@ -13,7 +13,7 @@ impl SystemdControl {
 #[async_trait]
 impl ServiceControl for SystemdControl {
     async fn start(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("systemctl")
+        let output = cmd::command("systemctl")
             .args(["start", service_id])
             .output()?;
 
@@ -25,7 +25,7 @@ impl ServiceControl for SystemdControl {
     }
 
     async fn stop(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("systemctl")
+        let output = cmd::command("systemctl")
             .args(["stop", service_id])
             .output()?;
 
@@ -37,7 +37,7 @@ impl ServiceControl for SystemdControl {
     }
 
     async fn restart(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("systemctl")
+        let output = cmd::command("systemctl")
             .args(["restart", service_id])
             .output()?;
 
@@ -49,7 +49,7 @@ impl ServiceControl for SystemdControl {
     }
 
     async fn kill(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("systemctl")
+        let output = cmd::command("systemctl")
             .args(["kill", "--signal=SIGKILL", service_id])
             .output()?;
 
@@ -65,7 +65,7 @@ impl ServiceControl for SystemdControl {
     }
 
     async fn enable_autostart(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("systemctl")
+        let output = cmd::command("systemctl")
             .args(["enable", service_id])
             .output()?;
 
@@ -77,7 +77,7 @@ impl ServiceControl for SystemdControl {
     }
 
     async fn disable_autostart(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("systemctl")
+        let output = cmd::command("systemctl")
             .args(["disable", service_id])
             .output()?;
 
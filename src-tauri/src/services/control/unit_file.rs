@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use crate::services::cmd;
+
+const SYSTEM_UNIT_DIR: &str = "/etc/systemd/system";
+const OVERRIDE_FILE: &str = "override.conf";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffLineKind {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+}
+
+/// Result of comparing an override drop-in with proposed content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnitOverrideDiff {
+    pub unit: String,
+    pub path: String,
+    pub previous: String,
+    pub lines: Vec<DiffLine>,
+    pub changed: bool,
+}
+
+/// Reads systemd unit files and manages override drop-ins
+pub struct UnitFileEditor {
+    unit_dir: PathBuf,
+}
+
+impl UnitFileEditor {
+    pub fn new() -> Self {
+        Self {
+            unit_dir: PathBuf::from(SYSTEM_UNIT_DIR),
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        cfg!(target_os = "linux")
+    }
+
+    /// Full unit content including existing drop-ins (`systemctl cat`)
+    pub fn cat_unit(&self, unit: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        self.check_unit(unit)?;
+
+        let output = cmd::command("systemctl")
+            .args(["cat", "--no-pager", unit])
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Failed to read unit file: {}", stderr).into());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Path of the `override.conf` drop-in for a unit
+    pub fn override_path(&self, unit: &str) -> PathBuf {
+        self.unit_dir.join(format!("{}.d", unit)).join(OVERRIDE_FILE)
+    }
+
+    /// Current override content, empty if none exists yet
+    pub fn read_override(&self, unit: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        self.check_unit(unit)?;
+        match std::fs::read_to_string(self.override_path(unit)) {
+            Ok(content) => Ok(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Validate proposed content and diff it against the current override
+    pub fn diff_override(&self, unit: &str, content: &str) -> Result<UnitOverrideDiff, Box<dyn std::error::Error + Send + Sync>> {
+        validate_unit_content(content)?;
+        let previous = self.read_override(unit)?;
+        let lines = diff_lines(&previous, content);
+        let changed = lines.iter().any(|l| l.kind != DiffLineKind::Unchanged);
+
+        Ok(UnitOverrideDiff {
+            unit: unit.to_string(),
+            path: self.override_path(unit).to_string_lossy().to_string(),
+            previous,
+            lines,
+            changed,
+        })
+    }
+
+    /// Write the override drop-in and reload systemd.
+    ///
+    /// `expected_previous` must match the override content the user edited,
+    /// otherwise the file was changed concurrently and the write is rejected.
+    pub fn write_override(
+        &self,
+        unit: &str,
+        content: &str,
+        expected_previous: &str,
+    ) -> Result<UnitOverrideDiff, Box<dyn std::error::Error + Send + Sync>> {
+        let diff = self.diff_override(unit, content)?;
+
+        if diff.previous != expected_previous {
+            return Err("Die Override-Datei wurde zwischenzeitlich geändert. Bitte neu laden.".into());
+        }
+        if !diff.changed {
+            return Ok(diff);
+        }
+
+        let path = self.override_path(unit);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, content)?;
+
+        self.daemon_reload()?;
+        Ok(diff)
+    }
+
+    /// Run `systemctl daemon-reload`
+    pub fn daemon_reload(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let output = cmd::command("systemctl")
+            .arg("daemon-reload")
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Failed to reload systemd: {}", stderr).into());
+        }
+        Ok(())
+    }
+
+    fn check_unit(&self, unit: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !self.is_available() {
+            return Err("systemd ist nur unter Linux verfügbar".into());
+        }
+        let valid_chars = unit
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '@' | ':' | '\\'));
+        if unit.is_empty() || !valid_chars || !unit.contains('.') {
+            return Err(format!("Ungültiger Unit-Name: {}", unit).into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for UnitFileEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Basic syntax check: every setting must be `Key=Value` inside a `[Section]`
+fn validate_unit_content(content: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut in_section = false;
+    let mut continued = false;
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        let was_continued = continued;
+        continued = line.ends_with('\\');
+
+        if was_continued || line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            if !line.ends_with(']') || line.len() < 3 {
+                return Err(format!("Zeile {}: ungültiger Abschnitt '{}'", index + 1, line).into());
+            }
+            in_section = true;
+            continue;
+        }
+
+        if !in_section {
+            return Err(format!("Zeile {}: Einstellung außerhalb eines Abschnitts", index + 1).into());
+        }
+
+        match line.split_once('=') {
+            Some((key, _)) if !key.trim().is_empty() && !key.trim().contains(char::is_whitespace) => {}
+            _ => return Err(format!("Zeile {}: erwartet 'Schlüssel=Wert'", index + 1).into()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Line-based diff using the longest common subsequence
+fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            result.push(DiffLine { kind: DiffLineKind::Unchanged, text: old[i].to_string() });
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(DiffLine { kind: DiffLineKind::Removed, text: old[i].to_string() });
+            i += 1;
+        } else {
+            result.push(DiffLine { kind: DiffLineKind::Added, text: new[j].to_string() });
+            j += 1;
+        }
+    }
+    result.extend(old[i..].iter().map(|l| DiffLine { kind: DiffLineKind::Removed, text: l.to_string() }));
+    result.extend(new[j..].iter().map(|l| DiffLine { kind: DiffLineKind::Added, text: l.to_string() }));
+    result
+}
@@ -1,8 +1,15 @@
 use async_trait::async_trait;
+use crate::models::service::ServiceDependencies;
 use super::traits::ServiceControl;
 
 #[cfg(target_os = "windows")]
-use std::process::Command;
+use crate::parsers::sc;
+#[cfg(target_os = "windows")]
+use crate::services::cmd;
+
+/// How long to wait for a service to reach STOPPED/RUNNING
+#[cfg(target_os = "windows")]
+const STATE_TIMEOUT_SECS: u64 = 30;
 
 pub struct WindowsControl;
 
@@ -10,21 +17,183 @@ impl WindowsControl {
     pub fn new() -> Self {
         Self
     }
-}
 
-#[async_trait]
-impl ServiceControl for WindowsControl {
     #[cfg(target_os = "windows")]
-    async fn start(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("sc")
+    pub fn dependencies(&self, service_id: &str) -> Result<ServiceDependencies, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(ServiceDependencies {
+            service: service_id.to_string(),
+            dependencies: self.query_dependencies(service_id)?,
+            running_dependents: self.running_dependents(service_id)?,
+        })
+    }
+
+    /// Stop all running dependents, then the service itself.
+    /// Returns the dependents that were stopped.
+    #[cfg(target_os = "windows")]
+    pub async fn stop_with_dependents(&self, service_id: &str) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let dependents = self.running_dependents(service_id)?;
+
+        // sc enumdepend lists dependents in reverse start order,
+        // so they can be stopped in the order returned
+        for dependent in &dependents {
+            self.stop_single(dependent).await.map_err(|e| {
+                format!("Abhängiger Dienst {} konnte nicht gestoppt werden: {}", dependent, e)
+            })?;
+        }
+
+        self.stop_single(service_id).await?;
+        Ok(dependents)
+    }
+
+    /// Start stopped dependencies (deepest first), then the service
+    #[cfg(target_os = "windows")]
+    async fn start_with_dependencies(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut order = Vec::new();
+        self.collect_start_order(service_id, &mut std::collections::HashSet::new(), &mut order)?;
+
+        for service in &order {
+            let result = self.start_single(service).await;
+            if service != service_id {
+                result.map_err(|e| {
+                    format!("{} benötigt den Dienst {}, der nicht gestartet werden konnte: {}", service_id, service, e)
+                })?;
+            } else {
+                result?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Depth-first walk over `sc qc` dependencies, collecting services that aren't running
+    #[cfg(target_os = "windows")]
+    fn collect_start_order(
+        &self,
+        service_id: &str,
+        visited: &mut std::collections::HashSet<String>,
+        order: &mut Vec<String>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !visited.insert(service_id.to_lowercase()) {
+            return Ok(());
+        }
+        if self.query_state(service_id)?.as_deref() == Some("RUNNING") {
+            return Ok(());
+        }
+
+        for dependency in self.query_dependencies(service_id)? {
+            self.collect_start_order(&dependency, visited, order)?;
+        }
+        order.push(service_id.to_string());
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    async fn start_single(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let output = cmd::command("sc")
             .args(["start", service_id])
             .output()?;
 
         if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("Failed to start service: {}", stderr).into());
+            return Err(format!("Failed to start service: {}", sc_error(&output)).into());
         }
-        Ok(())
+        self.wait_for_state(service_id, "RUNNING").await
+    }
+
+    #[cfg(target_os = "windows")]
+    async fn stop_single(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.query_state(service_id)?.as_deref() == Some("STOPPED") {
+            return Ok(());
+        }
+
+        let output = cmd::command("sc")
+            .args(["stop", service_id])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(format!("Failed to stop service: {}", sc_error(&output)).into());
+        }
+        self.wait_for_state(service_id, "STOPPED").await
+    }
+
+    #[cfg(target_os = "windows")]
+    async fn wait_for_state(&self, service_id: &str, state: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(STATE_TIMEOUT_SECS);
+        while std::time::Instant::now() < deadline {
+            if self.query_state(service_id)?.as_deref() == Some(state) {
+                return Ok(());
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        }
+        Err(format!("Dienst {} hat den Zustand {} nicht innerhalb von {}s erreicht", service_id, state, STATE_TIMEOUT_SECS).into())
+    }
+
+    #[cfg(target_os = "windows")]
+    fn query_state(&self, service_id: &str) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let output = cmd::command("sc")
+            .args(["query", service_id])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(format!("Failed to query service: {}", sc_error(&output)).into());
+        }
+        Ok(sc::parse_query_state(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    #[cfg(target_os = "windows")]
+    fn query_dependencies(&self, service_id: &str) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let output = cmd::command("sc")
+            .args(["qc", service_id])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(format!("Failed to query service config: {}", sc_error(&output)).into());
+        }
+        Ok(sc::parse_qc_dependencies(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    #[cfg(target_os = "windows")]
+    fn running_dependents(&self, service_id: &str) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        // The default buffer of 1024 bytes is too small for services with many dependents
+        let output = cmd::command("sc")
+            .args(["enumdepend", service_id, "65536"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(format!("Failed to enumerate dependent services: {}", sc_error(&output)).into());
+        }
+
+        let report = sc::parse_enumdepend(&String::from_utf8_lossy(&output.stdout));
+        Ok(report
+            .items
+            .into_iter()
+            .filter(|entry| entry.state.as_deref() != Some("STOPPED"))
+            .map(|entry| entry.name)
+            .collect())
+    }
+}
+
+impl Default for WindowsControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// sc prints its errors to stdout, stderr is usually empty
+#[cfg(target_os = "windows")]
+fn sc_error(output: &std::process::Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.trim().is_empty() {
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    } else {
+        stderr.trim().to_string()
+    }
+}
+
+#[async_trait]
+impl ServiceControl for WindowsControl {
+    /// Start the service after starting any stopped dependencies
+    #[cfg(target_os = "windows")]
+    async fn start(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.start_with_dependencies(service_id).await
     }
 
     #[cfg(not(target_os = "windows"))]
@@ -32,17 +201,20 @@ impl ServiceControl for WindowsControl {
         Err("Windows services not available on this platform".into())
     }
 
+    /// Stop the service, refusing while dependent services are still running
     #[cfg(target_os = "windows")]
     async fn stop(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("sc")
-            .args(["stop", service_id])
-            .output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("Failed to stop service: {}", stderr).into());
+        let dependents = self.running_dependents(service_id)?;
+        if !dependents.is_empty() {
+            return Err(format!(
+                "{} kann nicht gestoppt werden, solange abhängige Dienste laufen: {}. \
+                 Stoppe diese zuerst oder stoppe den Dienst zusammen mit seinen abhängigen Diensten.",
+                service_id,
+                dependents.join(", ")
+            )
+            .into());
         }
-        Ok(())
+        self.stop_single(service_id).await
     }
 
     #[cfg(not(target_os = "windows"))]
@@ -50,13 +222,24 @@ impl ServiceControl for WindowsControl {
         Err("Windows services not available on this platform".into())
     }
 
+    /// Restart the service and bring previously running dependents back up
+    #[cfg(target_os = "windows")]
     async fn restart(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        self.stop(service_id).await?;
-        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        let dependents = self.stop_with_dependents(service_id).await?;
         self.start(service_id).await?;
+
+        // Dependents were stopped in reverse start order
+        for dependent in dependents.iter().rev() {
+            self.start(dependent).await?;
+        }
         Ok(())
     }
 
+    #[cfg(not(target_os = "windows"))]
+    async fn restart(&self, _service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Err("Windows services not available on this platform".into())
+    }
+
     async fn kill(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         // Windows services don't have a kill - use stop
         self.stop(service_id).await
@@ -68,7 +251,7 @@ impl ServiceControl for WindowsControl {
 
     #[cfg(target_os = "windows")]
     async fn enable_autostart(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("sc")
+        let output = cmd::command("sc")
             .args(["config", service_id, "start=", "auto"])
             .output()?;
 
@@ -86,7 +269,7 @@ impl ServiceControl for WindowsControl {
 
     #[cfg(target_os = "windows")]
     async fn disable_autostart(&self, service_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("sc")
+        let output = cmd::command("sc")
             .args(["config", service_id, "start=", "demand"])
             .output()?;
 
@@ -0,0 +1,255 @@
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, OnceLock};
+use chrono::{DateTime, Utc};
+use maxminddb::{geoip2, Reader};
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use crate::models::config::Config;
+use crate::models::port::PortInfo;
+use crate::models::service::Service;
+use crate::parsers::{sockets::SocketRecord, split_host_port};
+
+/// Reverse lookups are slow; resolved names are kept for the app's lifetime
+static HOSTNAMES: OnceLock<Mutex<HashMap<IpAddr, Option<String>>>> = OnceLock::new();
+static GEOIP: OnceLock<Mutex<GeoIpState>> = OnceLock::new();
+
+#[derive(Default)]
+struct GeoIpState {
+    path: Option<String>,
+    reader: Option<Arc<Reader<Vec<u8>>>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    /// A discovered service on this machine
+    LocalService,
+    /// A listener on this machine that isn't a known service
+    LocalProcess,
+    /// Private, link-local or unique local address
+    Lan,
+    Internet,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeDependency {
+    /// Service name, host name or the address itself
+    pub name: String,
+    pub kind: DependencyKind,
+    pub address: String,
+    pub port: u16,
+    pub service_id: Option<String>,
+    /// Reverse DNS name of remote hosts
+    pub hostname: Option<String>,
+    /// ISO country code from the GeoIP database, if one is configured
+    pub country: Option<String>,
+    /// Open connections to this address and port
+    pub connections: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceRuntimeDependencies {
+    pub service_id: String,
+    pub service_name: String,
+    /// Main process and its children
+    pub pids: Vec<u32>,
+    pub dependencies: Vec<RuntimeDependency>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Resolves a service's established outbound connections to named
+/// dependencies. Connections of Docker containers live in their own network
+/// namespace and only show up through their proxy processes.
+pub struct DependencyMapper;
+
+impl DependencyMapper {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Apply the GeoIP database path from the config
+    pub fn configure(config: &Config) {
+        let mut state = geoip().lock().unwrap_or_else(|e| e.into_inner());
+        if state.path != config.geoip_database {
+            state.path = config.geoip_database.clone();
+            state.reader = None;
+        }
+    }
+
+    /// Blocking: resolves host names of remote peers
+    pub fn map(
+        &self,
+        service: &Service,
+        services: &[Service],
+        listeners: &[PortInfo],
+        connections: &[SocketRecord],
+    ) -> ServiceRuntimeDependencies {
+        let pids = process_tree(service.pid);
+        let pid_set: HashSet<u32> = pids.iter().copied().collect();
+
+        // Connections to the service's own listeners are inbound
+        let own_ports: HashSet<u16> = listeners
+            .iter()
+            .filter(|p| p.pid.is_some_and(|pid| pid_set.contains(&pid)))
+            .map(|p| p.port)
+            .chain(service.ports.iter().copied())
+            .collect();
+        let local_addresses: HashSet<String> = connections.iter().map(|c| c.local_address.clone()).collect();
+
+        let mut grouped: HashMap<(IpAddr, u16), usize> = HashMap::new();
+        for connection in connections {
+            if !connection.pid.is_some_and(|pid| pid_set.contains(&pid)) || own_ports.contains(&connection.local_port) {
+                continue;
+            }
+            let Some((address, port)) = connection.remote.as_deref().and_then(split_host_port) else {
+                continue;
+            };
+            let Ok(ip) = address.parse::<IpAddr>() else {
+                continue;
+            };
+            *grouped.entry((unmap(ip), port)).or_default() += 1;
+        }
+
+        let geoip = geoip_reader();
+        let mut dependencies: Vec<RuntimeDependency> = grouped
+            .into_iter()
+            .map(|((ip, port), count)| {
+                let is_local = ip.is_loopback() || ip.is_unspecified() || local_addresses.contains(&ip.to_string());
+                if is_local {
+                    local_dependency(ip, port, count, services, listeners)
+                } else {
+                    remote_dependency(ip, port, count, geoip.as_deref())
+                }
+            })
+            .collect();
+        dependencies.sort_by(|a, b| b.connections.cmp(&a.connections).then_with(|| a.name.cmp(&b.name)));
+
+        ServiceRuntimeDependencies {
+            service_id: service.id.clone(),
+            service_name: service.name.clone(),
+            pids,
+            dependencies,
+            checked_at: Utc::now(),
+        }
+    }
+}
+
+impl Default for DependencyMapper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `pid` and all of its descendants, e.g. nginx or postgres workers
+fn process_tree(pid: Option<u32>) -> Vec<u32> {
+    let Some(root) = pid else {
+        return Vec::new();
+    };
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+
+    let mut tree = vec![root];
+    let mut index = 0;
+    while index < tree.len() {
+        let parent = Pid::from_u32(tree[index]);
+        tree.extend(
+            system
+                .processes()
+                .iter()
+                .filter(|(_, process)| process.parent() == Some(parent))
+                .map(|(pid, _)| pid.as_u32()),
+        );
+        index += 1;
+    }
+    tree
+}
+
+/// `::ffff:10.0.0.1` -> `10.0.0.1`
+fn unmap(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        v4 => v4,
+    }
+}
+
+fn local_dependency(ip: IpAddr, port: u16, connections: usize, services: &[Service], listeners: &[PortInfo]) -> RuntimeDependency {
+    let owner = listeners.iter().find(|p| p.port == port);
+    let service = services
+        .iter()
+        .find(|s| s.pid.is_some() && s.pid == owner.and_then(|o| o.pid))
+        .or_else(|| services.iter().find(|s| s.ports.contains(&port)));
+
+    let (name, kind) = match (service, owner.and_then(|o| o.process_name.clone())) {
+        (Some(service), _) => (service.name.clone(), DependencyKind::LocalService),
+        (None, Some(process)) => (process, DependencyKind::LocalProcess),
+        (None, None) => (format!("localhost:{}", port), DependencyKind::LocalProcess),
+    };
+
+    RuntimeDependency {
+        name,
+        kind,
+        address: ip.to_string(),
+        port,
+        service_id: service.map(|s| s.id.clone()),
+        hostname: None,
+        country: None,
+        connections,
+    }
+}
+
+fn remote_dependency(ip: IpAddr, port: u16, connections: usize, geoip: Option<&Reader<Vec<u8>>>) -> RuntimeDependency {
+    let hostname = reverse_lookup(ip);
+    let kind = if is_lan(ip) { DependencyKind::Lan } else { DependencyKind::Internet };
+    let country = match kind {
+        DependencyKind::Internet => geoip.and_then(|reader| {
+            let record: geoip2::Country = reader.lookup(ip).ok()?;
+            record.country?.iso_code.map(String::from)
+        }),
+        _ => None,
+    };
+
+    RuntimeDependency {
+        name: hostname.clone().unwrap_or_else(|| ip.to_string()),
+        kind,
+        address: ip.to_string(),
+        port,
+        service_id: None,
+        hostname,
+        country,
+        connections,
+    }
+}
+
+fn is_lan(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_link_local(),
+        // fc00::/7 unique local, fe80::/10 link-local
+        IpAddr::V6(v6) => (v6.segments()[0] & 0xfe00) == 0xfc00 || (v6.segments()[0] & 0xffc0) == 0xfe80,
+    }
+}
+
+fn reverse_lookup(ip: IpAddr) -> Option<String> {
+    let cache = HOSTNAMES.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(name) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(&ip) {
+        return name.clone();
+    }
+    // getnameinfo falls back to the numeric address when there is no PTR record
+    let name = dns_lookup::lookup_addr(&ip).ok().filter(|name| name.parse::<IpAddr>().is_err());
+    cache.lock().unwrap_or_else(|e| e.into_inner()).insert(ip, name.clone());
+    name
+}
+
+fn geoip() -> &'static Mutex<GeoIpState> {
+    GEOIP.get_or_init(|| Mutex::new(GeoIpState::default()))
+}
+
+/// Open the configured MaxMind/DB-IP country database on first use
+fn geoip_reader() -> Option<Arc<Reader<Vec<u8>>>> {
+    let mut state = geoip().lock().unwrap_or_else(|e| e.into_inner());
+    if state.reader.is_none() {
+        let path = state.path.clone()?;
+        state.reader = Reader::open_readfile(&path).ok().map(Arc::new);
+    }
+    state.reader.clone()
+}
@@ -0,0 +1,456 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use chrono::{DateTime, Datelike, Local, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use crate::llm::{AnalysisStore, OllamaClient, Priority, ResponseLanguage};
+use crate::models::audit::{AuditEntry, EventType};
+use crate::models::config::{Config, DigestConfig, OllamaConfig};
+use crate::models::service::{Service, ServiceStatus};
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::export::escape_html;
+use crate::services::security::AuditLogger;
+use crate::services::storage::Storage;
+use crate::services::security_scanner::{SecurityScanResult, SecurityScanner, SecuritySeverity};
+use crate::services::system_stats::{SystemMonitor, SystemStats};
+use crate::services::ServiceManager;
+
+/// How often the scheduler checks whether a digest is due
+const CHECK_INTERVAL: Duration = Duration::from_secs(600);
+const PERIOD_DAYS: i64 = 7;
+/// Keeps a late start on the configured day from producing a second digest
+const MIN_DAYS_BETWEEN: i64 = 6;
+const MAX_AUDIT_ENTRIES: usize = 10000;
+const MAX_LISTED: usize = 10;
+
+static SETTINGS: OnceLock<Mutex<(DigestConfig, OllamaConfig)>> = OnceLock::new();
+
+/// A generated weekly digest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestReport {
+    pub generated_at: DateTime<Utc>,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub markdown: String,
+    pub html: String,
+    /// Where the Markdown report was saved
+    pub path: Option<String>,
+}
+
+/// State at the time of the last digest, used to report what changed since
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct DigestSnapshot {
+    taken_at: Option<DateTime<Utc>>,
+    service_ids: Vec<String>,
+    security_issue_ids: Vec<String>,
+    cpu_percent: Option<f32>,
+    memory_percent: Option<f32>,
+}
+
+impl DigestSnapshot {
+    fn of(taken_at: DateTime<Utc>, services: &[Service], security: &SecurityScanResult, stats: Option<&SystemStats>) -> Self {
+        Self {
+            taken_at: Some(taken_at),
+            service_ids: services.iter().map(|s| s.id.clone()).collect(),
+            security_issue_ids: security.issues.iter().map(|i| i.id.clone()).collect(),
+            cpu_percent: stats.map(|s| s.cpu.usage_percent),
+            memory_percent: stats.map(|s| s.memory.usage_percent),
+        }
+    }
+}
+
+struct Section {
+    title: &'static str,
+    lines: Vec<String>,
+}
+
+/// Builds digests from the audit log, service discovery, the security scan
+/// and system stats, and stores them next to a snapshot for the next run
+pub struct DigestGenerator {
+    dir: PathBuf,
+}
+
+impl DigestGenerator {
+    pub fn new() -> Self {
+        let dir = Storage::data_dir().join("digests");
+
+        std::fs::create_dir_all(&dir).ok();
+        Self { dir }
+    }
+
+    /// Generate, save and return a digest for the period since the last one.
+    /// `llm` adds a summary paragraph; it is skipped if the model is unavailable.
+    pub async fn generate(&self, llm: Option<&OllamaClient>) -> Result<DigestReport, Box<dyn std::error::Error + Send + Sync>> {
+        let previous = self.load_snapshot();
+        let now = Utc::now();
+        let period_start = previous
+            .taken_at
+            .unwrap_or_else(|| now - chrono::Duration::days(PERIOD_DAYS));
+
+        let services = ServiceManager::new().discover_all().await;
+        let security = SecurityScanner::new().scan(&services);
+        let stats = current_stats();
+        let audit: Vec<AuditEntry> = AuditLogger::new()
+            .get_entries(MAX_AUDIT_ENTRIES)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|e| e.timestamp >= period_start)
+            .collect();
+
+        let sections = vec![
+            events_section(&audit),
+            services_section(&services, &previous),
+            alerts_section(&services, period_start),
+            security_section(&security, &audit, &previous),
+            resources_section(&services, &stats, &previous),
+        ];
+
+        let title = format!(
+            "Wochenbericht {} – {}",
+            period_start.with_timezone(&Local).format("%d.%m.%Y"),
+            now.with_timezone(&Local).format("%d.%m.%Y")
+        );
+        let mut markdown = render_markdown(&title, None, &sections);
+
+        let mut summary = None;
+        if let Some(client) = llm {
+            if client.can_generate().await {
+                summary = client.generate(&summary_prompt(&markdown)).await.ok().map(|s| s.trim().to_string());
+                markdown = render_markdown(&title, summary.as_deref(), &sections);
+            }
+        }
+        let html = render_html(&title, summary.as_deref(), &sections);
+
+        let file_name = format!("digest-{}", now.with_timezone(&Local).format("%Y-%m-%d"));
+        let path = self.dir.join(format!("{}.md", file_name));
+        std::fs::write(&path, &markdown)?;
+        std::fs::write(self.dir.join(format!("{}.html", file_name)), &html)?;
+
+        self.save_snapshot(&DigestSnapshot::of(now, &services, &security, stats.as_ref()))?;
+
+        Ok(DigestReport {
+            generated_at: now,
+            period_start,
+            period_end: now,
+            markdown,
+            html,
+            path: Some(path.to_string_lossy().to_string()),
+        })
+    }
+
+    /// Record the current state as the starting point of the first digest,
+    /// so it lists what changed since setup. Returns `false` if digests
+    /// already have a starting point.
+    pub fn seed_baseline(
+        &self,
+        services: &[Service],
+        security: &SecurityScanResult,
+        stats: Option<&SystemStats>,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        if self.load_snapshot().taken_at.is_some() {
+            return Ok(false);
+        }
+        self.save_snapshot(&DigestSnapshot::of(Utc::now(), services, security, stats))?;
+        Ok(true)
+    }
+
+    /// When the last digest was generated
+    pub fn last_generated(&self) -> Option<DateTime<Utc>> {
+        self.load_snapshot().taken_at
+    }
+
+    fn load_snapshot(&self) -> DigestSnapshot {
+        std::fs::read_to_string(self.dir.join("state.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_snapshot(&self, snapshot: &DigestSnapshot) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        std::fs::write(self.dir.join("state.json"), serde_json::to_string_pretty(snapshot)?)?;
+        Ok(())
+    }
+}
+
+impl Default for DigestGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Generates the digest on the configured day and delivers it as a
+/// `digest-ready` event
+pub struct DigestScheduler;
+
+impl DigestScheduler {
+    /// Apply digest and LLM settings from the config
+    pub fn configure(config: &Config) {
+        *settings().lock().unwrap_or_else(|e| e.into_inner()) = (config.digest.clone(), config.ollama.clone());
+    }
+
+    pub fn start(app_handle: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(CHECK_INTERVAL).await;
+
+                let (digest, ollama) = settings().lock().unwrap_or_else(|e| e.into_inner()).clone();
+                let generator = DigestGenerator::new();
+                if !is_due(&digest, generator.last_generated()) {
+                    continue;
+                }
+
+                let client = (digest.llm_summary && ollama.enabled).then(|| summary_client(&ollama));
+                if let Ok(report) = generator.generate(client.as_ref()).await {
+                    let _ = app_handle.emit("digest-ready", &report);
+                }
+            }
+        });
+    }
+
+    /// Client for the summary paragraph, built from the configured endpoints;
+    /// `None` while Ollama is disabled
+    pub fn llm_client() -> Option<OllamaClient> {
+        let ollama = settings().lock().unwrap_or_else(|e| e.into_inner()).1.clone();
+        ollama.enabled.then(|| summary_client(&ollama))
+    }
+}
+
+fn settings() -> &'static Mutex<(DigestConfig, OllamaConfig)> {
+    SETTINGS.get_or_init(|| Mutex::new((DigestConfig::default(), OllamaConfig::default())))
+}
+
+fn summary_client(config: &OllamaConfig) -> OllamaClient {
+    OllamaClient::from_config(config).with_priority(Priority::Low)
+}
+
+fn is_due(config: &DigestConfig, last_generated: Option<DateTime<Utc>>) -> bool {
+    if !config.enabled {
+        return false;
+    }
+    let weekday = config.weekday.parse::<Weekday>().unwrap_or(Weekday::Mon);
+    let now = Local::now();
+    if now.weekday() != weekday || now.hour() < config.hour {
+        return false;
+    }
+    last_generated.is_none_or(|last| Utc::now() - last >= chrono::Duration::days(MIN_DAYS_BETWEEN))
+}
+
+fn current_stats() -> Option<SystemStats> {
+    if MockDiscovery::is_enabled() {
+        return Some(MockDiscovery::new().system_stats(Utc::now().timestamp() as u64));
+    }
+    Some(SystemMonitor::new().get_stats())
+}
+
+fn events_section(audit: &[AuditEntry]) -> Section {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for entry in audit {
+        let label = match entry.event_type {
+            EventType::ServiceStart => "Dienststarts",
+            EventType::ServiceStop => "Dienststopps",
+            EventType::ServiceRestart => "Neustarts",
+            EventType::ProcessKill => "Beendete Prozesse",
+            EventType::Alert => "Alarme",
+            _ => continue,
+        };
+        *counts.entry(label).or_default() += 1;
+    }
+
+    let mut lines: Vec<String> = counts
+        .into_iter()
+        .map(|(label, count)| format!("{}: {}", label, count))
+        .collect();
+
+    let failures: Vec<&AuditEntry> = audit.iter().filter(|e| !e.success).collect();
+    if !failures.is_empty() {
+        lines.push(format!("Fehlgeschlagene Aktionen: {}", failures.len()));
+        for entry in failures.iter().take(MAX_LISTED) {
+            lines.push(format!(
+                "{} – {} {}: {}",
+                entry.timestamp.with_timezone(&Local).format("%d.%m. %H:%M"),
+                entry.operation,
+                entry.service_id.as_deref().unwrap_or(""),
+                entry.error_message.as_deref().unwrap_or("unbekannter Fehler")
+            ));
+        }
+    }
+
+    Section { title: "Ereignisse", lines }
+}
+
+fn services_section(services: &[Service], previous: &DigestSnapshot) -> Section {
+    if previous.taken_at.is_none() {
+        return Section {
+            title: "Dienste",
+            lines: vec![format!("Erster Bericht: {} Dienste erfasst", services.len())],
+        };
+    }
+
+    let known: HashSet<&str> = previous.service_ids.iter().map(String::as_str).collect();
+    let current: HashSet<&str> = services.iter().map(|s| s.id.as_str()).collect();
+
+    let added: Vec<&Service> = services.iter().filter(|s| !known.contains(s.id.as_str())).collect();
+    let removed: Vec<&str> = previous
+        .service_ids
+        .iter()
+        .map(String::as_str)
+        .filter(|id| !current.contains(id))
+        .collect();
+
+    let mut lines = vec![format!(
+        "{} Dienste, {} neu, {} entfernt",
+        services.len(),
+        added.len(),
+        removed.len()
+    )];
+    lines.extend(added.iter().take(MAX_LISTED).map(|s| format!("Neu: {} ({})", s.name, s.id)));
+    lines.extend(removed.iter().take(MAX_LISTED).map(|id| format!("Entfernt: {}", id)));
+
+    Section { title: "Dienste", lines }
+}
+
+/// Services in an error state and incidents recorded during the period
+fn alerts_section(services: &[Service], since: DateTime<Utc>) -> Section {
+    let mut lines: Vec<String> = services
+        .iter()
+        .filter(|s| s.status == ServiceStatus::Error)
+        .take(MAX_LISTED)
+        .map(|s| format!("Fehlerzustand: {}", s.name))
+        .collect();
+
+    lines.extend(
+        AnalysisStore::new()
+            .since(since)
+            .into_iter()
+            .filter(|a| a.kind == "incident")
+            .take(MAX_LISTED)
+            .map(|a| format!("Vorfall am {}: {}", a.created_at.with_timezone(&Local).format("%d.%m.%Y"), a.title)),
+    );
+
+    Section { title: "Warnungen", lines }
+}
+
+fn security_section(security: &SecurityScanResult, audit: &[AuditEntry], previous: &DigestSnapshot) -> Section {
+    let mut lines = vec![format!(
+        "{} Befunde ({} kritisch, {} hoch)",
+        security.issues.len(),
+        security.critical_count,
+        security.high_count
+    )];
+
+    if previous.taken_at.is_some() {
+        let known: HashSet<&str> = previous.security_issue_ids.iter().map(String::as_str).collect();
+        let current: HashSet<&str> = security.issues.iter().map(|i| i.id.as_str()).collect();
+
+        for issue in security.issues.iter().filter(|i| !known.contains(i.id.as_str())).take(MAX_LISTED) {
+            let severity = match issue.severity {
+                SecuritySeverity::Critical => "kritisch",
+                SecuritySeverity::High => "hoch",
+                SecuritySeverity::Medium => "mittel",
+                SecuritySeverity::Low | SecuritySeverity::Info => "niedrig",
+            };
+            lines.push(format!("Neu ({}): {}", severity, issue.title));
+        }
+        let resolved = previous.security_issue_ids.iter().filter(|id| !current.contains(id.as_str())).count();
+        if resolved > 0 {
+            lines.push(format!("Behoben: {} Befund(e)", resolved));
+        }
+    }
+
+    let config_changes = audit.iter().filter(|e| matches!(e.event_type, EventType::ConfigChange)).count();
+    if config_changes > 0 {
+        lines.push(format!("Konfigurationsänderungen: {}", config_changes));
+    }
+    let escalations = audit.iter().filter(|e| matches!(e.event_type, EventType::PrivilegeEscalation)).count();
+    if escalations > 0 {
+        lines.push(format!("Rechteerhöhungen: {}", escalations));
+    }
+
+    Section { title: "Sicherheit", lines }
+}
+
+fn resources_section(services: &[Service], stats: &Option<SystemStats>, previous: &DigestSnapshot) -> Section {
+    let mut lines = Vec::new();
+
+    if let Some(stats) = stats {
+        lines.push(format!("CPU: {:.0} %{}", stats.cpu.usage_percent, trend(previous.cpu_percent, stats.cpu.usage_percent)));
+        lines.push(format!(
+            "Arbeitsspeicher: {:.0} %{}",
+            stats.memory.usage_percent,
+            trend(previous.memory_percent, stats.memory.usage_percent)
+        ));
+    }
+
+    let mut by_memory: Vec<&Service> = services.iter().filter(|s| s.memory_bytes.is_some()).collect();
+    by_memory.sort_by_key(|s| std::cmp::Reverse(s.memory_bytes));
+    for service in by_memory.iter().take(5) {
+        lines.push(format!(
+            "{}: {} MB",
+            service.name,
+            service.memory_bytes.unwrap_or_default() / (1024 * 1024)
+        ));
+    }
+
+    Section { title: "Ressourcen", lines }
+}
+
+fn trend(previous: Option<f32>, current: f32) -> String {
+    match previous {
+        Some(previous) => format!(" (Vorwoche {:.0} %, {:+.0})", previous, current - previous),
+        None => String::new(),
+    }
+}
+
+fn summary_prompt(markdown: &str) -> String {
+    format!(
+        "Fasse den folgenden Wochenbericht eines Servers in 3-4 Sätzen für einen Administrator zusammen. \
+         Nenne die wichtigsten Auffälligkeiten und erfinde keine Zahlen. {}\n\n{}",
+        ResponseLanguage::instruction(),
+        markdown
+    )
+}
+
+fn render_markdown(title: &str, summary: Option<&str>, sections: &[Section]) -> String {
+    let mut out = format!("# {}\n\n", title);
+    if let Some(summary) = summary {
+        out.push_str(&format!("{}\n\n", summary));
+    }
+    for section in sections {
+        out.push_str(&format!("## {}\n\n", section.title));
+        if section.lines.is_empty() {
+            out.push_str("_Keine Einträge_\n\n");
+            continue;
+        }
+        for line in &section.lines {
+            out.push_str(&format!("- {}\n", line));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn render_html(title: &str, summary: Option<&str>, sections: &[Section]) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{0}</title></head><body>\n<h1>{0}</h1>\n",
+        escape_html(title)
+    );
+    if let Some(summary) = summary {
+        out.push_str(&format!("<p>{}</p>\n", escape_html(summary)));
+    }
+    for section in sections {
+        out.push_str(&format!("<h2>{}</h2>\n", section.title));
+        if section.lines.is_empty() {
+            out.push_str("<p><em>Keine Einträge</em></p>\n");
+            continue;
+        }
+        out.push_str("<ul>\n");
+        for line in &section.lines {
+            out.push_str(&format!("<li>{}</li>\n", escape_html(line)));
+        }
+        out.push_str("</ul>\n");
+    }
+    out.push_str("</body></html>\n");
+    out
+}
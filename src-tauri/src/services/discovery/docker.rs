@@ -2,9 +2,16 @@ use async_trait::async_trait;
 use bollard::Docker;
 #[allow(deprecated)]
 use bollard::container::ListContainersOptions;
+use bollard::models::ContainerStatsResponse;
+use bollard::query_parameters::StatsOptions;
+use futures::StreamExt;
+use std::time::Duration;
 use crate::models::service::{Service, ServiceStatus, ServiceType};
 use super::traits::ServiceDiscovery;
 
+/// Docker waits for a second sample to report CPU usage
+const STATS_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub struct DockerDiscovery {
     docker: Option<Docker>,
 }
@@ -56,6 +63,10 @@ impl ServiceDiscovery for DockerDiscovery {
                 None => ServiceStatus::Unknown,
             };
 
+            let label = |key: &str| container.labels.as_ref().and_then(|labels| labels.get(key).cloned());
+            let compose_project = label("com.docker.compose.project");
+            let compose_service = label("com.docker.compose.service");
+
             // Extract all port mappings
             let ports: Vec<u16> = container.ports
                 .map(|ports| {
@@ -97,9 +108,31 @@ impl ServiceDiscovery for DockerDiscovery {
                 cpu_usage: None,
                 memory_bytes: None,
                 memory_percent: None,
+                cpu_usage_total: None,
+                memory_total: None,
+                gpu_usage: None,
+                energy: None,
+                network_rx_bytes: None,
+                network_tx_bytes: None,
+                user: None,
+                recent_restarts: 0,
+                flapping: false,
+                compose_project,
+                compose_service,
             });
         }
 
+        // Containers have no host PID, so their usage comes from the stats API
+        let running: Vec<usize> = (0..services.len())
+            .filter(|&i| services[i].status == ServiceStatus::Running)
+            .collect();
+        let stats = futures::future::join_all(running.iter().map(|&i| container_stats(docker, &services[i].id))).await;
+        for (i, stats) in running.into_iter().zip(stats) {
+            if let Some(stats) = stats {
+                apply_stats(&mut services[i], &stats);
+            }
+        }
+
         Ok(services)
     }
 
@@ -116,3 +149,58 @@ impl ServiceDiscovery for DockerDiscovery {
         "Docker"
     }
 }
+
+/// One stats sample including the previous one, which takes Docker about a
+/// second to collect
+async fn container_stats(docker: &Docker, id: &str) -> Option<ContainerStatsResponse> {
+    let options = StatsOptions {
+        stream: false,
+        one_shot: false,
+    };
+    let mut stream = docker.stats(id, Some(options));
+    tokio::time::timeout(STATS_TIMEOUT, stream.next()).await.ok()??.ok()
+}
+
+/// CPU and memory computed like `docker stats` does
+fn apply_stats(service: &mut Service, stats: &ContainerStatsResponse) {
+    if let (Some(cpu), Some(previous)) = (&stats.cpu_stats, &stats.precpu_stats) {
+        let total = |s: &bollard::models::ContainerCpuStats| s.cpu_usage.as_ref().and_then(|u| u.total_usage);
+        let cpu_delta = total(cpu).zip(total(previous)).map(|(now, before)| now.saturating_sub(before));
+        let system_delta = cpu
+            .system_cpu_usage
+            .zip(previous.system_cpu_usage)
+            .map(|(now, before)| now.saturating_sub(before));
+        let cpus = cpu.online_cpus.map(|n| n as u64).or_else(|| {
+            cpu.cpu_usage.as_ref().and_then(|u| u.percpu_usage.as_ref()).map(|per_cpu| per_cpu.len() as u64)
+        });
+        // Percent of one core, like the host processes
+        if let (Some(cpu_delta), Some(system_delta), Some(cpus)) = (cpu_delta, system_delta, cpus) {
+            if system_delta > 0 {
+                service.cpu_usage = Some((cpu_delta as f64 / system_delta as f64 * cpus as f64 * 100.0) as f32);
+            }
+        }
+    }
+
+    if let Some(memory) = &stats.memory_stats {
+        // The page cache counts towards the cgroup but can be dropped any time
+        let cache = memory
+            .stats
+            .as_ref()
+            .and_then(|s| s.get("inactive_file").or_else(|| s.get("total_inactive_file")).or_else(|| s.get("cache")))
+            .copied()
+            .unwrap_or(0);
+        if let Some(usage) = memory.usage {
+            let used = usage.saturating_sub(cache);
+            service.memory_bytes = Some(used);
+            service.memory_percent = memory
+                .limit
+                .filter(|&limit| limit > 0)
+                .map(|limit| (used as f64 / limit as f64 * 100.0) as f32);
+        }
+    }
+
+    if let Some(networks) = &stats.networks {
+        service.network_rx_bytes = Some(networks.values().filter_map(|n| n.rx_bytes).sum());
+        service.network_tx_bytes = Some(networks.values().filter_map(|n| n.tx_bytes).sum());
+    }
+}
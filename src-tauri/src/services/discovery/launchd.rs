@@ -1,6 +1,7 @@
 use async_trait::async_trait;
-use std::process::Command;
+use crate::services::cmd;
 use crate::models::service::{Service, ServiceStatus, ServiceType};
+use crate::parsers::launchctl::parse_launchctl_list;
 use super::traits::ServiceDiscovery;
 
 pub struct LaunchdDiscovery;
@@ -14,7 +15,7 @@ impl LaunchdDiscovery {
 #[async_trait]
 impl ServiceDiscovery for LaunchdDiscovery {
     async fn discover(&self) -> Result<Vec<Service>, Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("launchctl")
+        let output = cmd::command("launchctl")
             .arg("list")
             .output()?;
 
@@ -23,37 +24,37 @@ impl ServiceDiscovery for LaunchdDiscovery {
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let services: Vec<Service> = stdout
-            .lines()
-            .skip(1) // Skip header line
-            .filter_map(|line| {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 3 {
-                    let pid = parts[0].parse::<u32>().ok();
-                    let status = if pid.is_some() {
-                        ServiceStatus::Running
-                    } else {
-                        ServiceStatus::Stopped
-                    };
-                    let name = parts[2].to_string();
-
-                    Some(Service {
-                        id: name.clone(),
-                        name: name.clone(),
-                        status,
-                        service_type: ServiceType::Launchd,
-                        ports: Vec::new(),
-                        pid,
-                        path: None,
-                        description: None,
-                        auto_start: true,
-                        cpu_usage: None,
-                        memory_bytes: None,
-                        memory_percent: None,
-                    })
+        let services: Vec<Service> = parse_launchctl_list(&stdout)
+            .items
+            .into_iter()
+            .map(|entry| Service {
+                id: entry.label.clone(),
+                name: entry.label,
+                status: if entry.pid.is_some() {
+                    ServiceStatus::Running
                 } else {
-                    None
-                }
+                    ServiceStatus::Stopped
+                },
+                service_type: ServiceType::Launchd,
+                ports: Vec::new(),
+                pid: entry.pid,
+                path: None,
+                description: None,
+                auto_start: true,
+                cpu_usage: None,
+                memory_bytes: None,
+                memory_percent: None,
+                cpu_usage_total: None,
+                memory_total: None,
+                gpu_usage: None,
+                energy: None,
+                network_rx_bytes: None,
+                network_tx_bytes: None,
+                user: None,
+                recent_restarts: 0,
+                flapping: false,
+                compose_project: None,
+                compose_service: None,
             })
             .collect();
 
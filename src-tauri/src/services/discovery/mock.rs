@@ -0,0 +1,428 @@
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::models::port::{AddressFamily, Connection, IpcEndpoint, IpcKind, PortInfo, PortStatus, Protocol};
+use crate::models::service::{EnergyUsage, GpuProcessUsage, QosCpuTime, Service, ServiceStatus, ServiceType};
+use crate::services::system_stats::{
+    CpuStats, DiskStats, FanReading, GpuStats, HostInfo, LoadAverage, MemoryStats, NetworkInterfaceStats, NetworkStats,
+    SensorStats, SystemStats, TemperatureReading,
+};
+use crate::services::disk_health::{DiskHealth, DiskHealthState};
+use crate::services::port::ServiceNames;
+use super::traits::ServiceDiscovery;
+
+/// Environment variable that enables simulation mode (`1` or `true`)
+pub const MOCK_ENV_VAR: &str = "NETWORK_MANAGER_MOCK";
+
+/// Runtime toggle, driven by `Config::simulation_mode`
+static MOCK_ENABLED: AtomicBool = AtomicBool::new(false);
+
+const SEED: u64 = 0x5eed_1234_abcd_0042;
+const TOTAL_MEMORY: u64 = 16 * 1024 * 1024 * 1024;
+const CORE_COUNT: usize = 8;
+const DISK_SIZE: u64 = 512 * 1024 * 1024 * 1024;
+
+// name, type, ports, running, auto_start
+const FLEET: &[(&str, ServiceType, &[u16], bool, bool)] = &[
+    ("postgres", ServiceType::Docker, &[5432], true, true),
+    ("redis", ServiceType::Docker, &[6379], true, true),
+    ("nginx-proxy", ServiceType::Docker, &[80, 443], true, true),
+    ("grafana", ServiceType::Docker, &[3000], false, false),
+    ("sshd", ServiceType::Systemd, &[22], true, true),
+    ("cron", ServiceType::Systemd, &[], true, true),
+    ("cups", ServiceType::Systemd, &[631], false, false),
+    ("com.example.sync-agent", ServiceType::Launchd, &[], true, true),
+    ("com.example.updater", ServiceType::Launchd, &[], false, true),
+    ("Spooler", ServiceType::WindowsService, &[], true, true),
+    ("node", ServiceType::Process, &[5173, 9229], true, false),
+    ("python3", ServiceType::Process, &[8000], true, false),
+    ("ollama", ServiceType::Process, &[11434], true, false),
+];
+
+// fleet member, socket path
+const SOCKETS: &[(&str, &str)] = &[
+    ("postgres", "/var/run/postgresql/.s.PGSQL.5432"),
+    ("redis", "/var/run/redis/redis-server.sock"),
+    ("sshd", "/run/sshd.sock"),
+    ("node", "/tmp/vite-hmr.sock"),
+];
+
+// fleet member, local port, remote address, remote port, state
+const CONNECTIONS: &[(&str, u16, &str, u16, &str)] = &[
+    ("node", 41822, "127.0.0.1", 5432, "ESTABLISHED"),
+    ("node", 41830, "127.0.0.1", 6379, "ESTABLISHED"),
+    ("python3", 52114, "127.0.0.1", 5432, "ESTABLISHED"),
+    ("python3", 52120, "10.0.8.14", 9200, "SYN_SENT"),
+    ("sshd", 22, "192.168.1.23", 53218, "ESTABLISHED"),
+    ("ollama", 60342, "104.21.35.120", 443, "ESTABLISHED"),
+    ("nginx-proxy", 38410, "127.0.0.1", 8000, "CLOSE_WAIT"),
+];
+
+/// Simulation provider that returns a deterministic fleet of fake services,
+/// ports and metrics for demos and CI runs without Docker/systemd
+pub struct MockDiscovery;
+
+impl MockDiscovery {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether simulation mode is enabled via env var or config
+    pub fn is_enabled() -> bool {
+        MOCK_ENABLED.load(Ordering::Relaxed)
+            || std::env::var(MOCK_ENV_VAR)
+                .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false)
+    }
+
+    pub fn set_enabled(enabled: bool) {
+        MOCK_ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Simulated port table matching the fake fleet
+    pub fn port_usage(&self) -> Vec<PortInfo> {
+        FLEET
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, _, _, running, _))| *running)
+            .flat_map(|(index, (name, _, ports, _, _))| {
+                ports.iter().map(move |&port| PortInfo {
+                    port,
+                    protocol: Protocol::Tcp,
+                    family: AddressFamily::Ipv4,
+                    bind_address: "0.0.0.0".to_string(),
+                    status: PortStatus::Occupied,
+                    process_name: Some(name.to_string()),
+                    pid: Some(mock_pid(index)),
+                    detected_service: None,
+                    version: None,
+                    user: Some(whoami::username()),
+                    service_name: ServiceNames::lookup(port, &Protocol::Tcp),
+                    latency_ms: None,
+                    os_guess: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Simulated Unix sockets of the running fleet members
+    pub fn ipc_endpoints(&self) -> Vec<IpcEndpoint> {
+        SOCKETS
+            .iter()
+            .filter_map(|(name, path)| {
+                let index = FLEET.iter().position(|(fleet_name, _, _, running, _)| fleet_name == name && *running)?;
+                Some(IpcEndpoint {
+                    kind: IpcKind::UnixSocket,
+                    path: path.to_string(),
+                    state: Some("LISTEN".to_string()),
+                    process_name: Some(name.to_string()),
+                    pid: Some(mock_pid(index)),
+                    user: Some(whoami::username()),
+                })
+            })
+            .collect()
+    }
+
+    /// Simulated connections of the running fleet members
+    pub fn connections(&self) -> Vec<Connection> {
+        CONNECTIONS
+            .iter()
+            .filter_map(|&(name, local_port, remote_address, remote_port, state)| {
+                let index = FLEET.iter().position(|(fleet_name, _, _, running, _)| *fleet_name == name && *running)?;
+                let local_address = if remote_address == "127.0.0.1" { "127.0.0.1" } else { "192.168.1.10" };
+                Some(Connection {
+                    protocol: Protocol::Tcp,
+                    family: AddressFamily::Ipv4,
+                    local_address: local_address.to_string(),
+                    local_port,
+                    remote_address: remote_address.to_string(),
+                    remote_port,
+                    state: state.to_string(),
+                    process_name: Some(name.to_string()),
+                    pid: Some(mock_pid(index)),
+                    user: Some(whoami::username()),
+                    remote_service: ServiceNames::lookup(remote_port, &Protocol::Tcp),
+                })
+            })
+            .collect()
+    }
+
+    /// Simulated host, up for a few days at `tick`
+    pub fn host_info(tick: u64) -> HostInfo {
+        let load = (0.5 + unit(tick.wrapping_add(47)) * 3.0) as f64;
+        HostInfo {
+            hostname: Some("simulated-host".to_string()),
+            os_version: Some("Linux 24.04 Ubuntu".to_string()),
+            kernel_version: Some("6.8.0".to_string()),
+            arch: "x86_64".to_string(),
+            uptime_seconds: 3 * 86_400 + tick % 86_400,
+            boot_time: tick.saturating_sub(3 * 86_400),
+            load_average: Some(LoadAverage {
+                one: load,
+                five: load * 0.9,
+                fifteen: load * 0.8,
+            }),
+        }
+    }
+
+    /// A healthy SSD and a hard disk that started reallocating sectors
+    pub fn disk_health(&self) -> Vec<DiskHealth> {
+        let disk = |device: &str, model: &str, serial: &str| DiskHealth {
+            device: device.to_string(),
+            model: Some(model.to_string()),
+            serial: Some(serial.to_string()),
+            source: "smartctl".to_string(),
+            health: DiskHealthState::Unknown,
+            smart_passed: Some(true),
+            reallocated_sectors: None,
+            pending_sectors: None,
+            media_errors: None,
+            wear_percent: None,
+            temperature_celsius: None,
+            power_on_hours: None,
+            issues: Vec::new(),
+        };
+        vec![
+            DiskHealth {
+                media_errors: Some(0),
+                wear_percent: Some(12),
+                temperature_celsius: Some(38.0),
+                power_on_hours: Some(4_210),
+                ..disk("/dev/nvme0", "Simulated NVMe SSD", "SIM0001")
+            },
+            DiskHealth {
+                reallocated_sectors: Some(24),
+                pending_sectors: Some(0),
+                temperature_celsius: Some(41.0),
+                power_on_hours: Some(38_760),
+                ..disk("/dev/sda", "Simulated HDD 4TB", "SIM0002")
+            },
+        ]
+    }
+
+    /// Simulated system metrics; values vary with `tick` but are reproducible
+    pub fn system_stats(&self, tick: u64) -> SystemStats {
+        let per_core_usage: Vec<f32> = (0..CORE_COUNT)
+            .map(|core| 5.0 + unit(tick.wrapping_mul(31).wrapping_add(core as u64)) * 60.0)
+            .collect();
+        let usage_percent = per_core_usage.iter().sum::<f32>() / CORE_COUNT as f32;
+
+        let used_bytes = (TOTAL_MEMORY as f64 * (0.4 + unit(tick) as f64 * 0.3)) as u64;
+        let rx_bytes_per_sec = (unit(tick.wrapping_add(23)) * 4.0 * 1024.0 * 1024.0) as u64;
+        let tx_bytes_per_sec = (unit(tick.wrapping_add(29)) * 512.0 * 1024.0) as u64;
+
+        SystemStats {
+            cpu: CpuStats {
+                usage_percent,
+                core_count: CORE_COUNT,
+                per_core_usage,
+                frequency_mhz: Some(3200),
+            },
+            memory: MemoryStats {
+                total_bytes: TOTAL_MEMORY,
+                used_bytes,
+                available_bytes: TOTAL_MEMORY - used_bytes,
+                usage_percent: (used_bytes as f64 / TOTAL_MEMORY as f64 * 100.0) as f32,
+                swap_total_bytes: 2 * 1024 * 1024 * 1024,
+                swap_used_bytes: 128 * 1024 * 1024,
+            },
+            gpus: vec![GpuStats {
+                name: "Simulated GPU".to_string(),
+                usage_percent: Some(unit(tick.wrapping_add(7)) * 100.0),
+                memory_used_bytes: Some(2 * 1024 * 1024 * 1024),
+                memory_total_bytes: Some(8 * 1024 * 1024 * 1024),
+                temperature_celsius: Some(45.0 + unit(tick.wrapping_add(13)) * 20.0),
+                power_watts: Some(30.0 + unit(tick.wrapping_add(17)) * 90.0),
+                frequency_mhz: Some(1200 + (unit(tick.wrapping_add(19)) * 600.0) as u32),
+                provider: None,
+            }],
+            network: NetworkStats {
+                rx_bytes_per_sec,
+                tx_bytes_per_sec,
+                interfaces: vec![NetworkInterfaceStats {
+                    name: "eth0".to_string(),
+                    rx_bytes_per_sec,
+                    tx_bytes_per_sec,
+                    total_rx_bytes: 48 * 1024 * 1024 * 1024 + tick * rx_bytes_per_sec,
+                    total_tx_bytes: 6 * 1024 * 1024 * 1024 + tick * tx_bytes_per_sec,
+                    link_up: Some(true),
+                    mac_address: "02:00:00:00:00:01".to_string(),
+                    ip_addresses: vec!["192.168.1.20/24".to_string()],
+                }],
+            },
+            disks: vec![DiskStats {
+                name: "/dev/nvme0n1p2".to_string(),
+                mount_point: "/".to_string(),
+                file_system: "ext4".to_string(),
+                kind: "ssd".to_string(),
+                removable: false,
+                total_bytes: DISK_SIZE,
+                available_bytes: DISK_SIZE / 4,
+                usage_percent: 75.0,
+                read_bytes_per_sec: (unit(tick.wrapping_add(37)) * 20.0 * 1024.0 * 1024.0) as u64,
+                write_bytes_per_sec: (unit(tick.wrapping_add(41)) * 8.0 * 1024.0 * 1024.0) as u64,
+            }],
+            sensors: SensorStats {
+                temperatures: vec![
+                    TemperatureReading {
+                        label: "CPU Package".to_string(),
+                        kind: "cpu".to_string(),
+                        celsius: 40.0 + usage_percent * 0.5,
+                        max_celsius: Some(95.0),
+                        critical_celsius: Some(100.0),
+                    },
+                    TemperatureReading {
+                        label: "nvme Composite".to_string(),
+                        kind: "storage".to_string(),
+                        celsius: 38.0 + unit(tick.wrapping_add(43)) * 10.0,
+                        max_celsius: Some(80.0),
+                        critical_celsius: Some(85.0),
+                    },
+                ],
+                fans: vec![FanReading {
+                    label: "cpu_fan".to_string(),
+                    rpm: 900 + (usage_percent * 20.0) as u32,
+                }],
+            },
+            host: Self::host_info(tick),
+            timestamp: tick,
+        }
+    }
+}
+
+impl Default for MockDiscovery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ServiceDiscovery for MockDiscovery {
+    async fn discover(&self) -> Result<Vec<Service>, Box<dyn std::error::Error + Send + Sync>> {
+        let services = FLEET
+            .iter()
+            .enumerate()
+            .map(|(index, (name, service_type, ports, running, auto_start))| {
+                let pid = running.then(|| mock_pid(index));
+                let memory_bytes = running.then(|| (64 + (mix(index as u64) % 960)) * 1024 * 1024);
+                let cpu_usage = running.then(|| unit(index as u64) * 25.0);
+                let workers = 1 + mix(index as u64 + 3) % 4;
+                let network_rx_bytes = (*running && *service_type == ServiceType::Docker)
+                    .then(|| (100 + mix(index as u64 + 5) % 900) * 1024 * 1024);
+
+                Service {
+                    id: match service_type {
+                        ServiceType::Process => format!("process-{}", mock_pid(index)),
+                        _ => format!("mock-{}", name),
+                    },
+                    name: name.to_string(),
+                    status: if *running { ServiceStatus::Running } else { ServiceStatus::Stopped },
+                    service_type: service_type.clone(),
+                    ports: ports.to_vec(),
+                    pid,
+                    path: Some(format!("/usr/local/bin/{}", name)),
+                    description: Some(format!("Simulierter Service {}", name)),
+                    auto_start: *auto_start,
+                    cpu_usage,
+                    memory_bytes,
+                    memory_percent: memory_bytes.map(|m| (m as f64 / TOTAL_MEMORY as f64 * 100.0) as f32),
+                    // Every few services pretend to have worker processes
+                    cpu_usage_total: cpu_usage.map(|cpu| cpu * workers as f32),
+                    memory_total: memory_bytes.map(|m| m * workers),
+                    // The first running service plays an ML workload
+                    gpu_usage: (*running && index == 0).then(|| GpuProcessUsage {
+                        memory_bytes: Some(3 * 1024 * 1024 * 1024),
+                        utilization_percent: Some(unit(index as u64 + 11) * 100.0),
+                    }),
+                    // 1% CPU is 10 ms/s; every other service works in the background
+                    energy: cpu_usage.map(|cpu| {
+                        let background = index % 2 == 1;
+                        EnergyUsage {
+                            energy_impact: Some(cpu * if background { 0.6 } else { 1.5 }),
+                            power_milliwatts: Some(cpu * if background { 8.0 } else { 25.0 }),
+                            qos: Some(QosCpuTime {
+                                default: if background { 0.0 } else { cpu * 10.0 },
+                                background: if background { cpu * 10.0 } else { 0.0 },
+                                ..QosCpuTime::default()
+                            }),
+                        }
+                    }),
+                    network_rx_bytes,
+                    network_tx_bytes: network_rx_bytes.map(|rx| rx / 3),
+                    user: running.then(whoami::username),
+                    recent_restarts: 0,
+                    flapping: false,
+                    compose_project: None,
+                    compose_service: None,
+                }
+            })
+            .collect();
+
+        Ok(services)
+    }
+
+    async fn get_service(&self, id: &str) -> Result<Option<Service>, Box<dyn std::error::Error + Send + Sync>> {
+        let services = self.discover().await?;
+        Ok(services.into_iter().find(|s| s.id == id))
+    }
+
+    fn is_available(&self) -> bool {
+        Self::is_enabled()
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Simulation"
+    }
+}
+
+fn mock_pid(index: usize) -> u32 {
+    1000 + index as u32 * 7
+}
+
+/// SplitMix64 step - cheap, stable pseudo-random mixing
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(SEED).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Deterministic value in [0, 1)
+fn unit(value: u64) -> f32 {
+    (mix(value) >> 40) as f32 / (1u64 << 24) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json<T: serde::Serialize>(value: T) -> serde_json::Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn the_same_seed_gives_the_same_fleet() {
+        let first = MockDiscovery::new();
+        let second = MockDiscovery::new();
+
+        assert_eq!(json(first.discover().await.unwrap()), json(second.discover().await.unwrap()));
+        assert_eq!(json(first.port_usage()), json(second.port_usage()));
+        assert_eq!(json(first.connections()), json(second.connections()));
+        assert_eq!(json(first.system_stats(7)), json(second.system_stats(7)));
+    }
+
+    #[tokio::test]
+    async fn the_fleet_is_stable_across_runs() {
+        assert_eq!(mix(0), 0xabeb_abc1_ebdd_124a);
+
+        let services = MockDiscovery::new().discover().await.unwrap();
+        let postgres = services.iter().find(|s| s.name == "postgres").unwrap();
+        assert_eq!(postgres.id, "mock-postgres");
+        assert_eq!(postgres.pid, Some(1000));
+        assert_eq!(postgres.memory_bytes, Some(842 * 1024 * 1024));
+
+        let python = services.iter().find(|s| s.name == "python3").unwrap();
+        assert_eq!(python.id, format!("process-{}", mock_pid(11)));
+        let port = MockDiscovery::new().port_usage().into_iter().find(|p| p.port == 8000).unwrap();
+        assert_eq!(port.pid, python.pid);
+    }
+}
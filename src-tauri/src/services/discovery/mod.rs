@@ -3,6 +3,7 @@
 pub mod traits;
 pub mod docker;
 pub mod process;
+pub mod mock;
 
 #[cfg(target_os = "macos")]
 pub mod launchd;
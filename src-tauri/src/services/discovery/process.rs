@@ -58,6 +58,18 @@ impl ServiceDiscovery for ProcessDiscovery {
                     cpu_usage: Some(process.cpu_usage()),
                     memory_bytes: Some(memory_bytes),
                     memory_percent,
+                    cpu_usage_total: None,
+                    memory_total: None,
+                    gpu_usage: None,
+                    energy: None,
+                    network_rx_bytes: None,
+                    network_tx_bytes: None,
+                    // Filled in by the manager from one owner lookup
+                    user: None,
+                    recent_restarts: 0,
+                    flapping: false,
+                    compose_project: None,
+                    compose_service: None,
                 }
             })
             .collect();
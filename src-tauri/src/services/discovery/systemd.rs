@@ -1,6 +1,7 @@
 use async_trait::async_trait;
-use std::process::Command;
+use crate::services::cmd;
 use crate::models::service::{Service, ServiceStatus, ServiceType};
+use crate::parsers::systemctl::parse_list_units;
 use super::traits::ServiceDiscovery;
 
 pub struct SystemdDiscovery;
@@ -14,8 +15,8 @@ impl SystemdDiscovery {
 #[async_trait]
 impl ServiceDiscovery for SystemdDiscovery {
     async fn discover(&self) -> Result<Vec<Service>, Box<dyn std::error::Error + Send + Sync>> {
-        let output = Command::new("systemctl")
-            .args(["list-units", "--type=service", "--all", "--no-pager", "--plain"])
+        let output = cmd::command("systemctl")
+            .args(["list-units", "--type=service", "--all", "--no-pager", "--plain", "--no-legend"])
             .output()?;
 
         if !output.status.success() {
@@ -23,36 +24,41 @@ impl ServiceDiscovery for SystemdDiscovery {
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let services: Vec<Service> = stdout
-            .lines()
-            .filter(|line| line.contains(".service"))
-            .filter_map(|line| {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 4 {
-                    let name = parts[0].trim_end_matches(".service").to_string();
-                    let status = match parts[3] {
-                        "running" => ServiceStatus::Running,
-                        "exited" | "dead" | "inactive" => ServiceStatus::Stopped,
-                        "failed" => ServiceStatus::Error,
-                        _ => ServiceStatus::Unknown,
-                    };
+        let services: Vec<Service> = parse_list_units(&stdout)
+            .items
+            .into_iter()
+            .map(|unit| {
+                let status = match unit.sub.as_str() {
+                    "running" => ServiceStatus::Running,
+                    "exited" | "dead" | "inactive" => ServiceStatus::Stopped,
+                    "failed" => ServiceStatus::Error,
+                    _ => ServiceStatus::Unknown,
+                };
 
-                    Some(Service {
-                        id: parts[0].to_string(),
-                        name,
-                        status,
-                        service_type: ServiceType::Systemd,
-                        ports: Vec::new(),
-                        pid: None,
-                        path: None,
-                        description: parts.get(4..).map(|p| p.join(" ")),
-                        auto_start: parts.get(1).map(|s| *s == "enabled").unwrap_or(false),
-                        cpu_usage: None,
-                        memory_bytes: None,
-                        memory_percent: None,
-                    })
-                } else {
-                    None
+                Service {
+                    id: unit.unit.clone(),
+                    name: unit.unit.trim_end_matches(".service").to_string(),
+                    status,
+                    service_type: ServiceType::Systemd,
+                    ports: Vec::new(),
+                    pid: None,
+                    path: None,
+                    description: unit.description,
+                    auto_start: unit.load == "enabled",
+                    cpu_usage: None,
+                    memory_bytes: None,
+                    memory_percent: None,
+                    cpu_usage_total: None,
+                    memory_total: None,
+                    gpu_usage: None,
+                    energy: None,
+                    network_rx_bytes: None,
+                    network_tx_bytes: None,
+                    user: None,
+                    recent_restarts: 0,
+                    flapping: false,
+                    compose_project: None,
+                    compose_service: None,
                 }
             })
             .collect();
@@ -66,7 +72,7 @@ impl ServiceDiscovery for SystemdDiscovery {
     }
 
     fn is_available(&self) -> bool {
-        cfg!(target_os = "linux") && Command::new("systemctl").arg("--version").output().is_ok()
+        cfg!(target_os = "linux") && cmd::command("systemctl").arg("--version").output().is_ok()
     }
 
     fn provider_name(&self) -> &'static str {
@@ -3,7 +3,7 @@ use crate::models::service::{Service, ServiceStatus, ServiceType};
 use super::traits::ServiceDiscovery;
 
 #[cfg(target_os = "windows")]
-use std::process::Command;
+use crate::services::cmd;
 
 pub struct WindowsServiceDiscovery;
 
@@ -18,7 +18,7 @@ impl ServiceDiscovery for WindowsServiceDiscovery {
     #[cfg(target_os = "windows")]
     async fn discover(&self) -> Result<Vec<Service>, Box<dyn std::error::Error + Send + Sync>> {
         // Use PowerShell to get service information
-        let output = Command::new("powershell")
+        let output = cmd::command("powershell")
             .args(["-Command", "Get-Service | Select-Object Name,Status,DisplayName | ConvertTo-Json"])
             .output()?;
 
@@ -53,6 +53,17 @@ impl ServiceDiscovery for WindowsServiceDiscovery {
                         cpu_usage: None,
                         memory_bytes: None,
                         memory_percent: None,
+                        cpu_usage_total: None,
+                        memory_total: None,
+                        gpu_usage: None,
+                        energy: None,
+                        network_rx_bytes: None,
+                        network_tx_bytes: None,
+                        user: None,
+                        recent_restarts: 0,
+                        flapping: false,
+                        compose_project: None,
+                        compose_service: None,
                     })
                 })
                 .collect()
@@ -0,0 +1,347 @@
+// Drive health from SMART: smartctl where installed, IOKit or WMI otherwise
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::parsers::smartctl::{self, SmartRecord, SMARTCTL_OPEN_FAILED};
+use crate::services::cmd;
+use crate::services::security_scanner::{SecurityCategory, SecurityIssue, SecuritySeverity};
+
+/// Any remapped sector means the surface has started to fail
+const REALLOCATED_WARNING: u64 = 1;
+/// Failures at this rate usually end with the drive dying
+const REALLOCATED_CRITICAL: u64 = 100;
+const WEAR_WARNING_PERCENT: u8 = 80;
+const WEAR_CRITICAL_PERCENT: u8 = 95;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiskHealthState {
+    Ok,
+    Warning,
+    Critical,
+    /// No SMART data could be read
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskHealth {
+    /// `/dev/sda`, `disk0` or the Windows disk number
+    pub device: String,
+    pub model: Option<String>,
+    pub serial: Option<String>,
+    /// `smartctl`, `iokit` or `wmi`
+    pub source: String,
+    pub health: DiskHealthState,
+    /// The drive's overall self-assessment
+    pub smart_passed: Option<bool>,
+    pub reallocated_sectors: Option<u64>,
+    pub pending_sectors: Option<u64>,
+    /// NVMe media errors; uncorrected read errors on Windows
+    pub media_errors: Option<u64>,
+    /// Share of the rated endurance used up (SSDs)
+    pub wear_percent: Option<u8>,
+    pub temperature_celsius: Option<f32>,
+    pub power_on_hours: Option<u64>,
+    pub issues: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskHealthReport {
+    pub disks: Vec<DiskHealth>,
+    /// Why data is missing, e.g. smartctl not installed or no privileges
+    pub warnings: Vec<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Reads SMART data of all drives. smartctl gives the full attribute set on
+/// every platform; without it, macOS only has the overall status from IOKit
+/// (via `system_profiler`) and Windows the reliability counters from WMI.
+pub struct DiskHealthChecker;
+
+impl DiskHealthChecker {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Blocking: runs smartctl once per drive
+    pub fn check(&self) -> DiskHealthReport {
+        let mut warnings = Vec::new();
+        let mut disks = match cmd::find_in_path("smartctl") {
+            Some(path) => smartctl_disks(&path.to_string_lossy(), &mut warnings),
+            None => {
+                warnings.push("smartctl ist nicht installiert (Paket smartmontools); nur eingeschränkte Daten verfügbar".to_string());
+                Vec::new()
+            }
+        };
+        if disks.is_empty() {
+            disks = fallback_disks(&mut warnings);
+        }
+        DiskHealthReport::new(disks, warnings)
+    }
+
+    /// One reliability finding per drive with warnings, for `scan_security`
+    pub fn findings(report: &DiskHealthReport) -> Vec<SecurityIssue> {
+        report
+            .disks
+            .iter()
+            .filter_map(|disk| {
+                let severity = problems(disk).into_iter().map(|(severity, _)| severity).reduce(worse)?;
+                let name = disk.model.clone().unwrap_or_else(|| disk.device.clone());
+                let recommendation = if severity == SecuritySeverity::Critical {
+                    "Sofort ein Backup anlegen und das Laufwerk ersetzen"
+                } else {
+                    "Backups prüfen und den Austausch des Laufwerks einplanen"
+                };
+                Some(SecurityIssue {
+                    id: format!("disk-health-{}", disk.serial.as_deref().unwrap_or(&disk.device)),
+                    service_id: None,
+                    service_name: None,
+                    category: SecurityCategory::Reliability,
+                    severity,
+                    title: format!("Laufwerk {} meldet Probleme", name),
+                    description: disk.issues.join(". "),
+                    recommendation: recommendation.to_string(),
+                    port: None,
+                    details: Some(format!("{} ({})", disk.device, disk.source)),
+                })
+            })
+            .collect()
+    }
+}
+
+impl DiskHealthReport {
+    /// Rates each drive against the thresholds
+    pub fn new(mut disks: Vec<DiskHealth>, warnings: Vec<String>) -> Self {
+        for disk in &mut disks {
+            assess(disk);
+        }
+        Self {
+            disks,
+            warnings,
+            checked_at: Utc::now(),
+        }
+    }
+}
+
+impl Default for DiskHealthChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Threshold violations with the severity of the resulting finding
+fn problems(disk: &DiskHealth) -> Vec<(SecuritySeverity, String)> {
+    let mut problems = Vec::new();
+    if disk.smart_passed == Some(false) {
+        problems.push((SecuritySeverity::Critical, "Der SMART-Selbsttest meldet einen Ausfall".to_string()));
+    }
+    if let Some(sectors) = disk.reallocated_sectors.filter(|&s| s >= REALLOCATED_WARNING) {
+        let severity = if sectors >= REALLOCATED_CRITICAL {
+            SecuritySeverity::Critical
+        } else {
+            SecuritySeverity::High
+        };
+        problems.push((severity, format!("{} Sektoren wurden ersetzt", sectors)));
+    }
+    if let Some(sectors) = disk.pending_sectors.filter(|&s| s > 0) {
+        problems.push((SecuritySeverity::High, format!("{} Sektoren sind nicht lesbar", sectors)));
+    }
+    if let Some(errors) = disk.media_errors.filter(|&e| e > 0) {
+        problems.push((SecuritySeverity::High, format!("{} nicht korrigierbare Lesefehler", errors)));
+    }
+    if let Some(wear) = disk.wear_percent.filter(|&w| w >= WEAR_WARNING_PERCENT) {
+        let severity = if wear >= WEAR_CRITICAL_PERCENT {
+            SecuritySeverity::High
+        } else {
+            SecuritySeverity::Medium
+        };
+        problems.push((severity, format!("{}% der Schreib-Lebensdauer verbraucht", wear)));
+    }
+    problems
+}
+
+fn worse(a: SecuritySeverity, b: SecuritySeverity) -> SecuritySeverity {
+    let rank = |s: &SecuritySeverity| match s {
+        SecuritySeverity::Critical => 0,
+        SecuritySeverity::High => 1,
+        SecuritySeverity::Medium => 2,
+        SecuritySeverity::Low => 3,
+        SecuritySeverity::Info => 4,
+    };
+    if rank(&a) <= rank(&b) { a } else { b }
+}
+
+/// Set `health` and `issues` from the thresholds
+fn assess(disk: &mut DiskHealth) {
+    let problems = problems(disk);
+    let has_data = disk.smart_passed.is_some()
+        || disk.reallocated_sectors.is_some()
+        || disk.wear_percent.is_some()
+        || disk.media_errors.is_some();
+    disk.health = if problems.iter().any(|(s, _)| *s == SecuritySeverity::Critical) {
+        DiskHealthState::Critical
+    } else if !problems.is_empty() {
+        DiskHealthState::Warning
+    } else if has_data {
+        DiskHealthState::Ok
+    } else {
+        DiskHealthState::Unknown
+    };
+    disk.issues.extend(problems.into_iter().map(|(_, message)| message));
+}
+
+fn smartctl_disks(smartctl: &str, warnings: &mut Vec<String>) -> Vec<DiskHealth> {
+    let Ok(output) = cmd::command(smartctl).args(["--scan", "--json"]).output() else {
+        warnings.push("smartctl konnte nicht gestartet werden".to_string());
+        return Vec::new();
+    };
+    let devices = smartctl::parse_smartctl_scan(&String::from_utf8_lossy(&output.stdout)).items;
+
+    let mut disks = Vec::new();
+    for device in devices {
+        let mut args = vec!["--all".to_string(), "--json".to_string()];
+        if let Some(device_type) = &device.device_type {
+            args.extend(["-d".to_string(), device_type.clone()]);
+        }
+        args.push(device.name.clone());
+
+        let mut record = smartctl_info(cmd::command(smartctl).args(&args));
+        // Raw device access needs root on Linux; use a sudoers rule if there is one
+        if record.as_ref().is_none_or(|r| r.exit_status & SMARTCTL_OPEN_FAILED != 0) {
+            if let Some(elevated) = smartctl_info(cmd::command("sudo").arg("-n").arg(smartctl).args(&args)) {
+                if elevated.exit_status & SMARTCTL_OPEN_FAILED == 0 || record.is_none() {
+                    record = Some(elevated);
+                }
+            }
+        }
+        let Some(record) = record else {
+            continue;
+        };
+        if record.exit_status & SMARTCTL_OPEN_FAILED != 0 {
+            warnings.push(format!(
+                "{}: {}. smartctl braucht Root-Rechte, z.B. über eine sudoers-Regel für smartctl",
+                device.name,
+                record.error.as_deref().unwrap_or("Gerät konnte nicht geöffnet werden")
+            ));
+            continue;
+        }
+        disks.push(from_smart_record(device.name, record));
+    }
+    disks
+}
+
+fn smartctl_info(command: &mut std::process::Command) -> Option<SmartRecord> {
+    // Non-zero exit codes are a bit mask that includes "disk failing", so
+    // the output is read regardless
+    let output = command.output().ok()?;
+    smartctl::parse_smartctl_info(&String::from_utf8_lossy(&output.stdout)).items.pop()
+}
+
+fn from_smart_record(device: String, record: SmartRecord) -> DiskHealth {
+    DiskHealth {
+        model: record.model,
+        serial: record.serial,
+        smart_passed: record.passed,
+        reallocated_sectors: record.reallocated_sectors,
+        pending_sectors: record.pending_sectors,
+        media_errors: record.media_errors,
+        wear_percent: record.wear_percent,
+        temperature_celsius: record.temperature_celsius,
+        power_on_hours: record.power_on_hours,
+        ..empty_disk(device, "smartctl")
+    }
+}
+
+fn empty_disk(device: String, source: &str) -> DiskHealth {
+    DiskHealth {
+        device,
+        model: None,
+        serial: None,
+        source: source.to_string(),
+        health: DiskHealthState::Unknown,
+        smart_passed: None,
+        reallocated_sectors: None,
+        pending_sectors: None,
+        media_errors: None,
+        wear_percent: None,
+        temperature_celsius: None,
+        power_on_hours: None,
+        issues: Vec::new(),
+    }
+}
+
+/// Overall SMART status of internal drives from IOKit
+#[cfg(target_os = "macos")]
+fn fallback_disks(warnings: &mut Vec<String>) -> Vec<DiskHealth> {
+    let output = cmd::command("system_profiler")
+        .args(["SPNVMeDataType", "SPSerialATADataType", "-json"])
+        .output();
+    let Some(json) = output.ok().and_then(|o| serde_json::from_slice::<serde_json::Value>(&o.stdout).ok()) else {
+        warnings.push("system_profiler lieferte keine Laufwerksdaten".to_string());
+        return Vec::new();
+    };
+
+    let mut disks = Vec::new();
+    for data_type in ["SPNVMeDataType", "SPSerialATADataType"] {
+        // Controllers, each with its drives
+        for controller in json[data_type].as_array().into_iter().flatten() {
+            for drive in controller["_items"].as_array().into_iter().flatten() {
+                let device = drive["bsd_name"].as_str().or(drive["_name"].as_str()).unwrap_or_default();
+                let mut disk = empty_disk(device.to_string(), "iokit");
+                disk.model = drive["device_model"].as_str().or(drive["_name"].as_str()).map(String::from);
+                disk.serial = drive["device_serial"].as_str().map(String::from);
+                disk.smart_passed = match drive["smart_status"].as_str() {
+                    Some("Verified") => Some(true),
+                    Some("Failing") => Some(false),
+                    _ => None,
+                };
+                disks.push(disk);
+            }
+        }
+    }
+    disks
+}
+
+/// Health status and reliability counters from the Storage WMI provider
+#[cfg(target_os = "windows")]
+fn fallback_disks(warnings: &mut Vec<String>) -> Vec<DiskHealth> {
+    let script = "Get-PhysicalDisk | ForEach-Object { $r = $_ | Get-StorageReliabilityCounter; \
+        [pscustomobject]@{ DeviceId = $_.DeviceId; Name = $_.FriendlyName; Serial = $_.SerialNumber; \
+        Health = [string]$_.HealthStatus; Wear = $r.Wear; ReadErrors = $r.ReadErrorsUncorrected; \
+        Temperature = $r.Temperature; PowerOnHours = $r.PowerOnHours } } | ConvertTo-Json";
+    let output = cmd::command("powershell").args(["-NoProfile", "-Command", script]).output();
+    let Some(json) = output.ok().and_then(|o| serde_json::from_slice::<serde_json::Value>(&o.stdout).ok()) else {
+        warnings.push("Die Zuverlässigkeitszähler konnten nicht gelesen werden".to_string());
+        return Vec::new();
+    };
+
+    // A single disk is serialized as an object instead of an array
+    let items = match json {
+        serde_json::Value::Array(items) => items,
+        item => vec![item],
+    };
+    items
+        .iter()
+        .map(|item| {
+            let device = item["DeviceId"].as_str().unwrap_or_default();
+            let mut disk = empty_disk(format!("PhysicalDrive{}", device), "wmi");
+            disk.model = item["Name"].as_str().map(|s| s.trim().to_string());
+            disk.serial = item["Serial"].as_str().map(|s| s.trim().to_string());
+            disk.smart_passed = match item["Health"].as_str() {
+                Some("Healthy") => Some(true),
+                Some("Unhealthy") => Some(false),
+                _ => None,
+            };
+            disk.wear_percent = item["Wear"].as_u64().map(|w| w.min(100) as u8);
+            disk.media_errors = item["ReadErrors"].as_u64();
+            disk.temperature_celsius = item["Temperature"].as_f64().filter(|&t| t > 0.0).map(|t| t as f32);
+            disk.power_on_hours = item["PowerOnHours"].as_u64();
+            disk
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn fallback_disks(_warnings: &mut Vec<String>) -> Vec<DiskHealth> {
+    Vec::new()
+}
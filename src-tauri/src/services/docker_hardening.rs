@@ -0,0 +1,190 @@
+// Container hardening checks from `docker inspect`
+
+use bollard::Docker;
+use bollard::models::{ContainerInspectResponse, ContainerSummaryStateEnum, MountPointTypeEnum};
+use bollard::query_parameters::{InspectContainerOptions, ListContainersOptions};
+use crate::services::security_scanner::{SecurityCategory, SecurityIssue, SecuritySeverity};
+
+/// Capabilities that amount to root on the host or its network
+const DANGEROUS_CAPABILITIES: &[&str] = &[
+    "ALL", "SYS_ADMIN", "SYS_MODULE", "SYS_PTRACE", "SYS_RAWIO", "DAC_READ_SEARCH", "NET_ADMIN", "BPF",
+];
+
+/// Host paths whose bind mount exposes credentials, the boot chain or other containers
+const SENSITIVE_HOST_PATHS: &[&str] = &[
+    "/", "/etc", "/root", "/home", "/boot", "/proc", "/sys", "/dev", "/var/lib/docker", "/var/run", "/run",
+];
+
+const DOCKER_SOCKETS: &[&str] = &["/var/run/docker.sock", "/run/docker.sock"];
+
+/// Flags running containers whose configuration weakens the isolation from
+/// the host: privileged mode, host networking, a mounted Docker socket,
+/// added capabilities, sensitive bind mounts and root as container user.
+pub struct DockerHardening {
+    docker: Option<Docker>,
+}
+
+impl DockerHardening {
+    pub fn new() -> Self {
+        let docker = Docker::connect_with_local_defaults().ok();
+        Self { docker }
+    }
+
+    /// Findings for all running containers. Empty without Docker.
+    pub async fn findings(&self) -> Result<Vec<SecurityIssue>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(docker) = &self.docker else {
+            return Ok(Vec::new());
+        };
+
+        let containers = docker.list_containers(Some(ListContainersOptions::default())).await?;
+        let inspections = futures::future::join_all(
+            containers
+                .iter()
+                .filter(|c| c.state == Some(ContainerSummaryStateEnum::RUNNING))
+                .filter_map(|c| c.id.as_deref())
+                .map(|id| docker.inspect_container(id, None::<InspectContainerOptions>)),
+        )
+        .await;
+
+        Ok(inspections.into_iter().flatten().flat_map(|info| container_findings(&info)).collect())
+    }
+}
+
+impl Default for DockerHardening {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn container_findings(info: &ContainerInspectResponse) -> Vec<SecurityIssue> {
+    let id = info.id.clone().unwrap_or_default();
+    let name = info.name.as_deref().unwrap_or("unknown").trim_start_matches('/').to_string();
+    let short_id: String = id.chars().take(12).collect();
+    let host_config = info.host_config.as_ref();
+    let mut issues = Vec::new();
+    let mut push = |kind: &str, category, severity, title: String, description: &str, recommendation: &str, details: Option<String>| {
+        issues.push(SecurityIssue {
+            id: format!("docker-{}-{}", kind, short_id),
+            service_id: Some(id.clone()),
+            service_name: Some(name.clone()),
+            category,
+            severity,
+            title,
+            description: description.to_string(),
+            recommendation: recommendation.to_string(),
+            port: None,
+            details,
+        });
+    };
+
+    if host_config.and_then(|hc| hc.privileged) == Some(true) {
+        push(
+            "privileged",
+            SecurityCategory::PrivilegeEscalation,
+            SecuritySeverity::Critical,
+            format!("Container {} läuft privilegiert", name),
+            "Ein privilegierter Container hat Zugriff auf alle Geräte und kann aus dem Container auf den Host ausbrechen",
+            "Entferne --privileged und gib nur die benötigten Geräte (--device) oder Capabilities (--cap-add) frei",
+            None,
+        );
+    }
+
+    if host_config.and_then(|hc| hc.network_mode.as_deref()) == Some("host") {
+        push(
+            "host-network",
+            SecurityCategory::PublicExposure,
+            SecuritySeverity::Medium,
+            format!("Container {} nutzt das Host-Netzwerk", name),
+            "Jeder Port im Container lauscht direkt auf den Schnittstellen des Hosts, ohne Port-Mapping",
+            "Verwende ein Bridge-Netzwerk und veröffentliche nur benötigte Ports mit -p 127.0.0.1:PORT:PORT",
+            None,
+        );
+    }
+
+    let capabilities: Vec<String> = host_config
+        .and_then(|hc| hc.cap_add.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|cap| cap.trim_start_matches("CAP_").to_uppercase())
+        .collect();
+    if !capabilities.is_empty() {
+        let dangerous: Vec<&str> = capabilities
+            .iter()
+            .map(String::as_str)
+            .filter(|cap| DANGEROUS_CAPABILITIES.contains(cap))
+            .collect();
+        push(
+            "capabilities",
+            SecurityCategory::PrivilegeEscalation,
+            if dangerous.is_empty() { SecuritySeverity::Low } else { SecuritySeverity::High },
+            if dangerous.is_empty() {
+                format!("Container {} hat zusätzliche Capabilities", name)
+            } else {
+                format!("Container {} hat die Capability {}", name, dangerous.join(", "))
+            },
+            "Zusätzliche Capabilities erweitern die Rechte von root im Container",
+            "Entferne nicht benötigte --cap-add Einträge; SYS_ADMIN und ALL sind fast so mächtig wie --privileged",
+            Some(format!("cap_add: {}", capabilities.join(", "))),
+        );
+    }
+
+    for mount in info.mounts.iter().flatten() {
+        if mount.typ != Some(MountPointTypeEnum::BIND) {
+            continue;
+        }
+        let Some(source) = mount.source.as_deref() else {
+            continue;
+        };
+        let destination = mount.destination.as_deref().unwrap_or_default();
+        let writable = mount.rw.unwrap_or(true);
+        let details = Some(format!("{} -> {} ({})", source, destination, if writable { "rw" } else { "ro" }));
+
+        if DOCKER_SOCKETS.contains(&source) {
+            push(
+                "docker-socket",
+                SecurityCategory::PrivilegeEscalation,
+                SecuritySeverity::Critical,
+                format!("Container {} hat Zugriff auf den Docker-Socket", name),
+                "Über den Docker-Socket kann der Container beliebige privilegierte Container starten und damit den Host übernehmen",
+                "Entferne das Socket-Mount oder verwende einen Socket-Proxy, der nur lesende API-Aufrufe erlaubt",
+                details,
+            );
+        } else if SENSITIVE_HOST_PATHS.contains(&normalize(source)) {
+            let path = normalize(source).trim_start_matches('/');
+            push(
+                &format!("mount-{}", if path.is_empty() { "rootfs".to_string() } else { path.replace('/', "-") }),
+                SecurityCategory::DataLeakage,
+                if writable { SecuritySeverity::High } else { SecuritySeverity::Medium },
+                format!("Container {} bindet {} vom Host ein", name, source),
+                "Das Verzeichnis enthält Zugangsdaten oder Systemdateien des Hosts",
+                "Binde nur die benötigten Unterverzeichnisse ein, möglichst schreibgeschützt (:ro)",
+                details,
+            );
+        }
+    }
+
+    // The inspected config already includes the image's USER, so empty means root
+    let user = info.config.as_ref().and_then(|c| c.user.as_deref()).unwrap_or_default();
+    let user_name = user.split(':').next().unwrap_or_default();
+    if matches!(user_name, "" | "root" | "0") {
+        push(
+            "root-user",
+            SecurityCategory::PrivilegeEscalation,
+            SecuritySeverity::Low,
+            format!("Container {} läuft als root", name),
+            "Ein Ausbruch aus einem Container, der als root läuft, hat auf dem Host sofort volle Rechte, sofern kein User-Namespace-Remapping aktiv ist",
+            "Setze einen unprivilegierten Benutzer mit --user oder USER im Dockerfile",
+            None,
+        );
+    }
+
+    issues
+}
+
+/// `/etc/` -> `/etc`, keeping `/` itself
+fn normalize(path: &str) -> &str {
+    match path.trim_end_matches('/') {
+        "" => "/",
+        trimmed => trimmed,
+    }
+}
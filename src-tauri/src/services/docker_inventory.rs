@@ -0,0 +1,279 @@
+use bollard::Docker;
+use bollard::models::{ContainerSummary, ContainerSummaryStateEnum};
+use bollard::query_parameters::{
+    CreateImageOptionsBuilder, ListContainersOptions, ListImagesOptions, ListNetworksOptions,
+    ListVolumesOptions, RemoveImageOptions, RemoveVolumeOptions,
+};
+use std::collections::HashMap;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use crate::services::control::docker_control::image_reference;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerImage {
+    pub id: String,
+    pub repo_tags: Vec<String>,
+    pub size_bytes: u64,
+    /// Creation time (unix seconds)
+    pub created: i64,
+    /// Number of containers using this image, if known
+    pub containers: Option<u64>,
+    /// Untagged image no longer referenced by any tag
+    pub dangling: bool,
+}
+
+/// Progress update emitted while pulling an image
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImagePullProgress {
+    pub image: String,
+    pub layer_id: Option<String>,
+    pub status: String,
+    pub current_bytes: Option<u64>,
+    pub total_bytes: Option<u64>,
+}
+
+/// Container reference used by the volume/network inventory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerRef {
+    pub id: String,
+    pub name: String,
+    pub running: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerVolume {
+    pub name: String,
+    pub driver: String,
+    pub mountpoint: String,
+    pub created_at: Option<String>,
+    /// Containers (running or stopped) mounting this volume
+    pub containers: Vec<ContainerRef>,
+    /// Not mounted by any container
+    pub orphaned: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkAttachment {
+    pub container: ContainerRef,
+    pub ip_address: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerNetwork {
+    pub id: String,
+    pub name: String,
+    pub driver: String,
+    pub scope: Option<String>,
+    pub internal: bool,
+    pub subnets: Vec<String>,
+    pub containers: Vec<NetworkAttachment>,
+}
+
+/// Read/manage Docker resources that aren't services (images, ...)
+pub struct DockerInventory {
+    docker: Option<Docker>,
+}
+
+impl DockerInventory {
+    pub fn new() -> Self {
+        let docker = Docker::connect_with_local_defaults().ok();
+        Self { docker }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.docker.is_some()
+    }
+
+    /// List local images, largest first
+    pub async fn list_images(&self) -> Result<Vec<DockerImage>, Box<dyn std::error::Error + Send + Sync>> {
+        let docker = self.docker.as_ref().ok_or("Docker not available")?;
+
+        let summaries = docker.list_images(Some(ListImagesOptions::default())).await?;
+
+        let mut images: Vec<DockerImage> = summaries
+            .into_iter()
+            .map(|summary| {
+                let dangling = summary.repo_tags.is_empty()
+                    || summary.repo_tags.iter().all(|t| t == "<none>:<none>");
+
+                DockerImage {
+                    id: summary.id,
+                    repo_tags: summary.repo_tags,
+                    size_bytes: summary.size.max(0) as u64,
+                    created: summary.created,
+                    // -1 means "not calculated"
+                    containers: (summary.containers >= 0).then_some(summary.containers as u64),
+                    dangling,
+                }
+            })
+            .collect();
+
+        images.sort_by_key(|i| std::cmp::Reverse(i.size_bytes));
+        Ok(images)
+    }
+
+    /// Pull an image, reporting each progress message to `on_progress`.
+    /// Without a tag or digest, `:latest` is pulled rather than every tag.
+    pub async fn pull_image<F>(&self, image: &str, mut on_progress: F) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: FnMut(ImagePullProgress),
+    {
+        let docker = self.docker.as_ref().ok_or("Docker not available")?;
+        let image = &image_reference(image);
+
+        let options = CreateImageOptionsBuilder::new().from_image(image).build();
+        let mut stream = docker.create_image(Some(options), None, None);
+
+        while let Some(info) = stream.next().await {
+            let info = info?;
+            let detail = info.progress_detail.as_ref();
+            on_progress(ImagePullProgress {
+                image: image.to_string(),
+                layer_id: info.id.clone(),
+                status: info.status.clone().unwrap_or_default(),
+                current_bytes: detail.and_then(|d| d.current).map(|v| v.max(0) as u64),
+                total_bytes: detail.and_then(|d| d.total).map(|v| v.max(0) as u64),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Remove an image by ID or tag. Returns the deleted/untagged references.
+    pub async fn remove_image(&self, image: &str, force: bool) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let docker = self.docker.as_ref().ok_or("Docker not available")?;
+
+        let options = RemoveImageOptions {
+            force,
+            ..Default::default()
+        };
+        let removed = docker.remove_image(image, Some(options), None).await?;
+
+        Ok(removed
+            .into_iter()
+            .filter_map(|item| item.deleted.or(item.untagged))
+            .collect())
+    }
+
+    /// Remove a volume. Fails if a container still uses it.
+    pub async fn remove_volume(&self, name: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let docker = self.docker.as_ref().ok_or("Docker not available")?;
+        docker.remove_volume(name, None::<RemoveVolumeOptions>).await?;
+        Ok(())
+    }
+
+    /// List volumes together with the containers that mount them
+    pub async fn list_volumes(&self) -> Result<Vec<DockerVolume>, Box<dyn std::error::Error + Send + Sync>> {
+        let docker = self.docker.as_ref().ok_or("Docker not available")?;
+
+        let response = docker.list_volumes(Some(ListVolumesOptions::default())).await?;
+        let containers = self.list_all_containers(docker).await?;
+
+        let mut users: HashMap<String, Vec<ContainerRef>> = HashMap::new();
+        for container in &containers {
+            for mount in container.mounts.iter().flatten() {
+                if let Some(name) = &mount.name {
+                    users.entry(name.clone()).or_default().push(container_ref(container));
+                }
+            }
+        }
+
+        let mut volumes: Vec<DockerVolume> = response
+            .volumes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|volume| {
+                let containers = users.remove(&volume.name).unwrap_or_default();
+                DockerVolume {
+                    orphaned: containers.is_empty(),
+                    name: volume.name,
+                    driver: volume.driver,
+                    mountpoint: volume.mountpoint,
+                    created_at: volume.created_at.map(|d| d.to_string()),
+                    containers,
+                }
+            })
+            .collect();
+
+        volumes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(volumes)
+    }
+
+    /// List networks together with the containers attached to them
+    pub async fn list_networks(&self) -> Result<Vec<DockerNetwork>, Box<dyn std::error::Error + Send + Sync>> {
+        let docker = self.docker.as_ref().ok_or("Docker not available")?;
+
+        let networks = docker.list_networks(Some(ListNetworksOptions::default())).await?;
+        let containers = self.list_all_containers(docker).await?;
+
+        let mut attachments: HashMap<String, Vec<NetworkAttachment>> = HashMap::new();
+        for container in &containers {
+            let endpoints = container
+                .network_settings
+                .as_ref()
+                .and_then(|settings| settings.networks.as_ref());
+            for endpoint in endpoints.into_iter().flat_map(|e| e.values()) {
+                if let Some(network_id) = &endpoint.network_id {
+                    attachments.entry(network_id.clone()).or_default().push(NetworkAttachment {
+                        container: container_ref(container),
+                        ip_address: endpoint.ip_address.clone().filter(|ip| !ip.is_empty()),
+                    });
+                }
+            }
+        }
+
+        let mut result: Vec<DockerNetwork> = networks
+            .into_iter()
+            .map(|network| {
+                let id = network.id.unwrap_or_default();
+                let subnets = network
+                    .ipam
+                    .and_then(|ipam| ipam.config)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|config| config.subnet)
+                    .collect();
+
+                DockerNetwork {
+                    containers: attachments.remove(&id).unwrap_or_default(),
+                    id,
+                    name: network.name.unwrap_or_default(),
+                    driver: network.driver.unwrap_or_default(),
+                    scope: network.scope,
+                    internal: network.internal.unwrap_or(false),
+                    subnets,
+                }
+            })
+            .collect();
+
+        result.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(result)
+    }
+
+    async fn list_all_containers(&self, docker: &Docker) -> Result<Vec<ContainerSummary>, Box<dyn std::error::Error + Send + Sync>> {
+        let options = ListContainersOptions {
+            all: true,
+            ..Default::default()
+        };
+        Ok(docker.list_containers(Some(options)).await?)
+    }
+}
+
+fn container_ref(container: &ContainerSummary) -> ContainerRef {
+    ContainerRef {
+        id: container.id.clone().unwrap_or_default(),
+        name: container
+            .names
+            .as_ref()
+            .and_then(|names| names.first())
+            .map(|n| n.trim_start_matches('/').to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+        running: container.state == Some(ContainerSummaryStateEnum::RUNNING),
+    }
+}
+
+impl Default for DockerInventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
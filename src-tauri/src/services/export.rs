@@ -0,0 +1,512 @@
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::audit::AuditEntry;
+use crate::models::port::PortInfo;
+use crate::models::service::Service;
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::port::PortResolver;
+use crate::services::security::AuditLogger;
+use crate::services::security_scanner::{SecurityScanResult, SecurityScanner};
+use crate::services::storage::Storage;
+use crate::services::system_stats::{SystemMonitor, SystemStats};
+use crate::services::user_scope::UserScope;
+use crate::services::ServiceManager;
+
+const MAX_AUDIT_ENTRIES: usize = 10000;
+
+const MARKDOWN_TEMPLATE: &str = "# {{title}}\n\nErstellt am {{generated_at}}\n\n{{summary}}{{table}}";
+const HTML_TEMPLATE: &str = "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{{title}}</title>\n\
+<style>body{font-family:sans-serif}table{border-collapse:collapse}th,td{border:1px solid #ccc;padding:4px 8px;text-align:left}</style>\n\
+</head><body>\n<h1>{{title}}</h1>\n<p>Erstellt am {{generated_at}}</p>\n{{summary}}{{table}}</body></html>\n";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportKind {
+    Audit,
+    Security,
+    Inventory,
+    Metrics,
+}
+
+impl ExportKind {
+    fn name(&self) -> &'static str {
+        match self {
+            ExportKind::Audit => "audit",
+            ExportKind::Security => "security",
+            ExportKind::Inventory => "inventory",
+            ExportKind::Metrics => "metrics",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Markdown,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+/// Content of one export: a table for CSV, Markdown and HTML, and the
+/// complete records for JSON
+#[derive(Debug, Clone)]
+pub struct ExportDocument {
+    pub kind: ExportKind,
+    pub title: String,
+    pub generated_at: DateTime<Utc>,
+    /// Key figures shown above the table, e.g. `("Kritisch", "2")`
+    pub summary: Vec<(String, String)>,
+    pub columns: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+    pub data: serde_json::Value,
+}
+
+/// A written export file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportResult {
+    pub path: String,
+    pub bytes: usize,
+    /// User template the file was rendered with, `None` for the built-in one
+    pub template: Option<String>,
+}
+
+impl ExportDocument {
+    /// Gather the current data for `kind`
+    pub async fn collect(kind: ExportKind) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(match kind {
+            ExportKind::Audit => {
+                let entries = AuditLogger::new()
+                    .get_entries(MAX_AUDIT_ENTRIES)
+                    .map_err(|e| format!("Failed to read audit log: {}", e))?;
+                Self::audit(entries)
+            }
+            ExportKind::Security => {
+                let services = ServiceManager::new().discover_all().await;
+                Self::security(&SecurityScanner::new().scan(&services))
+            }
+            ExportKind::Inventory => {
+                let services = ServiceManager::new().discover_all().await;
+                let mut ports = if MockDiscovery::is_enabled() {
+                    MockDiscovery::new().port_usage()
+                } else {
+                    tokio::task::spawn_blocking(|| PortResolver::new().get_port_usage()).await?
+                };
+                UserScope::retain_ports(&mut ports);
+                Self::inventory(&services, &ports)
+            }
+            ExportKind::Metrics => {
+                let services = ServiceManager::new().discover_all().await;
+                let stats = if MockDiscovery::is_enabled() {
+                    MockDiscovery::new().system_stats(Utc::now().timestamp() as u64)
+                } else {
+                    tokio::task::spawn_blocking(|| SystemMonitor::new().get_stats()).await?
+                };
+                Self::metrics(&services, &stats)
+            }
+        })
+    }
+
+    pub fn audit(entries: Vec<AuditEntry>) -> Self {
+        let failed = entries.iter().filter(|e| !e.success).count();
+        let rows = entries
+            .iter()
+            .map(|entry| {
+                vec![
+                    entry.timestamp.to_rfc3339(),
+                    serde_name(&entry.event_type),
+                    entry.user.clone(),
+                    entry.operation.clone(),
+                    entry.service_id.clone().unwrap_or_default(),
+                    entry.success.to_string(),
+                    entry.error_message.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+
+        Self {
+            kind: ExportKind::Audit,
+            title: "Audit-Log".to_string(),
+            generated_at: Utc::now(),
+            summary: vec![
+                ("Einträge".to_string(), entries.len().to_string()),
+                ("Fehlgeschlagen".to_string(), failed.to_string()),
+            ],
+            columns: vec!["timestamp", "event_type", "user", "operation", "service_id", "success", "error_message"],
+            rows,
+            data: serde_json::to_value(&entries).unwrap_or_default(),
+        }
+    }
+
+    pub fn security(result: &SecurityScanResult) -> Self {
+        let rows = result
+            .issues
+            .iter()
+            .map(|issue| {
+                vec![
+                    serde_name(&issue.severity),
+                    serde_name(&issue.category),
+                    issue.service_name.clone().unwrap_or_default(),
+                    issue.port.map(|p| p.to_string()).unwrap_or_default(),
+                    issue.title.clone(),
+                    issue.description.clone(),
+                    issue.recommendation.clone(),
+                ]
+            })
+            .collect();
+
+        Self {
+            kind: ExportKind::Security,
+            title: "Sicherheitsbericht".to_string(),
+            generated_at: Utc::now(),
+            summary: vec![
+                ("Geprüfte Dienste".to_string(), result.services_scanned.to_string()),
+                ("Kritisch".to_string(), result.critical_count.to_string()),
+                ("Hoch".to_string(), result.high_count.to_string()),
+                ("Mittel".to_string(), result.medium_count.to_string()),
+                ("Niedrig".to_string(), result.low_count.to_string()),
+            ],
+            columns: vec!["severity", "category", "service", "port", "title", "description", "recommendation"],
+            rows,
+            data: serde_json::to_value(result).unwrap_or_default(),
+        }
+    }
+
+    /// Services followed by listening sockets that belong to none of them
+    pub fn inventory(services: &[Service], ports: &[PortInfo]) -> Self {
+        let mut rows: Vec<Vec<String>> = services
+            .iter()
+            .map(|service| {
+                vec![
+                    "service".to_string(),
+                    service.name.clone(),
+                    serde_name(&service.service_type),
+                    serde_name(&service.status),
+                    join_ports(&service.ports),
+                    service.pid.map(|p| p.to_string()).unwrap_or_default(),
+                    service.user.clone().unwrap_or_default(),
+                    service.path.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        let unowned: Vec<&PortInfo> = ports
+            .iter()
+            .filter(|port| !services.iter().any(|s| s.ports.contains(&port.port)))
+            .collect();
+        rows.extend(unowned.iter().map(|port| {
+            vec![
+                "port".to_string(),
+                port.process_name.clone().unwrap_or_default(),
+                serde_name(&port.protocol),
+                serde_name(&port.status),
+                format!("{}:{}", port.bind_address, port.port),
+                port.pid.map(|p| p.to_string()).unwrap_or_default(),
+                port.user.clone().unwrap_or_default(),
+                String::new(),
+            ]
+        }));
+
+        Self {
+            kind: ExportKind::Inventory,
+            title: "Inventar".to_string(),
+            generated_at: Utc::now(),
+            summary: vec![
+                ("Dienste".to_string(), services.len().to_string()),
+                ("Offene Ports".to_string(), ports.len().to_string()),
+            ],
+            columns: vec!["kind", "name", "type", "status", "ports", "pid", "user", "path"],
+            rows,
+            data: serde_json::json!({ "services": services, "ports": ports }),
+        }
+    }
+
+    /// System totals followed by one row per running service
+    pub fn metrics(services: &[Service], stats: &SystemStats) -> Self {
+        let mut rows = vec![vec![
+            "system".to_string(),
+            format!("{:.1}", stats.cpu.usage_percent),
+            stats.memory.used_bytes.to_string(),
+            format!("{:.1}", stats.memory.usage_percent),
+        ]];
+        rows.extend(stats.gpus.iter().map(|gpu| {
+            vec![
+                format!("gpu: {}", gpu.name),
+                gpu.usage_percent.map(|u| format!("{:.1}", u)).unwrap_or_default(),
+                gpu.memory_used_bytes.map(|m| m.to_string()).unwrap_or_default(),
+                match (gpu.memory_used_bytes, gpu.memory_total_bytes) {
+                    (Some(used), Some(total)) if total > 0 => format!("{:.1}", used as f64 / total as f64 * 100.0),
+                    _ => String::new(),
+                },
+            ]
+        }));
+        let mut running: Vec<&Service> = services.iter().filter(|s| s.cpu_usage.is_some() || s.memory_bytes.is_some()).collect();
+        running.sort_by(|a, b| b.cpu_usage.unwrap_or(0.0).total_cmp(&a.cpu_usage.unwrap_or(0.0)));
+        rows.extend(running.iter().map(|service| {
+            vec![
+                service.name.clone(),
+                service.cpu_usage.map(|c| format!("{:.1}", c)).unwrap_or_default(),
+                service.memory_bytes.map(|m| m.to_string()).unwrap_or_default(),
+                service.memory_percent.map(|m| format!("{:.1}", m)).unwrap_or_default(),
+            ]
+        }));
+
+        Self {
+            kind: ExportKind::Metrics,
+            title: "Ressourcen".to_string(),
+            generated_at: Utc::now(),
+            summary: vec![
+                ("CPU".to_string(), format!("{:.1} %", stats.cpu.usage_percent)),
+                ("Speicher".to_string(), format!("{:.1} %", stats.memory.usage_percent)),
+                ("Kerne".to_string(), stats.cpu.core_count.to_string()),
+            ],
+            columns: vec!["source", "cpu_percent", "memory_bytes", "memory_percent"],
+            rows,
+            data: serde_json::json!({ "system": stats, "services": running }),
+        }
+    }
+}
+
+/// Renders export documents and writes them to disk. Markdown and HTML go
+/// through a template; users can override the built-in ones by placing
+/// `<kind>.md`/`<kind>.html` or `default.md`/`default.html` in the template
+/// directory. Templates see `{{title}}`, `{{kind}}`, `{{generated_at}}`,
+/// `{{count}}`, `{{summary}}` and `{{table}}`.
+pub struct Exporter {
+    template_dir: PathBuf,
+}
+
+impl Exporter {
+    pub fn new() -> Self {
+        Self {
+            template_dir: Storage::config_dir().join("export_templates"),
+        }
+    }
+
+    pub fn with_template_dir(mut self, dir: PathBuf) -> Self {
+        self.template_dir = dir;
+        self
+    }
+
+    pub fn template_dir(&self) -> &Path {
+        &self.template_dir
+    }
+
+    pub fn render(&self, document: &ExportDocument, format: ExportFormat) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.render_with_template(document, format)?.0)
+    }
+
+    /// Render `document` and write it to `path`, see `write_export`
+    pub fn write(
+        &self,
+        document: &ExportDocument,
+        format: ExportFormat,
+        path: Option<&Path>,
+    ) -> Result<ExportResult, Box<dyn std::error::Error + Send + Sync>> {
+        let (content, template) = self.render_with_template(document, format)?;
+        let path = write_export(document.kind.name(), document.generated_at, format.extension(), content.as_bytes(), path)?;
+        Ok(ExportResult {
+            path: path.to_string_lossy().to_string(),
+            bytes: content.len(),
+            template: template.map(|t| t.to_string_lossy().to_string()),
+        })
+    }
+
+    fn render_with_template(
+        &self,
+        document: &ExportDocument,
+        format: ExportFormat,
+    ) -> Result<(String, Option<PathBuf>), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(match format {
+            ExportFormat::Json => (serde_json::to_string_pretty(&document.data)?, None),
+            ExportFormat::Csv => (render_csv(document), None),
+            ExportFormat::Markdown | ExportFormat::Html => {
+                let (template, path) = match self.user_template(document.kind, format) {
+                    Some((template, path)) => (template, Some(path)),
+                    None if format == ExportFormat::Html => (HTML_TEMPLATE.to_string(), None),
+                    None => (MARKDOWN_TEMPLATE.to_string(), None),
+                };
+                (fill_template(&template, document, format), path)
+            }
+        })
+    }
+
+    fn user_template(&self, kind: ExportKind, format: ExportFormat) -> Option<(String, PathBuf)> {
+        [kind.name(), "default"].iter().find_map(|name| {
+            let path = self.template_dir.join(format!("{}.{}", name, format.extension()));
+            std::fs::read_to_string(&path).ok().map(|template| (template, path))
+        })
+    }
+}
+
+impl Default for Exporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Write `content` to `path`, or to `<name>-<timestamp>.<extension>` in the
+/// exports directory. A directory as `path` receives the default file name;
+/// a missing extension is added.
+pub(crate) fn write_export(
+    name: &str,
+    generated_at: DateTime<Utc>,
+    extension: &str,
+    content: &[u8],
+    path: Option<&Path>,
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    let file_name = format!("{}-{}.{}", name, generated_at.with_timezone(&Local).format("%Y%m%d-%H%M%S"), extension);
+    let path = match path {
+        Some(path) if path.is_dir() => path.join(file_name),
+        Some(path) if path.extension().is_none() => path.with_extension(extension),
+        Some(path) => path.to_path_buf(),
+        None => Storage::data_dir().join("exports").join(file_name),
+    };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    // Write next to the target first so a failed export never leaves a truncated file
+    let temp = path.with_extension(format!("{}.tmp", extension));
+    std::fs::write(&temp, content).map_err(|e| format!("Failed to write {}: {}", temp.display(), e))?;
+    if let Err(e) = std::fs::rename(&temp, &path) {
+        let _ = std::fs::remove_file(&temp);
+        return Err(format!("Failed to write {}: {}", path.display(), e).into());
+    }
+    Ok(path)
+}
+
+fn fill_template(template: &str, document: &ExportDocument, format: ExportFormat) -> String {
+    let html = format == ExportFormat::Html;
+    let text = |value: &str| if html { escape_html(value) } else { value.to_string() };
+    let (summary, table) = if html {
+        (summary_html(document), table_html(document))
+    } else {
+        (summary_markdown(document), table_markdown(document))
+    };
+
+    // Generated parts last, so placeholders inside data are not expanded
+    template
+        .replace("{{title}}", &text(&document.title))
+        .replace("{{kind}}", document.kind.name())
+        .replace(
+            "{{generated_at}}",
+            &document.generated_at.with_timezone(&Local).format("%d.%m.%Y %H:%M").to_string(),
+        )
+        .replace("{{count}}", &document.rows.len().to_string())
+        .replacen("{{summary}}", &summary, 1)
+        .replacen("{{table}}", &table, 1)
+}
+
+fn summary_markdown(document: &ExportDocument) -> String {
+    if document.summary.is_empty() {
+        return String::new();
+    }
+    let mut out: String = document
+        .summary
+        .iter()
+        .map(|(label, value)| format!("- **{}:** {}\n", label, value))
+        .collect();
+    out.push('\n');
+    out
+}
+
+fn table_markdown(document: &ExportDocument) -> String {
+    if document.rows.is_empty() {
+        return "_Keine Einträge_\n".to_string();
+    }
+    let cell = |value: &str| value.replace('|', "\\|").replace(['\r', '\n'], " ");
+    let mut out = format!("| {} |\n", document.columns.join(" | "));
+    out.push_str(&format!("|{}\n", " --- |".repeat(document.columns.len())));
+    for row in &document.rows {
+        let cells: Vec<String> = row.iter().map(|v| cell(v)).collect();
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    out
+}
+
+fn summary_html(document: &ExportDocument) -> String {
+    if document.summary.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from("<ul>\n");
+    for (label, value) in &document.summary {
+        out.push_str(&format!("<li><strong>{}:</strong> {}</li>\n", escape_html(label), escape_html(value)));
+    }
+    out.push_str("</ul>\n");
+    out
+}
+
+fn table_html(document: &ExportDocument) -> String {
+    if document.rows.is_empty() {
+        return "<p><em>Keine Einträge</em></p>\n".to_string();
+    }
+    let mut out = String::from("<table>\n<tr>");
+    for column in &document.columns {
+        out.push_str(&format!("<th>{}</th>", escape_html(column)));
+    }
+    out.push_str("</tr>\n");
+    for row in &document.rows {
+        out.push_str("<tr>");
+        for value in row {
+            out.push_str(&format!("<td>{}</td>", escape_html(value)));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+    out
+}
+
+fn render_csv(document: &ExportDocument) -> String {
+    let mut out = document.columns.iter().map(|c| csv_field(c)).collect::<Vec<_>>().join(",");
+    out.push('\n');
+    for row in &document.rows {
+        out.push_str(&row.iter().map(|v| csv_field(v)).collect::<Vec<_>>().join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote fields per RFC 4180 and defuse values a spreadsheet would run as a formula
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) && value.parse::<f64>().is_err() {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The serde name of a unit enum variant, e.g. `service_start`
+pub(crate) fn serde_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+fn join_ports(ports: &[u16]) -> String {
+    ports.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,290 @@
+// Host firewall status: ufw/firewalld/nftables/iptables, ALF/pf, Windows Defender Firewall
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::port::PortInfo;
+use crate::services::cmd;
+use crate::services::security_scanner::{SecurityCategory, SecurityIssue, SecuritySeverity};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FirewallState {
+    Enabled,
+    Disabled,
+    /// No firewall found or its state could not be read
+    Unknown,
+}
+
+/// One firewall implementation found on the system
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirewallBackend {
+    /// `ufw`, `firewalld`, `nftables`, `iptables`, `alf`, `pf` or `windows`
+    pub name: String,
+    /// `None` if the state needs privileges we don't have
+    pub enabled: Option<bool>,
+    /// e.g. the Windows profiles that are switched off
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirewallStatus {
+    /// Enabled if any backend filters incoming traffic
+    pub state: FirewallState,
+    pub backends: Vec<FirewallBackend>,
+    /// Why a backend's state is missing, e.g. no root privileges
+    pub warnings: Vec<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Detects whether a host firewall filters incoming connections. Several
+/// backends can coexist (ufw writes iptables/nftables rules), so the
+/// firewall counts as enabled as soon as one of them is.
+pub struct FirewallChecker;
+
+impl FirewallChecker {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Blocking: runs the platform's firewall tools
+    pub fn check(&self) -> FirewallStatus {
+        let mut warnings = Vec::new();
+        let backends = backends(&mut warnings);
+        let state = if backends.iter().any(|b| b.enabled == Some(true)) {
+            FirewallState::Enabled
+        } else if !backends.is_empty() && backends.iter().all(|b| b.enabled == Some(false)) {
+            FirewallState::Disabled
+        } else {
+            FirewallState::Unknown
+        };
+        FirewallStatus {
+            state,
+            backends,
+            warnings,
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// A high-severity finding if the firewall is off while services listen
+    /// on non-loopback addresses
+    pub fn findings(status: &FirewallStatus, port_usage: &[PortInfo]) -> Vec<SecurityIssue> {
+        if status.state != FirewallState::Disabled {
+            return Vec::new();
+        }
+        let mut public: Vec<u16> = port_usage.iter().filter(|p| !p.is_loopback()).map(|p| p.port).collect();
+        public.sort_unstable();
+        public.dedup();
+        if public.is_empty() {
+            return Vec::new();
+        }
+
+        let names: Vec<&str> = status.backends.iter().map(|b| b.name.as_str()).collect();
+        let shown: Vec<String> = public.iter().take(20).map(|p| p.to_string()).collect();
+        vec![SecurityIssue {
+            id: "firewall-disabled".to_string(),
+            service_id: None,
+            service_name: None,
+            category: SecurityCategory::PublicExposure,
+            severity: SecuritySeverity::High,
+            title: format!("Firewall ist deaktiviert, {} Ports sind öffentlich gebunden", public.len()),
+            description: "Ohne Firewall ist jeder Dienst, der nicht nur auf localhost lauscht, aus dem Netzwerk erreichbar".to_string(),
+            recommendation: recommendation().to_string(),
+            port: None,
+            details: Some(format!(
+                "Geprüft: {}. Ports: {}{}",
+                names.join(", "),
+                shown.join(", "),
+                if public.len() > shown.len() { ", ..." } else { "" }
+            )),
+        }]
+    }
+}
+
+impl Default for FirewallChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(unix)]
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = cmd::command(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Run as is, then via a passwordless sudo rule if there is one
+#[cfg(unix)]
+fn run_privileged(program: &str, args: &[&str]) -> Option<String> {
+    run(program, args).or_else(|| {
+        let mut sudo_args = vec!["-n", program];
+        sudo_args.extend_from_slice(args);
+        run("sudo", &sudo_args)
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn recommendation() -> &'static str {
+    "Aktiviere eine Firewall, z.B. mit 'sudo ufw enable', und erlaube nur benötigte Ports"
+}
+
+#[cfg(target_os = "macos")]
+fn recommendation() -> &'static str {
+    "Aktiviere die Firewall unter Systemeinstellungen > Netzwerk > Firewall"
+}
+
+#[cfg(target_os = "windows")]
+fn recommendation() -> &'static str {
+    "Aktiviere die Windows Defender Firewall für alle Netzwerkprofile"
+}
+
+#[cfg(target_os = "linux")]
+fn backends(warnings: &mut Vec<String>) -> Vec<FirewallBackend> {
+    let mut backends = Vec::new();
+
+    // `ufw status` needs root, its config file does not
+    if let Ok(conf) = std::fs::read_to_string("/etc/ufw/ufw.conf") {
+        let enabled = conf
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("ENABLED="))
+            .map(|value| value.trim_matches(['"', '\'']).eq_ignore_ascii_case("yes"));
+        backends.push(FirewallBackend { name: "ufw".to_string(), enabled, details: None });
+    }
+
+    if cmd::find_in_path("firewall-cmd").is_some() {
+        let active = run("systemctl", &["is-active", "firewalld"]).is_some();
+        backends.push(FirewallBackend { name: "firewalld".to_string(), enabled: Some(active), details: None });
+    }
+
+    let mut needs_root = Vec::new();
+    if cmd::find_in_path("nft").is_some() {
+        let enabled = run_privileged("nft", &["list", "ruleset"]).map(|ruleset| nft_filters_input(&ruleset));
+        if enabled.is_none() {
+            needs_root.push("nft");
+        }
+        backends.push(FirewallBackend { name: "nftables".to_string(), enabled, details: None });
+    }
+    if cmd::find_in_path("iptables").is_some() {
+        let enabled = run_privileged("iptables", &["-S", "INPUT"]).map(|rules| iptables_filters_input(&rules));
+        if enabled.is_none() {
+            needs_root.push("iptables");
+        }
+        backends.push(FirewallBackend { name: "iptables".to_string(), enabled, details: None });
+    }
+    if !needs_root.is_empty() && !backends.iter().any(|b| b.enabled == Some(true)) {
+        warnings.push(format!(
+            "{} braucht Root-Rechte, z.B. über eine sudoers-Regel; die Regeln konnten nicht gelesen werden",
+            needs_root.join(" und ")
+        ));
+    }
+    if backends.is_empty() {
+        warnings.push("Keine Firewall gefunden (ufw, firewalld, nftables, iptables)".to_string());
+    }
+    backends
+}
+
+/// An input hook that drops or rejects anything
+#[cfg(target_os = "linux")]
+fn nft_filters_input(ruleset: &str) -> bool {
+    let mut in_input_chain = false;
+    for line in ruleset.lines().map(str::trim) {
+        if line.starts_with("chain ") {
+            in_input_chain = false;
+        } else if line.contains("hook input") {
+            in_input_chain = true;
+            if line.contains("policy drop") {
+                return true;
+            }
+        } else if in_input_chain && (line.contains(" drop") || line.contains(" reject") || line == "drop" || line == "reject") {
+            return true;
+        }
+    }
+    false
+}
+
+/// A non-ACCEPT policy, or rules that drop, reject or jump to another chain
+/// (ufw and firewalld hook in this way)
+#[cfg(target_os = "linux")]
+fn iptables_filters_input(rules: &str) -> bool {
+    rules.lines().any(|line| {
+        if let Some(policy) = line.strip_prefix("-P INPUT ") {
+            return policy.trim() != "ACCEPT";
+        }
+        line.starts_with("-A INPUT")
+            && line
+                .split_once(" -j ")
+                .is_some_and(|(_, target)| !matches!(target.split_whitespace().next(), Some("ACCEPT" | "RETURN" | "LOG")))
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn backends(warnings: &mut Vec<String>) -> Vec<FirewallBackend> {
+    let mut backends = Vec::new();
+
+    // `Firewall is enabled. (State = 1)`; state 2 blocks all incoming connections
+    if let Some(output) = run("/usr/libexec/ApplicationFirewall/socketfilterfw", &["--getglobalstate"]) {
+        let enabled = !output.contains("State = 0") && !output.contains("disabled");
+        let stealth = run("/usr/libexec/ApplicationFirewall/socketfilterfw", &["--getstealthmode"])
+            .is_some_and(|o| o.contains("enabled") || o.contains("on"));
+        backends.push(FirewallBackend {
+            name: "alf".to_string(),
+            enabled: Some(enabled),
+            details: stealth.then(|| "Tarnmodus aktiv".to_string()),
+        });
+    }
+
+    // `Status: Enabled for 0 days 01:02:03`; macOS enables pf without
+    // blocking rules for some features, so only a disabled pf is conclusive
+    match run_privileged("pfctl", &["-s", "info"]) {
+        Some(info) => {
+            let enabled = info.lines().any(|line| line.starts_with("Status: Enabled"));
+            let rules = enabled
+                .then(|| run_privileged("pfctl", &["-s", "rules"]))
+                .flatten()
+                .is_some_and(|rules| rules.lines().any(|line| line.starts_with("block")));
+            backends.push(FirewallBackend {
+                name: "pf".to_string(),
+                enabled: Some(enabled && rules),
+                details: (enabled && !rules).then(|| "Aktiv, aber ohne block-Regeln".to_string()),
+            });
+        }
+        None => warnings.push("pfctl braucht Root-Rechte; der Status von pf ist unbekannt".to_string()),
+    }
+    backends
+}
+
+#[cfg(target_os = "windows")]
+fn backends(warnings: &mut Vec<String>) -> Vec<FirewallBackend> {
+    // netsh output is localized, the cmdlet's is not
+    let script = "Get-NetFirewallProfile | Select-Object Name, Enabled | ConvertTo-Json";
+    let output = cmd::command("powershell").args(["-NoProfile", "-Command", script]).output();
+    let Some(json) = output.ok().and_then(|o| serde_json::from_slice::<serde_json::Value>(&o.stdout).ok()) else {
+        warnings.push("Der Status der Windows-Firewall konnte nicht gelesen werden".to_string());
+        return Vec::new();
+    };
+
+    // A single profile is serialized as an object instead of an array
+    let profiles = match json {
+        serde_json::Value::Array(profiles) => profiles,
+        profile => vec![profile],
+    };
+    let mut disabled = Vec::new();
+    let mut enabled_any = false;
+    for profile in &profiles {
+        let name = profile["Name"].as_str().unwrap_or("?");
+        // Enabled is a GpoBoolean, serialized as 1/0 or true/false
+        let enabled = profile["Enabled"].as_bool().or_else(|| profile["Enabled"].as_i64().map(|v| v == 1));
+        match enabled {
+            Some(true) => enabled_any = true,
+            Some(false) => disabled.push(name.to_string()),
+            None => {}
+        }
+    }
+    vec![FirewallBackend {
+        name: "windows".to_string(),
+        enabled: (enabled_any || !disabled.is_empty()).then_some(enabled_any),
+        details: (!disabled.is_empty()).then(|| format!("Deaktivierte Profile: {}", disabled.join(", "))),
+    }]
+}
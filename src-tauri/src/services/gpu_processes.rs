@@ -0,0 +1,136 @@
+// Which processes use the GPU, from nvidia-smi pmon and DRM fdinfo
+
+use std::collections::HashMap;
+#[cfg(target_os = "linux")]
+use std::sync::{Mutex, OnceLock};
+#[cfg(target_os = "linux")]
+use std::time::Instant;
+use crate::models::service::{GpuProcessUsage, Service};
+use crate::parsers::gpu;
+use crate::services::cmd;
+
+/// Engine busy times of the previous fdinfo scan per (PID, client, engine)
+#[cfg(target_os = "linux")]
+type EngineTimes = HashMap<(u32, String, String), u64>;
+
+#[cfg(target_os = "linux")]
+static PREVIOUS_SCAN: OnceLock<Mutex<Option<(Instant, EngineTimes)>>> = OnceLock::new();
+
+/// GPU memory and utilization per PID. NVIDIA processes come from
+/// `nvidia-smi pmon`; on Linux, every other DRM driver (amdgpu, i915, xe,
+/// msm, ...) reports its clients in `/proc/<pid>/fdinfo`. Only processes
+/// whose fdinfo is readable show up, i.e. those of the current user unless
+/// running as root.
+pub struct GpuProcesses;
+
+impl GpuProcesses {
+    pub fn snapshot() -> HashMap<u32, GpuProcessUsage> {
+        let mut usage = HashMap::new();
+        #[cfg(target_os = "linux")]
+        usage.extend(drm_clients());
+        // nvidia-smi knows the proprietary driver's processes better than fdinfo
+        usage.extend(nvidia_processes());
+        usage
+    }
+
+    /// Set `gpu_usage` on every service whose process uses the GPU
+    pub fn fill(services: &mut [Service]) {
+        let usage = Self::snapshot();
+        for service in services {
+            service.gpu_usage = service.pid.and_then(|pid| usage.get(&pid).cloned());
+        }
+    }
+}
+
+fn nvidia_processes() -> HashMap<u32, GpuProcessUsage> {
+    let output = cmd::command("nvidia-smi").args(["pmon", "-c", "1", "-s", "um"]).output();
+    let Ok(output) = output else {
+        return HashMap::new();
+    };
+    if !output.status.success() {
+        return HashMap::new();
+    }
+
+    let mut usage: HashMap<u32, GpuProcessUsage> = HashMap::new();
+    // One row per GPU the process uses
+    for record in gpu::parse_nvidia_pmon(&String::from_utf8_lossy(&output.stdout)).items {
+        let entry = usage.entry(record.pid).or_default();
+        entry.memory_bytes = add(entry.memory_bytes, record.memory_bytes);
+        entry.utilization_percent = add(entry.utilization_percent, record.sm_percent);
+    }
+    usage
+}
+
+/// Utilization is the busiest engine's share of the time since the previous
+/// scan, so the first scan only reports memory
+#[cfg(target_os = "linux")]
+fn drm_clients() -> HashMap<u32, GpuProcessUsage> {
+    let now = Instant::now();
+    let mut times: EngineTimes = HashMap::new();
+    let mut memory: HashMap<(u32, String), Option<u64>> = HashMap::new();
+
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return HashMap::new();
+    };
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            // Only render nodes and cards carry DRM stats; skip the rest cheaply
+            let is_drm = std::fs::read_link(fd.path()).is_ok_and(|target| target.starts_with("/dev/dri"));
+            if !is_drm {
+                continue;
+            }
+            let fdinfo = entry.path().join("fdinfo").join(fd.file_name());
+            let Some(client) = std::fs::read_to_string(fdinfo).ok().and_then(|c| gpu::parse_drm_fdinfo(&c)) else {
+                continue;
+            };
+            // Several descriptors can share one client
+            memory.insert((pid, client.client_id.clone()), client.memory_bytes);
+            for (engine, ns) in client.engines_ns {
+                times.insert((pid, client.client_id.clone(), engine), ns);
+            }
+        }
+    }
+
+    let mut previous = PREVIOUS_SCAN
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut busiest: HashMap<(u32, String), f32> = HashMap::new();
+    if let Some((taken_at, before)) = previous.as_ref() {
+        let elapsed_ns = now.duration_since(*taken_at).as_nanos() as f64;
+        for (key, ns) in &times {
+            let Some(previous_ns) = before.get(key) else {
+                continue;
+            };
+            let percent = (ns.saturating_sub(*previous_ns) as f64 / elapsed_ns.max(1.0) * 100.0).min(100.0) as f32;
+            let client = busiest.entry((key.0, key.1.clone())).or_default();
+            *client = client.max(percent);
+        }
+    }
+
+    let mut usage: HashMap<u32, GpuProcessUsage> = HashMap::new();
+    for ((pid, client_id), bytes) in memory {
+        let entry = usage.entry(pid).or_default();
+        entry.memory_bytes = add(entry.memory_bytes, bytes);
+        entry.utilization_percent = add(entry.utilization_percent, busiest.get(&(pid, client_id)).copied());
+    }
+    for entry in usage.values_mut() {
+        entry.utilization_percent = entry.utilization_percent.map(|p| p.min(100.0));
+    }
+
+    *previous = Some((now, times));
+    usage
+}
+
+fn add<T: std::ops::Add<Output = T>>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, b) => a.or(b),
+    }
+}
@@ -0,0 +1,363 @@
+// SHA-256 reputation of listening executables via MalwareBazaar or VirusTotal
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use crate::models::config::{HashReputationConfig, ReputationProvider};
+use crate::models::port::PortInfo;
+use crate::services::security_scanner::{SecurityCategory, SecurityIssue, SecuritySeverity};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Verdicts are reused for a day; a hash only turns bad when a vendor adds it
+const VERDICT_TTL_HOURS: i64 = 24;
+/// The free VirusTotal API allows 4 requests per minute
+const VIRUSTOTAL_LOOKUPS_PER_SCAN: usize = 4;
+const MALWAREBAZAAR_LOOKUPS_PER_SCAN: usize = 32;
+
+static SETTINGS: OnceLock<Mutex<HashReputationConfig>> = OnceLock::new();
+static VERDICTS: OnceLock<Mutex<HashMap<String, HashVerdict>>> = OnceLock::new();
+/// Hashes by path and modification time; Electron apps are hundreds of MB
+static HASHES: OnceLock<Mutex<HashMap<FileKey, String>>> = OnceLock::new();
+
+type FileKey = (PathBuf, Option<SystemTime>);
+
+fn settings() -> &'static Mutex<HashReputationConfig> {
+    SETTINGS.get_or_init(|| Mutex::new(HashReputationConfig::default()))
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReputationStatus {
+    Malicious,
+    /// Flagged by a few engines only, often a false positive
+    Suspicious,
+    Clean,
+    /// The provider has never seen the hash
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashVerdict {
+    pub sha256: String,
+    pub provider: ReputationProvider,
+    pub status: ReputationStatus,
+    /// Engines that flagged the file, VirusTotal only
+    pub detections: Option<u32>,
+    pub engines: Option<u32>,
+    /// Malware family or threat label
+    pub label: Option<String>,
+    pub link: String,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Looks up the SHA-256 of executables behind listening ports with the
+/// configured provider. Only the hash is sent, and only when enabled with
+/// an API key. Verdicts are cached, and each scan looks up a bounded number
+/// of new hashes to stay inside the free quotas.
+pub struct HashReputation;
+
+impl HashReputation {
+    pub fn configure(config: &HashReputationConfig) {
+        *settings().lock().unwrap_or_else(|e| e.into_inner()) = config.clone();
+    }
+
+    /// Enabled with an API key
+    pub fn is_enabled() -> bool {
+        let config = settings().lock().unwrap_or_else(|e| e.into_inner());
+        config.enabled && config.api_key.as_deref().is_some_and(|key| !key.trim().is_empty())
+    }
+
+    /// Verdict for one executable, `None` if disabled, not an executable
+    /// file or the lookup failed
+    pub async fn check_file(path: &str) -> Option<HashVerdict> {
+        if !Self::is_enabled() {
+            return None;
+        }
+        let path = PathBuf::from(path);
+        let sha256 = tokio::task::spawn_blocking(move || is_executable(&path).then(|| sha256_cached(&path)).flatten())
+            .await
+            .ok()
+            .flatten()?;
+        match cached_verdict(&sha256) {
+            Some(verdict) => Some(verdict),
+            None => lookup(&sha256).await.ok(),
+        }
+    }
+
+    /// One finding per listening executable a provider knows as malicious
+    /// or suspicious
+    pub async fn findings(port_usage: Vec<PortInfo>) -> Vec<SecurityIssue> {
+        if !Self::is_enabled() {
+            return Vec::new();
+        }
+        let executables = tokio::task::spawn_blocking(move || listening_executables(&port_usage))
+            .await
+            .unwrap_or_default();
+
+        let provider = settings().lock().unwrap_or_else(|e| e.into_inner()).provider;
+        let mut budget = match provider {
+            ReputationProvider::VirusTotal => VIRUSTOTAL_LOOKUPS_PER_SCAN,
+            ReputationProvider::MalwareBazaar => MALWAREBAZAAR_LOOKUPS_PER_SCAN,
+        };
+        let mut issues = Vec::new();
+        for executable in executables {
+            let verdict = match cached_verdict(&executable.sha256) {
+                Some(verdict) => verdict,
+                None if budget > 0 => {
+                    budget -= 1;
+                    // A bad key or exhausted quota fails every further lookup too
+                    match lookup(&executable.sha256).await {
+                        Ok(verdict) => verdict,
+                        Err(_) => break,
+                    }
+                }
+                None => continue,
+            };
+            if let Some(issue) = finding(&executable, &verdict) {
+                issues.push(issue);
+            }
+        }
+        issues
+    }
+}
+
+/// Lowercase hex SHA-256 of a file
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn sha256_cached(path: &Path) -> Option<String> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let key = (path.to_path_buf(), modified);
+    let hashes = HASHES.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(sha256) = hashes.lock().unwrap_or_else(|e| e.into_inner()).get(&key) {
+        return Some(sha256.clone());
+    }
+    let sha256 = sha256_file(path).ok()?;
+    hashes.lock().unwrap_or_else(|e| e.into_inner()).insert(key, sha256.clone());
+    Some(sha256)
+}
+
+/// Service paths can also point at a plist, a unit file or an app bundle
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
+
+struct ListeningExecutable {
+    path: String,
+    sha256: String,
+    name: String,
+    ports: Vec<u16>,
+}
+
+fn listening_executables(port_usage: &[PortInfo]) -> Vec<ListeningExecutable> {
+    let pids: Vec<Pid> = port_usage.iter().filter_map(|p| p.pid).map(Pid::from_u32).collect();
+    if pids.is_empty() {
+        return Vec::new();
+    }
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&pids),
+        true,
+        ProcessRefreshKind::nothing().with_exe(UpdateKind::Always),
+    );
+
+    let mut executables: Vec<ListeningExecutable> = Vec::new();
+    for port in port_usage {
+        let Some(exe) = port.pid.and_then(|pid| system.process(Pid::from_u32(pid))).and_then(|p| p.exe()) else {
+            continue;
+        };
+        let path = exe.to_string_lossy().to_string();
+        if let Some(existing) = executables.iter_mut().find(|e| e.path == path) {
+            if !existing.ports.contains(&port.port) {
+                existing.ports.push(port.port);
+            }
+            continue;
+        }
+        let Some(sha256) = sha256_cached(exe) else {
+            continue;
+        };
+        executables.push(ListeningExecutable {
+            name: port
+                .process_name
+                .clone()
+                .unwrap_or_else(|| exe.file_name().unwrap_or_default().to_string_lossy().to_string()),
+            path,
+            sha256,
+            ports: vec![port.port],
+        });
+    }
+    executables
+}
+
+fn cached_verdict(sha256: &str) -> Option<HashVerdict> {
+    let verdicts = VERDICTS.get_or_init(|| Mutex::new(HashMap::new()));
+    let verdicts = verdicts.lock().unwrap_or_else(|e| e.into_inner());
+    let verdict = verdicts.get(sha256)?;
+    // A changed provider or an expired verdict is looked up again
+    let provider = settings().lock().unwrap_or_else(|e| e.into_inner()).provider;
+    let fresh = (Utc::now() - verdict.checked_at).num_hours() < VERDICT_TTL_HOURS;
+    (fresh && verdict.provider == provider).then(|| verdict.clone())
+}
+
+async fn lookup(sha256: &str) -> Result<HashVerdict, String> {
+    let config = settings().lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let api_key = config.api_key.unwrap_or_default();
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let verdict = match config.provider {
+        ReputationProvider::VirusTotal => lookup_virustotal(&client, api_key.trim(), sha256).await?,
+        ReputationProvider::MalwareBazaar => lookup_malwarebazaar(&client, api_key.trim(), sha256).await?,
+    };
+    VERDICTS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(sha256.to_string(), verdict.clone());
+    Ok(verdict)
+}
+
+/// `GET /api/v3/files/{sha256}`; 404 if VirusTotal has never seen the file
+async fn lookup_virustotal(client: &reqwest::Client, api_key: &str, sha256: &str) -> Result<HashVerdict, String> {
+    let response = client
+        .get(format!("https://www.virustotal.com/api/v3/files/{}", sha256))
+        .header("x-apikey", api_key)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut verdict = HashVerdict {
+        sha256: sha256.to_string(),
+        provider: ReputationProvider::VirusTotal,
+        status: ReputationStatus::Unknown,
+        detections: None,
+        engines: None,
+        label: None,
+        link: format!("https://www.virustotal.com/gui/file/{}", sha256),
+        checked_at: Utc::now(),
+    };
+    match response.status().as_u16() {
+        404 => return Ok(verdict),
+        401 | 403 => return Err("VirusTotal hat den API-Schlüssel abgelehnt".to_string()),
+        429 => return Err("VirusTotal-Kontingent erschöpft".to_string()),
+        status if status >= 400 => return Err(format!("VirusTotal antwortete mit HTTP {}", status)),
+        _ => {}
+    }
+
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    let attributes = &body["data"]["attributes"];
+    let stats = &attributes["last_analysis_stats"];
+    let count = |key: &str| stats[key].as_u64().unwrap_or(0) as u32;
+    let malicious = count("malicious");
+    let suspicious = count("suspicious");
+    verdict.detections = Some(malicious + suspicious);
+    verdict.engines = Some(["malicious", "suspicious", "undetected", "harmless"].iter().map(|key| count(key)).sum());
+    verdict.label = attributes["popular_threat_classification"]["suggested_threat_label"]
+        .as_str()
+        .map(String::from);
+    // Single detections are mostly heuristics of small engines
+    verdict.status = match malicious {
+        0 if suspicious == 0 => ReputationStatus::Clean,
+        0..=2 => ReputationStatus::Suspicious,
+        _ => ReputationStatus::Malicious,
+    };
+    Ok(verdict)
+}
+
+/// `get_info` answers `hash_not_found` for anything that isn't a known sample
+async fn lookup_malwarebazaar(client: &reqwest::Client, api_key: &str, sha256: &str) -> Result<HashVerdict, String> {
+    let response = client
+        .post("https://mb-api.abuse.ch/api/v1/")
+        .header("Auth-Key", api_key)
+        .form(&[("query", "get_info"), ("hash", sha256)])
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if response.status().as_u16() == 401 {
+        return Err("MalwareBazaar hat den Auth-Key abgelehnt".to_string());
+    }
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    let status = match body["query_status"].as_str().unwrap_or_default() {
+        "ok" => ReputationStatus::Malicious,
+        "hash_not_found" | "no_results" => ReputationStatus::Unknown,
+        other => return Err(format!("MalwareBazaar: {}", other)),
+    };
+    Ok(HashVerdict {
+        sha256: sha256.to_string(),
+        provider: ReputationProvider::MalwareBazaar,
+        status,
+        detections: None,
+        engines: None,
+        label: body["data"][0]["signature"].as_str().map(String::from),
+        link: format!("https://bazaar.abuse.ch/sample/{}/", sha256),
+        checked_at: Utc::now(),
+    })
+}
+
+fn finding(executable: &ListeningExecutable, verdict: &HashVerdict) -> Option<SecurityIssue> {
+    let severity = match verdict.status {
+        ReputationStatus::Malicious => SecuritySeverity::Critical,
+        ReputationStatus::Suspicious => SecuritySeverity::High,
+        ReputationStatus::Clean | ReputationStatus::Unknown => return None,
+    };
+    let provider = match verdict.provider {
+        ReputationProvider::VirusTotal => "VirusTotal",
+        ReputationProvider::MalwareBazaar => "MalwareBazaar",
+    };
+    let mut details = vec![executable.path.clone(), format!("SHA-256: {}", verdict.sha256)];
+    if let (Some(detections), Some(engines)) = (verdict.detections, verdict.engines) {
+        details.push(format!("{} von {} Engines", detections, engines));
+    }
+    if let Some(label) = &verdict.label {
+        details.push(label.clone());
+    }
+    details.push(verdict.link.clone());
+
+    Some(SecurityIssue {
+        id: format!("hash-reputation-{}", verdict.sha256),
+        service_id: None,
+        service_name: Some(executable.name.clone()),
+        category: SecurityCategory::UntrustedBinary,
+        severity,
+        title: match verdict.status {
+            ReputationStatus::Malicious => format!("{} ist bei {} als Schadsoftware bekannt", executable.name, provider),
+            _ => format!("{} wird von {} als verdächtig eingestuft", executable.name, provider),
+        },
+        description: format!(
+            "Der Hash des Programms hinter Port {} ist bei {} gemeldet",
+            executable.ports.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", "),
+            provider
+        ),
+        recommendation: "Beende den Prozess, prüfe Herkunft und Autostart-Einträge und lösche die Datei, falls sie nicht bewusst installiert wurde".to_string(),
+        port: executable.ports.first().copied(),
+        details: Some(details.join(", ")),
+    })
+}
@@ -0,0 +1,60 @@
+// Monitoring history in SQLite
+
+pub mod store;
+
+pub use store::{HistoryStore, PortCountPoint, SecurityScanSummary, ServicePoint, SystemPoint};
+
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use chrono::{NaiveDate, Utc};
+use crate::models::config::MonitoringHistoryConfig;
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::port::PortResolver;
+use crate::services::system_stats::SystemMonitor;
+use crate::services::ServiceManager;
+
+const MIN_INTERVAL_SECONDS: u64 = 10;
+
+static SETTINGS: OnceLock<Mutex<MonitoringHistoryConfig>> = OnceLock::new();
+
+/// Samples service status, CPU/memory and the port table in the background
+/// and writes them to the `HistoryStore`. Runs on its own interval, so the
+/// history keeps growing while the `ServiceMonitor` is stopped.
+pub struct MonitoringHistory;
+
+impl MonitoringHistory {
+    pub fn configure(config: &MonitoringHistoryConfig) {
+        *settings().lock().unwrap_or_else(|e| e.into_inner()) = config.clone();
+    }
+
+    pub fn start() {
+        tauri::async_runtime::spawn(async move {
+            let mut pruned_on: Option<NaiveDate> = None;
+            loop {
+                let config = settings().lock().unwrap_or_else(|e| e.into_inner()).clone();
+                // Simulated values would end up in the charts
+                if config.enabled && !MockDiscovery::is_enabled() {
+                    let services = ServiceManager::new().discover_all().await;
+                    let sample = tokio::task::spawn_blocking(|| {
+                        (SystemMonitor::new().get_stats(), PortResolver::new().get_port_usage())
+                    })
+                    .await;
+                    if let Ok((stats, ports)) = sample {
+                        let _ = HistoryStore::record(Utc::now(), &services, &stats, &ports).await;
+                    }
+
+                    let today = Utc::now().date_naive();
+                    if pruned_on != Some(today) && HistoryStore::prune(config.retention_days).await.is_ok() {
+                        pruned_on = Some(today);
+                    }
+                }
+
+                tokio::time::sleep(Duration::from_secs(config.interval_seconds.max(MIN_INTERVAL_SECONDS))).await;
+            }
+        });
+    }
+}
+
+fn settings() -> &'static Mutex<MonitoringHistoryConfig> {
+    SETTINGS.get_or_init(|| Mutex::new(MonitoringHistoryConfig::default()))
+}
@@ -0,0 +1,392 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::Row;
+use tokio::sync::Mutex;
+use crate::models::alert::Alert;
+use crate::models::port::PortInfo;
+use crate::models::service::Service;
+use crate::services::security_scanner::SecurityScanResult;
+use crate::services::storage::Storage;
+use crate::services::system_stats::SystemStats;
+
+/// Most points returned per series; longer ranges are averaged into buckets
+const MAX_POINTS: i64 = 500;
+
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS system_samples (
+        taken_at INTEGER NOT NULL,
+        cpu_percent REAL NOT NULL,
+        memory_used INTEGER NOT NULL,
+        memory_total INTEGER NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS system_samples_time ON system_samples (taken_at)",
+    "CREATE TABLE IF NOT EXISTS service_samples (
+        taken_at INTEGER NOT NULL,
+        service_id TEXT NOT NULL,
+        name TEXT NOT NULL,
+        status TEXT NOT NULL,
+        cpu_percent REAL,
+        memory_bytes INTEGER
+    )",
+    "CREATE INDEX IF NOT EXISTS service_samples_service_time ON service_samples (service_id, taken_at)",
+    "CREATE TABLE IF NOT EXISTS port_samples (
+        taken_at INTEGER NOT NULL,
+        listening INTEGER NOT NULL,
+        public INTEGER NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS port_samples_time ON port_samples (taken_at)",
+    "CREATE TABLE IF NOT EXISTS alerts (
+        id TEXT PRIMARY KEY,
+        taken_at INTEGER NOT NULL,
+        rule_id TEXT NOT NULL,
+        rule_name TEXT NOT NULL,
+        service_id TEXT,
+        message TEXT NOT NULL,
+        value REAL
+    )",
+    "CREATE INDEX IF NOT EXISTS alerts_time ON alerts (taken_at)",
+    "CREATE TABLE IF NOT EXISTS security_scans (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        taken_at INTEGER NOT NULL,
+        critical_count INTEGER NOT NULL,
+        high_count INTEGER NOT NULL,
+        medium_count INTEGER NOT NULL,
+        low_count INTEGER NOT NULL,
+        issue_count INTEGER NOT NULL,
+        result TEXT NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS security_scans_time ON security_scans (taken_at)",
+];
+
+/// Open pool and the file it belongs to; reopened when the data directory moves
+static POOL: OnceLock<Mutex<Option<(PathBuf, SqlitePool)>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemPoint {
+    pub taken_at: DateTime<Utc>,
+    pub cpu_percent: f64,
+    pub memory_used: u64,
+    pub memory_total: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServicePoint {
+    pub taken_at: DateTime<Utc>,
+    /// Status at the end of the bucket
+    pub status: String,
+    pub cpu_percent: Option<f64>,
+    pub memory_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortCountPoint {
+    pub taken_at: DateTime<Utc>,
+    pub listening: u32,
+    /// Listeners not bound to loopback
+    pub public: u32,
+}
+
+/// A stored security scan without its findings, for listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityScanSummary {
+    pub id: i64,
+    pub taken_at: DateTime<Utc>,
+    pub critical_count: u32,
+    pub high_count: u32,
+    pub medium_count: u32,
+    pub low_count: u32,
+    pub issue_count: u32,
+}
+
+/// SQLite database (`history.db` in the data directory) with one row per
+/// sample for the system, each service and the port table, plus fired
+/// alerts and security scans. Queries average long ranges down to at most `MAX_POINTS` points
+/// for charts.
+pub struct HistoryStore;
+
+impl HistoryStore {
+    pub async fn record(
+        taken_at: DateTime<Utc>,
+        services: &[Service],
+        stats: &SystemStats,
+        ports: &[PortInfo],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let pool = pool().await?;
+        let time = taken_at.timestamp();
+        let mut transaction = pool.begin().await?;
+
+        sqlx::query("INSERT INTO system_samples (taken_at, cpu_percent, memory_used, memory_total) VALUES (?, ?, ?, ?)")
+            .bind(time)
+            .bind(stats.cpu.usage_percent as f64)
+            .bind(stats.memory.used_bytes as i64)
+            .bind(stats.memory.total_bytes as i64)
+            .execute(&mut *transaction)
+            .await?;
+
+        for service in services {
+            sqlx::query(
+                "INSERT INTO service_samples (taken_at, service_id, name, status, cpu_percent, memory_bytes)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(time)
+            .bind(&service.id)
+            .bind(&service.name)
+            .bind(format!("{:?}", service.status).to_lowercase())
+            .bind(service.cpu_usage.map(|usage| usage as f64))
+            .bind(service.memory_bytes.map(|bytes| bytes as i64))
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        let public = ports.iter().filter(|p| !p.is_loopback()).count();
+        sqlx::query("INSERT INTO port_samples (taken_at, listening, public) VALUES (?, ?, ?)")
+            .bind(time)
+            .bind(ports.len() as i64)
+            .bind(public as i64)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    pub async fn system_series(
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<SystemPoint>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = sqlx::query(
+            "SELECT MAX(taken_at) AS taken_at, AVG(cpu_percent) AS cpu_percent,
+                    AVG(memory_used) AS memory_used, MAX(memory_total) AS memory_total
+             FROM system_samples WHERE taken_at BETWEEN ? AND ?
+             GROUP BY taken_at / ? ORDER BY 1",
+        )
+        .bind(from.timestamp())
+        .bind(to.timestamp())
+        .bind(bucket_seconds(from, to))
+        .fetch_all(&pool().await?)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(SystemPoint {
+                    taken_at: timestamp(row)?,
+                    cpu_percent: row.try_get("cpu_percent")?,
+                    memory_used: row.try_get::<f64, _>("memory_used")? as u64,
+                    memory_total: row.try_get::<i64, _>("memory_total")? as u64,
+                })
+            })
+            .collect()
+    }
+
+    /// SQLite returns the other columns of the row that matched `MAX()`, so
+    /// `status` is the last one within each bucket
+    pub async fn service_series(
+        service_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ServicePoint>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = sqlx::query(
+            "SELECT MAX(taken_at) AS taken_at, status, AVG(cpu_percent) AS cpu_percent,
+                    AVG(memory_bytes) AS memory_bytes
+             FROM service_samples WHERE service_id = ? AND taken_at BETWEEN ? AND ?
+             GROUP BY taken_at / ? ORDER BY 1",
+        )
+        .bind(service_id)
+        .bind(from.timestamp())
+        .bind(to.timestamp())
+        .bind(bucket_seconds(from, to))
+        .fetch_all(&pool().await?)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ServicePoint {
+                    taken_at: timestamp(row)?,
+                    status: row.try_get("status")?,
+                    cpu_percent: row.try_get("cpu_percent")?,
+                    memory_bytes: row.try_get::<Option<f64>, _>("memory_bytes")?.map(|bytes| bytes as u64),
+                })
+            })
+            .collect()
+    }
+
+    pub async fn port_series(
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<PortCountPoint>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = sqlx::query(
+            "SELECT MAX(taken_at) AS taken_at, MAX(listening) AS listening, MAX(public) AS public
+             FROM port_samples WHERE taken_at BETWEEN ? AND ?
+             GROUP BY taken_at / ? ORDER BY 1",
+        )
+        .bind(from.timestamp())
+        .bind(to.timestamp())
+        .bind(bucket_seconds(from, to))
+        .fetch_all(&pool().await?)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(PortCountPoint {
+                    taken_at: timestamp(row)?,
+                    listening: row.try_get::<i64, _>("listening")? as u32,
+                    public: row.try_get::<i64, _>("public")? as u32,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn record_alert(alert: &Alert) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query(
+            "INSERT INTO alerts (id, taken_at, rule_id, rule_name, service_id, message, value)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&alert.id)
+        .bind(alert.triggered_at.timestamp())
+        .bind(&alert.rule_id)
+        .bind(&alert.rule_name)
+        .bind(&alert.service_id)
+        .bind(&alert.message)
+        .bind(alert.value)
+        .execute(&pool().await?)
+        .await?;
+        Ok(())
+    }
+
+    /// Most recent alerts first
+    pub async fn alerts(limit: u32) -> Result<Vec<Alert>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = sqlx::query(
+            "SELECT id, taken_at, rule_id, rule_name, service_id, message, value
+             FROM alerts ORDER BY taken_at DESC LIMIT ?",
+        )
+        .bind(limit as i64)
+        .fetch_all(&pool().await?)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(Alert {
+                    id: row.try_get("id")?,
+                    rule_id: row.try_get("rule_id")?,
+                    rule_name: row.try_get("rule_name")?,
+                    service_id: row.try_get("service_id")?,
+                    message: row.try_get("message")?,
+                    value: row.try_get("value")?,
+                    triggered_at: timestamp(row)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Store a finished scan; returns its ID
+    pub async fn record_security_scan(result: &SecurityScanResult) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        let id = sqlx::query(
+            "INSERT INTO security_scans (taken_at, critical_count, high_count, medium_count, low_count, issue_count, result)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(result.scan_timestamp as i64)
+        .bind(result.critical_count as i64)
+        .bind(result.high_count as i64)
+        .bind(result.medium_count as i64)
+        .bind(result.low_count as i64)
+        .bind(result.issues.len() as i64)
+        .bind(serde_json::to_string(result)?)
+        .execute(&pool().await?)
+        .await?
+        .last_insert_rowid();
+        Ok(id)
+    }
+
+    /// Most recent scans first
+    pub async fn security_scans(limit: u32) -> Result<Vec<SecurityScanSummary>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = sqlx::query(
+            "SELECT id, taken_at, critical_count, high_count, medium_count, low_count, issue_count
+             FROM security_scans ORDER BY taken_at DESC, id DESC LIMIT ?",
+        )
+        .bind(limit as i64)
+        .fetch_all(&pool().await?)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(SecurityScanSummary {
+                    id: row.try_get("id")?,
+                    taken_at: timestamp(row)?,
+                    critical_count: row.try_get::<i64, _>("critical_count")? as u32,
+                    high_count: row.try_get::<i64, _>("high_count")? as u32,
+                    medium_count: row.try_get::<i64, _>("medium_count")? as u32,
+                    low_count: row.try_get::<i64, _>("low_count")? as u32,
+                    issue_count: row.try_get::<i64, _>("issue_count")? as u32,
+                })
+            })
+            .collect()
+    }
+
+    /// A stored scan with all findings; `None` for the latest one
+    pub async fn security_scan(id: Option<i64>) -> Result<Option<(i64, SecurityScanResult)>, Box<dyn std::error::Error + Send + Sync>> {
+        let row = match id {
+            Some(id) => sqlx::query("SELECT id, result FROM security_scans WHERE id = ?").bind(id),
+            None => sqlx::query("SELECT id, result FROM security_scans ORDER BY taken_at DESC, id DESC LIMIT 1"),
+        }
+        .fetch_optional(&pool().await?)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let result: String = row.try_get("result")?;
+        Ok(Some((row.try_get("id")?, serde_json::from_str(&result)?)))
+    }
+
+    /// Delete samples, alerts and security scans older than `retention_days`
+    pub async fn prune(retention_days: u32) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let cutoff = (Utc::now() - chrono::Duration::days(retention_days.max(1) as i64)).timestamp();
+        let pool = pool().await?;
+        let mut deleted = 0;
+        for table in ["system_samples", "service_samples", "port_samples", "alerts", "security_scans"] {
+            deleted += sqlx::query(&format!("DELETE FROM {} WHERE taken_at < ?", table))
+                .bind(cutoff)
+                .execute(&pool)
+                .await?
+                .rows_affected();
+        }
+        Ok(deleted)
+    }
+}
+
+async fn pool() -> Result<SqlitePool, Box<dyn std::error::Error + Send + Sync>> {
+    let path = Storage::data_dir().join("history.db");
+    let mut guard = POOL.get_or_init(|| Mutex::new(None)).lock().await;
+    if let Some((open_path, pool)) = guard.as_ref() {
+        if *open_path == path {
+            return Ok(pool.clone());
+        }
+        pool.close().await;
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let pool = SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect_with(SqliteConnectOptions::new().filename(&path).create_if_missing(true))
+        .await
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    for statement in SCHEMA {
+        sqlx::query(statement).execute(&pool).await?;
+    }
+    *guard = Some((path, pool.clone()));
+    Ok(pool)
+}
+
+fn bucket_seconds(from: DateTime<Utc>, to: DateTime<Utc>) -> i64 {
+    ((to - from).num_seconds() / MAX_POINTS).max(1)
+}
+
+fn timestamp(row: &SqliteRow) -> Result<DateTime<Utc>, Box<dyn std::error::Error + Send + Sync>> {
+    let seconds: i64 = row.try_get("taken_at")?;
+    DateTime::from_timestamp(seconds, 0).ok_or_else(|| format!("Invalid timestamp {}", seconds).into())
+}
@@ -1,5 +1,5 @@
-use crate::models::service::Service;
-use crate::services::discovery::{docker::DockerDiscovery, process::ProcessDiscovery, ServiceDiscovery};
+use crate::models::service::{DiscoveryResult, ProviderWarning, Service, ServiceType};
+use crate::services::discovery::{docker::DockerDiscovery, mock::MockDiscovery, process::ProcessDiscovery, ServiceDiscovery};
 
 #[cfg(target_os = "macos")]
 use crate::services::discovery::launchd::LaunchdDiscovery;
@@ -11,11 +11,46 @@ use crate::services::discovery::systemd::SystemdDiscovery;
 use crate::services::discovery::windows_service::WindowsServiceDiscovery;
 
 use crate::services::port::resolver::PortResolver;
+use crate::services::gpu_processes::GpuProcesses;
+#[cfg(target_os = "macos")]
+use crate::services::process_energy::ProcessEnergy;
+use crate::services::process_tree::ProcessTree;
+
+/// Synthetic service for a process that holds ports but isn't known to any
+/// discovery provider. Its id is `process-<pid>`, so controllers act on `pid`.
+pub(crate) fn port_process_service(pid: u32, process_name: String, ports: Vec<u16>, description: String) -> Service {
+    Service {
+        id: format!("process-{}", pid),
+        name: process_name,
+        status: crate::models::service::ServiceStatus::Running,
+        service_type: crate::models::service::ServiceType::Process,
+        ports: ports.into_iter().take(10).collect(), // Limit ports per service
+        pid: Some(pid),
+        path: None,
+        description: Some(description),
+        auto_start: false,
+        cpu_usage: None,
+        memory_bytes: None,
+        memory_percent: None,
+        cpu_usage_total: None,
+        memory_total: None,
+        gpu_usage: None,
+        energy: None,
+        network_rx_bytes: None,
+        network_tx_bytes: None,
+        user: None,
+        recent_restarts: 0,
+        flapping: false,
+        compose_project: None,
+        compose_service: None,
+    }
+}
 
 /// Main service manager that orchestrates all discovery modules
 pub struct ServiceManager {
     docker: DockerDiscovery,
     process: ProcessDiscovery,
+    mock: MockDiscovery,
     #[cfg(target_os = "macos")]
     launchd: LaunchdDiscovery,
     #[cfg(target_os = "linux")]
@@ -30,6 +65,7 @@ impl ServiceManager {
         Self {
             docker: DockerDiscovery::new(),
             process: ProcessDiscovery::new(),
+            mock: MockDiscovery::new(),
             #[cfg(target_os = "macos")]
             launchd: LaunchdDiscovery::new(),
             #[cfg(target_os = "linux")]
@@ -42,22 +78,40 @@ impl ServiceManager {
 
     /// Discover all services from all available providers
     pub async fn discover_all(&self) -> Vec<Service> {
+        self.discover().await.services
+    }
+
+    /// Discover all services and report providers that returned incomplete data
+    pub async fn discover(&self) -> DiscoveryResult {
+        // Simulation mode replaces all real providers
+        if self.mock.is_available() {
+            return DiscoveryResult {
+                services: self.mock.discover().await.unwrap_or_default(),
+                warnings: Vec::new(),
+            };
+        }
+
         let mut all_services = Vec::new();
 
         // Get port usage for enriching service data
-        let port_usage = self.port_resolver.get_port_usage();
+        let (port_usage, mut warnings) = self.port_resolver.port_usage_with_warnings();
 
         // Docker containers
         if self.docker.is_available() {
-            if let Ok(services) = self.docker.discover().await {
-                all_services.extend(services);
+            match self.docker.discover().await {
+                Ok(services) => all_services.extend(services),
+                Err(e) => warnings.push(ProviderWarning::from_error(self.docker.provider_name(), e.as_ref())),
             }
         }
 
         // Platform-specific services
         #[cfg(target_os = "macos")]
         {
-            if let Ok(services) = self.launchd.discover().await {
+            let discovered = self.launchd.discover().await;
+            if let Err(e) = &discovered {
+                warnings.push(ProviderWarning::from_error(self.launchd.provider_name(), e.as_ref()));
+            }
+            if let Ok(services) = discovered {
                 // Include launchd services (limit to 100 for performance)
                 // Prioritize running services
                 let mut sorted_services: Vec<Service> = services;
@@ -76,15 +130,17 @@ impl ServiceManager {
 
         #[cfg(target_os = "linux")]
         {
-            if let Ok(services) = self.systemd.discover().await {
-                all_services.extend(services);
+            match self.systemd.discover().await {
+                Ok(services) => all_services.extend(services),
+                Err(e) => warnings.push(ProviderWarning::from_error(self.systemd.provider_name(), e.as_ref())),
             }
         }
 
         #[cfg(target_os = "windows")]
         {
-            if let Ok(services) = self.windows.discover().await {
-                all_services.extend(services);
+            match self.windows.discover().await {
+                Ok(services) => all_services.extend(services),
+                Err(e) => warnings.push(ProviderWarning::from_error(self.windows.provider_name(), e.as_ref())),
             }
         }
 
@@ -135,21 +191,17 @@ impl ServiceManager {
                 format!("Ports: {}", ports.iter().take(5).map(|p| p.to_string()).collect::<Vec<_>>().join(", "))
             };
 
-            all_services.push(Service {
-                id: format!("process-{}", pid),
-                name: process_name,
-                status: crate::models::service::ServiceStatus::Running,
-                service_type: crate::models::service::ServiceType::Process,
-                ports: ports.into_iter().take(10).collect(), // Limit ports per service
-                pid: Some(pid),
-                path: None,
-                description: Some(port_desc),
-                auto_start: false,
-                cpu_usage: None,
-                memory_bytes: None,
-                memory_percent: None,
-            });
+            all_services.push(port_process_service(pid, process_name, ports, port_desc));
+        }
+
+        let owners = crate::services::user_scope::process_owners();
+        for service in &mut all_services {
+            service.user = service.pid.and_then(|pid| owners.get(&pid).cloned());
         }
+        ProcessTree::snapshot().fill(&mut all_services);
+        GpuProcesses::fill(&mut all_services);
+        #[cfg(target_os = "macos")]
+        ProcessEnergy::fill(&mut all_services);
 
         // Deduplicate by ID first (keep first occurrence)
         let mut seen_ids = std::collections::HashSet::new();
@@ -168,7 +220,75 @@ impl ServiceManager {
         // Limit total services for performance (max 150)
         all_services.truncate(150);
 
-        all_services
+        DiscoveryResult {
+            services: all_services,
+            warnings,
+        }
+    }
+
+    /// Fresh state of a few known services, re-querying only their providers
+    /// and skipping the port table. Services that are gone are missing from
+    /// the result. Process services only come out of the port table, so they
+    /// are returned unchanged, as are services whose provider failed.
+    pub async fn refresh(&self, services: &[Service]) -> Vec<Service> {
+        let mut types: Vec<&ServiceType> = Vec::new();
+        for service in services {
+            if !types.contains(&&service.service_type) {
+                types.push(&service.service_type);
+            }
+        }
+
+        let mut fresh = Vec::new();
+        for service_type in types {
+            let unchanged = || services.iter().filter(|s| &s.service_type == service_type).cloned().collect::<Vec<_>>();
+            let discovered = if self.mock.is_available() {
+                self.mock.discover().await
+            } else {
+                match service_type {
+                    ServiceType::Docker => self.docker.discover().await,
+                    #[cfg(target_os = "linux")]
+                    ServiceType::Systemd => self.systemd.discover().await,
+                    #[cfg(target_os = "macos")]
+                    ServiceType::Launchd => self.launchd.discover().await,
+                    #[cfg(target_os = "windows")]
+                    ServiceType::WindowsService => self.windows.discover().await,
+                    _ => Ok(unchanged()),
+                }
+            };
+            match discovered {
+                Ok(discovered) => fresh.extend(
+                    discovered
+                        .into_iter()
+                        .filter(|s| &s.service_type == service_type && services.iter().any(|known| known.id == s.id)),
+                ),
+                Err(_) => fresh.extend(unchanged()),
+            }
+        }
+
+        // Ports and owner come from the port table in `discover`; they still
+        // hold as long as the process is the same
+        for service in &mut fresh {
+            let Some(known) = services.iter().find(|s| s.id == service.id) else {
+                continue;
+            };
+            if service.pid == known.pid {
+                if service.ports.is_empty() {
+                    service.ports = known.ports.clone();
+                }
+                if service.user.is_none() {
+                    service.user = known.user.clone();
+                }
+                if service.gpu_usage.is_none() {
+                    service.gpu_usage = known.gpu_usage.clone();
+                }
+            }
+        }
+        if !self.mock.is_available() {
+            ProcessTree::snapshot().fill(&mut fresh);
+            #[cfg(target_os = "macos")]
+            ProcessEnergy::fill(&mut fresh);
+        }
+        fresh
     }
 
     /// Get a specific service by ID
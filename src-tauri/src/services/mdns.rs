@@ -0,0 +1,420 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const MDNS_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+const MDNS_PORT: u16 = 5353;
+/// Lists the service types announced on the network (RFC 6763, section 9)
+const META_QUERY: &str = "_services._dns-sd._udp.local";
+
+const TYPE_A: u16 = 1;
+const TYPE_PTR: u16 = 12;
+const TYPE_TXT: u16 = 16;
+const TYPE_AAAA: u16 = 28;
+const TYPE_SRV: u16 = 33;
+/// IN class with the "unicast response" bit set
+const CLASS_IN_QU: u16 = 0x8001;
+
+/// Queried even if no responder lists them in the meta query
+pub const DEFAULT_SERVICE_TYPES: &[&str] = &[
+    "_http._tcp",
+    "_https._tcp",
+    "_ssh._tcp",
+    "_sftp-ssh._tcp",
+    "_smb._tcp",
+    "_afpovertcp._tcp",
+    "_nfs._tcp",
+    "_ipp._tcp",
+    "_ipps._tcp",
+    "_printer._tcp",
+    "_pdl-datastream._tcp",
+    "_scanner._tcp",
+    "_googlecast._tcp",
+    "_airplay._tcp",
+    "_raop._tcp",
+    "_spotify-connect._tcp",
+    "_hap._tcp",
+    "_workstation._tcp",
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkServiceCategory {
+    Web,
+    RemoteAccess,
+    FileSharing,
+    Printer,
+    Media,
+    SmartHome,
+    Other,
+}
+
+/// A service instance announced via mDNS/DNS-SD
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkService {
+    /// Instance name, e.g. `Office Printer`
+    pub name: String,
+    /// DNS-SD type, e.g. `_ipp._tcp`
+    pub service_type: String,
+    pub category: NetworkServiceCategory,
+    /// Target host, e.g. `printer.local`
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub addresses: Vec<String>,
+    /// TXT record key/values (model, path, ...)
+    pub txt: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkDiscovery {
+    pub services: Vec<NetworkService>,
+    /// Service types seen on the network, including ones without instances
+    pub service_types: Vec<String>,
+    pub scanned_at: DateTime<Utc>,
+}
+
+/// Records collected from all responses
+#[derive(Default)]
+struct RecordCache {
+    /// Service type or meta query -> instance names
+    pointers: HashMap<String, HashSet<String>>,
+    /// Instance -> (host, port)
+    services: HashMap<String, (String, u16)>,
+    texts: HashMap<String, BTreeMap<String, String>>,
+    addresses: HashMap<String, HashSet<IpAddr>>,
+}
+
+/// Browses DNS-SD services on the local network with one-shot mDNS queries
+/// (RFC 6762, section 5.1). Queries are sent from an ephemeral port, so this
+/// works next to Avahi or mDNSResponder, which already hold port 5353.
+/// IPv4 only.
+pub struct MdnsBrowser {
+    timeout: Duration,
+    service_types: Vec<String>,
+}
+
+impl MdnsBrowser {
+    pub fn new() -> Self {
+        Self {
+            timeout: Duration::from_millis(1500),
+            service_types: DEFAULT_SERVICE_TYPES.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    /// How long to collect responses per query round
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Replace the default service types, e.g. `["_ssh._tcp"]`
+    pub fn with_service_types(mut self, service_types: Vec<String>) -> Self {
+        self.service_types = service_types
+            .into_iter()
+            .map(|t| t.trim().trim_end_matches('.').trim_end_matches(".local").to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+        self
+    }
+
+    /// Blocking: two query rounds of `timeout` each
+    pub fn browse(&self) -> Result<NetworkDiscovery, Box<dyn std::error::Error + Send + Sync>> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_multicast_ttl_v4(255)?;
+        let mut cache = RecordCache::default();
+
+        // Round 1: the meta query plus the requested types
+        let mut queried: HashSet<String> = self.service_types.iter().map(|t| normalize(&qualify(t))).collect();
+        let mut names: Vec<String> = queried.iter().cloned().collect();
+        names.push(META_QUERY.to_string());
+        send_queries(&socket, &names)?;
+        collect(&socket, self.timeout, &mut cache);
+
+        // Round 2: types only found through the meta query
+        let discovered: Vec<String> = cache
+            .pointers
+            .get(META_QUERY)
+            .map(|types| {
+                types
+                    .iter()
+                    .filter(|t| is_service_type(t) && !queried.contains(*t))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !discovered.is_empty() {
+            send_queries(&socket, &discovered)?;
+            collect(&socket, self.timeout, &mut cache);
+            queried.extend(discovered);
+        }
+
+        let mut service_types: Vec<String> = cache
+            .pointers
+            .get(META_QUERY)
+            .into_iter()
+            .flatten()
+            .chain(cache.pointers.keys())
+            .filter(|t| is_service_type(t))
+            .map(|t| unqualify(t))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        service_types.sort();
+
+        let mut services: Vec<NetworkService> = cache
+            .pointers
+            .iter()
+            .filter(|(service_type, _)| is_service_type(service_type))
+            .flat_map(|(service_type, instances)| {
+                instances.iter().map(|instance| build_service(&cache, service_type, instance))
+            })
+            .collect();
+        services.sort_by(|a, b| a.service_type.cmp(&b.service_type).then_with(|| a.name.cmp(&b.name)));
+
+        Ok(NetworkDiscovery {
+            services,
+            service_types,
+            scanned_at: Utc::now(),
+        })
+    }
+}
+
+impl Default for MdnsBrowser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn build_service(cache: &RecordCache, service_type: &str, instance: &str) -> NetworkService {
+    let (host, port) = match cache.services.get(instance) {
+        Some((host, port)) => (Some(host.clone()), Some(*port)),
+        None => (None, None),
+    };
+    let mut addresses: Vec<IpAddr> = host
+        .as_ref()
+        .and_then(|h| cache.addresses.get(h))
+        .map(|a| a.iter().copied().collect())
+        .unwrap_or_default();
+    // IPv4 first, it's what most users type
+    addresses.sort_by_key(|a| (a.is_ipv6(), *a));
+
+    let service_type = unqualify(service_type);
+    NetworkService {
+        name: instance_name(instance, &service_type),
+        category: categorize(&service_type),
+        service_type,
+        host: host.map(|h| h.trim_end_matches('.').to_string()),
+        port,
+        addresses: addresses.iter().map(|a| a.to_string()).collect(),
+        txt: cache.texts.get(instance).cloned().unwrap_or_default(),
+    }
+}
+
+fn categorize(service_type: &str) -> NetworkServiceCategory {
+    match service_type {
+        "_http._tcp" | "_https._tcp" => NetworkServiceCategory::Web,
+        "_ssh._tcp" | "_sftp-ssh._tcp" | "_rfb._tcp" | "_rdp._tcp" | "_workstation._tcp" => {
+            NetworkServiceCategory::RemoteAccess
+        }
+        "_smb._tcp" | "_afpovertcp._tcp" | "_nfs._tcp" | "_webdav._tcp" | "_adisk._tcp" => {
+            NetworkServiceCategory::FileSharing
+        }
+        "_ipp._tcp" | "_ipps._tcp" | "_printer._tcp" | "_pdl-datastream._tcp" | "_scanner._tcp" | "_uscan._tcp" => {
+            NetworkServiceCategory::Printer
+        }
+        "_googlecast._tcp" | "_airplay._tcp" | "_raop._tcp" | "_spotify-connect._tcp" | "_daap._tcp" => {
+            NetworkServiceCategory::Media
+        }
+        "_hap._tcp" | "_homekit._tcp" | "_matter._tcp" | "_hue._tcp" => NetworkServiceCategory::SmartHome,
+        _ => NetworkServiceCategory::Other,
+    }
+}
+
+/// `_http._tcp.local`, but not the meta query, subtypes
+/// (`_printer._sub._http._tcp.local`) or reverse lookup names
+fn is_service_type(name: &str) -> bool {
+    name != META_QUERY
+        && name.starts_with('_')
+        && !name.contains("._sub.")
+        && (name.ends_with("._tcp.local") || name.ends_with("._udp.local"))
+}
+
+/// `_http._tcp` -> `_http._tcp.local`
+fn qualify(service_type: &str) -> String {
+    format!("{}.local", service_type)
+}
+
+fn unqualify(name: &str) -> String {
+    name.trim_end_matches('.').trim_end_matches(".local").to_string()
+}
+
+/// `Office Printer._ipp._tcp.local` -> `Office Printer`
+fn instance_name(instance: &str, service_type: &str) -> String {
+    instance
+        .strip_suffix(&format!(".{}", qualify(service_type)))
+        .unwrap_or(instance)
+        .replace("\\032", " ")
+}
+
+/// Several questions per packet, staying well below the 1472 byte payload
+/// of a 1500 byte MTU
+fn send_queries(socket: &UdpSocket, names: &[String]) -> std::io::Result<()> {
+    let target = SocketAddr::from((MDNS_GROUP, MDNS_PORT));
+    for chunk in names.chunks(12) {
+        socket.send_to(&build_query(chunk), target)?;
+    }
+    Ok(())
+}
+
+fn build_query(names: &[String]) -> Vec<u8> {
+    let mut packet = vec![0u8; 12];
+    packet[4..6].copy_from_slice(&(names.len() as u16).to_be_bytes());
+    for name in names {
+        for label in name.split('.').filter(|l| !l.is_empty()) {
+            let bytes = &label.as_bytes()[..label.len().min(63)];
+            packet.push(bytes.len() as u8);
+            packet.extend_from_slice(bytes);
+        }
+        packet.push(0);
+        packet.extend_from_slice(&TYPE_PTR.to_be_bytes());
+        packet.extend_from_slice(&CLASS_IN_QU.to_be_bytes());
+    }
+    packet
+}
+
+fn collect(socket: &UdpSocket, timeout: Duration, cache: &mut RecordCache) {
+    let deadline = Instant::now() + timeout;
+    let mut buffer = [0u8; 9000];
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() || socket.set_read_timeout(Some(remaining)).is_err() {
+            break;
+        }
+        match socket.recv_from(&mut buffer) {
+            Ok((len, _)) => parse_response(&buffer[..len], cache),
+            Err(_) => break,
+        }
+    }
+}
+
+/// Add all answer and additional records of a response to the cache.
+/// Malformed packets are ignored from the first bad record on.
+fn parse_response(packet: &[u8], cache: &mut RecordCache) {
+    if packet.len() < 12 || packet[2] & 0x80 == 0 {
+        return;
+    }
+    let count = |offset: usize| u16::from_be_bytes([packet[offset], packet[offset + 1]]) as usize;
+    let (questions, records) = (count(4), count(6) + count(8) + count(10));
+
+    let mut offset = 12;
+    for _ in 0..questions {
+        let Some((_, next)) = read_name(packet, offset) else {
+            return;
+        };
+        offset = next + 4;
+    }
+
+    for _ in 0..records {
+        let Some((name, next)) = read_name(packet, offset) else {
+            return;
+        };
+        let Some(header) = packet.get(next..next + 10) else {
+            return;
+        };
+        let record_type = u16::from_be_bytes([header[0], header[1]]);
+        let length = u16::from_be_bytes([header[8], header[9]]) as usize;
+        let data_start = next + 10;
+        let Some(data) = packet.get(data_start..data_start + length) else {
+            return;
+        };
+        let name = normalize(&name);
+
+        match record_type {
+            TYPE_PTR => {
+                if let Some((target, _)) = read_name(packet, data_start) {
+                    cache.pointers.entry(name).or_default().insert(normalize(&target));
+                }
+            }
+            TYPE_SRV if data.len() > 6 => {
+                let port = u16::from_be_bytes([data[4], data[5]]);
+                if let Some((host, _)) = read_name(packet, data_start + 6) {
+                    cache.services.insert(name, (normalize(&host), port));
+                }
+            }
+            TYPE_TXT => {
+                cache.texts.insert(name, parse_txt(data));
+            }
+            TYPE_A if data.len() == 4 => {
+                let ip = Ipv4Addr::new(data[0], data[1], data[2], data[3]);
+                cache.addresses.entry(name).or_default().insert(IpAddr::V4(ip));
+            }
+            TYPE_AAAA if data.len() == 16 => {
+                let mut octets = [0u8; 16];
+                octets.copy_from_slice(data);
+                cache.addresses.entry(name).or_default().insert(IpAddr::V6(Ipv6Addr::from(octets)));
+            }
+            _ => {}
+        }
+        offset = data_start + length;
+    }
+}
+
+/// DNS names are case-insensitive, but instance names are shown to the user,
+/// so only the `._type._proto.local` part of an instance is lowercased
+fn normalize(name: &str) -> String {
+    if name.starts_with('_') {
+        return name.to_lowercase();
+    }
+    match name.find("._") {
+        Some(index) => format!("{}{}", &name[..index], name[index..].to_lowercase()),
+        None => name.to_lowercase(),
+    }
+}
+
+/// Read a possibly compressed name. Returns the name and the offset after it.
+fn read_name(packet: &[u8], start: usize) -> Option<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut offset = start;
+    let mut end = None;
+    // Pointers may only go backwards, but limit jumps anyway
+    for _ in 0..128 {
+        let len = *packet.get(offset)? as usize;
+        match len {
+            0 => {
+                return Some((labels.join("."), end.unwrap_or(offset + 1)));
+            }
+            l if l & 0xc0 == 0xc0 => {
+                let pointer = ((l & 0x3f) << 8) | *packet.get(offset + 1)? as usize;
+                end.get_or_insert(offset + 2);
+                offset = pointer;
+            }
+            l => {
+                let label = packet.get(offset + 1..offset + 1 + l)?;
+                labels.push(String::from_utf8_lossy(label).into_owned());
+                offset += 1 + l;
+            }
+        }
+    }
+    None
+}
+
+/// Length-prefixed `key=value` strings
+fn parse_txt(data: &[u8]) -> BTreeMap<String, String> {
+    let mut entries = BTreeMap::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let len = data[offset] as usize;
+        let Some(entry) = data.get(offset + 1..offset + 1 + len) else {
+            break;
+        };
+        let entry = String::from_utf8_lossy(entry);
+        if !entry.is_empty() {
+            let (key, value) = entry.split_once('=').unwrap_or((&entry, ""));
+            entries.insert(key.to_string(), value.to_string());
+        }
+        offset += 1 + len;
+    }
+    entries
+}
@@ -1,5 +1,6 @@
 // Core service logic modules
 
+pub mod cmd;
 pub mod discovery;
 pub mod control;
 pub mod port;
@@ -7,9 +8,52 @@ pub mod security;
 pub mod manager;
 pub mod monitor;
 pub mod system_stats;
+pub mod stats_history;
+pub mod powermetrics;
+pub mod process_tree;
+pub mod gpu_processes;
+#[cfg(target_os = "macos")]
+pub mod process_energy;
+#[cfg(target_os = "macos")]
+pub mod code_signing;
+#[cfg(target_os = "windows")]
+pub mod windows_gpu;
 pub mod security_scanner;
+pub mod disk_health;
+pub mod compliance;
+#[cfg(unix)]
+pub mod config_audit;
+pub mod env_secrets;
+pub mod firewall;
+pub mod docker_inventory;
+pub mod docker_hardening;
+pub mod hash_reputation;
+pub mod process_knowledge;
+pub mod outdated;
+#[cfg(unix)]
+pub mod suid_audit;
+pub mod digest;
+pub mod history;
+pub mod alerts;
+pub mod notifier;
+pub mod export;
+pub mod pdf;
+pub mod security_report;
+pub mod cleanup;
+pub mod session;
+pub mod remote;
+pub mod resolvers;
+pub mod time_sync;
+pub mod dependency_map;
+pub mod storage;
+pub mod user_scope;
+pub mod mdns;
+pub mod ssdp;
+pub mod tunnel;
+pub mod bootstrap;
+pub mod webhooks;
 
 pub use manager::ServiceManager;
-pub use monitor::{ServiceMonitor, MonitorState, set_monitor_interval, enable_monitor};
+pub use monitor::{ServiceMonitor, MonitorState, PortWatcher, DiskWatcher, MemoryWatcher, FlapDetector, ResourceTracker, set_monitor_interval, enable_monitor, start_monitor, stop_monitor, watch_service, unwatch_service, get_watched_services, get_recent_events};
 pub use system_stats::{SystemMonitor, SystemStats, GpuProvider};
 pub use security_scanner::{SecurityScanner, SecurityScanResult, SecurityIssue};
@@ -1,12 +1,35 @@
-use crate::models::service::Service;
+use crate::models::alert::Alert;
+use crate::models::config::{DiskWatchConfig, FlappingConfig, MemoryWatchConfig, PortWatchConfig, ResourceEventsConfig, WebhookEvent};
+use crate::models::port::PortInfo;
+use crate::models::service::{Service, ServiceStatus};
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::notifier::{NotificationCategory, Notifier};
+use crate::services::port::baseline::BaselineDeviation;
+use crate::services::port::{PortBaseline, PortResolver, PortReservations};
 use crate::services::ServiceManager;
+use crate::services::system_stats::{DiskStats, MemoryStats, ProcessSort, ProcessUsage, SystemMonitor};
+use crate::services::tunnel::TunnelStatus;
+use crate::services::user_scope::UserScope;
+use crate::services::webhooks::WebhookDispatcher;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::Arc;
-use std::time::Duration;
-use tauri::{AppHandle, Emitter};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::Mutex;
 
+static PORT_WATCH: OnceLock<std::sync::Mutex<PortWatchConfig>> = OnceLock::new();
+static FLAPPING: OnceLock<std::sync::Mutex<FlapState>> = OnceLock::new();
+static RESOURCES: OnceLock<std::sync::Mutex<ResourceState>> = OnceLock::new();
+static DISK_WATCH: OnceLock<std::sync::Mutex<DiskWatchConfig>> = OnceLock::new();
+static MEMORY_WATCH: OnceLock<std::sync::Mutex<MemoryWatchConfig>> = OnceLock::new();
+
+/// Span over which available memory and swap trends are measured
+const MEMORY_TREND_WINDOW: Duration = Duration::from_secs(120);
+/// Shorter spans make the trend too noisy to extrapolate
+const MEMORY_TREND_MIN_SPAN: Duration = Duration::from_secs(60);
+
 /// Event types emitted by the service monitor
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type", content = "payload")]
@@ -28,13 +51,108 @@ pub enum ServiceEvent {
         service_id: String,
         ports: Vec<u16>,
     },
+    /// A new listener appeared (port watch)
+    PortOpened(PortInfo),
+    /// A listener went away (port watch)
+    PortClosed(PortInfo),
+    /// A process other than the expected owner listens on a reserved port
+    ReservedPortTaken {
+        port: PortInfo,
+        label: String,
+        owner: Option<String>,
+    },
+    /// A listener that isn't part of the recorded port baseline
+    BaselineDeviation(BaselineDeviation),
+    /// A managed SSH tunnel connected, dropped or was stopped
+    TunnelStateChanged(TunnelStatus),
+    /// An alert rule fired
+    AlertTriggered(Alert),
+    /// A service restarted more often than `FlappingConfig` allows
+    ServiceFlapping {
+        service_id: String,
+        restarts: u32,
+        window_minutes: u32,
+    },
+    /// A filesystem filled up past `DiskWatchConfig::threshold_percent`
+    DiskSpaceLow(DiskStats),
+    /// Memory is about to run out, see `MemoryWatchConfig`
+    MemoryPressure(MemoryPressure),
+    /// CPU or memory usage moved by more than `ResourceEventsConfig` allows
+    /// since it was last reported
+    ServiceResourceChanged {
+        service_id: String,
+        cpu: Option<f32>,
+        memory: Option<u64>,
+    },
+}
+
+/// Payload of `ServiceEvent::MemoryPressure`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MemoryPressure {
+    pub memory: MemoryStats,
+    /// Change of available memory over the trend window; negative while it shrinks
+    pub available_trend_bytes_per_minute: i64,
+    pub swap_growth_bytes_per_minute: i64,
+    /// When available memory runs out at the current trend, `None` while it
+    /// isn't shrinking
+    pub minutes_until_exhausted: Option<f32>,
+    /// Largest processes by resident memory
+    pub top_consumers: Vec<ProcessUsage>,
+}
+
+/// Events kept for `get_recent_events`
+const RECENT_EVENTS: usize = 500;
+
+/// A `ServiceEvent` as kept in the recent event buffer
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Increases with every event, so clients can skip ones they have seen
+    pub id: u64,
+    pub timestamp: DateTime<Utc>,
+    pub event: ServiceEvent,
+}
+
+#[derive(Default)]
+struct EventBuffer {
+    next_id: u64,
+    events: VecDeque<RecordedEvent>,
+}
+
+/// Emits a service event to the frontend and keeps it in `MonitorState` for
+/// windows that open later
+pub(crate) fn emit_service_event(app_handle: &AppHandle, event: ServiceEvent) {
+    if let Some(state) = app_handle.try_state::<MonitorState>() {
+        state.record(event.clone());
+    }
+    match &event {
+        ServiceEvent::ServiceStatusChanged { .. } => {
+            WebhookDispatcher::dispatch(WebhookEvent::ServiceStatus, webhook_data(&event));
+        }
+        ServiceEvent::AlertTriggered(_) => {
+            WebhookDispatcher::dispatch(WebhookEvent::Alert, webhook_data(&event));
+        }
+        _ => {}
+    }
+    let _ = app_handle.emit("service-event", event);
+}
+
+/// The event's payload without the `type`/`payload` envelope
+fn webhook_data(event: &ServiceEvent) -> serde_json::Value {
+    serde_json::to_value(event)
+        .ok()
+        .and_then(|mut value| value.get_mut("payload").map(serde_json::Value::take))
+        .unwrap_or_default()
 }
 
 /// Configuration for the service monitor
 #[derive(Clone)]
 pub struct MonitorConfig {
-    /// Interval between service checks (default: 5 seconds)
+    /// Interval for re-checking hot services (default: 2 seconds)
     pub check_interval: Duration,
+    /// Interval between full rediscoveries of all services (default: 30 seconds)
+    pub full_interval: Duration,
+    /// How long a service stays hot after it changed or was acted on
+    pub hot_period: Duration,
     /// Whether the monitor is enabled
     pub enabled: bool,
 }
@@ -42,16 +160,40 @@ pub struct MonitorConfig {
 impl Default for MonitorConfig {
     fn default() -> Self {
         Self {
-            check_interval: Duration::from_secs(5),
+            check_interval: Duration::from_secs(2),
+            full_interval: Duration::from_secs(30),
+            hot_period: Duration::from_secs(120),
             enabled: true,
         }
     }
 }
 
-/// Service monitor that watches for changes and emits events
+/// Refreshes requested from outside the loop, e.g. after a control command
+#[derive(Default)]
+struct RefreshRequests {
+    full: bool,
+    services: HashSet<String>,
+}
+
+static REFRESH_REQUESTS: OnceLock<std::sync::Mutex<RefreshRequests>> = OnceLock::new();
+static REFRESH_NOTIFY: OnceLock<tokio::sync::Notify> = OnceLock::new();
+static WATCHED: OnceLock<std::sync::Mutex<HashSet<String>>> = OnceLock::new();
+
+/// Upper bound for the watch list, which is meant for a handful of services
+const MAX_WATCHED_SERVICES: usize = 20;
+
+/// Service monitor that watches for changes and emits events. Polling is
+/// adaptive: services that recently changed or were acted on are "hot" and
+/// re-checked every `check_interval` through their provider only, while the
+/// full discovery (all providers plus the port table) runs every
+/// `full_interval`. Watched services (`watch`) are hot permanently.
+/// `request_refresh` wakes the loop right away.
+///
+/// The config is shared with `MonitorState` and read on every tick, so
+/// interval changes apply to the running loop.
 pub struct ServiceMonitor {
     manager: Arc<Mutex<ServiceManager>>,
-    config: MonitorConfig,
+    config: Arc<Mutex<MonitorConfig>>,
     last_state: Arc<Mutex<HashMap<String, Service>>>,
 }
 
@@ -59,130 +201,834 @@ impl ServiceMonitor {
     pub fn new(manager: Arc<Mutex<ServiceManager>>) -> Self {
         Self {
             manager,
-            config: MonitorConfig::default(),
+            config: Arc::new(Mutex::new(MonitorConfig::default())),
             last_state: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
-    pub fn with_config(mut self, config: MonitorConfig) -> Self {
+    pub fn with_config(mut self, config: Arc<Mutex<MonitorConfig>>) -> Self {
         self.config = config;
         self
     }
 
-    /// Start the monitoring loop
-    pub async fn start(&self, app_handle: AppHandle) {
-        if !self.config.enabled {
-            return;
+    /// Check a service (or, with `None`, everything) on the next tick, which
+    /// starts immediately. The service stays hot for `hot_period`.
+    pub fn request_refresh(service_id: Option<&str>) {
+        {
+            let mut requests = refresh_requests().lock().unwrap_or_else(|e| e.into_inner());
+            match service_id {
+                Some(id) => {
+                    requests.services.insert(id.to_string());
+                }
+                None => requests.full = true,
+            }
         }
+        refresh_notify().notify_one();
+    }
 
+    /// Re-check a service every `check_interval` until it is unwatched
+    pub fn watch(service_id: &str) -> Result<(), String> {
+        {
+            let mut watched = watched().lock().unwrap_or_else(|e| e.into_inner());
+            if !watched.contains(service_id) && watched.len() >= MAX_WATCHED_SERVICES {
+                return Err(format!("Es können höchstens {} Dienste beobachtet werden", MAX_WATCHED_SERVICES));
+            }
+            watched.insert(service_id.to_string());
+        }
+        refresh_notify().notify_one();
+        Ok(())
+    }
+
+    pub fn unwatch(service_id: &str) {
+        watched().lock().unwrap_or_else(|e| e.into_inner()).remove(service_id);
+    }
+
+    pub fn watched_services() -> Vec<String> {
+        let mut ids: Vec<String> = watched().lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Start the monitoring loop; it runs until the returned handle is aborted
+    pub fn start(&self, app_handle: AppHandle) -> tauri::async_runtime::JoinHandle<()> {
         let manager = Arc::clone(&self.manager);
         let last_state = Arc::clone(&self.last_state);
-        let interval = self.config.check_interval;
+        let shared_config = Arc::clone(&self.config);
 
-        tokio::spawn(async move {
+        tauri::async_runtime::spawn(async move {
+            let mut hot_until: HashMap<String, Instant> = HashMap::new();
+            let mut last_full: Option<Instant> = None;
             loop {
-                // Discover current services
-                let services = {
-                    let mgr = manager.lock().await;
-                    mgr.discover_all().await
-                };
+                let config = shared_config.lock().await.clone();
+                let requests = std::mem::take(&mut *refresh_requests().lock().unwrap_or_else(|e| e.into_inner()));
+                let now = Instant::now();
+                for id in requests.services {
+                    hot_until.insert(id, now + config.hot_period);
+                }
+                hot_until.retain(|_, until| *until > now);
+                let watched = watched().lock().unwrap_or_else(|e| e.into_inner()).clone();
 
-                // Build current state map
-                let current_state: HashMap<String, Service> = services
-                    .iter()
-                    .map(|s| (s.id.clone(), s.clone()))
-                    .collect();
+                let previous = last_state.lock().await.clone();
+                let full = requests.full
+                    || previous.is_empty()
+                    || last_full.is_none_or(|t| now.duration_since(t) >= config.full_interval);
 
-                // Compare with last state
-                let mut state = last_state.lock().await;
+                let current: HashMap<String, Service> = if full {
+                    last_full = Some(now);
+                    let services = manager.lock().await.discover_all().await;
+                    services.into_iter().map(|s| (s.id.clone(), s)).collect()
+                } else {
+                    let hot: Vec<Service> = previous
+                        .values()
+                        .filter(|s| hot_until.contains_key(&s.id) || watched.contains(&s.id))
+                        .cloned()
+                        .collect();
+                    let mut current = previous.clone();
+                    if !hot.is_empty() {
+                        for service in &hot {
+                            current.remove(&service.id);
+                        }
+                        for service in manager.lock().await.refresh(&hot).await {
+                            current.insert(service.id.clone(), service);
+                        }
+                    }
+                    current
+                };
 
-                if state.is_empty() {
+                if previous.is_empty() {
                     // First run - emit all services
-                    let _ = app_handle.emit("service-event", ServiceEvent::ServicesDiscovered(services.clone()));
+                    let services: Vec<Service> = current.values().cloned().collect();
+                    emit_service_event(&app_handle, ServiceEvent::ServicesDiscovered(services));
                 } else {
-                    // Check for changes
-                    for (id, service) in &current_state {
-                        if let Some(old_service) = state.get(id) {
-                            // Check if status changed
-                            let old_status = format!("{:?}", old_service.status);
-                            let new_status = format!("{:?}", service.status);
-                            if old_status != new_status {
-                                let _ = app_handle.emit(
-                                    "service-event",
-                                    ServiceEvent::ServiceStatusChanged {
-                                        service_id: id.clone(),
-                                        old_status,
-                                        new_status,
-                                    },
-                                );
-                            }
-
-                            // Check if ports changed
-                            if old_service.ports != service.ports {
-                                let _ = app_handle.emit(
-                                    "service-event",
-                                    ServiceEvent::ServicePortsChanged {
-                                        service_id: id.clone(),
-                                        ports: service.ports.clone(),
-                                    },
-                                );
-                            }
-                        } else {
-                            // New service detected
-                            let _ = app_handle.emit(
-                                "service-event",
-                                ServiceEvent::ServiceAdded(service.clone()),
-                            );
+                    for id in emit_changes(&app_handle, &previous, &current) {
+                        hot_until.insert(id, now + config.hot_period);
+                    }
+                }
+
+                // Update last state
+                *last_state.lock().await = current;
+
+                // Fast ticks only while something is hot or watched
+                let wait = if hot_until.is_empty() && watched.is_empty() {
+                    config
+                        .full_interval
+                        .saturating_sub(last_full.map(|t| t.elapsed()).unwrap_or_default())
+                } else {
+                    config.check_interval
+                };
+                tokio::select! {
+                    _ = tokio::time::sleep(wait) => {}
+                    _ = refresh_notify().notified() => {}
+                }
+            }
+        })
+    }
+}
+
+fn refresh_requests() -> &'static std::sync::Mutex<RefreshRequests> {
+    REFRESH_REQUESTS.get_or_init(|| std::sync::Mutex::new(RefreshRequests::default()))
+}
+
+fn refresh_notify() -> &'static tokio::sync::Notify {
+    REFRESH_NOTIFY.get_or_init(tokio::sync::Notify::new)
+}
+
+fn watched() -> &'static std::sync::Mutex<HashSet<String>> {
+    WATCHED.get_or_init(|| std::sync::Mutex::new(HashSet::new()))
+}
+
+/// Emits events for everything that differs between two ticks and returns
+/// the services that changed
+fn emit_changes(app_handle: &AppHandle, previous: &HashMap<String, Service>, current: &HashMap<String, Service>) -> Vec<String> {
+    let mut changed = Vec::new();
+    for (id, service) in current {
+        let Some(old_service) = previous.get(id) else {
+            // New service detected
+            emit_service_event(app_handle, ServiceEvent::ServiceAdded(service.clone()));
+            ResourceTracker::changed(service);
+            changed.push(id.clone());
+            continue;
+        };
+
+        if ResourceTracker::changed(service) {
+            emit_service_event(
+                app_handle,
+                ServiceEvent::ServiceResourceChanged {
+                    service_id: id.clone(),
+                    cpu: service.cpu_usage,
+                    memory: service.memory_bytes,
+                },
+            );
+        }
+
+        // Check if status changed
+        if old_service.status == ServiceStatus::Running && service.status == ServiceStatus::Error {
+            notify_crashed(app_handle, service);
+        }
+        if is_restart(old_service, service) {
+            if let Some(restarts) = FlapDetector::record(id, Utc::now()) {
+                notify_flapping(app_handle, service, restarts);
+                emit_service_event(
+                    app_handle,
+                    ServiceEvent::ServiceFlapping {
+                        service_id: id.clone(),
+                        restarts,
+                        window_minutes: FlapDetector::window_minutes(),
+                    },
+                );
+            }
+        }
+        let old_status = status_name(&old_service.status);
+        let new_status = status_name(&service.status);
+        if old_status != new_status {
+            emit_service_event(
+                app_handle,
+                ServiceEvent::ServiceStatusChanged {
+                    service_id: id.clone(),
+                    old_status,
+                    new_status,
+                },
+            );
+        }
+
+        // Check if ports changed
+        if old_service.ports != service.ports {
+            emit_service_event(
+                app_handle,
+                ServiceEvent::ServicePortsChanged {
+                    service_id: id.clone(),
+                    ports: service.ports.clone(),
+                },
+            );
+        }
+
+        if old_service.status != service.status || old_service.pid != service.pid || old_service.ports != service.ports {
+            changed.push(id.clone());
+        }
+    }
+
+    // Check for removed services
+    for id in previous.keys() {
+        if !current.contains_key(id) {
+            ResourceTracker::forget(id);
+            emit_service_event(
+                app_handle,
+                ServiceEvent::ServiceRemoved {
+                    service_id: id.clone(),
+                },
+            );
+        }
+    }
+    changed
+}
+
+/// Back to running, or running under a new PID (e.g. a container or unit
+/// that restarted between two ticks)
+fn is_restart(old: &Service, new: &Service) -> bool {
+    if new.status != ServiceStatus::Running {
+        return false;
+    }
+    old.status != ServiceStatus::Running || (old.pid.is_some() && new.pid.is_some() && old.pid != new.pid)
+}
+
+#[derive(Default)]
+struct FlapState {
+    config: FlappingConfig,
+    restarts: HashMap<String, VecDeque<DateTime<Utc>>>,
+    /// Services already reported, until they calm down again
+    flapping: HashSet<String>,
+}
+
+/// Crash loop detection: restart times per service as seen by the
+/// `ServiceMonitor`. A service keeps looking "running" at every poll while
+/// it restarts, so only the restart count gives it away.
+pub struct FlapDetector;
+
+impl FlapDetector {
+    pub fn configure(config: &FlappingConfig) {
+        flap_state().lock().unwrap_or_else(|e| e.into_inner()).config = config.clone();
+    }
+
+    pub fn window_minutes() -> u32 {
+        flap_state().lock().unwrap_or_else(|e| e.into_inner()).config.window_minutes
+    }
+
+    /// Records a restart; returns the count when the service just started flapping
+    pub fn record(service_id: &str, now: DateTime<Utc>) -> Option<u32> {
+        let mut state = flap_state().lock().unwrap_or_else(|e| e.into_inner());
+        let window = restart_window(&state.config);
+        let limit = state.config.max_restarts;
+        let FlapState { restarts, flapping, .. } = &mut *state;
+
+        let times = restarts.entry(service_id.to_string()).or_default();
+        times.push_back(now);
+        prune_restarts(times, now, window);
+        let count = times.len() as u32;
+        if count <= limit {
+            flapping.remove(service_id);
+            return None;
+        }
+        flapping.insert(service_id.to_string()).then_some(count)
+    }
+
+    /// Fills `recent_restarts` and `flapping` for services the monitor tracks
+    pub fn annotate(services: &mut [Service]) {
+        let mut state = flap_state().lock().unwrap_or_else(|e| e.into_inner());
+        let now = Utc::now();
+        let window = restart_window(&state.config);
+        let limit = state.config.max_restarts;
+        let FlapState { restarts, flapping, .. } = &mut *state;
+        restarts.retain(|id, times| {
+            prune_restarts(times, now, window);
+            if times.len() as u32 <= limit {
+                flapping.remove(id);
+            }
+            !times.is_empty()
+        });
+        for service in services {
+            let count = restarts.get(&service.id).map(|t| t.len() as u32).unwrap_or(0);
+            service.recent_restarts = count;
+            service.flapping = count > limit;
+        }
+    }
+}
+
+fn flap_state() -> &'static std::sync::Mutex<FlapState> {
+    FLAPPING.get_or_init(|| std::sync::Mutex::new(FlapState::default()))
+}
+
+fn restart_window(config: &FlappingConfig) -> chrono::Duration {
+    chrono::Duration::minutes(config.window_minutes.max(1) as i64)
+}
+
+fn prune_restarts(times: &mut VecDeque<DateTime<Utc>>, now: DateTime<Utc>, window: chrono::Duration) {
+    while times.front().is_some_and(|t| now - *t > window) {
+        times.pop_front();
+    }
+}
+
+#[derive(Default)]
+struct ResourceState {
+    config: ResourceEventsConfig,
+    /// Usage as last reported per service
+    reported: HashMap<String, (Option<f32>, Option<u64>)>,
+}
+
+/// Decides when a service's resource usage changed enough to report. Deltas
+/// are measured against the last reported value, so slow drift is reported
+/// too once it adds up.
+pub struct ResourceTracker;
+
+impl ResourceTracker {
+    pub fn configure(config: &ResourceEventsConfig) {
+        resource_state().lock().unwrap_or_else(|e| e.into_inner()).config = config.clone();
+    }
+
+    /// Whether the usage of `service` should be reported; a first sighting
+    /// is only recorded. Reported values become the new baseline.
+    fn changed(service: &Service) -> bool {
+        let mut state = resource_state().lock().unwrap_or_else(|e| e.into_inner());
+        if !state.config.enabled {
+            return false;
+        }
+        let current = (service.cpu_usage, service.memory_bytes);
+        let Some(&(cpu, memory)) = state.reported.get(&service.id) else {
+            state.reported.insert(service.id.clone(), current);
+            return false;
+        };
+
+        let cpu_changed = match (cpu, current.0) {
+            (Some(old), Some(new)) => (new - old).abs() >= state.config.cpu_delta_percent,
+            (old, new) => old.is_some() != new.is_some(),
+        };
+        let memory_changed = match (memory, current.1) {
+            (Some(old), Some(new)) => old.abs_diff(new) >= state.config.memory_delta_bytes,
+            (old, new) => old.is_some() != new.is_some(),
+        };
+        if cpu_changed || memory_changed {
+            state.reported.insert(service.id.clone(), current);
+        }
+        cpu_changed || memory_changed
+    }
+
+    fn forget(service_id: &str) {
+        resource_state().lock().unwrap_or_else(|e| e.into_inner()).reported.remove(service_id);
+    }
+}
+
+fn resource_state() -> &'static std::sync::Mutex<ResourceState> {
+    RESOURCES.get_or_init(|| std::sync::Mutex::new(ResourceState::default()))
+}
+
+/// Port watch: diffs port usage between ticks and emits `PortOpened`/`PortClosed`
+/// service events, with a desktop notification for newly public listeners.
+/// Reserved ports and the port baseline are checked on every tick, even with
+/// the watch disabled.
+pub struct PortWatcher;
+
+impl PortWatcher {
+    pub fn configure(config: &PortWatchConfig) {
+        *port_watch().lock().unwrap_or_else(|e| e.into_inner()) = config.clone();
+    }
+
+    pub fn start(app_handle: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            // None while the watch is disabled, so re-enabling doesn't report
+            // everything that changed in between
+            let mut last_ports: Option<HashMap<String, PortInfo>> = None;
+            // Conflicts already reported, so a squatter is only reported once
+            let mut taken: HashSet<(u16, Option<u32>)> = HashSet::new();
+            // Deviations already reported, by listener and owner
+            let mut deviating: HashSet<(String, Option<u32>)> = HashSet::new();
+            loop {
+                let config = port_watch().lock().unwrap_or_else(|e| e.into_inner()).clone();
+                if !config.enabled {
+                    last_ports = None;
+                }
+                if !config.enabled && PortReservations::list().is_empty() && PortBaseline::get().is_none() {
+                    taken.clear();
+                    deviating.clear();
+                    tokio::time::sleep(Duration::from_secs(config.interval_seconds.max(1))).await;
+                    continue;
+                }
+
+                let ports = tokio::task::spawn_blocking(current_ports).await.unwrap_or_default();
+
+                let conflicts = PortReservations::conflicts(&ports);
+                let current_taken: HashSet<(u16, Option<u32>)> = conflicts.iter().map(|(_, p)| (p.port, p.pid)).collect();
+                for (reservation, port) in conflicts {
+                    if taken.contains(&(port.port, port.pid)) {
+                        continue;
+                    }
+                    notify_reserved_port(&app_handle, &port, &reservation.label);
+                    emit_service_event(
+                        &app_handle,
+                        ServiceEvent::ReservedPortTaken {
+                            port,
+                            label: reservation.label,
+                            owner: reservation.owner,
+                        },
+                    );
+                }
+                taken = current_taken;
+
+                let deviations = PortBaseline::deviations(&ports);
+                let current_deviating: HashSet<(String, Option<u32>)> =
+                    deviations.iter().map(|d| (port_key(&d.port), d.port.pid)).collect();
+                for deviation in deviations {
+                    if deviating.contains(&(port_key(&deviation.port), deviation.port.pid))
+                        || !UserScope::is_visible(deviation.port.user.as_deref())
+                    {
+                        continue;
+                    }
+                    notify_baseline_deviation(&app_handle, &deviation);
+                    emit_service_event(&app_handle, ServiceEvent::BaselineDeviation(deviation));
+                }
+                deviating = current_deviating;
+
+                if !config.enabled {
+                    tokio::time::sleep(Duration::from_secs(config.interval_seconds.max(1))).await;
+                    continue;
+                }
+                let current: HashMap<String, PortInfo> = ports.into_iter().map(|p| (port_key(&p), p)).collect();
+
+                if let Some(previous) = &last_ports {
+                    for (key, port) in &current {
+                        if previous.contains_key(key) {
+                            continue;
+                        }
+                        if UserScope::is_visible(port.user.as_deref()) {
+                            emit_service_event(&app_handle, ServiceEvent::PortOpened(port.clone()));
+                        }
+                        if config.notify_public_ports && !port.is_loopback() {
+                            notify_public_port(&app_handle, port);
                         }
                     }
+                    for (key, port) in previous {
+                        if !current.contains_key(key) && UserScope::is_visible(port.user.as_deref()) {
+                            emit_service_event(&app_handle, ServiceEvent::PortClosed(port.clone()));
+                        }
+                    }
+                }
+                last_ports = Some(current);
+
+                tokio::time::sleep(Duration::from_secs(config.interval_seconds.max(1))).await;
+            }
+        });
+    }
+}
+
+/// Disk watch: emits `DiskSpaceLow` with a desktop notification when a
+/// filesystem crosses the fill threshold. Reported again only after it
+/// dropped below the threshold in between.
+pub struct DiskWatcher;
+
+impl DiskWatcher {
+    pub fn configure(config: &DiskWatchConfig) {
+        *disk_watch().lock().unwrap_or_else(|e| e.into_inner()) = config.clone();
+    }
 
-                    // Check for removed services
-                    for id in state.keys() {
-                        if !current_state.contains_key(id) {
-                            let _ = app_handle.emit(
-                                "service-event",
-                                ServiceEvent::ServiceRemoved {
-                                    service_id: id.clone(),
-                                },
-                            );
+    pub fn start(app_handle: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            // Mount points currently above the threshold
+            let mut full: HashSet<String> = HashSet::new();
+            loop {
+                let config = disk_watch().lock().unwrap_or_else(|e| e.into_inner()).clone();
+                if config.enabled && !MockDiscovery::is_enabled() {
+                    let disks = tokio::task::spawn_blocking(SystemMonitor::disk_usage).await.unwrap_or_default();
+                    let mut current = HashSet::new();
+                    for disk in disks.into_iter().filter(|d| d.usage_percent >= config.threshold_percent) {
+                        current.insert(disk.mount_point.clone());
+                        if full.contains(&disk.mount_point) {
+                            continue;
                         }
+                        notify_disk_space(&app_handle, &disk);
+                        emit_service_event(&app_handle, ServiceEvent::DiskSpaceLow(disk));
                     }
+                    full = current;
+                } else {
+                    full.clear();
                 }
+                tokio::time::sleep(Duration::from_secs(config.interval_seconds.max(1))).await;
+            }
+        });
+    }
+}
 
-                // Update last state
-                *state = current_state;
+/// Memory samples of the last `MEMORY_TREND_WINDOW`
+#[derive(Default)]
+struct MemoryTrend {
+    samples: VecDeque<(Instant, MemoryStats)>,
+}
+
+impl MemoryTrend {
+    fn push(&mut self, now: Instant, memory: MemoryStats) {
+        self.samples.push_back((now, memory));
+        while self.samples.front().is_some_and(|(t, _)| now.duration_since(*t) > MEMORY_TREND_WINDOW) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Change of available memory and of used swap in bytes per minute,
+    /// `None` until the samples span `MEMORY_TREND_MIN_SPAN`
+    fn per_minute(&self) -> Option<(f64, f64)> {
+        let (first_at, first) = self.samples.front()?;
+        let (last_at, last) = self.samples.back()?;
+        let span = last_at.duration_since(*first_at);
+        if span < MEMORY_TREND_MIN_SPAN {
+            return None;
+        }
+        let minutes = span.as_secs_f64() / 60.0;
+        Some((
+            (last.available_bytes as f64 - first.available_bytes as f64) / minutes,
+            (last.swap_used_bytes as f64 - first.swap_used_bytes as f64) / minutes,
+        ))
+    }
+}
 
-                // Wait for next interval
-                tokio::time::sleep(interval).await;
+/// Memory watch: follows available memory and swap and emits
+/// `MemoryPressure` with a desktop notification when memory is low, swap
+/// grows fast or available memory is projected to run out soon. Reported
+/// again only after the pressure went away in between.
+pub struct MemoryWatcher;
+
+impl MemoryWatcher {
+    pub fn configure(config: &MemoryWatchConfig) {
+        *memory_watch().lock().unwrap_or_else(|e| e.into_inner()) = config.clone();
+    }
+
+    pub fn start(app_handle: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut trend = MemoryTrend::default();
+            let mut under_pressure = false;
+            loop {
+                let config = memory_watch().lock().unwrap_or_else(|e| e.into_inner()).clone();
+                if config.enabled && !MockDiscovery::is_enabled() {
+                    let memory = SystemMonitor::shared().lock().await.memory_usage();
+                    trend.push(Instant::now(), memory.clone());
+
+                    let (available_trend, swap_growth) = trend.per_minute().unwrap_or_default();
+                    let minutes_until_exhausted =
+                        (available_trend < 0.0).then(|| (memory.available_bytes as f64 / -available_trend) as f32);
+                    let low = memory.total_bytes > 0
+                        && (memory.available_bytes as f64 / memory.total_bytes as f64 * 100.0)
+                            < config.available_percent as f64;
+                    let swapping = config.swap_growth_mb_per_minute > 0
+                        && swap_growth >= (config.swap_growth_mb_per_minute * 1024 * 1024) as f64;
+                    let exhausting = minutes_until_exhausted.is_some_and(|m| m <= config.exhaustion_minutes as f32);
+
+                    let pressure = low || swapping || exhausting;
+                    if pressure && !under_pressure {
+                        let top_consumers = SystemMonitor::shared()
+                            .lock()
+                            .await
+                            .top_processes(ProcessSort::Memory, config.top_consumers)
+                            .unwrap_or_default();
+                        let event = MemoryPressure {
+                            memory,
+                            available_trend_bytes_per_minute: available_trend as i64,
+                            swap_growth_bytes_per_minute: swap_growth as i64,
+                            minutes_until_exhausted,
+                            top_consumers,
+                        };
+                        notify_memory_pressure(&app_handle, &event);
+                        emit_service_event(&app_handle, ServiceEvent::MemoryPressure(event));
+                    }
+                    under_pressure = pressure;
+                } else {
+                    trend = MemoryTrend::default();
+                    under_pressure = false;
+                }
+                tokio::time::sleep(Duration::from_secs(config.interval_seconds.max(1))).await;
             }
         });
     }
 }
 
-/// Commands for controlling the monitor
+fn memory_watch() -> &'static std::sync::Mutex<MemoryWatchConfig> {
+    MEMORY_WATCH.get_or_init(|| std::sync::Mutex::new(MemoryWatchConfig::default()))
+}
+
+fn disk_watch() -> &'static std::sync::Mutex<DiskWatchConfig> {
+    DISK_WATCH.get_or_init(|| std::sync::Mutex::new(DiskWatchConfig::default()))
+}
+
+fn port_watch() -> &'static std::sync::Mutex<PortWatchConfig> {
+    PORT_WATCH.get_or_init(|| std::sync::Mutex::new(PortWatchConfig::default()))
+}
+
+pub(crate) fn current_ports() -> Vec<PortInfo> {
+    if MockDiscovery::is_enabled() {
+        return MockDiscovery::new().port_usage();
+    }
+    PortResolver::new().get_port_usage()
+}
+
+/// Owner changes (e.g. a restarted service) don't count as a new listener
+pub(crate) fn port_key(port: &PortInfo) -> String {
+    format!("{:?}/{:?}/{}/{}", port.protocol, port.family, port.bind_address, port.port)
+}
+
+fn notify_public_port(app_handle: &AppHandle, port: &PortInfo) {
+    let owner = match (&port.process_name, port.pid) {
+        (Some(name), Some(pid)) => format!("{} (PID {})", name, pid),
+        (Some(name), None) => name.clone(),
+        _ => "unbekannter Prozess".to_string(),
+    };
+    Notifier::send(
+        app_handle,
+        NotificationCategory::PortOpened,
+        format!("Neuer öffentlicher Port {}", port.port),
+        format!("{} lauscht auf {}:{} ({:?})", owner, port.bind_address, port.port, port.protocol),
+    );
+}
+
+fn notify_baseline_deviation(app_handle: &AppHandle, deviation: &BaselineDeviation) {
+    let port = &deviation.port;
+    let owner = port.process_name.as_deref().unwrap_or("Unbekannter Prozess");
+    let body = match &deviation.expected_process {
+        Some(expected) => format!("{} statt {} auf {}:{}", owner, expected, port.bind_address, port.port),
+        None => format!("{} lauscht auf {}:{} ({:?})", owner, port.bind_address, port.port, port.protocol),
+    };
+    Notifier::send(
+        app_handle,
+        NotificationCategory::PortOpened,
+        format!("Port {} weicht von der Baseline ab", port.port),
+        body,
+    );
+}
+
+/// Same spelling as the serialized `ServiceStatus`, which the frontend expects
+fn status_name(status: &ServiceStatus) -> String {
+    format!("{:?}", status).to_lowercase()
+}
+
+fn notify_crashed(app_handle: &AppHandle, service: &Service) {
+    let pid = service.pid.map(|pid| format!(" (PID {})", pid)).unwrap_or_default();
+    Notifier::send(
+        app_handle,
+        NotificationCategory::ServiceCrashed,
+        format!("Dienst {} abgestürzt", service.name),
+        format!("{}{} ist in den Fehlerzustand gewechselt", service.name, pid),
+    );
+}
+
+fn notify_flapping(app_handle: &AppHandle, service: &Service, restarts: u32) {
+    Notifier::send(
+        app_handle,
+        NotificationCategory::ServiceCrashed,
+        format!("Dienst {} startet ständig neu", service.name),
+        format!("{} Neustarts in {} Minuten", restarts, FlapDetector::window_minutes()),
+    );
+}
+
+fn notify_disk_space(app_handle: &AppHandle, disk: &DiskStats) {
+    Notifier::send(
+        app_handle,
+        NotificationCategory::DiskSpace,
+        format!("{} fast voll", disk.mount_point),
+        format!(
+            "{:.0}% belegt, noch {:.1} GB frei",
+            disk.usage_percent,
+            disk.available_bytes as f64 / 1024.0 / 1024.0 / 1024.0
+        ),
+    );
+}
+
+fn notify_memory_pressure(app_handle: &AppHandle, pressure: &MemoryPressure) {
+    let gb = |bytes: u64| bytes as f64 / 1024.0 / 1024.0 / 1024.0;
+    let mut body = format!("Noch {:.1} GB frei", gb(pressure.memory.available_bytes));
+    if let Some(minutes) = pressure.minutes_until_exhausted {
+        body.push_str(&format!(", in etwa {:.0} Minuten erschöpft", minutes.ceil()));
+    }
+    if let Some(top) = pressure.top_consumers.first() {
+        body.push_str(&format!(". Größter Verbraucher: {} ({:.1} GB)", top.name, gb(top.memory_bytes)));
+    }
+    Notifier::send(app_handle, NotificationCategory::MemoryPressure, "Arbeitsspeicher wird knapp", body);
+}
+
+fn notify_reserved_port(app_handle: &AppHandle, port: &PortInfo, label: &str) {
+    let owner = match (&port.process_name, port.pid) {
+        (Some(name), Some(pid)) => format!("{} (PID {})", name, pid),
+        (Some(name), None) => name.clone(),
+        _ => "Ein unbekannter Prozess".to_string(),
+    };
+    Notifier::send(
+        app_handle,
+        NotificationCategory::PortOpened,
+        format!("Reservierter Port {} belegt", port.port),
+        format!("{} belegt den für \"{}\" reservierten Port", owner, label),
+    );
+}
+
+// Commands for controlling the monitor
+
+/// Changes the interval for hot services; the running loop picks it up
+/// on its next tick, which starts immediately
 #[tauri::command]
 pub async fn set_monitor_interval(seconds: u64, state: tauri::State<'_, MonitorState>) -> Result<(), String> {
-    let mut config = state.config.lock().await;
-    config.check_interval = Duration::from_secs(seconds);
+    if seconds == 0 {
+        return Err("Das Intervall muss mindestens 1 Sekunde betragen".into());
+    }
+    state.config.lock().await.check_interval = Duration::from_secs(seconds);
+    refresh_notify().notify_one();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn enable_monitor(enabled: bool, app: AppHandle, state: tauri::State<'_, MonitorState>) -> Result<(), String> {
+    if enabled {
+        start_monitor(app, state).await
+    } else {
+        stop_monitor(state).await
+    }
+}
+
+/// Start the monitor loop if it is not running yet
+#[tauri::command]
+pub async fn start_monitor(app: AppHandle, state: tauri::State<'_, MonitorState>) -> Result<(), String> {
+    state.config.lock().await.enabled = true;
+    state.start(app).await;
     Ok(())
 }
 
+/// Stop the monitor loop; no service events are emitted until it is started again
 #[tauri::command]
-pub async fn enable_monitor(enabled: bool, state: tauri::State<'_, MonitorState>) -> Result<(), String> {
-    let mut config = state.config.lock().await;
-    config.enabled = enabled;
+pub async fn stop_monitor(state: tauri::State<'_, MonitorState>) -> Result<(), String> {
+    state.config.lock().await.enabled = false;
+    state.stop();
     Ok(())
 }
 
+/// Check a service at the fast interval while full discovery stays rare
+#[tauri::command]
+pub async fn watch_service(service_id: String) -> Result<(), String> {
+    ServiceMonitor::watch(&service_id)
+}
+
+#[tauri::command]
+pub async fn unwatch_service(service_id: String) -> Result<(), String> {
+    ServiceMonitor::unwatch(&service_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_watched_services() -> Result<Vec<String>, String> {
+    Ok(ServiceMonitor::watched_services())
+}
+
+/// Buffered service events newer than `since` (all buffered ones without),
+/// oldest first
+#[tauri::command]
+pub async fn get_recent_events(
+    since: Option<DateTime<Utc>>,
+    state: tauri::State<'_, MonitorState>,
+) -> Result<Vec<RecordedEvent>, String> {
+    Ok(state.recent_events(since))
+}
+
 /// State for the monitor that can be managed by Tauri
 pub struct MonitorState {
-    pub config: Mutex<MonitorConfig>,
+    pub config: Arc<Mutex<MonitorConfig>>,
+    task: std::sync::Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+    /// The last `RECENT_EVENTS` events from all emitters
+    events: std::sync::Mutex<EventBuffer>,
+}
+
+impl MonitorState {
+    /// Start the loop unless it is running or disabled. A restarted loop
+    /// begins with a fresh `ServicesDiscovered`.
+    pub async fn start(&self, app_handle: AppHandle) -> bool {
+        if !self.config.lock().await.enabled {
+            return false;
+        }
+        let mut task = self.task.lock().unwrap_or_else(|e| e.into_inner());
+        if task.is_some() {
+            return false;
+        }
+        let monitor = ServiceMonitor::new(Arc::new(Mutex::new(ServiceManager::new())))
+            .with_config(Arc::clone(&self.config));
+        *task = Some(monitor.start(app_handle));
+        true
+    }
+
+    pub fn stop(&self) {
+        if let Some(handle) = self.task.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            handle.abort();
+        }
+    }
+
+    fn record(&self, event: ServiceEvent) {
+        let mut buffer = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        buffer.next_id += 1;
+        let id = buffer.next_id;
+        if buffer.events.len() >= RECENT_EVENTS {
+            buffer.events.pop_front();
+        }
+        buffer.events.push_back(RecordedEvent {
+            id,
+            timestamp: Utc::now(),
+            event,
+        });
+    }
+
+    pub fn recent_events(&self, since: Option<DateTime<Utc>>) -> Vec<RecordedEvent> {
+        let buffer = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        buffer
+            .events
+            .iter()
+            .filter(|e| since.is_none_or(|since| e.timestamp > since))
+            .cloned()
+            .collect()
+    }
 }
 
 impl Default for MonitorState {
     fn default() -> Self {
         Self {
-            config: Mutex::new(MonitorConfig::default()),
+            config: Arc::new(Mutex::new(MonitorConfig::default())),
+            task: std::sync::Mutex::new(None),
+            events: std::sync::Mutex::new(EventBuffer::default()),
         }
     }
 }
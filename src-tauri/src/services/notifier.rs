@@ -0,0 +1,61 @@
+use crate::models::config::NotificationConfig;
+use std::sync::{Mutex, OnceLock};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+static SETTINGS: OnceLock<Mutex<NotificationConfig>> = OnceLock::new();
+
+/// Event categories that can raise a desktop notification
+#[derive(Debug, Clone, Copy)]
+pub enum NotificationCategory {
+    ServiceCrashed,
+    SecurityCritical,
+    PortOpened,
+    Alert,
+    DiskSpace,
+    MemoryPressure,
+}
+
+/// OS notifications through the notification plugin, filtered by the
+/// per-category toggles in `Config.notifications`. They are shown by the
+/// backend, so they also arrive while the window is hidden or minimized.
+pub struct Notifier;
+
+impl Notifier {
+    pub fn configure(config: &NotificationConfig) {
+        *settings().lock().unwrap_or_else(|e| e.into_inner()) = config.clone();
+    }
+
+    pub fn is_enabled(category: NotificationCategory) -> bool {
+        let config = settings().lock().unwrap_or_else(|e| e.into_inner());
+        match category {
+            NotificationCategory::ServiceCrashed => config.service_crashed,
+            NotificationCategory::SecurityCritical => config.security_critical,
+            NotificationCategory::PortOpened => config.port_opened,
+            NotificationCategory::Alert => config.alerts,
+            NotificationCategory::DiskSpace => config.disk_space,
+            NotificationCategory::MemoryPressure => config.memory_pressure,
+        }
+    }
+
+    /// Whether closing the window should hide it instead of quitting
+    pub fn run_in_background() -> bool {
+        settings().lock().unwrap_or_else(|e| e.into_inner()).run_in_background
+    }
+
+    pub fn send(app_handle: &AppHandle, category: NotificationCategory, title: impl Into<String>, body: impl Into<String>) {
+        if !Self::is_enabled(category) {
+            return;
+        }
+        let _ = app_handle
+            .notification()
+            .builder()
+            .title(title)
+            .body(body)
+            .show();
+    }
+}
+
+fn settings() -> &'static Mutex<NotificationConfig> {
+    SETTINGS.get_or_init(|| Mutex::new(NotificationConfig::default()))
+}
@@ -0,0 +1,321 @@
+// Installed vs. latest versions of service binaries from the package managers
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use crate::models::service::Service;
+use crate::services::cmd;
+use crate::services::security_scanner::{SecurityCategory, SecurityIssue, SecuritySeverity};
+
+/// A package with a newer version available
+#[derive(Debug, Clone)]
+struct Upgrade {
+    manager: &'static str,
+    package: String,
+    installed: String,
+    available: String,
+}
+
+/// Finds the package that installed each service's executable and asks
+/// its package manager (Homebrew, apt, dnf or winget) for pending upgrades.
+/// Only the local package metadata is read (no `apt update`, no Homebrew
+/// auto-update, dnf from its cache), so results are as fresh as the last
+/// refresh of the package lists.
+pub struct OutdatedSoftware;
+
+impl OutdatedSoftware {
+    /// Blocking: runs the package managers once per scan
+    pub fn findings(services: &[Service]) -> Vec<SecurityIssue> {
+        let executables = executables(services);
+        if executables.is_empty() {
+            return Vec::new();
+        }
+
+        let mut lists = Upgrades::default();
+        let mut upgrades: HashMap<String, (Upgrade, Vec<&Service>)> = HashMap::new();
+        for (service, path) in &executables {
+            if let Some(upgrade) = lists.lookup(path, &service.name) {
+                upgrades
+                    .entry(upgrade.package.clone())
+                    .or_insert_with(|| (upgrade, Vec::new()))
+                    .1
+                    .push(*service);
+            }
+        }
+
+        let mut issues: Vec<SecurityIssue> = upgrades
+            .into_values()
+            .map(|(upgrade, services)| {
+                let service = services[0];
+                let names: Vec<&str> = services.iter().map(|s| s.name.as_str()).collect();
+                SecurityIssue {
+                    id: format!("outdated-{}-{}", upgrade.manager, upgrade.package),
+                    service_id: Some(service.id.clone()),
+                    service_name: Some(service.name.clone()),
+                    category: SecurityCategory::OutdatedSoftware,
+                    severity: SecuritySeverity::Medium,
+                    title: format!("{} ist veraltet ({} → {})", upgrade.package, upgrade.installed, upgrade.available),
+                    description: format!(
+                        "Für das Paket hinter {} gibt es eine neuere Version, die Sicherheitslücken schließen kann",
+                        names.join(", ")
+                    ),
+                    recommendation: upgrade_command(&upgrade),
+                    port: service.ports.first().copied(),
+                    details: Some(format!("{}: {}", upgrade.manager, upgrade.package)),
+                }
+            })
+            .collect();
+        issues.sort_by(|a, b| a.id.cmp(&b.id));
+        issues
+    }
+}
+
+fn upgrade_command(upgrade: &Upgrade) -> String {
+    match upgrade.manager {
+        "brew" => format!("brew upgrade {}", upgrade.package),
+        "apt" => format!("sudo apt install --only-upgrade {}", upgrade.package),
+        "dnf" => format!("sudo dnf upgrade {}", upgrade.package),
+        _ => format!("winget upgrade --id {}", upgrade.package),
+    }
+}
+
+/// Executable of each running service, deduplicated by path
+fn executables(services: &[Service]) -> Vec<(&Service, PathBuf)> {
+    let pids: Vec<Pid> = services.iter().filter_map(|s| s.pid).map(Pid::from_u32).collect();
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&pids),
+        true,
+        ProcessRefreshKind::nothing().with_exe(UpdateKind::Always),
+    );
+
+    let mut seen = Vec::new();
+    services
+        .iter()
+        .filter_map(|service| {
+            let path = service
+                .pid
+                .and_then(|pid| system.process(Pid::from_u32(pid)))
+                .and_then(|p| p.exe())
+                .map(Path::to_path_buf)
+                .or_else(|| service.path.as_ref().map(PathBuf::from).filter(|p| p.is_file()))?;
+            if seen.contains(&path) {
+                return None;
+            }
+            seen.push(path.clone());
+            Some((service, path))
+        })
+        .collect()
+}
+
+/// The upgrade list of each package manager, read on first use per scan
+#[derive(Default)]
+struct Upgrades {
+    brew: Option<Vec<Upgrade>>,
+    apt: Option<Vec<Upgrade>>,
+    dnf: Option<Vec<Upgrade>>,
+    winget: Option<Vec<Upgrade>>,
+}
+
+impl Upgrades {
+    fn lookup(&mut self, path: &Path, service_name: &str) -> Option<Upgrade> {
+        // Homebrew installs into `<prefix>/Cellar/<formula>/<version>/`
+        if let Some(formula) = brew_formula(path) {
+            let list = self.brew.get_or_insert_with(brew_outdated);
+            return list.iter().find(|u| u.package == formula).cloned();
+        }
+        if cfg!(target_os = "linux") {
+            if cmd::find_in_path("dpkg").is_some() {
+                let package = dpkg_package(path)?;
+                let list = self.apt.get_or_insert_with(apt_upgradable);
+                return list.iter().find(|u| u.package == package).cloned();
+            }
+            if cmd::find_in_path("rpm").is_some() {
+                let package = rpm_package(path)?;
+                let list = self.dnf.get_or_insert_with(dnf_upgradable);
+                return list.iter().find(|u| u.package == package).cloned();
+            }
+        }
+        if cfg!(windows) {
+            let list = self.winget.get_or_insert_with(winget_upgradable);
+            return winget_match(list, path, service_name);
+        }
+        None
+    }
+}
+
+fn brew_formula(path: &Path) -> Option<String> {
+    let mut components = path.components().map(|c| c.as_os_str().to_string_lossy());
+    components.find(|c| c == "Cellar")?;
+    components.next().map(|c| c.to_string())
+}
+
+/// `brew outdated --json=v2` without touching the network
+fn brew_outdated() -> Vec<Upgrade> {
+    let Ok(output) = cmd::command("brew")
+        .args(["outdated", "--formula", "--json=v2"])
+        .env("HOMEBREW_NO_AUTO_UPDATE", "1")
+        .output()
+    else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_slice::<serde_json::Value>(&output.stdout) else {
+        return Vec::new();
+    };
+    json["formulae"]
+        .as_array()
+        .map(|formulae| {
+            formulae
+                .iter()
+                .filter_map(|f| {
+                    Some(Upgrade {
+                        manager: "brew",
+                        package: f["name"].as_str()?.to_string(),
+                        installed: f["installed_versions"].as_array()?.last()?.as_str()?.to_string(),
+                        available: f["current_version"].as_str()?.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `dpkg -S` records `/bin/...` on merged-usr systems where the executable
+/// resolves to `/usr/bin/...`, so both are tried
+fn dpkg_package(path: &Path) -> Option<String> {
+    let path = path.to_string_lossy();
+    let mut candidates = vec![path.to_string()];
+    if let Some(stripped) = path.strip_prefix("/usr") {
+        candidates.push(stripped.to_string());
+    }
+    candidates.into_iter().find_map(|candidate| {
+        let output = cmd::command("dpkg").args(["-S", &candidate]).output().ok()?;
+        // `nginx-core: /usr/sbin/nginx`, `libc-bin:amd64: /sbin/ldconfig`
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (package, _) = stdout.lines().next()?.split_once(": ")?;
+        Some(package.split(':').next()?.to_string())
+    })
+}
+
+/// `nginx-core/jammy-updates 1.18.0-6ubuntu14.5 amd64 [upgradable from: 1.18.0-6ubuntu14.4]`
+fn apt_upgradable() -> Vec<Upgrade> {
+    let Ok(output) = cmd::command("apt").args(["list", "--upgradable"]).output() else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (package, rest) = line.split_once('/')?;
+            let available = rest.split_whitespace().nth(1)?;
+            let installed = rest.split("upgradable from: ").nth(1)?.trim_end_matches(']');
+            Some(Upgrade {
+                manager: "apt",
+                package: package.to_string(),
+                installed: installed.to_string(),
+                available: available.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn rpm_package(path: &Path) -> Option<String> {
+    let output = cmd::command("rpm")
+        .args(["-qf", "--qf", "%{NAME}\\n", &path.to_string_lossy()])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(stdout.split_whitespace().next()?.to_string())
+}
+
+/// `dnf -C check-update` lists `nginx.x86_64  1:1.24.0-1.fc39  updates` from
+/// the metadata cache; installed versions come from rpm
+fn dnf_upgradable() -> Vec<Upgrade> {
+    let Ok(output) = cmd::command("dnf").args(["-C", "-q", "check-update"]).output() else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (name, _arch) = fields.next()?.rsplit_once('.')?;
+            let available = fields.next()?;
+            fields.next()?;
+            let installed = cmd::command("rpm")
+                .args(["-q", "--qf", "%{VERSION}-%{RELEASE}", name])
+                .output()
+                .ok()
+                .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+                .unwrap_or_default();
+            Some(Upgrade {
+                manager: "dnf",
+                package: name.to_string(),
+                installed,
+                available: available.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// `winget upgrade` prints a table with localized headers; the columns are
+/// Name, Id, Version, Available and Source, found by their offsets
+fn winget_upgradable() -> Vec<Upgrade> {
+    let Ok(output) = cmd::command("winget")
+        .args(["upgrade", "--accept-source-agreements", "--disable-interactivity"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().map(|line| line.rsplit('\r').next().unwrap_or(line)).collect();
+    let Some(separator) = lines.iter().position(|line| line.starts_with("---")) else {
+        return Vec::new();
+    };
+    let Some(header) = separator.checked_sub(1).and_then(|i| lines.get(i)) else {
+        return Vec::new();
+    };
+    // Start offset of each header word
+    let header: Vec<char> = header.chars().collect();
+    let starts: Vec<usize> = (0..header.len())
+        .filter(|&i| !header[i].is_whitespace() && (i == 0 || header[i - 1].is_whitespace()))
+        .collect();
+    if starts.len() < 4 {
+        return Vec::new();
+    }
+
+    lines[separator + 1..]
+        .iter()
+        .filter_map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            let column = |index: usize| -> String {
+                let start = starts[index].min(chars.len());
+                let end = starts.get(index + 1).copied().unwrap_or(chars.len()).min(chars.len());
+                chars[start..end].iter().collect::<String>().trim().to_string()
+            };
+            let id = column(1);
+            let available = column(3);
+            (!id.is_empty() && !available.is_empty()).then(|| Upgrade {
+                manager: "winget",
+                package: id,
+                installed: column(2),
+                available,
+            })
+        })
+        .collect()
+}
+
+/// winget doesn't map files to packages; match the executable's name
+/// against the package ID, e.g. `postgres` in `PostgreSQL.PostgreSQL.16`
+fn winget_match(upgrades: &[Upgrade], path: &Path, service_name: &str) -> Option<Upgrade> {
+    let stem = path.file_stem()?.to_string_lossy().to_lowercase();
+    let service = service_name.to_lowercase();
+    upgrades
+        .iter()
+        .find(|u| {
+            let id = u.package.to_lowercase();
+            (stem.len() >= 4 && id.contains(&stem)) || (service.len() >= 4 && id.contains(&service))
+        })
+        .cloned()
+}
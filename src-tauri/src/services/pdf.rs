@@ -0,0 +1,234 @@
+// Minimal PDF writer for text reports
+
+/// A4 in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const LINE_SPACING: f32 = 1.35;
+
+/// Font size, weight, color and left indent of a paragraph
+#[derive(Debug, Clone, Copy)]
+pub struct TextStyle {
+    pub size: f32,
+    pub bold: bool,
+    /// RGB, each 0.0..=1.0
+    pub color: (f32, f32, f32),
+    pub indent: f32,
+}
+
+impl TextStyle {
+    pub const TITLE: TextStyle = TextStyle { size: 20.0, bold: true, color: (0.1, 0.1, 0.1), indent: 0.0 };
+    pub const HEADING: TextStyle = TextStyle { size: 14.0, bold: true, color: (0.1, 0.1, 0.1), indent: 0.0 };
+    pub const BODY: TextStyle = TextStyle { size: 10.0, bold: false, color: (0.1, 0.1, 0.1), indent: 0.0 };
+    pub const MUTED: TextStyle = TextStyle { size: 9.0, bold: false, color: (0.4, 0.4, 0.4), indent: 0.0 };
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub fn color(mut self, color: (f32, f32, f32)) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn indent(mut self, indent: f32) -> Self {
+        self.indent = indent;
+        self
+    }
+}
+
+/// Builds a PDF of wrapped text paragraphs on A4 pages with the built-in
+/// Helvetica fonts, so no font has to be embedded. Text is encoded as
+/// WinAnsi, which covers German; other characters become `?`.
+pub struct PdfDocument {
+    /// Content streams of the finished pages
+    pages: Vec<String>,
+    current: String,
+    /// Baseline of the next line, from the bottom of the page
+    y: f32,
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            current: String::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    /// Text wrapped to the page width; starts a new page when it runs out
+    pub fn paragraph(&mut self, text: &str, style: TextStyle) {
+        let width = PAGE_WIDTH - 2.0 * MARGIN - style.indent;
+        for line in text.lines() {
+            for wrapped in wrap(line, width, style) {
+                self.line(&wrapped, style);
+            }
+        }
+    }
+
+    /// Vertical space in points
+    pub fn gap(&mut self, points: f32) {
+        self.y -= points;
+    }
+
+    /// Start a new page unless at least `points` are left on this one
+    pub fn keep(&mut self, points: f32) {
+        if self.y - points < MARGIN {
+            self.new_page();
+        }
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        if !self.current.is_empty() || self.pages.is_empty() {
+            self.pages.push(std::mem::take(&mut self.current));
+        }
+
+        // 1 catalog, 2 page tree, 3 and 4 fonts, then page and content per page
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 5 + 2 * i).collect();
+        let mut objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+                page_ids.len()
+            )
+            .into_bytes(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_vec(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_vec(),
+        ];
+        for (page, content) in page_ids.iter().zip(&self.pages) {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    page + 1
+                )
+                .into_bytes(),
+            );
+            let stream = encode(content);
+            let mut object = format!("<< /Length {} >>\nstream\n", stream.len()).into_bytes();
+            object.extend(stream);
+            object.extend(b"\nendstream");
+            objects.push(object);
+        }
+
+        let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend(format!("{} 0 obj\n", index + 1).into_bytes());
+            out.extend(object);
+            out.extend(b"\nendobj\n");
+        }
+        let xref = out.len();
+        out.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+        for offset in offsets {
+            out.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+        }
+        out.extend(format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref).into_bytes());
+        out
+    }
+
+    fn line(&mut self, text: &str, style: TextStyle) {
+        let height = style.size * LINE_SPACING;
+        if self.y - height < MARGIN {
+            self.new_page();
+        }
+        self.y -= height;
+        let (r, g, b) = style.color;
+        self.current.push_str(&format!(
+            "BT /{} {} Tf {:.3} {:.3} {:.3} rg {:.1} {:.1} Td ({}) Tj ET\n",
+            if style.bold { "F2" } else { "F1" },
+            style.size,
+            r,
+            g,
+            b,
+            MARGIN + style.indent,
+            self.y,
+            escape(&text.replace('→', "->"))
+        ));
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(std::mem::take(&mut self.current));
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+}
+
+impl Default for PdfDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Greedy word wrap; words longer than a line are split
+fn wrap(text: &str, width: f32, style: TextStyle) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split(' ') {
+        let candidate = if current.is_empty() { word.to_string() } else { format!("{} {}", current, word) };
+        if text_width(&candidate, style) <= width {
+            current = candidate;
+            continue;
+        }
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        for c in word.chars() {
+            current.push(c);
+            if text_width(&current, style) > width {
+                let last = current.pop().unwrap_or(c);
+                lines.push(std::mem::take(&mut current));
+                current.push(last);
+            }
+        }
+    }
+    lines.push(current);
+    lines
+}
+
+/// Approximate Helvetica advance widths, in 1/1000 em
+fn char_width(c: char, bold: bool) -> f32 {
+    let width = match c {
+        'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '\'' | '|' | '!' => 250.0,
+        'f' | 't' | 'r' | 'I' | ' ' | '(' | ')' | '[' | ']' | '/' | '-' => 320.0,
+        'm' | 'w' | 'M' | 'W' | '@' => 850.0,
+        'A'..='Z' | 'Ä' | 'Ö' | 'Ü' => 700.0,
+        '0'..='9' => 556.0,
+        _ => 540.0,
+    };
+    if bold { width * 1.06 } else { width }
+}
+
+fn text_width(text: &str, style: TextStyle) -> f32 {
+    text.chars().map(|c| char_width(c, style.bold)).sum::<f32>() * style.size / 1000.0
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('(', "\\(").replace(')', "\\)")
+}
+
+/// Content stream bytes in WinAnsi; Latin-1 maps directly
+fn encode(content: &str) -> Vec<u8> {
+    content
+        .chars()
+        .map(|c| match c {
+            '\u{20}'..='\u{7e}' | '\n' => c as u8,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '€' => 0x80,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '„' => 0x84,
+            _ => b'?',
+        })
+        .collect()
+}
@@ -0,0 +1,246 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::models::port::{PortInfo, Protocol};
+use super::fingerprint::probe_address;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_RESPONSE: usize = 16 * 1024;
+
+/// Services checked for access without credentials
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthProbeTarget {
+    Redis,
+    MongoDb,
+    Elasticsearch,
+    Memcached,
+}
+
+impl AuthProbeTarget {
+    const ALL: [AuthProbeTarget; 4] = [Self::Redis, Self::MongoDb, Self::Elasticsearch, Self::Memcached];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Redis => "Redis",
+            Self::MongoDb => "MongoDB",
+            Self::Elasticsearch => "Elasticsearch",
+            Self::Memcached => "Memcached",
+        }
+    }
+
+    pub fn default_port(&self) -> u16 {
+        match self {
+            Self::Redis => 6379,
+            Self::MongoDb => 27017,
+            Self::Elasticsearch => 9200,
+            Self::Memcached => 11211,
+        }
+    }
+
+    /// Service name the fingerprint pass reports for this protocol
+    fn fingerprint(&self) -> Option<&'static str> {
+        match self {
+            Self::Redis => Some("redis"),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of a read-only command sent without credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthProbe {
+    pub target: AuthProbeTarget,
+    pub port: u16,
+    /// The command succeeded, anyone who can connect can read the data
+    pub unauthenticated: bool,
+    /// What the server answered, in German
+    pub evidence: String,
+}
+
+/// Probe every listening TCP port of a known datastore. Ports that did not
+/// answer the protocol (TLS only, other service, timeout) are left out, so
+/// their findings stay speculative.
+pub fn probe_ports(ports: &[PortInfo]) -> Vec<AuthProbe> {
+    let mut targets: Vec<(AuthProbeTarget, SocketAddr)> = Vec::new();
+    for port in ports.iter().filter(|p| matches!(p.protocol, Protocol::Tcp)) {
+        let target = AuthProbeTarget::ALL.into_iter().find(|t| {
+            port.port == t.default_port() || (t.fingerprint().is_some() && port.detected_service.as_deref() == t.fingerprint())
+        });
+        let Some(target) = target else {
+            continue;
+        };
+        // Dual-stack listeners are the same server, probe it once
+        if targets.iter().any(|(_, addr)| addr.port() == port.port) {
+            continue;
+        }
+        targets.push((target, probe_address(port)));
+    }
+
+    let mut results: Vec<AuthProbe> = std::thread::scope(|scope| {
+        let handles: Vec<_> = targets
+            .iter()
+            .map(|&(target, addr)| scope.spawn(move || probe(target, addr)))
+            .collect();
+        handles.into_iter().filter_map(|h| h.join().ok().flatten()).collect()
+    });
+    results.sort_by_key(|r| r.port);
+    results
+}
+
+/// Send one read-only command and classify the answer. `None` if the
+/// server did not speak the expected protocol.
+pub fn probe(target: AuthProbeTarget, addr: SocketAddr) -> Option<AuthProbe> {
+    let (unauthenticated, evidence) = match target {
+        AuthProbeTarget::Redis => probe_redis(addr)?,
+        AuthProbeTarget::MongoDb => probe_mongodb(addr)?,
+        AuthProbeTarget::Elasticsearch => probe_elasticsearch(addr)?,
+        AuthProbeTarget::Memcached => probe_memcached(addr)?,
+    };
+    Some(AuthProbe {
+        target,
+        port: addr.port(),
+        unauthenticated,
+        evidence,
+    })
+}
+
+/// Send `request` and read until the server stops sending or `complete` is satisfied
+fn exchange(addr: SocketAddr, request: &[u8], complete: impl Fn(&[u8]) -> bool) -> Option<Vec<u8>> {
+    let mut stream = TcpStream::connect_timeout(&addr, PROBE_TIMEOUT).ok()?;
+    stream.set_read_timeout(Some(PROBE_TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(PROBE_TIMEOUT)).ok()?;
+    stream.write_all(request).ok()?;
+
+    let mut response = Vec::new();
+    let mut buffer = [0u8; 4096];
+    while response.len() < MAX_RESPONSE && !complete(&response) {
+        match stream.read(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(read) => response.extend_from_slice(&buffer[..read]),
+        }
+    }
+    (!response.is_empty()).then_some(response)
+}
+
+/// `DBSIZE` reads no data but needs an authenticated connection if a
+/// password or ACL is set
+fn probe_redis(addr: SocketAddr) -> Option<(bool, String)> {
+    let reply = exchange(addr, b"DBSIZE\r\n", |r| r.ends_with(b"\r\n"))?;
+    let reply = String::from_utf8_lossy(&reply);
+    if let Some(keys) = reply.strip_prefix(':') {
+        return Some((true, format!("DBSIZE ohne Passwort beantwortet ({} Schlüssel)", keys.trim())));
+    }
+    (reply.starts_with("-NOAUTH") || reply.starts_with("-NOPERM"))
+        .then(|| (false, "Server verlangt Authentifizierung (NOAUTH)".to_string()))
+}
+
+/// `listDatabases` via OP_MSG, answered with `ok: 1` only when access control is off
+fn probe_mongodb(addr: SocketAddr) -> Option<(bool, String)> {
+    let reply = exchange(addr, &mongodb_list_databases(), |r| {
+        r.len() >= 4 && r.len() >= i32::from_le_bytes([r[0], r[1], r[2], r[3]]) as usize
+    })?;
+    // Header (16 bytes), flag bits (4), section kind (1), then the BSON document
+    if reply.len() < 21 || i32::from_le_bytes([reply[12], reply[13], reply[14], reply[15]]) != 2013 {
+        return None;
+    }
+    let document = &reply[21..];
+    let ok = bson_double(document, "ok")?;
+    if ok == 1.0 {
+        return Some((true, "listDatabases ohne Anmeldung erfolgreich".to_string()));
+    }
+    let code = bson_int32(document, "code");
+    Some((false, match code {
+        Some(13) => "listDatabases verweigert (Unauthorized)".to_string(),
+        Some(code) => format!("listDatabases verweigert (Code {})", code),
+        None => "listDatabases verweigert".to_string(),
+    }))
+}
+
+/// OP_MSG `{ listDatabases: 1, nameOnly: true, $db: "admin" }`
+fn mongodb_list_databases() -> Vec<u8> {
+    let mut document = Vec::new();
+    document.push(0x10);
+    document.extend_from_slice(b"listDatabases\0");
+    document.extend_from_slice(&1i32.to_le_bytes());
+    document.push(0x08);
+    document.extend_from_slice(b"nameOnly\0");
+    document.push(1);
+    document.push(0x02);
+    document.extend_from_slice(b"$db\0");
+    document.extend_from_slice(&6i32.to_le_bytes());
+    document.extend_from_slice(b"admin\0");
+    document.push(0);
+    let document = [((document.len() + 4) as i32).to_le_bytes().as_slice(), &document].concat();
+
+    let mut message = Vec::new();
+    let length = (16 + 4 + 1 + document.len()) as i32;
+    message.extend_from_slice(&length.to_le_bytes());
+    message.extend_from_slice(&1i32.to_le_bytes()); // request ID
+    message.extend_from_slice(&0i32.to_le_bytes()); // response to
+    message.extend_from_slice(&2013i32.to_le_bytes()); // OP_MSG
+    message.extend_from_slice(&0u32.to_le_bytes()); // flag bits
+    message.push(0); // body section
+    message.extend_from_slice(&document);
+    message
+}
+
+/// Top-level double field of a BSON document. Fields are searched from
+/// the end, where the server puts `ok`, `errmsg` and `code`.
+fn bson_double(document: &[u8], name: &str) -> Option<f64> {
+    let value = bson_field(document, 0x01, name)?;
+    Some(f64::from_le_bytes(value.get(..8)?.try_into().ok()?))
+}
+
+fn bson_int32(document: &[u8], name: &str) -> Option<i32> {
+    let value = bson_field(document, 0x10, name)?;
+    Some(i32::from_le_bytes(value.get(..4)?.try_into().ok()?))
+}
+
+/// Bytes after the element header `<type> <name> 0x00`
+fn bson_field<'a>(document: &'a [u8], kind: u8, name: &str) -> Option<&'a [u8]> {
+    let mut header = vec![kind];
+    header.extend_from_slice(name.as_bytes());
+    header.push(0);
+    let start = document.windows(header.len()).rposition(|w| w == header.as_slice())?;
+    Some(&document[start + header.len()..])
+}
+
+/// `_cluster/health` returns 401 when security is enabled. Elasticsearch 8
+/// serves HTTPS by default and does not answer plain HTTP at all.
+fn probe_elasticsearch(addr: SocketAddr) -> Option<(bool, String)> {
+    let request = b"GET /_cluster/health HTTP/1.0\r\nHost: localhost\r\nAccept: application/json\r\n\r\n";
+    let reply = exchange(addr, request, |_| false)?;
+    let reply = String::from_utf8_lossy(&reply);
+    let status: u16 = reply.strip_prefix("HTTP/")?.split_whitespace().nth(1)?.parse().ok()?;
+    match status {
+        200 if reply.contains("cluster_name") => {
+            let cluster = reply
+                .split("\"cluster_name\":\"")
+                .nth(1)
+                .and_then(|rest| rest.split('"').next())
+                .unwrap_or_default();
+            Some((true, format!("_cluster/health ohne Anmeldung beantwortet (Cluster {})", cluster)))
+        }
+        401 | 403 => Some((false, format!("_cluster/health verweigert (HTTP {})", status))),
+        _ => None,
+    }
+}
+
+/// `stats` is answered by every memcached without authentication; with an
+/// auth file it answers `CLIENT_ERROR unauthenticated`
+fn probe_memcached(addr: SocketAddr) -> Option<(bool, String)> {
+    let reply = exchange(addr, b"stats\r\n", |r| r.ends_with(b"END\r\n") || r.ends_with(b"ERROR\r\n"))?;
+    let reply = String::from_utf8_lossy(&reply);
+    if reply.starts_with("STAT ") {
+        let items = reply
+            .lines()
+            .find_map(|line| line.strip_prefix("STAT curr_items "))
+            .unwrap_or("?");
+        return Some((true, format!("stats ohne Authentifizierung beantwortet ({} Einträge)", items.trim())));
+    }
+    reply
+        .contains("ERROR")
+        .then(|| (false, "stats verweigert, Authentifizierung aktiv".to_string()))
+}
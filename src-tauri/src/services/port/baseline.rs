@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::port::{AddressFamily, PortInfo, Protocol};
+use crate::services::monitor::port_key;
+use crate::services::security_scanner::{SecurityCategory, SecurityIssue, SecuritySeverity};
+use crate::services::storage::Storage;
+
+/// UDP sockets from this port up are mostly clients with an ephemeral port
+/// (DNS lookups, mDNS, QUIC), which would deviate on every tick
+const UDP_EPHEMERAL_START: u16 = 32768;
+
+static BASELINE: OnceLock<Mutex<Option<ListenerBaseline>>> = OnceLock::new();
+
+/// A listener as recorded in the baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineListener {
+    pub port: u16,
+    pub protocol: Protocol,
+    pub family: AddressFamily,
+    pub bind_address: String,
+    pub process_name: Option<String>,
+}
+
+/// The listeners that were expected when the baseline was recorded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListenerBaseline {
+    pub recorded_at: DateTime<Utc>,
+    pub listeners: Vec<BaselineListener>,
+}
+
+/// A listener that isn't part of the baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineDeviation {
+    pub port: PortInfo,
+    /// Process recorded for the same address and port, when another process
+    /// listens there now
+    pub expected_process: Option<String>,
+}
+
+/// Snapshot of the listening ports to compare later port tables against.
+/// Every listener that wasn't there when the baseline was recorded, or that
+/// belongs to another process now, is a deviation. Stored on disk, so it
+/// survives restarts until it is recorded again or cleared.
+pub struct PortBaseline;
+
+impl PortBaseline {
+    /// Replace the baseline with the given port table
+    pub fn record(ports: &[PortInfo]) -> ListenerBaseline {
+        let mut listeners: Vec<BaselineListener> = ports
+            .iter()
+            .filter(|p| is_compared(p))
+            .map(|p| BaselineListener {
+                port: p.port,
+                protocol: p.protocol.clone(),
+                family: p.family,
+                bind_address: p.bind_address.clone(),
+                process_name: p.process_name.clone(),
+            })
+            .collect();
+        listeners.sort_by_cached_key(|l| (l.port, listener_key(l), l.process_name.clone()));
+        listeners.dedup_by(|a, b| listener_key(a) == listener_key(b) && a.process_name == b.process_name);
+
+        let baseline = ListenerBaseline {
+            recorded_at: Utc::now(),
+            listeners,
+        };
+        save(&baseline);
+        *registry().lock().unwrap_or_else(|e| e.into_inner()) = Some(baseline.clone());
+        baseline
+    }
+
+    pub fn get() -> Option<ListenerBaseline> {
+        registry().lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Returns false if no baseline was recorded
+    pub fn clear() -> bool {
+        let removed = registry().lock().unwrap_or_else(|e| e.into_inner()).take().is_some();
+        if removed {
+            std::fs::remove_file(path()).ok();
+        }
+        removed
+    }
+
+    /// Listeners in `ports` that the baseline doesn't expect; empty without
+    /// a baseline
+    pub fn deviations(ports: &[PortInfo]) -> Vec<BaselineDeviation> {
+        let Some(baseline) = Self::get() else {
+            return Vec::new();
+        };
+        ports
+            .iter()
+            .filter(|p| is_compared(p))
+            .filter_map(|port| {
+                let key = port_key(port);
+                let recorded: Vec<&BaselineListener> = baseline.listeners.iter().filter(|l| listener_key(l) == key).collect();
+                if recorded.is_empty() {
+                    return Some(BaselineDeviation { port: port.clone(), expected_process: None });
+                }
+                // Unknown owners (another user's process without root) can't be compared
+                let known = recorded.iter().any(|l| match (&l.process_name, &port.process_name) {
+                    (Some(expected), Some(actual)) => expected.eq_ignore_ascii_case(actual),
+                    _ => true,
+                });
+                (!known).then(|| BaselineDeviation {
+                    port: port.clone(),
+                    expected_process: recorded.iter().find_map(|l| l.process_name.clone()),
+                })
+            })
+            .collect()
+    }
+
+    /// Deviations as security findings
+    pub fn findings(ports: &[PortInfo]) -> Vec<SecurityIssue> {
+        let Some(baseline) = Self::get() else {
+            return Vec::new();
+        };
+        Self::deviations(ports)
+            .into_iter()
+            .map(|deviation| {
+                let port = &deviation.port;
+                let owner = port.process_name.clone().unwrap_or_else(|| "Unbekannter Prozess".to_string());
+                let public = !port.is_loopback();
+                let (title, description) = match &deviation.expected_process {
+                    Some(expected) => (
+                        format!("{} lauscht auf Port {} statt {}", owner, port.port, expected),
+                        format!("Laut Baseline gehört {}:{} zu {}; ein anderer Prozess hat den Port übernommen", port.bind_address, port.port, expected),
+                    ),
+                    None => (
+                        format!("Neuer Listener auf Port {} ({})", port.port, owner),
+                        format!("{} lauscht auf {}:{}, was in der Baseline nicht vorkommt", owner, port.bind_address, port.port),
+                    ),
+                };
+                SecurityIssue {
+                    id: format!("baseline-deviation-{}", port_key(port)),
+                    service_id: None,
+                    service_name: port.process_name.clone(),
+                    category: if public { SecurityCategory::PublicExposure } else { SecurityCategory::InsecureConfiguration },
+                    severity: if public { SecuritySeverity::High } else { SecuritySeverity::Medium },
+                    title,
+                    description,
+                    recommendation: "Prüfe, ob der Listener gewollt ist; falls ja, nimm die Baseline neu auf".to_string(),
+                    port: Some(port.port),
+                    details: Some(format!(
+                        "{:?} {}:{}{}, Baseline vom {}",
+                        port.protocol,
+                        port.bind_address,
+                        port.port,
+                        port.pid.map(|pid| format!(", PID {}", pid)).unwrap_or_default(),
+                        baseline.recorded_at.format("%d.%m.%Y %H:%M UTC")
+                    )),
+                }
+            })
+            .collect()
+    }
+}
+
+fn is_compared(port: &PortInfo) -> bool {
+    !(port.protocol == Protocol::Udp && port.port >= UDP_EPHEMERAL_START)
+}
+
+/// Same key as `port_key` for a live listener
+fn listener_key(listener: &BaselineListener) -> String {
+    format!("{:?}/{:?}/{}/{}", listener.protocol, listener.family, listener.bind_address, listener.port)
+}
+
+fn registry() -> &'static Mutex<Option<ListenerBaseline>> {
+    BASELINE.get_or_init(|| Mutex::new(load()))
+}
+
+fn path() -> PathBuf {
+    Storage::data_dir().join("port_baseline.json")
+}
+
+fn load() -> Option<ListenerBaseline> {
+    std::fs::read(path()).ok().and_then(|data| serde_json::from_slice(&data).ok())
+}
+
+fn save(baseline: &ListenerBaseline) {
+    let path = path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+    if let Ok(data) = serde_json::to_vec_pretty(baseline) {
+        std::fs::write(&path, data).ok();
+    }
+}
@@ -0,0 +1,192 @@
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream};
+use std::time::Duration;
+use crate::models::port::{AddressFamily, PortInfo, Protocol};
+use super::tls;
+
+/// Read/connect timeout for each probe
+const PROBE_TIMEOUT: Duration = Duration::from_millis(800);
+const MAX_RESPONSE: usize = 4096;
+/// Ports probed in parallel by `fingerprint_ports`
+const PARALLEL_PROBES: usize = 32;
+
+/// Protocol and version detected on an open port
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fingerprint {
+    /// Protocol name, e.g. `ssh`, `http`, `redis`, `tls`
+    pub service: String,
+    /// Product/version string as announced by the server
+    pub version: Option<String>,
+}
+
+impl Fingerprint {
+    fn new(service: &str, version: Option<String>) -> Self {
+        Self {
+            service: service.to_string(),
+            version: version.filter(|v| !v.is_empty()),
+        }
+    }
+}
+
+/// Identify the service on an open TCP port. Probes run on separate
+/// connections, from passive to intrusive: server banner, HTTP HEAD,
+/// Redis PING and a TLS ClientHello.
+pub fn fingerprint(addr: SocketAddr) -> Option<Fingerprint> {
+    if let Some(banner) = exchange(addr, None) {
+        if let Some(found) = parse_banner(&banner, addr.port()) {
+            return Some(found);
+        }
+    }
+    if let Some(response) = exchange(addr, Some(b"HEAD / HTTP/1.0\r\n\r\n")) {
+        if let Some(found) = parse_http(&response) {
+            return Some(found);
+        }
+    }
+    if let Some(found) = probe_redis(addr) {
+        return Some(found);
+    }
+    if let Some(response) = exchange(addr, Some(&tls::client_hello(tls::TLS_1_2, tls::DEFAULT_SUITES, None, false))) {
+        // Handshake (ServerHello) or alert record, both mean TLS
+        if matches!(response.first(), Some(0x16) | Some(0x15)) && response.get(1) == Some(&0x03) {
+            return Some(Fingerprint::new("tls", None));
+        }
+    }
+    None
+}
+
+/// Fingerprint listening TCP ports from the socket table in place.
+/// Wildcard bindings are probed via the loopback address of their family.
+pub fn fingerprint_ports(ports: &mut [PortInfo]) {
+    let mut targets: Vec<&mut PortInfo> = ports
+        .iter_mut()
+        .filter(|p| matches!(p.protocol, Protocol::Tcp) && p.detected_service.is_none())
+        .collect();
+
+    for chunk in targets.chunks_mut(PARALLEL_PROBES) {
+        std::thread::scope(|scope| {
+            for port in chunk.iter_mut() {
+                scope.spawn(move || {
+                    if let Some(found) = fingerprint(probe_address(port)) {
+                        port.detected_service = Some(found.service);
+                        port.version = found.version;
+                    }
+                });
+            }
+        });
+    }
+}
+
+pub(crate) fn probe_address(port: &PortInfo) -> SocketAddr {
+    let loopback = match port.family {
+        AddressFamily::Ipv4 => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        AddressFamily::Ipv6 => IpAddr::V6(Ipv6Addr::LOCALHOST),
+    };
+    let ip = if port.is_wildcard() {
+        loopback
+    } else {
+        port.bind_address.parse().unwrap_or(loopback)
+    };
+    SocketAddr::new(ip, port.port)
+}
+
+/// Connect, optionally send `request`, and return whatever arrives before the timeout
+fn exchange(addr: SocketAddr, request: Option<&[u8]>) -> Option<Vec<u8>> {
+    let mut stream = TcpStream::connect_timeout(&addr, PROBE_TIMEOUT).ok()?;
+    stream.set_read_timeout(Some(PROBE_TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(PROBE_TIMEOUT)).ok()?;
+
+    if let Some(request) = request {
+        stream.write_all(request).ok()?;
+    }
+
+    let mut buffer = vec![0u8; MAX_RESPONSE];
+    let read = stream.read(&mut buffer).ok()?;
+    (read > 0).then(|| {
+        buffer.truncate(read);
+        buffer
+    })
+}
+
+/// Services that speak first: SSH, FTP, SMTP, POP3, IMAP, MySQL
+fn parse_banner(banner: &[u8], port: u16) -> Option<Fingerprint> {
+    if let Some(version) = mysql_version(banner) {
+        return Some(Fingerprint::new("mysql", Some(version)));
+    }
+
+    let text = String::from_utf8_lossy(banner);
+    let line = text.lines().next()?.trim();
+
+    if let Some(rest) = line.strip_prefix("SSH-") {
+        // SSH-2.0-OpenSSH_9.6p1 Ubuntu-3ubuntu13
+        let software = rest.split_once('-').map(|(_, s)| s).unwrap_or(rest);
+        return Some(Fingerprint::new("ssh", software.split_whitespace().next().map(String::from)));
+    }
+    if let Some(rest) = line.strip_prefix("220") {
+        let rest = rest.trim_start_matches(['-', ' ']).to_string();
+        let lower = rest.to_lowercase();
+        let service = if lower.contains("ftp") || (!lower.contains("smtp") && port == 21) {
+            "ftp"
+        } else {
+            "smtp"
+        };
+        return Some(Fingerprint::new(service, Some(rest)));
+    }
+    if let Some(rest) = line.strip_prefix("+OK") {
+        return Some(Fingerprint::new("pop3", Some(rest.trim().to_string())));
+    }
+    if let Some(rest) = line.strip_prefix("* OK") {
+        return Some(Fingerprint::new("imap", Some(rest.trim().to_string())));
+    }
+    None
+}
+
+/// MySQL/MariaDB greeting: 3-byte length, sequence 0, protocol 10, version string
+fn mysql_version(banner: &[u8]) -> Option<String> {
+    if banner.len() < 6 || banner[3] != 0 || banner[4] != 10 {
+        return None;
+    }
+    let end = banner[5..].iter().position(|&b| b == 0)?;
+    let version = std::str::from_utf8(&banner[5..5 + end]).ok()?;
+    version.chars().next()?.is_ascii_digit().then(|| version.to_string())
+}
+
+fn parse_http(response: &[u8]) -> Option<Fingerprint> {
+    let text = String::from_utf8_lossy(response);
+    if !text.starts_with("HTTP/") {
+        return None;
+    }
+    let server = text
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("server").then(|| value.trim().to_string())
+        });
+    Some(Fingerprint::new("http", server))
+}
+
+/// `PING`, then `INFO server` for the version if no password is set
+fn probe_redis(addr: SocketAddr) -> Option<Fingerprint> {
+    let mut stream = TcpStream::connect_timeout(&addr, PROBE_TIMEOUT).ok()?;
+    stream.set_read_timeout(Some(PROBE_TIMEOUT)).ok()?;
+    stream.write_all(b"PING\r\n").ok()?;
+
+    let mut buffer = [0u8; 256];
+    let read = stream.read(&mut buffer).ok()?;
+    let reply = String::from_utf8_lossy(&buffer[..read]);
+
+    if reply.starts_with("-NOAUTH") {
+        return Some(Fingerprint::new("redis", None));
+    }
+    if !reply.starts_with("+PONG") {
+        return None;
+    }
+
+    stream.write_all(b"INFO server\r\n").ok()?;
+    let mut info = vec![0u8; MAX_RESPONSE];
+    let version = stream.read(&mut info).ok().and_then(|read| {
+        String::from_utf8_lossy(&info[..read])
+            .lines()
+            .find_map(|line| line.strip_prefix("redis_version:").map(|v| v.trim().to_string()))
+    });
+    Some(Fingerprint::new("redis", version))
+}
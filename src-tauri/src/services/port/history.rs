@@ -0,0 +1,253 @@
+use std::collections::{BTreeSet, HashMap};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::config::PortHistoryConfig;
+use crate::models::port::PortInfo;
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::monitor::port_key;
+use crate::services::port::PortResolver;
+use crate::services::storage::Storage;
+
+/// An unchanged port table is still written this often, so a query can tell
+/// "nothing changed" from "the app wasn't running"
+const KEYFRAME_MINUTES: i64 = 15;
+const MIN_INTERVAL_SECONDS: u64 = 5;
+
+static SETTINGS: OnceLock<Mutex<PortHistoryConfig>> = OnceLock::new();
+
+/// Port usage at one point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub ports: Vec<PortInfo>,
+}
+
+/// One listener on a port over time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortOccupancy {
+    pub port: PortInfo,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// First snapshot without the listener, `None` if it was still there at
+    /// the end of the range
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+/// A listener whose process changed between two snapshots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortOwnerChange {
+    pub before: PortInfo,
+    pub after: PortInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortHistoryDiff {
+    /// Snapshots actually compared, at or before the requested times
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub opened: Vec<PortInfo>,
+    pub closed: Vec<PortInfo>,
+    pub owner_changed: Vec<PortOwnerChange>,
+}
+
+/// Records the port table in the background and answers questions about
+/// the past: who listened on a port, and what changed between two times.
+/// Snapshots are appended to one JSONL file per UTC day, only when the
+/// table changed or a keyframe is due.
+pub struct PortHistory;
+
+impl PortHistory {
+    pub fn configure(config: &PortHistoryConfig) {
+        *settings().lock().unwrap_or_else(|e| e.into_inner()) = config.clone();
+    }
+
+    pub fn start() {
+        tauri::async_runtime::spawn(async move {
+            // Owners of the last written snapshot and when it was written
+            let mut last: Option<(BTreeSet<String>, DateTime<Utc>)> = None;
+            let mut pruned_on: Option<NaiveDate> = None;
+            loop {
+                let config = settings().lock().unwrap_or_else(|e| e.into_inner()).clone();
+                // Simulated ports would end up next to real ones
+                if config.enabled && !MockDiscovery::is_enabled() {
+                    let ports = tokio::task::spawn_blocking(|| PortResolver::new().get_port_usage())
+                        .await
+                        .unwrap_or_default();
+                    let now = Utc::now();
+                    let signature: BTreeSet<String> =
+                        ports.iter().map(|p| format!("{}/{:?}", port_key(p), p.pid)).collect();
+                    let due = match &last {
+                        Some((previous, at)) => {
+                            *previous != signature || now - *at >= chrono::Duration::minutes(KEYFRAME_MINUTES)
+                        }
+                        None => true,
+                    };
+                    if due && append(&PortSnapshot { taken_at: now, ports }).is_ok() {
+                        last = Some((signature, now));
+                    }
+
+                    if pruned_on != Some(now.date_naive()) {
+                        prune(config.retention_days);
+                        pruned_on = Some(now.date_naive());
+                    }
+                } else {
+                    last = None;
+                }
+
+                tokio::time::sleep(Duration::from_secs(config.interval_seconds.max(MIN_INTERVAL_SECONDS))).await;
+            }
+        });
+    }
+
+    /// The last snapshot taken at or before `time`
+    pub fn at(time: DateTime<Utc>) -> Option<PortSnapshot> {
+        day_files()
+            .into_iter()
+            .rev()
+            .filter(|(day, _)| *day <= time.date_naive())
+            .find_map(|(_, path)| {
+                read_file(&path)
+                    .into_iter()
+                    .filter(|s| s.taken_at <= time)
+                    .max_by_key(|s| s.taken_at)
+            })
+    }
+
+    /// Listeners on `port` between `from` and `to`, including the ones that
+    /// were already there at `from`
+    pub fn port_timeline(port: u16, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<PortOccupancy> {
+        let mut snapshots: Vec<PortSnapshot> = Self::at(from).into_iter().collect();
+        snapshots.extend(between(from, to).into_iter().filter(|s| s.taken_at > from));
+
+        let mut open: HashMap<String, PortOccupancy> = HashMap::new();
+        let mut done = Vec::new();
+        for snapshot in snapshots {
+            let current: HashMap<String, PortInfo> = snapshot
+                .ports
+                .into_iter()
+                .filter(|p| p.port == port)
+                .map(|p| (format!("{}/{:?}", port_key(&p), p.pid), p))
+                .collect();
+
+            let ended: Vec<String> = open.keys().filter(|key| !current.contains_key(*key)).cloned().collect();
+            for key in ended {
+                if let Some(mut occupancy) = open.remove(&key) {
+                    occupancy.ended_at = Some(snapshot.taken_at);
+                    done.push(occupancy);
+                }
+            }
+            for (key, listener) in current {
+                open.entry(key)
+                    .and_modify(|o| o.last_seen = snapshot.taken_at)
+                    .or_insert(PortOccupancy {
+                        port: listener,
+                        first_seen: snapshot.taken_at,
+                        last_seen: snapshot.taken_at,
+                        ended_at: None,
+                    });
+            }
+        }
+
+        done.extend(open.into_values());
+        done.sort_by_key(|o| o.first_seen);
+        done
+    }
+
+    /// Compare the snapshots in effect at `from` and `to`
+    pub fn diff(from: DateTime<Utc>, to: DateTime<Utc>) -> Result<PortHistoryDiff, Box<dyn std::error::Error + Send + Sync>> {
+        let missing = |time: DateTime<Utc>| format!("Für {} liegt keine Port-Aufzeichnung vor", time.format("%d.%m.%Y %H:%M UTC"));
+        let before = Self::at(from).ok_or_else(|| missing(from))?;
+        let after = Self::at(to).ok_or_else(|| missing(to))?;
+
+        let old: HashMap<String, &PortInfo> = before.ports.iter().map(|p| (port_key(p), p)).collect();
+        let new: HashMap<String, &PortInfo> = after.ports.iter().map(|p| (port_key(p), p)).collect();
+
+        let mut opened: Vec<PortInfo> = new.iter().filter(|(k, _)| !old.contains_key(*k)).map(|(_, p)| (*p).clone()).collect();
+        let mut closed: Vec<PortInfo> = old.iter().filter(|(k, _)| !new.contains_key(*k)).map(|(_, p)| (*p).clone()).collect();
+        let mut owner_changed: Vec<PortOwnerChange> = new
+            .iter()
+            .filter_map(|(key, after)| {
+                let before = old.get(key)?;
+                (before.pid != after.pid || before.process_name != after.process_name).then(|| PortOwnerChange {
+                    before: (*before).clone(),
+                    after: (*after).clone(),
+                })
+            })
+            .collect();
+        opened.sort_by_key(|p| p.port);
+        closed.sort_by_key(|p| p.port);
+        owner_changed.sort_by_key(|c| c.after.port);
+
+        Ok(PortHistoryDiff {
+            from: before.taken_at,
+            to: after.taken_at,
+            opened,
+            closed,
+            owner_changed,
+        })
+    }
+}
+
+fn settings() -> &'static Mutex<PortHistoryConfig> {
+    SETTINGS.get_or_init(|| Mutex::new(PortHistoryConfig::default()))
+}
+
+fn history_dir() -> PathBuf {
+    Storage::data_dir().join("port_history")
+}
+
+fn append(snapshot: &PortSnapshot) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let dir = history_dir();
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.jsonl", snapshot.taken_at.format("%Y-%m-%d")));
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(snapshot)?)?;
+    Ok(())
+}
+
+/// Snapshot files by day, oldest first
+fn day_files() -> Vec<(NaiveDate, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(history_dir()) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(NaiveDate, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let day = NaiveDate::parse_from_str(path.file_stem()?.to_str()?, "%Y-%m-%d").ok()?;
+            (path.extension()? == "jsonl").then_some((day, path))
+        })
+        .collect();
+    files.sort_by_key(|(day, _)| *day);
+    files
+}
+
+fn read_file(path: &PathBuf) -> Vec<PortSnapshot> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn between(from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<PortSnapshot> {
+    day_files()
+        .into_iter()
+        .filter(|(day, _)| *day >= from.date_naive() && *day <= to.date_naive())
+        .flat_map(|(_, path)| read_file(&path))
+        .filter(|s| s.taken_at >= from && s.taken_at <= to)
+        .collect()
+}
+
+fn prune(retention_days: u32) {
+    let cutoff = Utc::now().date_naive() - chrono::Duration::days(retention_days.max(1) as i64);
+    for (day, path) in day_files() {
+        if day < cutoff {
+            std::fs::remove_file(path).ok();
+        }
+    }
+}
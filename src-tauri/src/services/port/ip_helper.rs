@@ -0,0 +1,212 @@
+use std::ffi::c_void;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use windows::Win32::Foundation::{ERROR_INSUFFICIENT_BUFFER, NO_ERROR};
+use windows::Win32::NetworkManagement::IpHelper::{
+    GetExtendedTcpTable, GetExtendedUdpTable, MIB_TCP6ROW_OWNER_PID, MIB_TCP6TABLE_OWNER_PID,
+    MIB_TCPROW_OWNER_PID, MIB_TCPTABLE_OWNER_PID, MIB_UDP6ROW_OWNER_PID, MIB_UDP6TABLE_OWNER_PID,
+    MIB_UDPROW_OWNER_PID, MIB_UDPTABLE_OWNER_PID, TCP_TABLE_CLASS, TCP_TABLE_OWNER_PID_CONNECTIONS,
+    TCP_TABLE_OWNER_PID_LISTENER, UDP_TABLE_OWNER_PID,
+};
+use windows::Win32::Networking::WinSock::{AF_INET, AF_INET6};
+use crate::models::port::{AddressFamily, Protocol};
+use crate::parsers::sockets::SocketRecord;
+
+/// `MIB_TCP_STATE` values from 1 (`CLOSED`) on, named like the Linux states
+const TCP_STATES: &[&str] = &[
+    "CLOSE", "LISTEN", "SYN_SENT", "SYN_RECV", "ESTABLISHED", "FIN_WAIT1",
+    "FIN_WAIT2", "CLOSE_WAIT", "CLOSING", "LAST_ACK", "TIME_WAIT", "DELETE_TCB",
+];
+
+/// Listening TCP and bound UDP sockets for IPv4 and IPv6 from the IP Helper API
+pub fn socket_table() -> Result<Vec<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut records = Vec::new();
+    records.extend(tcp4_table(TCP_TABLE_OWNER_PID_LISTENER)?);
+    records.extend(tcp6_table(TCP_TABLE_OWNER_PID_LISTENER)?);
+    records.extend(udp4_endpoints()?);
+    records.extend(udp6_endpoints()?);
+    fill_process_names(&mut records);
+    Ok(records)
+}
+
+/// Established TCP connections with their owners
+pub fn connections() -> Result<Vec<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut records = tcp4_table(TCP_TABLE_OWNER_PID_CONNECTIONS)?;
+    records.extend(tcp6_table(TCP_TABLE_OWNER_PID_CONNECTIONS)?);
+    records.retain(|record| record.state.as_deref() == Some("ESTABLISHED"));
+    fill_process_names(&mut records);
+    Ok(records)
+}
+
+/// TCP sockets in every state but LISTEN. The UDP tables carry no remote
+/// address, so connected UDP sockets can't be told apart.
+pub fn active_connections() -> Result<Vec<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut records = tcp4_table(TCP_TABLE_OWNER_PID_CONNECTIONS)?;
+    records.extend(tcp6_table(TCP_TABLE_OWNER_PID_CONNECTIONS)?);
+    fill_process_names(&mut records);
+    Ok(records)
+}
+
+/// The tables only carry PIDs
+fn fill_process_names(records: &mut [SocketRecord]) {
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+    for record in records {
+        record.process_name = record
+            .pid
+            .and_then(|pid| system.process(Pid::from_u32(pid)))
+            .map(|process| process.name().to_string_lossy().to_string());
+    }
+}
+
+fn tcp_state(state: u32, class: TCP_TABLE_CLASS) -> String {
+    if class == TCP_TABLE_OWNER_PID_LISTENER {
+        return "LISTEN".to_string();
+    }
+    (state as usize)
+        .checked_sub(1)
+        .and_then(|index| TCP_STATES.get(index))
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("STATE_{}", state))
+}
+
+fn tcp4_table(class: TCP_TABLE_CLASS) -> Result<Vec<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    let buffer = fetch_table(|table, size| unsafe {
+        GetExtendedTcpTable(table, size, false, AF_INET.0 as u32, class, 0)
+    })?;
+    let table = buffer.as_ptr() as *const MIB_TCPTABLE_OWNER_PID;
+    // `table` is declared as a one-element array; the rows follow it in the buffer
+    let rows = unsafe {
+        std::slice::from_raw_parts(
+            std::ptr::addr_of!((*table).table).cast::<MIB_TCPROW_OWNER_PID>(),
+            (*table).dwNumEntries as usize,
+        )
+    };
+
+    Ok(rows
+        .iter()
+        .map(|row| SocketRecord {
+            protocol: Protocol::Tcp,
+            family: AddressFamily::Ipv4,
+            local_address: Ipv4Addr::from(row.dwLocalAddr.to_ne_bytes()).to_string(),
+            local_port: port(row.dwLocalPort),
+            remote: (class != TCP_TABLE_OWNER_PID_LISTENER).then(|| {
+                format!("{}:{}", Ipv4Addr::from(row.dwRemoteAddr.to_ne_bytes()), port(row.dwRemotePort))
+            }),
+            state: Some(tcp_state(row.dwState, class)),
+            process_name: None,
+            pid: Some(row.dwOwningPid),
+        })
+        .collect())
+}
+
+fn tcp6_table(class: TCP_TABLE_CLASS) -> Result<Vec<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    let buffer = fetch_table(|table, size| unsafe {
+        GetExtendedTcpTable(table, size, false, AF_INET6.0 as u32, class, 0)
+    })?;
+    let table = buffer.as_ptr() as *const MIB_TCP6TABLE_OWNER_PID;
+    let rows = unsafe {
+        std::slice::from_raw_parts(
+            std::ptr::addr_of!((*table).table).cast::<MIB_TCP6ROW_OWNER_PID>(),
+            (*table).dwNumEntries as usize,
+        )
+    };
+
+    Ok(rows
+        .iter()
+        .map(|row| SocketRecord {
+            protocol: Protocol::Tcp,
+            family: AddressFamily::Ipv6,
+            local_address: Ipv6Addr::from(row.ucLocalAddr).to_string(),
+            local_port: port(row.dwLocalPort),
+            remote: (class != TCP_TABLE_OWNER_PID_LISTENER)
+                .then(|| format!("[{}]:{}", Ipv6Addr::from(row.ucRemoteAddr), port(row.dwRemotePort))),
+            state: Some(tcp_state(row.dwState, class)),
+            process_name: None,
+            pid: Some(row.dwOwningPid),
+        })
+        .collect())
+}
+
+fn udp4_endpoints() -> Result<Vec<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    let buffer = fetch_table(|table, size| unsafe {
+        GetExtendedUdpTable(table, size, false, AF_INET.0 as u32, UDP_TABLE_OWNER_PID, 0)
+    })?;
+    let table = buffer.as_ptr() as *const MIB_UDPTABLE_OWNER_PID;
+    let rows = unsafe {
+        std::slice::from_raw_parts(
+            std::ptr::addr_of!((*table).table).cast::<MIB_UDPROW_OWNER_PID>(),
+            (*table).dwNumEntries as usize,
+        )
+    };
+
+    Ok(rows
+        .iter()
+        .map(|row| SocketRecord {
+            protocol: Protocol::Udp,
+            family: AddressFamily::Ipv4,
+            local_address: Ipv4Addr::from(row.dwLocalAddr.to_ne_bytes()).to_string(),
+            local_port: port(row.dwLocalPort),
+            remote: None,
+            state: None,
+            process_name: None,
+            pid: Some(row.dwOwningPid),
+        })
+        .collect())
+}
+
+fn udp6_endpoints() -> Result<Vec<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    let buffer = fetch_table(|table, size| unsafe {
+        GetExtendedUdpTable(table, size, false, AF_INET6.0 as u32, UDP_TABLE_OWNER_PID, 0)
+    })?;
+    let table = buffer.as_ptr() as *const MIB_UDP6TABLE_OWNER_PID;
+    let rows = unsafe {
+        std::slice::from_raw_parts(
+            std::ptr::addr_of!((*table).table).cast::<MIB_UDP6ROW_OWNER_PID>(),
+            (*table).dwNumEntries as usize,
+        )
+    };
+
+    Ok(rows
+        .iter()
+        .map(|row| SocketRecord {
+            protocol: Protocol::Udp,
+            family: AddressFamily::Ipv6,
+            local_address: Ipv6Addr::from(row.ucLocalAddr).to_string(),
+            local_port: port(row.dwLocalPort),
+            remote: None,
+            state: None,
+            process_name: None,
+            pid: Some(row.dwOwningPid),
+        })
+        .collect())
+}
+
+/// Call a `GetExtended*Table` function until the buffer is large enough.
+/// The buffer is `u64`-backed so the table structs are properly aligned.
+fn fetch_table<F>(call: F) -> Result<Vec<u64>, Box<dyn std::error::Error + Send + Sync>>
+where
+    F: Fn(Option<*mut c_void>, *mut u32) -> u32,
+{
+    let mut size: u32 = 0;
+    let mut buffer: Vec<u64> = Vec::new();
+
+    // Sockets can be opened between the size query and the actual call
+    for _ in 0..4 {
+        let table = (!buffer.is_empty()).then(|| buffer.as_mut_ptr() as *mut c_void);
+        match call(table, &mut size as *mut u32) {
+            result if result == NO_ERROR.0 && !buffer.is_empty() => return Ok(buffer),
+            result if result == ERROR_INSUFFICIENT_BUFFER.0 || result == NO_ERROR.0 => {
+                buffer = vec![0u64; (size as usize).div_ceil(8).max(1)];
+            }
+            result => return Err(format!("Failed to read socket table (error {})", result).into()),
+        }
+    }
+
+    Err("Failed to read socket table: table keeps growing".into())
+}
+
+/// Ports are stored in network byte order in the low 16 bits
+fn port(value: u32) -> u16 {
+    u16::from_be(value as u16)
+}
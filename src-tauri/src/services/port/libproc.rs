@@ -0,0 +1,312 @@
+use std::ffi::{c_int, c_void};
+use std::mem::size_of;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use crate::models::port::{AddressFamily, Protocol};
+use crate::parsers::sockets::SocketRecord;
+
+// Not exported by libc, see <sys/proc_info.h>
+const PROC_PIDFDSOCKETINFO: c_int = 3;
+const SOCKINFO_IN: c_int = 1;
+const SOCKINFO_TCP: c_int = 2;
+const TSI_S_CLOSED: c_int = 0;
+const TSI_S_LISTEN: c_int = 1;
+const TSI_S_ESTABLISHED: c_int = 4;
+const IPPROTO_UDP: c_int = 17;
+/// `TSI_S_*` in order, named like the Linux states
+const TCP_STATES: &[&str] = &[
+    "CLOSE", "LISTEN", "SYN_SENT", "SYN_RECV", "ESTABLISHED", "CLOSE_WAIT",
+    "FIN_WAIT1", "CLOSING", "LAST_ACK", "FIN_WAIT2", "TIME_WAIT",
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum SocketFilter {
+    /// Listening TCP and bound UDP sockets
+    Listening,
+    /// Established TCP connections
+    Established,
+    /// TCP in every state but LISTEN and CLOSED, connected UDP
+    Active,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct ProcFileInfo {
+    openflags: u32,
+    status: u32,
+    offset: i64,
+    fd_type: i32,
+    guardflags: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct SockbufInfo {
+    cc: u32,
+    hiwat: u32,
+    mbcnt: u32,
+    mbmax: u32,
+    lowat: u32,
+    flags: i16,
+    timeo: i16,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct InSockInfoV4 {
+    tos: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct InSockInfoV6 {
+    hlim: u8,
+    cksum: c_int,
+    ifindex: u16,
+    hops: i16,
+}
+
+/// `struct in_sockinfo`. Addresses are `in4in6_addr`/`in6_addr` unions;
+/// IPv4 addresses occupy the last four bytes.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct InSockInfo {
+    fport: c_int,
+    lport: c_int,
+    gencnt: u64,
+    flags: u32,
+    flow: u32,
+    vflag: u8,
+    ip_ttl: u8,
+    rfu_1: u32,
+    faddr: [u32; 4],
+    laddr: [u32; 4],
+    v4: InSockInfoV4,
+    v6: InSockInfoV6,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct TcpSockInfo {
+    ini: InSockInfo,
+    state: c_int,
+    timer: [c_int; 4],
+    mss: c_int,
+    flags: u32,
+    rfu_1: u32,
+    tp: u64,
+}
+
+/// `soi_proto`, sized by its largest member (`un_sockinfo`)
+#[repr(C)]
+#[derive(Clone, Copy)]
+union SocketProto {
+    pri_in: InSockInfo,
+    pri_tcp: TcpSockInfo,
+    _size: [u64; 66],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct SocketInfo {
+    /// `struct vinfo_stat`, unused
+    stat: [u64; 17],
+    so: u64,
+    pcb: u64,
+    sock_type: c_int,
+    protocol: c_int,
+    family: c_int,
+    options: i16,
+    linger: i16,
+    state: i16,
+    qlen: i16,
+    incqlen: i16,
+    qlimit: i16,
+    timeo: i16,
+    error: u16,
+    oobmark: u32,
+    rcv: SockbufInfo,
+    snd: SockbufInfo,
+    kind: c_int,
+    rfu_1: u32,
+    proto: SocketProto,
+}
+
+/// `struct socket_fdinfo`
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct SocketFdInfo {
+    pfi: ProcFileInfo,
+    psi: SocketInfo,
+}
+
+/// Listening TCP and bound UDP sockets of all processes we may inspect, via
+/// `proc_pidinfo`/`proc_pidfdinfo`. Other users' processes need root, as with lsof.
+pub fn socket_table() -> Result<Vec<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    read_sockets(SocketFilter::Listening)
+}
+
+/// Established TCP connections with their owners
+pub fn connections() -> Result<Vec<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    read_sockets(SocketFilter::Established)
+}
+
+/// Sockets with a remote peer: TCP handshakes, established and closing
+/// connections, and connected UDP sockets. Unlike on Linux, TIME_WAIT
+/// sockets are gone once the process closed them.
+pub fn active_connections() -> Result<Vec<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    read_sockets(SocketFilter::Active)
+}
+
+fn read_sockets(filter: SocketFilter) -> Result<Vec<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut records = Vec::new();
+    for pid in all_pids()? {
+        let fds = socket_fds(pid);
+        if fds.is_empty() {
+            continue;
+        }
+        let name = process_name(pid);
+        for fd in fds {
+            if let Some(mut record) = socket_record(pid, fd, filter) {
+                record.pid = Some(pid as u32);
+                record.process_name = name.clone();
+                records.push(record);
+            }
+        }
+    }
+    Ok(records)
+}
+
+fn all_pids() -> Result<Vec<c_int>, Box<dyn std::error::Error + Send + Sync>> {
+    let count = unsafe { libc::proc_listallpids(std::ptr::null_mut(), 0) };
+    if count <= 0 {
+        return Err("Failed to list processes".into());
+    }
+    // Leave room for processes started in between
+    let mut pids = vec![0 as c_int; count as usize + 64];
+    let count = unsafe {
+        libc::proc_listallpids(pids.as_mut_ptr() as *mut c_void, (pids.len() * size_of::<c_int>()) as c_int)
+    };
+    if count <= 0 {
+        return Err("Failed to list processes".into());
+    }
+    pids.truncate(count as usize);
+    Ok(pids)
+}
+
+fn socket_fds(pid: c_int) -> Vec<i32> {
+    let size = unsafe { libc::proc_pidinfo(pid, libc::PROC_PIDLISTFDS, 0, std::ptr::null_mut(), 0) };
+    if size <= 0 {
+        return Vec::new();
+    }
+    let capacity = size as usize / size_of::<libc::proc_fdinfo>() + 16;
+    let mut fds: Vec<libc::proc_fdinfo> = Vec::with_capacity(capacity);
+    let written = unsafe {
+        libc::proc_pidinfo(
+            pid,
+            libc::PROC_PIDLISTFDS,
+            0,
+            fds.as_mut_ptr() as *mut c_void,
+            (capacity * size_of::<libc::proc_fdinfo>()) as c_int,
+        )
+    };
+    if written <= 0 {
+        return Vec::new();
+    }
+    unsafe { fds.set_len(written as usize / size_of::<libc::proc_fdinfo>()) };
+
+    fds.iter()
+        .filter(|fd| fd.proc_fdtype == libc::PROX_FDTYPE_SOCKET as u32)
+        .map(|fd| fd.proc_fd)
+        .collect()
+}
+
+fn socket_record(pid: c_int, fd: i32, filter: SocketFilter) -> Option<SocketRecord> {
+    let mut info = std::mem::MaybeUninit::<SocketFdInfo>::zeroed();
+    let size = size_of::<SocketFdInfo>() as c_int;
+    let read = unsafe {
+        libc::proc_pidfdinfo(pid, fd, PROC_PIDFDSOCKETINFO, info.as_mut_ptr() as *mut c_void, size)
+    };
+    if read != size {
+        return None;
+    }
+    let info = unsafe { info.assume_init() };
+    let socket = &info.psi;
+
+    let (protocol, inet, state) = match socket.kind {
+        SOCKINFO_TCP => {
+            let tcp = unsafe { &socket.proto.pri_tcp };
+            let wanted = match filter {
+                SocketFilter::Listening => tcp.state == TSI_S_LISTEN,
+                SocketFilter::Established => tcp.state == TSI_S_ESTABLISHED,
+                SocketFilter::Active => !matches!(tcp.state, TSI_S_CLOSED | TSI_S_LISTEN),
+            };
+            if !wanted {
+                return None;
+            }
+            let state = usize::try_from(tcp.state)
+                .ok()
+                .and_then(|state| TCP_STATES.get(state))
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| format!("STATE_{}", tcp.state));
+            (Protocol::Tcp, &tcp.ini, Some(state))
+        }
+        SOCKINFO_IN if socket.protocol == IPPROTO_UDP => {
+            let inet = unsafe { &socket.proto.pri_in };
+            match filter {
+                SocketFilter::Listening => (Protocol::Udp, inet, None),
+                SocketFilter::Active if inet.fport != 0 => (Protocol::Udp, inet, Some("ESTABLISHED".to_string())),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    // Dual-stack sockets are AF_INET6 with both version flags set
+    let family = if socket.family == libc::AF_INET { AddressFamily::Ipv4 } else { AddressFamily::Ipv6 };
+    // Ports are stored in network byte order
+    let remote = (filter != SocketFilter::Listening).then(|| {
+        let port = u16::from_be(inet.fport as u16);
+        match family {
+            AddressFamily::Ipv4 => format!("{}:{}", format_address(&inet.faddr, family), port),
+            AddressFamily::Ipv6 => format!("[{}]:{}", format_address(&inet.faddr, family), port),
+        }
+    });
+
+    Some(SocketRecord {
+        protocol,
+        family,
+        local_address: format_address(&inet.laddr, family),
+        local_port: u16::from_be(inet.lport as u16),
+        remote,
+        state,
+        process_name: None,
+        pid: None,
+    })
+}
+
+fn format_address(words: &[u32; 4], family: AddressFamily) -> String {
+    match family {
+        AddressFamily::Ipv4 => Ipv4Addr::from(words[3].to_ne_bytes()).to_string(),
+        AddressFamily::Ipv6 => {
+            let mut bytes = [0u8; 16];
+            for (chunk, word) in bytes.chunks_mut(4).zip(words) {
+                chunk.copy_from_slice(&word.to_ne_bytes());
+            }
+            Ipv6Addr::from(bytes).to_string()
+        }
+    }
+}
+
+fn process_name(pid: c_int) -> Option<String> {
+    let mut buffer = [0u8; 256];
+    let length = unsafe { libc::proc_name(pid, buffer.as_mut_ptr() as *mut c_void, buffer.len() as u32) };
+    (length > 0).then(|| String::from_utf8_lossy(&buffer[..length as usize]).to_string())
+}
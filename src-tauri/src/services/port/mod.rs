@@ -2,6 +2,31 @@
 
 pub mod scanner;
 pub mod resolver;
+pub mod fingerprint;
+pub mod tls;
+pub mod auth_probe;
+pub mod reservations;
+pub mod baseline;
+pub mod history;
+pub mod service_names;
+pub mod nmap;
+
+#[cfg(target_os = "windows")]
+pub mod ip_helper;
+
+#[cfg(target_os = "windows")]
+pub mod named_pipes;
+
+#[cfg(target_os = "linux")]
+pub mod procfs;
+
+#[cfg(target_os = "macos")]
+pub mod libproc;
 
 pub use scanner::PortScanner;
-pub use resolver::PortResolver;
+pub use resolver::{port_owner, PortResolver};
+pub use reservations::PortReservations;
+pub use baseline::PortBaseline;
+pub use history::PortHistory;
+pub use service_names::ServiceNames;
+pub use nmap::NmapScanner;
@@ -0,0 +1,60 @@
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::CloseHandle;
+use windows::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_READ_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows::Win32::System::Pipes::GetNamedPipeServerProcessId;
+use crate::models::port::{IpcEndpoint, IpcKind};
+
+const PIPE_ROOT: &str = r"\\.\pipe\";
+
+/// Named pipes from the `\\.\pipe\` namespace with the server process. The
+/// owner is only known for pipes we may open that have a free instance;
+/// busy or access-restricted pipes keep `pid: None`.
+pub fn named_pipes() -> Result<Vec<IpcEndpoint>, Box<dyn std::error::Error + Send + Sync>> {
+    let entries = std::fs::read_dir(PIPE_ROOT).map_err(|e| format!("Failed to list named pipes: {}", e))?;
+
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+
+    let mut endpoints = Vec::new();
+    for entry in entries.flatten() {
+        let path = format!("{}{}", PIPE_ROOT, entry.file_name().to_string_lossy());
+        let pid = server_pid(&path);
+        endpoints.push(IpcEndpoint {
+            kind: IpcKind::NamedPipe,
+            process_name: pid
+                .and_then(|pid| system.process(Pid::from_u32(pid)))
+                .map(|process| process.name().to_string_lossy().to_string()),
+            pid,
+            path,
+            state: None,
+            user: None,
+        });
+    }
+    Ok(endpoints)
+}
+
+/// Connect with attribute-only access and ask for the server's PID
+fn server_pid(path: &str) -> Option<u32> {
+    let wide: Vec<u16> = OsStr::new(path).encode_wide().chain(Some(0)).collect();
+    unsafe {
+        let handle = CreateFileW(
+            PCWSTR(wide.as_ptr()),
+            FILE_READ_ATTRIBUTES.0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAGS_AND_ATTRIBUTES(0),
+            None,
+        )
+        .ok()?;
+        let mut pid = 0u32;
+        let result = GetNamedPipeServerProcessId(handle, &mut pid);
+        let _ = CloseHandle(handle);
+        result.ok().map(|_| pid)
+    }
+}
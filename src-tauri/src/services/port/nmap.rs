@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::{Mutex, OnceLock};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use crate::models::config::NmapConfig;
+use crate::models::port::{AddressFamily, PortInfo, PortStatus, Protocol};
+use crate::services::cmd;
+use super::service_names::ServiceNames;
+
+/// Upper bound per host, so a filtered network can't stall a scan for hours
+const HOST_TIMEOUT: &str = "300s";
+/// `-T5` gives up on slow ports and produces false negatives
+const MAX_TIMING: u8 = 4;
+
+static SETTINGS: OnceLock<Mutex<NmapConfig>> = OnceLock::new();
+
+/// Whether scans go through nmap, for the settings page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NmapStatus {
+    pub installed: bool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    /// Enabled in the config and installed
+    pub active: bool,
+}
+
+/// Port scans through nmap with fixed, safe options: TCP connect scan (no
+/// raw packets, no root), no NSE scripts, light version probes, and OS
+/// detection only when enabled. Results are read from the XML report.
+pub struct NmapScanner {
+    path: PathBuf,
+    config: NmapConfig,
+}
+
+impl NmapScanner {
+    pub fn configure(config: &NmapConfig) {
+        *settings().lock().unwrap_or_else(|e| e.into_inner()) = config.clone();
+    }
+
+    /// The configured scanner, `None` if disabled or nmap isn't installed
+    pub fn from_config() -> Option<Self> {
+        let config = settings().lock().unwrap_or_else(|e| e.into_inner()).clone();
+        if !config.enabled {
+            return None;
+        }
+        Some(Self {
+            path: cmd::find_in_path("nmap")?,
+            config,
+        })
+    }
+
+    pub fn status() -> NmapStatus {
+        let path = cmd::find_in_path("nmap");
+        let version = path.as_ref().and_then(|path| {
+            let output = cmd::command(&path.to_string_lossy()).arg("--version").output().ok()?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            stdout.lines().next().map(|line| line.trim().to_string())
+        });
+        let enabled = settings().lock().unwrap_or_else(|e| e.into_inner()).enabled;
+        NmapStatus {
+            installed: path.is_some(),
+            active: enabled && path.is_some(),
+            path: path.map(|p| p.to_string_lossy().to_string()),
+            version,
+        }
+    }
+
+    /// Blocking. IPv4 and IPv6 hosts are scanned in separate runs, nmap
+    /// needs `-6` for the latter.
+    pub fn scan(&self, hosts: &[IpAddr], ports: &[u16]) -> Result<Vec<PortInfo>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut results = Vec::new();
+        for ipv6 in [false, true] {
+            let targets: Vec<String> = hosts
+                .iter()
+                .filter(|ip| ip.is_ipv6() == ipv6)
+                .map(|ip| ip.to_string())
+                .collect();
+            if targets.is_empty() {
+                continue;
+            }
+            results.extend(parse_report(&self.run(&targets, ports, ipv6)?));
+        }
+        Ok(results)
+    }
+
+    fn run(&self, targets: &[String], ports: &[u16], ipv6: bool) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut command = cmd::command(&self.path.to_string_lossy());
+        command
+            .args(["-sT", "-n", "--open", "--max-retries", "2", "--host-timeout", HOST_TIMEOUT])
+            .arg(format!("-T{}", self.config.timing.min(MAX_TIMING)))
+            .args(["-p", &port_spec(ports)]);
+        if self.config.version_detection {
+            command.args(["-sV", "--version-light"]);
+        }
+        if self.config.os_detection {
+            command.args(["-O", "--osscan-limit"]);
+        }
+        if ipv6 {
+            command.arg("-6");
+        }
+        // Targets via stdin: a /22 doesn't fit on every command line
+        command.args(["-oX", "-", "-iL", "-"]);
+
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to start nmap: {}", e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(targets.join("\n").as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("unbekannter Fehler");
+            return Err(format!("nmap ist fehlgeschlagen: {}", reason).into());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+fn settings() -> &'static Mutex<NmapConfig> {
+    SETTINGS.get_or_init(|| Mutex::new(NmapConfig::default()))
+}
+
+/// `[22, 80, 81, 82, 443]` -> `22,80-82,443`
+fn port_spec(ports: &[u16]) -> String {
+    let mut ports = ports.to_vec();
+    ports.sort_unstable();
+    ports.dedup();
+
+    let mut ranges: Vec<(u16, u16)> = Vec::new();
+    for port in ports {
+        match ranges.last_mut() {
+            Some((_, end)) if end.checked_add(1) == Some(port) => *end = port,
+            _ => ranges.push((port, port)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(start, end)| if start == end { start.to_string() } else { format!("{}-{}", start, end) })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Open ports from nmap's XML output (`-oX`). Service names only count when
+/// nmap actually probed the port; otherwise they come from its port table.
+fn parse_report(xml: &str) -> Vec<PortInfo> {
+    let host_re = Regex::new(r"(?s)<host[\s>].*?</host>").unwrap();
+    let address_re = Regex::new(r#"<address addr="([^"]+)" addrtype="(ipv4|ipv6)""#).unwrap();
+    let port_re = Regex::new(r#"(?s)<port protocol="(tcp|udp)" portid="(\d+)">(.*?)</port>"#).unwrap();
+    let service_re = Regex::new(r"<service\s([^>]*)>").unwrap();
+    let attribute_re = Regex::new(r#"(\w+)="([^"]*)""#).unwrap();
+    let os_re = Regex::new(r#"<osmatch name="([^"]+)""#).unwrap();
+
+    let mut results = Vec::new();
+    for host in host_re.find_iter(xml).map(|m| m.as_str()) {
+        let Some(address) = address_re.captures(host) else {
+            continue;
+        };
+        let family = if &address[2] == "ipv6" { AddressFamily::Ipv6 } else { AddressFamily::Ipv4 };
+        let os_guess = os_re.captures(host).map(|c| unescape(&c[1]));
+
+        for port in port_re.captures_iter(host) {
+            let body = port.get(3).map(|m| m.as_str()).unwrap_or_default();
+            let Ok(number) = port[2].parse::<u16>() else {
+                continue;
+            };
+            if !body.contains(r#"state="open""#) {
+                continue;
+            }
+            let protocol = if &port[1] == "udp" { Protocol::Udp } else { Protocol::Tcp };
+            let service_name = ServiceNames::lookup(number, &protocol);
+
+            let service: HashMap<&str, String> = service_re
+                .captures(body)
+                .and_then(|c| c.get(1))
+                .map(|attributes| {
+                    attribute_re
+                        .captures_iter(attributes.as_str())
+                        .filter_map(|a| Some((a.get(1)?.as_str(), unescape(a.get(2)?.as_str()))))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let probed = service.get("method").is_some_and(|method| method == "probed");
+            let detected_service = service.get("name").filter(|_| probed).map(|name| match service.get("tunnel") {
+                Some(tunnel) => format!("{}/{}", tunnel, name),
+                None => name.clone(),
+            });
+            let version = ["product", "version", "extrainfo"]
+                .iter()
+                .filter_map(|key| service.get(key).filter(|value| !value.is_empty()))
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            results.push(PortInfo {
+                port: number,
+                protocol,
+                family,
+                bind_address: unescape(&address[1]),
+                status: PortStatus::Occupied,
+                process_name: None,
+                pid: None,
+                detected_service,
+                version: (probed && !version.is_empty()).then_some(version),
+                user: None,
+                service_name,
+                latency_ms: None,
+                os_guess: os_guess.clone(),
+            });
+        }
+    }
+    results
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
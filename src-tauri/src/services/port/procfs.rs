@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use crate::models::port::{AddressFamily, IpcEndpoint, IpcKind, Protocol};
+use crate::parsers::{sockets::SocketRecord, ParseReport};
+
+/// `st` column values, see `include/net/tcp_states.h`
+const TCP_ESTABLISHED: &str = "01";
+const TCP_LISTEN: &str = "0A";
+/// Unconnected UDP sockets show up as TCP_CLOSE, connected ones as TCP_ESTABLISHED
+const UDP_UNCONNECTED: &str = "07";
+const TCP_STATES: &[(&str, &str)] = &[
+    ("01", "ESTABLISHED"),
+    ("02", "SYN_SENT"),
+    ("03", "SYN_RECV"),
+    ("04", "FIN_WAIT1"),
+    ("05", "FIN_WAIT2"),
+    ("06", "TIME_WAIT"),
+    ("07", "CLOSE"),
+    ("08", "CLOSE_WAIT"),
+    ("09", "LAST_ACK"),
+    ("0A", "LISTEN"),
+    ("0B", "CLOSING"),
+    ("0C", "NEW_SYN_RECV"),
+];
+/// `__SO_ACCEPTCON` in the `Flags` column of `/proc/net/unix`
+const UNIX_ACCEPTCON: u32 = 0x10000;
+/// `Type` column of `/proc/net/unix`
+const UNIX_DGRAM: &str = "0002";
+
+const TABLES: &[(&str, Protocol, AddressFamily)] = &[
+    ("/proc/net/tcp", Protocol::Tcp, AddressFamily::Ipv4),
+    ("/proc/net/tcp6", Protocol::Tcp, AddressFamily::Ipv6),
+    ("/proc/net/udp", Protocol::Udp, AddressFamily::Ipv4),
+    ("/proc/net/udp6", Protocol::Udp, AddressFamily::Ipv6),
+];
+
+/// Listening TCP and unconnected UDP sockets from `/proc/net/*`, with owners
+/// resolved through `/proc/<pid>/fd`. Sockets of other users' processes keep
+/// `pid: None` unless running as root, same as `ss -p`.
+pub fn socket_table() -> Result<ParseReport<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    read_tables(TABLES, |protocol, state| match protocol {
+        Protocol::Tcp => state == TCP_LISTEN,
+        Protocol::Udp => state == UDP_UNCONNECTED,
+    })
+}
+
+/// Established TCP connections with their owners
+pub fn connections() -> Result<ParseReport<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    read_tables(&TABLES[..2], |_, state| state == TCP_ESTABLISHED)
+}
+
+/// Sockets with a remote peer: TCP in every state but LISTEN (handshakes,
+/// TIME_WAIT, ...) and connected UDP sockets. Sockets in TIME_WAIT belong to
+/// no process anymore.
+pub fn active_connections() -> Result<ParseReport<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    read_tables(TABLES, |protocol, state| match protocol {
+        Protocol::Tcp => state != TCP_LISTEN,
+        Protocol::Udp => state == TCP_ESTABLISHED,
+    })
+}
+
+/// Listening stream and bound datagram Unix domain sockets with a path,
+/// including Linux's abstract namespace (`@name`)
+///
+/// Num RefCount Protocol Flags Type St Inode [Path]
+pub fn unix_sockets() -> Result<ParseReport<IpcEndpoint>, Box<dyn std::error::Error + Send + Sync>> {
+    let content = std::fs::read_to_string("/proc/net/unix")
+        .map_err(|e| format!("Failed to read /proc/net/unix: {}", e))?;
+    let mut report = ParseReport::new();
+    let mut inodes = Vec::new();
+
+    for (index, line) in content.lines().enumerate().skip(1) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 7 {
+            report.push_issue(index + 1, line, "/proc/net/unix: expected at least 7 columns");
+            continue;
+        }
+        // Unnamed sockets, e.g. socketpair() or the client end of a connection
+        if parts.len() < 8 {
+            continue;
+        }
+        let Ok(flags) = u32::from_str_radix(parts[3], 16) else {
+            report.push_issue(index + 1, line, "/proc/net/unix: invalid flags");
+            continue;
+        };
+        let state = if flags & UNIX_ACCEPTCON != 0 {
+            "LISTEN"
+        } else if parts[4] == UNIX_DGRAM {
+            "BOUND"
+        } else {
+            // Accepted connections repeat the listener's path
+            continue;
+        };
+
+        inodes.push(parts[6].parse::<u64>().unwrap_or(0));
+        report.items.push(IpcEndpoint {
+            kind: IpcKind::UnixSocket,
+            path: parts[7..].join(" "),
+            state: Some(state.to_string()),
+            process_name: None,
+            pid: None,
+            user: None,
+        });
+    }
+
+    let owners = socket_owners();
+    for (endpoint, inode) in report.items.iter_mut().zip(inodes) {
+        if let Some((pid, name)) = owners.get(&inode) {
+            endpoint.pid = Some(*pid);
+            endpoint.process_name = Some(name.clone());
+        }
+    }
+
+    Ok(report)
+}
+
+fn read_tables(
+    tables: &[(&str, Protocol, AddressFamily)],
+    wanted: fn(&Protocol, &str) -> bool,
+) -> Result<ParseReport<SocketRecord>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut report = ParseReport::new();
+    let mut inodes = Vec::new();
+
+    for (path, protocol, family) in tables {
+        // IPv6 tables are missing when IPv6 is disabled
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(_) if *family == AddressFamily::Ipv6 => continue,
+            Err(e) => return Err(format!("Failed to read {}: {}", path, e).into()),
+        };
+
+        for (index, line) in content.lines().enumerate().skip(1) {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 10 {
+                report.push_issue(index + 1, line, format!("{}: expected at least 10 columns", path));
+                continue;
+            }
+            if !wanted(protocol, parts[3]) {
+                continue;
+            }
+
+            let (Some((local_address, local_port)), Some((remote_address, remote_port))) =
+                (parse_address(parts[1], *family), parse_address(parts[2], *family))
+            else {
+                report.push_issue(index + 1, line, format!("{}: invalid address", path));
+                continue;
+            };
+
+            let remote_port = if remote_port == 0 { "*".to_string() } else { remote_port.to_string() };
+            let remote = match family {
+                AddressFamily::Ipv4 => format!("{}:{}", remote_address, remote_port),
+                AddressFamily::Ipv6 => format!("[{}]:{}", remote_address, remote_port),
+            };
+
+            inodes.push(parts[9].parse::<u64>().unwrap_or(0));
+            report.items.push(SocketRecord {
+                protocol: protocol.clone(),
+                family: *family,
+                local_address,
+                local_port,
+                remote: Some(remote),
+                state: Some(state_name(protocol, parts[3])),
+                process_name: None,
+                pid: None,
+            });
+        }
+    }
+
+    let owners = socket_owners();
+    for (record, inode) in report.items.iter_mut().zip(inodes) {
+        if let Some((pid, name)) = owners.get(&inode) {
+            record.pid = Some(*pid);
+            record.process_name = Some(name.clone());
+        }
+    }
+
+    Ok(report)
+}
+
+fn state_name(protocol: &Protocol, state: &str) -> String {
+    if *protocol == Protocol::Udp && state == UDP_UNCONNECTED {
+        return "UNCONN".to_string();
+    }
+    TCP_STATES
+        .iter()
+        .find(|(code, _)| *code == state)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| format!("STATE_{}", state))
+}
+
+/// `0100007F:1F90` -> (`127.0.0.1`, 8080). Addresses are printed as 32-bit
+/// words in host byte order, the port in network order.
+fn parse_address(value: &str, family: AddressFamily) -> Option<(String, u16)> {
+    let (address, port) = value.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let mut bytes = Vec::with_capacity(16);
+    for chunk in address.as_bytes().chunks(8) {
+        let word = u32::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+        bytes.extend(word.to_ne_bytes());
+    }
+
+    let address = match family {
+        AddressFamily::Ipv4 => Ipv4Addr::from(<[u8; 4]>::try_from(bytes.as_slice()).ok()?).to_string(),
+        AddressFamily::Ipv6 => Ipv6Addr::from(<[u8; 16]>::try_from(bytes.as_slice()).ok()?).to_string(),
+    };
+    Some((address, port))
+}
+
+/// Socket inode -> (pid, process name) for every process whose fds we can read
+fn socket_owners() -> HashMap<u64, (u32, String)> {
+    let mut owners = HashMap::new();
+    let Ok(processes) = std::fs::read_dir("/proc") else {
+        return owners;
+    };
+
+    for entry in processes.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+
+        let mut name = None;
+        for fd in fds.flatten() {
+            let Ok(target) = std::fs::read_link(fd.path()) else {
+                continue;
+            };
+            // `socket:[12345]`
+            let Some(inode) = target
+                .to_str()
+                .and_then(|t| t.strip_prefix("socket:["))
+                .and_then(|t| t.strip_suffix(']'))
+                .and_then(|t| t.parse::<u64>().ok())
+            else {
+                continue;
+            };
+
+            let name = name.get_or_insert_with(|| {
+                std::fs::read_to_string(entry.path().join("comm"))
+                    .map(|comm| comm.trim().to_string())
+                    .unwrap_or_default()
+            });
+            owners.entry(inode).or_insert_with(|| (pid, name.clone()));
+        }
+    }
+
+    owners
+}
@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use chrono::Utc;
+use crate::models::config::PortReservation;
+use crate::models::port::PortInfo;
+use crate::services::storage::Storage;
+
+static RESERVATIONS: OnceLock<Mutex<Vec<PortReservation>>> = OnceLock::new();
+
+/// Registry of reserved ports. Stored separately on disk, since the rest of
+/// the config isn't persisted yet; `Config.port_reservations` is a read-only
+/// copy for display.
+pub struct PortReservations;
+
+impl PortReservations {
+
+    pub fn list() -> Vec<PortReservation> {
+        registry().lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Add or relabel a reservation
+    pub fn reserve(port: u16, label: &str, owner: Option<String>) -> Result<PortReservation, Box<dyn std::error::Error + Send + Sync>> {
+        if port == 0 {
+            return Err("Port 0 kann nicht reserviert werden".into());
+        }
+        let label = label.trim();
+        if label.is_empty() {
+            return Err("Eine Reservierung braucht eine Bezeichnung".into());
+        }
+
+        let mut current = registry().lock().unwrap_or_else(|e| e.into_inner());
+        let created_at = current.iter().find(|r| r.port == port).map(|r| r.created_at).unwrap_or_else(Utc::now);
+        let reservation = PortReservation {
+            port,
+            label: label.to_string(),
+            owner: owner.map(|o| o.trim().to_string()).filter(|o| !o.is_empty()),
+            created_at,
+        };
+        current.retain(|r| r.port != port);
+        current.push(reservation.clone());
+        current.sort_by_key(|r| r.port);
+        save(&current);
+        Ok(reservation)
+    }
+
+    /// Returns false if the port wasn't reserved
+    pub fn release(port: u16) -> bool {
+        let mut current = registry().lock().unwrap_or_else(|e| e.into_inner());
+        let before = current.len();
+        current.retain(|r| r.port != port);
+        let removed = current.len() != before;
+        if removed {
+            save(&current);
+        }
+        removed
+    }
+
+    pub fn get(port: u16) -> Option<PortReservation> {
+        registry().lock().unwrap_or_else(|e| e.into_inner()).iter().find(|r| r.port == port).cloned()
+    }
+
+    pub fn is_reserved(port: u16) -> bool {
+        Self::get(port).is_some()
+    }
+
+    /// Listeners on reserved ports that aren't the expected owner. Without an
+    /// owner every listener is a conflict.
+    pub fn conflicts(ports: &[PortInfo]) -> Vec<(PortReservation, PortInfo)> {
+        let reservations = Self::list();
+        ports
+            .iter()
+            .filter_map(|port| {
+                let reservation = reservations.iter().find(|r| r.port == port.port)?;
+                let expected = match (&reservation.owner, &port.process_name) {
+                    (Some(owner), Some(process)) => owner.eq_ignore_ascii_case(process),
+                    _ => false,
+                };
+                (!expected).then(|| (reservation.clone(), port.clone()))
+            })
+            .collect()
+    }
+}
+
+fn registry() -> &'static Mutex<Vec<PortReservation>> {
+    RESERVATIONS.get_or_init(|| Mutex::new(load()))
+}
+
+fn path() -> PathBuf {
+    Storage::config_dir().join("port_reservations.json")
+}
+
+fn load() -> Vec<PortReservation> {
+    std::fs::read(path())
+        .ok()
+        .and_then(|data| serde_json::from_slice(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save(reservations: &[PortReservation]) {
+    let path = path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+    if let Ok(data) = serde_json::to_vec_pretty(reservations) {
+        std::fs::write(&path, data).ok();
+    }
+}
@@ -1,5 +1,10 @@
-use std::process::Command;
-use crate::models::port::{PortInfo, Protocol, PortStatus};
+use crate::services::cmd;
+use std::net::{Ipv4Addr, Ipv6Addr, TcpListener};
+use crate::models::port::{Connection, IpcEndpoint, PortInfo, PortStatus};
+use crate::models::service::{ProviderWarning, ProviderWarningKind, Service, ServiceType};
+use crate::parsers::{sockets::{self, SocketRecord}, split_host_port, ParseReport};
+use crate::services::user_scope::process_owners;
+use super::service_names::ServiceNames;
 
 pub struct PortResolver;
 
@@ -9,227 +14,215 @@ impl PortResolver {
     }
 
     /// Get all ports currently in use with their associated processes
-    #[cfg(target_os = "macos")]
     pub fn get_port_usage(&self) -> Vec<PortInfo> {
-        let output = Command::new("lsof")
+        self.port_usage_with_warnings().0
+    }
+
+    /// Port usage plus warnings about sockets whose owner could not be resolved
+    pub fn port_usage_with_warnings(&self) -> (Vec<PortInfo>, Vec<ProviderWarning>) {
+        let report = self.socket_report();
+        let mut warnings = Vec::new();
+
+        let without_process = report.items.iter().filter(|r| r.pid.is_none()).count();
+        if without_process > 0 {
+            warnings.push(ProviderWarning::new(
+                "Ports",
+                ProviderWarningKind::PermissionDenied,
+                format!(
+                    "{} Socket(s) ohne Prozessinformation. Prozesse anderer Benutzer sind nur mit Administratorrechten sichtbar.",
+                    without_process
+                ),
+            ));
+        }
+        if !report.is_complete() {
+            warnings.push(ProviderWarning::new(
+                "Ports",
+                ProviderWarningKind::Partial,
+                format!("{} Zeile(n) der Socket-Tabelle konnten nicht gelesen werden", report.issues.len()),
+            ));
+        }
+
+        let owners = process_owners();
+        let mut ports: Vec<PortInfo> = report.items.into_iter().map(to_port_info).collect();
+        for port in &mut ports {
+            port.user = port.pid.and_then(|pid| owners.get(&pid).cloned());
+        }
+
+        // Forked workers share their parent's listening socket, so deduplicate
+        // by port. IPv4 and IPv6 bindings of a dual-stack service are kept as
+        // separate entries, and the widest binding wins.
+        #[cfg(target_os = "macos")]
+        {
+            use crate::models::port::AddressFamily;
+            ports.sort_by_key(|p| (p.port, p.family == AddressFamily::Ipv6, !p.is_wildcard()));
+            ports.dedup_by_key(|p| (p.port, p.family));
+        }
+
+        (ports, warnings)
+    }
+
+    /// Raw socket table including lines the parser could not handle.
+    /// Read via libproc, falling back to `lsof`.
+    #[cfg(target_os = "macos")]
+    pub fn socket_report(&self) -> ParseReport<SocketRecord> {
+        if let Ok(items) = super::libproc::socket_table() {
+            return ParseReport {
+                items,
+                issues: Vec::new(),
+            };
+        }
+
+        let output = cmd::command("lsof")
             .args(["-i", "-P", "-n"])
             .output();
 
         match output {
             Ok(output) if output.status.success() => {
                 let stdout = String::from_utf8_lossy(&output.stdout);
-                self.parse_lsof_output(&stdout)
+                sockets::parse_lsof(&stdout)
             }
-            _ => vec![],
+            _ => ParseReport::new(),
         }
     }
 
+    /// Read from `/proc/net`, falling back to `ss` and `netstat`
     #[cfg(target_os = "linux")]
-    pub fn get_port_usage(&self) -> Vec<PortInfo> {
-        let output = Command::new("ss")
+    pub fn socket_report(&self) -> ParseReport<SocketRecord> {
+        if let Ok(report) = super::procfs::socket_table() {
+            return report;
+        }
+
+        let output = cmd::command("ss")
             .args(["-tulnp"])
             .output();
 
         match output {
             Ok(output) if output.status.success() => {
                 let stdout = String::from_utf8_lossy(&output.stdout);
-                self.parse_ss_output(&stdout)
+                sockets::parse_ss(&stdout)
             }
             _ => {
                 // Fallback to netstat
-                let output = Command::new("netstat")
+                let output = cmd::command("netstat")
                     .args(["-tulpn"])
                     .output();
                 match output {
                     Ok(output) if output.status.success() => {
                         let stdout = String::from_utf8_lossy(&output.stdout);
-                        self.parse_netstat_output(&stdout)
+                        sockets::parse_netstat_linux(&stdout)
                     }
-                    _ => vec![],
+                    _ => ParseReport::new(),
                 }
             }
         }
     }
 
+    /// TCP listeners and UDP endpoints for IPv4 and IPv6 via the IP Helper API,
+    /// falling back to `netstat -ano`
     #[cfg(target_os = "windows")]
-    pub fn get_port_usage(&self) -> Vec<PortInfo> {
-        let output = Command::new("netstat")
+    pub fn socket_report(&self) -> ParseReport<SocketRecord> {
+        if let Ok(items) = super::ip_helper::socket_table() {
+            return ParseReport {
+                items,
+                issues: Vec::new(),
+            };
+        }
+
+        let output = cmd::command("netstat")
             .args(["-ano"])
             .output();
 
         match output {
             Ok(output) if output.status.success() => {
                 let stdout = String::from_utf8_lossy(&output.stdout);
-                self.parse_netstat_windows_output(&stdout)
+                sockets::parse_netstat_windows(&stdout)
             }
-            _ => vec![],
+            _ => ParseReport::new(),
         }
     }
 
+    /// Established TCP connections with their owning processes
+    #[cfg(target_os = "linux")]
+    pub fn connections(&self) -> Vec<SocketRecord> {
+        super::procfs::connections().map(|report| report.items).unwrap_or_default()
+    }
+
+    /// Established TCP connections with their owning processes
     #[cfg(target_os = "macos")]
-    fn parse_lsof_output(&self, output: &str) -> Vec<PortInfo> {
-        let mut ports = vec![];
-
-        for line in output.lines().skip(1) {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 9 {
-                let process_name = parts[0].to_string();
-                let pid = parts[1].parse::<u32>().ok();
-
-                // Parse the name field (e.g., "TCP *:8080 (LISTEN)")
-                if let Some(name_part) = parts.get(8) {
-                    if let Some(port_str) = name_part.split(':').last() {
-                        if let Ok(port) = port_str.trim_end_matches(|c| c == ')' || c == '(').parse::<u16>() {
-                            let protocol = if line.contains("TCP") {
-                                Protocol::Tcp
-                            } else {
-                                Protocol::Udp
-                            };
-
-                            ports.push(PortInfo {
-                                port,
-                                protocol,
-                                status: PortStatus::Occupied,
-                                process_name: Some(process_name.clone()),
-                                pid,
-                            });
-                        }
-                    }
-                }
-            }
-        }
+    pub fn connections(&self) -> Vec<SocketRecord> {
+        super::libproc::connections().unwrap_or_default()
+    }
 
-        // Deduplicate by port
-        ports.sort_by_key(|p| p.port);
-        ports.dedup_by_key(|p| p.port);
-        ports
+    /// Established TCP connections with their owning processes
+    #[cfg(target_os = "windows")]
+    pub fn connections(&self) -> Vec<SocketRecord> {
+        super::ip_helper::connections().unwrap_or_default()
     }
 
-    #[cfg(target_os = "linux")]
-    fn parse_ss_output(&self, output: &str) -> Vec<PortInfo> {
-        let mut ports = vec![];
-
-        for line in output.lines().skip(1) {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 5 {
-                // Parse local address (e.g., "0.0.0.0:8080")
-                if let Some(addr) = parts.get(4) {
-                    if let Some(port_str) = addr.rsplit(':').next() {
-                        if let Ok(port) = port_str.parse::<u16>() {
-                            let protocol = if line.starts_with("tcp") {
-                                Protocol::Tcp
-                            } else {
-                                Protocol::Udp
-                            };
-
-                            let (process_name, pid) = if let Some(process_info) = parts.get(6) {
-                                // Parse users:((\"process\",pid=1234,...))
-                                let name = process_info
-                                    .split('"')
-                                    .nth(1)
-                                    .map(String::from);
-                                let pid = process_info
-                                    .split("pid=")
-                                    .nth(1)
-                                    .and_then(|s| s.split(',').next())
-                                    .and_then(|s| s.parse().ok());
-                                (name, pid)
-                            } else {
-                                (None, None)
-                            };
-
-                            ports.push(PortInfo {
-                                port,
-                                protocol,
-                                status: PortStatus::Occupied,
-                                process_name,
-                                pid,
-                            });
-                        }
-                    }
-                }
-            }
+    /// Everything with a remote peer, not just established TCP: what each
+    /// process is talking to. Sorted by process, then remote endpoint.
+    pub fn active_connections(&self) -> Vec<Connection> {
+        let owners = process_owners();
+        let mut connections: Vec<Connection> = self.active_socket_records().into_iter().filter_map(to_connection).collect();
+        for connection in &mut connections {
+            connection.user = connection.pid.and_then(|pid| owners.get(&pid).cloned());
         }
-
-        ports
+        connections.sort_by(|a, b| {
+            (&a.process_name, a.pid, &a.remote_address, a.remote_port)
+                .cmp(&(&b.process_name, b.pid, &b.remote_address, b.remote_port))
+        });
+        connections
     }
 
     #[cfg(target_os = "linux")]
-    fn parse_netstat_output(&self, output: &str) -> Vec<PortInfo> {
-        let mut ports = vec![];
-
-        for line in output.lines().skip(2) {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 4 {
-                if let Some(addr) = parts.get(3) {
-                    if let Some(port_str) = addr.rsplit(':').next() {
-                        if let Ok(port) = port_str.parse::<u16>() {
-                            let protocol = if line.starts_with("tcp") {
-                                Protocol::Tcp
-                            } else {
-                                Protocol::Udp
-                            };
-
-                            let (process_name, pid) = if let Some(process_info) = parts.last() {
-                                let mut split = process_info.split('/');
-                                let pid = split.next().and_then(|s| s.parse().ok());
-                                let name = split.next().map(String::from);
-                                (name, pid)
-                            } else {
-                                (None, None)
-                            };
-
-                            ports.push(PortInfo {
-                                port,
-                                protocol,
-                                status: PortStatus::Occupied,
-                                process_name,
-                                pid,
-                            });
-                        }
-                    }
-                }
-            }
-        }
+    fn active_socket_records(&self) -> Vec<SocketRecord> {
+        super::procfs::active_connections().map(|report| report.items).unwrap_or_default()
+    }
 
-        ports
+    #[cfg(target_os = "macos")]
+    fn active_socket_records(&self) -> Vec<SocketRecord> {
+        super::libproc::active_connections().unwrap_or_default()
     }
 
     #[cfg(target_os = "windows")]
-    fn parse_netstat_windows_output(&self, output: &str) -> Vec<PortInfo> {
-        let mut ports = vec![];
-
-        for line in output.lines().skip(4) {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 5 {
-                let protocol = match parts.get(0) {
-                    Some(&"TCP") => Protocol::Tcp,
-                    Some(&"UDP") => Protocol::Udp,
-                    _ => continue,
-                };
-
-                if let Some(addr) = parts.get(1) {
-                    if let Some(port_str) = addr.rsplit(':').next() {
-                        if let Ok(port) = port_str.parse::<u16>() {
-                            let pid = parts.last().and_then(|s| s.parse().ok());
-
-                            ports.push(PortInfo {
-                                port,
-                                protocol,
-                                status: PortStatus::Occupied,
-                                process_name: None,
-                                pid,
-                            });
-                        }
-                    }
-                }
-            }
+    fn active_socket_records(&self) -> Vec<SocketRecord> {
+        super::ip_helper::active_connections().unwrap_or_default()
+    }
+
+    /// Unix domain sockets and named pipes that local services listen on,
+    /// with their owners
+    pub fn ipc_endpoints(&self) -> ParseReport<IpcEndpoint> {
+        let mut report = self.ipc_report();
+        let owners = process_owners();
+        for endpoint in report.items.iter_mut().filter(|e| e.user.is_none()) {
+            endpoint.user = endpoint.pid.and_then(|pid| owners.get(&pid).cloned());
         }
+        report.items.sort_by(|a, b| a.path.cmp(&b.path));
+        report
+    }
+
+    /// Read from `/proc/net/unix`, falling back to `lsof -U`
+    #[cfg(target_os = "linux")]
+    fn ipc_report(&self) -> ParseReport<IpcEndpoint> {
+        super::procfs::unix_sockets().unwrap_or_else(|_| lsof_unix())
+    }
+
+    #[cfg(target_os = "macos")]
+    fn ipc_report(&self) -> ParseReport<IpcEndpoint> {
+        lsof_unix()
+    }
 
-        ports
+    #[cfg(target_os = "windows")]
+    fn ipc_report(&self) -> ParseReport<IpcEndpoint> {
+        ParseReport {
+            items: super::named_pipes::named_pipes().unwrap_or_default(),
+            issues: Vec::new(),
+        }
     }
 
-    /// Find free ports in a range
+    /// Find ports in a range that are free for both IPv4 and IPv6.
+    /// A port bound only on `::` or only on `0.0.0.0` is not free, and
+    /// reserved ports are never offered.
     pub fn find_free_ports(&self, start: u16, end: u16, count: usize) -> Vec<u16> {
         let occupied: std::collections::HashSet<u16> = self
             .get_port_usage()
@@ -239,7 +232,83 @@ impl PortResolver {
 
         (start..=end)
             .filter(|port| !occupied.contains(port))
+            .filter(|&port| !super::PortReservations::is_reserved(port))
+            .filter(|&port| is_bindable(port))
             .take(count)
             .collect()
     }
 }
+
+#[cfg(unix)]
+fn lsof_unix() -> ParseReport<IpcEndpoint> {
+    let output = cmd::command("lsof")
+        .args(["-U", "-n", "-P"])
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            sockets::parse_lsof_unix(&stdout)
+        }
+        _ => ParseReport::new(),
+    }
+}
+
+/// The socket table can miss sockets of other users, so confirm with a bind
+/// on both loopback addresses. Hosts without IPv6 only need the IPv4 bind.
+fn is_bindable(port: u16) -> bool {
+    if TcpListener::bind((Ipv4Addr::LOCALHOST, port)).is_err() {
+        return false;
+    }
+    match TcpListener::bind((Ipv6Addr::LOCALHOST, port)) {
+        Ok(_) => true,
+        Err(e) => e.kind() == std::io::ErrorKind::AddrNotAvailable,
+    }
+}
+
+/// `None` for records without a usable remote endpoint (`0.0.0.0:*`)
+fn to_connection(record: SocketRecord) -> Option<Connection> {
+    let (remote_address, remote_port) = record.remote.as_deref().and_then(split_host_port)?;
+    let remote_service = ServiceNames::lookup(remote_port, &record.protocol);
+    Some(Connection {
+        protocol: record.protocol,
+        family: record.family,
+        local_address: record.local_address,
+        local_port: record.local_port,
+        remote_address,
+        remote_port,
+        state: record.state.unwrap_or_default(),
+        process_name: record.process_name,
+        pid: record.pid,
+        user: None,
+        remote_service,
+    })
+}
+
+fn to_port_info(record: SocketRecord) -> PortInfo {
+    let service_name = ServiceNames::lookup(record.local_port, &record.protocol);
+    PortInfo {
+        port: record.local_port,
+        protocol: record.protocol,
+        family: record.family,
+        bind_address: record.local_address,
+        status: PortStatus::Occupied,
+        process_name: record.process_name,
+        pid: record.pid,
+        detected_service: None,
+        version: None,
+        user: None,
+        service_name,
+        latency_ms: None,
+        os_guess: None,
+    }
+}
+
+/// Service behind a listener. Docker publishes ports through docker-proxy,
+/// so a container with the port mapping wins over the listening PID.
+pub fn port_owner(port: u16, pid: Option<u32>, services: &[Service]) -> Option<&Service> {
+    services
+        .iter()
+        .find(|s| s.service_type == ServiceType::Docker && s.ports.contains(&port))
+        .or_else(|| pid.and_then(|pid| services.iter().find(|s| s.pid == Some(pid))))
+}
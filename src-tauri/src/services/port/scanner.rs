@@ -1,12 +1,35 @@
-use std::net::{SocketAddr, TcpStream};
-use std::time::Duration;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
 use tokio::sync::Semaphore;
 use std::sync::Arc;
-use crate::models::port::{PortInfo, Protocol, PortStatus};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::models::port::{AddressFamily, PortInfo, Protocol, PortStatus};
+use super::fingerprint::{self, Fingerprint};
+use super::service_names::ServiceNames;
+
+/// Largest number of hosts a single scan may cover (a /22)
+pub const MAX_SCAN_HOSTS: usize = 1024;
+/// Largest number of host/port pairs a single scan may probe: every port on
+/// four hosts, or 256 ports across the largest range
+pub const MAX_SCAN_PROBES: usize = 4 * 65_536;
+/// Progress is reported after this many probes
+const PROGRESS_STEP: usize = 64;
+
+/// Emitted while `scan_targets` runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanProgress {
+    pub scanned: usize,
+    pub total: usize,
+    pub open: usize,
+}
 
 pub struct PortScanner {
     timeout: Duration,
     max_concurrent: usize,
+    /// Identify services on open ports, see `fingerprint::fingerprint`
+    fingerprint: bool,
 }
 
 impl PortScanner {
@@ -14,9 +37,16 @@ impl PortScanner {
         Self {
             timeout: Duration::from_millis(200),
             max_concurrent: 100,
+            fingerprint: false,
         }
     }
 
+    /// Read banners and send minimal protocol probes to open ports
+    pub fn with_fingerprinting(mut self, enabled: bool) -> Self {
+        self.fingerprint = enabled;
+        self
+    }
+
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
         self
@@ -29,31 +59,32 @@ impl PortScanner {
 
     /// Scan a single port
     pub fn scan_port(&self, host: &str, port: u16) -> bool {
-        let addr: Result<SocketAddr, _> = format!("{}:{}", host, port).parse();
-        match addr {
-            Ok(addr) => TcpStream::connect_timeout(&addr, self.timeout).is_ok(),
-            Err(_) => false,
+        match parse_host(host) {
+            Some(ip) => connect(SocketAddr::new(ip, port), self.timeout).is_some(),
+            None => false,
         }
     }
 
-    /// Scan a range of ports
+    /// Scan a range of ports on an IPv4 or IPv6 host (`127.0.0.1`, `::1`, `[::1]`)
     pub async fn scan_range(&self, host: &str, start: u16, end: u16) -> Vec<PortInfo> {
+        let Some(ip) = parse_host(host) else {
+            return Vec::new();
+        };
+        let family = if ip.is_ipv6() { AddressFamily::Ipv6 } else { AddressFamily::Ipv4 };
         let semaphore = Arc::new(Semaphore::new(self.max_concurrent));
         let mut handles = vec![];
 
         for port in start..=end {
             let permit = semaphore.clone().acquire_owned().await.unwrap();
-            let host = host.to_string();
             let timeout = self.timeout;
+            let fingerprint = self.fingerprint;
 
             let handle = tokio::task::spawn_blocking(move || {
-                let addr: Result<SocketAddr, _> = format!("{}:{}", host, port).parse();
-                let is_open = match addr {
-                    Ok(addr) => TcpStream::connect_timeout(&addr, timeout).is_ok(),
-                    Err(_) => false,
-                };
+                let addr = SocketAddr::new(ip, port);
+                let latency = connect(addr, timeout);
+                let found = if latency.is_some() && fingerprint { fingerprint::fingerprint(addr) } else { None };
                 drop(permit);
-                (port, is_open)
+                (port, latency, found)
             });
 
             handles.push(handle);
@@ -61,18 +92,92 @@ impl PortScanner {
 
         let mut results = vec![];
         for handle in handles {
-            if let Ok((port, is_open)) = handle.await {
-                if is_open {
-                    results.push(PortInfo {
-                        port,
-                        protocol: Protocol::Tcp,
-                        status: PortStatus::Occupied,
-                        process_name: None,
-                        pid: None,
-                    });
-                }
+            if let Ok((port, Some(latency), found)) = handle.await {
+                let (detected_service, version) = split_fingerprint(found);
+                results.push(PortInfo {
+                    port,
+                    protocol: Protocol::Tcp,
+                    family,
+                    bind_address: ip.to_string(),
+                    status: PortStatus::Occupied,
+                    process_name: None,
+                    pid: None,
+                    detected_service,
+                    version,
+                    user: None,
+                    service_name: ServiceNames::lookup(port, &Protocol::Tcp),
+                    latency_ms: Some(millis(latency)),
+                    os_guess: None,
+                });
+            }
+        }
+
+        results
+    }
+
+    /// Scan a list of ports on each host. Probes across all hosts share the
+    /// concurrency limit; `on_progress` is called every few probes and once
+    /// at the end.
+    pub async fn scan_targets<F>(&self, hosts: &[IpAddr], ports: &[u16], on_progress: F) -> Vec<PortInfo>
+    where
+        F: Fn(ScanProgress) + Send + Sync + 'static,
+    {
+        let total = hosts.len() * ports.len();
+        let scanned = Arc::new(AtomicUsize::new(0));
+        let open = Arc::new(AtomicUsize::new(0));
+        let on_progress = Arc::new(on_progress);
+        let (timeout, fingerprint) = (self.timeout, self.fingerprint);
+
+        // Only `max_concurrent` probes exist at any time, however large the scan
+        let (hosts, ports) = (hosts.to_vec(), ports.to_vec());
+        let probes = (0..total).map(move |i| (hosts[i / ports.len()], ports[i % ports.len()]));
+        let finished: Vec<_> = stream::iter(probes)
+            .map(move |(ip, port)| {
+                let (scanned, open, on_progress) = (scanned.clone(), open.clone(), on_progress.clone());
+                tokio::task::spawn_blocking(move || {
+                    let addr = SocketAddr::new(ip, port);
+                    let latency = connect(addr, timeout);
+                    let found = if latency.is_some() && fingerprint { fingerprint::fingerprint(addr) } else { None };
+
+                    let open = if latency.is_some() {
+                        open.fetch_add(1, Ordering::Relaxed) + 1
+                    } else {
+                        open.load(Ordering::Relaxed)
+                    };
+                    let scanned = scanned.fetch_add(1, Ordering::Relaxed) + 1;
+                    if scanned % PROGRESS_STEP == 0 || scanned == total {
+                        on_progress(ScanProgress { scanned, total, open });
+                    }
+                    (ip, port, latency, found)
+                })
+            })
+            .buffer_unordered(self.max_concurrent.max(1))
+            .collect()
+            .await;
+
+        let mut results = vec![];
+        for probe in finished {
+            if let Ok((ip, port, Some(latency), found)) = probe {
+                let (detected_service, version) = split_fingerprint(found);
+                results.push(PortInfo {
+                    port,
+                    protocol: Protocol::Tcp,
+                    family: if ip.is_ipv6() { AddressFamily::Ipv6 } else { AddressFamily::Ipv4 },
+                    bind_address: ip.to_string(),
+                    status: PortStatus::Occupied,
+                    process_name: None,
+                    pid: None,
+                    detected_service,
+                    version,
+                    user: None,
+                    service_name: ServiceNames::lookup(port, &Protocol::Tcp),
+                    latency_ms: Some(millis(latency)),
+                    os_guess: None,
+                });
             }
         }
+        // Probes finish in any order
+        results.sort_by(|a, b| a.bind_address.cmp(&b.bind_address).then(a.port.cmp(&b.port)));
 
         results
     }
@@ -84,18 +189,133 @@ impl PortScanner {
             3000, 3306, 5432, 5672, 6379, 8000, 8080, 8443, 9000, 27017,
         ];
 
+        let Some(ip) = parse_host(host) else {
+            return Vec::new();
+        };
+        let family = AddressFamily::from_address(host);
         let mut results = vec![];
         for &port in &common_ports {
-            if self.scan_port(host, port) {
+            let addr = SocketAddr::new(ip, port);
+            if let Some(latency) = connect(addr, self.timeout) {
+                let found = if self.fingerprint { fingerprint::fingerprint(addr) } else { None };
+                let (detected_service, version) = split_fingerprint(found);
                 results.push(PortInfo {
                     port,
                     protocol: Protocol::Tcp,
+                    family,
+                    bind_address: host.trim_start_matches('[').trim_end_matches(']').to_string(),
                     status: PortStatus::Occupied,
                     process_name: None,
                     pid: None,
+                    detected_service,
+                    version,
+                    user: None,
+                    service_name: ServiceNames::lookup(port, &Protocol::Tcp),
+                    latency_ms: Some(millis(latency)),
+                    os_guess: None,
                 });
             }
         }
         results
     }
 }
+
+/// Time until the TCP handshake completed, `None` if the port is closed or
+/// didn't answer within `timeout`
+fn connect(addr: SocketAddr, timeout: Duration) -> Option<Duration> {
+    let started = Instant::now();
+    TcpStream::connect_timeout(&addr, timeout).ok()?;
+    Some(started.elapsed())
+}
+
+/// Milliseconds with 0.01 ms resolution, loopback connects take well under 1 ms
+fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 100_000.0).round() / 100.0
+}
+
+fn split_fingerprint(found: Option<Fingerprint>) -> (Option<String>, Option<String>) {
+    match found {
+        Some(found) => (Some(found.service), found.version),
+        None => (None, None),
+    }
+}
+
+/// IP literal, optionally in brackets. `format!("{}:{}")` breaks on IPv6 hosts.
+fn parse_host(host: &str) -> Option<IpAddr> {
+    host.trim_start_matches('[').trim_end_matches(']').parse().ok()
+}
+
+/// Expand a scan target into host addresses: an IP literal, a hostname, or a
+/// CIDR range like `192.168.1.0/24`. Network and broadcast addresses of IPv4
+/// ranges are skipped.
+pub fn parse_targets(target: &str) -> Result<Vec<IpAddr>, Box<dyn std::error::Error + Send + Sync>> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err("Es muss ein Ziel angegeben werden".into());
+    }
+
+    let Some((network, prefix)) = target.split_once('/') else {
+        if let Some(ip) = parse_host(target) {
+            return Ok(vec![ip]);
+        }
+        let mut addresses: Vec<IpAddr> = (target, 0)
+            .to_socket_addrs()
+            .map_err(|e| format!("{} konnte nicht aufgelöst werden: {}", target, e))?
+            .map(|addr| addr.ip())
+            .collect();
+        addresses.dedup();
+        return Ok(addresses);
+    };
+
+    let network: IpAddr = network.parse().map_err(|_| format!("Ungültige Netzadresse: {}", network))?;
+    let prefix: u32 = prefix.parse().map_err(|_| format!("Ungültige Präfixlänge: {}", prefix))?;
+    let bits = if network.is_ipv6() { 128 } else { 32 };
+    if prefix > bits {
+        return Err(format!("Ungültige Präfixlänge: {}", prefix).into());
+    }
+    let host_bits = bits - prefix;
+    if host_bits > MAX_SCAN_HOSTS.ilog2() {
+        return Err(format!("{} ist zu groß, maximal {} Hosts pro Scan", target, MAX_SCAN_HOSTS).into());
+    }
+    let size = 1u128 << host_bits;
+
+    Ok(match network {
+        IpAddr::V4(network) => {
+            let base = u32::from(network) as u128 & !(size - 1);
+            // /31 and /32 have no network or broadcast address
+            let range = if host_bits >= 2 { 1..size - 1 } else { 0..size };
+            range.map(|offset| IpAddr::V4(Ipv4Addr::from((base + offset) as u32))).collect()
+        }
+        IpAddr::V6(network) => {
+            let base = u128::from(network) & !(size - 1);
+            (0..size).map(|offset| IpAddr::V6(Ipv6Addr::from(base + offset))).collect()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn scan_targets_finds_listeners_with_bounded_concurrency() {
+        let listeners: Vec<_> = (0..3).map(|_| std::net::TcpListener::bind("127.0.0.1:0").unwrap()).collect();
+        let mut ports: Vec<u16> = listeners.iter().map(|l| l.local_addr().unwrap().port()).collect();
+        ports.reverse();
+        let hosts = [IpAddr::V4(Ipv4Addr::LOCALHOST)];
+
+        let last = Arc::new(Mutex::new(None));
+        let progress = last.clone();
+        let results = PortScanner::new()
+            .with_concurrency(2)
+            .scan_targets(&hosts, &ports, move |p| *progress.lock().unwrap() = Some((p.scanned, p.total, p.open)))
+            .await;
+
+        let found: Vec<u16> = results.iter().map(|p| p.port).collect();
+        let mut expected = ports.clone();
+        expected.sort();
+        assert_eq!(found, expected);
+        assert_eq!(*last.lock().unwrap(), Some((3, 3, 3)));
+    }
+}
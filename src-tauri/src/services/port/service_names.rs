@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use crate::models::config::ServiceNameOverride;
+use crate::models::port::Protocol;
+
+const REGISTRY: &str = include_str!("service_names.txt");
+
+static DATABASE: OnceLock<HashMap<(u16, Protocol), &'static str>> = OnceLock::new();
+static OVERRIDES: OnceLock<Mutex<Vec<ServiceNameOverride>>> = OnceLock::new();
+
+/// Port → service name lookup: user overrides from `Config.service_names`
+/// first, then the bundled IANA subset, so the UI and LLM prompts can show
+/// `5432 (postgresql)` instead of a bare number.
+pub struct ServiceNames;
+
+impl ServiceNames {
+    pub fn configure(overrides: &[ServiceNameOverride]) {
+        *overrides_store().lock().unwrap_or_else(|e| e.into_inner()) = overrides
+            .iter()
+            .filter(|o| !o.name.trim().is_empty())
+            .cloned()
+            .collect();
+    }
+
+    pub fn lookup(port: u16, protocol: &Protocol) -> Option<String> {
+        let overrides = overrides_store().lock().unwrap_or_else(|e| e.into_inner());
+        // An override for one protocol wins over one for both
+        let custom = overrides
+            .iter()
+            .filter(|o| o.port == port && o.protocol.as_ref().is_none_or(|p| p == protocol))
+            .max_by_key(|o| o.protocol.is_some());
+        if let Some(custom) = custom {
+            return Some(custom.name.trim().to_string());
+        }
+        database().get(&(port, protocol.clone())).map(|name| name.to_string())
+    }
+
+    /// `5432 (postgresql)`, or just the number for unknown ports
+    pub fn label(port: u16, protocol: &Protocol) -> String {
+        match Self::lookup(port, protocol) {
+            Some(name) => format!("{} ({})", port, name),
+            None => port.to_string(),
+        }
+    }
+
+    /// Service JSON from the frontend with numeric `ports` replaced by
+    /// labels, for LLM prompts. Input that isn't JSON is returned unchanged.
+    pub fn annotate(services_json: &str) -> String {
+        match serde_json::from_str::<serde_json::Value>(services_json) {
+            Ok(mut value) => {
+                annotate_ports(&mut value);
+                serde_json::to_string_pretty(&value).unwrap_or_else(|_| services_json.to_string())
+            }
+            Err(_) => services_json.to_string(),
+        }
+    }
+}
+
+fn annotate_ports(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Array(items) => items.iter_mut().for_each(annotate_ports),
+        serde_json::Value::Object(object) => {
+            for (key, field) in object.iter_mut() {
+                match field {
+                    serde_json::Value::Array(ports) if key == "ports" => {
+                        for port in ports.iter_mut() {
+                            if let Some(number) = port.as_u64().and_then(|n| u16::try_from(n).ok()) {
+                                *port = serde_json::Value::String(ServiceNames::label(number, &Protocol::Tcp));
+                            }
+                        }
+                    }
+                    _ => annotate_ports(field),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn overrides_store() -> &'static Mutex<Vec<ServiceNameOverride>> {
+    OVERRIDES.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn database() -> &'static HashMap<(u16, Protocol), &'static str> {
+    DATABASE.get_or_init(|| {
+        REGISTRY
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let name = fields.next()?;
+                let (port, protocol) = fields.next()?.split_once('/')?;
+                let protocol = match protocol {
+                    "tcp" => Protocol::Tcp,
+                    "udp" => Protocol::Udp,
+                    _ => return None,
+                };
+                Some(((port.parse().ok()?, protocol), name))
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,813 @@
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::port::{PortInfo, Protocol};
+use super::fingerprint::probe_address;
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Upper bound for the server's first flight (hello, certificates, done)
+const MAX_FLIGHT_BYTES: usize = 64 * 1024;
+/// Certificates expiring sooner are reported
+pub(crate) const EXPIRY_WARNING_DAYS: i64 = 30;
+/// Ports inspected in parallel by `scan_ports`
+const PARALLEL_PROBES: usize = 16;
+
+pub(crate) const SSL_3_0: u16 = 0x0300;
+pub(crate) const TLS_1_0: u16 = 0x0301;
+pub(crate) const TLS_1_1: u16 = 0x0302;
+pub(crate) const TLS_1_2: u16 = 0x0303;
+pub(crate) const TLS_1_3: u16 = 0x0304;
+
+/// Offered when fetching the certificate chain: modern suites first, legacy
+/// CBC suites last so old servers still answer
+pub(crate) const DEFAULT_SUITES: &[u16] = &[
+    0xc02f, 0xc030, 0xc02b, 0xc02c, 0xcca8, 0xcca9, 0x009e, 0x009f, 0x009c, 0x009d,
+    0xc013, 0xc014, 0xc009, 0xc00a, 0x002f, 0x0035, 0x000a,
+];
+const TLS13_SUITES: &[u16] = &[0x1301, 0x1302, 0x1303];
+
+/// Suites offering no or broken encryption (NULL, EXPORT, DES, RC4, 3DES, anonymous)
+const WEAK_SUITES: &[u16] = &[
+    0x0001, 0x0002, 0x003b, 0x0003, 0x0006, 0x0008, 0x0009, 0x0004, 0x0005, 0xc011, 0xc007,
+    0x000a, 0x0016, 0xc012, 0xc008, 0x0018, 0x001b, 0x0034, 0x003a,
+];
+
+const CIPHER_NAMES: &[(u16, &str)] = &[
+    (0x0001, "TLS_RSA_WITH_NULL_MD5"),
+    (0x0002, "TLS_RSA_WITH_NULL_SHA"),
+    (0x0003, "TLS_RSA_EXPORT_WITH_RC4_40_MD5"),
+    (0x0004, "TLS_RSA_WITH_RC4_128_MD5"),
+    (0x0005, "TLS_RSA_WITH_RC4_128_SHA"),
+    (0x0006, "TLS_RSA_EXPORT_WITH_RC2_CBC_40_MD5"),
+    (0x0008, "TLS_RSA_EXPORT_WITH_DES40_CBC_SHA"),
+    (0x0009, "TLS_RSA_WITH_DES_CBC_SHA"),
+    (0x000a, "TLS_RSA_WITH_3DES_EDE_CBC_SHA"),
+    (0x0016, "TLS_DHE_RSA_WITH_3DES_EDE_CBC_SHA"),
+    (0x0018, "TLS_DH_anon_WITH_RC4_128_MD5"),
+    (0x001b, "TLS_DH_anon_WITH_3DES_EDE_CBC_SHA"),
+    (0x002f, "TLS_RSA_WITH_AES_128_CBC_SHA"),
+    (0x0034, "TLS_DH_anon_WITH_AES_128_CBC_SHA"),
+    (0x0035, "TLS_RSA_WITH_AES_256_CBC_SHA"),
+    (0x003a, "TLS_DH_anon_WITH_AES_256_CBC_SHA"),
+    (0x003b, "TLS_RSA_WITH_NULL_SHA256"),
+    (0x009c, "TLS_RSA_WITH_AES_128_GCM_SHA256"),
+    (0x009d, "TLS_RSA_WITH_AES_256_GCM_SHA384"),
+    (0x009e, "TLS_DHE_RSA_WITH_AES_128_GCM_SHA256"),
+    (0x009f, "TLS_DHE_RSA_WITH_AES_256_GCM_SHA384"),
+    (0x1301, "TLS_AES_128_GCM_SHA256"),
+    (0x1302, "TLS_AES_256_GCM_SHA384"),
+    (0x1303, "TLS_CHACHA20_POLY1305_SHA256"),
+    (0xc007, "TLS_ECDHE_ECDSA_WITH_RC4_128_SHA"),
+    (0xc008, "TLS_ECDHE_ECDSA_WITH_3DES_EDE_CBC_SHA"),
+    (0xc009, "TLS_ECDHE_ECDSA_WITH_AES_128_CBC_SHA"),
+    (0xc00a, "TLS_ECDHE_ECDSA_WITH_AES_256_CBC_SHA"),
+    (0xc011, "TLS_ECDHE_RSA_WITH_RC4_128_SHA"),
+    (0xc012, "TLS_ECDHE_RSA_WITH_3DES_EDE_CBC_SHA"),
+    (0xc013, "TLS_ECDHE_RSA_WITH_AES_128_CBC_SHA"),
+    (0xc014, "TLS_ECDHE_RSA_WITH_AES_256_CBC_SHA"),
+    (0xc02b, "TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256"),
+    (0xc02c, "TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384"),
+    (0xc02f, "TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256"),
+    (0xc030, "TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384"),
+    (0xcca8, "TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256"),
+    (0xcca9, "TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    /// Hex encoded
+    pub serial: String,
+    pub not_before: Option<DateTime<Utc>>,
+    pub not_after: Option<DateTime<Utc>>,
+    pub subject_alt_names: Vec<String>,
+    pub signature_algorithm: String,
+    /// Issuer and subject are identical
+    pub self_signed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsProtocolSupport {
+    /// e.g. `TLS 1.2`
+    pub version: String,
+    pub supported: bool,
+    /// SSL 3.0, TLS 1.0 and TLS 1.1 are deprecated
+    pub deprecated: bool,
+}
+
+/// Certificate chain and protocol/cipher support of a TLS endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsInspection {
+    pub host: String,
+    pub port: u16,
+    /// Leaf certificate first. Empty if the server only speaks TLS 1.3,
+    /// where certificates are encrypted.
+    pub chain: Vec<CertificateInfo>,
+    pub protocols: Vec<TlsProtocolSupport>,
+    /// Suite chosen by the server for a regular TLS 1.2 handshake
+    pub cipher_suite: Option<String>,
+    /// Weak suites the server accepted
+    pub weak_ciphers: Vec<String>,
+    /// The leaf certificate is self-signed and sent without a chain
+    pub self_signed: bool,
+    /// Days until the leaf certificate expires, negative if expired
+    pub expires_in_days: Option<i64>,
+    /// Findings in German, ready for display
+    pub issues: Vec<String>,
+}
+
+/// Server answer up to the end of its first flight
+struct ServerFlight {
+    version: u16,
+    cipher_suite: u16,
+    /// DER certificates, leaf first (TLS 1.2 and older only)
+    certificates: Vec<Vec<u8>>,
+}
+
+/// Retrieves certificates and probes protocol/cipher support with raw
+/// handshakes. No connection is completed, so no key exchange takes place.
+pub struct TlsInspector;
+
+impl TlsInspector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn inspect(&self, host: &str, port: u16) -> Result<TlsInspection, Box<dyn std::error::Error + Send + Sync>> {
+        let addr = (host, port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| format!("{} konnte nicht aufgelöst werden", host))?;
+        let server_name = host.parse::<IpAddr>().is_err().then_some(host);
+        self.inspect_addr(addr, host, server_name)
+    }
+
+    /// Inspect every listening TCP port that answers a TLS handshake
+    pub fn scan_ports(&self, ports: &[PortInfo]) -> Vec<TlsInspection> {
+        let mut targets: Vec<SocketAddr> = ports
+            .iter()
+            .filter(|p| matches!(p.protocol, Protocol::Tcp))
+            .map(probe_address)
+            .collect();
+        targets.sort();
+        targets.dedup();
+
+        let mut results = Vec::new();
+        for chunk in targets.chunks(PARALLEL_PROBES) {
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = chunk
+                    .iter()
+                    .map(|&addr| {
+                        scope.spawn(move || self.inspect_addr(addr, &addr.ip().to_string(), None).ok())
+                    })
+                    .collect();
+                results.extend(handles.into_iter().filter_map(|h| h.join().ok().flatten()));
+            });
+        }
+        results.sort_by_key(|r| r.port);
+        results
+    }
+
+    fn inspect_addr(
+        &self,
+        addr: SocketAddr,
+        host: &str,
+        server_name: Option<&str>,
+    ) -> Result<TlsInspection, Box<dyn std::error::Error + Send + Sync>> {
+        let primary = handshake(addr, &client_hello(TLS_1_2, DEFAULT_SUITES, server_name, false));
+        let tls13 = handshake(addr, &client_hello(TLS_1_2, TLS13_SUITES, server_name, true))
+            .is_ok_and(|flight| flight.version == TLS_1_3);
+
+        let primary = match primary {
+            Ok(flight) => Some(flight),
+            Err(_) if tls13 => None,
+            Err(e) => return Err(e),
+        };
+
+        let mut protocols = Vec::new();
+        let mut weak_versions = Vec::new();
+        for (version, deprecated) in [(SSL_3_0, true), (TLS_1_0, true), (TLS_1_1, true), (TLS_1_2, false)] {
+            let supported = if version == TLS_1_2 {
+                primary.as_ref().is_some_and(|f| f.version == TLS_1_2)
+            } else {
+                handshake(addr, &client_hello(version, DEFAULT_SUITES, server_name, false))
+                    .is_ok_and(|flight| flight.version == version)
+            };
+            if supported && deprecated {
+                weak_versions.push(version);
+            }
+            protocols.push(TlsProtocolSupport {
+                version: version_name(version).to_string(),
+                supported,
+                deprecated,
+            });
+        }
+        protocols.push(TlsProtocolSupport {
+            version: version_name(TLS_1_3).to_string(),
+            supported: tls13,
+            deprecated: false,
+        });
+
+        // Weak suites are tried with the newest pre-1.3 version the server accepts
+        let weak_ciphers = primary
+            .as_ref()
+            .map(|f| f.version)
+            .or_else(|| weak_versions.last().copied())
+            .map(|version| accepted_suites(addr, version, WEAK_SUITES, server_name))
+            .unwrap_or_default()
+            .into_iter()
+            .map(cipher_name)
+            .collect::<Vec<_>>();
+
+        let chain: Vec<CertificateInfo> = primary
+            .as_ref()
+            .map(|f| f.certificates.iter().filter_map(|der| parse_certificate(der)).collect())
+            .unwrap_or_default();
+        let self_signed = chain.len() == 1 && chain[0].self_signed;
+        let expires_in_days = chain
+            .first()
+            .and_then(|leaf| leaf.not_after)
+            .map(|not_after| (not_after - Utc::now()).num_days());
+
+        let mut issues = Vec::new();
+        if chain.is_empty() {
+            issues.push("Zertifikatskette nicht lesbar (Server spricht nur TLS 1.3)".to_string());
+        }
+        match expires_in_days {
+            Some(days) if days < 0 => issues.push(format!("Zertifikat ist seit {} Tagen abgelaufen", -days)),
+            Some(days) if days < EXPIRY_WARNING_DAYS => issues.push(format!("Zertifikat läuft in {} Tagen ab", days)),
+            _ => {}
+        }
+        if self_signed {
+            issues.push("Selbstsigniertes Zertifikat".to_string());
+        }
+        if let Some(leaf) = chain.first() {
+            let algorithm = leaf.signature_algorithm.to_lowercase();
+            if algorithm.starts_with("sha1") || algorithm.starts_with("md5") {
+                issues.push(format!("Schwacher Signaturalgorithmus {}", leaf.signature_algorithm));
+            }
+        }
+        for version in &weak_versions {
+            issues.push(format!("Veraltetes Protokoll {} wird unterstützt", version_name(*version)));
+        }
+        for cipher in &weak_ciphers {
+            issues.push(format!("Schwache Cipher-Suite {} wird akzeptiert", cipher));
+        }
+
+        Ok(TlsInspection {
+            host: host.to_string(),
+            port: addr.port(),
+            chain,
+            protocols,
+            cipher_suite: primary.as_ref().map(|f| cipher_name(f.cipher_suite)),
+            weak_ciphers,
+            self_signed,
+            expires_in_days,
+            issues,
+        })
+    }
+}
+
+impl Default for TlsInspector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Offer `suites` repeatedly, dropping the one the server picked each time
+fn accepted_suites(addr: SocketAddr, version: u16, suites: &[u16], server_name: Option<&str>) -> Vec<u16> {
+    let mut offered = suites.to_vec();
+    let mut accepted = Vec::new();
+    while !offered.is_empty() {
+        let Ok(flight) = handshake(addr, &client_hello(version, &offered, server_name, false)) else {
+            break;
+        };
+        if !offered.contains(&flight.cipher_suite) {
+            break;
+        }
+        offered.retain(|&suite| suite != flight.cipher_suite);
+        accepted.push(flight.cipher_suite);
+    }
+    accepted
+}
+
+fn version_name(version: u16) -> &'static str {
+    match version {
+        SSL_3_0 => "SSL 3.0",
+        TLS_1_0 => "TLS 1.0",
+        TLS_1_1 => "TLS 1.1",
+        TLS_1_2 => "TLS 1.2",
+        TLS_1_3 => "TLS 1.3",
+        _ => "unbekannt",
+    }
+}
+
+fn cipher_name(suite: u16) -> String {
+    CIPHER_NAMES
+        .iter()
+        .find(|(id, _)| *id == suite)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| format!("0x{:04x}", suite))
+}
+
+/// Build a ClientHello record. With `tls13` the legacy version stays at 1.2
+/// and TLS 1.3 is offered via `supported_versions` and an x25519 key share.
+pub(crate) fn client_hello(version: u16, cipher_suites: &[u16], server_name: Option<&str>, tls13: bool) -> Vec<u8> {
+    let mut extensions = Vec::new();
+    if let Some(name) = server_name {
+        let name = name.as_bytes();
+        let mut list = vec![0x00];
+        list.extend((name.len() as u16).to_be_bytes());
+        list.extend(name);
+        let mut sni = (list.len() as u16).to_be_bytes().to_vec();
+        sni.extend(list);
+        push_extension(&mut extensions, 0x0000, &sni);
+    }
+    // supported_groups: x25519, secp256r1, secp384r1
+    push_extension(&mut extensions, 0x000a, &[0x00, 0x06, 0x00, 0x1d, 0x00, 0x17, 0x00, 0x18]);
+    // ec_point_formats: uncompressed
+    push_extension(&mut extensions, 0x000b, &[0x01, 0x00]);
+    // signature_algorithms: RSA PKCS#1, ECDSA and RSA-PSS with SHA-256/384/512, plus SHA-1
+    push_extension(
+        &mut extensions,
+        0x000d,
+        &[
+            0x00, 0x14, 0x04, 0x01, 0x05, 0x01, 0x06, 0x01, 0x04, 0x03, 0x05, 0x03, 0x08, 0x04, 0x08, 0x05,
+            0x08, 0x06, 0x02, 0x01, 0x02, 0x03,
+        ],
+    );
+    // renegotiation_info, required by some older servers
+    push_extension(&mut extensions, 0xff01, &[0x00]);
+    if tls13 {
+        push_extension(&mut extensions, 0x002b, &[0x02, 0x03, 0x04]);
+        let mut key_share = vec![0x00, 0x24, 0x00, 0x1d, 0x00, 0x20];
+        key_share.extend(std::iter::repeat_n(0x09, 32));
+        push_extension(&mut extensions, 0x0033, &key_share);
+    }
+
+    let mut hello = version.to_be_bytes().to_vec();
+    hello.extend(std::iter::repeat_n(0x2a, 32)); // random
+    hello.push(0); // session id
+    hello.extend(((cipher_suites.len() * 2) as u16).to_be_bytes());
+    for suite in cipher_suites {
+        hello.extend(suite.to_be_bytes());
+    }
+    hello.extend([0x01, 0x00]); // null compression
+    hello.extend((extensions.len() as u16).to_be_bytes());
+    hello.extend(extensions);
+
+    let mut handshake = vec![0x01];
+    handshake.extend(&(hello.len() as u32).to_be_bytes()[1..]);
+    handshake.extend(hello);
+
+    // Servers expect 1.0 at the record layer for anything newer
+    let mut record = vec![0x16];
+    record.extend(version.min(TLS_1_0).to_be_bytes());
+    record.extend((handshake.len() as u16).to_be_bytes());
+    record.extend(handshake);
+    record
+}
+
+fn push_extension(extensions: &mut Vec<u8>, kind: u16, data: &[u8]) {
+    extensions.extend(kind.to_be_bytes());
+    extensions.extend((data.len() as u16).to_be_bytes());
+    extensions.extend(data);
+}
+
+/// Send `hello` and read handshake records until ServerHelloDone, or until
+/// encrypted records start (TLS 1.3)
+fn handshake(addr: SocketAddr, hello: &[u8]) -> Result<ServerFlight, Box<dyn std::error::Error + Send + Sync>> {
+    let mut stream = TcpStream::connect_timeout(&addr, HANDSHAKE_TIMEOUT)?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    stream.set_write_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    stream.write_all(hello)?;
+
+    let mut messages = Vec::new();
+    let mut received = 0;
+    let mut flight: Option<ServerFlight> = None;
+
+    loop {
+        let mut header = [0u8; 5];
+        if stream.read_exact(&mut header).is_err() {
+            break;
+        }
+        let length = u16::from_be_bytes([header[3], header[4]]) as usize;
+        received += length;
+        if received > MAX_FLIGHT_BYTES {
+            break;
+        }
+        let mut body = vec![0u8; length];
+        stream.read_exact(&mut body)?;
+
+        match header[0] {
+            0x16 => messages.extend(body),
+            0x15 if flight.is_none() => return Err("Server hat den Handshake abgelehnt".into()),
+            // ChangeCipherSpec or encrypted data: nothing readable follows
+            _ => break,
+        }
+
+        let mut done = false;
+        while messages.len() >= 4 {
+            let size = u32::from_be_bytes([0, messages[1], messages[2], messages[3]]) as usize;
+            if messages.len() < 4 + size {
+                break;
+            }
+            let message: Vec<u8> = messages.drain(..4 + size).collect();
+            match message[0] {
+                0x02 => flight = Some(parse_server_hello(&message[4..]).ok_or("Ungültiges ServerHello")?),
+                0x0b => {
+                    if let Some(flight) = flight.as_mut() {
+                        flight.certificates = parse_certificate_list(&message[4..]);
+                    }
+                }
+                0x0e => done = true,
+                _ => {}
+            }
+        }
+        if done || flight.as_ref().is_some_and(|f| f.version == TLS_1_3) {
+            break;
+        }
+    }
+
+    flight.ok_or_else(|| "Keine TLS-Antwort".into())
+}
+
+fn parse_server_hello(body: &[u8]) -> Option<ServerFlight> {
+    let mut version = u16::from_be_bytes([*body.first()?, *body.get(1)?]);
+    let session_len = *body.get(34)? as usize;
+    let mut offset = 35 + session_len;
+    let cipher_suite = u16::from_be_bytes([*body.get(offset)?, *body.get(offset + 1)?]);
+    body.get(offset + 2)?; // compression method
+    offset += 3;
+
+    if let Some(extensions) = body.get(offset + 2..) {
+        let mut rest = extensions;
+        while rest.len() >= 4 {
+            let kind = u16::from_be_bytes([rest[0], rest[1]]);
+            let size = u16::from_be_bytes([rest[2], rest[3]]) as usize;
+            let data = rest.get(4..4 + size)?;
+            if kind == 0x002b && size == 2 {
+                version = u16::from_be_bytes([data[0], data[1]]);
+            }
+            rest = &rest[4 + size..];
+        }
+    }
+
+    Some(ServerFlight {
+        version,
+        cipher_suite,
+        certificates: Vec::new(),
+    })
+}
+
+fn parse_certificate_list(body: &[u8]) -> Vec<Vec<u8>> {
+    let mut certificates = Vec::new();
+    let mut rest = body.get(3..).unwrap_or_default();
+    while rest.len() >= 3 {
+        let size = u32::from_be_bytes([0, rest[0], rest[1], rest[2]]) as usize;
+        let Some(der) = rest.get(3..3 + size) else {
+            break;
+        };
+        certificates.push(der.to_vec());
+        rest = &rest[3 + size..];
+    }
+    certificates
+}
+
+/// Minimal DER reader, just enough to walk an X.509 certificate
+struct Der<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Der<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Next element as (tag, content, full encoding)
+    fn next(&mut self) -> Option<(u8, &'a [u8], &'a [u8])> {
+        let tag = *self.data.first()?;
+        let first = *self.data.get(1)? as usize;
+        let (length, header) = if first < 0x80 {
+            (first, 2)
+        } else {
+            let count = first & 0x7f;
+            if count == 0 || count > 4 {
+                return None;
+            }
+            let bytes = self.data.get(2..2 + count)?;
+            (bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize), 2 + count)
+        };
+        let full = self.data.get(..header + length)?;
+        self.data = &self.data[header + length..];
+        Some((tag, &full[header..], full))
+    }
+}
+
+fn parse_certificate(der: &[u8]) -> Option<CertificateInfo> {
+    let (_, certificate, _) = Der::new(der).next()?;
+    let mut certificate = Der::new(certificate);
+    let (_, tbs, _) = certificate.next()?;
+    let (_, signature_algorithm, _) = certificate.next()?;
+
+    let mut tbs = Der::new(tbs);
+    let mut field = tbs.next()?;
+    if field.0 == 0xa0 {
+        // Explicit version tag
+        field = tbs.next()?;
+    }
+    let serial = field.1.iter().map(|b| format!("{:02x}", b)).collect();
+    tbs.next()?; // signature algorithm, repeated
+    let (_, issuer, issuer_raw) = tbs.next()?;
+    let (_, validity, _) = tbs.next()?;
+    let (_, subject, subject_raw) = tbs.next()?;
+    tbs.next()?; // public key
+
+    let mut subject_alt_names = Vec::new();
+    while let Some((tag, content, _)) = tbs.next() {
+        if tag == 0xa3 {
+            subject_alt_names = parse_subject_alt_names(content);
+        }
+    }
+
+    let mut validity = Der::new(validity);
+    let not_before = validity.next().and_then(|(tag, content, _)| parse_time(tag, content));
+    let not_after = validity.next().and_then(|(tag, content, _)| parse_time(tag, content));
+
+    Some(CertificateInfo {
+        subject: format_name(subject),
+        issuer: format_name(issuer),
+        serial,
+        not_before,
+        not_after,
+        subject_alt_names,
+        signature_algorithm: Der::new(signature_algorithm)
+            .next()
+            .map(|(_, oid, _)| algorithm_name(&oid_string(oid)))
+            .unwrap_or_default(),
+        self_signed: issuer_raw == subject_raw,
+    })
+}
+
+/// `CN=example.com, O=Example`
+fn format_name(name: &[u8]) -> String {
+    let mut parts = Vec::new();
+    let mut sets = Der::new(name);
+    while let Some((_, set, _)) = sets.next() {
+        let mut attributes = Der::new(set);
+        while let Some((_, attribute, _)) = attributes.next() {
+            let mut attribute = Der::new(attribute);
+            let (Some((_, oid, _)), Some((_, value, _))) = (attribute.next(), attribute.next()) else {
+                continue;
+            };
+            let oid = oid_string(oid);
+            let key = match oid.as_str() {
+                "2.5.4.3" => "CN",
+                "2.5.4.6" => "C",
+                "2.5.4.7" => "L",
+                "2.5.4.8" => "ST",
+                "2.5.4.10" => "O",
+                "2.5.4.11" => "OU",
+                "1.2.840.113549.1.9.1" => "E",
+                _ => oid.as_str(),
+            };
+            parts.push(format!("{}={}", key, String::from_utf8_lossy(value)));
+        }
+    }
+    parts.join(", ")
+}
+
+fn parse_subject_alt_names(extensions: &[u8]) -> Vec<String> {
+    let Some((_, extensions, _)) = Der::new(extensions).next() else {
+        return Vec::new();
+    };
+    let mut names = Vec::new();
+    let mut extensions = Der::new(extensions);
+    while let Some((_, extension, _)) = extensions.next() {
+        let mut extension = Der::new(extension);
+        let Some((_, oid, _)) = extension.next() else {
+            continue;
+        };
+        if oid_string(oid) != "2.5.29.17" {
+            continue;
+        }
+        // Optional critical flag before the OCTET STRING
+        let Some(value) = std::iter::from_fn(|| extension.next()).find(|(tag, _, _)| *tag == 0x04) else {
+            continue;
+        };
+        let Some((_, general_names, _)) = Der::new(value.1).next() else {
+            continue;
+        };
+        let mut general_names = Der::new(general_names);
+        while let Some((tag, content, _)) = general_names.next() {
+            match (tag, content.len()) {
+                (0x82, _) => names.push(String::from_utf8_lossy(content).to_string()),
+                (0x87, 4) => names.push(IpAddr::from(<[u8; 4]>::try_from(content).unwrap_or_default()).to_string()),
+                (0x87, 16) => names.push(IpAddr::from(<[u8; 16]>::try_from(content).unwrap_or_default()).to_string()),
+                _ => {}
+            }
+        }
+    }
+    names
+}
+
+/// UTCTime (`YYMMDDHHMMSSZ`) or GeneralizedTime (`YYYYMMDDHHMMSSZ`)
+fn parse_time(tag: u8, content: &[u8]) -> Option<DateTime<Utc>> {
+    let text = std::str::from_utf8(content).ok()?.trim_end_matches('Z');
+    let text = match tag {
+        0x17 => {
+            let year: u32 = text.get(..2)?.parse().ok()?;
+            let century = if year >= 50 { "19" } else { "20" };
+            format!("{}{}", century, text)
+        }
+        0x18 => text.to_string(),
+        _ => return None,
+    };
+    NaiveDateTime::parse_from_str(&text, "%Y%m%d%H%M%S")
+        .ok()
+        .map(|time| time.and_utc())
+}
+
+fn oid_string(oid: &[u8]) -> String {
+    let Some((&first, rest)) = oid.split_first() else {
+        return String::new();
+    };
+    let mut parts = vec![(first / 40) as u64, (first % 40) as u64];
+    let mut value = 0u64;
+    for &byte in rest {
+        value = (value << 7) | (byte & 0x7f) as u64;
+        if byte & 0x80 == 0 {
+            parts.push(value);
+            value = 0;
+        }
+    }
+    parts.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(".")
+}
+
+fn algorithm_name(oid: &str) -> String {
+    match oid {
+        "1.2.840.113549.1.1.4" => "md5WithRSAEncryption",
+        "1.2.840.113549.1.1.5" => "sha1WithRSAEncryption",
+        "1.2.840.113549.1.1.10" => "rsassaPss",
+        "1.2.840.113549.1.1.11" => "sha256WithRSAEncryption",
+        "1.2.840.113549.1.1.12" => "sha384WithRSAEncryption",
+        "1.2.840.113549.1.1.13" => "sha512WithRSAEncryption",
+        "1.2.840.10045.4.1" => "sha1WithECDSA",
+        "1.2.840.10045.4.3.2" => "ecdsa-with-SHA256",
+        "1.2.840.10045.4.3.3" => "ecdsa-with-SHA384",
+        "1.2.840.10045.4.3.4" => "ecdsa-with-SHA512",
+        "1.3.101.112" => "Ed25519",
+        _ => oid,
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use chrono::TimeZone;
+
+    // Generated with openssl: a CA and a leaf it signed, both P-256
+    const LEAF: &[u8] = include_bytes!("testdata/leaf.der");
+    const CA: &[u8] = include_bytes!("testdata/ca.der");
+    // Server flights recorded from `openssl s_server` serving LEAF with CA as chain
+    const TLS12_FLIGHT: &[u8] = include_bytes!("testdata/tls12_flight.bin");
+    const TLS13_FLIGHT: &[u8] = include_bytes!("testdata/tls13_flight.bin");
+
+    /// Answer one ClientHello with `response`, then close the connection
+    fn replay(response: &'static [u8]) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut hello = [0u8; 1024];
+            let _ = stream.read(&mut hello);
+            let _ = stream.write_all(response);
+        });
+        addr
+    }
+
+    fn leaked(bytes: Vec<u8>) -> &'static [u8] {
+        Box::leak(bytes.into_boxed_slice())
+    }
+
+    #[test]
+    fn parses_leaf_certificate() {
+        let info = parse_certificate(LEAF).unwrap();
+        assert_eq!(info.subject, "CN=nas.local, O=Home Lab");
+        assert_eq!(info.issuer, "CN=Home Lab CA, O=Home Lab");
+        assert_eq!(info.serial, "1234abcd");
+        assert_eq!(info.not_before, Some(Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()));
+        assert_eq!(info.not_after, Some(Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap()));
+        assert_eq!(info.subject_alt_names, ["nas.local", "nas", "192.168.1.10", "fd00::10"]);
+        assert_eq!(info.signature_algorithm, "ecdsa-with-SHA256");
+        assert!(!info.self_signed);
+    }
+
+    #[test]
+    fn parses_self_signed_ca_with_generalized_time() {
+        let info = parse_certificate(CA).unwrap();
+        assert!(info.self_signed);
+        assert!(info.subject_alt_names.is_empty());
+        // Dates from 2050 on are GeneralizedTime
+        assert_eq!(info.not_after, Some(Utc.with_ymd_and_hms(2051, 1, 1, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn truncated_certificates_are_rejected() {
+        for length in 0..LEAF.len() {
+            assert!(parse_certificate(&LEAF[..length]).is_none(), "prefix of {} bytes", length);
+        }
+    }
+
+    #[test]
+    fn lying_der_lengths_are_rejected() {
+        // Outer SEQUENCE claims 4 GiB
+        let mut huge = vec![0x30, 0x84, 0xff, 0xff, 0xff, 0xff];
+        huge.extend(&LEAF[4..]);
+        assert!(parse_certificate(&huge).is_none());
+        // Length of length beyond what fits
+        assert!(parse_certificate(&[0x30, 0x89, 1, 2, 3, 4, 5, 6, 7, 8, 9]).is_none());
+        assert!(parse_certificate(&[0x30, 0x80]).is_none());
+
+        // Inner TBS length larger than the certificate; must not panic
+        let mut inner = LEAF.to_vec();
+        inner[6] = 0xff;
+        let _ = parse_certificate(&inner);
+    }
+
+    #[test]
+    fn reads_tls12_flight() {
+        let flight = handshake(replay(TLS12_FLIGHT), &client_hello(TLS_1_2, DEFAULT_SUITES, None, false)).unwrap();
+        assert_eq!(flight.version, TLS_1_2);
+        assert_eq!(cipher_name(flight.cipher_suite), "TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256");
+        assert_eq!(flight.certificates, [LEAF.to_vec(), CA.to_vec()]);
+    }
+
+    #[test]
+    fn reads_tls13_server_hello() {
+        let flight = handshake(replay(TLS13_FLIGHT), &client_hello(TLS_1_2, TLS13_SUITES, None, true)).unwrap();
+        assert_eq!(flight.version, TLS_1_3);
+        assert_eq!(flight.cipher_suite, 0x1302);
+        assert!(flight.certificates.is_empty());
+    }
+
+    #[test]
+    fn truncated_flights_fail_without_panicking() {
+        let hello = client_hello(TLS_1_2, DEFAULT_SUITES, None, false);
+        for length in [0, 3, 5, 20, 70, 100, 500, TLS12_FLIGHT.len() - 1] {
+            let result = handshake(replay(leaked(TLS12_FLIGHT[..length].to_vec())), &hello);
+            // Cut inside the certificate list: the ServerHello alone is still usable
+            if let Ok(flight) = result {
+                assert!(flight.certificates.is_empty(), "prefix of {} bytes", length);
+            }
+        }
+    }
+
+    #[test]
+    fn lying_record_and_message_lengths_fail() {
+        let hello = client_hello(TLS_1_2, DEFAULT_SUITES, None, false);
+
+        // Record claims more bytes than are sent
+        let mut record = TLS12_FLIGHT[..70].to_vec();
+        record[3..5].copy_from_slice(&0x4000u16.to_be_bytes());
+        assert!(handshake(replay(leaked(record)), &hello).is_err());
+
+        // ServerHello claims a session ID longer than the message
+        let mut session = TLS12_FLIGHT[..70].to_vec();
+        session[5 + 4 + 34] = 0xff;
+        assert!(handshake(replay(leaked(session)), &hello).is_err());
+
+        // Alert instead of a ServerHello
+        let alert: &[u8] = &[0x15, 0x03, 0x03, 0x00, 0x02, 0x02, 0x28];
+        assert!(handshake(replay(alert), &hello).is_err());
+    }
+
+    #[test]
+    fn lying_server_hello_fields_are_rejected() {
+        // Handshake body of the recorded ServerHello
+        let body = &TLS12_FLIGHT[9..5 + 0x41];
+        assert!(parse_server_hello(body).is_some());
+        for length in 0..38 {
+            assert!(parse_server_hello(&body[..length]).is_none(), "prefix of {} bytes", length);
+        }
+
+        let mut extension = body.to_vec();
+        let last = extension.len() - 2;
+        extension[last..].copy_from_slice(&0xfff0u16.to_be_bytes());
+        assert!(parse_server_hello(&extension).is_none());
+    }
+
+    #[test]
+    fn lying_certificate_list_stops_at_the_end() {
+        let mut list = vec![0, 0, 0];
+        list.extend([0x00, 0x01, 0xbb]);
+        list.extend(&LEAF[..LEAF.len() / 2]);
+        assert!(parse_certificate_list(&list).is_empty());
+        assert!(parse_certificate_list(&[0x00]).is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+// Apple GPU utilization and per-process energy impact from a privileged
+// powermetrics process
+
+use crate::models::config::PowermetricsConfig;
+use crate::parsers::gpu::{self, PowermetricsGpu};
+use crate::parsers::powermetrics;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Samples older than this many intervals count as stale
+const STALE_INTERVALS: u64 = 3;
+const MIN_INTERVAL_MS: u64 = 250;
+
+static SAMPLER: OnceLock<Mutex<SamplerState>> = OnceLock::new();
+
+#[derive(Default)]
+struct SamplerState {
+    config: PowermetricsConfig,
+    task: Option<tauri::async_runtime::JoinHandle<()>>,
+    latest: Option<(DateTime<Utc>, PowermetricsGpu)>,
+    /// Energy impact per PID
+    energy_impact: Option<(DateTime<Utc>, HashMap<u32, f32>)>,
+    last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowermetricsStatus {
+    /// Only macOS has powermetrics
+    pub supported: bool,
+    pub enabled: bool,
+    pub running: bool,
+    pub last_sample_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+/// Runs `sudo -n powermetrics --samplers gpu_power,tasks` in the background
+/// and keeps the latest sample for `SystemMonitor` and `ProcessEnergy`. The app never asks for a
+/// password: without a sudoers rule such as
+/// `%admin ALL=(root) NOPASSWD: /usr/bin/powermetrics` sudo fails right away
+/// and the error is kept for the status.
+pub struct PowermetricsSampler;
+
+impl PowermetricsSampler {
+    pub fn configure(config: &PowermetricsConfig) {
+        let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+        let restart = state.config.interval_ms != config.interval_ms;
+        state.config = config.clone();
+
+        let running = state.task.as_ref().is_some_and(|task| !task.inner().is_finished());
+        if running && (!config.enabled || restart) {
+            if let Some(task) = state.task.take() {
+                task.abort();
+            }
+            state.latest = None;
+            state.energy_impact = None;
+        }
+        if config.enabled && cfg!(target_os = "macos") && (!running || restart) {
+            state.last_error = None;
+            state.task = Some(tauri::async_runtime::spawn(run(config.interval_ms.max(MIN_INTERVAL_MS))));
+        }
+    }
+
+    /// Most recent sample, unless the sampler stopped delivering
+    pub fn latest() -> Option<PowermetricsGpu> {
+        let state = state().lock().unwrap_or_else(|e| e.into_inner());
+        let (taken_at, sample) = state.latest.as_ref()?;
+        state.is_fresh(taken_at).then(|| sample.clone())
+    }
+
+    /// Activity Monitor's "Energy Impact" per PID from the most recent sample
+    pub fn energy_impact() -> Option<HashMap<u32, f32>> {
+        let state = state().lock().unwrap_or_else(|e| e.into_inner());
+        let (taken_at, impact) = state.energy_impact.as_ref()?;
+        state.is_fresh(taken_at).then(|| impact.clone())
+    }
+
+    pub fn status() -> PowermetricsStatus {
+        let state = state().lock().unwrap_or_else(|e| e.into_inner());
+        PowermetricsStatus {
+            supported: cfg!(target_os = "macos"),
+            enabled: state.config.enabled,
+            running: state.task.as_ref().is_some_and(|task| !task.inner().is_finished()),
+            last_sample_at: state.latest.as_ref().map(|(taken_at, _)| *taken_at),
+            last_error: state.last_error.clone(),
+        }
+    }
+}
+
+impl SamplerState {
+    /// Whether a sample is recent enough that the sampler still delivers
+    fn is_fresh(&self, taken_at: &DateTime<Utc>) -> bool {
+        let max_age = self.config.interval_ms.max(MIN_INTERVAL_MS) * STALE_INTERVALS;
+        let age = (Utc::now() - *taken_at).num_milliseconds();
+        age >= 0 && (age as u64) < max_age
+    }
+}
+
+fn state() -> &'static Mutex<SamplerState> {
+    SAMPLER.get_or_init(|| Mutex::new(SamplerState::default()))
+}
+
+async fn run(interval_ms: u64) {
+    if let Err(e) = sample(interval_ms).await {
+        state().lock().unwrap_or_else(|e| e.into_inner()).last_error = Some(e.to_string());
+    }
+}
+
+async fn sample(interval_ms: u64) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    use std::process::Stdio;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+
+    let mut child = tokio::process::Command::new("sudo")
+        .arg("-n")
+        .arg("/usr/bin/powermetrics")
+        .args(["--samplers", "gpu_power,tasks", "--show-process-energy"])
+        .args(["-i", &interval_ms.to_string()])
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let stdout = child.stdout.take().ok_or("powermetrics liefert keine Ausgabe")?;
+    let mut lines = BufReader::new(stdout).lines();
+    let mut block = String::new();
+    while let Some(line) = lines.next_line().await? {
+        // A new block starts with the header line; parse the finished one
+        if line.starts_with("*** Sampled system activity") && !block.is_empty() {
+            let gpu = gpu::parse_powermetrics_gpu(&block).items.pop();
+            let tasks = powermetrics::parse_powermetrics_tasks(&block).items;
+            let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(gpu) = gpu {
+                state.latest = Some((Utc::now(), gpu));
+            }
+            if !tasks.is_empty() {
+                let impact = tasks.into_iter().map(|task| (task.pid, task.energy_impact)).collect();
+                state.energy_impact = Some((Utc::now(), impact));
+            }
+            block.clear();
+        }
+        block.push_str(&line);
+        block.push('\n');
+    }
+
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr).await;
+    }
+    let status = child.wait().await?;
+    let reason = stderr.lines().next().unwrap_or_default().trim().to_string();
+    Err(if reason.is_empty() {
+        format!("powermetrics beendet ({})", status)
+    } else {
+        format!("powermetrics beendet: {}", reason)
+    }
+    .into())
+}
@@ -0,0 +1,120 @@
+// Energy and QoS per process on macOS, from proc_pid_rusage and powermetrics
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use crate::models::service::{EnergyUsage, QosCpuTime, Service};
+use crate::services::powermetrics::PowermetricsSampler;
+
+/// Counters of processes not seen for this long are dropped
+const FORGET_AFTER: Duration = Duration::from_secs(600);
+
+/// Counters of the previous sample per PID
+static PREVIOUS: OnceLock<Mutex<HashMap<u32, (Instant, Counters)>>> = OnceLock::new();
+
+/// Cumulative counters from `rusage_info_v4`, CPU times in nanoseconds
+#[derive(Clone, Copy)]
+struct Counters {
+    billed_energy_nj: u64,
+    user_interactive: u64,
+    user_initiated: u64,
+    default: u64,
+    utility: u64,
+    background: u64,
+    maintenance: u64,
+}
+
+/// Power and CPU time per QoS class are rates between two samples, taken
+/// from the kernel's resource accounting, which needs no privileges for the
+/// user's own processes. The "Energy Impact" score Activity Monitor shows
+/// only exists in powermetrics and is added while `PowermetricsSampler` runs.
+pub struct ProcessEnergy;
+
+impl ProcessEnergy {
+    /// Set `energy` on every service with a PID. A service seen for the
+    /// first time only gets the energy impact.
+    pub fn fill(services: &mut [Service]) {
+        let impact = PowermetricsSampler::energy_impact().unwrap_or_default();
+        let now = Instant::now();
+        let mut previous = PREVIOUS
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        previous.retain(|_, (taken_at, _)| now.duration_since(*taken_at) < FORGET_AFTER);
+
+        for service in services {
+            let Some(pid) = service.pid else {
+                service.energy = None;
+                continue;
+            };
+            let counters = rusage(pid);
+            let mut energy = EnergyUsage {
+                energy_impact: impact.get(&pid).copied(),
+                ..EnergyUsage::default()
+            };
+            if let (Some(current), Some((taken_at, before))) = (counters, previous.get(&pid)) {
+                let seconds = now.duration_since(*taken_at).as_secs_f32();
+                if seconds > 0.0 {
+                    energy.power_milliwatts = (current.billed_energy_nj > 0).then(|| {
+                        current.billed_energy_nj.saturating_sub(before.billed_energy_nj) as f32 / 1e6 / seconds
+                    });
+                    let rate = |after: u64, before: u64| after.saturating_sub(before) as f32 / 1e6 / seconds;
+                    energy.qos = Some(QosCpuTime {
+                        user_interactive: rate(current.user_interactive, before.user_interactive),
+                        user_initiated: rate(current.user_initiated, before.user_initiated),
+                        default: rate(current.default, before.default),
+                        utility: rate(current.utility, before.utility),
+                        background: rate(current.background, before.background),
+                        maintenance: rate(current.maintenance, before.maintenance),
+                    });
+                }
+            }
+            if let Some(current) = counters {
+                previous.insert(pid, (now, current));
+            }
+            service.energy = (counters.is_some() || energy.energy_impact.is_some()).then_some(energy);
+        }
+    }
+}
+
+/// `None` for processes that are gone or belong to another user
+fn rusage(pid: u32) -> Option<Counters> {
+    let mut info: libc::rusage_info_v4 = unsafe { std::mem::zeroed() };
+    let status = unsafe {
+        libc::proc_pid_rusage(
+            pid as libc::c_int,
+            libc::RUSAGE_INFO_V4,
+            &mut info as *mut libc::rusage_info_v4 as *mut libc::rusage_info_t,
+        )
+    };
+    if status != 0 {
+        return None;
+    }
+
+    // CPU times are in Mach absolute time units, which are not nanoseconds on Apple silicon
+    let (numer, denom) = timebase();
+    let nanos = |ticks: u64| (ticks as u128 * numer as u128 / denom as u128) as u64;
+    Some(Counters {
+        billed_energy_nj: info.ri_billed_energy,
+        user_interactive: nanos(info.ri_cpu_time_qos_user_interactive),
+        user_initiated: nanos(info.ri_cpu_time_qos_user_initiated),
+        default: nanos(info.ri_cpu_time_qos_default + info.ri_cpu_time_qos_legacy),
+        utility: nanos(info.ri_cpu_time_qos_utility),
+        background: nanos(info.ri_cpu_time_qos_background),
+        maintenance: nanos(info.ri_cpu_time_qos_maintenance),
+    })
+}
+
+/// Numerator and denominator converting Mach absolute time to nanoseconds
+// libc points to the mach2 crate, not worth a dependency for one call
+#[allow(deprecated)]
+fn timebase() -> (u32, u32) {
+    static TIMEBASE: OnceLock<(u32, u32)> = OnceLock::new();
+    *TIMEBASE.get_or_init(|| {
+        let mut info = libc::mach_timebase_info { numer: 0, denom: 0 };
+        if unsafe { libc::mach_timebase_info(&mut info) } != 0 || info.denom == 0 {
+            return (1, 1);
+        }
+        (info.numer, info.denom)
+    })
+}
@@ -0,0 +1,185 @@
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use serde::{Deserialize, Serialize};
+use crate::llm::ResponseLanguage;
+use crate::services::storage::Storage;
+
+const BUNDLED: &str = include_str!("process_knowledge.json");
+
+static BUNDLED_KNOWLEDGE: OnceLock<KnowledgeFile> = OnceLock::new();
+static CUSTOM_ENTRIES: OnceLock<Mutex<Vec<ProcessKnowledgeEntry>>> = OnceLock::new();
+
+/// How safe it is to stop a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessSafety {
+    /// Needed by the operating system, never stop
+    Essential,
+    /// Stopping disables a feature such as networking, backups or security
+    Important,
+    /// Can be quit without side effects
+    Optional,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessCategory {
+    System,
+    Application,
+    Browser,
+    Development,
+    Database,
+    Communication,
+    Productivity,
+    Security,
+    Media,
+    Utility,
+    Other,
+}
+
+/// A known process. Patterns are matched case-insensitively against the
+/// whole process name, `*` matches any characters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessKnowledgeEntry {
+    pub name: String,
+    pub patterns: Vec<String>,
+    /// Names matching one of these are skipped, e.g. `*docker*` for Dock
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Only names up to this length match, for short patterns like `*go*`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    pub explanation: String,
+    pub safety: ProcessSafety,
+    pub category: ProcessCategory,
+}
+
+impl ProcessKnowledgeEntry {
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.max_length.is_none_or(|max| name.chars().count() <= max)
+            && self.patterns.iter().any(|p| glob_match(&p.to_lowercase(), &name))
+            && !self.exclude.iter().any(|p| glob_match(&p.to_lowercase(), &name))
+    }
+
+    /// `Name - explanation`, as shown for the process
+    pub fn describe(&self) -> String {
+        format!("{} - {}", self.name, self.explanation)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct KnowledgeFile {
+    /// Language code of the explanations
+    language: String,
+    entries: Vec<ProcessKnowledgeEntry>,
+}
+
+/// Explanations for common processes without asking the model. The bundled
+/// entries ship with the app; custom entries are stored in the config
+/// directory, are matched first and are used for every response language.
+pub struct ProcessKnowledge;
+
+impl ProcessKnowledge {
+    /// Parse the bundled entries and read the custom ones
+    pub fn load() {
+        bundled();
+        *custom().lock().unwrap_or_else(|e| e.into_inner()) = load();
+    }
+
+    /// The first entry matching `name`. Bundled entries are only used when
+    /// they are written in the configured response language.
+    pub fn lookup(name: &str) -> Option<ProcessKnowledgeEntry> {
+        if let Some(entry) = custom().lock().unwrap_or_else(|e| e.into_inner()).iter().find(|e| e.matches(name)) {
+            return Some(entry.clone());
+        }
+        let bundled = bundled();
+        if !ResponseLanguage::is(&bundled.language) {
+            return None;
+        }
+        bundled.entries.iter().find(|e| e.matches(name)).cloned()
+    }
+
+    pub fn custom_entries() -> Vec<ProcessKnowledgeEntry> {
+        custom().lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Add a custom entry; one with the same name is replaced
+    pub fn add(entry: ProcessKnowledgeEntry) -> Result<ProcessKnowledgeEntry, Box<dyn std::error::Error + Send + Sync>> {
+        let clean = |values: Vec<String>| -> Vec<String> {
+            values.iter().map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect()
+        };
+        let entry = ProcessKnowledgeEntry {
+            name: entry.name.trim().to_string(),
+            patterns: clean(entry.patterns),
+            exclude: clean(entry.exclude),
+            explanation: entry.explanation.trim().to_string(),
+            ..entry
+        };
+        if entry.name.is_empty() {
+            return Err("Bitte einen Namen angeben".into());
+        }
+        if entry.patterns.is_empty() {
+            return Err("Bitte mindestens ein Muster angeben".into());
+        }
+        if entry.explanation.is_empty() {
+            return Err("Bitte eine Erklärung angeben".into());
+        }
+
+        let mut current = custom().lock().unwrap_or_else(|e| e.into_inner());
+        current.retain(|e| !e.name.eq_ignore_ascii_case(&entry.name));
+        current.push(entry.clone());
+        save(&current)?;
+        Ok(entry)
+    }
+}
+
+/// `*` matches any run of characters, everything else literally
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || text.len() < first.len() + last.len() || !text.ends_with(last) {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+fn bundled() -> &'static KnowledgeFile {
+    BUNDLED_KNOWLEDGE.get_or_init(|| serde_json::from_str(BUNDLED).unwrap_or_default())
+}
+
+fn custom() -> &'static Mutex<Vec<ProcessKnowledgeEntry>> {
+    CUSTOM_ENTRIES.get_or_init(|| Mutex::new(load()))
+}
+
+fn path() -> PathBuf {
+    Storage::config_dir().join("process_knowledge.json")
+}
+
+fn load() -> Vec<ProcessKnowledgeEntry> {
+    std::fs::read(path())
+        .ok()
+        .and_then(|data| serde_json::from_slice(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save(entries: &[ProcessKnowledgeEntry]) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let path = path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    std::fs::write(&path, serde_json::to_vec_pretty(entries)?)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(())
+}
@@ -0,0 +1,79 @@
+// CPU and memory summed over process subtrees
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+use crate::models::service::Service;
+
+/// Kept between snapshots, CPU usage is measured against the previous refresh
+static SYSTEM: OnceLock<Mutex<System>> = OnceLock::new();
+
+/// Parent/child relations and per-process usage from one process refresh.
+/// Services like nginx, postgres or Chrome do their work in child processes,
+/// so the main PID alone understates what they cost.
+pub struct ProcessTree {
+    usage: HashMap<u32, (f32, u64)>,
+    children: HashMap<u32, Vec<u32>>,
+}
+
+impl ProcessTree {
+    /// Refresh CPU and memory of all processes. The first snapshot of a
+    /// session reports 0% CPU.
+    pub fn snapshot() -> Self {
+        let mut system = SYSTEM
+            .get_or_init(|| Mutex::new(System::new()))
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+
+        let mut usage = HashMap::new();
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        for (pid, process) in system.processes() {
+            // Threads show up as processes on Linux and would be counted twice
+            if process.thread_kind().is_some() {
+                continue;
+            }
+            usage.insert(pid.as_u32(), (process.cpu_usage(), process.memory()));
+            if let Some(parent) = process.parent() {
+                children.entry(parent.as_u32()).or_default().push(pid.as_u32());
+            }
+        }
+        Self { usage, children }
+    }
+
+    /// CPU and memory of `pid` and all its descendants, `None` if it is gone
+    pub fn subtree_usage(&self, pid: u32) -> Option<(f32, u64)> {
+        self.usage.get(&pid)?;
+
+        let mut total = (0.0, 0);
+        let mut seen = HashSet::new();
+        let mut pending = vec![pid];
+        while let Some(current) = pending.pop() {
+            // A reused PID could close a cycle
+            if !seen.insert(current) {
+                continue;
+            }
+            if let Some((cpu, memory)) = self.usage.get(&current) {
+                total.0 += cpu;
+                total.1 += memory;
+            }
+            if let Some(children) = self.children.get(&current) {
+                pending.extend(children);
+            }
+        }
+        Some(total)
+    }
+
+    /// Set `cpu_usage_total` and `memory_total` on every service with a PID
+    pub fn fill(&self, services: &mut [Service]) {
+        for service in services {
+            let total = service.pid.and_then(|pid| self.subtree_usage(pid));
+            service.cpu_usage_total = total.map(|(cpu, _)| cpu);
+            service.memory_total = total.map(|(_, memory)| memory);
+        }
+    }
+}
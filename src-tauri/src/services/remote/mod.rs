@@ -0,0 +1,326 @@
+// Read-only remote viewer: a token-protected status page for paired devices
+
+pub mod server;
+
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::sync::{Mutex, OnceLock};
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use qrcode::render::svg;
+use qrcode::{EcLevel, QrCode};
+use serde::{Deserialize, Serialize};
+use crate::models::audit::{AuditEntry, EventType};
+use crate::models::config::RemoteViewerConfig;
+use crate::services::security::AuditLogger;
+
+/// The code in the QR code has to be redeemed within this time
+const PAIRING_CODE_MINUTES: i64 = 10;
+
+static STATE: OnceLock<Mutex<ViewerState>> = OnceLock::new();
+
+#[derive(Default)]
+struct ViewerState {
+    config: RemoteViewerConfig,
+    server: Option<tauri::async_runtime::JoinHandle<()>>,
+    /// Port the running server is bound to
+    port: Option<u16>,
+    devices: Vec<PairedDevice>,
+}
+
+/// A device is paired in two steps: the QR code carries a one-time pairing
+/// code, which the viewer page exchanges for a session cookie. The session
+/// token never appears in a URL.
+struct PairedDevice {
+    id: String,
+    /// `None` once redeemed
+    pairing_code: Option<String>,
+    /// Set when the pairing code is redeemed
+    session: Option<String>,
+    paired_at: DateTime<Utc>,
+    /// End of the pairing code's validity, then of the session
+    expires_at: DateTime<Utc>,
+    last_seen: Option<DateTime<Utc>>,
+    user_agent: Option<String>,
+}
+
+/// Everything a device needs to connect, shown as a QR code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairingInfo {
+    /// Viewer URL including the one-time pairing code
+    pub url: String,
+    pub device_id: String,
+    pub host: String,
+    pub port: u16,
+    /// QR code of `url` as SVG
+    pub qr_svg: String,
+    pub created_at: DateTime<Utc>,
+    /// The code can't be redeemed after this
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairedDeviceInfo {
+    pub id: String,
+    /// The QR code hasn't been scanned yet
+    pub pending: bool,
+    pub paired_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_seen: Option<DateTime<Utc>>,
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteViewerStatus {
+    pub running: bool,
+    pub port: Option<u16>,
+    /// Devices with an active session
+    pub paired_devices: usize,
+    /// Pairing time of the oldest active session
+    pub paired_since: Option<DateTime<Utc>>,
+    pub devices: Vec<PairedDeviceInfo>,
+}
+
+fn state() -> &'static Mutex<ViewerState> {
+    STATE.get_or_init(|| Mutex::new(ViewerState::default()))
+}
+
+/// Serves services, ports and stats to paired devices over HTTP on the LAN.
+/// There are no endpoints that change anything.
+pub struct RemoteViewer;
+
+impl RemoteViewer {
+    /// Port changes apply the next time the server is started
+    pub fn configure(config: &RemoteViewerConfig) {
+        state().lock().unwrap_or_else(|e| e.into_inner()).config = config.clone();
+    }
+
+    /// Start the server if needed and issue a pairing code for one more device
+    pub async fn start_pairing() -> Result<PairingInfo, Box<dyn std::error::Error + Send + Sync>> {
+        let running_port = {
+            let state = state().lock().unwrap_or_else(|e| e.into_inner());
+            state.server.as_ref().and(state.port)
+        };
+
+        let port = match running_port {
+            Some(port) => port,
+            None => {
+                let configured = state().lock().unwrap_or_else(|e| e.into_inner()).config.port;
+                let listener = tokio::net::TcpListener::bind((Ipv4Addr::UNSPECIFIED, configured))
+                    .await
+                    .map_err(|e| format!("Port {} kann nicht geöffnet werden: {}", configured, e))?;
+                let port = listener.local_addr()?.port();
+                let handle = tauri::async_runtime::spawn(server::serve(listener));
+
+                let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+                state.server = Some(handle);
+                state.port = Some(port);
+                port
+            }
+        };
+
+        let code = random_token();
+        let device_id = uuid::Uuid::new_v4().to_string();
+        let created_at = Utc::now();
+        let expires_at = created_at + chrono::Duration::minutes(PAIRING_CODE_MINUTES);
+        state().lock().unwrap_or_else(|e| e.into_inner()).devices.push(PairedDevice {
+            id: device_id.clone(),
+            pairing_code: Some(code.clone()),
+            session: None,
+            paired_at: created_at,
+            expires_at,
+            last_seen: None,
+            user_agent: None,
+        });
+
+        let host = lan_address().to_string();
+        let url = format!("http://{}:{}/pair?code={}", host, port, code);
+        let qr_svg = QrCode::with_error_correction_level(url.as_bytes(), EcLevel::M)?
+            .render::<svg::Color>()
+            .min_dimensions(200, 200)
+            .build();
+
+        audit("remote_viewer_pair", serde_json::json!({ "port": port, "device_id": device_id }));
+
+        Ok(PairingInfo {
+            url,
+            device_id,
+            host,
+            port,
+            qr_svg,
+            created_at,
+            expires_at,
+        })
+    }
+
+    /// Exchange a pairing code for a session token. Codes work once.
+    pub(crate) fn redeem(code: &str, user_agent: Option<&str>) -> Option<(String, chrono::Duration)> {
+        let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+        let now = Utc::now();
+        state.devices.retain(|d| d.expires_at > now);
+        let lifetime = chrono::Duration::hours(i64::from(state.config.session_hours.max(1)));
+
+        let device = state.devices.iter_mut().find(|d| {
+            d.pairing_code
+                .as_deref()
+                .is_some_and(|pending| constant_time_eq(pending.as_bytes(), code.as_bytes()))
+        })?;
+        let session = random_token();
+        device.pairing_code = None;
+        device.session = Some(session.clone());
+        device.paired_at = now;
+        device.expires_at = now + lifetime;
+        device.last_seen = Some(now);
+        device.user_agent = user_agent.map(|ua| ua.chars().take(200).collect());
+        let device_id = device.id.clone();
+        drop(state);
+
+        audit("remote_viewer_paired", serde_json::json!({ "device_id": device_id, "user_agent": user_agent }));
+        Some((session, lifetime))
+    }
+
+    /// Unpair one device; its session stops working right away
+    pub fn revoke(device_id: &str) -> bool {
+        let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+        let before = state.devices.len();
+        state.devices.retain(|d| d.id != device_id);
+        let revoked = state.devices.len() != before;
+        drop(state);
+
+        if revoked {
+            audit("remote_viewer_revoke", serde_json::json!({ "device_id": device_id }));
+        }
+        revoked
+    }
+
+    /// Stop the server and unpair all devices
+    pub fn stop() {
+        let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(handle) = state.server.take() {
+            handle.abort();
+        }
+        let revoked = state.devices.len();
+        state.devices.clear();
+        state.port = None;
+        drop(state);
+
+        audit("remote_viewer_stop", serde_json::json!({ "revoked_tokens": revoked }));
+    }
+
+    pub fn status() -> RemoteViewerStatus {
+        let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+        let now = Utc::now();
+        state.devices.retain(|d| d.expires_at > now);
+        let paired = state.devices.iter().filter(|d| d.session.is_some());
+        RemoteViewerStatus {
+            running: state.server.is_some(),
+            port: state.port,
+            paired_devices: paired.clone().count(),
+            paired_since: paired.map(|d| d.paired_at).min(),
+            devices: state
+                .devices
+                .iter()
+                .map(|d| PairedDeviceInfo {
+                    id: d.id.clone(),
+                    pending: d.session.is_none(),
+                    paired_at: d.paired_at,
+                    expires_at: d.expires_at,
+                    last_seen: d.last_seen,
+                    user_agent: d.user_agent.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether `session` belongs to a paired device whose session hasn't expired
+    pub(crate) fn is_authorized(session: &str) -> bool {
+        let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+        let now = Utc::now();
+        let device = state.devices.iter_mut().find(|d| {
+            d.expires_at > now
+                && d.session
+                    .as_deref()
+                    .is_some_and(|token| constant_time_eq(token.as_bytes(), session.as_bytes()))
+        });
+        match device {
+            Some(device) => {
+                device.last_seen = Some(now);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn random_token() -> String {
+    let mut bytes = [0u8; 24];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Address other devices on the LAN can reach us at. Connecting a UDP
+/// socket sends nothing but selects the outgoing interface.
+fn lan_address() -> IpAddr {
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(192, 0, 2, 1), 9))?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST))
+}
+
+fn audit(operation: &str, details: serde_json::Value) {
+    let mut entry = AuditEntry::new(EventType::ConfigChange, operation.to_string());
+    entry.details = details;
+    let _ = AuditLogger::new().log(&entry);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_device(id: &str, code: &str) {
+        state().lock().unwrap().devices.push(PairedDevice {
+            id: id.to_string(),
+            pairing_code: Some(code.to_string()),
+            session: None,
+            paired_at: Utc::now(),
+            expires_at: Utc::now() + chrono::Duration::minutes(PAIRING_CODE_MINUTES),
+            last_seen: None,
+            user_agent: None,
+        });
+    }
+
+    #[test]
+    fn pairing_codes_work_once_and_devices_can_be_revoked() {
+        pending_device("device-a", "code-a");
+        assert!(!RemoteViewer::is_authorized("code-a"));
+
+        let (session, lifetime) = RemoteViewer::redeem("code-a", Some("Phone")).unwrap();
+        assert!(lifetime > chrono::Duration::zero());
+        assert!(RemoteViewer::is_authorized(&session));
+        assert!(RemoteViewer::redeem("code-a", None).is_none());
+
+        assert!(RemoteViewer::revoke("device-a"));
+        assert!(!RemoteViewer::is_authorized(&session));
+        assert!(!RemoteViewer::revoke("device-a"));
+    }
+
+    #[test]
+    fn expired_sessions_are_rejected() {
+        pending_device("device-b", "code-b");
+        let (session, _) = RemoteViewer::redeem("code-b", None).unwrap();
+        state()
+            .lock()
+            .unwrap()
+            .devices
+            .iter_mut()
+            .filter(|d| d.id == "device-b")
+            .for_each(|d| d.expires_at = Utc::now() - chrono::Duration::seconds(1));
+        assert!(!RemoteViewer::is_authorized(&session));
+    }
+}
@@ -0,0 +1,243 @@
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Mutex, Semaphore};
+use tokio::time::{timeout, Instant};
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::port::PortResolver;
+use crate::services::system_stats::SystemMonitor;
+use crate::services::ServiceManager;
+use crate::services::user_scope::UserScope;
+use super::RemoteViewer;
+
+/// Requests larger than this are rejected; the viewer only sends GETs
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+/// Connections beyond this are closed right after accept
+const MAX_CONNECTIONS: usize = 16;
+/// A single read may not stall longer than this
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+/// The whole request header has to arrive within this time
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// The whole response, including discovery, has to be written within this time
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+const SESSION_COOKIE: &str = "nm_viewer";
+
+static MONITOR: OnceLock<Mutex<SystemMonitor>> = OnceLock::new();
+
+/// Read-only status page for paired devices
+const VIEWER_PAGE: &str = r#"<!doctype html>
+<html lang="de"><head><meta charset="utf-8"><meta name="viewport" content="width=device-width,initial-scale=1">
+<title>Network Manager</title>
+<style>body{font-family:system-ui,sans-serif;margin:1rem;background:#111;color:#eee}table{width:100%;border-collapse:collapse}
+td,th{padding:.3rem;border-bottom:1px solid #333;text-align:left;font-size:.9rem}.running{color:#4ade80}.stopped{color:#888}
+.error{color:#f87171}#stats{display:flex;gap:1rem;margin-bottom:1rem}</style></head>
+<body><h1>Network Manager</h1><div id="stats"></div><table><thead><tr><th>Service</th><th>Status</th><th>Ports</th></tr></thead>
+<tbody id="services"></tbody></table>
+<script>
+const get = (path) => fetch(path, { credentials: "same-origin" }).then((r) => {
+  if (!r.ok) throw new Error(r.status === 401 ? "Kopplung abgelaufen" : r.statusText);
+  return r.json();
+});
+const esc = (s) => String(s).replace(/[&<>"]/g, (c) => ({ "&": "&amp;", "<": "&lt;", ">": "&gt;", '"': "&quot;" })[c]);
+async function refresh() {
+  try {
+    const [stats, services] = await Promise.all([get("/api/stats"), get("/api/services")]);
+    document.getElementById("stats").innerHTML =
+      `<span>CPU ${stats.cpu.usage_percent.toFixed(0)}%</span><span>RAM ${stats.memory.usage_percent.toFixed(0)}%</span>`;
+    document.getElementById("services").innerHTML = services.services
+      .map((s) => `<tr><td>${esc(s.name)}</td><td class="${esc(s.status)}">${esc(s.status)}</td><td>${s.ports.join(", ")}</td></tr>`)
+      .join("");
+  } catch (e) {
+    document.getElementById("stats").textContent = e.message;
+  }
+}
+refresh();
+setInterval(refresh, 5000);
+</script></body></html>"#;
+
+pub(crate) async fn serve(listener: TcpListener) {
+    let connections = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+        // Dropping the stream closes it; the viewer page retries on its next refresh
+        let Ok(permit) = connections.clone().try_acquire_owned() else {
+            continue;
+        };
+        tokio::spawn(async move {
+            let _ = timeout(RESPONSE_TIMEOUT, handle(stream)).await;
+            drop(permit);
+        });
+    }
+}
+
+async fn handle(mut stream: TcpStream) -> std::io::Result<()> {
+    let mut buffer = vec![0u8; MAX_REQUEST_BYTES];
+    let mut length = 0;
+    let deadline = Instant::now() + REQUEST_TIMEOUT;
+    // Read until the end of the header block; GET requests have no body
+    while !buffer[..length].windows(4).any(|w| w == b"\r\n\r\n") {
+        if length == buffer.len() {
+            return respond(&mut stream, 431, "text/plain", b"Request too large").await;
+        }
+        let limit = READ_TIMEOUT.min(deadline.saturating_duration_since(Instant::now()));
+        let Ok(read) = timeout(limit, stream.read(&mut buffer[length..])).await else {
+            return respond(&mut stream, 408, "text/plain", b"Request timeout").await;
+        };
+        let read = read?;
+        if read == 0 {
+            return Ok(());
+        }
+        length += read;
+    }
+
+    let request = String::from_utf8_lossy(&buffer[..length]);
+    let mut lines = request.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let target = request_line.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let headers: Vec<(&str, &str)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .collect();
+
+    if method != "GET" {
+        return respond(&mut stream, 405, "text/plain", b"Read-only").await;
+    }
+    if path == "/" {
+        return respond(&mut stream, 200, "text/html; charset=utf-8", VIEWER_PAGE.as_bytes()).await;
+    }
+    if path == "/pair" {
+        return pair(&mut stream, query, &headers).await;
+    }
+
+    if !session_token(&headers).is_some_and(|t| RemoteViewer::is_authorized(&t)) {
+        return respond(&mut stream, 401, "text/plain", b"Unauthorized").await;
+    }
+
+    let body = match path {
+        "/api/status" => to_json(&RemoteViewer::status()),
+        // Remote viewers never get other users' items, even in "all users" mode
+        "/api/services" => {
+            let mut discovery = ServiceManager::new().discover().await;
+            discovery.services.retain(|s| UserScope::is_own(s.user.as_deref()));
+            to_json(&discovery)
+        }
+        "/api/ports" => {
+            let mut ports = if MockDiscovery::is_enabled() {
+                MockDiscovery::new().port_usage()
+            } else {
+                PortResolver::new().get_port_usage()
+            };
+            ports.retain(|p| UserScope::is_own(p.user.as_deref()));
+            to_json(&ports)
+        }
+        "/api/stats" => {
+            if MockDiscovery::is_enabled() {
+                let tick = chrono::Utc::now().timestamp() as u64;
+                to_json(&MockDiscovery::new().system_stats(tick))
+            } else {
+                let monitor = MONITOR.get_or_init(|| Mutex::new(SystemMonitor::new()));
+                to_json(&monitor.lock().await.get_stats())
+            }
+        }
+        _ => return respond(&mut stream, 404, "text/plain", b"Not found").await,
+    };
+
+    respond(&mut stream, 200, "application/json", body.as_bytes()).await
+}
+
+/// Redeem the one-time code from the QR code and hand the session out as a cookie
+async fn pair(stream: &mut TcpStream, query: &str, headers: &[(&str, &str)]) -> std::io::Result<()> {
+    let code = query.split('&').find_map(|pair| pair.strip_prefix("code="));
+    let user_agent = header(headers, "user-agent");
+    let Some((session, lifetime)) = code.and_then(|code| RemoteViewer::redeem(code, user_agent)) else {
+        let body = "Kopplungscode ungültig oder abgelaufen".as_bytes();
+        return respond(stream, 401, "text/plain; charset=utf-8", body).await;
+    };
+    let extra = format!(
+        "Location: /\r\nSet-Cookie: {}={}; Path=/; HttpOnly; SameSite=Strict; Max-Age={}\r\n",
+        SESSION_COOKIE,
+        session,
+        lifetime.num_seconds()
+    );
+    respond_with(stream, 303, "text/plain", &extra, b"").await
+}
+
+/// Session from the cookie set by `/pair`, or from a Bearer header for scripted clients
+fn session_token(headers: &[(&str, &str)]) -> Option<String> {
+    let from_cookie = headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("cookie"))
+        .flat_map(|(_, value)| value.split(';'))
+        .find_map(|pair| pair.trim().strip_prefix(SESSION_COOKIE)?.strip_prefix('='));
+    from_cookie
+        .or_else(|| header(headers, "authorization")?.strip_prefix("Bearer "))
+        .map(|t| t.trim().to_string())
+}
+
+fn header<'a>(headers: &[(&'a str, &'a str)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header.eq_ignore_ascii_case(name))
+        .map(|(_, value)| *value)
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "null".to_string())
+}
+
+async fn respond(stream: &mut TcpStream, status: u16, content_type: &str, body: &[u8]) -> std::io::Result<()> {
+    respond_with(stream, status, content_type, "", body).await
+}
+
+/// `extra_headers` are complete header lines, each ending in CRLF
+async fn respond_with(
+    stream: &mut TcpStream,
+    status: u16,
+    content_type: &str,
+    extra_headers: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        303 => "See Other",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        _ => "Request Header Fields Too Large",
+    };
+    let header = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n{}\r\n",
+        status,
+        reason,
+        content_type,
+        body.len(),
+        extra_headers
+    );
+    stream.write_all(header.as_bytes()).await?;
+    stream.write_all(body).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_comes_from_cookie_or_bearer_header() {
+        let headers = [("Host", "x"), ("Cookie", "theme=dark; nm_viewer=abc ; other=1")];
+        assert_eq!(session_token(&headers).as_deref(), Some("abc"));
+
+        let headers = [("authorization", "Bearer def")];
+        assert_eq!(session_token(&headers).as_deref(), Some("def"));
+
+        let headers = [("Cookie", "nm_viewer_old=abc")];
+        assert_eq!(session_token(&headers), None);
+    }
+}
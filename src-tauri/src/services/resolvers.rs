@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::port::{PortInfo, Protocol};
+use crate::services::cmd;
+
+const DNS_PORT: u16 = 53;
+const MDNS_PORT: u16 = 5353;
+const LLMNR_PORT: u16 = 5355;
+
+/// Known local resolvers: (process name, display name, systemd unit).
+/// Linux truncates process names to 15 characters.
+const KNOWN_RESOLVERS: &[(&str, &str, Option<&str>)] = &[
+    ("systemd-resolve", "systemd-resolved", Some("systemd-resolved.service")),
+    ("mDNSResponder", "mDNSResponder", None),
+    ("dnsmasq", "dnsmasq", Some("dnsmasq.service")),
+    ("avahi-daemon", "Avahi", Some("avahi-daemon.service")),
+    ("named", "BIND", Some("named.service")),
+    ("unbound", "Unbound", Some("unbound.service")),
+    ("pihole-FTL", "Pi-hole FTL", Some("pihole-FTL.service")),
+    ("dnscrypt-proxy", "dnscrypt-proxy", Some("dnscrypt-proxy.service")),
+    ("coredns", "CoreDNS", None),
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResolverRole {
+    Dns,
+    Mdns,
+    Llmnr,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolverPort {
+    pub port: u16,
+    pub protocol: Protocol,
+    pub address: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalResolver {
+    pub name: String,
+    pub process_name: String,
+    pub pid: Option<u32>,
+    pub unit: Option<String>,
+    /// systemd `ActiveState` (`active`, `failed`, `activating`, ...)
+    pub unit_state: Option<String>,
+    pub running: bool,
+    /// Derived from the ports the resolver holds
+    pub roles: Vec<ResolverRole>,
+    pub ports: Vec<ResolverPort>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolverConflictKind {
+    /// Two processes bound to overlapping addresses on the same port
+    PortClash,
+    /// A resolver failed to start while another one holds its port
+    BlockedStart,
+    /// Several mDNS responders answer queries for this host
+    DuplicateMdns,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolverConflict {
+    /// Stable id, e.g. `blocked-53-dnsmasq`
+    pub id: String,
+    pub kind: ResolverConflictKind,
+    pub port: u16,
+    /// Display names of the involved resolvers or processes
+    pub resolvers: Vec<String>,
+    pub title: String,
+    pub description: String,
+    /// Guided fix, one step per entry
+    pub fix_steps: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolverStatus {
+    pub resolvers: Vec<LocalResolver>,
+    pub conflicts: Vec<ResolverConflict>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Detects systemd-resolved, mDNSResponder, dnsmasq and similar resolvers from
+/// the socket table and reports port conflicts between them
+pub struct ResolverInspector {
+    /// Query systemd for units that are installed but not running
+    query_units: bool,
+}
+
+impl ResolverInspector {
+    pub fn new() -> Self {
+        Self {
+            query_units: cfg!(target_os = "linux"),
+        }
+    }
+
+    pub fn with_unit_states(mut self, enabled: bool) -> Self {
+        self.query_units = enabled && cfg!(target_os = "linux");
+        self
+    }
+
+    pub fn inspect(&self, ports: &[PortInfo]) -> ResolverStatus {
+        let unit_states = if self.query_units { unit_states() } else { HashMap::new() };
+        let resolvers = collect_resolvers(ports, &unit_states);
+
+        let mut conflicts = port_clashes(ports, DNS_PORT);
+        conflicts.extend(blocked_starts(&resolvers, ports));
+        conflicts.extend(duplicate_mdns(&resolvers));
+
+        ResolverStatus {
+            resolvers,
+            conflicts,
+            checked_at: Utc::now(),
+        }
+    }
+}
+
+impl Default for ResolverInspector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn known_resolver(process_name: &str) -> Option<&'static (&'static str, &'static str, Option<&'static str>)> {
+    KNOWN_RESOLVERS
+        .iter()
+        .find(|(process, _, _)| process.eq_ignore_ascii_case(process_name))
+}
+
+/// Display name for a port holder, falling back to the process name
+fn holder_name(port: &PortInfo) -> String {
+    let process = port.process_name.as_deref().unwrap_or("Unbekannter Prozess");
+    known_resolver(process)
+        .map(|(_, name, _)| name.to_string())
+        .unwrap_or_else(|| process.to_string())
+}
+
+fn collect_resolvers(ports: &[PortInfo], unit_states: &HashMap<String, String>) -> Vec<LocalResolver> {
+    let mut resolvers: Vec<LocalResolver> = Vec::new();
+
+    for port in ports {
+        let Some(&(process, name, unit)) = port.process_name.as_deref().and_then(known_resolver) else {
+            continue;
+        };
+        let resolver = match resolvers.iter_mut().position(|r| r.process_name == process && r.pid == port.pid) {
+            Some(index) => &mut resolvers[index],
+            None => {
+                resolvers.push(LocalResolver {
+                    name: name.to_string(),
+                    process_name: process.to_string(),
+                    pid: port.pid,
+                    unit: unit.map(String::from),
+                    unit_state: unit.and_then(|u| unit_states.get(u).cloned()),
+                    running: true,
+                    roles: Vec::new(),
+                    ports: Vec::new(),
+                });
+                resolvers.last_mut().unwrap()
+            }
+        };
+
+        let role = match port.port {
+            DNS_PORT => Some(ResolverRole::Dns),
+            MDNS_PORT => Some(ResolverRole::Mdns),
+            LLMNR_PORT => Some(ResolverRole::Llmnr),
+            _ => None,
+        };
+        if let Some(role) = role.filter(|r| !resolver.roles.contains(r)) {
+            resolver.roles.push(role);
+        }
+        resolver.ports.push(ResolverPort {
+            port: port.port,
+            protocol: port.protocol.clone(),
+            address: port.bind_address.clone(),
+        });
+    }
+
+    // Installed resolvers that aren't running, e.g. dnsmasq after a failed start
+    for &(process, name, unit) in KNOWN_RESOLVERS {
+        let Some(unit) = unit else { continue };
+        let Some(state) = unit_states.get(unit) else { continue };
+        if resolvers.iter().any(|r| r.process_name == process) || state == "inactive" {
+            continue;
+        }
+        resolvers.push(LocalResolver {
+            name: name.to_string(),
+            process_name: process.to_string(),
+            pid: None,
+            unit: Some(unit.to_string()),
+            unit_state: Some(state.clone()),
+            running: false,
+            roles: Vec::new(),
+            ports: Vec::new(),
+        });
+    }
+
+    resolvers
+}
+
+/// `ActiveState` of all known resolver units that are installed
+fn unit_states() -> HashMap<String, String> {
+    let units: Vec<&str> = KNOWN_RESOLVERS.iter().filter_map(|(_, _, unit)| *unit).collect();
+    let output = cmd::command("systemctl")
+        .args(["show", "--property=Id,LoadState,ActiveState"])
+        .args(&units)
+        .output();
+    let Ok(output) = output else {
+        return HashMap::new();
+    };
+
+    // One `key=value` block per unit, separated by blank lines
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut states = HashMap::new();
+    for block in stdout.split("\n\n") {
+        let fields: HashMap<&str, &str> = block.lines().filter_map(|line| line.split_once('=')).collect();
+        if let (Some(id), Some(state), Some("loaded")) =
+            (fields.get("Id"), fields.get("ActiveState"), fields.get("LoadState").copied())
+        {
+            states.insert(id.to_string(), state.to_string());
+        }
+    }
+    states
+}
+
+/// Wildcard bindings overlap every address of the same family
+fn addresses_overlap(a: &PortInfo, b: &PortInfo) -> bool {
+    a.family == b.family && (a.bind_address == b.bind_address || a.is_wildcard() || b.is_wildcard())
+}
+
+fn port_clashes(ports: &[PortInfo], port: u16) -> Vec<ResolverConflict> {
+    let holders: Vec<&PortInfo> = ports.iter().filter(|p| p.port == port).collect();
+    let mut conflicts: Vec<ResolverConflict> = Vec::new();
+
+    for (index, a) in holders.iter().enumerate() {
+        for b in &holders[index + 1..] {
+            if a.protocol != b.protocol || a.pid == b.pid || a.process_name == b.process_name || !addresses_overlap(a, b) {
+                continue;
+            }
+            let (first, second) = (holder_name(a), holder_name(b));
+            let id = format!("clash-{}-{}-{}", port, first, second);
+            if conflicts.iter().any(|c| c.id == id) {
+                continue;
+            }
+            conflicts.push(ResolverConflict {
+                id,
+                kind: ResolverConflictKind::PortClash,
+                port,
+                resolvers: vec![first.clone(), second.clone()],
+                title: format!("{} und {} teilen sich Port {}", first, second, port),
+                description: format!(
+                    "{} lauscht auf {} und {} auf {}. Welcher Dienst eine Anfrage beantwortet, hängt von der Zieladresse ab; DNS-Antworten können dadurch inkonsistent sein.",
+                    first, a.bind_address, second, b.bind_address
+                ),
+                fix_steps: fix_steps(&first, &second),
+            });
+        }
+    }
+    conflicts
+}
+
+/// The classic clash: systemd-resolved's stub listener holds port 53 and
+/// dnsmasq or another resolver fails with "Address already in use"
+fn blocked_starts(resolvers: &[LocalResolver], ports: &[PortInfo]) -> Vec<ResolverConflict> {
+    let holders: Vec<String> = ports
+        .iter()
+        .filter(|p| p.port == DNS_PORT)
+        .map(holder_name)
+        .fold(Vec::new(), |mut names, name| {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+            names
+        });
+    let Some(holder) = holders.first() else {
+        return Vec::new();
+    };
+
+    resolvers
+        .iter()
+        .filter(|r| !r.running && matches!(r.unit_state.as_deref(), Some("failed" | "activating")))
+        // Resolvers that only do mDNS don't need port 53
+        .filter(|r| r.process_name != "avahi-daemon")
+        .map(|blocked| ResolverConflict {
+            id: format!("blocked-{}-{}", DNS_PORT, blocked.process_name),
+            kind: ResolverConflictKind::BlockedStart,
+            port: DNS_PORT,
+            resolvers: vec![holder.clone(), blocked.name.clone()],
+            title: format!("{} kann nicht starten, Port {} ist belegt", blocked.name, DNS_PORT),
+            description: format!(
+                "{} ist im Zustand \"{}\", während {} Port {} hält. Das ist der typische Port-53-Konflikt zwischen lokalen Resolvern.",
+                blocked.name,
+                blocked.unit_state.as_deref().unwrap_or_default(),
+                holder,
+                DNS_PORT
+            ),
+            fix_steps: fix_steps(holder, &blocked.name),
+        })
+        .collect()
+}
+
+fn duplicate_mdns(resolvers: &[LocalResolver]) -> Vec<ResolverConflict> {
+    let responders: Vec<&LocalResolver> = resolvers
+        .iter()
+        .filter(|r| r.running && r.roles.contains(&ResolverRole::Mdns))
+        .collect();
+    if responders.len() < 2 {
+        return Vec::new();
+    }
+
+    let names: Vec<String> = responders.iter().map(|r| r.name.clone()).collect();
+    let mut fix_steps = Vec::new();
+    if names.iter().any(|n| n == "systemd-resolved") {
+        fix_steps.push("In /etc/systemd/resolved.conf \"MulticastDNS=no\" setzen".to_string());
+        fix_steps.push("sudo systemctl restart systemd-resolved".to_string());
+    } else if names.iter().any(|n| n == "mDNSResponder") {
+        fix_steps.push(
+            "mDNSResponder ist Teil von macOS; den zusätzlichen Responder beenden und dessen Autostart deaktivieren".to_string(),
+        );
+    } else {
+        fix_steps.push(format!("Nur einen mDNS-Responder behalten und {} deaktivieren", names[1]));
+    }
+
+    vec![ResolverConflict {
+        id: format!("mdns-{}", names.join("-")),
+        kind: ResolverConflictKind::DuplicateMdns,
+        port: MDNS_PORT,
+        title: format!("Mehrere mDNS-Responder aktiv ({})", names.join(", ")),
+        description: "Mehrere Dienste beantworten mDNS-Anfragen für diesen Rechner. Das führt zu doppelten oder widersprüchlichen .local-Namen und Service-Einträgen.".to_string(),
+        resolvers: names,
+        fix_steps,
+    }]
+}
+
+/// Step-by-step fix for two resolvers competing for port 53
+fn fix_steps(holder: &str, other: &str) -> Vec<String> {
+    let unit = |name: &str| {
+        KNOWN_RESOLVERS
+            .iter()
+            .find(|(_, display, _)| *display == name)
+            .and_then(|(_, _, unit)| *unit)
+            .map(|u| u.trim_end_matches(".service").to_string())
+            .unwrap_or_else(|| name.to_string())
+    };
+
+    if holder == "systemd-resolved" || other == "systemd-resolved" {
+        let replacement = if holder == "systemd-resolved" { other } else { holder };
+        return vec![
+            "In /etc/systemd/resolved.conf \"DNSStubListener=no\" setzen".to_string(),
+            "sudo systemctl restart systemd-resolved".to_string(),
+            "sudo ln -sf /run/systemd/resolve/resolv.conf /etc/resolv.conf".to_string(),
+            format!("sudo systemctl restart {}", unit(replacement)),
+        ];
+    }
+    if holder == "dnsmasq" || other == "dnsmasq" {
+        return vec![
+            "In /etc/dnsmasq.conf \"bind-interfaces\" und eine feste \"listen-address=\" setzen".to_string(),
+            "sudo systemctl restart dnsmasq".to_string(),
+        ];
+    }
+    vec![
+        format!("Entscheiden, ob {} oder {} der lokale Resolver sein soll", holder, other),
+        format!("Den anderen Dienst stoppen und dessen Autostart deaktivieren, oder ihn an eine andere Adresse als {} binden", holder),
+    ]
+}
@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use chrono::{DateTime, Utc};
+use crate::models::config::AcknowledgedIssue;
+use crate::services::storage::Storage;
+
+static ACKNOWLEDGEMENTS: OnceLock<Mutex<Vec<AcknowledgedIssue>>> = OnceLock::new();
+
+/// Security findings the user accepted. Stored separately on disk, like the
+/// port reservations; `Config.acknowledged_issues` is a read-only copy.
+/// Acknowledged findings are moved out of the scan's issues and severity counts.
+pub struct SecurityAcknowledgements;
+
+impl SecurityAcknowledgements {
+    pub fn list() -> Vec<AcknowledgedIssue> {
+        registry().lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Acknowledge a finding or update the reason and expiry
+    pub fn acknowledge(
+        issue_id: &str,
+        reason: &str,
+        expires: Option<DateTime<Utc>>,
+    ) -> Result<AcknowledgedIssue, Box<dyn std::error::Error + Send + Sync>> {
+        let issue_id = issue_id.trim();
+        if issue_id.is_empty() {
+            return Err("Keine Befund-ID angegeben".into());
+        }
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err("Bitte eine Begründung angeben".into());
+        }
+        if expires.is_some_and(|expires| expires <= Utc::now()) {
+            return Err("Das Ablaufdatum muss in der Zukunft liegen".into());
+        }
+
+        let acknowledgement = AcknowledgedIssue {
+            issue_id: issue_id.to_string(),
+            reason: reason.to_string(),
+            acknowledged_at: Utc::now(),
+            expires,
+        };
+        let mut current = registry().lock().unwrap_or_else(|e| e.into_inner());
+        current.retain(|a| a.issue_id != issue_id);
+        current.push(acknowledgement.clone());
+        save(&current);
+        Ok(acknowledgement)
+    }
+
+    /// Returns false if the finding wasn't acknowledged
+    pub fn revoke(issue_id: &str) -> bool {
+        let mut current = registry().lock().unwrap_or_else(|e| e.into_inner());
+        let before = current.len();
+        current.retain(|a| a.issue_id != issue_id);
+        let removed = current.len() != before;
+        if removed {
+            save(&current);
+        }
+        removed
+    }
+
+    /// The unexpired acknowledgement for a finding
+    pub fn active(issue_id: &str) -> Option<AcknowledgedIssue> {
+        let now = Utc::now();
+        registry()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|a| a.issue_id == issue_id && a.is_active(now))
+            .cloned()
+    }
+}
+
+fn registry() -> &'static Mutex<Vec<AcknowledgedIssue>> {
+    ACKNOWLEDGEMENTS.get_or_init(|| Mutex::new(load()))
+}
+
+fn path() -> PathBuf {
+    Storage::config_dir().join("acknowledged_issues.json")
+}
+
+fn load() -> Vec<AcknowledgedIssue> {
+    std::fs::read(path())
+        .ok()
+        .and_then(|data| serde_json::from_slice(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save(acknowledgements: &[AcknowledgedIssue]) {
+    let path = path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+    if let Ok(data) = serde_json::to_vec_pretty(acknowledgements) {
+        std::fs::write(&path, data).ok();
+    }
+}
@@ -2,7 +2,8 @@ use std::fs::{File, OpenOptions};
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 use chrono::Utc;
-use crate::models::audit::{AuditEntry, EventType};
+use crate::models::audit::{AuditEntry, AuditQuery, EventType};
+use crate::services::storage::Storage;
 
 pub struct AuditLogger {
     log_path: PathBuf,
@@ -10,10 +11,7 @@ pub struct AuditLogger {
 
 impl AuditLogger {
     pub fn new() -> Self {
-        let log_dir = dirs::data_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("network_manager")
-            .join("logs");
+        let log_dir = Storage::log_dir();
 
         std::fs::create_dir_all(&log_dir).ok();
 
@@ -74,6 +72,22 @@ impl AuditLogger {
         Ok(entries)
     }
 
+    /// Entries matching `query`, newest first
+    pub fn query(&self, query: &AuditQuery, limit: usize) -> Result<Vec<AuditEntry>, Box<dyn std::error::Error>> {
+        let content = match std::fs::read_to_string(&self.log_path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(content
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+            .filter(|entry| query.matches(entry))
+            .take(limit)
+            .collect())
+    }
+
     /// Get the log file path
     pub fn log_path(&self) -> &PathBuf {
         &self.log_path
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use crate::models::config::SecurityConfig;
+
+/// How long a confirmation token stays valid
+const TOKEN_TTL_SECS: u64 = 60;
+
+// Runtime toggles, driven by `SecurityConfig`
+static REQUIRE_CONFIRMATION: AtomicBool = AtomicBool::new(true);
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+static PENDING: OnceLock<Mutex<HashMap<String, PendingAction>>> = OnceLock::new();
+
+/// What a destructive command would do if executed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpactPreview {
+    /// Command name, e.g. `kill_process`
+    pub action: String,
+    /// PID or service ID the action targets
+    pub target: String,
+    pub target_name: Option<String>,
+    /// Ports that will be released
+    pub ports_freed: Vec<u16>,
+    /// Child processes or dependent services that will be affected
+    pub dependents_affected: Vec<String>,
+}
+
+/// Result of a destructive command under the two-phase protocol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ActionOutcome {
+    Executed,
+    /// Dry-run mode is enabled, nothing was changed
+    DryRun { preview: ImpactPreview },
+    /// Simulation mode is enabled; the target is part of the fake fleet and
+    /// nothing on the host was changed
+    Simulated,
+    /// Echo `token` back to the same command to execute it
+    ConfirmationRequired {
+        token: String,
+        preview: ImpactPreview,
+        expires_in_secs: u64,
+    },
+}
+
+struct PendingAction {
+    action: String,
+    target: String,
+    expires_at: Instant,
+}
+
+/// Issues and validates single-use confirmation tokens for destructive commands
+pub struct ConfirmationGuard;
+
+impl ConfirmationGuard {
+    /// Apply confirmation and dry-run settings from the config
+    pub fn configure(config: &SecurityConfig) {
+        REQUIRE_CONFIRMATION.store(config.require_confirmation_for_kill, Ordering::Relaxed);
+        DRY_RUN.store(config.dry_run, Ordering::Relaxed);
+    }
+
+    pub fn is_dry_run() -> bool {
+        DRY_RUN.load(Ordering::Relaxed)
+    }
+
+    pub fn requires_confirmation() -> bool {
+        REQUIRE_CONFIRMATION.load(Ordering::Relaxed)
+    }
+
+    /// Decide whether a destructive action may run now.
+    ///
+    /// Returns `Ok(None)` if the caller should execute the action, or the
+    /// outcome to return instead (dry-run preview or confirmation request).
+    /// `preview` is only evaluated when it is needed.
+    pub fn check<F>(
+        action: &str,
+        target: &str,
+        token: Option<&str>,
+        preview: F,
+    ) -> Result<Option<ActionOutcome>, Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: FnOnce() -> ImpactPreview,
+    {
+        if Self::is_dry_run() {
+            return Ok(Some(ActionOutcome::DryRun { preview: preview() }));
+        }
+        if !Self::requires_confirmation() {
+            return Ok(None);
+        }
+
+        match token {
+            Some(token) => {
+                Self::consume(action, target, token)?;
+                Ok(None)
+            }
+            None => Ok(Some(Self::request(action, target, preview()))),
+        }
+    }
+
+    fn request(action: &str, target: &str, preview: ImpactPreview) -> ActionOutcome {
+        let token = uuid::Uuid::new_v4().to_string();
+        let mut pending = pending().lock().unwrap_or_else(|e| e.into_inner());
+
+        let now = Instant::now();
+        pending.retain(|_, p| p.expires_at > now);
+        pending.insert(
+            token.clone(),
+            PendingAction {
+                action: action.to_string(),
+                target: target.to_string(),
+                expires_at: now + Duration::from_secs(TOKEN_TTL_SECS),
+            },
+        );
+
+        ActionOutcome::ConfirmationRequired {
+            token,
+            preview,
+            expires_in_secs: TOKEN_TTL_SECS,
+        }
+    }
+
+    /// Tokens are single-use and bound to the action and target they were issued for
+    fn consume(action: &str, target: &str, token: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut pending = pending().lock().unwrap_or_else(|e| e.into_inner());
+
+        let Some(entry) = pending.remove(token) else {
+            return Err("Ungültiges oder bereits verwendetes Bestätigungstoken".into());
+        };
+        if entry.expires_at <= Instant::now() {
+            return Err("Die Bestätigung ist abgelaufen. Bitte erneut bestätigen.".into());
+        }
+        if entry.action != action || entry.target != target {
+            return Err("Das Bestätigungstoken gehört zu einer anderen Aktion".into());
+        }
+        Ok(())
+    }
+}
+
+fn pending() -> &'static Mutex<HashMap<String, PendingAction>> {
+    PENDING.get_or_init(|| Mutex::new(HashMap::new()))
+}
@@ -4,6 +4,7 @@ use chacha20poly1305::{
 };
 use argon2::{Argon2, password_hash::SaltString};
 use std::path::PathBuf;
+use crate::services::storage::Storage;
 
 const NONCE_SIZE: usize = 12;
 const SALT_SIZE: usize = 32;
@@ -21,9 +22,7 @@ struct EncryptedConfig {
 
 impl ConfigEncryption {
     pub fn new() -> Self {
-        let config_dir = dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("network_manager");
+        let config_dir = Storage::config_dir();
 
         std::fs::create_dir_all(&config_dir).ok();
 
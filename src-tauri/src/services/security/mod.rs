@@ -2,6 +2,9 @@
 
 pub mod audit;
 pub mod encryption;
+pub mod confirmation;
+pub mod acknowledgements;
 
 pub use audit::AuditLogger;
 pub use encryption::ConfigEncryption;
+pub use acknowledgements::SecurityAcknowledgements;
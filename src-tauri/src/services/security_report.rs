@@ -0,0 +1,520 @@
+use std::path::Path;
+use chrono::{DateTime, Local, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use crate::services::export::{escape_html, serde_name, write_export, ExportResult};
+use crate::services::history::{HistoryStore, SecurityScanSummary};
+use crate::services::pdf::{PdfDocument, TextStyle};
+use crate::services::security_scanner::{SecurityIssue, SecurityScanResult, SecuritySeverity};
+
+/// Stored scans shown in the trend
+const TREND_SCANS: u32 = 30;
+
+const SEVERITIES: &[SecuritySeverity] = &[
+    SecuritySeverity::Critical,
+    SecuritySeverity::High,
+    SecuritySeverity::Medium,
+    SecuritySeverity::Low,
+    SecuritySeverity::Info,
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Html,
+    Pdf,
+    /// SARIF 2.1.0 for code scanning dashboards and other tools
+    Sarif,
+}
+
+impl ReportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Html => "html",
+            ReportFormat::Pdf => "pdf",
+            ReportFormat::Sarif => "sarif",
+        }
+    }
+}
+
+/// The latest stored security scan with the trend of the scans before it
+pub struct SecurityReport {
+    pub generated_at: DateTime<Utc>,
+    pub scan_id: i64,
+    pub result: SecurityScanResult,
+    /// Oldest first, ending with the reported scan
+    pub trend: Vec<SecurityScanSummary>,
+    /// Findings of the latest scan that the previous one didn't have
+    pub new_issues: Vec<String>,
+    /// Findings of the previous scan that are gone
+    pub fixed_issues: usize,
+}
+
+impl SecurityReport {
+    /// Load the latest scan from the history database
+    pub async fn collect() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let (scan_id, result) = HistoryStore::security_scan(None)
+            .await?
+            .ok_or("Es ist noch kein Sicherheitsscan gespeichert; starte zuerst einen Scan")?;
+        let mut trend = HistoryStore::security_scans(TREND_SCANS).await?;
+        trend.reverse();
+
+        let previous = trend.iter().rev().find(|s| s.id != scan_id).map(|s| s.id);
+        let (new_issues, fixed_issues) = match previous {
+            Some(id) => match HistoryStore::security_scan(Some(id)).await? {
+                Some((_, before)) => {
+                    let diff = before.diff(&result);
+                    (diff.new_issues.into_iter().map(|i| i.id).collect(), diff.fixed_issues.len())
+                }
+                None => (Vec::new(), 0),
+            },
+            None => (Vec::new(), 0),
+        };
+
+        Ok(Self {
+            generated_at: Utc::now(),
+            scan_id,
+            result,
+            trend,
+            new_issues,
+            fixed_issues,
+        })
+    }
+
+    /// Render and write to `path`, or to the exports directory
+    pub fn write(&self, format: ReportFormat, path: Option<&Path>) -> Result<ExportResult, Box<dyn std::error::Error + Send + Sync>> {
+        let content = self.render(format)?;
+        let path = write_export("security-report", self.generated_at, format.extension(), &content, path)?;
+        Ok(ExportResult {
+            path: path.to_string_lossy().to_string(),
+            bytes: content.len(),
+            template: None,
+        })
+    }
+
+    pub fn render(&self, format: ReportFormat) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(match format {
+            ReportFormat::Html => self.html().into_bytes(),
+            ReportFormat::Pdf => self.pdf(),
+            ReportFormat::Sarif => serde_json::to_vec_pretty(&self.sarif())?,
+        })
+    }
+
+    fn scanned_at(&self) -> DateTime<Local> {
+        Local
+            .timestamp_opt(self.result.scan_timestamp as i64, 0)
+            .single()
+            .unwrap_or_else(|| self.generated_at.with_timezone(&Local))
+    }
+
+    fn issues_by_severity(&self, severity: &SecuritySeverity) -> Vec<&SecurityIssue> {
+        self.result.issues.iter().filter(|i| &i.severity == severity).collect()
+    }
+
+    fn html(&self) -> String {
+        let r = &self.result;
+        let mut out = String::from(
+            "<!DOCTYPE html>\n<html lang=\"de\"><head><meta charset=\"utf-8\"><title>Sicherheitsbericht</title>\n<style>\n\
+body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;color:#1f2328;max-width:960px;margin:2rem auto;padding:0 1rem}\n\
+h1{margin-bottom:.2rem}h2{margin-top:2rem;border-bottom:1px solid #d0d7de;padding-bottom:.3rem}\n\
+.muted{color:#656d76}.cards{display:flex;gap:.75rem;flex-wrap:wrap;margin:1rem 0}\n\
+.card{border:1px solid #d0d7de;border-radius:8px;padding:.75rem 1rem;min-width:110px}.card b{display:block;font-size:1.6rem}\n\
+.issue{border:1px solid #d0d7de;border-left-width:5px;border-radius:6px;padding:.6rem .9rem;margin:.6rem 0}\n\
+.issue h3{margin:0 0 .3rem;font-size:1rem}.issue p{margin:.25rem 0}\n\
+.critical{border-left-color:#b91c1c}.high{border-left-color:#ea580c}.medium{border-left-color:#ca8a04}.low{border-left-color:#2563eb}.info{border-left-color:#6b7280}\n\
+.badge{display:inline-block;font-size:.75rem;border-radius:4px;padding:0 .4rem;margin-right:.3rem;background:#eaeef2}\n\
+.new{background:#fde68a}table{border-collapse:collapse;width:100%}th,td{border:1px solid #d0d7de;padding:4px 8px;text-align:left}\n\
+@media print{.issue{break-inside:avoid}}\n</style>\n</head><body>\n",
+        );
+        out.push_str("<h1>Sicherheitsbericht</h1>\n");
+        out.push_str(&format!(
+            "<p class=\"muted\">Scan vom {}, erstellt am {}</p>\n",
+            self.scanned_at().format("%d.%m.%Y %H:%M"),
+            self.generated_at.with_timezone(&Local).format("%d.%m.%Y %H:%M")
+        ));
+
+        out.push_str("<div class=\"cards\">\n");
+        for (label, value) in [
+            ("Kritisch", r.critical_count),
+            ("Hoch", r.high_count),
+            ("Mittel", r.medium_count),
+            ("Niedrig", r.low_count),
+            ("Akzeptiert", r.acknowledged.len()),
+            ("Geprüfte Dienste", r.services_scanned),
+        ] {
+            out.push_str(&format!("<div class=\"card\"><b>{}</b>{}</div>\n", value, label));
+        }
+        out.push_str("</div>\n");
+        if self.trend.len() > 1 {
+            out.push_str(&format!(
+                "<p>Seit dem vorherigen Scan: {} neu, {} behoben.</p>\n",
+                self.new_issues.len(),
+                self.fixed_issues
+            ));
+        }
+
+        out.push_str("<h2>Verlauf</h2>\n");
+        out.push_str(&self.trend_svg());
+        out.push_str("<table>\n<tr><th>Scan</th><th>Kritisch</th><th>Hoch</th><th>Mittel</th><th>Niedrig</th><th>Gesamt</th></tr>\n");
+        for scan in self.trend.iter().rev() {
+            out.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                scan.taken_at.with_timezone(&Local).format("%d.%m.%Y %H:%M"),
+                scan.critical_count,
+                scan.high_count,
+                scan.medium_count,
+                scan.low_count,
+                scan.issue_count
+            ));
+        }
+        out.push_str("</table>\n");
+
+        out.push_str("<h2>Befunde</h2>\n");
+        if r.issues.is_empty() {
+            out.push_str("<p><em>Keine offenen Befunde</em></p>\n");
+        }
+        for severity in SEVERITIES {
+            for issue in self.issues_by_severity(severity) {
+                out.push_str(&self.issue_html(issue, None));
+            }
+        }
+
+        if !r.acknowledged.is_empty() {
+            out.push_str("<h2>Akzeptierte Risiken</h2>\n");
+            for acknowledged in &r.acknowledged {
+                out.push_str(&self.issue_html(&acknowledged.issue, Some(&acknowledged.acknowledgement.reason)));
+            }
+        }
+
+        if !r.warnings.is_empty() {
+            out.push_str("<h2>Hinweise zum Scan</h2>\n<ul>\n");
+            for warning in &r.warnings {
+                out.push_str(&format!("<li>{}</li>\n", escape_html(&warning.message)));
+            }
+            out.push_str("</ul>\n");
+        }
+        out.push_str("</body></html>\n");
+        out
+    }
+
+    fn issue_html(&self, issue: &SecurityIssue, reason: Option<&str>) -> String {
+        let severity = serde_name(&issue.severity);
+        let mut badges = format!("<span class=\"badge\">{}</span>", escape_html(&severity_label(&issue.severity)));
+        badges.push_str(&format!("<span class=\"badge\">{}</span>", escape_html(&serde_name(&issue.category))));
+        if let Some(service) = &issue.service_name {
+            badges.push_str(&format!("<span class=\"badge\">{}</span>", escape_html(service)));
+        }
+        if let Some(port) = issue.port {
+            badges.push_str(&format!("<span class=\"badge\">Port {}</span>", port));
+        }
+        if reason.is_none() && self.new_issues.contains(&issue.id) {
+            badges.push_str("<span class=\"badge new\">neu</span>");
+        }
+        let mut out = format!(
+            "<div class=\"issue {}\">\n<h3>{}</h3>\n<p>{}</p>\n<p>{}</p>\n<p><strong>Empfehlung:</strong> {}</p>\n",
+            severity,
+            escape_html(&issue.title),
+            badges,
+            escape_html(&issue.description),
+            escape_html(&issue.recommendation)
+        );
+        if let Some(details) = &issue.details {
+            out.push_str(&format!("<p class=\"muted\">{}</p>\n", escape_html(details)));
+        }
+        if let Some(reason) = reason {
+            out.push_str(&format!("<p><strong>Begründung:</strong> {}</p>\n", escape_html(reason)));
+        }
+        out.push_str("</div>\n");
+        out
+    }
+
+    /// Stacked bars of the open findings per scan
+    fn trend_svg(&self) -> String {
+        if self.trend.len() < 2 {
+            return "<p class=\"muted\">Für einen Verlauf sind mindestens zwei gespeicherte Scans nötig.</p>\n".to_string();
+        }
+        let (width, height) = (900.0, 160.0);
+        let max = self.trend.iter().map(|s| s.issue_count).max().unwrap_or(0).max(1) as f64;
+        let slot = width / self.trend.len() as f64;
+        let bar = (slot * 0.7).max(2.0);
+        let mut out = format!(
+            "<svg viewBox=\"0 0 {} {}\" width=\"100%\" role=\"img\" aria-label=\"Befunde pro Scan\">\n",
+            width,
+            height + 20.0
+        );
+        for (index, scan) in self.trend.iter().enumerate() {
+            let x = index as f64 * slot + (slot - bar) / 2.0;
+            let mut y = height;
+            let other = scan
+                .issue_count
+                .saturating_sub(scan.critical_count + scan.high_count + scan.medium_count + scan.low_count);
+            for (count, color) in [
+                (scan.low_count, "#2563eb"),
+                (other, "#6b7280"),
+                (scan.medium_count, "#ca8a04"),
+                (scan.high_count, "#ea580c"),
+                (scan.critical_count, "#b91c1c"),
+            ] {
+                if count == 0 {
+                    continue;
+                }
+                let h = count as f64 / max * height;
+                y -= h;
+                out.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"><title>{}: {}</title></rect>\n",
+                    x,
+                    y,
+                    bar,
+                    h,
+                    color,
+                    scan.taken_at.with_timezone(&Local).format("%d.%m. %H:%M"),
+                    scan.issue_count
+                ));
+            }
+        }
+        for (index, scan) in [(0, &self.trend[0]), (self.trend.len() - 1, &self.trend[self.trend.len() - 1])] {
+            let anchor = if index == 0 { "start" } else { "end" };
+            let x = if index == 0 { 0.0 } else { width };
+            out.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" font-size=\"12\" fill=\"#656d76\" text-anchor=\"{}\">{}</text>\n",
+                x,
+                height + 16.0,
+                anchor,
+                scan.taken_at.with_timezone(&Local).format("%d.%m.%Y")
+            ));
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    fn pdf(&self) -> Vec<u8> {
+        let r = &self.result;
+        let mut pdf = PdfDocument::new();
+        pdf.paragraph("Sicherheitsbericht", TextStyle::TITLE);
+        pdf.paragraph(
+            &format!(
+                "Scan vom {}, erstellt am {}",
+                self.scanned_at().format("%d.%m.%Y %H:%M"),
+                self.generated_at.with_timezone(&Local).format("%d.%m.%Y %H:%M")
+            ),
+            TextStyle::MUTED,
+        );
+        pdf.gap(10.0);
+        pdf.paragraph(
+            &format!(
+                "Kritisch: {}   Hoch: {}   Mittel: {}   Niedrig: {}   Akzeptiert: {}   Geprüfte Dienste: {}",
+                r.critical_count,
+                r.high_count,
+                r.medium_count,
+                r.low_count,
+                r.acknowledged.len(),
+                r.services_scanned
+            ),
+            TextStyle::BODY.bold(),
+        );
+        if self.trend.len() > 1 {
+            pdf.paragraph(
+                &format!("Seit dem vorherigen Scan: {} neu, {} behoben", self.new_issues.len(), self.fixed_issues),
+                TextStyle::BODY,
+            );
+        }
+
+        pdf.gap(14.0);
+        pdf.paragraph("Verlauf", TextStyle::HEADING);
+        if self.trend.len() < 2 {
+            pdf.paragraph("Für einen Verlauf sind mindestens zwei gespeicherte Scans nötig.", TextStyle::MUTED);
+        } else {
+            for scan in self.trend.iter().rev() {
+                pdf.paragraph(
+                &format!(
+                    "{}   {} gesamt (kritisch {}, hoch {}, mittel {}, niedrig {})",
+                    scan.taken_at.with_timezone(&Local).format("%d.%m.%Y %H:%M"),
+                    scan.issue_count,
+                    scan.critical_count,
+                    scan.high_count,
+                    scan.medium_count,
+                    scan.low_count
+                ),
+                TextStyle::BODY,
+            );
+            }
+        }
+
+        pdf.gap(14.0);
+        pdf.paragraph("Befunde", TextStyle::HEADING);
+        if r.issues.is_empty() {
+            pdf.paragraph("Keine offenen Befunde", TextStyle::MUTED);
+        }
+        for severity in SEVERITIES {
+            for issue in self.issues_by_severity(severity) {
+                self.issue_pdf(&mut pdf, issue, None);
+            }
+        }
+
+        if !r.acknowledged.is_empty() {
+            pdf.gap(14.0);
+            pdf.paragraph("Akzeptierte Risiken", TextStyle::HEADING);
+            for acknowledged in &r.acknowledged {
+                self.issue_pdf(&mut pdf, &acknowledged.issue, Some(&acknowledged.acknowledgement.reason));
+            }
+        }
+        pdf.finish()
+    }
+
+    fn issue_pdf(&self, pdf: &mut PdfDocument, issue: &SecurityIssue, reason: Option<&str>) {
+        pdf.keep(70.0);
+        pdf.gap(6.0);
+        let mut meta = vec![severity_label(&issue.severity), serde_name(&issue.category)];
+        meta.extend(issue.service_name.clone());
+        meta.extend(issue.port.map(|p| format!("Port {}", p)));
+        if reason.is_none() && self.new_issues.contains(&issue.id) {
+            meta.push("neu".to_string());
+        }
+        pdf.paragraph(&issue.title, TextStyle::BODY.bold().color(severity_rgb(&issue.severity)));
+        pdf.paragraph(&meta.join(" · "), TextStyle::MUTED.indent(10.0));
+        pdf.paragraph(&issue.description, TextStyle::BODY.indent(10.0));
+        pdf.paragraph(&format!("Empfehlung: {}", issue.recommendation), TextStyle::BODY.indent(10.0));
+        if let Some(details) = &issue.details {
+            pdf.paragraph(details, TextStyle::MUTED.indent(10.0));
+        }
+        if let Some(reason) = reason {
+            pdf.paragraph(&format!("Begründung: {}", reason), TextStyle::BODY.indent(10.0));
+        }
+    }
+
+    /// One rule per finding ID; acknowledged findings are reported with an
+    /// external suppression
+    fn sarif(&self) -> serde_json::Value {
+        let r = &self.result;
+        let all: Vec<(&SecurityIssue, Option<&str>)> = r
+            .issues
+            .iter()
+            .map(|i| (i, None))
+            .chain(r.acknowledged.iter().map(|a| (&a.issue, Some(a.acknowledgement.reason.as_str()))))
+            .collect();
+
+        let rules: Vec<serde_json::Value> = all
+            .iter()
+            .map(|(issue, _)| {
+                serde_json::json!({
+                    "id": issue.id,
+                    "name": serde_name(&issue.category),
+                    "shortDescription": { "text": issue.title },
+                    "fullDescription": { "text": issue.description },
+                    "help": { "text": issue.recommendation },
+                    "defaultConfiguration": { "level": sarif_level(&issue.severity) },
+                    "properties": {
+                        "tags": ["security", serde_name(&issue.category)],
+                        "security-severity": security_severity(&issue.severity),
+                    },
+                })
+            })
+            .collect();
+
+        let results: Vec<serde_json::Value> = all
+            .iter()
+            .enumerate()
+            .map(|(index, (issue, reason))| {
+                let location = match (&issue.service_name, issue.port) {
+                    (Some(service), Some(port)) => format!("{}:{}", service, port),
+                    (Some(service), None) => service.clone(),
+                    (None, Some(port)) => format!("Port {}", port),
+                    (None, None) => "System".to_string(),
+                };
+                let mut result = serde_json::json!({
+                    "ruleId": issue.id,
+                    "ruleIndex": index,
+                    "level": sarif_level(&issue.severity),
+                    "message": { "text": format!("{}. {}", issue.title, issue.description) },
+                    "locations": [{
+                        "logicalLocations": [{ "name": location, "kind": "module" }],
+                    }],
+                    "partialFingerprints": { "findingId/v1": issue.id },
+                    "properties": {
+                        "severity": serde_name(&issue.severity),
+                        "category": serde_name(&issue.category),
+                        "details": issue.details,
+                    },
+                });
+                if let Some(reason) = reason {
+                    result["suppressions"] = serde_json::json!([{ "kind": "external", "justification": reason }]);
+                }
+                result
+            })
+            .collect();
+
+        let scanned_at = Utc
+            .timestamp_opt(r.scan_timestamp as i64, 0)
+            .single()
+            .unwrap_or(self.generated_at);
+        serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "Network Manager",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    }
+                },
+                "invocations": [{
+                    "executionSuccessful": true,
+                    "endTimeUtc": scanned_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                    "toolExecutionNotifications": r.warnings.iter().map(|w| serde_json::json!({
+                        "level": "warning",
+                        "message": { "text": w.message },
+                    })).collect::<Vec<_>>(),
+                }],
+                "results": results,
+                "properties": {
+                    "scanId": self.scan_id,
+                    "servicesScanned": r.services_scanned,
+                    "portsScanned": r.ports_scanned,
+                    "newSincePrevious": self.new_issues.len(),
+                    "fixedSincePrevious": self.fixed_issues,
+                    "trend": self.trend,
+                },
+            }],
+        })
+    }
+}
+
+fn severity_label(severity: &SecuritySeverity) -> String {
+    match severity {
+        SecuritySeverity::Critical => "Kritisch",
+        SecuritySeverity::High => "Hoch",
+        SecuritySeverity::Medium => "Mittel",
+        SecuritySeverity::Low => "Niedrig",
+        SecuritySeverity::Info => "Info",
+    }
+    .to_string()
+}
+
+fn severity_rgb(severity: &SecuritySeverity) -> (f32, f32, f32) {
+    match severity {
+        SecuritySeverity::Critical => (0.73, 0.11, 0.11),
+        SecuritySeverity::High => (0.92, 0.35, 0.05),
+        SecuritySeverity::Medium => (0.79, 0.54, 0.02),
+        SecuritySeverity::Low => (0.15, 0.39, 0.92),
+        SecuritySeverity::Info => (0.42, 0.45, 0.5),
+    }
+}
+
+fn sarif_level(severity: &SecuritySeverity) -> &'static str {
+    match severity {
+        SecuritySeverity::Critical | SecuritySeverity::High => "error",
+        SecuritySeverity::Medium => "warning",
+        SecuritySeverity::Low | SecuritySeverity::Info => "note",
+    }
+}
+
+/// CVSS-like score that GitHub code scanning maps to its severity labels
+fn security_severity(severity: &SecuritySeverity) -> &'static str {
+    match severity {
+        SecuritySeverity::Critical => "9.5",
+        SecuritySeverity::High => "8.0",
+        SecuritySeverity::Medium => "5.5",
+        SecuritySeverity::Low => "2.0",
+        SecuritySeverity::Info => "0.0",
+    }
+}
@@ -1,9 +1,20 @@
 use serde::{Deserialize, Serialize};
-use crate::models::service::Service;
+use crate::models::config::AcknowledgedIssue;
+use crate::models::port::PortInfo;
+use crate::models::service::{ProviderWarning, Service};
+use crate::services::discovery::mock::MockDiscovery;
+use crate::services::env_secrets::EnvSecretScanner;
+use crate::services::outdated::OutdatedSoftware;
+use crate::services::firewall::FirewallChecker;
+use crate::services::security::SecurityAcknowledgements;
+use crate::services::port::auth_probe::{self, AuthProbe, AuthProbeTarget};
+use crate::services::port::fingerprint;
+use crate::services::port::PortBaseline;
+use crate::services::port::tls::{TlsInspection, TlsInspector, EXPIRY_WARNING_DAYS};
 use crate::services::port::resolver::PortResolver;
 use std::collections::HashSet;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SecuritySeverity {
     Critical,
@@ -24,6 +35,10 @@ pub enum SecurityCategory {
     InsecureConfiguration,
     PrivilegeEscalation,
     DataLeakage,
+    /// Failing hardware, e.g. a drive with reallocated sectors
+    Reliability,
+    /// Executables without a trustworthy signature
+    UntrustedBinary,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,10 +65,73 @@ pub struct SecurityScanResult {
     pub high_count: usize,
     pub medium_count: usize,
     pub low_count: usize,
+    /// Gaps in the scanned data, e.g. sockets of other users' processes
+    pub warnings: Vec<ProviderWarning>,
+    /// Findings accepted as known risks; not part of `issues` or the counts
+    #[serde(default)]
+    pub acknowledged: Vec<AcknowledgedSecurityIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcknowledgedSecurityIssue {
+    pub issue: SecurityIssue,
+    pub acknowledgement: AcknowledgedIssue,
+}
+
+/// Findings of two scans matched by ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityScanDiff {
+    pub from_timestamp: u64,
+    pub to_timestamp: u64,
+    /// Only in the later scan
+    pub new_issues: Vec<SecurityIssue>,
+    /// Only in the earlier scan and not acknowledged since
+    pub fixed_issues: Vec<SecurityIssue>,
+    /// In both scans, as reported by the later one
+    pub persistent_issues: Vec<SecurityIssue>,
+}
+
+impl SecurityScanResult {
+    /// What changed from this scan to `later`
+    pub fn diff(&self, later: &SecurityScanResult) -> SecurityScanDiff {
+        let ids = |result: &SecurityScanResult| -> HashSet<String> {
+            result
+                .issues
+                .iter()
+                .chain(result.acknowledged.iter().map(|a| &a.issue))
+                .map(|i| i.id.clone())
+                .collect()
+        };
+        let (before, after) = (ids(self), ids(later));
+        let (persistent_issues, new_issues) = later.issues.iter().cloned().partition(|i| before.contains(&i.id));
+        SecurityScanDiff {
+            from_timestamp: self.scan_timestamp,
+            to_timestamp: later.scan_timestamp,
+            new_issues,
+            fixed_issues: self.issues.iter().filter(|i| !after.contains(&i.id)).cloned().collect(),
+            persistent_issues,
+        }
+    }
+
+    /// Append findings from other checks and update the severity counts.
+    /// Acknowledged findings go to `acknowledged` instead.
+    pub fn add_issues(&mut self, issues: Vec<SecurityIssue>) {
+        for issue in issues {
+            match SecurityAcknowledgements::active(&issue.id) {
+                Some(acknowledgement) => self.acknowledged.push(AcknowledgedSecurityIssue { issue, acknowledgement }),
+                None => self.issues.push(issue),
+            }
+        }
+        let count = |severity: SecuritySeverity| self.issues.iter().filter(|i| i.severity == severity).count();
+        self.critical_count = count(SecuritySeverity::Critical);
+        self.high_count = count(SecuritySeverity::High);
+        self.medium_count = count(SecuritySeverity::Medium);
+        self.low_count = count(SecuritySeverity::Low);
+    }
 }
 
 // Known insecure ports and their issues
-const INSECURE_PORTS: &[(u16, &str, &str)] = &[
+pub(crate) const INSECURE_PORTS: &[(u16, &str, &str)] = &[
     (21, "FTP", "FTP überträgt Daten unverschlüsselt, inkl. Passwörter"),
     (23, "Telnet", "Telnet ist unverschlüsselt, verwende SSH stattdessen"),
     (25, "SMTP", "SMTP ohne TLS überträgt E-Mails unverschlüsselt"),
@@ -77,22 +155,55 @@ const INSECURE_PORTS: &[(u16, &str, &str)] = &[
 ];
 
 // Ports that indicate services listening on all interfaces
-const DATABASE_PORTS: &[u16] = &[1433, 1521, 3306, 5432, 6379, 11211, 27017, 5984, 9200, 9300];
+pub(crate) const DATABASE_PORTS: &[u16] = &[1433, 1521, 3306, 5432, 6379, 11211, 27017, 5984, 9200, 9300];
+
+// Ports usually serving TLS; others are only inspected when fingerprinting found TLS
+const TLS_PORTS: &[u16] = &[443, 465, 636, 853, 993, 995, 2376, 5986, 6443, 8443, 9443];
+
+/// Certificates expiring sooner are reported as high severity
+const EXPIRY_URGENT_DAYS: i64 = 7;
 
 pub struct SecurityScanner {
     port_resolver: PortResolver,
+    /// Probe listening ports to identify services on non-standard ports
+    fingerprint: bool,
+    /// Connect to TLS ports and check certificates and protocol support
+    tls: bool,
+    /// Send read-only commands to local datastores to confirm missing authentication
+    auth_probe: bool,
 }
 
 impl SecurityScanner {
     pub fn new() -> Self {
         Self {
             port_resolver: PortResolver::new(),
+            fingerprint: false,
+            tls: true,
+            auth_probe: false,
         }
     }
 
+    pub fn with_fingerprinting(mut self, enabled: bool) -> Self {
+        self.fingerprint = enabled;
+        self
+    }
+
+    pub fn with_tls_inspection(mut self, enabled: bool) -> Self {
+        self.tls = enabled;
+        self
+    }
+
+    pub fn with_auth_probing(mut self, enabled: bool) -> Self {
+        self.auth_probe = enabled;
+        self
+    }
+
     pub fn scan(&self, services: &[Service]) -> SecurityScanResult {
         let mut issues = Vec::new();
-        let port_usage = self.port_resolver.get_port_usage();
+        let (mut port_usage, warnings) = self.port_resolver.port_usage_with_warnings();
+        if self.fingerprint {
+            fingerprint::fingerprint_ports(&mut port_usage);
+        }
         let open_ports: HashSet<u16> = port_usage.iter().map(|p| p.port).collect();
 
         // Check for insecure ports
@@ -111,17 +222,49 @@ impl SecurityScanner {
                     description: description.to_string(),
                     recommendation: self.get_port_recommendation(port),
                     port: Some(port),
-                    details: None,
+                    details: detected_version(&port_usage, port),
                 });
             }
         }
 
-        // Check for databases exposed on all interfaces
+        // Insecure protocols the fingerprint pass found on non-standard ports
+        let mut reported_services = HashSet::new();
+        for port_info in &port_usage {
+            let Some(detected) = &port_info.detected_service else {
+                continue;
+            };
+            let Some(&(standard_port, name, description)) = INSECURE_PORTS
+                .iter()
+                .find(|(_, name, _)| name.eq_ignore_ascii_case(detected))
+            else {
+                continue;
+            };
+            if port_info.port == standard_port || !reported_services.insert(port_info.port) {
+                continue;
+            }
+
+            let service = services.iter().find(|s| s.ports.contains(&port_info.port));
+            issues.push(SecurityIssue {
+                id: format!("service-{}-{}", detected, port_info.port),
+                service_id: service.map(|s| s.id.clone()),
+                service_name: service.map(|s| s.name.clone()).or_else(|| port_info.process_name.clone()),
+                category: SecurityCategory::UnencryptedConnection,
+                severity: self.get_port_severity(standard_port),
+                title: format!("{} auf Port {} erkannt", name, port_info.port),
+                description: description.to_string(),
+                recommendation: self.get_port_recommendation(standard_port),
+                port: Some(port_info.port),
+                details: port_info.version.clone(),
+            });
+        }
+
+        // Check for databases reachable from other machines. Dual-stack
+        // services report one entry per family, so report each port once.
+        let mut reported_db_ports = HashSet::new();
         for port_info in &port_usage {
             if DATABASE_PORTS.contains(&port_info.port) {
-                // Check if listening on 0.0.0.0 or ::
-                let is_public = self.is_port_public(port_info.port);
-                if is_public {
+                let is_public = !port_info.is_loopback();
+                if is_public && reported_db_ports.insert(port_info.port) {
                     let service = services.iter().find(|s| s.ports.contains(&port_info.port));
                     issues.push(SecurityIssue {
                         id: format!("public-db-{}", port_info.port),
@@ -133,40 +276,76 @@ impl SecurityScanner {
                         description: "Datenbanken sollten nicht von außen erreichbar sein".to_string(),
                         recommendation: "Binde die Datenbank an localhost (127.0.0.1) oder verwende eine Firewall".to_string(),
                         port: Some(port_info.port),
-                        details: port_info.process_name.clone(),
+                        details: Some(match &port_info.process_name {
+                            Some(name) => format!("{} gebunden an {}", name, port_info.bind_address),
+                            None => format!("Gebunden an {}", port_info.bind_address),
+                        }),
                     });
                 }
             }
         }
 
+        if self.tls {
+            self.check_tls(&port_usage, services, &mut issues);
+        }
+
+        // Publicly bound ports with no firewall in front of them
+        issues.extend(FirewallChecker::findings(&FirewallChecker::new().check(), &port_usage));
+
+        // Probed ports replace the name-based guesses below
+        let probes = if self.auth_probe {
+            auth_probe::probe_ports(&port_usage)
+        } else {
+            Vec::new()
+        };
+        self.check_auth_probes(&probes, &port_usage, services, &mut issues);
+
         // Check services for common security issues
         for service in services {
-            self.check_service_security(service, &mut issues);
+            self.check_service_security(service, &probes, &mut issues);
+        }
+
+        // Listeners that weren't there when the baseline was recorded
+        issues.extend(PortBaseline::findings(&port_usage));
+
+        // Simulated services carry made-up PIDs that may belong to real processes
+        if !MockDiscovery::is_enabled() {
+            issues.extend(EnvSecretScanner::findings(services));
+            issues.extend(OutdatedSoftware::findings(services));
         }
 
+        // Config files of sshd, Redis, nginx and PostgreSQL
+        #[cfg(unix)]
+        issues.extend(crate::services::config_audit::ConfigAudit::findings(services));
+
         // Check for services running as root (on Unix)
         #[cfg(unix)]
         self.check_root_services(services, &mut issues);
 
-        // Count by severity
-        let critical_count = issues.iter().filter(|i| matches!(i.severity, SecuritySeverity::Critical)).count();
-        let high_count = issues.iter().filter(|i| matches!(i.severity, SecuritySeverity::High)).count();
-        let medium_count = issues.iter().filter(|i| matches!(i.severity, SecuritySeverity::Medium)).count();
-        let low_count = issues.iter().filter(|i| matches!(i.severity, SecuritySeverity::Low)).count();
+        // Binaries anyone can run with raised privileges
+        #[cfg(unix)]
+        issues.extend(crate::services::suid_audit::SuidAudit::findings());
+
+        #[cfg(target_os = "macos")]
+        issues.extend(crate::services::code_signing::CodeSigning::findings(&port_usage));
 
-        SecurityScanResult {
-            issues,
+        let mut result = SecurityScanResult {
+            issues: Vec::new(),
             scan_timestamp: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .map(|d| d.as_secs())
                 .unwrap_or(0),
             services_scanned: services.len(),
             ports_scanned: open_ports.len(),
-            critical_count,
-            high_count,
-            medium_count,
-            low_count,
-        }
+            critical_count: 0,
+            high_count: 0,
+            medium_count: 0,
+            low_count: 0,
+            warnings,
+            acknowledged: Vec::new(),
+        };
+        result.add_issues(issues);
+        result
     }
 
     fn get_port_severity(&self, port: u16) -> SecuritySeverity {
@@ -196,43 +375,137 @@ impl SecurityScanner {
         }
     }
 
-    fn is_port_public(&self, port: u16) -> bool {
-        // Check lsof/netstat output for binding address
-        #[cfg(target_os = "macos")]
-        {
-            let output = std::process::Command::new("lsof")
-                .args(["-i", &format!(":{}", port), "-P", "-n"])
-                .output();
-
-            if let Ok(output) = output {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                // If it shows *:port or 0.0.0.0:port, it's public
-                return stdout.contains(&format!("*:{}", port))
-                    || stdout.contains(&format!("0.0.0.0:{}", port))
-                    || stdout.contains(&format!("[::]:{}", port));
-            }
+    /// Certificate expiry, self-signed certificates and legacy protocols on
+    /// ports that speak TLS
+    fn check_tls(&self, port_usage: &[PortInfo], services: &[Service], issues: &mut Vec<SecurityIssue>) {
+        let candidates: Vec<PortInfo> = port_usage
+            .iter()
+            .filter(|p| TLS_PORTS.contains(&p.port) || p.detected_service.as_deref() == Some("tls"))
+            .cloned()
+            .collect();
+        if candidates.is_empty() {
+            return;
         }
 
-        #[cfg(target_os = "linux")]
-        {
-            let output = std::process::Command::new("ss")
-                .args(["-tlnp", &format!("sport = :{}", port)])
-                .output();
+        for inspection in TlsInspector::new().scan_ports(&candidates) {
+            let port = inspection.port;
+            let service = services.iter().find(|s| s.ports.contains(&port));
+            let service_name = service
+                .map(|s| s.name.clone())
+                .or_else(|| candidates.iter().find(|p| p.port == port).and_then(|p| p.process_name.clone()));
+            let details = tls_details(&inspection);
+            let mut push = |id: &str, category, severity, title: String, description: &str, recommendation: &str| {
+                issues.push(SecurityIssue {
+                    id: format!("{}-{}", id, port),
+                    service_id: service.map(|s| s.id.clone()),
+                    service_name: service_name.clone(),
+                    category,
+                    severity,
+                    title,
+                    description: description.to_string(),
+                    recommendation: recommendation.to_string(),
+                    port: Some(port),
+                    details: Some(details.clone()),
+                });
+            };
+
+            match inspection.expires_in_days {
+                Some(days) if days < 0 => push(
+                    "tls-expired",
+                    SecurityCategory::InsecureConfiguration,
+                    SecuritySeverity::Critical,
+                    format!("TLS-Zertifikat auf Port {} ist seit {} Tagen abgelaufen", port, -days),
+                    "Clients lehnen die Verbindung ab oder Nutzer gewöhnen sich an Zertifikatswarnungen",
+                    "Erneuere das Zertifikat und lade den Dienst neu",
+                ),
+                Some(days) if days < EXPIRY_WARNING_DAYS => push(
+                    "tls-expiry",
+                    SecurityCategory::InsecureConfiguration,
+                    if days < EXPIRY_URGENT_DAYS { SecuritySeverity::High } else { SecuritySeverity::Medium },
+                    format!("TLS-Zertifikat auf Port {} läuft in {} Tagen ab", port, days),
+                    "Nach Ablauf schlagen TLS-Verbindungen zu diesem Dienst fehl",
+                    "Erneuere das Zertifikat rechtzeitig oder prüfe die automatische Erneuerung (z.B. certbot, Caddy)",
+                ),
+                _ => {}
+            }
+
+            if inspection.self_signed {
+                push(
+                    "tls-self-signed",
+                    SecurityCategory::InsecureConfiguration,
+                    SecuritySeverity::Low,
+                    format!("Selbstsigniertes TLS-Zertifikat auf Port {}", port),
+                    "Clients können die Identität des Servers nicht prüfen",
+                    "Verwende ein Zertifikat einer vertrauenswürdigen oder lokalen CA (z.B. mkcert)",
+                );
+            }
+
+            let legacy: Vec<&str> = inspection
+                .protocols
+                .iter()
+                .filter(|p| p.supported && p.deprecated)
+                .map(|p| p.version.as_str())
+                .collect();
+            if !legacy.is_empty() {
+                push(
+                    "tls-legacy-protocol",
+                    SecurityCategory::UnencryptedConnection,
+                    SecuritySeverity::Medium,
+                    format!("Port {} unterstützt {}", port, legacy.join(", ")),
+                    "Veraltete Protokollversionen haben bekannte Schwachstellen",
+                    "Erlaube nur TLS 1.2 und TLS 1.3",
+                );
+            }
 
-            if let Ok(output) = output {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                return stdout.contains("0.0.0.0") || stdout.contains("[::]") || stdout.contains("*:");
+            if !inspection.weak_ciphers.is_empty() {
+                push(
+                    "tls-weak-cipher",
+                    SecurityCategory::UnencryptedConnection,
+                    SecuritySeverity::Medium,
+                    format!("Port {} akzeptiert {} schwache Cipher-Suites", port, inspection.weak_ciphers.len()),
+                    "NULL-, EXPORT-, RC4-, DES- und anonyme Suites bieten keinen ausreichenden Schutz",
+                    "Deaktiviere die schwachen Cipher-Suites in der TLS-Konfiguration",
+                );
             }
         }
+    }
+
+    /// Confirmed findings for datastores that answered without credentials
+    fn check_auth_probes(&self, probes: &[AuthProbe], port_usage: &[PortInfo], services: &[Service], issues: &mut Vec<SecurityIssue>) {
+        for probe in probes.iter().filter(|p| p.unauthenticated) {
+            let service = services.iter().find(|s| s.ports.contains(&probe.port));
+            let bindings: Vec<&PortInfo> = port_usage.iter().filter(|p| p.port == probe.port).collect();
+            let is_public = bindings.iter().any(|p| !p.is_loopback());
+            let name = probe.target.name();
 
-        false
+            issues.push(SecurityIssue {
+                id: format!("unauthenticated-{}-{}", name.to_lowercase(), probe.port),
+                service_id: service.map(|s| s.id.clone()),
+                service_name: service
+                    .map(|s| s.name.clone())
+                    .or_else(|| bindings.iter().find_map(|p| p.process_name.clone())),
+                category: SecurityCategory::MissingAuthentication,
+                severity: if is_public { SecuritySeverity::Critical } else { SecuritySeverity::High },
+                title: format!("{} auf Port {} ist ohne Authentifizierung erreichbar", name, probe.port),
+                description: if is_public {
+                    format!("{} beantwortet Befehle ohne Anmeldung und ist von anderen Rechnern erreichbar", name)
+                } else {
+                    format!("{} beantwortet Befehle ohne Anmeldung; jeder lokale Prozess kann die Daten lesen und ändern", name)
+                },
+                recommendation: auth_recommendation(probe).to_string(),
+                port: Some(probe.port),
+                details: Some(format!("Bestätigt: {}", probe.evidence)),
+            });
+        }
     }
 
-    fn check_service_security(&self, service: &Service, issues: &mut Vec<SecurityIssue>) {
+    fn check_service_security(&self, service: &Service, probes: &[AuthProbe], issues: &mut Vec<SecurityIssue>) {
         let name_lower = service.name.to_lowercase();
+        // A probe answered for one of the service's ports, nothing left to guess
+        let probed = probes.iter().any(|p| service.ports.contains(&p.port));
 
         // Check for known vulnerable services
-        if name_lower.contains("redis") && service.ports.contains(&6379) {
+        if name_lower.contains("redis") && service.ports.contains(&6379) && !probed {
             issues.push(SecurityIssue {
                 id: format!("redis-auth-{}", service.id),
                 service_id: Some(service.id.clone()),
@@ -247,7 +520,7 @@ impl SecurityScanner {
             });
         }
 
-        if name_lower.contains("mongodb") && service.ports.contains(&27017) {
+        if name_lower.contains("mongodb") && service.ports.contains(&27017) && !probed {
             issues.push(SecurityIssue {
                 id: format!("mongo-auth-{}", service.id),
                 service_id: Some(service.id.clone()),
@@ -262,7 +535,7 @@ impl SecurityScanner {
             });
         }
 
-        if name_lower.contains("elasticsearch") {
+        if name_lower.contains("elasticsearch") && !probed {
             issues.push(SecurityIssue {
                 id: format!("elastic-auth-{}", service.id),
                 service_id: Some(service.id.clone()),
@@ -276,12 +549,6 @@ impl SecurityScanner {
                 details: None,
             });
         }
-
-        // Check Docker containers for privileged mode or host network
-        if service.service_type == crate::models::service::ServiceType::Docker {
-            // Note: Would need Docker API to check these details
-            // For now, just flag Docker services for review
-        }
     }
 
     #[cfg(unix)]
@@ -289,7 +556,7 @@ impl SecurityScanner {
         for service in services {
             if let Some(pid) = service.pid {
                 // Check if process is running as root
-                let output = std::process::Command::new("ps")
+                let output = crate::services::cmd::command("ps")
                     .args(["-o", "user=", "-p", &pid.to_string()])
                     .output();
 
@@ -320,6 +587,42 @@ impl SecurityScanner {
     }
 }
 
+/// Version announced on `port`, if the fingerprint pass found one
+fn detected_version(port_usage: &[PortInfo], port: u16) -> Option<String> {
+    port_usage
+        .iter()
+        .filter(|p| p.port == port)
+        .find_map(|p| p.version.clone())
+}
+
+fn auth_recommendation(probe: &AuthProbe) -> &'static str {
+    match probe.target {
+        AuthProbeTarget::Redis => "Setze ein Passwort mit 'requirepass' oder lege ACL-Benutzer an",
+        AuthProbeTarget::MongoDb => "Lege einen Admin-Benutzer an und aktiviere 'security.authorization'",
+        AuthProbeTarget::Elasticsearch => "Aktiviere 'xpack.security.enabled' und setze Passwörter",
+        AuthProbeTarget::Memcached => "Binde Memcached an localhost und aktiviere SASL oder eine Auth-Datei (-Y)",
+    }
+}
+
+/// Leaf certificate, expiry and everything the inspection found
+fn tls_details(inspection: &TlsInspection) -> String {
+    let mut parts = Vec::new();
+    if let Some(leaf) = inspection.chain.first() {
+        parts.push(format!("Subject: {}", leaf.subject));
+        parts.push(format!("Aussteller: {}", leaf.issuer));
+        if let Some(not_after) = leaf.not_after {
+            parts.push(format!("Gültig bis: {}", not_after.format("%Y-%m-%d")));
+        }
+    }
+    if let Some(days) = inspection.expires_in_days {
+        parts.push(format!("Tage bis Ablauf: {}", days));
+    }
+    if !inspection.weak_ciphers.is_empty() {
+        parts.push(format!("Schwache Suites: {}", inspection.weak_ciphers.join(", ")));
+    }
+    parts.join(", ")
+}
+
 impl Default for SecurityScanner {
     fn default() -> Self {
         Self::new()
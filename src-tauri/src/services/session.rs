@@ -0,0 +1,78 @@
+use std::sync::{Mutex, OnceLock};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::service::ServiceType;
+
+static CHANGES: OnceLock<Mutex<Vec<SessionChange>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionChangeKind {
+    /// The service was running and has been stopped
+    Stopped,
+    AutostartDisabled,
+}
+
+/// A service state change made by the app in the current session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionChange {
+    pub service_id: String,
+    pub service_type: ServiceType,
+    pub kind: SessionChangeKind,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRestoreResult {
+    pub change: SessionChange,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Outcome of `restore_session_state`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRestoreReport {
+    pub results: Vec<SessionRestoreResult>,
+    pub restored: usize,
+    pub failed: usize,
+}
+
+/// Remembers which services the app stopped or took out of autostart since
+/// launch, so they can be brought back in one go. Killed ad-hoc processes
+/// can't be restarted and are not recorded.
+pub struct SessionJournal;
+
+impl SessionJournal {
+    fn changes() -> &'static Mutex<Vec<SessionChange>> {
+        CHANGES.get_or_init(|| Mutex::new(Vec::new()))
+    }
+
+    pub fn record(service_id: &str, service_type: ServiceType, kind: SessionChangeKind) {
+        if service_type == ServiceType::Process {
+            return;
+        }
+
+        let mut changes = Self::changes().lock().unwrap_or_else(|e| e.into_inner());
+        // Keep the first change: it reflects the state before the session touched it
+        if changes.iter().any(|c| c.service_id == service_id && c.kind == kind) {
+            return;
+        }
+        changes.push(SessionChange {
+            service_id: service_id.to_string(),
+            service_type,
+            kind,
+            changed_at: Utc::now(),
+        });
+    }
+
+    /// Drop a change that was undone by hand, e.g. a stopped service started again
+    pub fn forget(service_id: &str, kind: SessionChangeKind) {
+        let mut changes = Self::changes().lock().unwrap_or_else(|e| e.into_inner());
+        changes.retain(|c| !(c.service_id == service_id && c.kind == kind));
+    }
+
+    /// Recorded changes, oldest first
+    pub fn list() -> Vec<SessionChange> {
+        Self::changes().lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
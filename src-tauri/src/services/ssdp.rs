@@ -0,0 +1,435 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use reqwest::{redirect, Client, Response, Url};
+use serde::{Deserialize, Serialize};
+use tokio::net::UdpSocket;
+use crate::services::security_scanner::{SecurityCategory, SecurityIssue, SecuritySeverity, DATABASE_PORTS, INSECURE_PORTS};
+
+const SSDP_ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(239, 255, 255, 250)), 1900);
+/// Routers answer with this device type, media devices with their own
+const SEARCH_TARGETS: &[&str] = &["ssdp:all", "urn:schemas-upnp-org:device:InternetGatewayDevice:1"];
+/// Connection services that manage port mappings
+const WAN_SERVICES: &[&str] = &["WANIPConnection", "WANPPPConnection"];
+/// Upper bound for GetGenericPortMappingEntry, some routers never return an error
+const MAX_MAPPINGS: u32 = 256;
+/// Device descriptions and SOAP answers are a few KiB; anything bigger is not a router
+const MAX_RESPONSE_BYTES: usize = 256 * 1024;
+
+/// A UPnP device that answered the SSDP search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsdpDevice {
+    /// URL of the device description
+    pub location: String,
+    pub address: String,
+    /// `SERVER` header, e.g. `Linux/5.4 UPnP/1.0 MiniUPnPd/2.2`
+    pub server: Option<String>,
+    pub device_type: Option<String>,
+    pub friendly_name: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model_name: Option<String>,
+    /// Offers an IGD connection service (port mappings)
+    pub is_gateway: bool,
+}
+
+/// An existing port forwarding on the router
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpnpPortMapping {
+    pub external_port: u16,
+    /// `TCP` or `UDP`
+    pub protocol: String,
+    pub internal_client: String,
+    pub internal_port: u16,
+    pub description: String,
+    pub enabled: bool,
+    /// Seconds, 0 means permanent
+    pub lease_duration: u32,
+    /// Restriction to a remote host, empty for any
+    pub remote_host: String,
+    /// Forwarded to this machine
+    pub to_this_host: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpnpGateway {
+    pub location: String,
+    pub friendly_name: Option<String>,
+    pub external_ip: Option<String>,
+    pub mappings: Vec<UpnpPortMapping>,
+    /// The router refused or failed to list its mappings
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsdpDiscovery {
+    pub devices: Vec<SsdpDevice>,
+    pub gateways: Vec<UpnpGateway>,
+    /// Findings about the port mappings, in the security scanner's format
+    pub issues: Vec<SecurityIssue>,
+    pub scanned_at: DateTime<Utc>,
+}
+
+/// Finds UPnP devices via SSDP and lists the port mappings of Internet
+/// gateways (IGD). Forwardings set up by games, NAS boxes or old test
+/// servers are invisible to the local port scan.
+pub struct SsdpScanner {
+    timeout: Duration,
+    client: Client,
+}
+
+impl SsdpScanner {
+    pub fn new() -> Self {
+        Self {
+            timeout: Duration::from_secs(2),
+            // Any host on the LAN can answer the search, so never follow it elsewhere
+            client: Client::builder()
+                .timeout(Duration::from_secs(4))
+                .redirect(redirect::Policy::none())
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// How long to wait for SSDP responses
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub async fn scan(&self) -> Result<SsdpDiscovery, Box<dyn std::error::Error + Send + Sync>> {
+        let responses = self.search().await?;
+
+        let mut devices = Vec::new();
+        let mut gateways = Vec::new();
+        for (location, (address, server)) in responses {
+            // A LOCATION pointing at another host (or at ourselves) would let any
+            // device on the LAN make us send requests there
+            if !Url::parse(&location).is_ok_and(|url| is_responder(&url, address)) {
+                continue;
+            }
+            let description = self.fetch(&location).await.unwrap_or_default();
+            let wan_service = find_wan_service(&description, &location, address);
+
+            if let Some((service_type, control_url)) = &wan_service {
+                let local_ip = local_ip_towards(address);
+                gateways.push(self.read_gateway(&location, &description, service_type, control_url, local_ip).await);
+            }
+
+            devices.push(SsdpDevice {
+                address: address.to_string(),
+                server,
+                device_type: xml_value(&description, "deviceType"),
+                friendly_name: xml_value(&description, "friendlyName"),
+                manufacturer: xml_value(&description, "manufacturer"),
+                model_name: xml_value(&description, "modelName"),
+                is_gateway: wan_service.is_some(),
+                location,
+            });
+        }
+        devices.sort_by(|a, b| b.is_gateway.cmp(&a.is_gateway).then_with(|| a.address.cmp(&b.address)));
+
+        let issues = gateways.iter().flat_map(mapping_issues).collect();
+        Ok(SsdpDiscovery {
+            devices,
+            gateways,
+            issues,
+            scanned_at: Utc::now(),
+        })
+    }
+
+    /// M-SEARCH and collect the `LOCATION` of every answer, deduplicated
+    async fn search(&self) -> Result<HashMap<String, (IpAddr, Option<String>)>, Box<dyn std::error::Error + Send + Sync>> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+        socket.set_multicast_ttl_v4(4)?;
+        for target in SEARCH_TARGETS {
+            let request = format!(
+                "M-SEARCH * HTTP/1.1\r\nHOST: 239.255.255.250:1900\r\nMAN: \"ssdp:discover\"\r\nMX: 1\r\nST: {}\r\n\r\n",
+                target
+            );
+            socket.send_to(request.as_bytes(), SSDP_ADDR).await?;
+        }
+
+        let mut found = HashMap::new();
+        let mut buffer = [0u8; 4096];
+        let deadline = tokio::time::Instant::now() + self.timeout;
+        while let Ok(Ok((len, from))) = tokio::time::timeout_at(deadline, socket.recv_from(&mut buffer)).await {
+            let response = String::from_utf8_lossy(&buffer[..len]);
+            let headers = parse_headers(&response);
+            if let Some(location) = headers.get("location") {
+                found
+                    .entry(location.clone())
+                    .or_insert_with(|| (from.ip(), headers.get("server").cloned()));
+            }
+        }
+        Ok(found)
+    }
+
+    async fn fetch(&self, url: &str) -> Result<String, String> {
+        let response = self.client.get(url).send().await.map_err(|e| e.to_string())?;
+        let response = response.error_for_status().map_err(|e| e.to_string())?;
+        read_limited(response).await
+    }
+
+    async fn read_gateway(
+        &self,
+        location: &str,
+        description: &str,
+        service_type: &str,
+        control_url: &str,
+        local_ip: Option<IpAddr>,
+    ) -> UpnpGateway {
+        let external_ip = self
+            .soap(control_url, service_type, "GetExternalIPAddress", "")
+            .await
+            .ok()
+            .and_then(|body| xml_value(&body, "NewExternalIPAddress"))
+            .filter(|ip| !ip.is_empty());
+
+        let mut mappings = Vec::new();
+        let mut error = None;
+        for index in 0..MAX_MAPPINGS {
+            let arguments = format!("<NewPortMappingIndex>{}</NewPortMappingIndex>", index);
+            match self.soap(control_url, service_type, "GetGenericPortMappingEntry", &arguments).await {
+                Ok(body) => match parse_mapping(&body, local_ip) {
+                    Some(mapping) => mappings.push(mapping),
+                    None => break,
+                },
+                // 713 SpecifiedArrayIndexInvalid ends the list
+                Err(e) if index > 0 || e.contains("713") => break,
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+
+        UpnpGateway {
+            location: location.to_string(),
+            friendly_name: xml_value(description, "friendlyName"),
+            external_ip,
+            mappings,
+            error,
+        }
+    }
+
+    /// Call an action; SOAP faults come back as `Err` with the UPnP error code
+    async fn soap(&self, control_url: &str, service_type: &str, action: &str, arguments: &str) -> Result<String, String> {
+        let body = format!(
+            "<?xml version=\"1.0\"?>\
+             <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+             <s:Body><u:{action} xmlns:u=\"{service_type}\">{arguments}</u:{action}></s:Body></s:Envelope>"
+        );
+        let response = self
+            .client
+            .post(control_url)
+            .header("Content-Type", "text/xml; charset=\"utf-8\"")
+            .header("SOAPAction", format!("\"{}#{}\"", service_type, action))
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        let text = read_limited(response).await?;
+        if status.is_success() {
+            Ok(text)
+        } else {
+            let code = xml_value(&text, "errorCode").unwrap_or_else(|| status.as_u16().to_string());
+            let message = xml_value(&text, "errorDescription").unwrap_or_default();
+            Err(format!("UPnP-Fehler {} {}", code, message).trim_end().to_string())
+        }
+    }
+}
+
+impl Default for SsdpScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One finding per active mapping; databases and unencrypted protocols rank
+/// higher, as do permanent mappings
+fn mapping_issues(gateway: &UpnpGateway) -> Vec<SecurityIssue> {
+    gateway
+        .mappings
+        .iter()
+        .filter(|m| m.enabled)
+        .map(|mapping| {
+            let insecure = INSECURE_PORTS.iter().find(|(port, _, _)| *port == mapping.internal_port);
+            let severity = match insecure {
+                _ if DATABASE_PORTS.contains(&mapping.internal_port) => SecuritySeverity::Critical,
+                Some(_) => SecuritySeverity::High,
+                None if mapping.lease_duration == 0 => SecuritySeverity::Medium,
+                None => SecuritySeverity::Low,
+            };
+            let target = if mapping.to_this_host {
+                "diesen Rechner".to_string()
+            } else {
+                mapping.internal_client.clone()
+            };
+            let mut description = format!(
+                "Der Router leitet Port {}/{} aus dem Internet an {}:{} weiter",
+                mapping.external_port, mapping.protocol, mapping.internal_client, mapping.internal_port
+            );
+            if let Some((_, name, risk)) = insecure {
+                description.push_str(&format!(". {}: {}", name, risk));
+            }
+            if mapping.lease_duration == 0 {
+                description.push_str(". Die Weiterleitung läuft nicht ab.");
+            }
+
+            SecurityIssue {
+                id: format!("upnp-{}-{}", mapping.protocol.to_lowercase(), mapping.external_port),
+                service_id: None,
+                service_name: Some(mapping.description.clone()).filter(|d| !d.is_empty()),
+                category: SecurityCategory::PublicExposure,
+                severity,
+                title: format!("UPnP-Weiterleitung von Port {} an {}", mapping.external_port, target),
+                description,
+                recommendation: "Weiterleitung im Router entfernen, falls sie nicht mehr gebraucht wird, und UPnP deaktivieren, wenn keine Anwendung es benötigt".to_string(),
+                port: Some(mapping.external_port),
+                details: gateway.external_ip.as_ref().map(|ip| format!("Externe Adresse: {}", ip)),
+            }
+        })
+        .collect()
+}
+
+fn parse_mapping(body: &str, local_ip: Option<IpAddr>) -> Option<UpnpPortMapping> {
+    let internal_client = xml_value(body, "NewInternalClient")?;
+    Some(UpnpPortMapping {
+        external_port: xml_value(body, "NewExternalPort")?.parse().ok()?,
+        protocol: xml_value(body, "NewProtocol").unwrap_or_default().to_uppercase(),
+        internal_port: xml_value(body, "NewInternalPort")?.parse().ok()?,
+        description: xml_value(body, "NewPortMappingDescription").unwrap_or_default(),
+        enabled: xml_value(body, "NewEnabled").is_none_or(|v| v == "1" || v.eq_ignore_ascii_case("true")),
+        lease_duration: xml_value(body, "NewLeaseDuration").and_then(|v| v.parse().ok()).unwrap_or(0),
+        remote_host: xml_value(body, "NewRemoteHost").unwrap_or_default(),
+        to_this_host: local_ip.is_some_and(|ip| internal_client.parse::<IpAddr>().ok() == Some(ip)),
+        internal_client,
+    })
+}
+
+/// Body as text, giving up once it exceeds `MAX_RESPONSE_BYTES`
+async fn read_limited(mut response: Response) -> Result<String, String> {
+    if response.content_length().is_some_and(|len| len > MAX_RESPONSE_BYTES as u64) {
+        return Err("Antwort zu groß".to_string());
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        if body.len() + chunk.len() > MAX_RESPONSE_BYTES {
+            return Err("Antwort zu groß".to_string());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// `url` is plain HTTP(S) to the device that answered, and that device isn't us
+fn is_responder(url: &Url, responder: IpAddr) -> bool {
+    // Host names are not accepted, they could resolve anywhere
+    let Some(host) = url
+        .host_str()
+        .and_then(|h| h.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>().ok())
+    else {
+        return false;
+    };
+    matches!(url.scheme(), "http" | "https") && host == responder && !responder.is_loopback() && !responder.is_unspecified()
+}
+
+/// The first WANIPConnection/WANPPPConnection service and its absolute control
+/// URL; control URLs on other hosts than the responder are ignored
+fn find_wan_service(description: &str, location: &str, responder: IpAddr) -> Option<(String, String)> {
+    let base = xml_value(description, "URLBase")
+        .and_then(|b| Url::parse(&b).ok())
+        .or_else(|| Url::parse(location).ok())?;
+    let services = Regex::new(r"(?s)<service>(.*?)</service>").ok()?;
+    let found = services.captures_iter(description).find_map(|block| {
+        let block = &block[1];
+        let service_type = xml_value(block, "serviceType")?;
+        if !WAN_SERVICES.iter().any(|s| service_type.contains(s)) {
+            return None;
+        }
+        let control_url = base.join(&xml_value(block, "controlURL")?).ok()?;
+        is_responder(&control_url, responder).then(|| (service_type, control_url.to_string()))
+    });
+    found
+}
+
+/// Text of the first `<tag>`, ignoring namespace prefixes
+fn xml_value(xml: &str, tag: &str) -> Option<String> {
+    let pattern = format!(r"(?s)<(?:\w+:)?{}(?:\s[^>]*)?>(.*?)</(?:\w+:)?{}>", regex::escape(tag), regex::escape(tag));
+    let captures = Regex::new(&pattern).ok()?.captures(xml)?;
+    Some(
+        captures[1]
+            .trim()
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"),
+    )
+}
+
+/// HTTP-style headers of an SSDP response, keys lowercased
+fn parse_headers(response: &str) -> HashMap<String, String> {
+    response
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_lowercase(), value.trim().to_string()))
+        .collect()
+}
+
+/// Our address on the interface that reaches `peer`, to spot mappings to this host
+fn local_ip_towards(peer: IpAddr) -> Option<IpAddr> {
+    let socket = std::net::UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((peer, 1900)).ok()?;
+    Some(socket.local_addr().ok()?.ip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROUTER: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1));
+
+    fn description(control_url: &str) -> String {
+        format!(
+            "<root><device><serviceList><service>\
+             <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>\
+             <controlURL>{}</controlURL></service></serviceList></device></root>",
+            control_url
+        )
+    }
+
+    #[test]
+    fn location_must_point_at_the_responder() {
+        let url = |s: &str| Url::parse(s).unwrap();
+        assert!(is_responder(&url("http://192.168.1.1:5000/rootDesc.xml"), ROUTER));
+        assert!(!is_responder(&url("http://192.168.1.2:5000/rootDesc.xml"), ROUTER));
+        assert!(!is_responder(&url("http://router.local/rootDesc.xml"), ROUTER));
+        assert!(!is_responder(&url("file:///etc/passwd"), ROUTER));
+
+        let loopback = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        assert!(!is_responder(&url("http://127.0.0.1:8080/"), loopback));
+    }
+
+    #[test]
+    fn relative_control_url_resolves_against_location() {
+        let (service_type, control_url) =
+            find_wan_service(&description("/ctl/IPConn"), "http://192.168.1.1:5000/rootDesc.xml", ROUTER).unwrap();
+        assert_eq!(service_type, "urn:schemas-upnp-org:service:WANIPConnection:1");
+        assert_eq!(control_url, "http://192.168.1.1:5000/ctl/IPConn");
+    }
+
+    #[test]
+    fn control_url_on_another_host_is_ignored() {
+        let location = "http://192.168.1.1:5000/rootDesc.xml";
+        assert!(find_wan_service(&description("http://127.0.0.1:8080/admin"), location, ROUTER).is_none());
+        assert!(find_wan_service(&description("http://10.0.0.5/ctl"), location, ROUTER).is_none());
+
+        let rebased = format!("<URLBase>http://10.0.0.5/</URLBase>{}", description("/ctl/IPConn"));
+        assert!(find_wan_service(&rebased, location, ROUTER).is_none());
+    }
+}
@@ -0,0 +1,122 @@
+// Rolling in-memory CPU/memory/GPU history for sparklines
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// 30 minutes of per-second samples
+const SECOND_SAMPLES: usize = 30 * 60;
+/// 24 hours of minute averages
+const MINUTE_SAMPLES: usize = 24 * 60;
+/// 7 days of hour averages
+const HOUR_SAMPLES: usize = 7 * 24;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsSample {
+    /// Unix timestamp; start of the bucket for minute and hour averages
+    pub timestamp: u64,
+    pub cpu_percent: f32,
+    pub memory_percent: f32,
+    /// First GPU only; `None` without a readable GPU
+    pub gpu_percent: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsResolution {
+    Second,
+    Minute,
+    Hour,
+}
+
+impl StatsResolution {
+    /// Finest tier that still covers `range_seconds`
+    pub fn for_range(range_seconds: u64) -> Self {
+        if range_seconds <= StatsResolution::Second.retention_seconds() {
+            StatsResolution::Second
+        } else if range_seconds <= StatsResolution::Minute.retention_seconds() {
+            StatsResolution::Minute
+        } else {
+            StatsResolution::Hour
+        }
+    }
+
+    pub fn step_seconds(self) -> u64 {
+        match self {
+            StatsResolution::Second => 1,
+            StatsResolution::Minute => 60,
+            StatsResolution::Hour => 3600,
+        }
+    }
+
+    pub fn retention_seconds(self) -> u64 {
+        let samples = match self {
+            StatsResolution::Second => SECOND_SAMPLES,
+            StatsResolution::Minute => MINUTE_SAMPLES,
+            StatsResolution::Hour => HOUR_SAMPLES,
+        };
+        samples as u64 * self.step_seconds()
+    }
+}
+
+/// Three tiers of samples: raw samples, minute averages and hour averages,
+/// each a ring buffer with a fixed length. A minute or hour is averaged and
+/// moved into its tier once the first sample of the next one arrives.
+/// Nothing is persisted; the SQLite `HistoryStore` covers longer ranges.
+#[derive(Debug, Default)]
+pub struct StatsHistory {
+    seconds: VecDeque<StatsSample>,
+    minutes: VecDeque<StatsSample>,
+    hours: VecDeque<StatsSample>,
+    open_minute: Vec<StatsSample>,
+    open_hour: Vec<StatsSample>,
+}
+
+impl StatsHistory {
+    pub fn push(&mut self, sample: StatsSample) {
+        if let Some(minute) = close_bucket(&mut self.open_minute, sample.timestamp, 60) {
+            if let Some(hour) = close_bucket(&mut self.open_hour, minute.timestamp, 3600) {
+                push_capped(&mut self.hours, hour, HOUR_SAMPLES);
+            }
+            self.open_hour.push(minute.clone());
+            push_capped(&mut self.minutes, minute, MINUTE_SAMPLES);
+        }
+        self.open_minute.push(sample.clone());
+        push_capped(&mut self.seconds, sample, SECOND_SAMPLES);
+    }
+
+    /// Samples of the last `range_seconds` before `now`, oldest first
+    pub fn query(&self, now: u64, range_seconds: u64, resolution: StatsResolution) -> Vec<StatsSample> {
+        let tier = match resolution {
+            StatsResolution::Second => &self.seconds,
+            StatsResolution::Minute => &self.minutes,
+            StatsResolution::Hour => &self.hours,
+        };
+        let from = now.saturating_sub(range_seconds);
+        tier.iter().filter(|s| s.timestamp >= from).cloned().collect()
+    }
+}
+
+/// Average of the open bucket if `timestamp` falls into a later one
+fn close_bucket(open: &mut Vec<StatsSample>, timestamp: u64, width: u64) -> Option<StatsSample> {
+    let start = open.first()?.timestamp / width * width;
+    if timestamp < start + width {
+        return None;
+    }
+
+    let samples = std::mem::take(open);
+    let count = samples.len() as f32;
+    let gpu: Vec<f32> = samples.iter().filter_map(|s| s.gpu_percent).collect();
+    Some(StatsSample {
+        timestamp: start,
+        cpu_percent: samples.iter().map(|s| s.cpu_percent).sum::<f32>() / count,
+        memory_percent: samples.iter().map(|s| s.memory_percent).sum::<f32>() / count,
+        gpu_percent: (!gpu.is_empty()).then(|| gpu.iter().sum::<f32>() / gpu.len() as f32),
+    })
+}
+
+fn push_capped(tier: &mut VecDeque<StatsSample>, sample: StatsSample, capacity: usize) {
+    if tier.len() == capacity {
+        tier.pop_front();
+    }
+    tier.push_back(sample);
+}
@@ -0,0 +1,357 @@
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+use serde::{Deserialize, Serialize};
+use crate::models::config::StorageConfig;
+
+const APP_DIR: &str = "network_manager";
+/// Portable mode is also enabled by a file with this name next to the executable
+const PORTABLE_MARKER: &str = "portable";
+/// Directory overrides live here, since the rest of the config isn't persisted
+const OVERRIDES_FILE: &str = "storage.json";
+/// What lives in the config directory. On macOS and Windows it is the same
+/// directory as the data directory, so a move must only take these along.
+const CONFIG_ENTRIES: &[&str] = &[
+    "acknowledged_issues.json",
+    "bootstrap.json",
+    "config.enc",
+    "export_templates",
+    "port_reservations.json",
+    "process_knowledge.json",
+    "prompt_templates",
+    "tunnels.json",
+];
+const LOG_ENTRIES: &[&str] = &["audit.jsonl"];
+
+static STATE: OnceLock<RwLock<StorageState>> = OnceLock::new();
+
+#[derive(Default)]
+struct StorageState {
+    /// `<exe dir>/portable` in portable mode
+    portable_root: Option<PathBuf>,
+    overrides: StorageConfig,
+}
+
+/// Effective directories, for display in the settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoragePaths {
+    pub portable: bool,
+    pub config_dir: String,
+    pub data_dir: String,
+    pub log_dir: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StorageMigration {
+    /// Files and directories moved to the new locations
+    pub moved: Vec<String>,
+    /// Entries left behind because the target already had them
+    pub skipped: Vec<String>,
+}
+
+/// Resolves where config, data and logs are stored: the OS defaults,
+/// `<exe dir>/portable` with `--portable`, or the directories from the config.
+pub struct Storage;
+
+impl Storage {
+    /// Call once at startup, before anything touches the disk
+    pub fn init(portable: bool) {
+        let portable_root = (portable || portable_marker_exists()).then(portable_root).flatten();
+        let mut state = StorageState {
+            portable_root,
+            overrides: StorageConfig::default(),
+        };
+        state.overrides = load_overrides(&base_config_dir(&state));
+        let _ = STATE.set(RwLock::new(state));
+    }
+
+    pub fn is_portable() -> bool {
+        read_state(|s| s.portable_root.is_some())
+    }
+
+    /// Overrides as stored in `storage.json`
+    pub fn overrides() -> StorageConfig {
+        read_state(|s| s.overrides.clone())
+    }
+
+    pub fn config_dir() -> PathBuf {
+        read_state(config_dir)
+    }
+
+    pub fn data_dir() -> PathBuf {
+        read_state(data_dir)
+    }
+
+    pub fn log_dir() -> PathBuf {
+        read_state(log_dir)
+    }
+
+    pub fn paths() -> StoragePaths {
+        read_state(|s| StoragePaths {
+            portable: s.portable_root.is_some(),
+            config_dir: config_dir(s).display().to_string(),
+            data_dir: data_dir(s).display().to_string(),
+            log_dir: log_dir(s).display().to_string(),
+        })
+    }
+
+    /// Apply new directory overrides and move existing files over. Nothing
+    /// changes if one of the new locations can't be used.
+    pub fn configure(config: &StorageConfig) -> Result<StorageMigration, Box<dyn std::error::Error + Send + Sync>> {
+        let config = normalize(config);
+        let mut guard = state().write().unwrap_or_else(|e| e.into_inner());
+        if guard.overrides == config {
+            return Ok(StorageMigration::default());
+        }
+
+        let next = StorageState {
+            portable_root: guard.portable_root.clone(),
+            overrides: config.clone(),
+        };
+        // Logs first: by default they live inside the data directory
+        let moves = [
+            (log_dir(&guard), log_dir(&next), Category::Logs),
+            (data_dir(&guard), data_dir(&next), Category::Data),
+            (config_dir(&guard), config_dir(&next), Category::Config),
+        ];
+        for (from, to, _) in &moves {
+            if from != to && (to.starts_with(from) || from.starts_with(to)) {
+                return Err(format!(
+                    "{} und {} dürfen nicht ineinander liegen",
+                    from.display(),
+                    to.display()
+                )
+                .into());
+            }
+            std::fs::create_dir_all(to)
+                .map_err(|e| format!("Verzeichnis {} kann nicht angelegt werden: {}", to.display(), e))?;
+        }
+
+        let mut migration = StorageMigration::default();
+        for (from, to, category) in &moves {
+            if from == to {
+                continue;
+            }
+            // A directory shared with another category only gives up this category's entries
+            let shared = moves.iter().any(|(other, _, c)| c != category && other == from);
+            let only = shared.then_some(*category);
+            migrate_dir(from, to, only, &mut migration)?;
+        }
+
+        save_overrides(&base_config_dir(&guard), &config)?;
+        guard.overrides = config;
+        Ok(migration)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Category {
+    Config,
+    Data,
+    Logs,
+}
+
+impl Category {
+    fn owns(self, name: &str) -> bool {
+        match self {
+            Category::Config => CONFIG_ENTRIES.contains(&name),
+            Category::Logs => LOG_ENTRIES.contains(&name),
+            Category::Data => !CONFIG_ENTRIES.contains(&name) && !LOG_ENTRIES.contains(&name),
+        }
+    }
+}
+
+fn state() -> &'static RwLock<StorageState> {
+    STATE.get_or_init(|| RwLock::new(StorageState::default()))
+}
+
+fn read_state<T>(f: impl FnOnce(&StorageState) -> T) -> T {
+    f(&state().read().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Where `storage.json` lives; not affected by the overrides themselves
+fn base_config_dir(state: &StorageState) -> PathBuf {
+    match &state.portable_root {
+        Some(root) => root.join("config"),
+        None => dirs::config_dir().unwrap_or_else(|| PathBuf::from(".")).join(APP_DIR),
+    }
+}
+
+fn config_dir(state: &StorageState) -> PathBuf {
+    match &state.overrides.config_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => base_config_dir(state),
+    }
+}
+
+fn data_dir(state: &StorageState) -> PathBuf {
+    match (&state.overrides.data_dir, &state.portable_root) {
+        (Some(dir), _) => PathBuf::from(dir),
+        (None, Some(root)) => root.join("data"),
+        (None, None) => dirs::data_dir().unwrap_or_else(|| PathBuf::from(".")).join(APP_DIR),
+    }
+}
+
+fn log_dir(state: &StorageState) -> PathBuf {
+    match &state.overrides.log_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => data_dir(state).join("logs"),
+    }
+}
+
+fn portable_root() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    Some(exe.parent()?.join("portable"))
+}
+
+fn portable_marker_exists() -> bool {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(PORTABLE_MARKER).is_file()))
+        .unwrap_or(false)
+}
+
+/// Blank entries mean "use the default"
+fn normalize(config: &StorageConfig) -> StorageConfig {
+    let clean = |dir: &Option<String>| dir.as_deref().map(str::trim).filter(|d| !d.is_empty()).map(String::from);
+    StorageConfig {
+        config_dir: clean(&config.config_dir),
+        data_dir: clean(&config.data_dir),
+        log_dir: clean(&config.log_dir),
+    }
+}
+
+fn load_overrides(dir: &Path) -> StorageConfig {
+    std::fs::read(dir.join(OVERRIDES_FILE))
+        .ok()
+        .and_then(|data| serde_json::from_slice(&data).ok())
+        .map(|config| normalize(&config))
+        .unwrap_or_default()
+}
+
+fn save_overrides(dir: &Path, config: &StorageConfig) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(OVERRIDES_FILE);
+    if *config == StorageConfig::default() {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    std::fs::write(path, serde_json::to_string_pretty(config)?)?;
+    Ok(())
+}
+
+/// Move the entries of `from` into `to`, or only those `only` owns. Entries
+/// the target already has are kept on both sides rather than overwritten.
+fn migrate_dir(
+    from: &Path,
+    to: &Path,
+    only: Option<Category>,
+    migration: &mut StorageMigration,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Ok(entries) = std::fs::read_dir(from) else {
+        return Ok(());
+    };
+    for entry in entries.flatten() {
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+        // Leave the overrides file where it is looked up
+        if entry.file_name() == OVERRIDES_FILE {
+            continue;
+        }
+        if only.is_some_and(|category| !category.owns(&entry.file_name().to_string_lossy())) {
+            continue;
+        }
+        if target.exists() {
+            migration.skipped.push(source.display().to_string());
+            continue;
+        }
+        // rename fails across file systems, e.g. onto a USB stick
+        if std::fs::rename(&source, &target).is_err() {
+            copy_recursive(&source, &target)
+                .map_err(|e| format!("Failed to move {}: {}", source.display(), e))?;
+            if source.is_dir() {
+                std::fs::remove_dir_all(&source)?;
+            } else {
+                std::fs::remove_file(&source)?;
+            }
+        }
+        migration.moved.push(target.display().to_string());
+    }
+    // Only succeeds once everything was moved
+    std::fs::remove_dir(from).ok();
+    Ok(())
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> std::io::Result<()> {
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)?.flatten() {
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        std::fs::copy(from, to).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nm-storage-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn shared_directory_only_gives_up_owned_entries() {
+        let shared = temp_dir("shared");
+        let target = temp_dir("config-target");
+        for name in ["tunnels.json", "history.db", "audit.jsonl", OVERRIDES_FILE] {
+            std::fs::write(shared.join(name), name).unwrap();
+        }
+        std::fs::create_dir(shared.join("prompt_templates")).unwrap();
+
+        let mut migration = StorageMigration::default();
+        migrate_dir(&shared, &target, Some(Category::Config), &mut migration).unwrap();
+
+        assert!(target.join("tunnels.json").exists());
+        assert!(target.join("prompt_templates").is_dir());
+        assert!(shared.join("history.db").exists());
+        assert!(shared.join("audit.jsonl").exists());
+        assert!(shared.join(OVERRIDES_FILE).exists());
+        assert_eq!(migration.moved.len(), 2);
+
+        let _ = std::fs::remove_dir_all(shared);
+        let _ = std::fs::remove_dir_all(target);
+    }
+
+    #[test]
+    fn own_directory_moves_everything() {
+        let from = temp_dir("data-from");
+        let to = temp_dir("data-to");
+        std::fs::write(from.join("history.db"), "").unwrap();
+        std::fs::write(from.join("tunnels.json"), "").unwrap();
+
+        let mut migration = StorageMigration::default();
+        migrate_dir(&from, &to, None, &mut migration).unwrap();
+
+        assert!(to.join("history.db").exists());
+        assert!(to.join("tunnels.json").exists());
+        assert!(!from.exists());
+
+        let _ = std::fs::remove_dir_all(to);
+    }
+
+    #[test]
+    fn data_never_takes_config_or_log_entries() {
+        assert!(Category::Data.owns("history.db"));
+        assert!(!Category::Data.owns("config.enc"));
+        assert!(!Category::Data.owns("audit.jsonl"));
+        assert!(Category::Logs.owns("audit.jsonl"));
+        assert!(!Category::Config.owns("chats"));
+    }
+}
@@ -0,0 +1,240 @@
+// SUID/SGID binaries and Linux file capabilities in the standard bin paths
+
+use std::collections::HashSet;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use crate::services::security_scanner::{SecurityCategory, SecurityIssue, SecuritySeverity};
+
+/// Searched with their direct children; the lib directories also one level
+/// deeper, where helpers like `dbus-daemon-launch-helper` live
+const BIN_DIRS: &[&str] = &["/bin", "/sbin", "/usr/bin", "/usr/sbin", "/usr/local/bin", "/usr/local/sbin", "/opt/homebrew/bin"];
+const LIB_DIRS: &[&str] = &["/usr/lib", "/usr/libexec", "/usr/local/libexec"];
+/// Stop walking after this many entries, `/usr/lib` can be huge
+const MAX_ENTRIES: usize = 50_000;
+
+/// SUID/SGID binaries shipped by common distributions and macOS
+const EXPECTED_SETID: &[&str] = &[
+    "su", "sudo", "sudoedit", "doas", "passwd", "chsh", "chfn", "newgrp", "sg", "gpasswd", "chage", "expiry",
+    "mount", "umount", "fusermount", "fusermount3", "mount.nfs", "mount.cifs", "ping", "ping6", "traceroute",
+    "traceroute6", "traceroute6.iputils", "pkexec", "polkit-agent-helper-1", "dbus-daemon-launch-helper",
+    "ssh-keysign", "ssh-agent", "unix_chkpwd", "pam_timestamp_check", "newuidmap", "newgidmap", "crontab", "at",
+    "atq", "atrm", "batch", "wall", "write", "bsd-write", "login", "quota", "Xorg.wrap", "Xorg", "snap-confine",
+    "chrome-sandbox", "chrome_sandbox", "utempter", "locate", "mlocate", "plocate", "staprun", "pppd", "ksu",
+    "lppasswd", "postdrop", "postqueue", "dotlockfile", "mail-lock", "mail-unlock", "mail-touchlock",
+    "vmware-user-suid-wrapper", "dmcrypt-get-device", "authopen", "security_authtrampoline",
+    "ntfs-3g", "fping", "mtr-packet", "procmail", "exim4", "Xvfb", "usernetctl", "userhelper",
+    "nvidia-modprobe", "firejail", "VBoxHeadless", "VBoxNetAdpCtl", "VBoxNetDHCP", "VBoxNetNAT", "VBoxSDL",
+    "VBoxVolInfo", "VirtualBoxVM", "lockdev", "slock",
+];
+
+/// Binaries that hand out a shell or arbitrary file access when SUID,
+/// see GTFOBins
+const SHELL_ESCAPES: &[&str] = &[
+    "bash", "sh", "dash", "zsh", "ksh", "csh", "tcsh", "fish", "python", "python2", "python3", "perl", "ruby",
+    "php", "node", "lua", "env", "find", "vim", "vi", "nano", "less", "more", "awk", "gawk", "mawk", "sed", "cp",
+    "mv", "tar", "zip", "rsync", "nmap", "tee", "dd", "docker", "bwrap", "cat", "chmod", "chown", "gdb",
+    "strace", "socat", "nc", "ncat", "busybox", "systemctl", "journalctl", "make", "git", "xargs", "ed", "ssh",
+];
+
+/// Capabilities that amount to root or read any file; `all` stands for a
+/// bare `=ep`, which grants every capability
+const DANGEROUS_CAPABILITIES: &[&str] = &[
+    "all", "cap_setuid", "cap_setgid", "cap_sys_admin", "cap_sys_ptrace", "cap_sys_module", "cap_dac_override",
+    "cap_dac_read_search", "cap_chown", "cap_fowner", "cap_sys_rawio", "cap_bpf",
+];
+
+/// File capabilities set by common packages
+const EXPECTED_CAPABILITIES: &[(&str, &[&str])] = &[
+    ("ping", &["cap_net_raw"]),
+    ("arping", &["cap_net_raw"]),
+    ("clockdiff", &["cap_net_raw"]),
+    ("fping", &["cap_net_raw"]),
+    ("mtr-packet", &["cap_net_raw"]),
+    ("traceroute6.iputils", &["cap_net_raw"]),
+    ("dumpcap", &["cap_net_admin", "cap_net_raw"]),
+    ("gst-ptp-helper", &["cap_net_bind_service", "cap_net_admin", "cap_sys_nice"]),
+    ("gnome-keyring-daemon", &["cap_ipc_lock"]),
+    ("kwin_wayland", &["cap_sys_resource", "cap_sys_nice"]),
+    ("newuidmap", &["cap_setuid"]),
+    ("newgidmap", &["cap_setgid"]),
+    ("rtkit-daemon", &["cap_sys_nice"]),
+    ("systemd-detect-virt", &["cap_dac_override", "cap_sys_ptrace"]),
+];
+
+/// Audits the standard bin and lib directories for SUID/SGID files and,
+/// on Linux, file capabilities. Binaries the distributions ship with
+/// these bits are skipped; everything else is reported, with shell-capable
+/// or world-writable files as the most severe.
+pub struct SuidAudit;
+
+impl SuidAudit {
+    /// Blocking: walks the directories and runs `getcap`
+    pub fn findings() -> Vec<SecurityIssue> {
+        let files = candidate_files();
+        let mut issues: Vec<SecurityIssue> = files.iter().filter_map(|path| setid_finding(path)).collect();
+        #[cfg(target_os = "linux")]
+        issues.extend(capability_findings());
+        issues.sort_by(|a, b| a.id.cmp(&b.id));
+        issues
+    }
+}
+
+/// Files below the audited directories; `/bin` and `/usr/bin` are the same
+/// directory on merged-usr systems, so roots are deduplicated
+fn candidate_files() -> Vec<PathBuf> {
+    let mut roots = HashSet::new();
+    let mut files = Vec::new();
+    let dirs = BIN_DIRS.iter().map(|dir| (*dir, 1)).chain(LIB_DIRS.iter().map(|dir| (*dir, 2)));
+    for (dir, depth) in dirs {
+        let Ok(root) = std::fs::canonicalize(dir) else {
+            continue;
+        };
+        if roots.insert(root.clone()) {
+            walk(&root, depth, &mut files);
+        }
+    }
+    files
+}
+
+fn walk(dir: &Path, depth: u32, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if files.len() >= MAX_ENTRIES {
+            return;
+        }
+        // Symlinks point at files that are audited where they live
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_file() {
+            files.push(entry.path());
+        } else if file_type.is_dir() && depth > 1 {
+            walk(&entry.path(), depth - 1, files);
+        }
+    }
+}
+
+fn setid_finding(path: &Path) -> Option<SecurityIssue> {
+    let metadata = std::fs::symlink_metadata(path).ok()?;
+    let mode = metadata.mode();
+    let suid = mode & 0o4000 != 0;
+    let sgid = mode & 0o2000 != 0;
+    if !suid && !sgid {
+        return None;
+    }
+    let name = path.file_name()?.to_string_lossy().to_string();
+    // Versioned interpreters like python3.12 or perl5.36
+    let base = name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let shell_escape = SHELL_ESCAPES.contains(&name.as_str()) || SHELL_ESCAPES.contains(&base);
+    let writable = mode & 0o022 != 0;
+    if EXPECTED_SETID.contains(&name.as_str()) && !shell_escape && !writable {
+        return None;
+    }
+
+    let bits = match (suid, sgid) {
+        (true, true) => "SUID und SGID",
+        (true, false) => "SUID",
+        _ => "SGID",
+    };
+    let (severity, description) = if writable {
+        (SecuritySeverity::Critical, "Die Datei ist für andere beschreibbar; wer sie ersetzt, führt Code mit den Rechten des Eigentümers aus")
+    } else if shell_escape && suid {
+        (SecuritySeverity::Critical, "Das Programm kann eine Shell starten oder beliebige Dateien lesen und schreiben; mit SUID bekommt jeder Benutzer die Rechte des Eigentümers")
+    } else if suid {
+        (SecuritySeverity::Medium, "Das Programm läuft für jeden Aufrufer mit den Rechten seines Eigentümers und gehört nicht zu den üblichen SUID-Programmen")
+    } else {
+        (SecuritySeverity::Low, "Das Programm läuft mit den Rechten seiner Gruppe und gehört nicht zu den üblichen SGID-Programmen")
+    };
+
+    let path_str = path.to_string_lossy().to_string();
+    Some(SecurityIssue {
+        id: format!("setid-{}", path_str),
+        service_id: None,
+        service_name: None,
+        category: SecurityCategory::PrivilegeEscalation,
+        severity,
+        title: format!("{} hat das {}-Bit", path_str, bits),
+        description: description.to_string(),
+        recommendation: format!("Entferne das Bit mit 'sudo chmod u-s,g-s {}', falls es nicht bewusst gesetzt wurde", path_str),
+        port: None,
+        details: Some(format!("Modus {:o}, Eigentümer UID {}, Gruppe GID {}", mode & 0o7777, metadata.uid(), metadata.gid())),
+    })
+}
+
+/// `getcap -r` prints `/usr/bin/ping cap_net_raw=ep` (libcap 2.4x) or
+/// `/usr/bin/ping = cap_net_raw+ep` (older releases)
+#[cfg(target_os = "linux")]
+fn capability_findings() -> Vec<SecurityIssue> {
+    let Some(getcap) = crate::services::cmd::find_in_path("getcap") else {
+        return Vec::new();
+    };
+    let mut roots: Vec<String> = Vec::new();
+    for dir in BIN_DIRS.iter().chain(LIB_DIRS) {
+        if let Ok(root) = std::fs::canonicalize(dir) {
+            let root = root.to_string_lossy().to_string();
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+    }
+    let Ok(output) = crate::services::cmd::command(&getcap.to_string_lossy()).arg("-r").args(&roots).output() else {
+        return Vec::new();
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (path, caps) = line.split_once(' ')?;
+            let caps = caps.trim().trim_start_matches("= ");
+            // `cap_net_admin,cap_net_raw=eip` -> [cap_net_admin, cap_net_raw]
+            let names: Vec<String> = caps
+                .split([' ', '='])
+                .next()?
+                .split(',')
+                .map(|cap| match cap.split('+').next().unwrap_or(cap) {
+                    "" => "all".to_string(),
+                    cap => cap.to_lowercase(),
+                })
+                .collect();
+            capability_finding(path, &names, caps)
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn capability_finding(path: &str, capabilities: &[String], raw: &str) -> Option<SecurityIssue> {
+    let name = Path::new(path).file_name()?.to_string_lossy().to_string();
+    let expected = EXPECTED_CAPABILITIES
+        .iter()
+        .find(|(binary, _)| *binary == name)
+        .map(|(_, caps)| *caps)
+        .unwrap_or_default();
+    let unexpected: Vec<&str> = capabilities
+        .iter()
+        .map(String::as_str)
+        .filter(|cap| !expected.contains(cap))
+        .collect();
+    if unexpected.is_empty() {
+        return None;
+    }
+    let dangerous: Vec<&str> = unexpected.iter().copied().filter(|cap| DANGEROUS_CAPABILITIES.contains(cap)).collect();
+
+    Some(SecurityIssue {
+        id: format!("file-capability-{}", path),
+        service_id: None,
+        service_name: None,
+        category: SecurityCategory::PrivilegeEscalation,
+        severity: if dangerous.is_empty() { SecuritySeverity::Low } else { SecuritySeverity::High },
+        title: format!("{} hat die Capability {}", path, unexpected.join(", ")),
+        description: if dangerous.is_empty() {
+            "Ungewöhnliche Datei-Capability; das Programm bekommt für jeden Aufrufer zusätzliche Rechte".to_string()
+        } else {
+            "Diese Capability reicht aus, um root zu werden oder beliebige Dateien zu lesen".to_string()
+        },
+        recommendation: format!("Entferne die Capability mit 'sudo setcap -r {}', falls sie nicht bewusst gesetzt wurde", path),
+        port: None,
+        details: Some(raw.to_string()),
+    })
+}
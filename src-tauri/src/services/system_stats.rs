@@ -1,6 +1,21 @@
 use serde::{Deserialize, Serialize};
-use std::process::Command;
-use sysinfo::{System, CpuRefreshKind, MemoryRefreshKind, RefreshKind};
+use crate::services::cmd;
+use sysinfo::{
+    Components, System, CpuRefreshKind, Disk, DiskKind, Disks, MemoryRefreshKind, Networks, ProcessRefreshKind,
+    ProcessesToUpdate, RefreshKind, UpdateKind, Users,
+};
+use crate::services::discovery::mock::MockDiscovery;
+#[cfg(target_os = "macos")]
+use crate::services::powermetrics::PowermetricsSampler;
+#[cfg(target_os = "windows")]
+use crate::services::windows_gpu::WindowsGpuMonitor;
+use crate::services::stats_history::{StatsHistory, StatsResolution, StatsSample};
+use crate::services::user_scope::UserScope;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use crate::parsers::gpu::{self, GpuRecord};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuStats {
@@ -28,6 +43,147 @@ pub struct GpuStats {
     pub memory_total_bytes: Option<u64>,
     pub temperature_celsius: Option<f32>,
     pub power_watts: Option<f32>,
+    #[serde(default)]
+    pub frequency_mhz: Option<u32>,
+    /// Which provider reported this GPU
+    #[serde(default)]
+    pub provider: Option<GpuProvider>,
+}
+
+impl From<GpuRecord> for GpuStats {
+    fn from(record: GpuRecord) -> Self {
+        Self {
+            name: record.name,
+            usage_percent: record.usage_percent,
+            memory_used_bytes: record.memory_used_bytes,
+            memory_total_bytes: record.memory_total_bytes,
+            temperature_celsius: record.temperature_celsius,
+            power_watts: record.power_watts,
+            frequency_mhz: record.frequency_mhz,
+            provider: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkInterfaceStats {
+    pub name: String,
+    pub rx_bytes_per_sec: u64,
+    pub tx_bytes_per_sec: u64,
+    /// Since boot (or since the interface appeared)
+    pub total_rx_bytes: u64,
+    pub total_tx_bytes: u64,
+    /// Operational state; only known on Linux
+    pub link_up: Option<bool>,
+    pub mac_address: String,
+    /// Addresses with prefix length, e.g. `192.168.1.20/24`
+    pub ip_addresses: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkStats {
+    /// Sum over all interfaces except loopback
+    pub rx_bytes_per_sec: u64,
+    pub tx_bytes_per_sec: u64,
+    pub interfaces: Vec<NetworkInterfaceStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskStats {
+    /// Device, e.g. `/dev/nvme0n1p2` or `C:`
+    pub name: String,
+    pub mount_point: String,
+    pub file_system: String,
+    /// `ssd`, `hdd` or `unknown`
+    pub kind: String,
+    pub removable: bool,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+    pub usage_percent: f32,
+    pub read_bytes_per_sec: u64,
+    pub write_bytes_per_sec: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemperatureReading {
+    pub label: String,
+    /// `cpu`, `gpu`, `storage` or `other`, guessed from the label
+    pub kind: String,
+    pub celsius: f32,
+    pub max_celsius: Option<f32>,
+    pub critical_celsius: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanReading {
+    pub label: String,
+    pub rpm: u32,
+}
+
+/// Temperature sensors (SMC on macOS, hwmon on Linux, WMI on Windows, all
+/// through sysinfo) and fan speeds, which are only readable from hwmon
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SensorStats {
+    pub temperatures: Vec<TemperatureReading>,
+    pub fans: Vec<FanReading>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+/// Basic facts about the machine, also used as context in LLM prompts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub hostname: Option<String>,
+    /// E.g. `macOS 14.5 Sonoma` or `Linux 24.04 Ubuntu`
+    pub os_version: Option<String>,
+    pub kernel_version: Option<String>,
+    pub arch: String,
+    pub uptime_seconds: u64,
+    /// Unix timestamp
+    pub boot_time: u64,
+    /// Not available on Windows
+    pub load_average: Option<LoadAverage>,
+}
+
+impl HostInfo {
+    pub fn current() -> Self {
+        let load = System::load_average();
+        Self {
+            hostname: System::host_name(),
+            os_version: System::long_os_version(),
+            kernel_version: System::kernel_version(),
+            arch: System::cpu_arch(),
+            uptime_seconds: System::uptime(),
+            boot_time: System::boot_time(),
+            load_average: (!cfg!(windows)).then_some(LoadAverage {
+                one: load.one,
+                five: load.five,
+                fifteen: load.fifteen,
+            }),
+        }
+    }
+
+    /// One line for LLM prompts, e.g.
+    /// `build-box, Linux 24.04 Ubuntu, Kernel 6.8.0, x86_64, up 3d 4h, load 0.52 0.61 0.70`
+    pub fn prompt_context(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        parts.extend(self.hostname.clone());
+        parts.extend(self.os_version.clone());
+        parts.extend(self.kernel_version.as_ref().map(|k| format!("Kernel {}", k)));
+        parts.push(self.arch.clone());
+        let days = self.uptime_seconds / 86_400;
+        let hours = self.uptime_seconds % 86_400 / 3600;
+        parts.push(format!("up {}d {}h", days, hours));
+        if let Some(load) = &self.load_average {
+            parts.push(format!("load {:.2} {:.2} {:.2}", load.one, load.five, load.fifteen));
+        }
+        parts.join(", ")
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,9 +191,39 @@ pub struct SystemStats {
     pub cpu: CpuStats,
     pub memory: MemoryStats,
     pub gpus: Vec<GpuStats>,
+    #[serde(default)]
+    pub network: NetworkStats,
+    /// Mounted filesystems, one entry per device
+    #[serde(default)]
+    pub disks: Vec<DiskStats>,
+    #[serde(default)]
+    pub sensors: SensorStats,
+    #[serde(default)]
+    pub host: HostInfo,
     pub timestamp: u64,
 }
 
+/// Ordering for `SystemMonitor::top_processes`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessSort {
+    Cpu,
+    Memory,
+    /// Per-process throughput is not collected yet
+    Network,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessUsage {
+    pub pid: u32,
+    pub name: String,
+    pub path: Option<String>,
+    pub user: Option<String>,
+    pub cpu_usage: f32,
+    pub memory_bytes: u64,
+    pub memory_percent: f32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum GpuProvider {
@@ -45,12 +231,51 @@ pub enum GpuProvider {
     Apple,
     Nvidia,
     Amd,
+    /// DXGI and the GPU performance counters, any vendor
+    Windows,
     None,
 }
 
+/// One GPU as seen by a provider, for `list_gpu_providers`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuDevice {
+    pub name: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuProviderInfo {
+    pub provider: GpuProvider,
+    /// Hardware or tool for this provider was found
+    pub detected: bool,
+    pub enabled: bool,
+    pub gpus: Vec<GpuDevice>,
+}
+
+/// The sampler reads the GPU only every n-th second; nvidia-smi and friends
+/// are too slow to spawn every second
+const GPU_SAMPLE_EVERY: u64 = 5;
+
+static SHARED: OnceLock<Mutex<SystemMonitor>> = OnceLock::new();
+
 pub struct SystemMonitor {
     system: System,
-    gpu_provider: GpuProvider,
+    /// Enabled providers; several for an eGPU or a hybrid laptop
+    gpu_providers: Vec<GpuProvider>,
+    /// GPU names hidden from the stats
+    disabled_gpus: HashSet<String>,
+    networks: Networks,
+    disks: Disks,
+    components: Components,
+    /// Network and disk throughput is averaged over the time since the
+    /// previous refresh
+    io_refreshed: Instant,
+    history: StatsHistory,
+    /// Carried over between GPU reads of the sampler
+    gpu_percent: Option<f32>,
+    /// Opened on the first read; the counters need the previous collection
+    #[cfg(target_os = "windows")]
+    windows_gpu: Option<WindowsGpuMonitor>,
 }
 
 impl SystemMonitor {
@@ -61,44 +286,149 @@ impl SystemMonitor {
                 .with_memory(MemoryRefreshKind::everything())
         );
 
-        // Auto-detect GPU provider
-        let gpu_provider = Self::detect_gpu_provider();
+        Self {
+            system,
+            gpu_providers: Self::detect_gpu_providers(),
+            disabled_gpus: HashSet::new(),
+            networks: Networks::new_with_refreshed_list(),
+            disks: Disks::new_with_refreshed_list(),
+            components: Components::new_with_refreshed_list(),
+            io_refreshed: Instant::now(),
+            history: StatsHistory::default(),
+            gpu_percent: None,
+            #[cfg(target_os = "windows")]
+            windows_gpu: None,
+        }
+    }
+
+    /// Instance used by the commands and the history sampler, so CPU usage
+    /// is measured between their refreshes and the history survives
+    pub fn shared() -> &'static Mutex<SystemMonitor> {
+        SHARED.get_or_init(|| Mutex::new(SystemMonitor::new()))
+    }
 
-        Self { system, gpu_provider }
+    /// Record a CPU/memory (and every few seconds GPU) sample into the
+    /// shared monitor's history once per second. Idle in mock mode.
+    pub fn start_sampler() {
+        tauri::async_runtime::spawn(async move {
+            let mut tick: u64 = 0;
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                if MockDiscovery::is_enabled() {
+                    continue;
+                }
+                let with_gpu = tick.is_multiple_of(GPU_SAMPLE_EVERY);
+                tick += 1;
+                let _ = tokio::task::spawn_blocking(move || Self::shared().blocking_lock().sample(with_gpu)).await;
+            }
+        });
     }
 
-    pub fn with_gpu_provider(mut self, provider: GpuProvider) -> Self {
-        self.gpu_provider = if provider == GpuProvider::Auto {
-            Self::detect_gpu_provider()
-        } else {
-            provider
+    /// Every provider with hardware behind it. On Windows the counters
+    /// already cover NVIDIA, AMD and Intel, so nothing else is probed there.
+    fn detect_gpu_providers() -> Vec<GpuProvider> {
+        if cfg!(target_os = "windows") {
+            return vec![GpuProvider::Windows];
+        }
+
+        let mut providers = Vec::new();
+        // Apple Silicon
+        if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+            providers.push(GpuProvider::Apple);
+        }
+        // NVIDIA, also an eGPU or the discrete half of a hybrid laptop
+        if cmd::command("nvidia-smi").arg("--version").output().is_ok_and(|o| o.status.success()) {
+            providers.push(GpuProvider::Nvidia);
+        }
+        // AMD (Linux)
+        if cfg!(target_os = "linux") && std::path::Path::new("/opt/rocm/bin/rocm-smi").exists() {
+            providers.push(GpuProvider::Amd);
+        }
+        providers
+    }
+
+    /// Switch to one provider, all detected ones (`Auto`) or none, without
+    /// losing the history. Disabled GPUs stay disabled.
+    pub fn set_gpu_provider(&mut self, provider: GpuProvider) {
+        self.gpu_providers = match provider {
+            GpuProvider::Auto => Self::detect_gpu_providers(),
+            GpuProvider::None => Vec::new(),
+            provider => vec![provider],
         };
-        self
+        self.gpu_percent = None;
     }
 
-    fn detect_gpu_provider() -> GpuProvider {
-        #[cfg(target_os = "macos")]
-        {
-            // Check for Apple Silicon
-            if cfg!(target_arch = "aarch64") {
-                return GpuProvider::Apple;
-            }
+    /// Turn one provider on or off, keeping the others
+    pub fn set_gpu_provider_enabled(&mut self, provider: GpuProvider, enabled: bool) {
+        if matches!(provider, GpuProvider::Auto | GpuProvider::None) {
+            return;
         }
+        self.gpu_providers.retain(|p| *p != provider);
+        if enabled {
+            self.gpu_providers.push(provider);
+        }
+        self.gpu_percent = None;
+    }
 
-        // Check for NVIDIA
-        if Command::new("nvidia-smi").arg("--version").output().is_ok() {
-            return GpuProvider::Nvidia;
+    /// Hide a single GPU by name, e.g. the integrated one of a hybrid laptop
+    pub fn set_gpu_enabled(&mut self, name: &str, enabled: bool) {
+        if enabled {
+            self.disabled_gpus.remove(name);
+        } else {
+            self.disabled_gpus.insert(name.to_string());
         }
+        self.gpu_percent = None;
+    }
 
-        // Check for AMD (Linux)
-        #[cfg(target_os = "linux")]
-        {
-            if std::path::Path::new("/opt/rocm/bin/rocm-smi").exists() {
-                return GpuProvider::Amd;
+    /// Detected and enabled providers with the GPUs each of them sees
+    pub fn gpu_providers(&mut self) -> Vec<GpuProviderInfo> {
+        let detected = Self::detect_gpu_providers();
+        let mut providers = detected.clone();
+        for provider in &self.gpu_providers {
+            if !providers.contains(provider) {
+                providers.push(provider.clone());
             }
         }
 
-        GpuProvider::None
+        providers
+            .into_iter()
+            .map(|provider| GpuProviderInfo {
+                detected: detected.contains(&provider),
+                enabled: self.gpu_providers.contains(&provider),
+                gpus: self
+                    .provider_gpu_stats(&provider)
+                    .into_iter()
+                    .map(|gpu| GpuDevice {
+                        enabled: !self.disabled_gpus.contains(&gpu.name),
+                        name: gpu.name,
+                    })
+                    .collect(),
+                provider,
+            })
+            .collect()
+    }
+
+    /// Append a sample to the history. CPU usage covers the time since the
+    /// previous refresh.
+    pub fn sample(&mut self, with_gpu: bool) {
+        self.refresh();
+        if with_gpu {
+            self.gpu_percent = self.get_gpu_stats().first().and_then(|gpu| gpu.usage_percent);
+        }
+        let sample = StatsSample {
+            timestamp: unix_now(),
+            cpu_percent: self.get_cpu_stats().usage_percent,
+            memory_percent: self.get_memory_stats().usage_percent,
+            gpu_percent: self.gpu_percent,
+        };
+        self.history.push(sample);
+    }
+
+    /// Samples of the last `range_seconds`, by default at the finest
+    /// resolution that covers the range
+    pub fn stats_history(&self, range_seconds: u64, resolution: Option<StatsResolution>) -> Vec<StatsSample> {
+        let resolution = resolution.unwrap_or_else(|| StatsResolution::for_range(range_seconds));
+        self.history.query(unix_now(), range_seconds, resolution)
     }
 
     pub fn refresh(&mut self) {
@@ -116,15 +446,21 @@ impl SystemMonitor {
         let cpu = self.get_cpu_stats();
         let memory = self.get_memory_stats();
         let gpus = self.get_gpu_stats();
+        let elapsed = self.io_refreshed.elapsed().as_secs_f64().max(0.001);
+        self.io_refreshed = Instant::now();
+        let network = self.get_network_stats(elapsed);
+        let disks = self.get_disk_stats(elapsed);
+        let sensors = self.get_sensor_stats();
 
         SystemStats {
             cpu,
             memory,
             gpus,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|d| d.as_secs())
-                .unwrap_or(0),
+            network,
+            disks,
+            sensors,
+            host: HostInfo::current(),
+            timestamp: unix_now(),
         }
     }
 
@@ -165,20 +501,200 @@ impl SystemMonitor {
         }
     }
 
-    fn get_gpu_stats(&self) -> Vec<GpuStats> {
-        match self.gpu_provider {
+    fn get_network_stats(&mut self, elapsed: f64) -> NetworkStats {
+        self.networks.refresh(true);
+        let per_second = |bytes: u64| (bytes as f64 / elapsed) as u64;
+
+        let mut interfaces: Vec<NetworkInterfaceStats> = self
+            .networks
+            .list()
+            .iter()
+            .map(|(name, data)| NetworkInterfaceStats {
+                name: name.clone(),
+                rx_bytes_per_sec: per_second(data.received()),
+                tx_bytes_per_sec: per_second(data.transmitted()),
+                total_rx_bytes: data.total_received(),
+                total_tx_bytes: data.total_transmitted(),
+                link_up: link_state(name),
+                mac_address: data.mac_address().to_string(),
+                ip_addresses: data
+                    .ip_networks()
+                    .iter()
+                    .map(|net| format!("{}/{}", net.addr, net.prefix))
+                    .collect(),
+            })
+            .collect();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let external = interfaces.iter().filter(|i| !is_loopback(&i.name));
+        NetworkStats {
+            rx_bytes_per_sec: external.clone().map(|i| i.rx_bytes_per_sec).sum(),
+            tx_bytes_per_sec: external.map(|i| i.tx_bytes_per_sec).sum(),
+            interfaces,
+        }
+    }
+
+    fn get_disk_stats(&mut self, elapsed: f64) -> Vec<DiskStats> {
+        self.disks.refresh(true);
+        let per_second = |bytes: u64| (bytes as f64 / elapsed) as u64;
+
+        mounted_devices(&self.disks)
+            .map(|disk| {
+                let usage = disk.usage();
+                DiskStats {
+                    read_bytes_per_sec: per_second(usage.read_bytes),
+                    write_bytes_per_sec: per_second(usage.written_bytes),
+                    ..disk_stats(disk)
+                }
+            })
+            .collect()
+    }
+
+    fn get_sensor_stats(&mut self) -> SensorStats {
+        self.components.refresh(true);
+        let mut temperatures: Vec<TemperatureReading> = self
+            .components
+            .list()
+            .iter()
+            .filter_map(|component| {
+                let celsius = component.temperature().filter(|t| t.is_finite() && *t > 0.0)?;
+                Some(TemperatureReading {
+                    label: component.label().to_string(),
+                    kind: sensor_kind(component.label()).to_string(),
+                    celsius,
+                    max_celsius: component.max().filter(|t| t.is_finite() && *t > 0.0),
+                    critical_celsius: component.critical().filter(|t| t.is_finite() && *t > 0.0),
+                })
+            })
+            .collect();
+        temperatures.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.label.cmp(&b.label)));
+
+        SensorStats {
+            temperatures,
+            fans: fan_speeds(),
+        }
+    }
+
+    /// The `n` heaviest processes visible in the current user scope. One
+    /// process refresh; CPU usage is measured since the previous call, so
+    /// the first call reports 0% for everything.
+    pub fn top_processes(&mut self, by: ProcessSort, n: usize) -> Result<Vec<ProcessUsage>, String> {
+        if by == ProcessSort::Network {
+            return Err("Der Netzwerkdurchsatz pro Prozess wird noch nicht erfasst".into());
+        }
+
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_cpu()
+                .with_memory()
+                .with_exe(UpdateKind::OnlyIfNotSet)
+                .with_user(UpdateKind::OnlyIfNotSet),
+        );
+        self.system.refresh_memory();
+        let total_memory = self.system.total_memory();
+        let users = Users::new_with_refreshed_list();
+
+        let mut processes: Vec<ProcessUsage> = self
+            .system
+            .processes()
+            .iter()
+            .map(|(pid, process)| ProcessUsage {
+                pid: pid.as_u32(),
+                name: process.name().to_string_lossy().to_string(),
+                path: process.exe().map(|p| p.to_string_lossy().to_string()),
+                user: process
+                    .user_id()
+                    .and_then(|uid| users.get_user_by_id(uid))
+                    .map(|u| u.name().to_string()),
+                cpu_usage: process.cpu_usage(),
+                memory_bytes: process.memory(),
+                memory_percent: if total_memory > 0 {
+                    (process.memory() as f64 / total_memory as f64 * 100.0) as f32
+                } else {
+                    0.0
+                },
+            })
+            .filter(|p| UserScope::is_visible(p.user.as_deref()))
+            .collect();
+
+        sort_processes(&mut processes, by);
+        processes.truncate(n);
+        Ok(processes)
+    }
+
+    /// Fill level of the mounted filesystems, without I/O rates
+    /// Fresh memory and swap figures without refreshing anything else
+    pub fn memory_usage(&mut self) -> MemoryStats {
+        self.system.refresh_memory();
+        self.get_memory_stats()
+    }
+
+    pub fn disk_usage() -> Vec<DiskStats> {
+        mounted_devices(&Disks::new_with_refreshed_list()).map(disk_stats).collect()
+    }
+
+    fn get_gpu_stats(&mut self) -> Vec<GpuStats> {
+        let mut gpus = Vec::new();
+        for provider in self.gpu_providers.clone() {
+            gpus.extend(
+                self.provider_gpu_stats(&provider)
+                    .into_iter()
+                    .filter(|gpu| !self.disabled_gpus.contains(&gpu.name)),
+            );
+        }
+        gpus
+    }
+
+    fn provider_gpu_stats(&mut self, provider: &GpuProvider) -> Vec<GpuStats> {
+        let gpus = match provider {
             GpuProvider::Apple => self.get_apple_gpu_stats(),
             GpuProvider::Nvidia => self.get_nvidia_gpu_stats(),
             GpuProvider::Amd => self.get_amd_gpu_stats(),
+            GpuProvider::Windows => self.get_windows_gpu_stats(),
             GpuProvider::None | GpuProvider::Auto => vec![],
+        };
+        gpus.into_iter()
+            .map(|gpu| GpuStats {
+                provider: Some(provider.clone()),
+                ..gpu
+            })
+            .collect()
+    }
+
+    #[cfg(target_os = "windows")]
+    fn get_windows_gpu_stats(&mut self) -> Vec<GpuStats> {
+        if self.windows_gpu.is_none() {
+            self.windows_gpu = WindowsGpuMonitor::new();
+        }
+        let Some(monitor) = self.windows_gpu.as_mut() else {
+            return self.get_nvidia_gpu_stats();
+        };
+        let mut gpus = monitor.stats();
+
+        // The counters have no temperature or power; nvidia-smi fills them in by name
+        let nvidia = self.get_nvidia_gpu_stats();
+        for gpu in gpus.iter_mut() {
+            if let Some(extra) = nvidia.iter().find(|n| n.name == gpu.name) {
+                gpu.temperature_celsius = extra.temperature_celsius;
+                gpu.power_watts = extra.power_watts;
+            }
         }
+        gpus
     }
 
+    #[cfg(not(target_os = "windows"))]
+    fn get_windows_gpu_stats(&mut self) -> Vec<GpuStats> {
+        vec![]
+    }
+
+    /// Name from system_profiler; utilization, frequency and power only
+    /// while the `PowermetricsSampler` runs, as powermetrics needs root
     #[cfg(target_os = "macos")]
     fn get_apple_gpu_stats(&self) -> Vec<GpuStats> {
-        // Use powermetrics or ioreg for Apple Silicon GPU stats
-        // This requires sudo for detailed stats, so we provide basic info
-        let output = Command::new("system_profiler")
+        let sample = PowermetricsSampler::latest().unwrap_or_default();
+        let output = cmd::command("system_profiler")
             .args(["SPDisplaysDataType", "-json"])
             .output();
 
@@ -196,22 +712,26 @@ impl SystemMonitor {
 
                             Some(GpuStats {
                                 name,
-                                usage_percent: None, // Requires sudo for powermetrics
+                                usage_percent: sample.usage_percent,
                                 memory_used_bytes: None,
                                 memory_total_bytes: None,
                                 temperature_celsius: None,
-                                power_watts: None,
+                                power_watts: sample.power_watts,
+                                frequency_mhz: sample.frequency_mhz,
+                                provider: None,
                             })
                         }).collect();
                     }
                 }
                 vec![GpuStats {
                     name: "Apple Silicon GPU".to_string(),
-                    usage_percent: None,
+                    usage_percent: sample.usage_percent,
                     memory_used_bytes: None,
                     memory_total_bytes: None,
                     temperature_celsius: None,
-                    power_watts: None,
+                    power_watts: sample.power_watts,
+                    frequency_mhz: sample.frequency_mhz,
+                    provider: None,
                 }]
             }
             _ => vec![],
@@ -224,7 +744,7 @@ impl SystemMonitor {
     }
 
     fn get_nvidia_gpu_stats(&self) -> Vec<GpuStats> {
-        let output = Command::new("nvidia-smi")
+        let output = cmd::command("nvidia-smi")
             .args([
                 "--query-gpu=name,utilization.gpu,memory.used,memory.total,temperature.gpu,power.draw",
                 "--format=csv,noheader,nounits"
@@ -234,21 +754,7 @@ impl SystemMonitor {
         match output {
             Ok(output) if output.status.success() => {
                 let stdout = String::from_utf8_lossy(&output.stdout);
-                stdout.lines().filter_map(|line| {
-                    let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
-                    if parts.len() >= 6 {
-                        Some(GpuStats {
-                            name: parts[0].to_string(),
-                            usage_percent: parts[1].parse().ok(),
-                            memory_used_bytes: parts[2].parse::<u64>().ok().map(|m| m * 1024 * 1024),
-                            memory_total_bytes: parts[3].parse::<u64>().ok().map(|m| m * 1024 * 1024),
-                            temperature_celsius: parts[4].parse().ok(),
-                            power_watts: parts[5].parse().ok(),
-                        })
-                    } else {
-                        None
-                    }
-                }).collect()
+                gpu::parse_nvidia_smi(&stdout).items.into_iter().map(GpuStats::from).collect()
             }
             _ => vec![],
         }
@@ -256,46 +762,139 @@ impl SystemMonitor {
 
     fn get_amd_gpu_stats(&self) -> Vec<GpuStats> {
         // Try rocm-smi for AMD GPUs
-        let output = Command::new("rocm-smi")
+        let output = cmd::command("rocm-smi")
             .args(["--showuse", "--showmeminfo", "vram", "--showtemp", "--json"])
             .output();
 
         match output {
             Ok(output) if output.status.success() => {
                 let stdout = String::from_utf8_lossy(&output.stdout);
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&stdout) {
-                    // Parse AMD GPU info from JSON
-                    let mut gpus = vec![];
-                    if let Some(cards) = json.as_object() {
-                        for (name, info) in cards {
-                            if name.starts_with("card") {
-                                let usage = info.get("GPU use (%)")
-                                    .and_then(|v| v.as_str())
-                                    .and_then(|s| s.parse().ok());
-                                let temp = info.get("Temperature (Sensor edge) (C)")
-                                    .and_then(|v| v.as_str())
-                                    .and_then(|s| s.parse().ok());
-
-                                gpus.push(GpuStats {
-                                    name: format!("AMD GPU {}", name),
-                                    usage_percent: usage,
-                                    memory_used_bytes: None,
-                                    memory_total_bytes: None,
-                                    temperature_celsius: temp,
-                                    power_watts: None,
-                                });
-                            }
-                        }
-                    }
-                    return gpus;
-                }
-                vec![]
+                gpu::parse_rocm_smi(&stdout).items.into_iter().map(GpuStats::from).collect()
             }
             _ => vec![],
         }
     }
 }
 
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+pub fn sort_processes(processes: &mut [ProcessUsage], by: ProcessSort) {
+    match by {
+        ProcessSort::Cpu => processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+        ProcessSort::Memory | ProcessSort::Network => processes.sort_by_key(|p| std::cmp::Reverse(p.memory_bytes)),
+    }
+}
+
+fn sensor_kind(label: &str) -> &'static str {
+    let label = label.to_lowercase();
+    if ["cpu", "core", "package", "tctl", "tdie", "k10temp", "coretemp", "soc"].iter().any(|k| label.contains(k)) {
+        "cpu"
+    } else if ["gpu", "amdgpu", "nouveau", "radeon"].iter().any(|k| label.contains(k)) {
+        "gpu"
+    } else if ["nvme", "ssd", "drive", "disk", "sata"].iter().any(|k| label.contains(k)) {
+        "storage"
+    } else {
+        "other"
+    }
+}
+
+/// Fans from `/sys/class/hwmon/*/fan*_input`, labeled by `fan*_label` or the
+/// chip name
+#[cfg(target_os = "linux")]
+fn fan_speeds() -> Vec<FanReading> {
+    let Ok(chips) = std::fs::read_dir("/sys/class/hwmon") else {
+        return Vec::new();
+    };
+    let mut fans = Vec::new();
+    for chip in chips.flatten() {
+        let path = chip.path();
+        let chip_name = std::fs::read_to_string(path.join("name"))
+            .map(|n| n.trim().to_string())
+            .unwrap_or_else(|_| chip.file_name().to_string_lossy().to_string());
+        let Ok(entries) = std::fs::read_dir(&path) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some(index) = file_name.strip_prefix("fan").and_then(|f| f.strip_suffix("_input")) else {
+                continue;
+            };
+            let Some(rpm) = std::fs::read_to_string(entry.path()).ok().and_then(|v| v.trim().parse().ok()) else {
+                continue;
+            };
+            let label = std::fs::read_to_string(path.join(format!("fan{}_label", index)))
+                .map(|l| l.trim().to_string())
+                .unwrap_or_else(|_| format!("{} fan{}", chip_name, index));
+            fans.push(FanReading { label, rpm });
+        }
+    }
+    fans.sort_by(|a, b| a.label.cmp(&b.label));
+    fans
+}
+
+// The SMC (macOS) and WMI (Windows) fan readings need privileges or vendor
+// drivers, so fans are only reported on Linux
+#[cfg(not(target_os = "linux"))]
+fn fan_speeds() -> Vec<FanReading> {
+    Vec::new()
+}
+
+/// One mount per device; bind mounts and subvolumes repeat it
+fn mounted_devices(disks: &Disks) -> impl Iterator<Item = &Disk> {
+    let mut seen = std::collections::HashSet::new();
+    disks
+        .list()
+        .iter()
+        .filter(move |disk| disk.total_space() > 0 && seen.insert(disk.name().to_os_string()))
+}
+
+fn disk_stats(disk: &Disk) -> DiskStats {
+    let total = disk.total_space();
+    let available = disk.available_space();
+    DiskStats {
+        name: disk.name().to_string_lossy().to_string(),
+        mount_point: disk.mount_point().to_string_lossy().to_string(),
+        file_system: disk.file_system().to_string_lossy().to_string(),
+        kind: match disk.kind() {
+            DiskKind::SSD => "ssd",
+            DiskKind::HDD => "hdd",
+            DiskKind::Unknown(_) => "unknown",
+        }
+        .to_string(),
+        removable: disk.is_removable(),
+        total_bytes: total,
+        available_bytes: available,
+        usage_percent: (total.saturating_sub(available) as f64 / total as f64 * 100.0) as f32,
+        read_bytes_per_sec: 0,
+        write_bytes_per_sec: 0,
+    }
+}
+
+fn is_loopback(name: &str) -> bool {
+    name == "lo" || name.starts_with("lo0") || name.to_lowercase().contains("loopback")
+}
+
+#[cfg(target_os = "linux")]
+fn link_state(name: &str) -> Option<bool> {
+    let state = std::fs::read_to_string(format!("/sys/class/net/{}/operstate", name)).ok()?;
+    match state.trim() {
+        "up" => Some(true),
+        "down" | "lowerlayerdown" | "notpresent" => Some(false),
+        // Loopback and some virtual interfaces report "unknown"
+        _ => None,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn link_state(_name: &str) -> Option<bool> {
+    None
+}
+
 impl Default for SystemMonitor {
     fn default() -> Self {
         Self::new()
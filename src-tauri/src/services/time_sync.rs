@@ -0,0 +1,330 @@
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::services::cmd;
+
+/// Server used when the sync daemon doesn't tell us its own
+const DEFAULT_NTP_SERVER: &str = "pool.ntp.org";
+const NTP_TIMEOUT: Duration = Duration::from_secs(2);
+/// Seconds between 1900-01-01 (NTP epoch) and 1970-01-01
+const NTP_UNIX_OFFSET: f64 = 2_208_988_800.0;
+/// Log lines from different machines stop lining up
+const DRIFT_WARNING_MS: f64 = 1_000.0;
+/// Certificates, Kerberos tickets and TOTP codes start failing
+const DRIFT_CRITICAL_MS: f64 = 60_000.0;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeSyncHealth {
+    Ok,
+    Warning,
+    Critical,
+    /// Neither the daemon nor an NTP server could be queried
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeSyncStatus {
+    pub health: TimeSyncHealth,
+    /// `chrony`, `systemd-timesyncd`, `ntpd`, `timed` or `w32time`
+    pub service: Option<String>,
+    /// Network time sync is switched on
+    pub ntp_enabled: Option<bool>,
+    /// The daemon considers the clock synchronized
+    pub synchronized: Option<bool>,
+    pub server: Option<String>,
+    /// Offset reported by the daemon; positive means the local clock is ahead
+    pub daemon_offset_ms: Option<f64>,
+    /// Offset measured with our own SNTP query against `ntp_server`
+    pub measured_offset_ms: Option<f64>,
+    pub ntp_server: String,
+    pub round_trip_ms: Option<f64>,
+    pub issues: Vec<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// What the platform's sync daemon reports
+#[derive(Debug, Default)]
+struct DaemonStatus {
+    service: Option<String>,
+    ntp_enabled: Option<bool>,
+    synchronized: Option<bool>,
+    server: Option<String>,
+    offset_ms: Option<f64>,
+}
+
+/// Checks chrony/timesyncd/ntpd (or timed/w32time) and measures the clock
+/// offset against an NTP server
+pub struct TimeSyncChecker {
+    server: Option<String>,
+}
+
+impl TimeSyncChecker {
+    pub fn new() -> Self {
+        Self { server: None }
+    }
+
+    /// Measure against this server instead of the daemon's
+    pub fn with_server(mut self, server: Option<String>) -> Self {
+        self.server = server.filter(|s| !s.trim().is_empty());
+        self
+    }
+
+    /// Blocking: runs external tools and waits up to two seconds for NTP
+    pub fn check(&self) -> TimeSyncStatus {
+        let daemon = daemon_status();
+        let ntp_server = self
+            .server
+            .clone()
+            .or_else(|| daemon.server.clone())
+            .unwrap_or_else(|| DEFAULT_NTP_SERVER.to_string());
+
+        let mut issues = Vec::new();
+        let (measured_offset_ms, round_trip_ms) = match sntp_offset(&ntp_server) {
+            Ok((offset, delay)) => (Some(offset), Some(delay)),
+            Err(e) => {
+                issues.push(format!("NTP-Server {} nicht erreichbar: {}", ntp_server, e));
+                (None, None)
+            }
+        };
+
+        if daemon.service.is_none() {
+            issues.push("Kein Zeitsynchronisationsdienst gefunden".to_string());
+        }
+        if daemon.ntp_enabled == Some(false) {
+            issues.push("Netzwerkzeit ist deaktiviert".to_string());
+        } else if daemon.synchronized == Some(false) {
+            issues.push("Die Uhr ist laut Zeitdienst nicht synchronisiert".to_string());
+        }
+
+        let offset = measured_offset_ms.or(daemon.offset_ms);
+        let mut health = match offset {
+            Some(offset) if offset.abs() >= DRIFT_CRITICAL_MS => TimeSyncHealth::Critical,
+            Some(offset) if offset.abs() >= DRIFT_WARNING_MS => TimeSyncHealth::Warning,
+            Some(_) => TimeSyncHealth::Ok,
+            None => TimeSyncHealth::Unknown,
+        };
+        if let Some(offset) = offset.filter(|o| o.abs() >= DRIFT_WARNING_MS) {
+            issues.push(format!(
+                "Die Uhr geht {} {}; TLS-Zertifikate und Log-Zeitstempel können dadurch falsch bewertet werden",
+                format_offset(offset.abs()),
+                if offset > 0.0 { "vor" } else { "nach" }
+            ));
+        }
+        if health == TimeSyncHealth::Ok && (daemon.synchronized == Some(false) || daemon.ntp_enabled == Some(false)) {
+            health = TimeSyncHealth::Warning;
+        }
+
+        TimeSyncStatus {
+            health,
+            service: daemon.service,
+            ntp_enabled: daemon.ntp_enabled,
+            synchronized: daemon.synchronized,
+            server: daemon.server,
+            daemon_offset_ms: daemon.offset_ms,
+            measured_offset_ms,
+            ntp_server,
+            round_trip_ms,
+            issues,
+            checked_at: Utc::now(),
+        }
+    }
+}
+
+impl Default for TimeSyncChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn format_offset(ms: f64) -> String {
+    if ms >= 60_000.0 {
+        format!("{:.1} min", ms / 60_000.0)
+    } else {
+        format!("{:.1} s", ms / 1_000.0)
+    }
+}
+
+/// (offset, round trip) in milliseconds from a single SNTP exchange (RFC 4330).
+/// A positive offset means the local clock is ahead.
+fn sntp_offset(server: &str) -> Result<(f64, f64), Box<dyn std::error::Error + Send + Sync>> {
+    let address = (server, 123)
+        .to_socket_addrs()?
+        .next()
+        .ok_or("keine Adresse gefunden")?;
+    let bind = if address.is_ipv6() { "[::]:0" } else { "0.0.0.0:0" };
+    let socket = UdpSocket::bind(bind)?;
+    socket.set_read_timeout(Some(NTP_TIMEOUT))?;
+    socket.connect(address)?;
+
+    // LI 0, version 4, mode 3 (client)
+    let mut request = [0u8; 48];
+    request[0] = 0x23;
+    let sent = unix_now();
+    request[40..48].copy_from_slice(&to_ntp(sent));
+    socket.send(&request)?;
+
+    let mut response = [0u8; 48];
+    let length = socket.recv(&mut response)?;
+    let received = unix_now();
+    if length < 48 || response[0] & 0x07 != 4 {
+        return Err("ungültige Antwort".into());
+    }
+    // Stratum 0 is a kiss-of-death packet, leap indicator 3 an unsynchronized server
+    if response[1] == 0 || response[0] >> 6 == 3 {
+        return Err("Server ist nicht synchronisiert".into());
+    }
+    // Our transmit timestamp must come back as the originate timestamp
+    if response[24..32] != request[40..48] {
+        return Err("Antwort passt nicht zur Anfrage".into());
+    }
+
+    let server_received = from_ntp(&response[32..40]);
+    let server_sent = from_ntp(&response[40..48]);
+    let offset = ((server_received - sent) + (server_sent - received)) / 2.0;
+    let delay = (received - sent) - (server_sent - server_received);
+    // NTP gives the server's offset from us; report ours from the server
+    Ok((-offset * 1000.0, delay * 1000.0))
+}
+
+fn unix_now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+fn to_ntp(unix_seconds: f64) -> [u8; 8] {
+    let ntp = unix_seconds + NTP_UNIX_OFFSET;
+    let seconds = ntp.trunc() as u32;
+    let fraction = (ntp.fract() * 4_294_967_296.0) as u32;
+    let mut bytes = [0u8; 8];
+    bytes[..4].copy_from_slice(&seconds.to_be_bytes());
+    bytes[4..].copy_from_slice(&fraction.to_be_bytes());
+    bytes
+}
+
+fn from_ntp(bytes: &[u8]) -> f64 {
+    let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64;
+    let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as f64;
+    seconds + fraction / 4_294_967_296.0 - NTP_UNIX_OFFSET
+}
+
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = cmd::command(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Value after the first `key :` or `key=` line, e.g. `Leap status : Normal`
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn field<'a>(output: &'a str, key: &str, separator: char) -> Option<&'a str> {
+    output.lines().find_map(|line| {
+        let (name, value) = line.split_once(separator)?;
+        (name.trim() == key).then(|| value.trim())
+    })
+}
+
+/// `+1.234ms`, `-850us`, `2.5s` -> milliseconds
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_duration_ms(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let (number, factor) = if let Some(n) = value.strip_suffix("ms") {
+        (n, 1.0)
+    } else if let Some(n) = value.strip_suffix("us").or_else(|| value.strip_suffix("µs")) {
+        (n, 0.001)
+    } else if let Some(n) = value.strip_suffix("min") {
+        (n, 60_000.0)
+    } else if let Some(n) = value.strip_suffix('s') {
+        (n, 1_000.0)
+    } else {
+        return None;
+    };
+    number.trim().parse::<f64>().ok().map(|n| n * factor)
+}
+
+#[cfg(target_os = "linux")]
+fn daemon_status() -> DaemonStatus {
+    let mut status = DaemonStatus::default();
+    if let Some(output) = run("timedatectl", &["show"]) {
+        status.ntp_enabled = field(&output, "NTP", '=').map(|v| v == "yes");
+        status.synchronized = field(&output, "NTPSynchronized", '=').map(|v| v == "yes");
+    }
+
+    // `System time     : 0.000012345 seconds fast of NTP time`
+    if let Some(output) = run("chronyc", &["tracking"]) {
+        status.service = Some("chrony".to_string());
+        status.server = field(&output, "Reference ID", ':')
+            .and_then(|v| v.split_once('(').map(|(_, name)| name.trim_end_matches(')').to_string()))
+            .filter(|name| !name.is_empty());
+        status.offset_ms = field(&output, "System time", ':').and_then(|value| {
+            let mut parts = value.split_whitespace();
+            let seconds = parts.next()?.parse::<f64>().ok()?;
+            let sign = if parts.nth(1)? == "fast" { 1.0 } else { -1.0 };
+            Some(sign * seconds * 1000.0)
+        });
+        if let Some(leap) = field(&output, "Leap status", ':') {
+            status.synchronized = Some(leap != "Not synchronised");
+        }
+        return status;
+    }
+
+    // `Server: 185.125.190.57 (ntp.ubuntu.com)` and `Offset: +1.234ms`. Like
+    // ntpq, timesyncd reports the correction, i.e. server minus local time.
+    if let Some(output) = run("timedatectl", &["timesync-status"]) {
+        status.service = Some("systemd-timesyncd".to_string());
+        status.server = field(&output, "Server", ':').map(|v| {
+            v.split_once('(')
+                .map(|(_, name)| name.trim_end_matches(')').to_string())
+                .unwrap_or_else(|| v.to_string())
+        });
+        status.offset_ms = field(&output, "Offset", ':').and_then(parse_duration_ms).map(|o| -o);
+        return status;
+    }
+
+    // The selected peer is marked with `*`; offset is the 9th column, in ms
+    if let Some(output) = run("ntpq", &["-pn"]) {
+        status.service = Some("ntpd".to_string());
+        if let Some(peer) = output.lines().find(|line| line.starts_with('*')) {
+            let columns: Vec<&str> = peer.split_whitespace().collect();
+            status.server = Some(columns[0].trim_start_matches('*').to_string());
+            status.offset_ms = columns.get(8).and_then(|v| v.parse::<f64>().ok()).map(|o| -o);
+            status.synchronized = Some(true);
+        } else {
+            status.synchronized = Some(false);
+        }
+    }
+    status
+}
+
+#[cfg(target_os = "macos")]
+fn daemon_status() -> DaemonStatus {
+    let mut status = DaemonStatus::default();
+    if run("pgrep", &["-x", "timed"]).is_some() {
+        status.service = Some("timed".to_string());
+    }
+    // Set by System Settings > Date & Time
+    status.server = std::fs::read_to_string("/etc/ntp.conf").ok().and_then(|conf| {
+        conf.lines()
+            .find_map(|line| line.trim().strip_prefix("server ").map(|s| s.split_whitespace().next().unwrap_or_default().to_string()))
+    });
+    status
+}
+
+#[cfg(target_os = "windows")]
+fn daemon_status() -> DaemonStatus {
+    let mut status = DaemonStatus::default();
+    // `Source: time.windows.com,0x9` or `Source: Local CMOS Clock`
+    if let Some(output) = run("w32tm", &["/query", "/status"]) {
+        status.service = Some("w32time".to_string());
+        let source = field(&output, "Source", ':').map(|s| s.split(',').next().unwrap_or(s).to_string());
+        let local = source.as_deref().is_some_and(|s| s.contains("CMOS") || s.contains("Free-running"));
+        status.ntp_enabled = Some(!local);
+        status.synchronized = Some(!local);
+        status.server = source.filter(|_| !local);
+    }
+    status
+}
@@ -0,0 +1,480 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tauri::AppHandle;
+use tokio::io::AsyncReadExt;
+use tokio::sync::watch;
+use crate::models::config::{TunnelKind, TunnelSpec};
+use crate::services::monitor::{emit_service_event, ServiceEvent};
+use crate::services::storage::Storage;
+
+/// A forward that survives this long counts as connected. With
+/// `ExitOnForwardFailure` ssh exits well before if the login or bind fails.
+const SETTLE_SECONDS: u64 = 3;
+const MAX_BACKOFF_SECONDS: u64 = 60;
+
+static TUNNELS: OnceLock<Mutex<Registry>> = OnceLock::new();
+/// Distinguishes supervisors of the same tunnel across stop/start
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Default)]
+struct Registry {
+    specs: Vec<TunnelSpec>,
+    runtime: HashMap<String, Runtime>,
+}
+
+struct Runtime {
+    generation: u64,
+    state: TunnelState,
+    pid: Option<u32>,
+    last_error: Option<String>,
+    reconnects: u32,
+    connected_since: Option<DateTime<Utc>>,
+    /// Dropping the sender stops the supervisor and its ssh process
+    stop: Option<watch::Sender<()>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TunnelState {
+    Stopped,
+    Connecting,
+    Connected,
+    /// Waiting to retry after the connection dropped
+    Reconnecting,
+    /// Gave up, see `last_error`
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TunnelStatus {
+    pub spec: TunnelSpec,
+    pub state: TunnelState,
+    /// PID of the `ssh` process
+    pub pid: Option<u32>,
+    /// Last error output of ssh, kept across reconnects
+    pub last_error: Option<String>,
+    pub reconnects: u32,
+    pub connected_since: Option<DateTime<Utc>>,
+}
+
+/// SSH port forwards run by the app. Each running tunnel is an `ssh -N`
+/// process watched by a supervisor task; keepalives make ssh exit when the
+/// connection dies, and the supervisor reconnects with backoff. ssh runs in
+/// batch mode, so the server must accept a key or the agent.
+pub struct TunnelManager;
+
+impl TunnelManager {
+    /// Check a definition before it reaches ssh. Hosts come from the user or
+    /// a config file and must not be read as ssh options.
+    pub fn validate(spec: &TunnelSpec) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if spec.name.trim().is_empty() {
+            return Err("Ein Tunnel braucht einen Namen".into());
+        }
+        if spec.bind_port == 0 || spec.target_port == 0 {
+            return Err("Ports müssen zwischen 1 und 65535 liegen".into());
+        }
+        let hosts = [
+            Some(spec.ssh_host.as_str()),
+            Some(spec.target_host.as_str()),
+            spec.bind_address.as_deref(),
+            spec.jump_host.as_deref(),
+        ];
+        for host in hosts.into_iter().flatten() {
+            // A leading dash would be read as an ssh option
+            if host.is_empty() || host.starts_with('-') || host.chars().any(|c| c.is_whitespace() || c.is_control()) {
+                return Err(format!("Ungültiger Host '{}'", host).into());
+            }
+        }
+        if spec.identity_file.as_deref().is_some_and(|f| f.starts_with('-')) {
+            return Err("Ungültige Schlüsseldatei".into());
+        }
+        Ok(())
+    }
+
+    pub fn specs() -> Vec<TunnelSpec> {
+        registry().lock().unwrap_or_else(|e| e.into_inner()).specs.clone()
+    }
+
+    pub fn list() -> Vec<TunnelStatus> {
+        let registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+        registry.specs.iter().map(|spec| status_of(&registry, spec)).collect()
+    }
+
+    /// Add a tunnel or update the one with the same id. A running tunnel is
+    /// stopped if its definition changed.
+    pub fn save(spec: TunnelSpec) -> Result<TunnelSpec, Box<dyn std::error::Error + Send + Sync>> {
+        Self::validate(&spec)?;
+        let spec = with_id(spec);
+
+        let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+        match registry.specs.iter_mut().find(|s| s.id == spec.id) {
+            Some(existing) if *existing == spec => {}
+            Some(existing) => {
+                *existing = spec.clone();
+                registry.runtime.remove(&spec.id);
+            }
+            None => registry.specs.push(spec.clone()),
+        }
+        persist(&registry.specs);
+        Ok(spec)
+    }
+
+    /// Stop and delete a tunnel. Returns false if it didn't exist.
+    pub fn remove(id: &str) -> bool {
+        let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+        let before = registry.specs.len();
+        registry.specs.retain(|s| s.id != id);
+        registry.runtime.remove(id);
+        let removed = registry.specs.len() != before;
+        if removed {
+            persist(&registry.specs);
+        }
+        removed
+    }
+
+    pub fn start(app: &AppHandle, id: &str) -> Result<TunnelStatus, Box<dyn std::error::Error + Send + Sync>> {
+        let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+        let spec = registry
+            .specs
+            .iter()
+            .find(|s| s.id == id)
+            .cloned()
+            .ok_or_else(|| format!("Tunnel {} nicht gefunden", id))?;
+        if registry.runtime.get(id).is_some_and(|r| r.stop.is_some()) {
+            return Ok(status_of(&registry, &spec));
+        }
+
+        let generation = GENERATION.fetch_add(1, Ordering::Relaxed);
+        let (stop, stopped) = watch::channel(());
+        registry.runtime.insert(
+            id.to_string(),
+            Runtime {
+                generation,
+                state: TunnelState::Connecting,
+                pid: None,
+                last_error: None,
+                reconnects: 0,
+                connected_since: None,
+                stop: Some(stop),
+            },
+        );
+        let status = status_of(&registry, &spec);
+        drop(registry);
+
+        tauri::async_runtime::spawn(supervise(app.clone(), spec, generation, stopped));
+        Ok(status)
+    }
+
+    pub fn stop(id: &str) -> Result<TunnelStatus, Box<dyn std::error::Error + Send + Sync>> {
+        let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+        let spec = registry
+            .specs
+            .iter()
+            .find(|s| s.id == id)
+            .cloned()
+            .ok_or_else(|| format!("Tunnel {} nicht gefunden", id))?;
+        if let Some(runtime) = registry.runtime.get_mut(id) {
+            runtime.stop = None;
+            runtime.state = TunnelState::Stopped;
+            runtime.pid = None;
+            runtime.connected_since = None;
+        }
+        Ok(status_of(&registry, &spec))
+    }
+
+    /// Start the tunnels marked for autostart, once at launch
+    pub fn start_autostart(app: &AppHandle) {
+        for spec in Self::specs().into_iter().filter(|s| s.autostart) {
+            Self::start(app, &spec.id).ok();
+        }
+    }
+
+    /// Kill all ssh processes right away, on app exit when the supervisors
+    /// won't get to run anymore
+    pub fn stop_all() {
+        let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+        let pids: Vec<u32> = registry.runtime.values().filter_map(|r| r.pid).collect();
+        registry.runtime.clear();
+        drop(registry);
+
+        if pids.is_empty() {
+            return;
+        }
+        let pids: Vec<Pid> = pids.into_iter().map(Pid::from_u32).collect();
+        let mut system = System::new();
+        system.refresh_processes(ProcessesToUpdate::Some(&pids), true);
+        for pid in pids {
+            if let Some(process) = system.process(pid) {
+                process.kill();
+            }
+        }
+    }
+}
+
+/// Run ssh until stopped, reconnecting with exponential backoff
+async fn supervise(app: AppHandle, spec: TunnelSpec, generation: u64, mut stopped: watch::Receiver<()>) {
+    let mut attempt: u32 = 0;
+    loop {
+        let mut child = match ssh_command(&spec).spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                update(&app, &spec.id, generation, |r| {
+                    r.state = TunnelState::Failed;
+                    r.last_error = Some(format!("ssh konnte nicht gestartet werden: {}", e));
+                    r.stop = None;
+                });
+                return;
+            }
+        };
+        let pid = child.id();
+        update(&app, &spec.id, generation, |r| r.pid = pid);
+
+        let mut stderr = child.stderr.take();
+        let error_output = tokio::spawn(async move {
+            let mut output = String::new();
+            if let Some(stderr) = stderr.as_mut() {
+                stderr.read_to_string(&mut output).await.ok();
+            }
+            output
+        });
+
+        let settle = tokio::time::sleep(Duration::from_secs(SETTLE_SECONDS));
+        tokio::pin!(settle);
+        let mut connected = false;
+        let exit = loop {
+            tokio::select! {
+                exit = child.wait() => break exit,
+                _ = stopped.changed() => {
+                    let _ = child.kill().await;
+                    return;
+                }
+                _ = &mut settle, if !connected => {
+                    connected = true;
+                    attempt = 0;
+                    update(&app, &spec.id, generation, |r| {
+                        r.state = TunnelState::Connected;
+                        r.connected_since = Some(Utc::now());
+                    });
+                }
+            }
+        };
+
+        let output = error_output.await.unwrap_or_default();
+        let error = output
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(String::from)
+            .unwrap_or_else(|| match exit {
+                Ok(status) => format!("ssh beendet ({})", status),
+                Err(e) => e.to_string(),
+            });
+
+        if !spec.auto_reconnect {
+            update(&app, &spec.id, generation, |r| {
+                r.state = TunnelState::Failed;
+                r.last_error = Some(error);
+                r.pid = None;
+                r.connected_since = None;
+                r.stop = None;
+            });
+            return;
+        }
+
+        attempt += 1;
+        update(&app, &spec.id, generation, |r| {
+            r.state = TunnelState::Reconnecting;
+            r.last_error = Some(error);
+            r.pid = None;
+            r.connected_since = None;
+            r.reconnects += 1;
+        });
+        let delay = Duration::from_secs(2u64.saturating_pow(attempt).min(MAX_BACKOFF_SECONDS));
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = stopped.changed() => return,
+        }
+        update(&app, &spec.id, generation, |r| r.state = TunnelState::Connecting);
+    }
+}
+
+/// Apply `f` to the tunnel's runtime unless a newer start replaced it, and
+/// emit the new status
+fn update(app: &AppHandle, id: &str, generation: u64, f: impl FnOnce(&mut Runtime)) {
+    let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+    let Some(runtime) = registry.runtime.get_mut(id).filter(|r| r.generation == generation) else {
+        return;
+    };
+    f(runtime);
+    let Some(spec) = registry.specs.iter().find(|s| s.id == id) else {
+        return;
+    };
+    let status = status_of(&registry, spec);
+    drop(registry);
+    emit_service_event(app, ServiceEvent::TunnelStateChanged(status));
+}
+
+fn ssh_command(spec: &TunnelSpec) -> tokio::process::Command {
+    let bind = spec.bind_address.as_deref().unwrap_or("localhost");
+    let forward = format!(
+        "{}:{}:{}:{}",
+        bracket_ipv6(bind),
+        spec.bind_port,
+        bracket_ipv6(&spec.target_host),
+        spec.target_port
+    );
+
+    let mut command = tokio::process::Command::new("ssh");
+    command
+        .arg("-N")
+        .args(["-o", "BatchMode=yes"])
+        .args(["-o", "ExitOnForwardFailure=yes"])
+        .args(["-o", "ServerAliveInterval=15"])
+        .args(["-o", "ServerAliveCountMax=3"])
+        .arg(match spec.kind {
+            TunnelKind::Local => "-L",
+            TunnelKind::Remote => "-R",
+        })
+        .arg(forward);
+    if let Some(port) = spec.ssh_port {
+        command.arg("-p").arg(port.to_string());
+    }
+    if let Some(jump_host) = &spec.jump_host {
+        command.arg("-J").arg(jump_host);
+    }
+    if let Some(identity_file) = &spec.identity_file {
+        command.arg("-i").arg(identity_file);
+    }
+    // Everything after `--` is the destination, even if it looks like an option
+    command
+        .arg("--")
+        .arg(&spec.ssh_host)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    command
+}
+
+fn bracket_ipv6(host: &str) -> String {
+    if host.contains(':') && !host.starts_with('[') {
+        format!("[{}]", host)
+    } else {
+        host.to_string()
+    }
+}
+
+fn with_id(mut spec: TunnelSpec) -> TunnelSpec {
+    if spec.id.is_empty() {
+        spec.id = uuid::Uuid::new_v4().to_string();
+    }
+    spec
+}
+
+fn status_of(registry: &Registry, spec: &TunnelSpec) -> TunnelStatus {
+    match registry.runtime.get(&spec.id) {
+        Some(runtime) => TunnelStatus {
+            spec: spec.clone(),
+            state: runtime.state,
+            pid: runtime.pid,
+            last_error: runtime.last_error.clone(),
+            reconnects: runtime.reconnects,
+            connected_since: runtime.connected_since,
+        },
+        None => TunnelStatus {
+            spec: spec.clone(),
+            state: TunnelState::Stopped,
+            pid: None,
+            last_error: None,
+            reconnects: 0,
+            connected_since: None,
+        },
+    }
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    TUNNELS.get_or_init(|| {
+        Mutex::new(Registry {
+            specs: load(),
+            runtime: HashMap::new(),
+        })
+    })
+}
+
+fn path() -> PathBuf {
+    Storage::config_dir().join("tunnels.json")
+}
+
+/// Definitions from disk; a hand-edited file gets the same checks as `save`
+fn load() -> Vec<TunnelSpec> {
+    std::fs::read(path())
+        .ok()
+        .and_then(|data| serde_json::from_slice::<Vec<TunnelSpec>>(&data).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|spec| TunnelManager::validate(spec).is_ok())
+        .collect()
+}
+
+fn persist(specs: &[TunnelSpec]) {
+    let path = path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+    if let Ok(data) = serde_json::to_vec_pretty(specs) {
+        std::fs::write(&path, data).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> TunnelSpec {
+        TunnelSpec {
+            id: "db".to_string(),
+            name: "prod-db".to_string(),
+            kind: TunnelKind::Local,
+            bind_port: 5432,
+            bind_address: None,
+            target_host: "prod-db".to_string(),
+            target_port: 5432,
+            ssh_host: "deploy@bastion".to_string(),
+            ssh_port: None,
+            jump_host: None,
+            identity_file: None,
+            auto_reconnect: true,
+            autostart: false,
+        }
+    }
+
+    #[test]
+    fn accepts_plain_hosts() {
+        assert!(TunnelManager::validate(&spec()).is_ok());
+    }
+
+    #[test]
+    fn rejects_hosts_that_look_like_options() {
+        let ssh_host = TunnelSpec { ssh_host: "-oProxyCommand=touch /tmp/pwned".to_string(), ..spec() };
+        assert!(TunnelManager::validate(&ssh_host).is_err());
+        let jump_host = TunnelSpec { jump_host: Some("-oProxyCommand=id".to_string()), ..spec() };
+        assert!(TunnelManager::validate(&jump_host).is_err());
+        let newline = TunnelSpec { target_host: "db\nProxyCommand id".to_string(), ..spec() };
+        assert!(TunnelManager::validate(&newline).is_err());
+    }
+
+    #[test]
+    fn destination_follows_end_of_options() {
+        let command = ssh_command(&spec());
+        let args: Vec<_> = command.as_std().get_args().collect();
+        assert_eq!(&args[args.len() - 2..], ["--", "deploy@bastion"]);
+    }
+}
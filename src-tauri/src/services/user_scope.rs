@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind, Users};
+use crate::models::audit::{AuditEntry, EventType};
+use crate::models::config::SecurityConfig;
+use crate::models::port::PortInfo;
+use crate::models::service::Service;
+use crate::services::security::AuditLogger;
+
+/// Minutes the "all users" mode stays on, from `privilege_cache_ttl_minutes`
+static ALL_USERS_TTL_MINUTES: AtomicU32 = AtomicU32::new(15);
+static ALL_USERS_UNTIL: OnceLock<Mutex<Option<DateTime<Utc>>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserScopeStatus {
+    pub current_user: String,
+    /// Items of other users are listed too
+    pub all_users: bool,
+    /// When the "all users" mode switches itself off
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Limits service and port listings to the current user's processes, so a
+/// shared workstation doesn't show everybody's process list by default.
+/// Listing all users is an explicit, audited mode that expires on its own.
+/// Items without a known owner (stopped units, sockets we can't attribute)
+/// are always shown.
+pub struct UserScope;
+
+impl UserScope {
+    pub fn configure(config: &SecurityConfig) {
+        ALL_USERS_TTL_MINUTES.store(config.privilege_cache_ttl_minutes.max(1), Ordering::Relaxed);
+    }
+
+    pub fn current_user() -> String {
+        whoami::username()
+    }
+
+    pub fn status() -> UserScopeStatus {
+        let expires_at = active_until();
+        UserScopeStatus {
+            current_user: Self::current_user(),
+            all_users: expires_at.is_some(),
+            expires_at,
+        }
+    }
+
+    pub fn enable_all_users() -> UserScopeStatus {
+        let minutes = ALL_USERS_TTL_MINUTES.load(Ordering::Relaxed);
+        let expires_at = Utc::now() + Duration::minutes(minutes as i64);
+        *all_users_until().lock().unwrap_or_else(|e| e.into_inner()) = Some(expires_at);
+
+        let mut entry = AuditEntry::new(EventType::PrivilegeEscalation, "show_all_users".to_string());
+        entry.details = serde_json::json!({ "expires_at": expires_at, "minutes": minutes });
+        let _ = AuditLogger::new().log(&entry);
+
+        Self::status()
+    }
+
+    pub fn disable_all_users() -> UserScopeStatus {
+        *all_users_until().lock().unwrap_or_else(|e| e.into_inner()) = None;
+        Self::status()
+    }
+
+    /// Whether an item owned by `user` may be listed in the current mode
+    pub fn is_visible(user: Option<&str>) -> bool {
+        active_until().is_some() || Self::is_own(user)
+    }
+
+    /// Owned by the current user or without a known owner, regardless of the mode
+    pub fn is_own(user: Option<&str>) -> bool {
+        match user {
+            Some(user) => same_user(user, &Self::current_user()),
+            None => true,
+        }
+    }
+
+    pub fn retain_services(services: &mut Vec<Service>) {
+        services.retain(|s| Self::is_visible(s.user.as_deref()));
+    }
+
+    pub fn retain_ports(ports: &mut Vec<PortInfo>) {
+        ports.retain(|p| Self::is_visible(p.user.as_deref()));
+    }
+}
+
+/// Account names of all running processes by PID. Only the owner is
+/// refreshed, which is much cheaper than a full process scan.
+pub fn process_owners() -> HashMap<u32, String> {
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_user(UpdateKind::Always),
+    );
+    let users = Users::new_with_refreshed_list();
+
+    system
+        .processes()
+        .iter()
+        .filter_map(|(pid, process)| {
+            let user = users.get_user_by_id(process.user_id()?)?;
+            Some((pid.as_u32(), user.name().to_string()))
+        })
+        .collect()
+}
+
+fn all_users_until() -> &'static Mutex<Option<DateTime<Utc>>> {
+    ALL_USERS_UNTIL.get_or_init(|| Mutex::new(None))
+}
+
+/// End of the "all users" mode, `None` once it expired
+fn active_until() -> Option<DateTime<Utc>> {
+    let mut until = all_users_until().lock().unwrap_or_else(|e| e.into_inner());
+    if until.is_some_and(|t| t <= Utc::now()) {
+        *until = None;
+    }
+    *until
+}
+
+/// Windows reports `DOMAIN\user` for some processes and is case-insensitive
+fn same_user(a: &str, b: &str) -> bool {
+    let strip = |name: &str| name.rsplit('\\').next().unwrap_or(name).to_lowercase();
+    strip(a) == strip(b)
+}
@@ -0,0 +1,116 @@
+use crate::models::config::{WebhookConfig, WebhookEvent};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+const REQUEST_TIMEOUT: u64 = 10;
+
+static HOOKS: OnceLock<Mutex<Vec<WebhookConfig>>> = OnceLock::new();
+
+/// Body of every webhook request
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    event: WebhookEvent,
+    timestamp: DateTime<Utc>,
+    /// Set for `test_webhook` deliveries
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    test: bool,
+    data: &'a serde_json::Value,
+}
+
+/// POSTs service status changes, alerts and security findings to the
+/// configured webhooks. With a secret, the body is signed with HMAC-SHA256
+/// in `X-Network-Manager-Signature: sha256=<hex>`, the same scheme GitHub uses.
+/// Deliveries run in the background and are not retried.
+pub struct WebhookDispatcher;
+
+impl WebhookDispatcher {
+    pub fn configure(hooks: &[WebhookConfig]) {
+        *hooks_lock().lock().unwrap_or_else(|e| e.into_inner()) = hooks.to_vec();
+    }
+
+    /// Send `data` to every enabled webhook subscribed to `event`
+    pub fn dispatch(event: WebhookEvent, data: serde_json::Value) {
+        let targets: Vec<WebhookConfig> = hooks_lock()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|h| h.enabled && (h.events.is_empty() || h.events.contains(&event)))
+            .cloned()
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+
+        tauri::async_runtime::spawn(async move {
+            for hook in targets {
+                let _ = deliver(&hook, event, &data, false).await;
+            }
+        });
+    }
+
+    /// Send a sample event to one webhook and report the outcome
+    pub async fn test(id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let hook = hooks_lock()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|h| h.id == id)
+            .cloned()
+            .ok_or_else(|| format!("Webhook {} nicht gefunden", id))?;
+        let data = serde_json::json!({ "message": "Test von Network Manager" });
+        deliver(&hook, WebhookEvent::ServiceStatus, &data, true).await
+    }
+}
+
+fn hooks_lock() -> &'static Mutex<Vec<WebhookConfig>> {
+    HOOKS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+async fn deliver(
+    hook: &WebhookConfig,
+    event: WebhookEvent,
+    data: &serde_json::Value,
+    test: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let body = serde_json::to_vec(&WebhookPayload {
+        event,
+        timestamp: Utc::now(),
+        test,
+        data,
+    })?;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT))
+        .build()?;
+    let mut request = client
+        .post(&hook.url)
+        .header("Content-Type", "application/json")
+        .header("X-Network-Manager-Event", event_name(event));
+    if let Some(secret) = hook.secret.as_deref().filter(|s| !s.is_empty()) {
+        request = request.header("X-Network-Manager-Signature", format!("sha256={}", sign(secret, &body)));
+    }
+
+    let response = request.body(body).send().await?;
+    if !response.status().is_success() {
+        return Err(format!("Webhook {} antwortete mit {}", hook.name, response.status()).into());
+    }
+    Ok(())
+}
+
+fn event_name(event: WebhookEvent) -> &'static str {
+    match event {
+        WebhookEvent::ServiceStatus => "service_status",
+        WebhookEvent::Alert => "alert",
+        WebhookEvent::SecurityFinding => "security_finding",
+    }
+}
+
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
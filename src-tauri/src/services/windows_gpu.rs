@@ -0,0 +1,158 @@
+// GPU statistics on Windows from DXGI and the GPU performance counters
+
+use std::collections::HashMap;
+use windows::core::{w, PCWSTR};
+use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1, DXGI_ADAPTER_FLAG_SOFTWARE};
+use windows::Win32::System::Performance::{
+    PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterArrayW, PdhOpenQueryW,
+    PDH_CSTATUS_NEW_DATA, PDH_FMT_COUNTERVALUE_ITEM_W, PDH_FMT_DOUBLE, PDH_HCOUNTER, PDH_HQUERY, PDH_MORE_DATA,
+};
+use crate::services::system_stats::GpuStats;
+
+/// A DXGI adapter, keyed like the counter instances
+struct Adapter {
+    name: String,
+    /// `luid_0x00000000_0x0000c5a3`, lowercase
+    luid: String,
+    dedicated_memory: u64,
+}
+
+/// Adapters from DXGI combined with the "GPU Engine" and "GPU Adapter
+/// Memory" counters that Task Manager shows. Works for NVIDIA, AMD and Intel
+/// alike with a WDDM 2 driver. Utilization is a rate between two collections,
+/// so the first read after `new` reports nothing.
+pub struct WindowsGpuMonitor {
+    query: PDH_HQUERY,
+    engine_utilization: PDH_HCOUNTER,
+    dedicated_usage: PDH_HCOUNTER,
+}
+
+// PDH handles are not tied to a thread; the SystemMonitor mutex serializes access
+unsafe impl Send for WindowsGpuMonitor {}
+
+impl WindowsGpuMonitor {
+    /// `None` if the counters are missing (Windows 10 before 1709, no WDDM 2 driver)
+    pub fn new() -> Option<Self> {
+        let mut query = PDH_HQUERY::default();
+        let mut engine_utilization = PDH_HCOUNTER::default();
+        let mut dedicated_usage = PDH_HCOUNTER::default();
+        unsafe {
+            if PdhOpenQueryW(PCWSTR::null(), 0, &mut query) != 0 {
+                return None;
+            }
+            if PdhAddEnglishCounterW(query, w!("\\GPU Engine(*)\\Utilization Percentage"), 0, &mut engine_utilization) != 0
+                || PdhAddEnglishCounterW(query, w!("\\GPU Adapter Memory(*)\\Dedicated Usage"), 0, &mut dedicated_usage) != 0
+            {
+                PdhCloseQuery(query);
+                return None;
+            }
+            PdhCollectQueryData(query);
+        }
+        Some(Self {
+            query,
+            engine_utilization,
+            dedicated_usage,
+        })
+    }
+
+    pub fn stats(&mut self) -> Vec<GpuStats> {
+        let adapters = adapters();
+        unsafe {
+            PdhCollectQueryData(self.query);
+        }
+        // Instances look like `pid_1234_luid_0x00000000_0x0000c5a3_phys_0_eng_0_engtype_3d`
+        let engines = counter_values(self.engine_utilization);
+        let memory = counter_values(self.dedicated_usage);
+
+        adapters
+            .into_iter()
+            .map(|adapter| {
+                // Busiest engine type (3D, copy, video decode, ...) summed over processes
+                let mut by_type: HashMap<&str, f64> = HashMap::new();
+                for (instance, value) in engines.iter().filter(|(instance, _)| instance.contains(&adapter.luid)) {
+                    let engine_type = instance.rsplit("engtype_").next().unwrap_or_default();
+                    *by_type.entry(engine_type).or_default() += value;
+                }
+                let usage = by_type.into_values().reduce(f64::max);
+                let used: Vec<f64> = memory
+                    .iter()
+                    .filter(|(instance, _)| instance.contains(&adapter.luid))
+                    .map(|(_, value)| *value)
+                    .collect();
+
+                GpuStats {
+                    name: adapter.name,
+                    usage_percent: usage.map(|u| u.min(100.0) as f32),
+                    memory_used_bytes: (!used.is_empty()).then(|| used.iter().sum::<f64>() as u64),
+                    memory_total_bytes: (adapter.dedicated_memory > 0).then_some(adapter.dedicated_memory),
+                    temperature_celsius: None,
+                    power_watts: None,
+                    frequency_mhz: None,
+                    provider: None,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Drop for WindowsGpuMonitor {
+    fn drop(&mut self) {
+        unsafe {
+            PdhCloseQuery(self.query);
+        }
+    }
+}
+
+/// Hardware adapters; skips the Microsoft Basic Render Driver
+fn adapters() -> Vec<Adapter> {
+    let Ok(factory) = (unsafe { CreateDXGIFactory1::<IDXGIFactory1>() }) else {
+        return Vec::new();
+    };
+
+    let mut adapters = Vec::new();
+    let mut index = 0;
+    while let Ok(adapter) = unsafe { factory.EnumAdapters1(index) } {
+        index += 1;
+        let Ok(desc) = (unsafe { adapter.GetDesc1() }) else {
+            continue;
+        };
+        if desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 as u32 != 0 {
+            continue;
+        }
+        let len = desc.Description.iter().position(|&c| c == 0).unwrap_or(desc.Description.len());
+        adapters.push(Adapter {
+            name: String::from_utf16_lossy(&desc.Description[..len]),
+            luid: format!("luid_0x{:08x}_0x{:08x}", desc.AdapterLuid.HighPart as u32, desc.AdapterLuid.LowPart),
+            dedicated_memory: desc.DedicatedVideoMemory as u64,
+        });
+    }
+    adapters
+}
+
+/// Lowercased instance names and values of a wildcard counter
+fn counter_values(counter: PDH_HCOUNTER) -> Vec<(String, f64)> {
+    let mut size: u32 = 0;
+    let mut count: u32 = 0;
+    let status = unsafe { PdhGetFormattedCounterArrayW(counter, PDH_FMT_DOUBLE, &mut size, &mut count, None) };
+    if status != PDH_MORE_DATA {
+        return Vec::new();
+    }
+
+    // The items are followed by their name strings in the same buffer
+    let mut buffer = vec![0u64; (size as usize).div_ceil(8).max(1)];
+    let items = buffer.as_mut_ptr() as *mut PDH_FMT_COUNTERVALUE_ITEM_W;
+    let status = unsafe { PdhGetFormattedCounterArrayW(counter, PDH_FMT_DOUBLE, &mut size, &mut count, Some(items)) };
+    if status != 0 {
+        return Vec::new();
+    }
+
+    let items = unsafe { std::slice::from_raw_parts(items, count as usize) };
+    items
+        .iter()
+        .filter(|item| item.FmtValue.CStatus <= PDH_CSTATUS_NEW_DATA)
+        .filter_map(|item| {
+            let name = unsafe { item.szName.to_string() }.ok()?;
+            Some((name.to_lowercase(), unsafe { item.FmtValue.Anonymous.doubleValue }))
+        })
+        .collect()
+}
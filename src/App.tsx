@@ -4,10 +4,13 @@ import { AppLayout } from "./components/layout/AppLayout";
 import { Dashboard } from "./pages/Dashboard";
 import { Services } from "./pages/Services";
 import { Ports } from "./pages/Ports";
+import { Network } from "./pages/Network";
 import { Performance } from "./pages/Performance";
 import { Security } from "./pages/Security";
 import { Logs } from "./pages/Logs";
+import { Chat } from "./pages/Chat";
 import { Settings } from "./pages/Settings";
+import { Setup } from "./pages/Setup";
 import { useUIStore } from "./stores/uiStore";
 import { useRealtime } from "./lib/hooks";
 import "./index.css";
@@ -43,10 +46,13 @@ function App() {
           <Route path="/" element={<Dashboard />} />
           <Route path="/services" element={<Services />} />
           <Route path="/ports" element={<Ports />} />
+          <Route path="/network" element={<Network />} />
           <Route path="/performance" element={<Performance />} />
           <Route path="/security" element={<Security />} />
           <Route path="/logs" element={<Logs />} />
+          <Route path="/chat" element={<Chat />} />
           <Route path="/settings" element={<Settings />} />
+          <Route path="/setup" element={<Setup />} />
         </Route>
       </Routes>
     </BrowserRouter>
@@ -0,0 +1,115 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { History } from "lucide-react";
+import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "../ui/card";
+import { Badge } from "../ui/badge";
+import * as api from "../../lib/tauri/commands";
+import type { RecordedEvent } from "../../lib/tauri/types";
+import type { ServiceEvent } from "../../lib/hooks/useRealtime";
+
+// Events shown on the dashboard; the backend keeps more
+const VISIBLE_EVENTS = 15;
+
+// Full service lists and gauge updates are too noisy for a feed
+const HIDDEN_TYPES: ServiceEvent["type"][] = ["ServicesDiscovered", "ServiceResourceChanged"];
+
+function describe(event: ServiceEvent): string {
+  switch (event.type) {
+    case "ServiceStatusChanged":
+      return `${event.payload.service_id}: ${event.payload.old_status} → ${event.payload.new_status}`;
+    case "ServiceAdded":
+      return `${event.payload.name} appeared`;
+    case "ServiceRemoved":
+      return `${event.payload.service_id} disappeared`;
+    case "ServicePortsChanged":
+      return `${event.payload.service_id} now listens on ${event.payload.ports.map((p) => `:${p}`).join(", ") || "no ports"}`;
+    case "PortOpened":
+      return `Port ${event.payload.port} opened${event.payload.process_name ? ` by ${event.payload.process_name}` : ""}`;
+    case "PortClosed":
+      return `Port ${event.payload.port} closed`;
+    case "ReservedPortTaken":
+      return `Reserved port ${event.payload.port.port} (${event.payload.label}) taken`;
+    case "BaselineDeviation": {
+      const { port, expected_process } = event.payload;
+      const owner = port.process_name ?? "unknown process";
+      return expected_process
+        ? `${owner} took port ${port.port} from ${expected_process}`
+        : `Port ${port.port} opened by ${owner}, not in the baseline`;
+    }
+    case "TunnelStateChanged":
+      return `Tunnel ${event.payload.spec.name} is ${event.payload.state}`;
+    case "ServiceFlapping":
+      return `${event.payload.service_id} restarted ${event.payload.restarts} times in ${event.payload.window_minutes} min`;
+    case "DiskSpaceLow":
+      return `${event.payload.mount_point} is ${event.payload.usage_percent.toFixed(0)}% full`;
+    case "MemoryPressure": {
+      const free = (event.payload.memory.available_bytes / 1024 ** 3).toFixed(1);
+      const top = event.payload.top_consumers[0];
+      return `Memory running low, ${free} GB free${top ? ` (largest: ${top.name})` : ""}`;
+    }
+    case "AlertTriggered":
+      return event.payload.message;
+    default:
+      return event.type;
+  }
+}
+
+export function ActivityCard() {
+  const [events, setEvents] = useState<RecordedEvent<ServiceEvent>[]>([]);
+
+  useEffect(() => {
+    let latest: string | undefined;
+    const catchUp = async () => {
+      const recent = await api.getRecentEvents<ServiceEvent>(latest);
+      if (recent.length === 0) return;
+      latest = recent[recent.length - 1].timestamp;
+      // Overlapping catch-ups can return the same event twice
+      setEvents((current) => [
+        ...recent
+          .filter((e) => !HIDDEN_TYPES.includes(e.event.type) && !current.some((c) => c.id === e.id))
+          .reverse(),
+        ...current,
+      ].slice(0, VISIBLE_EVENTS));
+    };
+
+    // Replay what happened before this window opened, then follow live events
+    catchUp().catch(() => {});
+    const unlisten = listen("service-event", () => {
+      catchUp().catch(() => {});
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <History className="h-5 w-5" />
+          Recent Activity
+        </CardTitle>
+        <CardDescription>Service, port and tunnel events seen by the background monitor</CardDescription>
+      </CardHeader>
+      <CardContent>
+        {events.length === 0 ? (
+          <p className="text-sm text-muted-foreground">Nothing happened yet</p>
+        ) : (
+          <div className="space-y-2">
+            {events.map((recorded) => (
+              <div key={recorded.id} className="flex items-center justify-between rounded-md border p-3">
+                <div>
+                  <p className="text-sm font-medium">{describe(recorded.event)}</p>
+                  <p className="text-xs text-muted-foreground">
+                    {new Date(recorded.timestamp).toLocaleString()}
+                  </p>
+                </div>
+                <Badge variant="outline">{recorded.event.type}</Badge>
+              </div>
+            ))}
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,48 @@
+import { useEffect } from "react";
+import { BellRing } from "lucide-react";
+import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "../ui/card";
+import { Badge } from "../ui/badge";
+import { useAlertStore } from "../../stores/alertStore";
+
+// Alerts shown on the dashboard; the rest stay in the history
+const VISIBLE_ALERTS = 10;
+
+export function AlertsCard() {
+  const { alerts, error, fetchAlerts } = useAlertStore();
+
+  useEffect(() => {
+    fetchAlerts();
+  }, [fetchAlerts]);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <BellRing className="h-5 w-5" />
+          Alerts
+        </CardTitle>
+        <CardDescription>Rules that fired recently, configured in Settings</CardDescription>
+      </CardHeader>
+      <CardContent>
+        {error && <p className="text-sm text-destructive">{error}</p>}
+        {alerts.length === 0 ? (
+          <p className="text-sm text-muted-foreground">No alerts</p>
+        ) : (
+          <div className="space-y-2">
+            {alerts.slice(0, VISIBLE_ALERTS).map((alert) => (
+              <div key={alert.id} className="flex items-center justify-between rounded-md border p-3">
+                <div>
+                  <p className="text-sm font-medium">{alert.message}</p>
+                  <p className="text-xs text-muted-foreground">
+                    {new Date(alert.triggered_at).toLocaleString()}
+                  </p>
+                </div>
+                <Badge variant="warning">{alert.rule_name}</Badge>
+              </div>
+            ))}
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
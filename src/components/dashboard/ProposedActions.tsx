@@ -0,0 +1,135 @@
+import { useState } from "react";
+import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "../ui/card";
+import { Button } from "../ui/button";
+import { Badge } from "../ui/badge";
+import { CheckCircle2, Loader2, Play, Wand2, XCircle } from "lucide-react";
+import { proposeServiceActions, executeProposedActions } from "../../lib/tauri/commands";
+import type { ActionProposal, ProposedActionResult } from "../../lib/tauri/types";
+
+const statusLabels: Record<ProposedActionResult["status"], string> = {
+  executed: "Ausgeführt",
+  failed: "Fehlgeschlagen",
+  dry_run: "Testmodus",
+  simulated: "Simulation",
+};
+
+export function ProposedActions() {
+  const [proposal, setProposal] = useState<ActionProposal | null>(null);
+  const [accepted, setAccepted] = useState<Set<string>>(new Set());
+  const [results, setResults] = useState<Record<string, ProposedActionResult>>({});
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const handlePropose = async () => {
+    setIsLoading(true);
+    setError(null);
+    setResults({});
+    try {
+      const next = await proposeServiceActions();
+      setProposal(next);
+      setAccepted(new Set());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleExecute = async () => {
+    if (!proposal || accepted.size === 0) return;
+    setIsLoading(true);
+    setError(null);
+    try {
+      const executed = await executeProposedActions(proposal.id, [...accepted]);
+      setResults(Object.fromEntries(executed.map((r) => [r.action_id, r])));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const toggle = (id: string) => {
+    setAccepted((current) => {
+      const next = new Set(current);
+      if (next.has(id)) {
+        next.delete(id);
+      } else {
+        next.add(id);
+      }
+      return next;
+    });
+  };
+
+  const executed = Object.keys(results).length > 0;
+
+  return (
+    <Card>
+      <CardHeader className="pb-3">
+        <div className="flex items-center justify-between">
+          <div className="flex items-center gap-2">
+            <Wand2 className="h-5 w-5 text-violet-500" />
+            <CardTitle>KI-Aktionen</CardTitle>
+          </div>
+          <Button variant="outline" size="sm" onClick={handlePropose} disabled={isLoading}>
+            {isLoading && !proposal ? <Loader2 className="mr-2 h-4 w-4 animate-spin" /> : null}
+            Aktionen vorschlagen
+          </Button>
+        </div>
+        <CardDescription>
+          Das Modell schlägt konkrete Aktionen vor; ausgeführt wird nur, was Sie auswählen und bestätigen
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {error && <p className="text-sm text-destructive">{error}</p>}
+
+        {proposal && proposal.actions.length === 0 && (
+          <p className="text-sm text-muted-foreground">Das Modell hat keine Aktionen vorgeschlagen</p>
+        )}
+
+        {proposal?.actions.map((action) => {
+          const result = results[action.id];
+          return (
+            <label key={action.id} className="flex items-start gap-3 rounded-lg border p-3">
+              <input
+                type="checkbox"
+                className="mt-1"
+                checked={accepted.has(action.id)}
+                onChange={() => toggle(action.id)}
+                disabled={isLoading || executed}
+              />
+              <div className="flex-1 min-w-0">
+                <div className="flex items-center gap-2">
+                  <span className="text-sm font-medium">{action.title}</span>
+                  {result && (
+                    <Badge variant={result.status === "failed" ? "destructive" : "outline"} className="text-xs">
+                      {result.status === "failed" ? (
+                        <XCircle className="mr-1 h-3 w-3" />
+                      ) : (
+                        <CheckCircle2 className="mr-1 h-3 w-3" />
+                      )}
+                      {statusLabels[result.status]}
+                    </Badge>
+                  )}
+                </div>
+                {action.reason && <p className="text-sm text-muted-foreground">{action.reason}</p>}
+                {result?.error && <p className="text-xs text-destructive">{result.error}</p>}
+              </div>
+            </label>
+          );
+        })}
+
+        {proposal && proposal.rejected.length > 0 && (
+          <p className="text-xs text-muted-foreground">Verworfen: {proposal.rejected.join("; ")}</p>
+        )}
+
+        {proposal && proposal.actions.length > 0 && !executed && (
+          <Button onClick={handleExecute} disabled={isLoading || accepted.size === 0}>
+            {isLoading ? <Loader2 className="mr-2 h-4 w-4 animate-spin" /> : <Play className="mr-2 h-4 w-4" />}
+            {accepted.size} {accepted.size === 1 ? "Aktion" : "Aktionen"} ausführen
+          </Button>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
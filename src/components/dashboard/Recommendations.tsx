@@ -62,6 +62,7 @@ export function Recommendations({ services }: RecommendationsProps) {
   const [isLoading, setIsLoading] = useState(false);
   const [ollamaAvailable, setOllamaAvailable] = useState(false);
   const [hasLoaded, setHasLoaded] = useState(false);
+  const [error, setError] = useState<string | null>(null);
 
   useEffect(() => {
     checkOllamaStatus().then(setOllamaAvailable);
@@ -71,13 +72,14 @@ export function Recommendations({ services }: RecommendationsProps) {
     if (services.length === 0) return;
 
     setIsLoading(true);
+    setError(null);
     try {
       const result = await getServiceRecommendations(services);
       setRecommendations(result);
-      setHasLoaded(true);
     } catch (err) {
-      console.error("Failed to load recommendations:", err);
+      setError(String(err));
     } finally {
+      setHasLoaded(true);
       setIsLoading(false);
     }
   };
@@ -131,6 +133,12 @@ export function Recommendations({ services }: RecommendationsProps) {
             <Loader2 className="h-6 w-6 animate-spin mr-2" />
             <span>Analysiere Services...</span>
           </div>
+        ) : error ? (
+          <div className="flex flex-col items-center justify-center py-8 text-center text-muted-foreground">
+            <AlertTriangle className="h-10 w-10 mb-3 opacity-50" />
+            <p className="text-sm">Empfehlungen konnten nicht erstellt werden</p>
+            <p className="text-xs mt-1">{error}</p>
+          </div>
         ) : recommendations.length === 0 ? (
           <div className="flex flex-col items-center justify-center py-8 text-center text-muted-foreground">
             <Lightbulb className="h-10 w-10 mb-3 opacity-50" />
@@ -1,8 +1,22 @@
-import { Outlet } from "react-router-dom";
+import { useEffect } from "react";
+import { Outlet, useNavigate } from "react-router-dom";
 import { Sidebar } from "./Sidebar";
 import { TopBar } from "./TopBar";
+import * as api from "../../lib/tauri/commands";
 
 export function AppLayout() {
+  const navigate = useNavigate();
+
+  // Offer the setup assistant until it has been finished or skipped once
+  useEffect(() => {
+    api
+      .getBootstrapStatus()
+      .then((status) => {
+        if (status.first_run) navigate("/setup");
+      })
+      .catch(() => {});
+  }, [navigate]);
+
   return (
     <div className="flex h-screen bg-background">
       {/* Sidebar */}
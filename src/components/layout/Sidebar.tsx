@@ -5,9 +5,11 @@ import {
   LayoutDashboard,
   Server,
   Network,
+  Wifi,
   Cpu,
   Shield,
   FileText,
+  MessageSquare,
   Settings,
   ChevronLeft,
   ChevronRight,
@@ -17,9 +19,11 @@ const navItems = [
   { path: "/", label: "Dashboard", icon: LayoutDashboard },
   { path: "/services", label: "Services", icon: Server },
   { path: "/ports", label: "Ports", icon: Network },
+  { path: "/network", label: "Network", icon: Wifi },
   { path: "/performance", label: "Performance", icon: Cpu },
   { path: "/security", label: "Sicherheit", icon: Shield },
   { path: "/logs", label: "Logs", icon: FileText },
+  { path: "/chat", label: "Assistant", icon: MessageSquare },
   { path: "/settings", label: "Settings", icon: Settings },
 ];
 
@@ -0,0 +1,132 @@
+import { useEffect, useState } from "react";
+import { AlertTriangle, Camera, Trash2 } from "lucide-react";
+import { Card, CardContent, CardHeader, CardTitle } from "../ui/card";
+import { Button } from "../ui/button";
+import { Badge } from "../ui/badge";
+import { usePortStore } from "../../stores/portStore";
+import * as api from "../../lib/tauri/commands";
+import type { BaselineListener, ListenerBaseline, PortInfo } from "../../lib/tauri/types";
+
+/** UDP ports from here on are mostly ephemeral client sockets; skipped as in the backend */
+const UDP_EPHEMERAL_START = 32768;
+
+const sameListener = (listener: BaselineListener, port: PortInfo) =>
+  listener.port === port.port &&
+  listener.protocol === port.protocol &&
+  listener.family === port.family &&
+  listener.bind_address === port.bind_address;
+
+/** Same rule as the backend: new listeners and known ones with another owner deviate */
+function deviation(baseline: ListenerBaseline, port: PortInfo): { expected: string | null } | null {
+  if (port.protocol === "udp" && port.port >= UDP_EPHEMERAL_START) return null;
+  const recorded = baseline.listeners.filter((listener) => sameListener(listener, port));
+  if (recorded.length === 0) return { expected: null };
+  const known = recorded.some(
+    (listener) =>
+      !listener.process_name ||
+      !port.process_name ||
+      listener.process_name.toLowerCase() === port.process_name.toLowerCase()
+  );
+  return known ? null : { expected: recorded.find((listener) => listener.process_name)?.process_name ?? null };
+}
+
+export function BaselineCard() {
+  const { ports } = usePortStore();
+  const [baseline, setBaseline] = useState<ListenerBaseline | null>(null);
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    api.getPortBaseline().then(setBaseline).catch(() => {});
+  }, []);
+
+  const run = async (action: () => Promise<void>) => {
+    setIsLoading(true);
+    setError(null);
+    try {
+      await action();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleRecord = () =>
+    run(async () => {
+      setBaseline(await api.recordPortBaseline());
+    });
+
+  const handleClear = () =>
+    run(async () => {
+      await api.clearPortBaseline();
+      setBaseline(null);
+    });
+
+  const deviations = baseline
+    ? ports.flatMap((port) => {
+        const found = deviation(baseline, port);
+        return found ? [{ port, expected: found.expected }] : [];
+      })
+    : [];
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="text-lg">Listener Baseline</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex flex-wrap items-center gap-2">
+          <Button variant="outline" onClick={handleRecord} disabled={isLoading}>
+            <Camera className="mr-2 h-4 w-4" />
+            {baseline ? "Record Again" : "Record Baseline"}
+          </Button>
+          {baseline && (
+            <Button variant="ghost" onClick={handleClear} disabled={isLoading}>
+              <Trash2 className="mr-2 h-4 w-4" />
+              Clear
+            </Button>
+          )}
+          {baseline && (
+            <span className="text-sm text-muted-foreground">
+              {baseline.listeners.length} listeners recorded {new Date(baseline.recorded_at).toLocaleString()}
+            </span>
+          )}
+        </div>
+        {error && <p className="text-sm text-destructive">{error}</p>}
+
+        {!baseline ? (
+          <p className="text-sm text-muted-foreground">
+            Record the listeners of a known-good state. Security scans and the port watch then report every new
+            listener and every port taken over by another process.
+          </p>
+        ) : deviations.length === 0 ? (
+          <p className="text-sm text-muted-foreground">All current listeners match the baseline</p>
+        ) : (
+          <div className="space-y-2">
+            {deviations.map(({ port, expected }) => (
+              <div
+                key={`${port.protocol}-${port.family}-${port.bind_address}-${port.port}`}
+                className="flex items-center gap-2 rounded-md border border-amber-500/50 p-3"
+              >
+                <AlertTriangle className="h-4 w-4 shrink-0 text-amber-500" />
+                <span className="font-mono text-sm">
+                  {port.bind_address}:{port.port}/{port.protocol}
+                </span>
+                <span className="text-sm">
+                  {port.process_name ?? "unknown process"}
+                  {port.pid !== null && ` (PID ${port.pid})`}
+                </span>
+                {expected ? (
+                  <Badge variant="outline">expected {expected}</Badge>
+                ) : (
+                  <Badge variant="outline">new</Badge>
+                )}
+              </div>
+            ))}
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
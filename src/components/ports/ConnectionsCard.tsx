@@ -0,0 +1,111 @@
+import { useEffect, useState } from "react";
+import { RefreshCw } from "lucide-react";
+import { Card, CardContent, CardHeader, CardTitle } from "../ui/card";
+import { Button } from "../ui/button";
+import { Badge } from "../ui/badge";
+import * as api from "../../lib/tauri/commands";
+import type { Connection } from "../../lib/tauri/types";
+
+const inputClass =
+  "h-10 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring";
+
+function endpoint(address: string, port: number): string {
+  return address.includes(":") ? `[${address}]:${port}` : `${address}:${port}`;
+}
+
+export function ConnectionsCard() {
+  const [connections, setConnections] = useState<Connection[]>([]);
+  const [pid, setPid] = useState("");
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = async () => {
+    setIsLoading(true);
+    setError(null);
+    try {
+      setConnections(await api.getConnections(pid ? Number(pid) : undefined));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    refresh();
+  }, []);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="text-lg">Active Connections</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex flex-wrap gap-2">
+          <input
+            type="number"
+            placeholder="PID (all processes)"
+            value={pid}
+            onChange={(e) => setPid(e.target.value)}
+            onKeyDown={(e) => e.key === "Enter" && refresh()}
+            className={`${inputClass} w-48`}
+          />
+          <Button variant="outline" onClick={refresh} disabled={isLoading}>
+            <RefreshCw className={`mr-2 h-4 w-4 ${isLoading ? "animate-spin" : ""}`} />
+            Refresh
+          </Button>
+        </div>
+        {error && <p className="text-sm text-destructive">{error}</p>}
+
+        {connections.length === 0 ? (
+          <p className="text-sm text-muted-foreground">
+            {pid ? `Process ${pid} has no open connections` : "No open connections"}
+          </p>
+        ) : (
+          <div className="max-h-96 overflow-auto">
+            <table className="w-full">
+              <thead>
+                <tr className="border-b text-left text-sm text-muted-foreground">
+                  <th className="pb-3 font-medium">Process</th>
+                  <th className="pb-3 font-medium">PID</th>
+                  <th className="pb-3 font-medium">Local</th>
+                  <th className="pb-3 font-medium">Remote</th>
+                  <th className="pb-3 font-medium">State</th>
+                </tr>
+              </thead>
+              <tbody>
+                {connections.map((connection) => (
+                  <tr
+                    key={`${connection.protocol}-${connection.local_address}-${connection.local_port}-${connection.remote_address}-${connection.remote_port}`}
+                    className="border-b last:border-0"
+                  >
+                    <td className="py-2 text-sm">
+                      {connection.process_name ?? <span className="text-muted-foreground">Unknown</span>}
+                    </td>
+                    <td className="py-2 font-mono text-sm">{connection.pid ?? "-"}</td>
+                    <td className="py-2 font-mono text-sm">
+                      {endpoint(connection.local_address, connection.local_port)}
+                    </td>
+                    <td className="py-2 font-mono text-sm">
+                      {endpoint(connection.remote_address, connection.remote_port)}
+                      {connection.remote_service && (
+                        <span className="ml-1 font-sans text-xs text-muted-foreground">
+                          {connection.remote_service}
+                        </span>
+                      )}
+                    </td>
+                    <td className="py-2">
+                      <Badge variant={connection.state === "ESTABLISHED" ? "secondary" : "outline"}>
+                        {connection.protocol.toUpperCase()} {connection.state}
+                      </Badge>
+                    </td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
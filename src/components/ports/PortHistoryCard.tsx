@@ -0,0 +1,160 @@
+import { useState } from "react";
+import { GitCompare, History } from "lucide-react";
+import { Card, CardContent, CardHeader, CardTitle } from "../ui/card";
+import { Button } from "../ui/button";
+import { Badge } from "../ui/badge";
+import * as api from "../../lib/tauri/commands";
+import type { PortHistoryDiff, PortInfo, PortOccupancy } from "../../lib/tauri/types";
+
+const ranges = [
+  { hours: 1, label: "Last hour" },
+  { hours: 24, label: "Last 24 hours" },
+  { hours: 24 * 7, label: "Last 7 days" },
+];
+
+const inputClass =
+  "h-10 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring";
+
+function hoursAgo(hours: number): string {
+  return new Date(Date.now() - hours * 3600 * 1000).toISOString();
+}
+
+function formatTime(value: string): string {
+  return new Date(value).toLocaleString();
+}
+
+function describeListener(port: PortInfo): string {
+  const owner = port.process_name ? `${port.process_name}${port.pid ? ` (PID ${port.pid})` : ""}` : "unknown process";
+  const name = port.service_name ? ` (${port.service_name})` : "";
+  return `${owner} on ${port.bind_address}:${port.port}/${port.protocol}${name}`;
+}
+
+export function PortHistoryCard() {
+  const [port, setPort] = useState("");
+  const [hours, setHours] = useState(24);
+  const [timeline, setTimeline] = useState<PortOccupancy[] | null>(null);
+  const [diff, setDiff] = useState<PortHistoryDiff | null>(null);
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const run = async (action: () => Promise<void>) => {
+    setIsLoading(true);
+    setError(null);
+    try {
+      await action();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleLookup = () =>
+    run(async () => {
+      setDiff(null);
+      setTimeline(await api.getPortHistory(Number(port), hoursAgo(hours)));
+    });
+
+  const handleDiff = () =>
+    run(async () => {
+      setTimeline(null);
+      setDiff(await api.diffPortUsage(hoursAgo(hours)));
+    });
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="text-lg">Port History</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex flex-wrap gap-2">
+          <input
+            type="number"
+            placeholder="Port (8080)"
+            value={port}
+            onChange={(e) => setPort(e.target.value)}
+            className={`${inputClass} w-32`}
+          />
+          <select value={hours} onChange={(e) => setHours(Number(e.target.value))} className={`${inputClass} w-40`}>
+            {ranges.map((range) => (
+              <option key={range.hours} value={range.hours}>
+                {range.label}
+              </option>
+            ))}
+          </select>
+          <Button variant="outline" onClick={handleLookup} disabled={!port || isLoading}>
+            <History className="mr-2 h-4 w-4" />
+            Who Listened
+          </Button>
+          <Button variant="outline" onClick={handleDiff} disabled={isLoading}>
+            <GitCompare className="mr-2 h-4 w-4" />
+            What Changed
+          </Button>
+        </div>
+        {error && <p className="text-sm text-destructive">{error}</p>}
+
+        {!timeline && !diff && !error && (
+          <p className="text-sm text-muted-foreground">
+            The port table is recorded every minute while the app runs, so intermittent conflicts can be traced
+            afterwards.
+          </p>
+        )}
+
+        {timeline &&
+          (timeline.length === 0 ? (
+            <p className="text-sm text-muted-foreground">Nothing listened on port {port} in this period</p>
+          ) : (
+            <div className="space-y-2">
+              {timeline.map((occupancy) => (
+                <div
+                  key={`${occupancy.first_seen}-${occupancy.port.bind_address}-${occupancy.port.pid}`}
+                  className="rounded-md border p-3"
+                >
+                  <p className="font-mono text-sm">{describeListener(occupancy.port)}</p>
+                  <p className="text-xs text-muted-foreground">
+                    {formatTime(occupancy.first_seen)} –{" "}
+                    {occupancy.ended_at ? formatTime(occupancy.ended_at) : `still open at ${formatTime(occupancy.last_seen)}`}
+                  </p>
+                </div>
+              ))}
+            </div>
+          ))}
+
+        {diff && (
+          <div className="space-y-3">
+            <p className="text-xs text-muted-foreground">
+              {formatTime(diff.from)} → {formatTime(diff.to)}
+            </p>
+            {diff.opened.length + diff.closed.length + diff.owner_changed.length === 0 && (
+              <p className="text-sm text-muted-foreground">No listeners changed</p>
+            )}
+            {diff.opened.map((p) => (
+              <div key={`opened-${p.protocol}-${p.bind_address}-${p.port}`} className="flex items-center gap-2 text-sm">
+                <Badge>Opened</Badge>
+                <span className="font-mono">{describeListener(p)}</span>
+              </div>
+            ))}
+            {diff.closed.map((p) => (
+              <div key={`closed-${p.protocol}-${p.bind_address}-${p.port}`} className="flex items-center gap-2 text-sm">
+                <Badge variant="outline">Closed</Badge>
+                <span className="font-mono">{describeListener(p)}</span>
+              </div>
+            ))}
+            {diff.owner_changed.map((change) => (
+              <div
+                key={`changed-${change.after.protocol}-${change.after.bind_address}-${change.after.port}`}
+                className="flex items-center gap-2 text-sm"
+              >
+                <Badge variant="secondary">New owner</Badge>
+                <span className="font-mono">
+                  {describeListener(change.before)} → {change.after.process_name ?? "unknown process"}
+                  {change.after.pid ? ` (PID ${change.after.pid})` : ""}
+                </span>
+              </div>
+            ))}
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
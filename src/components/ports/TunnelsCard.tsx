@@ -0,0 +1,179 @@
+import { useState } from "react";
+import { Play, Plus, Square, Trash2 } from "lucide-react";
+import { Card, CardContent, CardHeader, CardTitle } from "../ui/card";
+import { Button } from "../ui/button";
+import { Badge } from "../ui/badge";
+import { usePortStore } from "../../stores/portStore";
+import * as api from "../../lib/tauri/commands";
+import type { TunnelKind, TunnelSpec, TunnelState } from "../../lib/tauri/types";
+
+const stateVariants: Record<TunnelState, "default" | "secondary" | "destructive" | "outline"> = {
+  connected: "default",
+  connecting: "secondary",
+  reconnecting: "secondary",
+  failed: "destructive",
+  stopped: "outline",
+};
+
+const inputClass =
+  "h-10 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring";
+
+function describeForward(spec: TunnelSpec): string {
+  const bind = `${spec.bind_address ?? "localhost"}:${spec.bind_port}`;
+  const target = `${spec.target_host}:${spec.target_port}`;
+  const via = spec.jump_host ? `${spec.jump_host} → ${spec.ssh_host}` : spec.ssh_host;
+  return spec.kind === "local" ? `${bind} → ${target} via ${via}` : `${via}:${spec.bind_port} → ${target}`;
+}
+
+export function TunnelsCard() {
+  const { tunnels, fetchTunnels, startTunnel, stopTunnel, removeTunnel } = usePortStore();
+  const [kind, setKind] = useState<TunnelKind>("local");
+  const [name, setName] = useState("");
+  const [bindPort, setBindPort] = useState("");
+  const [target, setTarget] = useState("");
+  const [sshHost, setSshHost] = useState("");
+  const [jumpHost, setJumpHost] = useState("");
+  const [formError, setFormError] = useState<string | null>(null);
+
+  const handleAdd = async () => {
+    setFormError(null);
+    const [targetHost, targetPort] = target.includes(":")
+      ? [target.slice(0, target.lastIndexOf(":")), Number(target.slice(target.lastIndexOf(":") + 1))]
+      : ["localhost", Number(target)];
+    try {
+      await api.saveTunnel({
+        id: "",
+        name: name.trim(),
+        kind,
+        bind_port: Number(bindPort),
+        bind_address: null,
+        target_host: targetHost,
+        target_port: targetPort,
+        ssh_host: sshHost.trim(),
+        ssh_port: null,
+        jump_host: jumpHost.trim() || null,
+        identity_file: null,
+        auto_reconnect: true,
+        autostart: false,
+      });
+      await fetchTunnels();
+      setName("");
+      setBindPort("");
+      setTarget("");
+      setSshHost("");
+      setJumpHost("");
+    } catch (err) {
+      setFormError(String(err));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="text-lg">SSH Tunnels</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex flex-wrap gap-2">
+          <select
+            value={kind}
+            onChange={(e) => setKind(e.target.value as TunnelKind)}
+            className={`${inputClass} w-28`}
+            title="Local forwards open a port here, remote forwards a port on the SSH server"
+          >
+            <option value="local">Local</option>
+            <option value="remote">Remote</option>
+          </select>
+          <input
+            type="text"
+            placeholder="Name (prod db)"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+            className={`${inputClass} w-40`}
+          />
+          <input
+            type="number"
+            placeholder="Port"
+            value={bindPort}
+            onChange={(e) => setBindPort(e.target.value)}
+            className={`${inputClass} w-24`}
+          />
+          <input
+            type="text"
+            placeholder="Target (prod-db:5432)"
+            value={target}
+            onChange={(e) => setTarget(e.target.value)}
+            className={`${inputClass} w-48`}
+          />
+          <input
+            type="text"
+            placeholder="SSH host (user@server)"
+            value={sshHost}
+            onChange={(e) => setSshHost(e.target.value)}
+            className={`${inputClass} w-48`}
+          />
+          <input
+            type="text"
+            placeholder="Bastion (optional)"
+            value={jumpHost}
+            onChange={(e) => setJumpHost(e.target.value)}
+            className={`${inputClass} w-44`}
+          />
+          <Button
+            variant="outline"
+            onClick={handleAdd}
+            disabled={!name.trim() || !bindPort || !target.trim() || !sshHost.trim()}
+          >
+            <Plus className="mr-2 h-4 w-4" />
+            Add
+          </Button>
+        </div>
+        {formError && <p className="text-sm text-destructive">{formError}</p>}
+        {tunnels.length === 0 ? (
+          <p className="text-sm text-muted-foreground">
+            Tunnels run ssh in the background and reconnect when the connection drops. The server must accept
+            your key or SSH agent.
+          </p>
+        ) : (
+          <div className="space-y-2">
+            {tunnels.map((tunnel) => {
+              const running = tunnel.state !== "stopped" && tunnel.state !== "failed";
+              return (
+                <div key={tunnel.spec.id} className="flex items-center justify-between rounded-md border p-3">
+                  <div className="min-w-0">
+                    <p className="flex items-center gap-2 font-medium">
+                      {tunnel.spec.name}
+                      <Badge variant={stateVariants[tunnel.state]}>{tunnel.state}</Badge>
+                      {tunnel.reconnects > 0 && (
+                        <span className="text-xs text-muted-foreground">{tunnel.reconnects} reconnects</span>
+                      )}
+                    </p>
+                    <p className="font-mono text-xs text-muted-foreground">{describeForward(tunnel.spec)}</p>
+                    {tunnel.last_error && tunnel.state !== "connected" && (
+                      <p className="text-xs text-destructive">{tunnel.last_error}</p>
+                    )}
+                  </div>
+                  <div className="flex gap-1">
+                    {running ? (
+                      <Button variant="ghost" size="sm" onClick={() => stopTunnel(tunnel.spec.id)}>
+                        <Square className="mr-1 h-4 w-4" />
+                        Stop
+                      </Button>
+                    ) : (
+                      <Button variant="ghost" size="sm" onClick={() => startTunnel(tunnel.spec.id)}>
+                        <Play className="mr-1 h-4 w-4" />
+                        Start
+                      </Button>
+                    )}
+                    <Button variant="ghost" size="sm" onClick={() => removeTunnel(tunnel.spec.id)} title="Remove">
+                      <Trash2 className="h-4 w-4" />
+                    </Button>
+                  </div>
+                </div>
+              );
+            })}
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,103 @@
+import { useState } from "react";
+import { Loader2, Network, X } from "lucide-react";
+import { Button } from "../ui/button";
+import { getServiceDependenciesRuntime } from "../../lib/tauri/commands";
+import type { DependencyKind, Service, ServiceRuntimeDependencies } from "../../lib/tauri/types";
+
+interface ServiceDependenciesButtonProps {
+  service: Service;
+}
+
+const kindLabels: Record<DependencyKind, string> = {
+  local_service: "Dienst",
+  local_process: "Lokal",
+  lan: "LAN",
+  internet: "Internet",
+};
+
+export function ServiceDependenciesButton({ service }: ServiceDependenciesButtonProps) {
+  const [isOpen, setIsOpen] = useState(false);
+  const [result, setResult] = useState<ServiceRuntimeDependencies | null>(null);
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleClick = async () => {
+    if (isOpen) {
+      setIsOpen(false);
+      return;
+    }
+
+    setIsOpen(true);
+    setIsLoading(true);
+    setError(null);
+
+    try {
+      setResult(await getServiceDependenciesRuntime(service.id));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  return (
+    <div className="relative inline-block">
+      <Button
+        variant="ghost"
+        size="icon"
+        className="h-6 w-6"
+        onClick={handleClick}
+        title="Womit spricht dieser Dienst?"
+      >
+        <Network className="h-3.5 w-3.5" />
+      </Button>
+
+      {isOpen && (
+        <div className="absolute z-50 w-96 rounded-lg border bg-popover p-4 text-popover-foreground shadow-lg left-0 top-full mt-1">
+          <div className="flex items-start justify-between gap-2 mb-2">
+            <h4 className="font-semibold text-sm">Verbindungen von {service.name}</h4>
+            <Button
+              variant="ghost"
+              size="icon"
+              className="h-5 w-5 -mr-2 -mt-1"
+              onClick={() => setIsOpen(false)}
+            >
+              <X className="h-3 w-3" />
+            </Button>
+          </div>
+
+          {isLoading ? (
+            <div className="flex items-center gap-2 text-sm text-muted-foreground py-2">
+              <Loader2 className="h-4 w-4 animate-spin" />
+              <span>Lese Verbindungen...</span>
+            </div>
+          ) : error ? (
+            <div className="text-sm text-destructive">{error}</div>
+          ) : result && result.dependencies.length === 0 ? (
+            <p className="text-sm text-muted-foreground">Keine ausgehenden Verbindungen</p>
+          ) : result ? (
+            <ul className="space-y-1 text-sm">
+              {result.dependencies.map((dependency) => (
+                <li
+                  key={`${dependency.address}-${dependency.port}`}
+                  className="flex items-center justify-between gap-2"
+                  title={`${dependency.address}:${dependency.port}`}
+                >
+                  <span className="truncate">
+                    {dependency.name}
+                    <span className="text-muted-foreground font-mono">:{dependency.port}</span>
+                  </span>
+                  <span className="shrink-0 text-xs text-muted-foreground">
+                    {kindLabels[dependency.kind]}
+                    {dependency.country && ` · ${dependency.country}`}
+                    {dependency.connections > 1 && ` · ${dependency.connections}×`}
+                  </span>
+                </li>
+              ))}
+            </ul>
+          ) : null}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -2,7 +2,21 @@ import { useState } from "react";
 import { Info, Loader2, X } from "lucide-react";
 import { Button } from "../ui/button";
 import { explainProcess } from "../../lib/tauri/commands";
-import type { Service } from "../../lib/tauri/types";
+import type { HashVerdict, ProcessSafety, Service } from "../../lib/tauri/types";
+
+const reputationText: Record<HashVerdict["status"], { label: string; className: string }> = {
+  malicious: { label: "Als Schadsoftware bekannt", className: "text-red-500" },
+  suspicious: { label: "Verdächtig", className: "text-orange-500" },
+  clean: { label: "Keine Funde", className: "text-green-500" },
+  unknown: { label: "Hash nicht bekannt", className: "text-muted-foreground" },
+};
+
+const safetyText: Record<ProcessSafety, { label: string; className: string }> = {
+  essential: { label: "Systemkritisch – nicht beenden", className: "text-red-500" },
+  important: { label: "Wichtig – Beenden schaltet Funktionen ab", className: "text-orange-500" },
+  optional: { label: "Kann beendet werden", className: "text-green-500" },
+  unknown: { label: "Sicherheit unbekannt", className: "text-muted-foreground" },
+};
 
 interface ServiceInfoButtonProps {
   service: Service;
@@ -12,6 +26,8 @@ interface ServiceInfoButtonProps {
 export function ServiceInfoButton({ service, size = "sm" }: ServiceInfoButtonProps) {
   const [isOpen, setIsOpen] = useState(false);
   const [explanation, setExplanation] = useState<string | null>(null);
+  const [reputation, setReputation] = useState<HashVerdict | null>(null);
+  const [safety, setSafety] = useState<ProcessSafety | null>(null);
   const [isLoading, setIsLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
 
@@ -31,7 +47,9 @@ export function ServiceInfoButton({ service, size = "sm" }: ServiceInfoButtonPro
         service.path,
         service.description
       );
-      setExplanation(result);
+      setExplanation(result.explanation);
+      setReputation(result.reputation);
+      setSafety(result.safety);
     } catch (err) {
       setError(err instanceof Error ? err.message : "Fehler beim Laden der Erklärung");
     } finally {
@@ -78,6 +96,25 @@ export function ServiceInfoButton({ service, size = "sm" }: ServiceInfoButtonPro
             </p>
           ) : null}
 
+          {safety && !isLoading && !error && (
+            <p className={`text-xs mt-2 ${safetyText[safety].className}`}>{safetyText[safety].label}</p>
+          )}
+
+          {reputation && (
+            <p className="text-xs mt-3">
+              <span className={reputationText[reputation.status].className}>
+                {reputationText[reputation.status].label}
+              </span>
+              <span className="text-muted-foreground">
+                {" "}({reputation.provider === "virustotal" ? "VirusTotal" : "MalwareBazaar"}
+                {reputation.detections !== null && reputation.engines !== null
+                  ? `, ${reputation.detections}/${reputation.engines} Engines`
+                  : ""}
+                {reputation.label ? `, ${reputation.label}` : ""})
+              </span>
+            </p>
+          )}
+
           {service.path && (
             <p className="text-xs text-muted-foreground mt-3 pt-2 border-t font-mono truncate">
               {service.path}
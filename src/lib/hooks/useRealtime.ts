@@ -2,7 +2,8 @@ import { useEffect, useCallback } from "react";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { useServiceStore } from "../../stores/serviceStore";
 import { usePortStore } from "../../stores/portStore";
-import type { Service } from "../tauri/types";
+import { useAlertStore } from "../../stores/alertStore";
+import type { Alert, BaselineDeviation, DiskStats, MemoryPressure, PortInfo, Service, TunnelStatus } from "../tauri/types";
 
 // Event types matching the Rust enum
 interface ServicesDiscoveredEvent {
@@ -39,12 +40,90 @@ interface ServicePortsChangedEvent {
   };
 }
 
-type ServiceEvent =
+interface PortOpenedEvent {
+  type: "PortOpened";
+  payload: PortInfo;
+}
+
+interface PortClosedEvent {
+  type: "PortClosed";
+  payload: PortInfo;
+}
+
+interface ReservedPortTakenEvent {
+  type: "ReservedPortTaken";
+  payload: {
+    port: PortInfo;
+    label: string;
+    owner: string | null;
+  };
+}
+
+interface BaselineDeviationEvent {
+  type: "BaselineDeviation";
+  payload: BaselineDeviation;
+}
+
+interface TunnelStateChangedEvent {
+  type: "TunnelStateChanged";
+  payload: TunnelStatus;
+}
+
+interface ServiceFlappingEvent {
+  type: "ServiceFlapping";
+  payload: {
+    service_id: string;
+    restarts: number;
+    window_minutes: number;
+  };
+}
+
+interface ServiceResourceChangedEvent {
+  type: "ServiceResourceChanged";
+  payload: {
+    service_id: string;
+    cpu: number | null;
+    memory: number | null;
+  };
+}
+
+interface DiskSpaceLowEvent {
+  type: "DiskSpaceLow";
+  payload: DiskStats;
+}
+
+interface MemoryPressureEvent {
+  type: "MemoryPressure";
+  payload: MemoryPressure;
+}
+
+interface AlertTriggeredEvent {
+  type: "AlertTriggered";
+  payload: Alert;
+}
+
+export type ServiceEvent =
   | ServicesDiscoveredEvent
   | ServiceStatusChangedEvent
   | ServiceAddedEvent
   | ServiceRemovedEvent
-  | ServicePortsChangedEvent;
+  | ServicePortsChangedEvent
+  | PortOpenedEvent
+  | PortClosedEvent
+  | ReservedPortTakenEvent
+  | BaselineDeviationEvent
+  | TunnelStateChangedEvent
+  | ServiceFlappingEvent
+  | ServiceResourceChangedEvent
+  | DiskSpaceLowEvent
+  | MemoryPressureEvent
+  | AlertTriggeredEvent;
+
+const samePort = (a: PortInfo, b: PortInfo) =>
+  a.port === b.port &&
+  a.protocol === b.protocol &&
+  a.family === b.family &&
+  a.bind_address === b.bind_address;
 
 /**
  * Hook that subscribes to real-time service events from the backend
@@ -105,6 +184,74 @@ export function useRealtime() {
           // Also refresh port usage when ports change
           portStore.fetchPortUsage();
           break;
+
+        case "PortOpened":
+          // Port watch found a new listener
+          usePortStore.setState((state) => ({
+            ports: [...state.ports.filter((port) => !samePort(port, event.payload)), event.payload],
+          }));
+          break;
+
+        case "PortClosed":
+          usePortStore.setState((state) => ({
+            ports: state.ports.filter((port) => !samePort(port, event.payload)),
+          }));
+          break;
+
+        case "ReservedPortTaken":
+          // The backend already shows a notification; make sure the table has the squatter
+          usePortStore.setState((state) => ({
+            ports: [...state.ports.filter((port) => !samePort(port, event.payload.port)), event.payload.port],
+          }));
+          break;
+
+        case "BaselineDeviation":
+          usePortStore.setState((state) => ({
+            ports: [...state.ports.filter((port) => !samePort(port, event.payload.port)), event.payload.port],
+          }));
+          break;
+
+        case "TunnelStateChanged":
+          usePortStore.setState((state) => ({
+            tunnels: state.tunnels.map((tunnel) =>
+              tunnel.spec.id === event.payload.spec.id ? event.payload : tunnel
+            ),
+          }));
+          // A connected local forward adds a listener, a dropped one removes it
+          if (event.payload.spec.kind === "local") {
+            portStore.fetchPortUsage();
+          }
+          break;
+
+        case "ServiceFlapping":
+          useServiceStore.setState((state) => ({
+            services: state.services.map((service) =>
+              service.id === event.payload.service_id
+                ? { ...service, flapping: true, recent_restarts: event.payload.restarts }
+                : service
+            ),
+          }));
+          break;
+
+        case "ServiceResourceChanged":
+          // Gauges follow the monitor without polling discover_services
+          useServiceStore.setState((state) => ({
+            services: state.services.map((service) =>
+              service.id === event.payload.service_id
+                ? { ...service, cpu_usage: event.payload.cpu, memory_bytes: event.payload.memory }
+                : service
+            ),
+          }));
+          break;
+
+        case "DiskSpaceLow":
+        case "MemoryPressure":
+          // The backend already shows a notification; the Performance page polls the stats
+          break;
+
+        case "AlertTriggered":
+          useAlertStore.getState().addAlert(event.payload);
+          break;
       }
     },
     [portStore]
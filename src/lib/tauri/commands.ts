@@ -1,14 +1,30 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
-  Service, PortInfo, Config, AuditEntry, ServiceRecommendation,
-  SystemStats, GpuProvider, SecurityScanResult
+  Service, DiscoveryResult, UserScopeStatus, ActionOutcome, CustomServiceSpec, UnitOverrideDiff, ServiceDependencies, ServiceRuntimeDependencies, PortInfo, PortMapping, PortCollision, ParseReport, SocketRecord, IpcEndpoint, ResolverStatus, PortReservation, ListenerBaseline, DockerImage, DockerVolume, DockerNetwork, Config, StoragePaths, AuditEntry, AuditAnswer, EndpointHealth, LlmQueueStatus, LlmDiagnostics, TaskModels, LogAnalysisResult, LogClusterReport, StoredAnalysis, ChatSession, ChatSessionMessage, ChatSessionSummary, ServiceRecommendation,
+  SystemStats, GpuProvider, SecurityScanResult, DigestReport, CleanupPlan, CleanupReport, ActionProposal, ProposedActionResult,
+  SessionChange, SessionRestoreReport, TlsInspection, TimeSyncStatus, DiskHealthReport, FirewallStatus, PairingInfo, RemoteViewerStatus,
+  NetworkDiscovery, SsdpDiscovery, TunnelSpec, TunnelStatus, ExportKind, ExportFormat, ExportResult, ReportFormat,
+  BootstrapStatus, BaselineSummary, PortSnapshot, PortOccupancy, PortHistoryDiff, Connection,
+  NmapStatus, SystemPoint, ServicePoint, PortCountPoint, Alert, RecordedEvent, HostInfo,
+  ProcessSort, ProcessUsage, StatsResolution, StatsSample, PowermetricsStatus,
+  GpuProviderInfo, SecurityScanSummary, SecurityScanDiff, AcknowledgedIssue, ProcessExplanation,
+  ComplianceProfile, ComplianceProfileInfo, ComplianceReport, SecurityAnalysis, RemediationPlan, PromptKind, PromptTemplate, ProcessKnowledgeEntry
 } from "./types";
 
 // Service commands
-export async function discoverServices(): Promise<Service[]> {
+export async function discoverServices(): Promise<DiscoveryResult> {
   return invoke("discover_services");
 }
 
+export async function getUserScope(): Promise<UserScopeStatus> {
+  return invoke("get_user_scope");
+}
+
+/** Enabling is audited and expires after the privilege cache TTL */
+export async function setAllUsersMode(enabled: boolean): Promise<UserScopeStatus> {
+  return invoke("set_all_users_mode", { enabled });
+}
+
 export async function getServiceDetails(serviceId: string): Promise<Service | null> {
   return invoke("get_service_details", { serviceId });
 }
@@ -17,16 +33,21 @@ export async function startService(serviceId: string): Promise<void> {
   return invoke("start_service", { serviceId });
 }
 
-export async function stopService(serviceId: string): Promise<void> {
-  return invoke("stop_service", { serviceId });
+export async function stopService(serviceId: string, confirmationToken?: string): Promise<ActionOutcome> {
+  return invoke("stop_service", { serviceId, confirmationToken: confirmationToken ?? null });
 }
 
 export async function restartService(serviceId: string): Promise<void> {
   return invoke("restart_service", { serviceId });
 }
 
-export async function killProcess(pid: number): Promise<void> {
-  return invoke("kill_process", { pid });
+export async function killProcess(pid: number, confirmationToken?: string): Promise<ActionOutcome> {
+  return invoke("kill_process", { pid, confirmationToken: confirmationToken ?? null });
+}
+
+/** Stop the service or process listening on `port` */
+export async function freePort(port: number, confirmationToken?: string): Promise<ActionOutcome> {
+  return invoke("free_port", { port, confirmationToken: confirmationToken ?? null });
 }
 
 export async function enableServiceAutostart(
@@ -43,9 +64,117 @@ export async function disableServiceAutostart(
   return invoke("disable_service_autostart", { serviceId, serviceType });
 }
 
+export async function registerCustomService(spec: CustomServiceSpec): Promise<string> {
+  return invoke("register_custom_service", { spec });
+}
+
+export async function getUnitFile(unit: string): Promise<string> {
+  return invoke("get_unit_file", { unit });
+}
+
+export async function getUnitOverride(unit: string): Promise<string> {
+  return invoke("get_unit_override", { unit });
+}
+
+export async function previewUnitOverride(unit: string, content: string): Promise<UnitOverrideDiff> {
+  return invoke("preview_unit_override", { unit, content });
+}
+
+export async function writeUnitOverride(
+  unit: string,
+  content: string,
+  expectedPrevious: string
+): Promise<UnitOverrideDiff> {
+  return invoke("write_unit_override", { unit, content, expectedPrevious });
+}
+
+export async function systemdDaemonReload(): Promise<void> {
+  return invoke("systemd_daemon_reload");
+}
+
+export async function getWindowsServiceDependencies(serviceId: string): Promise<ServiceDependencies> {
+  return invoke("get_windows_service_dependencies", { serviceId });
+}
+
+/** Established outbound connections of a running service, resolved to names */
+export async function getServiceDependenciesRuntime(serviceId: string): Promise<ServiceRuntimeDependencies> {
+  return invoke("get_service_dependencies_runtime", { serviceId });
+}
+
+/** Stops the service and its running dependents; the preview lists the dependents */
+export async function stopServiceWithDependents(serviceId: string, confirmationToken?: string): Promise<ActionOutcome> {
+  return invoke("stop_service_with_dependents", { serviceId, confirmationToken: confirmationToken ?? null });
+}
+
+export async function getSessionChanges(): Promise<SessionChange[]> {
+  return invoke("get_session_changes");
+}
+
+/** Restart services and re-enable autostart changed by the app in this session */
+export async function restoreSessionState(): Promise<SessionRestoreReport> {
+  return invoke("restore_session_state");
+}
+
+export async function dockerRun(
+  image: string,
+  name: string | null,
+  ports: PortMapping[],
+  env?: Record<string, string>,
+  volumes?: string[]
+): Promise<string> {
+  return invoke("docker_run", { image, name, ports, env: env ?? null, volumes: volumes ?? null });
+}
+
+/** Host ports of a stopped container and/or planned mappings that are already taken */
+export async function checkPortCollisions(serviceId?: string, ports?: PortMapping[]): Promise<PortCollision[]> {
+  return invoke("check_port_collisions", { serviceId: serviceId ?? null, ports: ports ?? null });
+}
+
+// Docker commands
+export async function listImages(danglingOnly?: boolean): Promise<DockerImage[]> {
+  return invoke("list_images", { danglingOnly: danglingOnly ?? null });
+}
+
+/** Emits `image-pull-progress` events (ImagePullProgress) while pulling */
+export async function pullImage(image: string): Promise<void> {
+  return invoke("pull_image", { image });
+}
+
+export async function removeImage(image: string, force?: boolean): Promise<string[]> {
+  return invoke("remove_image", { image, force: force ?? null });
+}
+
+export async function listDockerVolumes(): Promise<DockerVolume[]> {
+  return invoke("list_docker_volumes");
+}
+
+export async function listDockerNetworks(): Promise<DockerNetwork[]> {
+  return invoke("list_docker_networks");
+}
+
 // Port commands
-export async function scanPorts(start: number, end: number): Promise<PortInfo[]> {
-  return invoke("scan_ports", { start, end });
+/**
+ * Scans localhost, or `target` (IP, hostname or CIDR range like "192.168.1.0/24").
+ * `ports` replaces the start..end range. Emits `port-scan-progress` events (ScanProgress).
+ */
+export async function scanPorts(
+  start: number,
+  end: number,
+  fingerprint?: boolean,
+  target?: string,
+  ports?: number[]
+): Promise<PortInfo[]> {
+  return invoke("scan_ports", {
+    start,
+    end,
+    fingerprint: fingerprint ?? null,
+    target: target ?? null,
+    ports: ports ?? null,
+  });
+}
+
+export async function getNmapStatus(): Promise<NmapStatus> {
+  return invoke("get_nmap_status");
 }
 
 export async function getPortUsage(): Promise<PortInfo[]> {
@@ -56,6 +185,92 @@ export async function findFreePorts(count: number): Promise<number[]> {
   return invoke("find_free_ports", { count });
 }
 
+export async function getSocketReport(): Promise<ParseReport<SocketRecord>> {
+  return invoke("get_socket_report");
+}
+
+export async function getIpcEndpoints(): Promise<ParseReport<IpcEndpoint>> {
+  return invoke("get_ipc_endpoints");
+}
+
+export async function getConnections(pid?: number): Promise<Connection[]> {
+  return invoke("get_connections", { pid: pid ?? null });
+}
+
+export async function getResolverStatus(): Promise<ResolverStatus> {
+  return invoke("get_resolver_status");
+}
+
+export async function reservePort(port: number, label: string, owner?: string): Promise<PortReservation> {
+  return invoke("reserve_port", { port, label, owner: owner ?? null });
+}
+
+export async function releasePort(port: number): Promise<void> {
+  return invoke("release_port", { port });
+}
+
+export async function listPortReservations(): Promise<PortReservation[]> {
+  return invoke("list_port_reservations");
+}
+
+/** Snapshot the current listeners; scans and the port watch report deviations from it */
+export async function recordPortBaseline(): Promise<ListenerBaseline> {
+  return invoke("record_port_baseline");
+}
+
+export async function getPortBaseline(): Promise<ListenerBaseline | null> {
+  return invoke("get_port_baseline");
+}
+
+export async function clearPortBaseline(): Promise<void> {
+  return invoke("clear_port_baseline");
+}
+
+/** Times are ISO 8601 strings */
+export async function getPortsAt(time: string): Promise<PortSnapshot> {
+  return invoke("get_ports_at", { time });
+}
+
+/** Defaults to the last 24 hours */
+export async function getPortHistory(port: number, from?: string, to?: string): Promise<PortOccupancy[]> {
+  return invoke("get_port_history", { port, from: from ?? null, to: to ?? null });
+}
+
+export async function diffPortUsage(from: string, to?: string): Promise<PortHistoryDiff> {
+  return invoke("diff_port_usage", { from, to: to ?? null });
+}
+
+// Monitoring history commands
+export async function getSystemHistory(from?: string, to?: string): Promise<SystemPoint[]> {
+  return invoke("get_system_history", { from: from ?? null, to: to ?? null });
+}
+
+export async function getServiceHistory(serviceId: string, from?: string, to?: string): Promise<ServicePoint[]> {
+  return invoke("get_service_history", { serviceId, from: from ?? null, to: to ?? null });
+}
+
+export async function getPortCountHistory(from?: string, to?: string): Promise<PortCountPoint[]> {
+  return invoke("get_port_count_history", { from: from ?? null, to: to ?? null });
+}
+
+export async function getAlertHistory(limit?: number): Promise<Alert[]> {
+  return invoke("get_alert_history", { limit: limit ?? null });
+}
+
+/** Stored security scans, newest first */
+export async function getSecurityScans(limit?: number): Promise<SecurityScanSummary[]> {
+  return invoke("get_security_scans", { limit: limit ?? null });
+}
+
+export async function getSecurityScan(id: number): Promise<SecurityScanResult> {
+  return invoke("get_security_scan", { id });
+}
+
+/** `to` defaults to the latest scan */
+export async function diffSecurityScans(from: number, to?: number): Promise<SecurityScanDiff> {
+  return invoke("diff_security_scans", { from, to: to ?? null });
+}
+
 // Config commands
 export async function getConfig(): Promise<Config> {
   return invoke("get_config");
@@ -65,6 +280,14 @@ export async function updateConfig(config: Config): Promise<void> {
   return invoke("update_config", { config });
 }
 
+export async function getStoragePaths(): Promise<StoragePaths> {
+  return invoke("get_storage_paths");
+}
+
+export async function testWebhook(id: string): Promise<void> {
+  return invoke("test_webhook", { id });
+}
+
 // Audit commands
 export async function getAuditLogs(limit?: number): Promise<AuditEntry[]> {
   return invoke("get_audit_logs", { limit });
@@ -74,6 +297,11 @@ export async function exportAuditLogs(format: string): Promise<string> {
   return invoke("export_audit_logs", { format });
 }
 
+/** Answers questions like "what stopped postgres last Tuesday?" from matching audit entries */
+export async function askAuditLog(question: string): Promise<AuditAnswer> {
+  return invoke("ask_audit_log", { question });
+}
+
 // LLM commands
 export async function checkOllamaStatus(): Promise<boolean> {
   return invoke("check_ollama_status");
@@ -83,19 +311,93 @@ export async function listOllamaModels(): Promise<string[]> {
   return invoke("list_ollama_models");
 }
 
-export async function analyzeLogs(logs: string, analysisType: string): Promise<string> {
-  return invoke("analyze_logs", { logs, analysisType });
+/** Downloads `model` or the analysis model; emits `model-pull-progress` events (PullProgress) */
+export async function pullOllamaModel(model?: string): Promise<void> {
+  return invoke("pull_ollama_model", { model: model ?? null });
+}
+
+/** With `cluster`, similar lines are grouped by embeddings and the model only sees the groups */
+export async function analyzeLogs(logs: string, analysisType: string, cluster = false): Promise<LogAnalysisResult> {
+  return invoke("analyze_logs", { logs, analysisType, cluster });
+}
+
+/** Groups similar lines and flags unusual ones with the embedding model only */
+export async function clusterLogs(logs: string): Promise<LogClusterReport> {
+  return invoke("cluster_logs", { logs });
+}
+
+/** Store an incident so similar log analyses link to it; returns its ID */
+export async function recordIncident(title: string, details: string): Promise<string> {
+  return invoke("record_incident", { title, details });
+}
+
+export async function getStoredAnalysis(id: string): Promise<StoredAnalysis> {
+  return invoke("get_stored_analysis", { id });
+}
+
+/** Without a title the first question names the conversation */
+export async function createChatSession(title?: string): Promise<ChatSession> {
+  return invoke("create_chat_session", { title: title ?? null });
+}
+
+/** Returns the assistant's answer; the question and answer are stored with the session */
+export async function sendChatMessage(sessionId: string, message: string): Promise<ChatSessionMessage> {
+  return invoke("send_chat_message", { sessionId, message });
+}
+
+export async function listChatSessions(): Promise<ChatSessionSummary[]> {
+  return invoke("list_chat_sessions");
+}
+
+export async function getChatSession(sessionId: string): Promise<ChatSession> {
+  return invoke("get_chat_session", { sessionId });
+}
+
+export async function deleteChatSession(sessionId: string): Promise<void> {
+  return invoke("delete_chat_session", { sessionId });
 }
 
 export async function setOllamaModel(model: string): Promise<void> {
   return invoke("set_ollama_model", { model });
 }
 
+/** Model for quick process explanations; falls back to the smallest installed model if not pulled */
+export async function setOllamaFastModel(model: string): Promise<void> {
+  return invoke("set_ollama_fast_model", { model });
+}
+
+export async function setOllamaEmbeddingModel(model: string): Promise<void> {
+  return invoke("set_ollama_embedding_model", { model });
+}
+
+/** Primary endpoint first, followed by fallbacks */
+export async function setOllamaEndpoints(endpoints: string[]): Promise<void> {
+  return invoke("set_ollama_endpoints", { endpoints });
+}
+
+export async function checkOllamaEndpoints(): Promise<EndpointHealth[]> {
+  return invoke("check_ollama_endpoints");
+}
+
+export async function getLlmQueueStatus(): Promise<LlmQueueStatus> {
+  return invoke("get_llm_queue_status");
+}
+
+/** Ollama version, models against free memory and a timed probe generation */
+export async function getLlmDiagnostics(probe = true): Promise<LlmDiagnostics> {
+  return invoke("get_llm_diagnostics", { probe });
+}
+
+/** Models each task is sent to, in order of preference */
+export async function getTaskModels(): Promise<TaskModels[]> {
+  return invoke("get_task_models");
+}
+
 export async function explainProcess(
   processName: string,
   processPath?: string | null,
   description?: string | null
-): Promise<string> {
+): Promise<ProcessExplanation> {
   return invoke("explain_process", {
     processName,
     processPath: processPath ?? null,
@@ -103,6 +405,15 @@ export async function explainProcess(
   });
 }
 
+/** Custom entries replace one with the same name and are matched before the bundled ones */
+export async function addProcessKnowledgeEntry(entry: ProcessKnowledgeEntry): Promise<ProcessKnowledgeEntry> {
+  return invoke("add_process_knowledge_entry", { entry });
+}
+
+export async function listProcessKnowledgeEntries(): Promise<ProcessKnowledgeEntry[]> {
+  return invoke("list_process_knowledge_entries");
+}
+
 export async function getServiceRecommendations(
   services: Service[]
 ): Promise<ServiceRecommendation[]> {
@@ -119,6 +430,24 @@ export async function getServiceRecommendations(
   return invoke("get_service_recommendations", { servicesJson });
 }
 
+// Prompt template commands
+export async function listPromptTemplates(): Promise<PromptTemplate[]> {
+  return invoke("list_prompt_templates");
+}
+
+export async function getPromptTemplate(kind: PromptKind): Promise<PromptTemplate> {
+  return invoke("get_prompt_template", { kind });
+}
+
+/** Fails when the template lacks the placeholder for the prompt's data */
+export async function updatePromptTemplate(kind: PromptKind, content: string): Promise<PromptTemplate> {
+  return invoke("update_prompt_template", { kind, content });
+}
+
+export async function resetPromptTemplate(kind: PromptKind): Promise<PromptTemplate> {
+  return invoke("reset_prompt_template", { kind });
+}
+
 // Monitor commands
 export async function setMonitorInterval(seconds: number): Promise<void> {
   return invoke("set_monitor_interval", { seconds });
@@ -128,20 +457,242 @@ export async function enableMonitor(enabled: boolean): Promise<void> {
   return invoke("enable_monitor", { enabled });
 }
 
+export async function startMonitor(): Promise<void> {
+  return invoke("start_monitor");
+}
+
+export async function stopMonitor(): Promise<void> {
+  return invoke("stop_monitor");
+}
+
+export async function watchService(serviceId: string): Promise<void> {
+  return invoke("watch_service", { serviceId });
+}
+
+export async function unwatchService(serviceId: string): Promise<void> {
+  return invoke("unwatch_service", { serviceId });
+}
+
+export async function getWatchedServices(): Promise<string[]> {
+  return invoke("get_watched_services");
+}
+
+export async function getRecentEvents<E = unknown>(since?: string): Promise<RecordedEvent<E>[]> {
+  return invoke("get_recent_events", { since: since ?? null });
+}
+
 // System stats commands
 export async function getSystemStats(): Promise<SystemStats> {
   return invoke("get_system_stats");
 }
 
+export async function getHostInfo(): Promise<HostInfo> {
+  return invoke("get_host_info");
+}
+
+/** Without a resolution the backend picks the finest tier covering the range */
+export async function getStatsHistory(rangeSeconds: number, resolution?: StatsResolution): Promise<StatsSample[]> {
+  return invoke("get_stats_history", { rangeSeconds, resolution: resolution ?? null });
+}
+
+export async function getTopProcesses(by: ProcessSort, n?: number): Promise<ProcessUsage[]> {
+  return invoke("get_top_processes", { by, n: n ?? null });
+}
+
+export async function getPowermetricsStatus(): Promise<PowermetricsStatus> {
+  return invoke("get_powermetrics_status");
+}
+
+/** One provider, all detected ones ("auto") or none */
 export async function setGpuProvider(provider: GpuProvider): Promise<void> {
   return invoke("set_gpu_provider", { provider });
 }
 
+export async function listGpuProviders(): Promise<GpuProviderInfo[]> {
+  return invoke("list_gpu_providers");
+}
+
+export async function setGpuProviderEnabled(provider: GpuProvider, enabled: boolean): Promise<void> {
+  return invoke("set_gpu_provider_enabled", { provider, enabled });
+}
+
+export async function setGpuEnabled(name: string, enabled: boolean): Promise<void> {
+  return invoke("set_gpu_enabled", { name, enabled });
+}
+
+/** Sync daemon status and clock offset; `server` defaults to the daemon's NTP server */
+export async function checkTimeSync(server?: string): Promise<TimeSyncStatus> {
+  return invoke("check_time_sync", { server: server ?? null });
+}
+
+/** SMART health of all drives; failing drives also show up in scanSecurity */
+export async function checkDiskHealth(): Promise<DiskHealthReport> {
+  return invoke("check_disk_health");
+}
+
+/** Host firewall state; scanSecurity reports it when public ports are unprotected */
+export async function checkFirewall(): Promise<FirewallStatus> {
+  return invoke("check_firewall");
+}
+
 // Security commands
-export async function scanSecurity(): Promise<SecurityScanResult> {
-  return invoke("scan_security");
+/** Accept a finding as a known risk until `expires` (ISO timestamp) */
+export async function acknowledgeSecurityIssue(id: string, reason: string, expires?: string): Promise<AcknowledgedIssue> {
+  return invoke("acknowledge_security_issue", { id, reason, expires: expires ?? null });
+}
+
+export async function revokeSecurityAcknowledgement(id: string): Promise<void> {
+  return invoke("revoke_security_acknowledgement", { id });
+}
+
+export async function listSecurityAcknowledgements(): Promise<AcknowledgedIssue[]> {
+  return invoke("list_security_acknowledgements");
+}
+
+/**
+ * `upnp` adds findings for port forwardings on the router; `authProbe` sends
+ * read-only commands to local Redis/MongoDB/Elasticsearch/Memcached to
+ * confirm missing authentication
+ */
+export async function scanSecurity(fingerprint?: boolean, upnp?: boolean, authProbe?: boolean): Promise<SecurityScanResult> {
+  return invoke("scan_security", { fingerprint: fingerprint ?? null, upnp: upnp ?? null, authProbe: authProbe ?? null });
+}
+
+export async function getComplianceProfiles(): Promise<ComplianceProfileInfo[]> {
+  return invoke("get_compliance_profiles");
+}
+
+/** Runs its own security scan and scores it against the profile's controls */
+export async function runComplianceScan(profile: ComplianceProfile): Promise<ComplianceReport> {
+  return invoke("run_compliance_scan", { profile });
 }
 
-export async function getSecurityAnalysis(servicesJson: string): Promise<string> {
+export async function inspectTls(host: string, port: number): Promise<TlsInspection> {
+  return invoke("inspect_tls", { host, port });
+}
+
+export async function scanTlsPorts(): Promise<TlsInspection[]> {
+  return invoke("scan_tls_ports");
+}
+
+export async function getSecurityAnalysis(servicesJson: string): Promise<SecurityAnalysis> {
   return invoke("get_security_analysis", { servicesJson });
 }
+
+/** Prioritized remediation plan for the findings of `scan` */
+export async function getRemediationPlan(scan: SecurityScanResult): Promise<RemediationPlan> {
+  return invoke("get_remediation_plan", { scan });
+}
+
+// Digest commands
+/** Generate the weekly digest now; optionally with an LLM-written summary */
+export async function generateDigest(llmSummary?: boolean): Promise<DigestReport> {
+  return invoke("generate_digest", { llmSummary: llmSummary ?? null });
+}
+
+// Cleanup commands
+/** Ordered cleanup suggestions; nothing is changed until executeCleanup */
+export async function planCleanup(): Promise<CleanupPlan> {
+  return invoke("plan_cleanup");
+}
+
+/** Apply only the accepted step IDs of a plan; steps that stop services or remove volumes need a confirmation token */
+export async function executeCleanup(
+  planId: string,
+  acceptedSteps: string[],
+  confirmationToken?: string
+): Promise<CleanupReport> {
+  return invoke("execute_cleanup", { planId, acceptedSteps, confirmationToken: confirmationToken ?? null });
+}
+
+// Assistant action commands
+/** Stop, autostart and port actions proposed by the model; nothing is changed until executeProposedActions */
+export async function proposeServiceActions(): Promise<ActionProposal> {
+  return invoke("propose_service_actions");
+}
+
+/** Run the accepted action IDs of a proposal; each one is audit logged */
+export async function executeProposedActions(
+  proposalId: string,
+  acceptedActions: string[]
+): Promise<ProposedActionResult[]> {
+  return invoke("execute_proposed_actions", { proposalId, acceptedActions });
+}
+
+// Remote viewer commands
+export async function startRemotePairing(): Promise<PairingInfo> {
+  return invoke("start_remote_pairing");
+}
+
+export async function stopRemoteViewer(): Promise<void> {
+  return invoke("stop_remote_viewer");
+}
+
+/** Unpair a single device */
+export async function revokeRemoteDevice(deviceId: string): Promise<void> {
+  return invoke("revoke_remote_device", { deviceId });
+}
+
+export async function getRemoteViewerStatus(): Promise<RemoteViewerStatus> {
+  return invoke("get_remote_viewer_status");
+}
+
+// Network commands
+/** Browse mDNS/DNS-SD services on the LAN; `serviceTypes` replaces the default list */
+export async function discoverNetworkServices(timeoutMs?: number, serviceTypes?: string[]): Promise<NetworkDiscovery> {
+  return invoke("discover_network_services", { timeoutMs: timeoutMs ?? null, serviceTypes: serviceTypes ?? null });
+}
+
+export async function discoverUpnpDevices(timeoutMs?: number): Promise<SsdpDiscovery> {
+  return invoke("discover_upnp_devices", { timeoutMs: timeoutMs ?? null });
+}
+
+// Tunnel commands
+export async function listTunnels(): Promise<TunnelStatus[]> {
+  return invoke("list_tunnels");
+}
+
+/** Add a tunnel (empty id) or update an existing one */
+export async function saveTunnel(spec: TunnelSpec): Promise<TunnelSpec> {
+  return invoke("save_tunnel", { spec });
+}
+
+export async function removeTunnel(id: string): Promise<void> {
+  return invoke("remove_tunnel", { id });
+}
+
+export async function startTunnel(id: string): Promise<TunnelStatus> {
+  return invoke("start_tunnel", { id });
+}
+
+export async function stopTunnel(id: string): Promise<TunnelStatus> {
+  return invoke("stop_tunnel", { id });
+}
+
+// Export commands
+/** Writes to `path` (file or directory), or to the exports directory */
+export async function exportReport(kind: ExportKind, format: ExportFormat, path?: string): Promise<ExportResult> {
+  return invoke("export", { kind, format, path: path ?? null });
+}
+
+/** The latest stored security scan with its trend; fails before the first scan */
+export async function exportSecurityReport(format: ReportFormat, path?: string): Promise<ExportResult> {
+  return invoke("export_security_report", { format, path: path ?? null });
+}
+
+export async function getExportTemplateDir(): Promise<string> {
+  return invoke("get_export_template_dir");
+}
+
+// Setup commands
+export async function getBootstrapStatus(): Promise<BootstrapStatus> {
+  return invoke("get_bootstrap_status");
+}
+
+export async function createBaselineSnapshot(): Promise<BaselineSummary> {
+  return invoke("create_baseline_snapshot");
+}
+
+export async function completeBootstrap(): Promise<void> {
+  return invoke("complete_bootstrap");
+}
@@ -18,18 +18,315 @@ export interface Service {
   memory_bytes: number | null;
   /** Memory usage as percentage of total system memory */
   memory_percent: number | null;
+  /** CPU usage of the process and all its descendants */
+  cpu_usage_total: number | null;
+  /** Memory of the process and all its descendants in bytes */
+  memory_total: number | null;
+  /** GPU use of the main process, null if it doesn't use the GPU */
+  gpu_usage: GpuProcessUsage | null;
+  /** Energy and QoS of the main process, macOS only */
+  energy: EnergyUsage | null;
+  /** Bytes received and sent since the container started, Docker only */
+  network_rx_bytes: number | null;
+  network_tx_bytes: number | null;
+  /** Account the process runs as */
+  user: string | null;
+  /** Restarts the service monitor saw within the flapping window */
+  recent_restarts: number;
+  /** Restarted more often than the flapping config allows */
+  flapping: boolean;
+  /** Docker Compose project from the container labels */
+  compose_project: string | null;
+  /** Service within the Compose project, shared by its replicas */
+  compose_service: string | null;
+}
+
+export type ProviderWarningKind = "permission_denied" | "unavailable" | "partial";
+
+export interface ProviderWarning {
+  provider: string;
+  kind: ProviderWarningKind;
+  message: string;
+}
+
+/** Whether other users' services and ports are listed */
+export interface UserScopeStatus {
+  current_user: string;
+  all_users: boolean;
+  /** When the "all users" mode switches itself off */
+  expires_at: string | null;
+}
+
+export interface DiscoveryResult {
+  services: Service[];
+  warnings: ProviderWarning[];
+}
+
+export interface ImpactPreview {
+  action: string;
+  target: string;
+  target_name: string | null;
+  ports_freed: number[];
+  dependents_affected: string[];
+}
+
+/** Result of a destructive command; echo `token` back to confirm */
+export type ActionOutcome =
+  | { status: "executed" }
+  | { status: "dry_run"; preview: ImpactPreview }
+  /** Simulation mode: the target is fake, nothing on the host was touched */
+  | { status: "simulated" }
+  | { status: "confirmation_required"; token: string; preview: ImpactPreview; expires_in_secs: number };
+
+export type RestartPolicy = "never" | "on_failure" | "always";
+
+export interface CustomServiceSpec {
+  name: string;
+  description?: string | null;
+  command: string;
+  args?: string[];
+  env?: Record<string, string>;
+  working_directory?: string | null;
+  restart_policy?: RestartPolicy;
+  auto_start?: boolean;
+}
+
+export type DiffLineKind = "unchanged" | "added" | "removed";
+
+export interface DiffLine {
+  kind: DiffLineKind;
+  text: string;
+}
+
+export interface UnitOverrideDiff {
+  unit: string;
+  path: string;
+  previous: string;
+  lines: DiffLine[];
+  changed: boolean;
+}
+
+export interface ServiceDependencies {
+  service: string;
+  /** Services that must run before this one can start */
+  dependencies: string[];
+  /** Running dependents, in the order they can be stopped */
+  running_dependents: string[];
+}
+
+// Docker types
+export interface DockerImage {
+  id: string;
+  repo_tags: string[];
+  size_bytes: number;
+  created: number;
+  containers: number | null;
+  dangling: boolean;
+}
+
+export interface ImagePullProgress {
+  image: string;
+  layer_id: string | null;
+  status: string;
+  current_bytes: number | null;
+  total_bytes: number | null;
+}
+
+export interface ContainerRef {
+  id: string;
+  name: string;
+  running: boolean;
+}
+
+export interface DockerVolume {
+  name: string;
+  driver: string;
+  mountpoint: string;
+  created_at: string | null;
+  containers: ContainerRef[];
+  orphaned: boolean;
+}
+
+export interface NetworkAttachment {
+  container: ContainerRef;
+  ip_address: string | null;
+}
+
+export interface DockerNetwork {
+  id: string;
+  name: string;
+  driver: string;
+  scope: string | null;
+  internal: boolean;
+  subnets: string[];
+  containers: NetworkAttachment[];
 }
 
 // Port types
 export type Protocol = "tcp" | "udp";
 export type PortStatus = "occupied" | "free";
+export type AddressFamily = "ipv4" | "ipv6";
 
 export interface PortInfo {
   port: number;
   protocol: Protocol;
+  family: AddressFamily;
+  /** Local address the socket is bound to, e.g. "127.0.0.1", "0.0.0.0", "::" */
+  bind_address: string;
   status: PortStatus;
   process_name: string | null;
   pid: number | null;
+  /** Protocol found by the fingerprint pass, e.g. "ssh", "http", "redis", "tls" */
+  detected_service: string | null;
+  /** Product/version announced by the service */
+  version: string | null;
+  /** Account of the owning process */
+  user: string | null;
+  /** Registered or user-assigned name of the port, e.g. "postgresql" */
+  service_name: string | null;
+  /** TCP connect round-trip time, only set for scan results */
+  latency_ms: number | null;
+  /** Operating system of the host, only set by nmap scans with OS detection */
+  os_guess: string | null;
+}
+
+/** A socket with a remote peer: TCP in any state but LISTEN, or connected UDP */
+export interface Connection {
+  protocol: Protocol;
+  family: AddressFamily;
+  local_address: string;
+  local_port: number;
+  remote_address: string;
+  remote_port: number;
+  /** "ESTABLISHED", "SYN_SENT", "TIME_WAIT", "CLOSE_WAIT", ... */
+  state: string;
+  process_name: string | null;
+  pid: number | null;
+  user: string | null;
+  /** Registered name of the remote port, e.g. "https" */
+  remote_service: string | null;
+}
+
+export type IpcKind = "unix_socket" | "named_pipe";
+
+/** Unix domain socket or Windows named pipe a local service listens on */
+export interface IpcEndpoint {
+  kind: IpcKind;
+  /** Socket path ("@name" for Linux abstract sockets) or `\\.\pipe\name` */
+  path: string;
+  /** "LISTEN" or "BOUND" where known */
+  state: string | null;
+  process_name: string | null;
+  pid: number | null;
+  user: string | null;
+}
+
+/** Payload of `port-scan-progress` events */
+export interface ScanProgress {
+  scanned: number;
+  total: number;
+  open: number;
+}
+
+export interface PortMapping {
+  host_port: number;
+  container_port: number;
+  protocol?: Protocol;
+}
+
+/** A host port a container would publish that is already in use */
+export interface PortCollision {
+  mapping: PortMapping;
+  listener: PortInfo;
+  service_id: string | null;
+  /** e.g. "postgres (docker)" or "nginx (PID 812)" */
+  owner: string;
+}
+
+export interface SocketRecord {
+  protocol: Protocol;
+  family: AddressFamily;
+  local_address: string;
+  local_port: number;
+  remote: string | null;
+  state: string | null;
+  process_name: string | null;
+  pid: number | null;
+}
+
+// Runtime dependency types
+export type DependencyKind = "local_service" | "local_process" | "lan" | "internet";
+
+export interface RuntimeDependency {
+  /** Service name, host name or the address itself */
+  name: string;
+  kind: DependencyKind;
+  address: string;
+  port: number;
+  service_id: string | null;
+  hostname: string | null;
+  /** ISO country code, if a GeoIP database is configured */
+  country: string | null;
+  connections: number;
+}
+
+export interface ServiceRuntimeDependencies {
+  service_id: string;
+  service_name: string;
+  pids: number[];
+  dependencies: RuntimeDependency[];
+  checked_at: string;
+}
+
+// Local resolver types
+export type ResolverRole = "dns" | "mdns" | "llmnr";
+export type ResolverConflictKind = "port_clash" | "blocked_start" | "duplicate_mdns";
+
+export interface ResolverPort {
+  port: number;
+  protocol: Protocol;
+  address: string;
+}
+
+export interface LocalResolver {
+  name: string;
+  process_name: string;
+  pid: number | null;
+  unit: string | null;
+  /** systemd ActiveState, e.g. "active", "failed" */
+  unit_state: string | null;
+  running: boolean;
+  roles: ResolverRole[];
+  ports: ResolverPort[];
+}
+
+export interface ResolverConflict {
+  id: string;
+  kind: ResolverConflictKind;
+  port: number;
+  resolvers: string[];
+  title: string;
+  description: string;
+  /** Guided fix, one step per entry */
+  fix_steps: string[];
+}
+
+export interface ResolverStatus {
+  resolvers: LocalResolver[];
+  conflicts: ResolverConflict[];
+  checked_at: string;
+}
+
+// Parser diagnostics
+export interface ParseIssue {
+  line: number;
+  content: string;
+  reason: string;
+}
+
+export interface ParseReport<T> {
+  items: T[];
+  issues: ParseIssue[];
 }
 
 // Audit types
@@ -41,7 +338,9 @@ export type EventType =
   | "config_change"
   | "privilege_escalation"
   | "llm_analysis"
-  | "port_scan";
+  | "port_scan"
+  | "cleanup"
+  | "alert";
 
 export interface AuditEntry {
   id: string;
@@ -55,6 +354,25 @@ export interface AuditEntry {
   details: Record<string, unknown>;
 }
 
+/** Audit filter; empty fields match everything */
+export interface AuditQuery {
+  since: string | null;
+  /** Exclusive */
+  until: string | null;
+  service: string | null;
+  event_types: EventType[];
+  success: boolean | null;
+  keywords: string[];
+}
+
+export interface AuditAnswer {
+  answer: string;
+  /** Filter the model derived from the question */
+  query: AuditQuery;
+  /** Entries the answer cites as [1], [2], ... */
+  entries: AuditEntry[];
+}
+
 // Recommendation types
 export type RecommendationType =
   | "stop_service"
@@ -73,6 +391,58 @@ export interface ServiceRecommendation {
   action: string | null;
 }
 
+/** AI security analysis, returned as structured JSON by the model */
+export interface SecurityAnalysis {
+  summary: string;
+  risks: SecurityRisk[];
+}
+
+export interface SecurityRisk {
+  service_name: string | null;
+  severity: SecuritySeverity;
+  title: string;
+  recommendation: string;
+}
+
+/** AI remediation plan for the findings of a security scan */
+export interface RemediationPlan {
+  summary: string;
+  /** Most urgent first */
+  steps: RemediationStep[];
+  /** Findings no step refers to */
+  unaddressed_finding_ids: string[];
+}
+
+export interface RemediationStep {
+  /** Position in the plan, starting at 1 */
+  priority: number;
+  title: string;
+  text: string;
+  /** IDs of the `SecurityIssue`s this step fixes */
+  finding_ids: string[];
+  severity: SecuritySeverity | null;
+}
+
+// Prompt templates
+export type PromptKind =
+  | "log_analysis"
+  | "log_chunk_summary"
+  | "process_explanation"
+  | "recommendations"
+  | "remediation_plan"
+  | "security_analysis";
+
+export interface PromptTemplate {
+  kind: PromptKind;
+  content: string;
+  default_content: string;
+  /** A template file overrides the built-in prompt */
+  customized: boolean;
+  path: string;
+  /** e.g. `{{logs}}`; `{{language}}` and `{{language_instruction}}` work everywhere */
+  placeholders: string[];
+}
+
 // System Stats types
 export interface CpuStats {
   usage_percent: number;
@@ -97,16 +467,158 @@ export interface GpuStats {
   memory_total_bytes: number | null;
   temperature_celsius: number | null;
   power_watts: number | null;
+  frequency_mhz: number | null;
+  /** Which provider reported this GPU */
+  provider: GpuProvider | null;
+}
+
+export interface GpuProcessUsage {
+  memory_bytes: number | null;
+  /** Busiest engine (NVIDIA: streaming multiprocessors) */
+  utilization_percent: number | null;
+}
+
+export interface EnergyUsage {
+  /** Activity Monitor's score, only while the powermetrics sampler runs */
+  energy_impact: number | null;
+  power_milliwatts: number | null;
+  qos: QosCpuTime | null;
+}
+
+/** CPU time in ms per second by QoS class */
+export interface QosCpuTime {
+  user_interactive: number;
+  user_initiated: number;
+  default: number;
+  utility: number;
+  background: number;
+  maintenance: number;
+}
+
+export interface GpuDevice {
+  name: string;
+  enabled: boolean;
+}
+
+export interface GpuProviderInfo {
+  provider: GpuProvider;
+  /** Hardware or tool for this provider was found */
+  detected: boolean;
+  enabled: boolean;
+  gpus: GpuDevice[];
+}
+
+export interface NetworkInterfaceStats {
+  name: string;
+  rx_bytes_per_sec: number;
+  tx_bytes_per_sec: number;
+  total_rx_bytes: number;
+  total_tx_bytes: number;
+  /** Only known on Linux */
+  link_up: boolean | null;
+  mac_address: string;
+  /** With prefix length, e.g. 192.168.1.20/24 */
+  ip_addresses: string[];
+}
+
+export interface NetworkStats {
+  /** Sum over all interfaces except loopback */
+  rx_bytes_per_sec: number;
+  tx_bytes_per_sec: number;
+  interfaces: NetworkInterfaceStats[];
+}
+
+export interface DiskStats {
+  /** Device, e.g. /dev/nvme0n1p2 or C: */
+  name: string;
+  mount_point: string;
+  file_system: string;
+  kind: "ssd" | "hdd" | "unknown";
+  removable: boolean;
+  total_bytes: number;
+  available_bytes: number;
+  usage_percent: number;
+  read_bytes_per_sec: number;
+  write_bytes_per_sec: number;
+}
+
+export interface TemperatureReading {
+  label: string;
+  /** Guessed from the label */
+  kind: "cpu" | "gpu" | "storage" | "other";
+  celsius: number;
+  max_celsius: number | null;
+  critical_celsius: number | null;
+}
+
+export interface FanReading {
+  label: string;
+  rpm: number;
+}
+
+/** Fans are only reported on Linux */
+export interface SensorStats {
+  temperatures: TemperatureReading[];
+  fans: FanReading[];
+}
+
+export interface LoadAverage {
+  one: number;
+  five: number;
+  fifteen: number;
+}
+
+export interface HostInfo {
+  hostname: string | null;
+  /** E.g. "macOS 14.5 Sonoma" */
+  os_version: string | null;
+  kernel_version: string | null;
+  arch: string;
+  uptime_seconds: number;
+  /** Unix timestamp */
+  boot_time: number;
+  /** Not available on Windows */
+  load_average: LoadAverage | null;
+}
+
+/** "network" is rejected until per-process throughput is collected */
+export type ProcessSort = "cpu" | "memory" | "network";
+
+export interface ProcessUsage {
+  pid: number;
+  name: string;
+  path: string | null;
+  user: string | null;
+  cpu_usage: number;
+  memory_bytes: number;
+  memory_percent: number;
 }
 
 export interface SystemStats {
   cpu: CpuStats;
   memory: MemoryStats;
   gpus: GpuStats[];
+  network: NetworkStats;
+  /** Mounted filesystems, one entry per device */
+  disks: DiskStats[];
+  sensors: SensorStats;
+  host: HostInfo;
   timestamp: number;
 }
 
-export type GpuProvider = "auto" | "apple" | "nvidia" | "amd" | "none";
+export type StatsResolution = "second" | "minute" | "hour";
+
+/** One point of the in-memory stats history */
+export interface StatsSample {
+  /** Unix timestamp; bucket start for minute and hour averages */
+  timestamp: number;
+  cpu_percent: number;
+  memory_percent: number;
+  gpu_percent: number | null;
+}
+
+/** "windows" reads DXGI and the GPU performance counters for any vendor */
+export type GpuProvider = "auto" | "apple" | "nvidia" | "amd" | "windows" | "none";
 
 // Security types
 export type SecuritySeverity = "critical" | "high" | "medium" | "low" | "info";
@@ -119,7 +631,9 @@ export type SecurityCategory =
   | "missing_authentication"
   | "insecure_configuration"
   | "privilege_escalation"
-  | "data_leakage";
+  | "data_leakage"
+  | "reliability"
+  | "untrusted_binary";
 
 export interface SecurityIssue {
   id: string;
@@ -134,6 +648,137 @@ export interface SecurityIssue {
   details: string | null;
 }
 
+export interface CertificateInfo {
+  subject: string;
+  issuer: string;
+  /** Hex encoded */
+  serial: string;
+  not_before: string | null;
+  not_after: string | null;
+  subject_alt_names: string[];
+  signature_algorithm: string;
+  self_signed: boolean;
+}
+
+export interface TlsProtocolSupport {
+  /** e.g. "TLS 1.2" */
+  version: string;
+  supported: boolean;
+  deprecated: boolean;
+}
+
+export interface TlsInspection {
+  host: string;
+  port: number;
+  /** Leaf first; empty for TLS 1.3-only servers */
+  chain: CertificateInfo[];
+  protocols: TlsProtocolSupport[];
+  cipher_suite: string | null;
+  weak_ciphers: string[];
+  self_signed: boolean;
+  /** Negative if expired */
+  expires_in_days: number | null;
+  issues: string[];
+}
+
+// Time sync types
+export type TimeSyncHealth = "ok" | "warning" | "critical" | "unknown";
+
+export interface TimeSyncStatus {
+  health: TimeSyncHealth;
+  /** "chrony", "systemd-timesyncd", "ntpd", "timed" or "w32time" */
+  service: string | null;
+  ntp_enabled: boolean | null;
+  synchronized: boolean | null;
+  server: string | null;
+  /** Positive means the local clock is ahead */
+  daemon_offset_ms: number | null;
+  measured_offset_ms: number | null;
+  ntp_server: string;
+  round_trip_ms: number | null;
+  issues: string[];
+  checked_at: string;
+}
+
+export type DiskHealthState = "ok" | "warning" | "critical" | "unknown";
+
+export interface DiskHealth {
+  device: string;
+  model: string | null;
+  serial: string | null;
+  /** "smartctl", "iokit" or "wmi" */
+  source: string;
+  health: DiskHealthState;
+  smart_passed: boolean | null;
+  reallocated_sectors: number | null;
+  pending_sectors: number | null;
+  /** NVMe media errors; uncorrected read errors on Windows */
+  media_errors: number | null;
+  /** Share of the rated endurance used up */
+  wear_percent: number | null;
+  temperature_celsius: number | null;
+  power_on_hours: number | null;
+  issues: string[];
+}
+
+export interface DiskHealthReport {
+  disks: DiskHealth[];
+  /** Why data is missing, e.g. smartctl not installed */
+  warnings: string[];
+  checked_at: string;
+}
+
+export type FirewallState = "enabled" | "disabled" | "unknown";
+
+export interface FirewallBackend {
+  /** `ufw`, `firewalld`, `nftables`, `iptables`, `alf`, `pf` or `windows` */
+  name: string;
+  /** null if reading the state needs root */
+  enabled: boolean | null;
+  details: string | null;
+}
+
+export interface FirewallStatus {
+  state: FirewallState;
+  backends: FirewallBackend[];
+  warnings: string[];
+  checked_at: string;
+}
+
+export type ComplianceProfile = "macos_workstation" | "linux_server";
+
+export type ControlStatus = "pass" | "fail" | "accepted" | "unknown" | "not_applicable";
+
+export interface ComplianceProfileInfo {
+  profile: ComplianceProfile;
+  name: string;
+  description: string;
+  controls: number;
+}
+
+export interface ControlResult {
+  id: string;
+  title: string;
+  description: string;
+  severity: SecuritySeverity;
+  /** `accepted`: failed only by acknowledged findings */
+  status: ControlStatus;
+  evidence: string | null;
+}
+
+export interface ComplianceReport {
+  profile: ComplianceProfile;
+  name: string;
+  controls: ControlResult[];
+  passed: number;
+  failed: number;
+  /** Unknown and not applicable controls, not scored */
+  skipped: number;
+  /** Percent of scored controls that passed */
+  score: number;
+  checked_at: string;
+}
+
 export interface SecurityScanResult {
   issues: SecurityIssue[];
   scan_timestamp: number;
@@ -143,6 +788,195 @@ export interface SecurityScanResult {
   high_count: number;
   medium_count: number;
   low_count: number;
+  /** Gaps in the scanned data, e.g. sockets of other users' processes */
+  warnings: ProviderWarning[];
+  /** Findings accepted as known risks; not part of `issues` or the counts */
+  acknowledged: AcknowledgedSecurityIssue[];
+}
+
+export interface AcknowledgedIssue {
+  /** `SecurityIssue.id` */
+  issue_id: string;
+  reason: string;
+  acknowledged_at: string;
+  /** The finding counts again from this time on */
+  expires: string | null;
+}
+
+export interface AcknowledgedSecurityIssue {
+  issue: SecurityIssue;
+  acknowledgement: AcknowledgedIssue;
+}
+
+/** A stored security scan without its findings */
+export interface SecurityScanSummary {
+  id: number;
+  taken_at: string;
+  critical_count: number;
+  high_count: number;
+  medium_count: number;
+  low_count: number;
+  issue_count: number;
+}
+
+/** Findings of two scans matched by ID */
+export interface SecurityScanDiff {
+  from_timestamp: number;
+  to_timestamp: number;
+  new_issues: SecurityIssue[];
+  fixed_issues: SecurityIssue[];
+  persistent_issues: SecurityIssue[];
+}
+
+export interface EndpointHealth {
+  endpoint: string;
+  healthy: boolean;
+  active: boolean;
+  latency_ms: number | null;
+  error: string | null;
+}
+
+/** A past analysis that resembles the current one */
+export interface SimilarAnalysis {
+  id: string;
+  created_at: string;
+  kind: string;
+  title: string;
+  similarity: number;
+}
+
+/** Payload of `log-analysis-progress` events; long logs are summarized in chunks first */
+export interface LogAnalysisProgress {
+  stage: "clustering" | "summarizing" | "combining" | "analyzing" | "done";
+  completed: number;
+  total: number;
+}
+
+/** Similar log lines grouped by their embeddings */
+export interface LogCluster {
+  representative: string;
+  examples: string[];
+  occurrences: number;
+  /** Lines that differ in more than numbers and IDs */
+  variants: number;
+  contains_errors: boolean;
+  anomaly_score: number;
+  /** The score is well above that of the other groups */
+  anomalous: boolean;
+}
+
+export interface LogClusterReport {
+  total_lines: number;
+  /** Most frequent first */
+  clusters: LogCluster[];
+  /** Lines beyond the embedding limit */
+  skipped_lines: number;
+}
+
+export interface LogAnalysisResult {
+  report: string;
+  /** ID of the stored report; null if no embedding could be created */
+  analysis_id: string | null;
+  similar: SimilarAnalysis[];
+}
+
+export interface StoredAnalysis {
+  id: string;
+  created_at: string;
+  /** Analysis type or "incident" */
+  kind: string;
+  title: string;
+  input_excerpt: string;
+  report: string;
+}
+
+export type ChatRole = "system" | "user" | "assistant";
+
+export interface ChatSessionMessage {
+  role: ChatRole;
+  content: string;
+  created_at: string;
+}
+
+/** Conversation with the assistant; live service, port and scan context is added per question */
+export interface ChatSession {
+  id: string;
+  title: string;
+  created_at: string;
+  updated_at: string;
+  messages: ChatSessionMessage[];
+}
+
+export interface ChatSessionSummary {
+  id: string;
+  title: string;
+  created_at: string;
+  updated_at: string;
+  message_count: number;
+}
+
+export interface LlmQueueStatus {
+  max_concurrent: number;
+  running: number;
+  waiting: number;
+}
+
+export type LlmTask = "explanation" | "analysis" | "structured" | "chat";
+
+/** Why AI analysis is slow or unavailable */
+export interface LlmDiagnostics {
+  endpoint: string;
+  reachable: boolean;
+  version: string | null;
+  /** Set when a cloud provider replaces Ollama */
+  cloud_model: string | null;
+  models: ModelFit[];
+  loaded_models: RunningModel[];
+  task_models: TaskModels[];
+  memory_total_bytes: number;
+  memory_available_bytes: number;
+  vram_total_bytes: number | null;
+  queue: LlmQueueStatus;
+  probe: GenerationProbe | null;
+  probe_error: string | null;
+  /** Findings in plain language, most important first */
+  hints: string[];
+}
+
+export interface ModelFit {
+  name: string;
+  size_bytes: number;
+  parameter_size: string | null;
+  /** Weights plus about 20% for the context */
+  required_bytes: number;
+  fits_in_ram: boolean;
+  /** null when the GPU memory is unknown */
+  fits_in_vram: boolean | null;
+  loaded: boolean;
+}
+
+export interface RunningModel {
+  name: string;
+  size: number;
+  /** Part of `size` in GPU memory */
+  size_vram: number;
+  expires_at: string | null;
+}
+
+export interface GenerationProbe {
+  model: string;
+  total_ms: number;
+  load_ms: number;
+  prompt_eval_ms: number;
+  tokens_per_second: number | null;
+}
+
+export interface TaskModels {
+  task: LlmTask;
+  /** Models in the order they are tried */
+  models: string[];
+  /** null when the general timeout applies */
+  timeout_seconds: number | null;
 }
 
 // Config types
@@ -156,12 +990,708 @@ export interface Config {
   ollama: {
     enabled: boolean;
     endpoint: string;
+    /** Used when `endpoint` is unreachable */
+    fallback_endpoints: string[];
     model: string;
+    /** Small model for quick process explanations */
+    fast_model: string;
+    /** Model for embeddings used by the similarity search */
+    embedding_model: string;
     timeout_seconds: number;
+    /** Per-task timeouts; null uses `timeout_seconds` */
+    task_timeouts: {
+      explanation_seconds: number | null;
+      analysis_seconds: number | null;
+      structured_seconds: number | null;
+      chat_seconds: number | null;
+    };
+    /** Fall back to other installed models when the configured one is missing or times out */
+    auto_select_models: boolean;
+    max_concurrent_generations: number;
+    max_queued_generations: number;
+    queue_timeout_seconds: number;
+    /** Language of AI answers, ISO 639-1 code such as "de" or "en" */
+    language: string;
   };
   security: {
     audit_logging: boolean;
     require_confirmation_for_kill: boolean;
     privilege_cache_ttl_minutes: number;
+    /** Destructive commands only return an impact preview */
+    dry_run: boolean;
+  };
+  simulation_mode: boolean;
+  digest: {
+    enabled: boolean;
+    /** e.g. "monday" */
+    weekday: string;
+    /** Local hour (0-23) */
+    hour: number;
+    llm_summary: boolean;
+  };
+  remote_viewer: {
+    /** 0 picks a free port */
+    port: number;
+    /** Paired devices have to pair again after this many hours */
+    session_hours: number;
   };
+  port_watch: {
+    enabled: boolean;
+    interval_seconds: number;
+    /** Desktop notification when a port opens on a non-loopback address */
+    notify_public_ports: boolean;
+  };
+  port_history: {
+    enabled: boolean;
+    interval_seconds: number;
+    /** Days of snapshots to keep */
+    retention_days: number;
+  };
+  /** MaxMind/DB-IP country database (.mmdb) for locating remote peers */
+  geoip_database: string | null;
+  /** Read-only; change through the reservation commands */
+  port_reservations: PortReservation[];
+  /** Security findings accepted as known risks. Read-only; change through the acknowledgement commands */
+  acknowledged_issues: AcknowledgedIssue[];
+  /** Directory overrides; null keeps the default (or portable) location */
+  storage: StorageConfig;
+  /** Read-only; change through the tunnel commands */
+  tunnels: TunnelSpec[];
+  /** Names for ports, replacing the IANA registration */
+  service_names: ServiceNameOverride[];
+  nmap: NmapConfig;
+  monitoring_history: MonitoringHistoryConfig;
+  alerts: AlertConfig;
+  notifications: NotificationConfig;
+  flapping: FlappingConfig;
+  resource_events: ResourceEventsConfig;
+  /** Outbound webhooks for service, alert and security events */
+  webhooks: WebhookConfig[];
+  disk_watch: DiskWatchConfig;
+  powermetrics: PowermetricsConfig;
+  memory_watch: MemoryWatchConfig;
+  hash_reputation: HashReputationConfig;
+  cloud_llm: CloudLlmConfig;
+}
+
+export type CloudProvider = "anthropic" | "openai";
+
+/** Cloud model used instead of Ollama; prompts are sanitized and every request is audit logged */
+export interface CloudLlmConfig {
+  enabled: boolean;
+  provider: CloudProvider;
+  api_key: string | null;
+  /** Empty for the provider's default model */
+  model: string;
+}
+
+export type ReputationProvider = "malwarebazaar" | "virustotal";
+
+/** Online lookup of executable hashes; only the SHA-256 is sent */
+export interface HashReputationConfig {
+  enabled: boolean;
+  provider: ReputationProvider;
+  /** VirusTotal API key or abuse.ch Auth-Key */
+  api_key: string | null;
+}
+
+export type ReputationStatus = "malicious" | "suspicious" | "clean" | "unknown";
+
+export interface HashVerdict {
+  sha256: string;
+  provider: ReputationProvider;
+  status: ReputationStatus;
+  /** Engines that flagged the file, VirusTotal only */
+  detections: number | null;
+  engines: number | null;
+  /** Malware family or threat label */
+  label: string | null;
+  link: string;
+  checked_at: string;
+}
+
+export interface ProcessExplanation {
+  explanation: string;
+  /** Set for processes from the knowledge base */
+  safety: ProcessSafety | null;
+  category: ProcessCategory | null;
+  /** Set when hash lookups are enabled and the path is an executable */
+  reputation: HashVerdict | null;
+}
+
+export type ProcessSafety = "essential" | "important" | "optional" | "unknown";
+
+export type ProcessCategory =
+  | "system"
+  | "application"
+  | "browser"
+  | "development"
+  | "database"
+  | "communication"
+  | "productivity"
+  | "security"
+  | "media"
+  | "utility"
+  | "other";
+
+/** Patterns match the whole process name case-insensitively; `*` matches anything */
+export interface ProcessKnowledgeEntry {
+  name: string;
+  patterns: string[];
+  exclude?: string[];
+  max_length?: number | null;
+  explanation: string;
+  safety: ProcessSafety;
+  category: ProcessCategory;
+}
+
+/** Apple GPU sampler; needs a sudoers rule for passwordless powermetrics */
+export interface PowermetricsConfig {
+  enabled: boolean;
+  interval_ms: number;
+}
+
+export interface PowermetricsStatus {
+  /** macOS only */
+  supported: boolean;
+  enabled: boolean;
+  running: boolean;
+  last_sample_at: string | null;
+  last_error: string | null;
+}
+
+export type WebhookEvent = "service_status" | "alert" | "security_finding";
+
+/** An HTTP endpoint that receives events as JSON POSTs */
+export interface WebhookConfig {
+  id: string;
+  name: string;
+  url: string;
+  /** Signs the body with HMAC-SHA256 (X-Network-Manager-Signature: sha256=...) */
+  secret: string | null;
+  enabled: boolean;
+  /** Empty delivers all events */
+  events: WebhookEvent[];
+}
+
+/** A service restarting more than max_restarts times within window_minutes is flapping */
+export interface FlappingConfig {
+  max_restarts: number;
+  window_minutes: number;
+}
+
+/** Minimum change in CPU (percentage points) or memory before a ServiceResourceChanged event */
+export interface ResourceEventsConfig {
+  enabled: boolean;
+  cpu_delta_percent: number;
+  memory_delta_bytes: number;
+}
+
+/** Desktop notifications per event category */
+export interface NotificationConfig {
+  service_crashed: boolean;
+  security_critical: boolean;
+  /** New public listeners and taken port reservations */
+  port_opened: boolean;
+  alerts: boolean;
+  /** A disk crossed the disk watch threshold */
+  disk_space: boolean;
+  /** Memory is about to run out */
+  memory_pressure: boolean;
+  /** Closing the window hides it to the tray instead of quitting */
+  run_in_background: boolean;
+}
+
+/** Warn when a filesystem is filled beyond threshold_percent */
+export interface DiskWatchConfig {
+  enabled: boolean;
+  threshold_percent: number;
+  interval_seconds: number;
+}
+
+/** Warn before memory runs out; any one condition counts as pressure */
+export interface MemoryWatchConfig {
+  enabled: boolean;
+  interval_seconds: number;
+  available_percent: number;
+  swap_growth_mb_per_minute: number;
+  exhaustion_minutes: number;
+  /** Processes attached to the event */
+  top_consumers: number;
+}
+
+/** Payload of the MemoryPressure service event */
+export interface MemoryPressure {
+  memory: MemoryStats;
+  /** Negative while available memory shrinks */
+  available_trend_bytes_per_minute: number;
+  swap_growth_bytes_per_minute: number;
+  minutes_until_exhausted: number | null;
+  top_consumers: ProcessUsage[];
+}
+
+export interface AlertConfig {
+  interval_seconds: number;
+  rules: AlertRule[];
+}
+
+export type AlertCondition =
+  | { kind: "cpu_above"; percent: number; minutes: number }
+  | { kind: "memory_above"; bytes: number; minutes: number }
+  | { kind: "service_stopped" }
+  | { kind: "new_public_port" };
+
+export interface AlertRule {
+  id: string;
+  name: string;
+  enabled: boolean;
+  /** null watches the whole system (CPU/memory) or every service */
+  service_id: string | null;
+  condition: AlertCondition;
+}
+
+/** A fired alert rule */
+export interface Alert {
+  id: string;
+  rule_id: string;
+  rule_name: string;
+  service_id: string | null;
+  message: string;
+  /** Measured value for threshold rules (percent or bytes) */
+  value: number | null;
+  triggered_at: string;
+}
+
+/** A service event as buffered by the monitor; `event` has the shape of the "service-event" payload */
+export interface RecordedEvent<E = unknown> {
+  /** Increases with every event */
+  id: number;
+  timestamp: string;
+  event: E;
+}
+
+/** Service, CPU/memory and port samples stored in SQLite for charts */
+export interface MonitoringHistoryConfig {
+  enabled: boolean;
+  interval_seconds: number;
+  /** Days of samples to keep */
+  retention_days: number;
+}
+
+/** Recorded system usage, averaged per chart bucket */
+export interface SystemPoint {
+  taken_at: string;
+  cpu_percent: number;
+  memory_used: number;
+  memory_total: number;
+}
+
+export interface ServicePoint {
+  taken_at: string;
+  /** Status at the end of the bucket */
+  status: ServiceStatus;
+  cpu_percent: number | null;
+  memory_bytes: number | null;
+}
+
+export interface PortCountPoint {
+  taken_at: string;
+  listening: number;
+  /** Listeners not bound to loopback */
+  public: number;
+}
+
+/** Opt-in nmap backend for port scans; the built-in scanner is the fallback */
+export interface NmapConfig {
+  enabled: boolean;
+  /** Probe open ports for product and version (-sV) */
+  version_detection: boolean;
+  /** Guess the operating system (-O); needs root */
+  os_detection: boolean;
+  /** Timing template 0-4 (-T) */
+  timing: number;
+}
+
+export interface NmapStatus {
+  installed: boolean;
+  path: string | null;
+  version: string | null;
+  /** Enabled in the config and installed */
+  active: boolean;
+}
+
+/** User-defined name for a port, e.g. 3000 → "frontend" */
+export interface ServiceNameOverride {
+  port: number;
+  /** null names the port for TCP and UDP */
+  protocol: Protocol | null;
+  name: string;
+}
+
+export interface StorageConfig {
+  config_dir: string | null;
+  data_dir: string | null;
+  log_dir: string | null;
+}
+
+/** Effective directories; portable mode keeps them next to the executable */
+export interface StoragePaths {
+  portable: boolean;
+  config_dir: string;
+  data_dir: string;
+  log_dir: string;
+}
+
+/** A port kept free for a team convention, e.g. 3000 for the frontend dev server */
+export interface PortReservation {
+  port: number;
+  label: string;
+  /** Process expected on the port; any other listener triggers a warning */
+  owner: string | null;
+  created_at: string;
+}
+
+/** A listener as recorded in the port baseline */
+export interface BaselineListener {
+  port: number;
+  protocol: Protocol;
+  family: AddressFamily;
+  bind_address: string;
+  process_name: string | null;
+}
+
+/** Listeners expected at the time of recording; everything else is reported */
+export interface ListenerBaseline {
+  recorded_at: string;
+  listeners: BaselineListener[];
+}
+
+/** A listener that isn't part of the baseline */
+export interface BaselineDeviation {
+  port: PortInfo;
+  /** Process recorded for the same address and port, when another process listens there now */
+  expected_process: string | null;
+}
+
+// Tunnel types
+export type TunnelKind = "local" | "remote";
+export type TunnelState = "stopped" | "connecting" | "connected" | "reconnecting" | "failed";
+
+/** SSH port forward, e.g. localhost:5432 to prod-db:5432 via a bastion */
+export interface TunnelSpec {
+  /** Empty for new tunnels; the backend assigns one */
+  id: string;
+  name: string;
+  /** local = ssh -L, remote = ssh -R */
+  kind: TunnelKind;
+  /** Port opened on this machine (local) or on the SSH server (remote) */
+  bind_port: number;
+  /** Defaults to localhost */
+  bind_address: string | null;
+  target_host: string;
+  target_port: number;
+  /** user@host or a Host alias from ~/.ssh/config */
+  ssh_host: string;
+  ssh_port: number | null;
+  jump_host: string | null;
+  identity_file: string | null;
+  auto_reconnect: boolean;
+  autostart: boolean;
+}
+
+export interface TunnelStatus {
+  spec: TunnelSpec;
+  state: TunnelState;
+  /** PID of the ssh process */
+  pid: number | null;
+  last_error: string | null;
+  reconnects: number;
+  connected_since: string | null;
+}
+
+// Remote viewer types
+export interface PairingInfo {
+  /** Viewer URL including the one-time pairing code */
+  url: string;
+  device_id: string;
+  host: string;
+  port: number;
+  /** QR code of `url` as SVG markup */
+  qr_svg: string;
+  created_at: string;
+  /** The code can't be redeemed after this */
+  expires_at: string;
+}
+
+export interface PairedDeviceInfo {
+  id: string;
+  /** The QR code hasn't been scanned yet */
+  pending: boolean;
+  paired_at: string;
+  expires_at: string;
+  last_seen: string | null;
+  user_agent: string | null;
+}
+
+export interface RemoteViewerStatus {
+  running: boolean;
+  port: number | null;
+  /** Devices with an active session */
+  paired_devices: number;
+  paired_since: string | null;
+  devices: PairedDeviceInfo[];
+}
+
+// Cleanup types
+export type CleanupCategory = "docker_prune" | "duplicate" | "idle_service" | "startup_impact";
+export type CleanupRisk = "low" | "medium" | "high";
+
+export type CleanupAction =
+  | { type: "stop_service"; service_id: string }
+  | { type: "disable_autostart"; service_id: string; service_type: ServiceType }
+  | { type: "remove_image"; image_id: string }
+  | { type: "remove_volume"; name: string };
+
+export interface CleanupStep {
+  id: string;
+  category: CleanupCategory;
+  action: CleanupAction;
+  title: string;
+  reason: string;
+  risk: CleanupRisk;
+  expected_savings_bytes: number | null;
+}
+
+/** Ordered cleanup suggestions, safest first */
+export interface CleanupPlan {
+  id: string;
+  created_at: string;
+  steps: CleanupStep[];
+  total_savings_bytes: number;
+}
+
+export interface CleanupStepResult {
+  step_id: string;
+  status: "executed" | "failed" | "dry_run";
+  error: string | null;
+}
+
+/** Nothing was changed unless `status` is "executed" */
+export type CleanupReport = ActionOutcome & {
+  plan_id: string;
+  results: CleanupStepResult[];
+  freed_bytes: number;
+};
+
+export type ProposedActionKind =
+  | { type: "stop_service"; service_id: string }
+  | { type: "disable_autostart"; service_id: string; service_type: ServiceType }
+  | { type: "free_port"; port: number; service_id: string | null; pid: number | null };
+
+/** An action the model proposed as a tool call */
+export interface ProposedAction {
+  id: string;
+  action: ProposedActionKind;
+  title: string;
+  reason: string;
+}
+
+/** Nothing runs until the accepted actions are passed to executeProposedActions */
+export interface ActionProposal {
+  id: string;
+  created_at: string;
+  actions: ProposedAction[];
+  /** Tool calls dropped for unknown or protected targets */
+  rejected: string[];
+}
+
+export interface ProposedActionResult {
+  action_id: string;
+  status: "executed" | "failed" | "dry_run" | "simulated";
+  error: string | null;
+}
+
+export type SessionChangeKind = "stopped" | "autostart_disabled";
+
+/** Service state changed by the app in the current session */
+export interface SessionChange {
+  service_id: string;
+  service_type: ServiceType;
+  kind: SessionChangeKind;
+  changed_at: string;
+}
+
+export interface SessionRestoreResult {
+  change: SessionChange;
+  success: boolean;
+  error: string | null;
+}
+
+export interface SessionRestoreReport {
+  results: SessionRestoreResult[];
+  restored: number;
+  failed: number;
+}
+
+/** Weekly summary, also delivered as a "digest-ready" event */
+export interface DigestReport {
+  generated_at: string;
+  period_start: string;
+  period_end: string;
+  markdown: string;
+  html: string;
+  path: string | null;
+}
+
+// LAN discovery types
+export type NetworkServiceCategory =
+  | "web"
+  | "remote_access"
+  | "file_sharing"
+  | "printer"
+  | "media"
+  | "smart_home"
+  | "other";
+
+/** A service instance announced via mDNS/DNS-SD */
+export interface NetworkService {
+  /** Instance name, e.g. "Office Printer" */
+  name: string;
+  /** DNS-SD type, e.g. "_ipp._tcp" */
+  service_type: string;
+  category: NetworkServiceCategory;
+  host: string | null;
+  port: number | null;
+  addresses: string[];
+  txt: Record<string, string>;
+}
+
+export interface NetworkDiscovery {
+  services: NetworkService[];
+  /** Service types seen on the network, including ones without instances */
+  service_types: string[];
+  scanned_at: string;
+}
+
+export interface SsdpDevice {
+  location: string;
+  address: string;
+  server: string | null;
+  device_type: string | null;
+  friendly_name: string | null;
+  manufacturer: string | null;
+  model_name: string | null;
+  /** Internet gateway that manages port mappings */
+  is_gateway: boolean;
+}
+
+export interface UpnpPortMapping {
+  external_port: number;
+  protocol: string;
+  internal_client: string;
+  internal_port: number;
+  description: string;
+  enabled: boolean;
+  /** Seconds, 0 means permanent */
+  lease_duration: number;
+  remote_host: string;
+  to_this_host: boolean;
+}
+
+export interface UpnpGateway {
+  location: string;
+  friendly_name: string | null;
+  external_ip: string | null;
+  mappings: UpnpPortMapping[];
+  error: string | null;
+}
+
+export interface SsdpDiscovery {
+  devices: SsdpDevice[];
+  gateways: UpnpGateway[];
+  issues: SecurityIssue[];
+  scanned_at: string;
+}
+
+export type ExportKind = "audit" | "security" | "inventory" | "metrics";
+
+export type ExportFormat = "json" | "csv" | "markdown" | "html";
+
+/** Formats of `export_security_report`; SARIF 2.1.0 for code scanning tools */
+export type ReportFormat = "html" | "pdf" | "sarif";
+
+export interface ExportResult {
+  path: string;
+  bytes: number;
+  /** User template the file was rendered with, null for the built-in one */
+  template: string | null;
+}
+
+export interface ToolStatus {
+  tool: string;
+  purpose: string;
+  installed: boolean;
+  path: string | null;
+  version: string | null;
+  /** Whether the daemon answers (docker, ollama); null for plain CLIs */
+  running: boolean | null;
+  /** Shell commands that install the tool on this platform, in order */
+  install_commands: string[];
+  docs_url: string;
+}
+
+export interface BaselineSummary {
+  created_at: string;
+  services: number;
+  ports: number;
+  security_issues: number;
+  path: string;
+}
+
+export interface BootstrapStatus {
+  /** Setup has neither been completed nor skipped */
+  first_run: boolean;
+  completed_at: string | null;
+  platform: string;
+  tools: ToolStatus[];
+  model: string;
+  model_installed: boolean;
+  baseline: BaselineSummary | null;
+}
+
+/** Payload of `model-pull-progress` events */
+export interface PullProgress {
+  model: string;
+  status: string;
+  completed: number | null;
+  total: number | null;
+}
+
+/** Recorded port table at one point in time */
+export interface PortSnapshot {
+  taken_at: string;
+  ports: PortInfo[];
+}
+
+/** One listener on a port over time */
+export interface PortOccupancy {
+  port: PortInfo;
+  first_seen: string;
+  last_seen: string;
+  /** First snapshot without the listener, null if it was still there at the end */
+  ended_at: string | null;
+}
+
+export interface PortOwnerChange {
+  before: PortInfo;
+  after: PortInfo;
+}
+
+export interface PortHistoryDiff {
+  /** Snapshots actually compared */
+  from: string;
+  to: string;
+  opened: PortInfo[];
+  closed: PortInfo[];
+  owner_changed: PortOwnerChange[];
 }
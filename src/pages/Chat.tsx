@@ -0,0 +1,187 @@
+import { useEffect, useRef, useState } from "react";
+import { Card, CardContent, CardHeader, CardTitle } from "../components/ui/card";
+import { Button } from "../components/ui/button";
+import { Bot, MessageSquarePlus, Send, Trash2, User } from "lucide-react";
+import { cn } from "../lib/utils";
+import * as api from "../lib/tauri/commands";
+import type { ChatSession, ChatSessionSummary } from "../lib/tauri/types";
+
+export function Chat() {
+  const [sessions, setSessions] = useState<ChatSessionSummary[]>([]);
+  const [session, setSession] = useState<ChatSession | null>(null);
+  const [input, setInput] = useState("");
+  const [isSending, setIsSending] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const bottomRef = useRef<HTMLDivElement>(null);
+
+  useEffect(() => {
+    refreshSessions();
+  }, []);
+
+  useEffect(() => {
+    bottomRef.current?.scrollIntoView({ behavior: "smooth" });
+  }, [session?.messages.length, isSending]);
+
+  const refreshSessions = async () => {
+    try {
+      setSessions(await api.listChatSessions());
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const openSession = async (id: string) => {
+    setError(null);
+    try {
+      setSession(await api.getChatSession(id));
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const handleNew = () => {
+    setSession(null);
+    setError(null);
+  };
+
+  const handleDelete = async (id: string) => {
+    try {
+      await api.deleteChatSession(id);
+      if (session?.id === id) setSession(null);
+      await refreshSessions();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const handleSend = async () => {
+    const message = input.trim();
+    if (!message || isSending) return;
+
+    setIsSending(true);
+    setError(null);
+    try {
+      const current = session ?? (await api.createChatSession());
+      // Show the question right away; the answer can take a while
+      setSession({
+        ...current,
+        messages: [...current.messages, { role: "user", content: message, created_at: new Date().toISOString() }],
+      });
+      setInput("");
+      await api.sendChatMessage(current.id, message);
+      setSession(await api.getChatSession(current.id));
+      await refreshSessions();
+    } catch (err) {
+      setError(String(err));
+      setInput(message);
+      if (session) setSession(await api.getChatSession(session.id).catch(() => session));
+    } finally {
+      setIsSending(false);
+    }
+  };
+
+  return (
+    <div className="space-y-6">
+      {/* Header */}
+      <div>
+        <h1 className="text-3xl font-bold tracking-tight">Assistant</h1>
+        <p className="text-muted-foreground">
+          Ask about your services, ports and security findings; follow-up questions keep the context
+        </p>
+      </div>
+
+      <div className="grid gap-6 lg:grid-cols-[16rem_1fr]">
+        {/* Sessions */}
+        <Card>
+          <CardHeader>
+            <div className="flex items-center justify-between">
+              <CardTitle className="text-lg">Conversations</CardTitle>
+              <Button variant="ghost" size="icon" onClick={handleNew} title="New conversation">
+                <MessageSquarePlus className="h-4 w-4" />
+              </Button>
+            </div>
+          </CardHeader>
+          <CardContent className="space-y-1">
+            {sessions.length === 0 && <p className="text-sm text-muted-foreground">No conversations yet</p>}
+            {sessions.map((s) => (
+              <div
+                key={s.id}
+                className={cn(
+                  "group flex items-center gap-2 rounded-md px-2 py-1.5 text-sm hover:bg-accent",
+                  session?.id === s.id && "bg-accent"
+                )}
+              >
+                <button className="min-w-0 flex-1 text-left" onClick={() => openSession(s.id)}>
+                  <p className="truncate">{s.title}</p>
+                  <p className="text-xs text-muted-foreground">{new Date(s.updated_at).toLocaleString()}</p>
+                </button>
+                <button
+                  className="opacity-0 group-hover:opacity-100"
+                  onClick={() => handleDelete(s.id)}
+                  title="Delete conversation"
+                >
+                  <Trash2 className="h-4 w-4 text-muted-foreground" />
+                </button>
+              </div>
+            ))}
+          </CardContent>
+        </Card>
+
+        {/* Conversation */}
+        <Card className="flex min-h-[32rem] flex-col">
+          <CardHeader>
+            <CardTitle className="text-lg">{session?.title ?? "New conversation"}</CardTitle>
+          </CardHeader>
+          <CardContent className="flex flex-1 flex-col gap-4">
+            <div className="flex-1 space-y-4 overflow-y-auto">
+              {(!session || session.messages.length === 0) && (
+                <p className="text-sm text-muted-foreground">
+                  For example: "Which services are listening on public interfaces?" and then "Which of those can I
+                  safely disable?"
+                </p>
+              )}
+              {session?.messages.map((message, index) => (
+                <div key={index} className="flex gap-3">
+                  {message.role === "user" ? (
+                    <User className="mt-0.5 h-5 w-5 shrink-0 text-muted-foreground" />
+                  ) : (
+                    <Bot className="mt-0.5 h-5 w-5 shrink-0 text-primary" />
+                  )}
+                  <p className="whitespace-pre-wrap text-sm">{message.content}</p>
+                </div>
+              ))}
+              {isSending && (
+                <div className="flex gap-3">
+                  <Bot className="mt-0.5 h-5 w-5 shrink-0 animate-pulse text-primary" />
+                  <p className="text-sm text-muted-foreground">Thinking...</p>
+                </div>
+              )}
+              <div ref={bottomRef} />
+            </div>
+
+            {error && <p className="text-sm text-destructive">{error}</p>}
+
+            <div className="flex gap-2">
+              <textarea
+                value={input}
+                onChange={(e) => setInput(e.target.value)}
+                onKeyDown={(e) => {
+                  if (e.key === "Enter" && !e.shiftKey) {
+                    e.preventDefault();
+                    handleSend();
+                  }
+                }}
+                placeholder="Ask a question..."
+                rows={2}
+                className="flex-1 resize-none rounded-md border bg-background p-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <Button onClick={handleSend} disabled={isSending || !input.trim()}>
+                <Send className="h-4 w-4" />
+              </Button>
+            </div>
+          </CardContent>
+        </Card>
+      </div>
+    </div>
+  );
+}
@@ -1,4 +1,4 @@
-import { useEffect } from "react";
+import { useEffect, useState } from "react";
 import { Card, CardContent, CardHeader, CardTitle } from "../components/ui/card";
 import { Badge } from "../components/ui/badge";
 import { useServiceStore } from "../stores/serviceStore";
@@ -6,15 +6,29 @@ import { usePortStore } from "../stores/portStore";
 import { Server, Network, Activity, AlertCircle } from "lucide-react";
 import { ServiceInfoButton } from "../components/services/ServiceInfoButton";
 import { Recommendations } from "../components/dashboard/Recommendations";
+import { ProposedActions } from "../components/dashboard/ProposedActions";
+import { AlertsCard } from "../components/dashboard/AlertsCard";
+import { ActivityCard } from "../components/dashboard/ActivityCard";
 import { ResourceStats } from "../components/services/ResourceBar";
+import * as api from "../lib/tauri/commands";
+import type { HostInfo } from "../lib/tauri/types";
+
+function formatUptime(seconds: number): string {
+  const days = Math.floor(seconds / 86400);
+  const hours = Math.floor((seconds % 86400) / 3600);
+  const minutes = Math.floor((seconds % 3600) / 60);
+  return days > 0 ? `${days}d ${hours}h` : `${hours}h ${minutes}m`;
+}
 
 export function Dashboard() {
   const { services, fetchServices, isLoading: servicesLoading } = useServiceStore();
   const { ports, fetchPortUsage, isLoading: portsLoading } = usePortStore();
+  const [host, setHost] = useState<HostInfo | null>(null);
 
   useEffect(() => {
     fetchServices();
     fetchPortUsage();
+    api.getHostInfo().then(setHost).catch(() => {});
   }, [fetchServices, fetchPortUsage]);
 
   const runningServices = services.filter((s) => s.status === "running").length;
@@ -32,6 +46,16 @@ export function Dashboard() {
         <p className="text-muted-foreground">
           Overview of your services and network status
         </p>
+        {host && (
+          <p className="mt-1 text-sm text-muted-foreground">
+            {[host.hostname, host.os_version, host.kernel_version && `Kernel ${host.kernel_version}`, host.arch]
+              .filter(Boolean)
+              .join(" • ")}
+            {` • up ${formatUptime(host.uptime_seconds)}`}
+            {host.load_average &&
+              ` • load ${host.load_average.one.toFixed(2)} ${host.load_average.five.toFixed(2)} ${host.load_average.fifteen.toFixed(2)}`}
+          </p>
+        )}
       </div>
 
       {/* Stats Grid */}
@@ -181,8 +205,15 @@ export function Dashboard() {
         </Card>
       </div>
 
+      {/* Alerts */}
+      <AlertsCard />
+
+      {/* Recent service events */}
+      <ActivityCard />
+
       {/* AI Recommendations */}
       <Recommendations services={services} />
+      <ProposedActions />
 
       {/* Errors Section */}
       {errorServices > 0 && (
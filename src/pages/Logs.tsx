@@ -1,4 +1,5 @@
 import { useState, useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
 import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "../components/ui/card";
 import { Button } from "../components/ui/button";
 import { Badge } from "../components/ui/badge";
@@ -14,12 +15,28 @@ import {
   Trash2,
   Search,
   ChevronDown,
+  Layers,
 } from "lucide-react";
 import * as api from "../lib/tauri/commands";
-import type { AuditEntry } from "../lib/tauri/types";
+import type {
+  AuditAnswer, AuditEntry, ExportFormat, LogAnalysisProgress, LogClusterReport, SimilarAnalysis, StoredAnalysis,
+} from "../lib/tauri/types";
 
 type AnalysisType = "error" | "pattern" | "anomaly" | "performance" | "security";
 
+function describeProgress(progress: LogAnalysisProgress | null): string {
+  switch (progress?.stage) {
+    case "clustering":
+      return "Grouping similar lines...";
+    case "summarizing":
+      return `Summarizing part ${progress.completed + 1} of ${progress.total}...`;
+    case "combining":
+      return `Combining summaries (${progress.completed + 1} of ${progress.total})...`;
+    default:
+      return "Analyzing...";
+  }
+}
+
 const analysisTypes: { value: AnalysisType; label: string; description: string }[] = [
   { value: "error", label: "Error Detection", description: "Find and explain errors in logs" },
   { value: "pattern", label: "Pattern Analysis", description: "Identify recurring patterns" },
@@ -34,6 +51,11 @@ export function Logs() {
   const [logsLoading, setLogsLoading] = useState(false);
   const [logsError, setLogsError] = useState<string | null>(null);
   const [searchTerm, setSearchTerm] = useState("");
+  const [exportFormat, setExportFormat] = useState<ExportFormat>("json");
+  const [exportedPath, setExportedPath] = useState<string | null>(null);
+  const [auditQuestion, setAuditQuestion] = useState("");
+  const [auditAnswer, setAuditAnswer] = useState<AuditAnswer | null>(null);
+  const [isAsking, setIsAsking] = useState(false);
 
   // LLM state
   const [ollamaStatus, setOllamaStatus] = useState<"checking" | "connected" | "disconnected">("checking");
@@ -42,7 +64,13 @@ export function Logs() {
   const [logInput, setLogInput] = useState("");
   const [analysisType, setAnalysisType] = useState<AnalysisType>("error");
   const [analysisResult, setAnalysisResult] = useState<string | null>(null);
+  const [similarAnalyses, setSimilarAnalyses] = useState<SimilarAnalysis[]>([]);
+  const [openedAnalysis, setOpenedAnalysis] = useState<StoredAnalysis | null>(null);
   const [isAnalyzing, setIsAnalyzing] = useState(false);
+  const [analysisProgress, setAnalysisProgress] = useState<LogAnalysisProgress | null>(null);
+  const [groupLines, setGroupLines] = useState(false);
+  const [clusterReport, setClusterReport] = useState<LogClusterReport | null>(null);
+  const [isClustering, setIsClustering] = useState(false);
   const [analysisError, setAnalysisError] = useState<string | null>(null);
 
   // Fetch audit logs on mount
@@ -93,31 +121,65 @@ export function Logs() {
     setIsAnalyzing(true);
     setAnalysisError(null);
     setAnalysisResult(null);
+    setSimilarAnalyses([]);
+    setOpenedAnalysis(null);
+    setAnalysisProgress(null);
+    const unlisten = await listen<LogAnalysisProgress>("log-analysis-progress", (event) => {
+      setAnalysisProgress(event.payload);
+    });
 
     try {
       if (selectedModel) {
         await api.setOllamaModel(selectedModel);
       }
-      const result = await api.analyzeLogs(logInput, analysisType);
-      setAnalysisResult(result);
+      const result = await api.analyzeLogs(logInput, analysisType, groupLines);
+      setAnalysisResult(result.report);
+      setSimilarAnalyses(result.similar);
     } catch (error) {
       setAnalysisError(String(error));
     } finally {
+      unlisten();
       setIsAnalyzing(false);
     }
   };
 
+  const handleCluster = async () => {
+    setIsClustering(true);
+    setAnalysisError(null);
+    try {
+      setClusterReport(await api.clusterLogs(logInput));
+    } catch (error) {
+      setAnalysisError(String(error));
+    } finally {
+      setIsClustering(false);
+    }
+  };
+
+  const handleOpenAnalysis = async (id: string) => {
+    try {
+      setOpenedAnalysis(await api.getStoredAnalysis(id));
+    } catch (error) {
+      setAnalysisError(String(error));
+    }
+  };
+
+  const handleAskAuditLog = async () => {
+    if (!auditQuestion.trim()) return;
+    setIsAsking(true);
+    setLogsError(null);
+    try {
+      setAuditAnswer(await api.askAuditLog(auditQuestion));
+    } catch (error) {
+      setLogsError(String(error));
+    } finally {
+      setIsAsking(false);
+    }
+  };
+
   const handleExportLogs = async () => {
     try {
-      const exportData = await api.exportAuditLogs("json");
-      // Create download link
-      const blob = new Blob([exportData], { type: "application/json" });
-      const url = URL.createObjectURL(blob);
-      const a = document.createElement("a");
-      a.href = url;
-      a.download = `audit-logs-${new Date().toISOString().split("T")[0]}.json`;
-      a.click();
-      URL.revokeObjectURL(url);
+      const result = await api.exportReport("audit", exportFormat);
+      setExportedPath(result.path);
     } catch (error) {
       setLogsError(String(error));
     }
@@ -142,6 +204,8 @@ export function Logs() {
         return <AlertCircle className="h-4 w-4 text-amber-500" />;
       case "llm_analysis":
         return <Bot className="h-4 w-4 text-purple-500" />;
+      case "alert":
+        return <AlertCircle className="h-4 w-4 text-red-500" />;
       default:
         return <FileText className="h-4 w-4 text-muted-foreground" />;
     }
@@ -266,7 +330,7 @@ export function Logs() {
                   {isAnalyzing ? (
                     <>
                       <RefreshCw className="mr-2 h-4 w-4 animate-spin" />
-                      Analyzing...
+                      {describeProgress(analysisProgress)}
                     </>
                   ) : (
                     <>
@@ -275,6 +339,14 @@ export function Logs() {
                     </>
                   )}
                 </Button>
+                <Button variant="outline" onClick={handleCluster} disabled={isClustering || isAnalyzing || !logInput.trim()}>
+                  {isClustering ? <RefreshCw className="mr-2 h-4 w-4 animate-spin" /> : <Layers className="mr-2 h-4 w-4" />}
+                  Group Lines
+                </Button>
+                <label className="flex items-center gap-2 text-sm text-muted-foreground">
+                  <input type="checkbox" checked={groupLines} onChange={(e) => setGroupLines(e.target.checked)} />
+                  Group similar lines before analysis
+                </label>
                 {logInput && (
                   <Button variant="ghost" size="sm" onClick={() => setLogInput("")}>
                     <Trash2 className="mr-2 h-4 w-4" />
@@ -290,6 +362,47 @@ export function Logs() {
                 </div>
               )}
 
+              {/* Line groups */}
+              {clusterReport && (
+                <div className="rounded-md border bg-muted/50 p-4">
+                  <div className="mb-2 flex items-center gap-2">
+                    <Layers className="h-4 w-4 text-purple-500" />
+                    <span className="text-sm font-medium">
+                      {clusterReport.total_lines} lines in {clusterReport.clusters.length} groups
+                    </span>
+                    {clusterReport.skipped_lines > 0 && (
+                      <span className="text-xs text-muted-foreground">
+                        ({clusterReport.skipped_lines} lines beyond the limit not grouped)
+                      </span>
+                    )}
+                  </div>
+                  <div className="max-h-96 space-y-1 overflow-y-auto">
+                    {[...clusterReport.clusters]
+                      .sort((a, b) => Number(b.anomalous) - Number(a.anomalous))
+                      .map((cluster, index) => (
+                        <div key={index} className="flex items-start gap-2 rounded bg-background p-2 text-sm">
+                          <Badge variant={cluster.anomalous ? "destructive" : "secondary"} className="shrink-0">
+                            {cluster.occurrences}x
+                          </Badge>
+                          <div className="min-w-0 flex-1">
+                            <p className={`break-all font-mono text-xs ${cluster.contains_errors ? "text-red-500" : ""}`}>
+                              {cluster.representative}
+                            </p>
+                            {cluster.variants > 1 && (
+                              <p className="text-xs text-muted-foreground">{cluster.variants} variants</p>
+                            )}
+                          </div>
+                          {cluster.anomalous && (
+                            <Badge variant="outline" className="shrink-0">
+                              Unusual
+                            </Badge>
+                          )}
+                        </div>
+                      ))}
+                  </div>
+                </div>
+              )}
+
               {/* Results */}
               {analysisResult && (
                 <div className="rounded-md border bg-muted/50 p-4">
@@ -305,6 +418,31 @@ export function Logs() {
                       {analysisResult}
                     </pre>
                   </div>
+                  {similarAnalyses.length > 0 && (
+                    <div className="mt-3 space-y-1">
+                      <span className="text-sm font-medium">Similar past analyses</span>
+                      {similarAnalyses.map((similar) => (
+                        <button
+                          key={similar.id}
+                          className="block text-left text-sm text-primary hover:underline"
+                          onClick={() => handleOpenAnalysis(similar.id)}
+                        >
+                          You saw a similar {similar.kind === "incident" ? "incident" : "error"} on{" "}
+                          {new Date(similar.created_at).toLocaleDateString()}: {similar.title}
+                        </button>
+                      ))}
+                    </div>
+                  )}
+                  {openedAnalysis && (
+                    <div className="mt-3">
+                      <span className="text-sm font-medium">
+                        Report from {new Date(openedAnalysis.created_at).toLocaleString()}
+                      </span>
+                      <pre className="mt-1 whitespace-pre-wrap rounded bg-background p-3 text-sm">
+                        {openedAnalysis.report}
+                      </pre>
+                    </div>
+                  )}
                 </div>
               )}
             </>
@@ -322,6 +460,16 @@ export function Logs() {
               <Badge variant="secondary">{filteredLogs.length} entries</Badge>
             </div>
             <div className="flex items-center gap-2">
+              <select
+                value={exportFormat}
+                onChange={(e) => setExportFormat(e.target.value as ExportFormat)}
+                className="h-9 rounded-md border bg-background px-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              >
+                <option value="json">JSON</option>
+                <option value="csv">CSV</option>
+                <option value="markdown">Markdown</option>
+                <option value="html">HTML</option>
+              </select>
               <Button variant="outline" size="sm" onClick={handleExportLogs}>
                 <Download className="mr-2 h-4 w-4" />
                 Export
@@ -334,6 +482,50 @@ export function Logs() {
           </div>
         </CardHeader>
         <CardContent>
+          {exportedPath && (
+            <p className="mb-4 text-sm text-muted-foreground">
+              Exported to <span className="font-mono">{exportedPath}</span>
+            </p>
+          )}
+
+          {/* Ask */}
+          <div className="mb-4 flex max-w-2xl gap-2">
+            <input
+              type="text"
+              placeholder='Ask the audit log, e.g. "What stopped postgres last Tuesday?"'
+              value={auditQuestion}
+              onChange={(e) => setAuditQuestion(e.target.value)}
+              onKeyDown={(e) => e.key === "Enter" && handleAskAuditLog()}
+              className="h-10 flex-1 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+            <Button
+              variant="outline"
+              onClick={handleAskAuditLog}
+              disabled={isAsking || !auditQuestion.trim() || ollamaStatus !== "connected"}
+            >
+              {isAsking ? <RefreshCw className="mr-2 h-4 w-4 animate-spin" /> : <Bot className="mr-2 h-4 w-4" />}
+              Ask
+            </Button>
+          </div>
+
+          {auditAnswer && (
+            <div className="mb-4 rounded-md border bg-muted/50 p-4">
+              <p className="whitespace-pre-wrap text-sm">{auditAnswer.answer}</p>
+              {auditAnswer.entries.length > 0 && (
+                <div className="mt-3 space-y-1">
+                  <span className="text-sm font-medium">Entries used</span>
+                  {auditAnswer.entries.map((entry, index) => (
+                    <p key={entry.id} className="font-mono text-xs text-muted-foreground">
+                      [{index + 1}] {new Date(entry.timestamp).toLocaleString()} · {entry.event_type} · {entry.user} ·{" "}
+                      {entry.operation}
+                      {entry.success ? "" : " (failed)"}
+                    </p>
+                  ))}
+                </div>
+              )}
+            </div>
+          )}
+
           {/* Search */}
           <div className="mb-4 relative">
             <Search className="absolute left-3 top-1/2 h-4 w-4 -translate-y-1/2 text-muted-foreground" />
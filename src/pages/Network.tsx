@@ -0,0 +1,277 @@
+import { useState } from "react";
+import { Card, CardContent, CardHeader, CardTitle } from "../components/ui/card";
+import { Button } from "../components/ui/button";
+import { Badge } from "../components/ui/badge";
+import * as api from "../lib/tauri/commands";
+import type { NetworkDiscovery, NetworkServiceCategory, SsdpDiscovery } from "../lib/tauri/types";
+import { RefreshCw, Router, Search } from "lucide-react";
+
+const categoryLabels: Record<NetworkServiceCategory, string> = {
+  web: "Web",
+  remote_access: "Remote Access",
+  file_sharing: "File Sharing",
+  printer: "Printer",
+  media: "Media",
+  smart_home: "Smart Home",
+  other: "Other",
+};
+
+function formatEndpoint(address: string | undefined, port: number | null): string {
+  if (!address) return port ? `:${port}` : "-";
+  if (!port) return address;
+  return address.includes(":") ? `[${address}]:${port}` : `${address}:${port}`;
+}
+
+function formatLease(seconds: number): string {
+  if (seconds === 0) return "Permanent";
+  if (seconds < 3600) return `${Math.round(seconds / 60)} min`;
+  return `${Math.round(seconds / 3600)} h`;
+}
+
+export function Network() {
+  const [discovery, setDiscovery] = useState<NetworkDiscovery | null>(null);
+  const [isBrowsing, setIsBrowsing] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [searchTerm, setSearchTerm] = useState("");
+  const [upnp, setUpnp] = useState<SsdpDiscovery | null>(null);
+  const [isScanningUpnp, setIsScanningUpnp] = useState(false);
+
+  const handleBrowse = async () => {
+    setIsBrowsing(true);
+    setError(null);
+    try {
+      setDiscovery(await api.discoverNetworkServices());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsBrowsing(false);
+    }
+  };
+
+  const handleUpnpScan = async () => {
+    setIsScanningUpnp(true);
+    setError(null);
+    try {
+      setUpnp(await api.discoverUpnpDevices());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsScanningUpnp(false);
+    }
+  };
+
+  const term = searchTerm.toLowerCase();
+  const filteredServices = (discovery?.services ?? []).filter(
+    (service) =>
+      service.name.toLowerCase().includes(term) ||
+      service.service_type.includes(term) ||
+      service.host?.toLowerCase().includes(term) ||
+      service.addresses.some((address) => address.includes(term))
+  );
+
+  return (
+    <div className="space-y-6">
+      {/* Header */}
+      <div className="flex items-center justify-between">
+        <div>
+          <h1 className="text-3xl font-bold tracking-tight">Network</h1>
+          <p className="text-muted-foreground">
+            Services and devices announced on the local network
+          </p>
+        </div>
+        <div className="flex items-center gap-2">
+          <Button onClick={handleUpnpScan} disabled={isScanningUpnp} variant="outline">
+            <Router className={`mr-2 h-4 w-4 ${isScanningUpnp ? "animate-pulse" : ""}`} />
+            {isScanningUpnp ? "Searching..." : "Scan UPnP"}
+          </Button>
+          <Button onClick={handleBrowse} disabled={isBrowsing}>
+            <RefreshCw className={`mr-2 h-4 w-4 ${isBrowsing ? "animate-spin" : ""}`} />
+            {isBrowsing ? "Browsing..." : "Browse mDNS"}
+          </Button>
+        </div>
+      </div>
+      {error && <p className="text-sm text-destructive">{error}</p>}
+
+      {/* Search */}
+      <div className="relative max-w-md">
+        <Search className="absolute left-3 top-1/2 h-4 w-4 -translate-y-1/2 text-muted-foreground" />
+        <input
+          type="text"
+          placeholder="Search by name, type, host or address..."
+          value={searchTerm}
+          onChange={(e) => setSearchTerm(e.target.value)}
+          className="h-10 w-full rounded-md border bg-background pl-10 pr-4 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+        />
+      </div>
+
+      {/* mDNS Services */}
+      <Card>
+        <CardHeader>
+          <CardTitle>
+            mDNS / Bonjour Services ({filteredServices.length})
+          </CardTitle>
+        </CardHeader>
+        <CardContent>
+          {!discovery ? (
+            <p className="py-8 text-center text-muted-foreground">
+              {isBrowsing ? "Listening for announcements..." : "Browse to discover printers, file shares, SSH hosts and more"}
+            </p>
+          ) : filteredServices.length === 0 ? (
+            <p className="py-8 text-center text-muted-foreground">
+              {searchTerm ? "No services matching your search" : "No services answered"}
+            </p>
+          ) : (
+            <div className="overflow-x-auto">
+              <table className="w-full">
+                <thead>
+                  <tr className="border-b text-left text-sm text-muted-foreground">
+                    <th className="pb-3 font-medium">Name</th>
+                    <th className="pb-3 font-medium">Category</th>
+                    <th className="pb-3 font-medium">Type</th>
+                    <th className="pb-3 font-medium">Host</th>
+                    <th className="pb-3 font-medium">Address</th>
+                  </tr>
+                </thead>
+                <tbody>
+                  {filteredServices.map((service) => (
+                    <tr key={`${service.service_type}-${service.name}`} className="border-b last:border-0">
+                      <td
+                        className="py-3 font-medium"
+                        title={Object.entries(service.txt).map(([k, v]) => (v ? `${k}=${v}` : k)).join("\n")}
+                      >
+                        {service.name}
+                      </td>
+                      <td className="py-3">
+                        <Badge variant="secondary">{categoryLabels[service.category]}</Badge>
+                      </td>
+                      <td className="py-3 font-mono text-sm">{service.service_type}</td>
+                      <td className="py-3 text-sm">{service.host ?? "-"}</td>
+                      <td className="py-3 font-mono text-sm">
+                        {formatEndpoint(service.addresses[0], service.port)}
+                        {service.addresses.length > 1 && (
+                          <span className="ml-1 text-xs text-muted-foreground" title={service.addresses.join("\n")}>
+                            +{service.addresses.length - 1}
+                          </span>
+                        )}
+                      </td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            </div>
+          )}
+          {discovery && discovery.service_types.length > 0 && (
+            <div className="mt-4 flex flex-wrap gap-2">
+              {discovery.service_types.map((type) => (
+                <Badge key={type} variant="outline" className="font-mono">
+                  {type}
+                </Badge>
+              ))}
+            </div>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* UPnP Port Mappings */}
+      {upnp?.gateways.map((gateway) => (
+        <Card key={gateway.location}>
+          <CardHeader>
+            <CardTitle>
+              Port Mappings on {gateway.friendly_name ?? "Gateway"} ({gateway.mappings.length})
+            </CardTitle>
+            {gateway.external_ip && (
+              <p className="text-sm text-muted-foreground">External address {gateway.external_ip}</p>
+            )}
+          </CardHeader>
+          <CardContent>
+            {gateway.error ? (
+              <p className="text-sm text-destructive">{gateway.error}</p>
+            ) : gateway.mappings.length === 0 ? (
+              <p className="py-8 text-center text-muted-foreground">No ports are forwarded</p>
+            ) : (
+              <div className="overflow-x-auto">
+                <table className="w-full">
+                  <thead>
+                    <tr className="border-b text-left text-sm text-muted-foreground">
+                      <th className="pb-3 font-medium">External</th>
+                      <th className="pb-3 font-medium">Forwarded To</th>
+                      <th className="pb-3 font-medium">Description</th>
+                      <th className="pb-3 font-medium">Lease</th>
+                      <th className="pb-3 font-medium">Status</th>
+                    </tr>
+                  </thead>
+                  <tbody>
+                    {gateway.mappings.map((mapping) => (
+                      <tr key={`${mapping.protocol}-${mapping.external_port}`} className="border-b last:border-0">
+                        <td className="py-3 font-mono text-sm">
+                          {mapping.external_port}/{mapping.protocol}
+                        </td>
+                        <td className="py-3 font-mono text-sm">
+                          {formatEndpoint(mapping.internal_client, mapping.internal_port)}
+                          {mapping.to_this_host && (
+                            <Badge variant="secondary" className="ml-2">This machine</Badge>
+                          )}
+                        </td>
+                        <td className="py-3 text-sm">{mapping.description || "-"}</td>
+                        <td className="py-3 text-sm">{formatLease(mapping.lease_duration)}</td>
+                        <td className="py-3">
+                          <Badge variant={mapping.enabled ? "destructive" : "outline"}>
+                            {mapping.enabled ? "Active" : "Disabled"}
+                          </Badge>
+                        </td>
+                      </tr>
+                    ))}
+                  </tbody>
+                </table>
+              </div>
+            )}
+          </CardContent>
+        </Card>
+      ))}
+
+      {/* UPnP Devices */}
+      {upnp && (
+        <Card>
+          <CardHeader>
+            <CardTitle>UPnP / SSDP Devices ({upnp.devices.length})</CardTitle>
+          </CardHeader>
+          <CardContent>
+            {upnp.devices.length === 0 ? (
+              <p className="py-8 text-center text-muted-foreground">No devices answered</p>
+            ) : (
+              <div className="overflow-x-auto">
+                <table className="w-full">
+                  <thead>
+                    <tr className="border-b text-left text-sm text-muted-foreground">
+                      <th className="pb-3 font-medium">Name</th>
+                      <th className="pb-3 font-medium">Model</th>
+                      <th className="pb-3 font-medium">Address</th>
+                      <th className="pb-3 font-medium">Server</th>
+                    </tr>
+                  </thead>
+                  <tbody>
+                    {upnp.devices.map((device) => (
+                      <tr key={device.location} className="border-b last:border-0">
+                        <td className="py-3 font-medium" title={device.device_type ?? undefined}>
+                          {device.friendly_name ?? device.location}
+                          {device.is_gateway && (
+                            <Badge variant="secondary" className="ml-2">Gateway</Badge>
+                          )}
+                        </td>
+                        <td className="py-3 text-sm">
+                          {[device.manufacturer, device.model_name].filter(Boolean).join(" ") || "-"}
+                        </td>
+                        <td className="py-3 font-mono text-sm">{device.address}</td>
+                        <td className="py-3 text-sm text-muted-foreground">{device.server ?? "-"}</td>
+                      </tr>
+                    ))}
+                  </tbody>
+                </table>
+              </div>
+            )}
+          </CardContent>
+        </Card>
+      )}
+    </div>
+  );
+}
@@ -2,9 +2,9 @@ import { useEffect, useState, useRef } from "react";
 import { Card, CardContent, CardHeader, CardTitle } from "../components/ui/card";
 import { Button } from "../components/ui/button";
 import { Badge } from "../components/ui/badge";
-import { Cpu, MemoryStick, MonitorSpeaker, RefreshCw } from "lucide-react";
+import { Activity, Clock, Cpu, Fan, HardDrive, MemoryStick, MonitorSpeaker, Network, RefreshCw, Thermometer } from "lucide-react";
 import * as api from "../lib/tauri/commands";
-import type { SystemStats, GpuProvider } from "../lib/tauri/types";
+import type { SystemStats, GpuProvider, TimeSyncStatus, DiskHealthReport, DiskHealthState, SystemPoint, PortCountPoint, ProcessSort, ProcessUsage, GpuProviderInfo } from "../lib/tauri/types";
 
 interface StatsHistory {
   timestamps: number[];
@@ -13,8 +13,23 @@ interface StatsHistory {
   gpuUsage: number[];
 }
 
-const MAX_HISTORY_POINTS = 360; // 30 minutes at 5s intervals
+const HISTORY_RANGE = 30 * 60; // 30 minutes of per-second samples
 const UPDATE_INTERVAL = 5000; // 5 seconds
+const TOP_PROCESSES = 8;
+
+// Ranges for the recorded history
+const STORED_RANGES = [
+  { label: "24h", hours: 24 },
+  { label: "7 Tage", hours: 24 * 7 },
+  { label: "30 Tage", hours: 24 * 30 },
+];
+
+const diskHealthVariant: Record<DiskHealthState, "success" | "warning" | "destructive" | "secondary"> = {
+  ok: "success",
+  warning: "warning",
+  critical: "destructive",
+  unknown: "secondary",
+};
 
 function formatBytes(bytes: number): string {
   const units = ["B", "KB", "MB", "GB", "TB"];
@@ -103,27 +118,35 @@ export function Performance() {
   const [isLive, setIsLive] = useState(true);
   const [error, setError] = useState<string | null>(null);
   const [gpuProvider, setGpuProvider] = useState<GpuProvider>("auto");
+  const [timeSync, setTimeSync] = useState<TimeSyncStatus | null>(null);
+  const [isCheckingTime, setIsCheckingTime] = useState(false);
+  const [diskHealth, setDiskHealth] = useState<DiskHealthReport | null>(null);
+  const [isCheckingDisks, setIsCheckingDisks] = useState(false);
+  const [storedHours, setStoredHours] = useState(24);
+  const [storedSystem, setStoredSystem] = useState<SystemPoint[]>([]);
+  const [storedPorts, setStoredPorts] = useState<PortCountPoint[]>([]);
+  const [gpuProviders, setGpuProviders] = useState<GpuProviderInfo[]>([]);
+  const [processSort, setProcessSort] = useState<ProcessSort>("cpu");
+  const [topProcesses, setTopProcesses] = useState<ProcessUsage[]>([]);
   const intervalRef = useRef<number | null>(null);
 
   const fetchStats = async () => {
     try {
-      const newStats = await api.getSystemStats();
+      const [newStats, processes, samples] = await Promise.all([
+        api.getSystemStats(),
+        api.getTopProcesses(processSort, TOP_PROCESSES),
+        api.getStatsHistory(HISTORY_RANGE, "second"),
+      ]);
       setStats(newStats);
+      setTopProcesses(processes);
       setError(null);
 
-      setHistory((prev) => {
-        const newTimestamps = [...prev.timestamps, newStats.timestamp];
-        const newCpuUsage = [...prev.cpuUsage, newStats.cpu.usage_percent];
-        const newMemoryUsage = [...prev.memoryUsage, newStats.memory.usage_percent];
-        const newGpuUsage = [...prev.gpuUsage, newStats.gpus[0]?.usage_percent ?? 0];
-
-        // Keep only last MAX_HISTORY_POINTS
-        return {
-          timestamps: newTimestamps.slice(-MAX_HISTORY_POINTS),
-          cpuUsage: newCpuUsage.slice(-MAX_HISTORY_POINTS),
-          memoryUsage: newMemoryUsage.slice(-MAX_HISTORY_POINTS),
-          gpuUsage: newGpuUsage.slice(-MAX_HISTORY_POINTS),
-        };
+      // The backend samples every second, also while this page is closed
+      setHistory({
+        timestamps: samples.map((s) => s.timestamp),
+        cpuUsage: samples.map((s) => s.cpu_percent),
+        memoryUsage: samples.map((s) => s.memory_percent),
+        gpuUsage: samples.map((s) => s.gpu_percent ?? 0),
       });
     } catch (err) {
       setError(String(err));
@@ -142,12 +165,76 @@ export function Performance() {
         clearInterval(intervalRef.current);
       }
     };
-  }, [isLive]);
+  }, [isLive, processSort]);
+
+  const checkTimeSync = async () => {
+    setIsCheckingTime(true);
+    try {
+      setTimeSync(await api.checkTimeSync());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsCheckingTime(false);
+    }
+  };
+
+  const checkDiskHealth = async () => {
+    setIsCheckingDisks(true);
+    try {
+      setDiskHealth(await api.checkDiskHealth());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsCheckingDisks(false);
+    }
+  };
+
+  useEffect(() => {
+    checkTimeSync();
+    checkDiskHealth();
+  }, []);
+
+  useEffect(() => {
+    const from = new Date(Date.now() - storedHours * 3600 * 1000).toISOString();
+    Promise.all([api.getSystemHistory(from), api.getPortCountHistory(from)])
+      .then(([system, ports]) => {
+        setStoredSystem(system);
+        setStoredPorts(ports);
+      })
+      .catch((err) => setError(String(err)));
+  }, [storedHours]);
+
+  const loadGpuProviders = () => {
+    api.listGpuProviders().then(setGpuProviders).catch((err) => setError(String(err)));
+  };
+
+  useEffect(() => {
+    loadGpuProviders();
+  }, []);
 
   const handleGpuProviderChange = async (provider: GpuProvider) => {
     try {
       await api.setGpuProvider(provider);
       setGpuProvider(provider);
+      loadGpuProviders();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const toggleGpuProvider = async (info: GpuProviderInfo) => {
+    try {
+      await api.setGpuProviderEnabled(info.provider, !info.enabled);
+      loadGpuProviders();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const toggleGpu = async (name: string, enabled: boolean) => {
+    try {
+      await api.setGpuEnabled(name, !enabled);
+      loadGpuProviders();
     } catch (err) {
       setError(String(err));
     }
@@ -320,10 +407,11 @@ export function Performance() {
                   onChange={(e) => handleGpuProviderChange(e.target.value as GpuProvider)}
                   className="text-xs bg-muted rounded px-2 py-1"
                 >
-                  <option value="auto">Auto</option>
+                  <option value="auto">Alle erkannten</option>
                   <option value="apple">Apple</option>
                   <option value="nvidia">NVIDIA</option>
                   <option value="amd">AMD</option>
+                  <option value="windows">Windows</option>
                   <option value="none">Keine</option>
                 </select>
               </div>
@@ -339,6 +427,7 @@ export function Performance() {
                   ) : (
                     <p className="text-xs text-muted-foreground">
                       GPU-Auslastung nicht verfügbar
+                      {gpu.name.startsWith("Apple") && " – powermetrics in den Einstellungen aktivieren"}
                     </p>
                   )}
                   {gpu.memory_used_bytes !== null && gpu.memory_total_bytes !== null && (
@@ -351,6 +440,16 @@ export function Performance() {
                       Temperatur: {gpu.temperature_celsius}°C
                     </div>
                   )}
+                  {(gpu.frequency_mhz !== null || gpu.power_watts !== null) && (
+                    <div className="text-xs text-muted-foreground">
+                      {[
+                        gpu.frequency_mhz !== null && `Takt: ${gpu.frequency_mhz} MHz`,
+                        gpu.power_watts !== null && `Leistung: ${gpu.power_watts.toFixed(1)} W`,
+                      ]
+                        .filter(Boolean)
+                        .join(" • ")}
+                    </div>
+                  )}
                 </div>
               ))
             ) : (
@@ -362,10 +461,206 @@ export function Performance() {
             {history.gpuUsage.some((v) => v > 0) && (
               <MiniChart data={history.gpuUsage} color="#10b981" label="GPU History" />
             )}
+            {gpuProviders.length > 0 && (
+              <div className="space-y-2 border-t pt-3">
+                <p className="text-xs font-medium text-muted-foreground">Quellen</p>
+                {gpuProviders.map((info) => (
+                  <div key={info.provider} className="space-y-1">
+                    <div className="flex items-center justify-between text-sm">
+                      <span>
+                        {info.provider}
+                        {!info.detected && <span className="text-xs text-muted-foreground"> (nicht erkannt)</span>}
+                      </span>
+                      <Button variant="outline" size="sm" onClick={() => toggleGpuProvider(info)}>
+                        {info.enabled ? "An" : "Aus"}
+                      </Button>
+                    </div>
+                    {info.gpus.map((gpu) => (
+                      <div key={gpu.name} className="flex items-center justify-between pl-3 text-xs text-muted-foreground">
+                        <span className="truncate">{gpu.name}</span>
+                        <Button variant="ghost" size="sm" onClick={() => toggleGpu(gpu.name, gpu.enabled)}>
+                          {gpu.enabled ? "An" : "Aus"}
+                        </Button>
+                      </div>
+                    ))}
+                  </div>
+                ))}
+              </div>
+            )}
           </CardContent>
         </Card>
       </div>
 
+      {/* Network Card */}
+      {stats && stats.network.interfaces.length > 0 && (
+        <Card>
+          <CardHeader className="pb-2">
+            <div className="flex items-center justify-between">
+              <CardTitle className="text-lg flex items-center gap-2">
+                <Network className="h-5 w-5" />
+                Netzwerk
+              </CardTitle>
+              <Badge variant="secondary">
+                ↓ {formatBytes(stats.network.rx_bytes_per_sec)}/s ↑ {formatBytes(stats.network.tx_bytes_per_sec)}/s
+              </Badge>
+            </div>
+          </CardHeader>
+          <CardContent>
+            <div className="space-y-2">
+              {stats.network.interfaces.map((iface) => (
+                <div key={iface.name} className="flex flex-wrap items-center justify-between gap-2 rounded-md border p-3 text-sm">
+                  <div className="min-w-0">
+                    <div className="flex items-center gap-2">
+                      <p className="font-mono font-medium">{iface.name}</p>
+                      {iface.link_up !== null && (
+                        <Badge variant={iface.link_up ? "success" : "secondary"}>{iface.link_up ? "up" : "down"}</Badge>
+                      )}
+                    </div>
+                    <p className="text-xs text-muted-foreground font-mono truncate">
+                      {iface.ip_addresses.length > 0 ? iface.ip_addresses.join(", ") : "Keine Adresse"}
+                    </p>
+                  </div>
+                  <div className="text-right font-mono text-xs">
+                    <p>↓ {formatBytes(iface.rx_bytes_per_sec)}/s ↑ {formatBytes(iface.tx_bytes_per_sec)}/s</p>
+                    <p className="text-muted-foreground">
+                      Gesamt {formatBytes(iface.total_rx_bytes)} / {formatBytes(iface.total_tx_bytes)}
+                    </p>
+                  </div>
+                </div>
+              ))}
+            </div>
+          </CardContent>
+        </Card>
+      )}
+
+      {/* Disk Card */}
+      {stats && stats.disks.length > 0 && (
+        <Card>
+          <CardHeader className="pb-2">
+            <CardTitle className="text-lg flex items-center gap-2">
+              <HardDrive className="h-5 w-5" />
+              Datenträger
+            </CardTitle>
+          </CardHeader>
+          <CardContent>
+            <div className="space-y-4">
+              {stats.disks.map((disk) => (
+                <div key={disk.name} className="space-y-1">
+                  <div className="flex flex-wrap items-center justify-between gap-2 text-sm">
+                    <p className="font-mono font-medium truncate">
+                      {disk.mount_point}{" "}
+                      <span className="text-xs text-muted-foreground">
+                        {disk.name} • {disk.file_system} • {disk.kind.toUpperCase()}
+                      </span>
+                    </p>
+                    <p className="font-mono text-xs text-muted-foreground">
+                      Lesen {formatBytes(disk.read_bytes_per_sec)}/s • Schreiben {formatBytes(disk.write_bytes_per_sec)}/s
+                    </p>
+                  </div>
+                  <UsageBar
+                    value={disk.usage_percent}
+                    label={`${formatBytes(disk.total_bytes - disk.available_bytes)} / ${formatBytes(disk.total_bytes)}`}
+                    color={disk.usage_percent >= 90 ? "#ef4444" : disk.usage_percent >= 75 ? "#f59e0b" : "#3b82f6"}
+                  />
+                </div>
+              ))}
+            </div>
+          </CardContent>
+        </Card>
+      )}
+
+      {/* Sensors Card */}
+      {stats && (stats.sensors.temperatures.length > 0 || stats.sensors.fans.length > 0) && (
+        <Card>
+          <CardHeader className="pb-2">
+            <CardTitle className="text-lg flex items-center gap-2">
+              <Thermometer className="h-5 w-5" />
+              Sensoren
+            </CardTitle>
+          </CardHeader>
+          <CardContent>
+            <div className="grid gap-2 sm:grid-cols-2 lg:grid-cols-3">
+              {stats.sensors.temperatures.map((sensor) => {
+                const limit = sensor.critical_celsius ?? sensor.max_celsius;
+                const hot = limit !== null && sensor.celsius >= limit - 10;
+                return (
+                  <div key={sensor.label} className="flex items-center justify-between rounded-md border p-3 text-sm">
+                    <div className="min-w-0">
+                      <p className="font-medium truncate">{sensor.label}</p>
+                      <p className="text-xs text-muted-foreground">
+                        {sensor.kind.toUpperCase()}
+                        {limit !== null && ` • kritisch ab ${limit.toFixed(0)}°C`}
+                      </p>
+                    </div>
+                    <Badge variant={hot ? "destructive" : "secondary"}>{sensor.celsius.toFixed(0)}°C</Badge>
+                  </div>
+                );
+              })}
+              {stats.sensors.fans.map((fan) => (
+                <div key={fan.label} className="flex items-center justify-between rounded-md border p-3 text-sm">
+                  <p className="font-medium truncate flex items-center gap-2">
+                    <Fan className="h-4 w-4" />
+                    {fan.label}
+                  </p>
+                  <Badge variant="secondary">{fan.rpm} U/min</Badge>
+                </div>
+              ))}
+            </div>
+          </CardContent>
+        </Card>
+      )}
+
+      {/* Top Processes Card */}
+      <Card>
+        <CardHeader className="pb-2">
+          <div className="flex items-center justify-between">
+            <CardTitle className="text-lg flex items-center gap-2">
+              <Activity className="h-5 w-5" />
+              Top-Prozesse
+            </CardTitle>
+            <div className="flex gap-1">
+              {(["cpu", "memory"] as ProcessSort[]).map((sort) => (
+                <Button
+                  key={sort}
+                  variant={processSort === sort ? "default" : "outline"}
+                  size="sm"
+                  onClick={() => setProcessSort(sort)}
+                >
+                  {sort === "cpu" ? "CPU" : "RAM"}
+                </Button>
+              ))}
+            </div>
+          </div>
+        </CardHeader>
+        <CardContent>
+          {topProcesses.length === 0 ? (
+            <p className="text-sm text-muted-foreground">Keine Prozesse</p>
+          ) : (
+            <div className="space-y-2">
+              {topProcesses.map((process) => (
+                <div key={process.pid} className="flex items-center justify-between rounded-md border p-3 text-sm">
+                  <div className="min-w-0">
+                    <p className="font-medium truncate">{process.name}</p>
+                    <p className="text-xs text-muted-foreground">
+                      PID {process.pid}
+                      {process.user && ` • ${process.user}`}
+                    </p>
+                  </div>
+                  <div className="flex gap-2">
+                    <Badge variant={processSort === "cpu" ? "default" : "secondary"}>
+                      {process.cpu_usage.toFixed(1)}%
+                    </Badge>
+                    <Badge variant={processSort === "memory" ? "default" : "secondary"}>
+                      {formatBytes(process.memory_bytes)}
+                    </Badge>
+                  </div>
+                </div>
+              ))}
+            </div>
+          )}
+        </CardContent>
+      </Card>
+
       {/* History Charts */}
       <Card>
         <CardHeader>
@@ -378,10 +673,203 @@ export function Performance() {
             <MiniChart data={history.gpuUsage} color="#10b981" label="GPU" />
           </div>
           <p className="text-xs text-muted-foreground mt-4 text-center">
-            {history.timestamps.length} Datenpunkte • Update alle {UPDATE_INTERVAL / 1000}s
+            {history.timestamps.length} Datenpunkte • Messung jede Sekunde, Anzeige alle {UPDATE_INTERVAL / 1000}s
           </p>
         </CardContent>
       </Card>
+
+      {/* Stored History */}
+      <Card>
+        <CardHeader>
+          <div className="flex items-center justify-between">
+            <CardTitle>Gespeicherter Verlauf</CardTitle>
+            <div className="flex gap-1">
+              {STORED_RANGES.map((range) => (
+                <Button
+                  key={range.hours}
+                  variant={storedHours === range.hours ? "default" : "outline"}
+                  size="sm"
+                  onClick={() => setStoredHours(range.hours)}
+                >
+                  {range.label}
+                </Button>
+              ))}
+            </div>
+          </div>
+        </CardHeader>
+        <CardContent>
+          {storedSystem.length === 0 ? (
+            <p className="text-sm text-muted-foreground text-center">
+              Noch keine Aufzeichnungen in diesem Zeitraum
+            </p>
+          ) : (
+            <>
+              <div className="grid gap-4 md:grid-cols-2">
+                <MiniChart
+                  data={storedSystem.map((point) => point.cpu_percent)}
+                  color="#22c55e"
+                  label="CPU (gespeichert)"
+                />
+                <MiniChart
+                  data={storedSystem.map((point) =>
+                    point.memory_total > 0 ? (point.memory_used / point.memory_total) * 100 : 0
+                  )}
+                  color="#3b82f6"
+                  label="RAM (gespeichert)"
+                />
+              </div>
+              <p className="text-xs text-muted-foreground mt-4 text-center">
+                {storedSystem.length} Datenpunkte seit{" "}
+                {new Date(storedSystem[0].taken_at).toLocaleString()}
+                {storedPorts.length > 0 &&
+                  ` • ${storedPorts[storedPorts.length - 1].listening} offene Ports, davon ${
+                    storedPorts[storedPorts.length - 1].public
+                  } öffentlich`}
+              </p>
+            </>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Time Sync */}
+      <Card className={timeSync?.health === "critical" ? "border-destructive" : undefined}>
+        <CardHeader className="pb-2">
+          <div className="flex items-center justify-between">
+            <CardTitle className="text-lg flex items-center gap-2">
+              <Clock className="h-5 w-5" />
+              Zeitsynchronisation
+            </CardTitle>
+            <Button variant="outline" size="sm" onClick={checkTimeSync} disabled={isCheckingTime}>
+              <RefreshCw className={`h-4 w-4 ${isCheckingTime ? "animate-spin" : ""}`} />
+            </Button>
+          </div>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          {timeSync ? (
+            <>
+              <div className="flex flex-wrap items-center gap-2">
+                <Badge
+                  variant={
+                    timeSync.health === "ok"
+                      ? "success"
+                      : timeSync.health === "warning"
+                        ? "warning"
+                        : timeSync.health === "critical"
+                          ? "destructive"
+                          : "secondary"
+                  }
+                >
+                  {timeSync.health}
+                </Badge>
+                <span className="text-sm">{timeSync.service ?? "Kein Zeitdienst"}</span>
+                {timeSync.synchronized !== null && (
+                  <span className="text-sm text-muted-foreground">
+                    {timeSync.synchronized ? "synchronisiert" : "nicht synchronisiert"}
+                  </span>
+                )}
+              </div>
+              <div className="grid grid-cols-2 gap-2 text-sm md:grid-cols-4">
+                <div>
+                  <p className="text-muted-foreground">Abweichung</p>
+                  <p className="font-mono font-medium">
+                    {timeSync.measured_offset_ms !== null
+                      ? `${timeSync.measured_offset_ms.toFixed(1)} ms`
+                      : "-"}
+                  </p>
+                </div>
+                <div>
+                  <p className="text-muted-foreground">Laut Zeitdienst</p>
+                  <p className="font-mono font-medium">
+                    {timeSync.daemon_offset_ms !== null
+                      ? `${timeSync.daemon_offset_ms.toFixed(1)} ms`
+                      : "-"}
+                  </p>
+                </div>
+                <div>
+                  <p className="text-muted-foreground">NTP-Server</p>
+                  <p className="font-mono font-medium truncate">{timeSync.ntp_server}</p>
+                </div>
+                <div>
+                  <p className="text-muted-foreground">Round Trip</p>
+                  <p className="font-mono font-medium">
+                    {timeSync.round_trip_ms !== null ? `${timeSync.round_trip_ms.toFixed(0)} ms` : "-"}
+                  </p>
+                </div>
+              </div>
+              {timeSync.issues.map((issue) => (
+                <p key={issue} className="text-sm text-amber-600">{issue}</p>
+              ))}
+            </>
+          ) : (
+            <p className="text-sm text-muted-foreground">Prüfe Systemzeit...</p>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Disk Health */}
+      <Card className={diskHealth?.disks.some((d) => d.health === "critical") ? "border-destructive" : undefined}>
+        <CardHeader className="pb-2">
+          <div className="flex items-center justify-between">
+            <CardTitle className="text-lg flex items-center gap-2">
+              <HardDrive className="h-5 w-5" />
+              Laufwerksgesundheit
+            </CardTitle>
+            <Button variant="outline" size="sm" onClick={checkDiskHealth} disabled={isCheckingDisks}>
+              <RefreshCw className={`h-4 w-4 ${isCheckingDisks ? "animate-spin" : ""}`} />
+            </Button>
+          </div>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          {diskHealth ? (
+            <>
+              {diskHealth.disks.length === 0 && (
+                <p className="text-sm text-muted-foreground">Keine SMART-Daten verfügbar</p>
+              )}
+              {diskHealth.disks.map((disk) => (
+                <div key={disk.serial ?? disk.device} className="space-y-1">
+                  <div className="flex flex-wrap items-center gap-2">
+                    <Badge variant={diskHealthVariant[disk.health]}>{disk.health}</Badge>
+                    <span className="text-sm font-medium">{disk.model ?? disk.device}</span>
+                    <span className="text-xs text-muted-foreground">
+                      {disk.device} • {disk.source}
+                    </span>
+                  </div>
+                  <div className="grid grid-cols-2 gap-2 text-sm md:grid-cols-4">
+                    <div>
+                      <p className="text-muted-foreground">Ersetzte Sektoren</p>
+                      <p className="font-mono font-medium">{disk.reallocated_sectors ?? "-"}</p>
+                    </div>
+                    <div>
+                      <p className="text-muted-foreground">Verschleiß</p>
+                      <p className="font-mono font-medium">
+                        {disk.wear_percent !== null ? `${disk.wear_percent}%` : "-"}
+                      </p>
+                    </div>
+                    <div>
+                      <p className="text-muted-foreground">Temperatur</p>
+                      <p className="font-mono font-medium">
+                        {disk.temperature_celsius !== null ? `${disk.temperature_celsius.toFixed(0)}°C` : "-"}
+                      </p>
+                    </div>
+                    <div>
+                      <p className="text-muted-foreground">Betriebsstunden</p>
+                      <p className="font-mono font-medium">{disk.power_on_hours ?? "-"}</p>
+                    </div>
+                  </div>
+                  {disk.issues.map((issue) => (
+                    <p key={issue} className="text-sm text-amber-600">{issue}</p>
+                  ))}
+                </div>
+              ))}
+              {diskHealth.warnings.map((warning) => (
+                <p key={warning} className="text-xs text-muted-foreground">{warning}</p>
+              ))}
+            </>
+          ) : (
+            <p className="text-sm text-muted-foreground">Lese SMART-Daten...</p>
+          )}
+        </CardContent>
+      </Card>
     </div>
   );
 }
@@ -1,35 +1,123 @@
 import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
 import { Card, CardContent, CardHeader, CardTitle } from "../components/ui/card";
 import { Button } from "../components/ui/button";
 import { Badge } from "../components/ui/badge";
 import { usePortStore } from "../stores/portStore";
-import { RefreshCw, Search } from "lucide-react";
+import { TunnelsCard } from "../components/ports/TunnelsCard";
+import { PortHistoryCard } from "../components/ports/PortHistoryCard";
+import { BaselineCard } from "../components/ports/BaselineCard";
+import { ConnectionsCard } from "../components/ports/ConnectionsCard";
+import * as api from "../lib/tauri/commands";
+import type { IpcEndpoint, PortInfo, PortReservation, ResolverStatus, ScanProgress } from "../lib/tauri/types";
+import { AlertTriangle, Bookmark, Power, RefreshCw, Search, X } from "lucide-react";
+
+/** Half the scanner's connect timeout; slower handshakes point to an overloaded or half-open service */
+const SLOW_CONNECT_MS = 100;
+
+/** "22,80,8000-8010" -> [22, 80, 8000, ..., 8010]; empty means the default range */
+function parsePortList(value: string): number[] | undefined {
+  const ports = value
+    .split(",")
+    .map((part) => part.trim())
+    .filter(Boolean)
+    .flatMap((part) => {
+      const [from, to] = part.split("-").map(Number);
+      if (!to) return [from];
+      return Array.from({ length: Math.max(0, to - from + 1) }, (_, i) => from + i);
+    })
+    .filter((port) => Number.isInteger(port) && port > 0 && port <= 65535);
+  return ports.length > 0 ? ports : undefined;
+}
 
 export function Ports() {
-  const { ports, fetchPortUsage, scanPorts, findFreePorts, isLoading } = usePortStore();
+  const { ports, tunnels, fetchPortUsage, fetchTunnels, scanPorts, findFreePorts, freePort, dryRunPreview, isLoading, error } = usePortStore();
   const [searchTerm, setSearchTerm] = useState("");
   const [freePorts, setFreePorts] = useState<number[]>([]);
   const [isScanning, setIsScanning] = useState(false);
+  const [scanTarget, setScanTarget] = useState("");
+  const [scanPortList, setScanPortList] = useState("");
+  const [scanProgress, setScanProgress] = useState<ScanProgress | null>(null);
+  const [resolverStatus, setResolverStatus] = useState<ResolverStatus | null>(null);
+  const [reservations, setReservations] = useState<PortReservation[]>([]);
+  const [reservePortInput, setReservePortInput] = useState("");
+  const [reserveLabel, setReserveLabel] = useState("");
+  const [reserveOwner, setReserveOwner] = useState("");
+  const [reserveError, setReserveError] = useState<string | null>(null);
+  const [ipcEndpoints, setIpcEndpoints] = useState<IpcEndpoint[]>([]);
 
   useEffect(() => {
     fetchPortUsage();
-  }, [fetchPortUsage]);
+    api.getResolverStatus().then(setResolverStatus).catch(() => {});
+    api.listPortReservations().then(setReservations).catch(() => {});
+    api.getIpcEndpoints().then((report) => setIpcEndpoints(report.items)).catch(() => {});
+    fetchTunnels();
+  }, [fetchPortUsage, fetchTunnels]);
+
+  const reservationFor = (port: number) => reservations.find((r) => r.port === port);
+  // Local forwards listen through their ssh process
+  const tunnelFor = (port: PortInfo) =>
+    tunnels.find((t) => t.pid !== null && t.pid === port.pid && t.spec.kind === "local" && t.spec.bind_port === port.port);
+  // Same rule as the backend: without an owner every listener is a conflict
+  const isExpectedOwner = (port: PortInfo) => {
+    const owner = reservationFor(port.port)?.owner;
+    return !!owner && !!port.process_name && owner.toLowerCase() === port.process_name.toLowerCase();
+  };
 
   const filteredPorts = ports.filter(
     (port) =>
       port.port.toString().includes(searchTerm) ||
+      port.service_name?.toLowerCase().includes(searchTerm.toLowerCase()) ||
       port.process_name?.toLowerCase().includes(searchTerm.toLowerCase())
   );
 
+  const filteredIpc = ipcEndpoints.filter(
+    (endpoint) =>
+      endpoint.path.toLowerCase().includes(searchTerm.toLowerCase()) ||
+      endpoint.process_name?.toLowerCase().includes(searchTerm.toLowerCase())
+  );
+
   const handleFindFreePorts = async () => {
     const free = await findFreePorts(10);
     setFreePorts(free);
   };
 
+  const handleReservePort = async () => {
+    setReserveError(null);
+    try {
+      await api.reservePort(Number(reservePortInput), reserveLabel, reserveOwner.trim() || undefined);
+      setReservations(await api.listPortReservations());
+      setReservePortInput("");
+      setReserveLabel("");
+      setReserveOwner("");
+    } catch (err) {
+      setReserveError(String(err));
+    }
+  };
+
+  const handleReleasePort = async (port: number) => {
+    setReserveError(null);
+    try {
+      await api.releasePort(port);
+      setReservations((current) => current.filter((r) => r.port !== port));
+    } catch (err) {
+      setReserveError(String(err));
+    }
+  };
+
   const handleScanPorts = async () => {
     setIsScanning(true);
-    await scanPorts(1, 1024); // Scan common ports
-    setIsScanning(false);
+    setScanProgress(null);
+    const unlisten = await listen<ScanProgress>("port-scan-progress", (event) => {
+      setScanProgress(event.payload);
+    });
+    try {
+      // Scan common ports unless a list is given
+      await scanPorts(1, 1024, scanTarget.trim() || undefined, parsePortList(scanPortList));
+    } finally {
+      unlisten();
+      setIsScanning(false);
+    }
   };
 
   return (
@@ -47,7 +135,11 @@ export function Ports() {
             Find Free Ports
           </Button>
           <Button variant="outline" onClick={handleScanPorts} disabled={isScanning}>
-            {isScanning ? "Scanning..." : "Scan Range"}
+            {isScanning
+              ? scanProgress
+                ? `Scanning ${Math.round((scanProgress.scanned / scanProgress.total) * 100)}%`
+                : "Scanning..."
+              : "Scan Range"}
           </Button>
           <Button onClick={fetchPortUsage} disabled={isLoading}>
             <RefreshCw className={`mr-2 h-4 w-4 ${isLoading ? "animate-spin" : ""}`} />
@@ -56,6 +148,36 @@ export function Ports() {
         </div>
       </div>
 
+      {/* Scan Target */}
+      <div className="flex flex-wrap gap-2">
+        <input
+          type="text"
+          placeholder="Target (localhost, 192.168.1.10, 192.168.1.0/24)"
+          value={scanTarget}
+          onChange={(e) => setScanTarget(e.target.value)}
+          className="h-10 w-80 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+        />
+        <input
+          type="text"
+          placeholder="Ports (22,80,443,8000-8100)"
+          value={scanPortList}
+          onChange={(e) => setScanPortList(e.target.value)}
+          className="h-10 w-64 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+        />
+        {scanProgress && (
+          <span className="self-center text-sm text-muted-foreground">
+            {scanProgress.scanned}/{scanProgress.total} probed, {scanProgress.open} open
+          </span>
+        )}
+      </div>
+      {error && <p className="text-sm text-destructive">{error}</p>}
+      {dryRunPreview && (
+        <p className="text-sm text-muted-foreground">
+          Dry run: {dryRunPreview.target_name ?? dryRunPreview.target} would be stopped, freeing{" "}
+          {dryRunPreview.ports_freed.map((p) => `:${p}`).join(", ")}
+        </p>
+      )}
+
       {/* Search */}
       <div className="relative max-w-md">
         <Search className="absolute left-3 top-1/2 h-4 w-4 -translate-y-1/2 text-muted-foreground" />
@@ -86,6 +208,114 @@ export function Ports() {
         </Card>
       )}
 
+      {/* Reserved Ports */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="text-lg">Reserved Ports</CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          <div className="flex flex-wrap gap-2">
+            <input
+              type="number"
+              placeholder="Port"
+              value={reservePortInput}
+              onChange={(e) => setReservePortInput(e.target.value)}
+              className="h-10 w-28 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+            <input
+              type="text"
+              placeholder="Label (frontend dev server)"
+              value={reserveLabel}
+              onChange={(e) => setReserveLabel(e.target.value)}
+              className="h-10 w-64 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+            <input
+              type="text"
+              placeholder="Expected process (optional)"
+              value={reserveOwner}
+              onChange={(e) => setReserveOwner(e.target.value)}
+              className="h-10 w-56 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+            <Button variant="outline" onClick={handleReservePort} disabled={!reservePortInput || !reserveLabel.trim()}>
+              <Bookmark className="mr-2 h-4 w-4" />
+              Reserve
+            </Button>
+          </div>
+          {reserveError && <p className="text-sm text-destructive">{reserveError}</p>}
+          {reservations.length === 0 ? (
+            <p className="text-sm text-muted-foreground">
+              Reserved ports are skipped by Find Free Ports and raise a warning when another process takes them.
+            </p>
+          ) : (
+            <div className="flex flex-wrap gap-2">
+              {reservations.map((reservation) => (
+                <Badge key={reservation.port} variant="outline" className="gap-1 font-mono">
+                  :{reservation.port} {reservation.label}
+                  {reservation.owner && <span className="text-muted-foreground">({reservation.owner})</span>}
+                  <button
+                    onClick={() => handleReleasePort(reservation.port)}
+                    className="ml-1 rounded-sm hover:text-destructive"
+                    title="Release"
+                  >
+                    <X className="h-3 w-3" />
+                  </button>
+                </Badge>
+              ))}
+            </div>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Listener Baseline */}
+      <BaselineCard />
+
+      {/* SSH Tunnels */}
+      <TunnelsCard />
+
+      {/* Port History */}
+      <PortHistoryCard />
+
+      {/* Active Connections */}
+      <ConnectionsCard />
+
+      {/* Local Resolvers */}
+      {resolverStatus && resolverStatus.resolvers.length > 0 && (
+        <Card>
+          <CardHeader>
+            <CardTitle className="text-lg">Local Resolvers</CardTitle>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            <div className="flex flex-wrap gap-2">
+              {resolverStatus.resolvers.map((resolver) => (
+                <Badge
+                  key={`${resolver.process_name}-${resolver.pid}`}
+                  variant={resolver.running ? "secondary" : "destructive"}
+                  title={resolver.ports.map((p) => `${p.address}:${p.port}/${p.protocol}`).join(", ")}
+                >
+                  {resolver.name}
+                  {resolver.roles.length > 0 && ` (${resolver.roles.join(", ").toUpperCase()})`}
+                  {!resolver.running && ` – ${resolver.unit_state}`}
+                </Badge>
+              ))}
+            </div>
+            {resolverStatus.conflicts.map((conflict) => (
+              <div key={conflict.id} className="rounded-md border border-amber-500/50 p-3">
+                <p className="flex items-center gap-2 font-medium">
+                  <AlertTriangle className="h-4 w-4 text-amber-500" />
+                  {conflict.title}
+                </p>
+                <p className="mt-1 text-sm text-muted-foreground">{conflict.description}</p>
+                <ol className="mt-2 list-decimal space-y-1 pl-5 text-sm">
+                  {conflict.fix_steps.map((step) => (
+                    <li key={step} className="font-mono text-xs">{step}</li>
+                  ))}
+                </ol>
+              </div>
+            ))}
+          </CardContent>
+        </Card>
+      )}
+
       {/* Ports Table */}
       <Card>
         <CardHeader>
@@ -109,34 +339,102 @@ export function Ports() {
                   <tr className="border-b text-left text-sm text-muted-foreground">
                     <th className="pb-3 font-medium">Port</th>
                     <th className="pb-3 font-medium">Protocol</th>
+                    <th className="pb-3 font-medium">Family</th>
+                    <th className="pb-3 font-medium">Address</th>
                     <th className="pb-3 font-medium">Process</th>
                     <th className="pb-3 font-medium">PID</th>
+                    <th className="pb-3 font-medium">User</th>
                     <th className="pb-3 font-medium">Status</th>
+                    <th className="pb-3 font-medium"></th>
                   </tr>
                 </thead>
                 <tbody>
                   {filteredPorts.map((port) => (
-                    <tr key={port.port} className="border-b last:border-0">
-                      <td className="py-3 font-mono font-medium">:{port.port}</td>
+                    <tr key={`${port.port}-${port.protocol}-${port.family}-${port.bind_address}`} className="border-b last:border-0">
+                      <td className="py-3 font-mono font-medium">
+                        :{port.port}
+                        {port.service_name && (
+                          <span className="ml-1 font-sans text-xs text-muted-foreground">{port.service_name}</span>
+                        )}
+                        {reservationFor(port.port) && (
+                          <Badge
+                            variant={isExpectedOwner(port) ? "secondary" : "destructive"}
+                            className="ml-2 font-sans"
+                            title="Reserved port"
+                          >
+                            {reservationFor(port.port)?.label}
+                          </Badge>
+                        )}
+                        {tunnelFor(port) && (
+                          <Badge variant="secondary" className="ml-2 font-sans" title="Managed SSH tunnel">
+                            Tunnel: {tunnelFor(port)?.spec.name}
+                          </Badge>
+                        )}
+                      </td>
                       <td className="py-3">
                         <Badge variant="outline">
                           {port.protocol.toUpperCase()}
                         </Badge>
                       </td>
+                      <td className="py-3">
+                        <Badge variant="secondary">
+                          {port.family === "ipv6" ? "IPv6" : "IPv4"}
+                        </Badge>
+                      </td>
+                      <td className="py-3 font-mono text-sm">
+                        {port.bind_address}
+                        {port.os_guess && (
+                          <span className="block font-sans text-xs text-muted-foreground" title="OS guess by nmap">
+                            {port.os_guess}
+                          </span>
+                        )}
+                      </td>
                       <td className="py-3">
                         {port.process_name || (
                           <span className="text-muted-foreground">Unknown</span>
                         )}
+                        {port.detected_service && (
+                          <span
+                            className="ml-2 text-xs text-muted-foreground"
+                            title={port.version ?? undefined}
+                          >
+                            ({port.detected_service})
+                          </span>
+                        )}
                       </td>
                       <td className="py-3 font-mono">
                         {port.pid || "-"}
                       </td>
+                      <td className="py-3 text-sm">{port.user ?? "-"}</td>
                       <td className="py-3">
                         <Badge
                           variant={port.status === "occupied" ? "default" : "secondary"}
                         >
                           {port.status}
                         </Badge>
+                        {port.latency_ms != null && (
+                          <span
+                            className={`ml-2 font-mono text-xs ${
+                              port.latency_ms >= SLOW_CONNECT_MS ? "text-yellow-500" : "text-muted-foreground"
+                            }`}
+                            title="TCP connect time"
+                          >
+                            {port.latency_ms < 1 ? port.latency_ms.toFixed(2) : Math.round(port.latency_ms)} ms
+                          </span>
+                        )}
+                      </td>
+                      <td className="py-3 text-right">
+                        {port.pid && (
+                          <Button
+                            variant="ghost"
+                            size="sm"
+                            onClick={() => freePort(port.port)}
+                            title="Stop the service or process holding this port"
+                          >
+                            <Power className="mr-1 h-4 w-4" />
+                            Free
+                          </Button>
+                        )}
                       </td>
                     </tr>
                   ))}
@@ -146,6 +444,56 @@ export function Ports() {
           )}
         </CardContent>
       </Card>
+
+      {/* Unix Sockets / Named Pipes */}
+      {ipcEndpoints.length > 0 && (
+        <Card>
+          <CardHeader>
+            <CardTitle>
+              Local Sockets &amp; Pipes ({filteredIpc.length})
+            </CardTitle>
+          </CardHeader>
+          <CardContent>
+            {filteredIpc.length === 0 ? (
+              <p className="py-8 text-center text-muted-foreground">No sockets matching your search</p>
+            ) : (
+              <div className="overflow-x-auto">
+                <table className="w-full">
+                  <thead>
+                    <tr className="border-b text-left text-sm text-muted-foreground">
+                      <th className="pb-3 font-medium">Path</th>
+                      <th className="pb-3 font-medium">Type</th>
+                      <th className="pb-3 font-medium">Process</th>
+                      <th className="pb-3 font-medium">PID</th>
+                      <th className="pb-3 font-medium">User</th>
+                    </tr>
+                  </thead>
+                  <tbody>
+                    {filteredIpc.map((endpoint) => (
+                      <tr key={`${endpoint.path}-${endpoint.pid}`} className="border-b last:border-0">
+                        <td className="py-3 font-mono text-sm break-all">{endpoint.path}</td>
+                        <td className="py-3">
+                          <Badge variant="outline">
+                            {endpoint.kind === "named_pipe" ? "Pipe" : "Unix"}
+                            {endpoint.state && ` ${endpoint.state}`}
+                          </Badge>
+                        </td>
+                        <td className="py-3">
+                          {endpoint.process_name || (
+                            <span className="text-muted-foreground">Unknown</span>
+                          )}
+                        </td>
+                        <td className="py-3 font-mono">{endpoint.pid || "-"}</td>
+                        <td className="py-3 text-sm">{endpoint.user ?? "-"}</td>
+                      </tr>
+                    ))}
+                  </tbody>
+                </table>
+              </div>
+            )}
+          </CardContent>
+        </Card>
+      )}
     </div>
   );
 }
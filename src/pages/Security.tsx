@@ -4,11 +4,11 @@ import { Button } from "../components/ui/button";
 import { Badge } from "../components/ui/badge";
 import {
   Shield, ShieldAlert, ShieldCheck, ShieldX,
-  RefreshCw, AlertTriangle, Lock, Unlock, Globe, Database, Cpu
+  RefreshCw, AlertTriangle, Lock, Unlock, Globe, Database, Cpu, Router, HardDrive, KeyRound, BrickWall, CheckCircle2, Undo2, FileWarning, ClipboardCheck, FileDown, ListChecks
 } from "lucide-react";
 import * as api from "../lib/tauri/commands";
 import { useServiceStore } from "../stores/serviceStore";
-import type { SecurityScanResult, SecurityIssue, SecuritySeverity } from "../lib/tauri/types";
+import type { ComplianceProfile, ComplianceProfileInfo, ComplianceReport, ControlStatus, FirewallState, FirewallStatus, RemediationPlan, ReportFormat, SecurityAnalysis, SecurityScanDiff, SecurityScanResult, SecurityIssue, SecuritySeverity, TlsInspection } from "../lib/tauri/types";
 
 const severityColors: Record<SecuritySeverity, { bg: string; text: string; icon: typeof ShieldAlert }> = {
   critical: { bg: "bg-red-500/10", text: "text-red-500", icon: ShieldX },
@@ -27,14 +27,32 @@ const categoryIcons: Record<string, typeof Lock> = {
   outdated_software: AlertTriangle,
   insecure_configuration: AlertTriangle,
   data_leakage: Database,
+  reliability: HardDrive,
+  untrusted_binary: FileWarning,
 };
 
-function SecurityIssueCard({ issue }: { issue: SecurityIssue }) {
+function SecurityIssueCard({
+  issue,
+  onAcknowledge,
+  highlighted,
+  onHover,
+}: {
+  issue: SecurityIssue;
+  onAcknowledge?: (issue: SecurityIssue) => void;
+  /** Referenced by the hovered step of the remediation plan */
+  highlighted?: boolean;
+  onHover?: (issueId: string | null) => void;
+}) {
   const { bg, text, icon: SeverityIcon } = severityColors[issue.severity];
   const CategoryIcon = categoryIcons[issue.category] || Shield;
 
   return (
-    <Card className={`${bg} border-l-4 ${text.replace("text-", "border-")}`}>
+    <Card
+      id={`issue-${issue.id}`}
+      className={`${bg} border-l-4 ${text.replace("text-", "border-")} ${highlighted ? "ring-2 ring-primary" : ""}`}
+      onMouseEnter={() => onHover?.(issue.id)}
+      onMouseLeave={() => onHover?.(null)}
+    >
       <CardContent className="pt-4">
         <div className="flex items-start gap-3">
           <div className={`p-2 rounded-full ${bg}`}>
@@ -64,17 +82,244 @@ function SecurityIssueCard({ issue }: { issue: SecurityIssue }) {
               </p>
             )}
           </div>
+          {onAcknowledge && (
+            <Button size="sm" variant="ghost" onClick={() => onAcknowledge(issue)} title="Als bekanntes Risiko akzeptieren">
+              <CheckCircle2 className="h-4 w-4 mr-1" />
+              Akzeptieren
+            </Button>
+          )}
         </div>
       </CardContent>
     </Card>
   );
 }
 
+function TlsInspectionCard({ inspection }: { inspection: TlsInspection }) {
+  const leaf = inspection.chain[0];
+  const hasIssues = inspection.issues.length > 0;
+
+  return (
+    <Card className={hasIssues ? "border-l-4 border-yellow-500" : ""}>
+      <CardContent className="pt-4 space-y-2">
+        <div className="flex items-center gap-2 flex-wrap">
+          <h3 className="font-medium">{leaf?.subject || inspection.host}</h3>
+          <Badge variant="secondary" className="text-xs font-mono">
+            Port {inspection.port}
+          </Badge>
+          {inspection.protocols
+            .filter((p) => p.supported)
+            .map((p) => (
+              <Badge key={p.version} variant={p.deprecated ? "destructive" : "outline"} className="text-xs">
+                {p.version}
+              </Badge>
+            ))}
+        </div>
+        {leaf && (
+          <p className="text-xs text-muted-foreground">
+            Aussteller: {leaf.issuer}
+            {leaf.not_after && <> · gültig bis {new Date(leaf.not_after).toLocaleDateString()}</>}
+            {inspection.cipher_suite && <> · {inspection.cipher_suite}</>}
+          </p>
+        )}
+        {hasIssues && (
+          <ul className="text-sm list-disc pl-5">
+            {inspection.issues.map((issue) => (
+              <li key={issue}>{issue}</li>
+            ))}
+          </ul>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
+
+const firewallStates: Record<FirewallState, { label: string; variant: "success" | "destructive" | "secondary" }> = {
+  enabled: { label: "Aktiv", variant: "success" },
+  disabled: { label: "Deaktiviert", variant: "destructive" },
+  unknown: { label: "Unbekannt", variant: "secondary" },
+};
+
+function FirewallCard({ status }: { status: FirewallStatus }) {
+  const state = firewallStates[status.state];
+  return (
+    <Card>
+      <CardHeader className="pb-2">
+        <CardTitle className="text-sm flex items-center gap-2">
+          <BrickWall className="h-4 w-4" />
+          Firewall
+          <Badge variant={state.variant} className="text-xs">{state.label}</Badge>
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-1 text-sm">
+        {status.backends.map((backend) => (
+          <div key={backend.name} className="flex items-center gap-2">
+            <span className="font-mono">{backend.name}</span>
+            <span className="text-muted-foreground">
+              {backend.enabled === null ? "unbekannt" : backend.enabled ? "an" : "aus"}
+              {backend.details && ` · ${backend.details}`}
+            </span>
+          </div>
+        ))}
+        {status.warnings.map((warning) => (
+          <p key={warning} className="text-xs text-muted-foreground">{warning}</p>
+        ))}
+      </CardContent>
+    </Card>
+  );
+}
+
+const controlStates: Record<ControlStatus, { label: string; className: string }> = {
+  pass: { label: "Erfüllt", className: "text-green-500" },
+  fail: { label: "Nicht erfüllt", className: "text-red-500" },
+  accepted: { label: "Ausnahme", className: "text-blue-500" },
+  unknown: { label: "Unbekannt", className: "text-muted-foreground" },
+  not_applicable: { label: "Entfällt", className: "text-muted-foreground" },
+};
+
+function ComplianceCard() {
+  const [profiles, setProfiles] = useState<ComplianceProfileInfo[]>([]);
+  const [profile, setProfile] = useState<ComplianceProfile | null>(null);
+  const [report, setReport] = useState<ComplianceReport | null>(null);
+  const [isRunning, setIsRunning] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    api.getComplianceProfiles().then((loaded) => {
+      setProfiles(loaded);
+      setProfile(loaded[0]?.profile ?? null);
+    }).catch(() => {});
+  }, []);
+
+  const run = async () => {
+    if (!profile) return;
+    setIsRunning(true);
+    setError(null);
+    try {
+      setReport(await api.runComplianceScan(profile));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsRunning(false);
+    }
+  };
+
+  const selected = profiles.find((p) => p.profile === profile);
+  return (
+    <Card>
+      <CardHeader className="pb-2">
+        <CardTitle className="text-sm flex items-center gap-2">
+          <ClipboardCheck className="h-4 w-4" />
+          Compliance
+          {report && (
+            <Badge variant={report.failed === 0 ? "success" : "secondary"} className="text-xs">
+              {report.score}%
+            </Badge>
+          )}
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-3 text-sm">
+        <div className="flex flex-wrap items-center gap-2">
+          <select
+            value={profile ?? ""}
+            onChange={(e) => {
+              setProfile(e.target.value as ComplianceProfile);
+              setReport(null);
+            }}
+            className="h-9 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+          >
+            {profiles.map((p) => (
+              <option key={p.profile} value={p.profile}>{p.name}</option>
+            ))}
+          </select>
+          <Button variant="outline" size="sm" onClick={run} disabled={!profile || isRunning}>
+            <RefreshCw className={`h-4 w-4 mr-2 ${isRunning ? "animate-spin" : ""}`} />
+            Prüfen
+          </Button>
+          {selected && (
+            <span className="text-xs text-muted-foreground">
+              {selected.controls} Kontrollen · {selected.description}
+            </span>
+          )}
+        </div>
+        {error && <p className="text-destructive">{error}</p>}
+        {report && (
+          <>
+            <div className="flex gap-4">
+              <span className="text-green-500">{report.passed} erfüllt</span>
+              <span className="text-red-500">{report.failed} nicht erfüllt</span>
+              <span className="text-muted-foreground">{report.skipped} nicht bewertet</span>
+            </div>
+            <ul className="space-y-1">
+              {report.controls.map((control) => (
+                <li key={control.id} className="flex items-start gap-2">
+                  <span className={`w-24 shrink-0 ${controlStates[control.status].className}`}>
+                    {controlStates[control.status].label}
+                  </span>
+                  <div>
+                    <p>{control.title}</p>
+                    {control.evidence && (
+                      <p className="text-xs text-muted-foreground">{control.evidence}</p>
+                    )}
+                  </div>
+                </li>
+              ))}
+            </ul>
+          </>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
+
+function ScanDiffCard({ diff }: { diff: SecurityScanDiff }) {
+  const previous = new Date(diff.from_timestamp * 1000).toLocaleString();
+  return (
+    <Card>
+      <CardHeader className="pb-2">
+        <CardTitle className="text-sm">Seit dem Scan vom {previous}</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-2 text-sm">
+        <div className="flex gap-4">
+          <span className="text-red-500">{diff.new_issues.length} neu</span>
+          <span className="text-green-500">{diff.fixed_issues.length} behoben</span>
+          <span className="text-muted-foreground">{diff.persistent_issues.length} unverändert</span>
+        </div>
+        {diff.new_issues.length > 0 && (
+          <ul className="list-disc pl-5">
+            {diff.new_issues.map((issue) => (
+              <li key={issue.id}>{issue.title}</li>
+            ))}
+          </ul>
+        )}
+        {diff.fixed_issues.length > 0 && (
+          <ul className="list-disc pl-5 text-muted-foreground line-through">
+            {diff.fixed_issues.map((issue) => (
+              <li key={issue.id}>{issue.title}</li>
+            ))}
+          </ul>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
+
 export function Security() {
   const [scanResult, setScanResult] = useState<SecurityScanResult | null>(null);
-  const [llmAnalysis, setLlmAnalysis] = useState<string | null>(null);
+  const [llmAnalysis, setLlmAnalysis] = useState<SecurityAnalysis | null>(null);
   const [isScanning, setIsScanning] = useState(false);
+  const [isScanningRouter, setIsScanningRouter] = useState(false);
+  const [isProbingAuth, setIsProbingAuth] = useState(false);
+  const [firewall, setFirewall] = useState<FirewallStatus | null>(null);
+  const [scanDiff, setScanDiff] = useState<SecurityScanDiff | null>(null);
   const [isAnalyzing, setIsAnalyzing] = useState(false);
+  const [remediationPlan, setRemediationPlan] = useState<RemediationPlan | null>(null);
+  const [isPlanning, setIsPlanning] = useState(false);
+  // Findings of the hovered plan step, or the hovered finding
+  const [highlightedIds, setHighlightedIds] = useState<string[]>([]);
+  const [tlsResults, setTlsResults] = useState<TlsInspection[] | null>(null);
+  const [isScanningTls, setIsScanningTls] = useState(false);
+  const [reportFormat, setReportFormat] = useState<ReportFormat>("html");
+  const [reportPath, setReportPath] = useState<string | null>(null);
   const [error, setError] = useState<string | null>(null);
   const { services, fetchServices } = useServiceStore();
 
@@ -85,6 +330,7 @@ export function Security() {
       await fetchServices();
       const result = await api.scanSecurity();
       setScanResult(result);
+      api.checkFirewall().then(setFirewall).catch(() => setFirewall(null));
     } catch (err) {
       setError(String(err));
     } finally {
@@ -92,6 +338,44 @@ export function Security() {
     }
   };
 
+  // Also asks the router for UPnP port forwardings, takes a few seconds
+  const runRouterScan = async () => {
+    setIsScanningRouter(true);
+    setError(null);
+    try {
+      setScanResult(await api.scanSecurity(undefined, true));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsScanningRouter(false);
+    }
+  };
+
+  // Connects to local datastores and sends read-only commands without credentials
+  const runAuthProbe = async () => {
+    setIsProbingAuth(true);
+    setError(null);
+    try {
+      setScanResult(await api.scanSecurity(undefined, undefined, true));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsProbingAuth(false);
+    }
+  };
+
+  const runTlsScan = async () => {
+    setIsScanningTls(true);
+    setError(null);
+    try {
+      setTlsResults(await api.scanTlsPorts());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsScanningTls(false);
+    }
+  };
+
   const runLlmAnalysis = async () => {
     setIsAnalyzing(true);
     setError(null);
@@ -113,10 +397,73 @@ export function Security() {
     }
   };
 
+  const runRemediationPlan = async () => {
+    if (!scanResult) return;
+    setIsPlanning(true);
+    setError(null);
+    try {
+      setRemediationPlan(await api.getRemediationPlan(scanResult));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsPlanning(false);
+    }
+  };
+
+  const showIssue = (issueId: string) => {
+    document.getElementById(`issue-${issueId}`)?.scrollIntoView({ behavior: "smooth", block: "center" });
+  };
+
+  const hoverIssue = (issueId: string | null) => setHighlightedIds(issueId ? [issueId] : []);
+
+  // Exports the latest stored scan, which runScan has just written
+  const exportReport = async () => {
+    setError(null);
+    try {
+      setReportPath((await api.exportSecurityReport(reportFormat)).path);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
   useEffect(() => {
     runScan();
   }, []);
 
+  const acknowledgeIssue = async (issue: SecurityIssue) => {
+    const reason = prompt(`Warum ist "${issue.title}" ein akzeptiertes Risiko?`);
+    if (!reason?.trim()) return;
+    const days = Number(prompt("Wie viele Tage soll die Ausnahme gelten? (leer = unbegrenzt)") || 0);
+    const expires = days > 0 ? new Date(Date.now() + days * 86_400_000).toISOString() : undefined;
+    try {
+      await api.acknowledgeSecurityIssue(issue.id, reason, expires);
+      await runScan();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const revokeAcknowledgement = async (id: string) => {
+    try {
+      await api.revokeSecurityAcknowledgement(id);
+      await runScan();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  // Every scan is stored; compare the latest with the one before it
+  useEffect(() => {
+    // The plan refers to findings of the previous scan
+    setRemediationPlan(null);
+    if (!scanResult) return;
+    api
+      .getSecurityScans(2)
+      .then((scans) => (scans.length < 2 ? null : api.diffSecurityScans(scans[1].id, scans[0].id)))
+      .then(setScanDiff)
+      .catch(() => setScanDiff(null));
+  }, [scanResult]);
+
   const criticalAndHigh = scanResult?.issues.filter(
     (i) => i.severity === "critical" || i.severity === "high"
   ) || [];
@@ -139,13 +486,59 @@ export function Security() {
             <RefreshCw className={`h-4 w-4 mr-2 ${isScanning ? "animate-spin" : ""}`} />
             Scan
           </Button>
+          <Button onClick={runRouterScan} disabled={isScanningRouter} variant="outline">
+            <Router className={`h-4 w-4 mr-2 ${isScanningRouter ? "animate-pulse" : ""}`} />
+            Router prüfen
+          </Button>
+          <Button
+            onClick={runAuthProbe}
+            disabled={isProbingAuth}
+            variant="outline"
+            title="Prüft Redis, MongoDB, Elasticsearch und Memcached auf Zugriff ohne Passwort"
+          >
+            <KeyRound className={`h-4 w-4 mr-2 ${isProbingAuth ? "animate-pulse" : ""}`} />
+            Zugang prüfen
+          </Button>
+          <Button onClick={runTlsScan} disabled={isScanningTls} variant="outline">
+            <Lock className={`h-4 w-4 mr-2 ${isScanningTls ? "animate-pulse" : ""}`} />
+            TLS prüfen
+          </Button>
           <Button onClick={runLlmAnalysis} disabled={isAnalyzing} variant="default">
             <Shield className={`h-4 w-4 mr-2 ${isAnalyzing ? "animate-pulse" : ""}`} />
             KI-Analyse
           </Button>
+          <Button
+            onClick={runRemediationPlan}
+            disabled={isPlanning || !scanResult || scanResult.issues.length === 0}
+            variant="outline"
+            title="Priorisierter Plan zur Behebung der gefundenen Probleme"
+          >
+            <ListChecks className={`h-4 w-4 mr-2 ${isPlanning ? "animate-pulse" : ""}`} />
+            Maßnahmenplan
+          </Button>
+          <select
+            value={reportFormat}
+            onChange={(e) => setReportFormat(e.target.value as ReportFormat)}
+            className="h-10 rounded-md border bg-background px-3 text-sm"
+            title="Format des Berichts"
+          >
+            <option value="html">HTML</option>
+            <option value="pdf">PDF</option>
+            <option value="sarif">SARIF</option>
+          </select>
+          <Button onClick={exportReport} disabled={!scanResult} variant="outline">
+            <FileDown className="h-4 w-4 mr-2" />
+            Bericht
+          </Button>
         </div>
       </div>
 
+      {reportPath && (
+        <p className="text-sm text-muted-foreground">
+          Bericht gespeichert unter <span className="font-mono">{reportPath}</span>
+        </p>
+      )}
+
       {error && (
         <Card className="border-destructive">
           <CardContent className="pt-4">
@@ -219,6 +612,12 @@ export function Security() {
         </Card>
       </div>
 
+      {firewall && <FirewallCard status={firewall} />}
+
+      {scanDiff && <ScanDiffCard diff={scanDiff} />}
+
+      <ComplianceCard />
+
       {/* LLM Analysis */}
       {llmAnalysis && (
         <Card>
@@ -228,12 +627,77 @@ export function Security() {
               KI-Sicherheitsanalyse
             </CardTitle>
           </CardHeader>
-          <CardContent>
-            <div className="prose prose-sm max-w-none dark:prose-invert">
-              <pre className="whitespace-pre-wrap text-sm bg-muted p-4 rounded-lg">
-                {llmAnalysis}
-              </pre>
-            </div>
+          <CardContent className="space-y-3">
+            <p className="text-sm">{llmAnalysis.summary}</p>
+            {llmAnalysis.risks.map((risk, index) => {
+              const { bg, text, icon: SeverityIcon } = severityColors[risk.severity] ?? severityColors.info;
+              return (
+                <div key={index} className={`flex items-start gap-3 rounded-lg p-3 ${bg}`}>
+                  <SeverityIcon className={`mt-0.5 h-4 w-4 shrink-0 ${text}`} />
+                  <div className="space-y-1">
+                    <p className="text-sm font-medium">
+                      {risk.title}
+                      {risk.service_name && (
+                        <span className="ml-2 font-normal text-muted-foreground">{risk.service_name}</span>
+                      )}
+                    </p>
+                    <p className="text-sm text-muted-foreground">{risk.recommendation}</p>
+                  </div>
+                </div>
+              );
+            })}
+          </CardContent>
+        </Card>
+      )}
+
+      {/* Remediation Plan */}
+      {remediationPlan && (
+        <Card>
+          <CardHeader>
+            <CardTitle className="flex items-center gap-2">
+              <ListChecks className="h-5 w-5" />
+              Maßnahmenplan
+            </CardTitle>
+          </CardHeader>
+          <CardContent className="space-y-3">
+            <p className="text-sm">{remediationPlan.summary}</p>
+            {remediationPlan.steps.map((step) => {
+              const { bg, text } = severityColors[step.severity ?? "info"];
+              const active = step.finding_ids.some((id) => highlightedIds.includes(id));
+              return (
+                <div
+                  key={step.priority}
+                  className={`rounded-lg border p-3 ${active ? "ring-2 ring-primary" : ""}`}
+                  onMouseEnter={() => setHighlightedIds(step.finding_ids)}
+                  onMouseLeave={() => setHighlightedIds([])}
+                >
+                  <p className="text-sm font-medium">
+                    <span className={`mr-2 rounded px-1.5 py-0.5 text-xs ${bg} ${text}`}>{step.priority}</span>
+                    {step.title}
+                  </p>
+                  <p className="mt-1 text-sm text-muted-foreground">{step.text}</p>
+                  {step.finding_ids.length > 0 && (
+                    <div className="mt-2 flex flex-wrap gap-1">
+                      {step.finding_ids.map((id) => (
+                        <Badge
+                          key={id}
+                          variant="outline"
+                          className="cursor-pointer text-xs"
+                          onClick={() => showIssue(id)}
+                        >
+                          {scanResult?.issues.find((i) => i.id === id)?.title ?? id}
+                        </Badge>
+                      ))}
+                    </div>
+                  )}
+                </div>
+              );
+            })}
+            {remediationPlan.unaddressed_finding_ids.length > 0 && (
+              <p className="text-xs text-muted-foreground">
+                Nicht im Plan: {remediationPlan.unaddressed_finding_ids.length} Befunde
+              </p>
+            )}
           </CardContent>
         </Card>
       )}
@@ -247,7 +711,13 @@ export function Security() {
           </h2>
           <div className="space-y-3">
             {criticalAndHigh.map((issue) => (
-              <SecurityIssueCard key={issue.id} issue={issue} />
+              <SecurityIssueCard
+                key={issue.id}
+                issue={issue}
+                onAcknowledge={acknowledgeIssue}
+                highlighted={highlightedIds.includes(issue.id)}
+                onHover={remediationPlan ? hoverIssue : undefined}
+              />
             ))}
           </div>
         </div>
@@ -262,12 +732,67 @@ export function Security() {
           </h2>
           <div className="space-y-3">
             {mediumAndLow.map((issue) => (
-              <SecurityIssueCard key={issue.id} issue={issue} />
+              <SecurityIssueCard
+                key={issue.id}
+                issue={issue}
+                onAcknowledge={acknowledgeIssue}
+                highlighted={highlightedIds.includes(issue.id)}
+                onHover={remediationPlan ? hoverIssue : undefined}
+              />
+            ))}
+          </div>
+        </div>
+      )}
+
+      {/* Acknowledged Issues */}
+      {scanResult && scanResult.acknowledged.length > 0 && (
+        <div className="space-y-4">
+          <h2 className="text-xl font-semibold flex items-center gap-2">
+            <CheckCircle2 className="h-5 w-5 text-muted-foreground" />
+            Akzeptierte Risiken ({scanResult.acknowledged.length})
+          </h2>
+          <div className="space-y-2">
+            {scanResult.acknowledged.map(({ issue, acknowledgement }) => (
+              <Card key={issue.id}>
+                <CardContent className="pt-4 flex items-start justify-between gap-3">
+                  <div className="min-w-0">
+                    <p className="font-medium">{issue.title}</p>
+                    <p className="text-sm text-muted-foreground">{acknowledgement.reason}</p>
+                    <p className="text-xs text-muted-foreground mt-1">
+                      Akzeptiert am {new Date(acknowledgement.acknowledged_at).toLocaleDateString()}
+                      {acknowledgement.expires && `, gültig bis ${new Date(acknowledgement.expires).toLocaleDateString()}`}
+                    </p>
+                  </div>
+                  <Button size="sm" variant="ghost" onClick={() => revokeAcknowledgement(issue.id)}>
+                    <Undo2 className="h-4 w-4 mr-1" />
+                    Zurücknehmen
+                  </Button>
+                </CardContent>
+              </Card>
             ))}
           </div>
         </div>
       )}
 
+      {/* TLS Endpoints */}
+      {tlsResults && (
+        <div className="space-y-4">
+          <h2 className="text-xl font-semibold flex items-center gap-2">
+            <Lock className="h-5 w-5" />
+            TLS-Endpunkte ({tlsResults.length})
+          </h2>
+          {tlsResults.length === 0 ? (
+            <p className="text-sm text-muted-foreground">Keine lokalen TLS-Ports gefunden.</p>
+          ) : (
+            <div className="space-y-3">
+              {tlsResults.map((inspection) => (
+                <TlsInspectionCard key={`${inspection.host}-${inspection.port}`} inspection={inspection} />
+              ))}
+            </div>
+          )}
+        </div>
+      )}
+
       {/* No Issues */}
       {scanResult && scanResult.issues.length === 0 && (
         <Card className="border-green-500">
@@ -4,9 +4,11 @@ import { Button } from "../components/ui/button";
 import { Badge } from "../components/ui/badge";
 import { Switch } from "../components/ui/switch";
 import { useServiceStore } from "../stores/serviceStore";
-import { Play, Square, RefreshCw, Skull, Pause } from "lucide-react";
-import type { ServiceStatus, ServiceType } from "../lib/tauri/types";
+import { Play, Square, RefreshCw, Skull, Pause, Undo2, Users, Eye, EyeOff } from "lucide-react";
+import * as api from "../lib/tauri/commands";
+import type { EnergyUsage, ServiceStatus, ServiceType, UserScopeStatus } from "../lib/tauri/types";
 import { ServiceInfoButton } from "../components/services/ServiceInfoButton";
+import { ServiceDependenciesButton } from "../components/services/ServiceDependenciesButton";
 import { ResourceStats } from "../components/services/ResourceBar";
 
 const statusColors: Record<ServiceStatus, "success" | "secondary" | "destructive" | "outline"> = {
@@ -24,6 +26,21 @@ const typeLabels: Record<ServiceType, string> = {
   process: "Process",
 };
 
+// Impact score, power draw and how much of the CPU time runs at background QoS
+function describeEnergy(energy: EnergyUsage): string {
+  const parts: string[] = [];
+  if (energy.energy_impact !== null) parts.push(`impact ${energy.energy_impact.toFixed(1)}`);
+  if (energy.power_milliwatts !== null) parts.push(`${energy.power_milliwatts.toFixed(0)} mW`);
+  if (energy.qos) {
+    const total = Object.values(energy.qos).reduce((sum, ms) => sum + ms, 0);
+    if (total > 0) {
+      const background = ((energy.qos.background + energy.qos.maintenance) / total) * 100;
+      parts.push(`${background.toFixed(0)}% background QoS`);
+    }
+  }
+  return parts.join(" • ");
+}
+
 export function Services() {
   const {
     services,
@@ -40,6 +57,9 @@ export function Services() {
   } = useServiceStore();
 
   const [togglingAutostart, setTogglingAutostart] = useState<Set<string>>(new Set());
+  const [isRestoring, setIsRestoring] = useState(false);
+  const [userScope, setUserScope] = useState<UserScopeStatus | null>(null);
+  const [watched, setWatched] = useState<Set<string>>(new Set());
   const isAutoRefreshing = autoRefreshInterval !== null;
 
   const handleToggleAutostart = async (serviceId: string, enable: boolean) => {
@@ -60,6 +80,57 @@ export function Services() {
     }
   };
 
+  const handleRestoreSession = async () => {
+    const changes = await api.getSessionChanges();
+    if (changes.length === 0) {
+      alert("No changes were made in this session.");
+      return;
+    }
+    if (!confirm(`Restart or re-enable ${changes.length} service(s) changed in this session?`)) {
+      return;
+    }
+
+    setIsRestoring(true);
+    try {
+      const report = await api.restoreSessionState();
+      if (report.failed > 0) {
+        const errors = report.results
+          .filter(r => !r.success)
+          .map(r => `${r.change.service_id}: ${r.error}`)
+          .join("\n");
+        alert(`Restored ${report.restored}, failed ${report.failed}:\n${errors}`);
+      }
+      await fetchServices();
+    } catch (error) {
+      console.error("Failed to restore session state:", error);
+    } finally {
+      setIsRestoring(false);
+    }
+  };
+
+  // Watched services are checked by the backend monitor every few seconds
+  const toggleWatch = async (serviceId: string) => {
+    try {
+      if (watched.has(serviceId)) {
+        await api.unwatchService(serviceId);
+      } else {
+        await api.watchService(serviceId);
+      }
+      setWatched(new Set(await api.getWatchedServices()));
+    } catch (error) {
+      alert(String(error));
+    }
+  };
+
+  const toggleAllUsers = async () => {
+    const enable = !userScope?.all_users;
+    if (enable && !confirm("Show services and processes of all users? This is recorded in the audit log and switches off automatically.")) {
+      return;
+    }
+    setUserScope(await api.setAllUsersMode(enable));
+    await fetchServices();
+  };
+
   // Check if autostart is supported for a service type
   const supportsAutostart = (serviceType: string): boolean => {
     return ["launchd", "systemd", "windows_service", "docker"].includes(serviceType);
@@ -67,6 +138,8 @@ export function Services() {
 
   useEffect(() => {
     fetchServices();
+    api.getUserScope().then(setUserScope).catch(() => {});
+    api.getWatchedServices().then((ids) => setWatched(new Set(ids))).catch(() => {});
     // Start auto-refresh when component mounts (5 seconds for better performance)
     startAutoRefresh(5000);
 
@@ -95,6 +168,19 @@ export function Services() {
           </p>
         </div>
         <div className="flex items-center gap-2">
+          <Button
+            variant={userScope?.all_users ? "default" : "outline"}
+            onClick={toggleAllUsers}
+            size="sm"
+            title={
+              userScope?.all_users && userScope.expires_at
+                ? `All users until ${new Date(userScope.expires_at).toLocaleTimeString()}`
+                : `Only ${userScope?.current_user ?? "your"} processes`
+            }
+          >
+            <Users className="mr-2 h-4 w-4" />
+            {userScope?.all_users ? "All Users" : "My Processes"}
+          </Button>
           <Button
             variant={isAutoRefreshing ? "default" : "outline"}
             onClick={toggleAutoRefresh}
@@ -112,6 +198,10 @@ export function Services() {
               </>
             )}
           </Button>
+          <Button onClick={handleRestoreSession} disabled={isRestoring} variant="outline" size="sm">
+            <Undo2 className="mr-2 h-4 w-4" />
+            Undo Session
+          </Button>
           <Button onClick={fetchServices} disabled={isLoading} variant="outline">
             <RefreshCw className={`mr-2 h-4 w-4 ${isLoading ? "animate-spin" : ""}`} />
             Refresh
@@ -141,13 +231,29 @@ export function Services() {
                 <div className="flex flex-col gap-2 sm:flex-row sm:items-center sm:justify-between">
                   <div className="flex items-center gap-2 flex-wrap">
                     <ServiceInfoButton service={service} />
+                    {service.status === "running" && service.pid && (
+                      <ServiceDependenciesButton service={service} />
+                    )}
                     <CardTitle className="text-base truncate max-w-[200px] sm:max-w-[300px]">{service.name}</CardTitle>
                     <Badge variant={statusColors[service.status]} className="shrink-0">
                       {service.status}
                     </Badge>
                     <Badge variant="outline" className="shrink-0">{typeLabels[service.service_type]}</Badge>
+                    {service.flapping && (
+                      <Badge variant="warning" className="shrink-0" title="Restarts repeatedly">
+                        flapping
+                      </Badge>
+                    )}
                   </div>
                   <div className="flex items-center gap-1 flex-wrap">
+                    <Button
+                      size="sm"
+                      variant={watched.has(service.id) ? "secondary" : "ghost"}
+                      onClick={() => toggleWatch(service.id)}
+                      title={watched.has(service.id) ? "Stop watching" : "Watch for status changes"}
+                    >
+                      {watched.has(service.id) ? <EyeOff className="h-4 w-4" /> : <Eye className="h-4 w-4" />}
+                    </Button>
                     {service.status === "stopped" && (
                       <Button
                         size="sm"
@@ -189,14 +295,38 @@ export function Services() {
               </CardHeader>
               <CardContent>
                 {/* Resource Usage */}
-                {(service.cpu_usage !== null || service.memory_bytes !== null) && (
-                  <div className="mb-4 p-3 rounded-lg bg-muted/50">
-                    <ResourceStats
-                      cpuUsage={service.cpu_usage}
-                      memoryBytes={service.memory_bytes}
-                      memoryPercent={service.memory_percent}
-                      compact
-                    />
+                {(service.cpu_usage !== null || service.memory_bytes !== null || service.memory_total !== null || service.gpu_usage || service.energy || service.network_rx_bytes !== null) && (
+                  <div className="mb-4 p-3 rounded-lg bg-muted/50 space-y-1">
+                    {(service.cpu_usage !== null || service.memory_bytes !== null) && (
+                      <ResourceStats
+                        cpuUsage={service.cpu_usage}
+                        memoryBytes={service.memory_bytes}
+                        memoryPercent={service.memory_percent}
+                        compact
+                      />
+                    )}
+                    {service.memory_total !== null && service.memory_total !== service.memory_bytes && (
+                      <p className="text-xs text-muted-foreground">
+                        With child processes: CPU {service.cpu_usage_total?.toFixed(1) ?? "-"}%, RAM{" "}
+                        {(service.memory_total / (1024 * 1024)).toFixed(0)} MB
+                      </p>
+                    )}
+                    {service.gpu_usage && (
+                      <p className="text-xs text-muted-foreground">
+                        GPU: {service.gpu_usage.utilization_percent?.toFixed(0) ?? "-"}%
+                        {service.gpu_usage.memory_bytes !== null &&
+                          `, VRAM ${(service.gpu_usage.memory_bytes / (1024 * 1024)).toFixed(0)} MB`}
+                      </p>
+                    )}
+                    {service.energy && describeEnergy(service.energy) && (
+                      <p className="text-xs text-muted-foreground">Energy: {describeEnergy(service.energy)}</p>
+                    )}
+                    {service.network_rx_bytes !== null && (
+                      <p className="text-xs text-muted-foreground">
+                        Network: {(service.network_rx_bytes / (1024 * 1024)).toFixed(1)} MB received,{" "}
+                        {((service.network_tx_bytes ?? 0) / (1024 * 1024)).toFixed(1)} MB sent
+                      </p>
+                    )}
                   </div>
                 )}
 
@@ -214,6 +344,20 @@ export function Services() {
                       {service.pid || "-"}
                     </p>
                   </div>
+                  {service.recent_restarts > 0 && (
+                    <div className="min-w-[60px]">
+                      <p className="text-muted-foreground text-xs">Restarts</p>
+                      <p className={`font-mono font-medium text-sm ${service.flapping ? "text-amber-500" : ""}`}>
+                        {service.recent_restarts}
+                      </p>
+                    </div>
+                  )}
+                  {service.user && (
+                    <div className="min-w-[60px]">
+                      <p className="text-muted-foreground text-xs">User</p>
+                      <p className="font-medium text-sm">{service.user}</p>
+                    </div>
+                  )}
                   {supportsAutostart(service.service_type) && (
                     <div className="min-w-[80px]">
                       <p className="text-muted-foreground text-xs">Autostart</p>
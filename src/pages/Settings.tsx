@@ -1,8 +1,86 @@
 import { Card, CardContent, CardHeader, CardTitle, CardDescription } from "../components/ui/card";
 import { Button } from "../components/ui/button";
 import { useUIStore } from "../stores/uiStore";
-import { Moon, Sun, Monitor, Shield, Bot, Clock } from "lucide-react";
-import type { ThemeMode } from "../lib/tauri/types";
+import { useEffect, useState } from "react";
+import { Moon, Sun, Monitor, Shield, Bot, Clock, Smartphone, HardDrive, Download, Wrench, Tag, X, Radar, BellRing, Bell, Webhook, MonitorSpeaker, FileSearch, FileText, BookOpen, Stethoscope } from "lucide-react";
+import { useNavigate } from "react-router-dom";
+import * as api from "../lib/tauri/commands";
+import type {
+  AlertCondition, AlertRule, CloudLlmConfig, Config, LlmDiagnostics, ExportFormat, ExportKind, HashReputationConfig, NmapConfig, NmapStatus, NotificationConfig, PairingInfo, PowermetricsStatus, ProcessCategory, ProcessKnowledgeEntry, ProcessSafety, PromptKind, PromptTemplate, Protocol, RemoteViewerStatus, ServiceNameOverride, StorageConfig,
+  StoragePaths, TaskModels, ThemeMode, WebhookConfig, WebhookEvent,
+} from "../lib/tauri/types";
+
+const alertKinds: { value: AlertCondition["kind"]; label: string }[] = [
+  { value: "cpu_above", label: "CPU above %" },
+  { value: "memory_above", label: "Memory above GB" },
+  { value: "service_stopped", label: "Service stopped" },
+  { value: "new_public_port", label: "New public port" },
+];
+
+function describeCondition(condition: AlertCondition): string {
+  switch (condition.kind) {
+    case "cpu_above":
+      return `CPU > ${condition.percent}%` + (condition.minutes ? ` for ${condition.minutes} min` : "");
+    case "memory_above":
+      return (
+        `Memory > ${(condition.bytes / 1024 ** 3).toFixed(1)} GB` +
+        (condition.minutes ? ` for ${condition.minutes} min` : "")
+      );
+    case "service_stopped":
+      return "Service stopped";
+    case "new_public_port":
+      return "New public port";
+  }
+}
+
+const notificationOptions: { key: keyof NotificationConfig; label: string; description: string }[] = [
+  { key: "service_crashed", label: "Service crashed", description: "A running service went into the error state" },
+  { key: "security_critical", label: "Critical security findings", description: "A security scan found critical issues" },
+  { key: "port_opened", label: "Watched ports", description: "New public listeners and taken port reservations" },
+  { key: "alerts", label: "Alerts", description: "An alert rule fired" },
+  { key: "disk_space", label: "Disk space", description: "A disk is filled beyond the warning threshold" },
+  { key: "memory_pressure", label: "Memory pressure", description: "Memory is low, swap grows fast or memory is about to run out" },
+  {
+    key: "run_in_background",
+    label: "Keep running when closed",
+    description: "Closing the window hides it to the tray so notifications keep arriving",
+  },
+];
+
+const webhookEvents: { value: WebhookEvent; label: string }[] = [
+  { value: "service_status", label: "Status changes" },
+  { value: "alert", label: "Alerts" },
+  { value: "security_finding", label: "Security findings" },
+];
+
+const gigabytes = (bytes: number) => `${(bytes / 1024 ** 3).toFixed(1)} GB`;
+
+const taskOptions: { key: keyof Config["ollama"]["task_timeouts"]; task: TaskModels["task"]; label: string }[] = [
+  { key: "explanation_seconds", task: "explanation", label: "Explanations" },
+  { key: "analysis_seconds", task: "analysis", label: "Analyses" },
+  { key: "structured_seconds", task: "structured", label: "Recommendations (JSON)" },
+  { key: "chat_seconds", task: "chat", label: "Chat" },
+];
+
+const promptLabels: Record<PromptKind, string> = {
+  log_analysis: "Log analysis",
+  log_chunk_summary: "Log chunk summary",
+  process_explanation: "Process explanation",
+  recommendations: "Recommendations",
+  remediation_plan: "Remediation plan",
+  security_analysis: "Security analysis",
+};
+
+const safetyLabels: Record<ProcessSafety, string> = {
+  essential: "Essential",
+  important: "Important",
+  optional: "Optional",
+  unknown: "Unknown",
+};
+
+const categoryOptions: ProcessCategory[] = [
+  "system", "application", "browser", "development", "database", "communication", "productivity", "security", "media", "utility", "other",
+];
 
 const themeOptions: { value: ThemeMode; label: string; icon: typeof Sun }[] = [
   { value: "light", label: "Light", icon: Sun },
@@ -12,15 +90,374 @@ const themeOptions: { value: ThemeMode; label: string; icon: typeof Sun }[] = [
 
 export function Settings() {
   const { theme, setTheme, refreshInterval, setRefreshInterval } = useUIStore();
+  const navigate = useNavigate();
+  const [pairing, setPairing] = useState<PairingInfo | null>(null);
+  const [viewerStatus, setViewerStatus] = useState<RemoteViewerStatus | null>(null);
+  const [viewerError, setViewerError] = useState<string | null>(null);
+  const [config, setConfig] = useState<Config | null>(null);
+  const [storagePaths, setStoragePaths] = useState<StoragePaths | null>(null);
+  const [storageDraft, setStorageDraft] = useState<StorageConfig>({ config_dir: null, data_dir: null, log_dir: null });
+  const [storageError, setStorageError] = useState<string | null>(null);
+  const [exportKind, setExportKind] = useState<ExportKind>("inventory");
+  const [exportFormat, setExportFormat] = useState<ExportFormat>("markdown");
+  const [exportPath, setExportPath] = useState("");
+  const [exportResult, setExportResult] = useState<string | null>(null);
+  const [exportError, setExportError] = useState<string | null>(null);
+  const [isExporting, setIsExporting] = useState(false);
+  const [templateDir, setTemplateDir] = useState<string | null>(null);
+  const [namePort, setNamePort] = useState("");
+  const [nameProtocol, setNameProtocol] = useState<Protocol | "">("");
+  const [nameLabel, setNameLabel] = useState("");
+  const [nmapStatus, setNmapStatus] = useState<NmapStatus | null>(null);
+  const [alertName, setAlertName] = useState("");
+  const [alertKind, setAlertKind] = useState<AlertCondition["kind"]>("cpu_above");
+  const [alertThreshold, setAlertThreshold] = useState("80");
+  const [alertMinutes, setAlertMinutes] = useState("5");
+  const [alertService, setAlertService] = useState("");
+  const [webhookName, setWebhookName] = useState("");
+  const [webhookUrl, setWebhookUrl] = useState("");
+  const [webhookSecret, setWebhookSecret] = useState("");
+  const [webhookEventFilter, setWebhookEventFilter] = useState<WebhookEvent[]>([]);
+  const [webhookResult, setWebhookResult] = useState<{ id: string; message: string } | null>(null);
+  const [powermetricsStatus, setPowermetricsStatus] = useState<PowermetricsStatus | null>(null);
+  const [reputationKey, setReputationKey] = useState("");
+  const [cloudDraft, setCloudDraft] = useState({ api_key: "", model: "" });
+  const [ollamaDraft, setOllamaDraft] = useState({ endpoint: "", model: "", timeout_seconds: "30" });
+  const [taskModels, setTaskModels] = useState<TaskModels[]>([]);
+  const [diagnostics, setDiagnostics] = useState<LlmDiagnostics | null>(null);
+  const [isDiagnosing, setIsDiagnosing] = useState(false);
+  const [diagnosticsError, setDiagnosticsError] = useState<string | null>(null);
+  const [promptTemplates, setPromptTemplates] = useState<PromptTemplate[]>([]);
+  const [promptKind, setPromptKind] = useState<PromptKind>("log_analysis");
+  const [promptDraft, setPromptDraft] = useState("");
+  const [promptError, setPromptError] = useState<string | null>(null);
+  const [knowledgeEntries, setKnowledgeEntries] = useState<ProcessKnowledgeEntry[]>([]);
+  const [knowledgeDraft, setKnowledgeDraft] = useState({
+    name: "",
+    patterns: "",
+    explanation: "",
+    safety: "optional" as ProcessSafety,
+    category: "application" as ProcessCategory,
+  });
+  const [knowledgeError, setKnowledgeError] = useState<string | null>(null);
+
+  useEffect(() => {
+    api.getRemoteViewerStatus().then(setViewerStatus).catch(() => {});
+    api.getConfig().then((loaded) => {
+      setConfig(loaded);
+      setStorageDraft(loaded.storage);
+      setReputationKey(loaded.hash_reputation.api_key ?? "");
+      setCloudDraft({ api_key: loaded.cloud_llm.api_key ?? "", model: loaded.cloud_llm.model });
+      setOllamaDraft({
+        endpoint: loaded.ollama.endpoint,
+        model: loaded.ollama.model,
+        timeout_seconds: String(loaded.ollama.timeout_seconds),
+      });
+    }).catch(() => {});
+    api.getStoragePaths().then(setStoragePaths).catch(() => {});
+    api.getExportTemplateDir().then(setTemplateDir).catch(() => {});
+    api.getNmapStatus().then(setNmapStatus).catch(() => {});
+    api.getPowermetricsStatus().then(setPowermetricsStatus).catch(() => {});
+    api.getTaskModels().then(setTaskModels).catch(() => {});
+    api.listProcessKnowledgeEntries().then(setKnowledgeEntries).catch(() => {});
+    api.listPromptTemplates().then((templates) => {
+      setPromptTemplates(templates);
+      setPromptDraft(templates.find((t) => t.kind === "log_analysis")?.content ?? "");
+    }).catch(() => {});
+  }, []);
+
+  const applyStorage = async () => {
+    if (!config) return;
+    setStorageError(null);
+    const updated = { ...config, storage: storageDraft };
+    try {
+      // Existing files are moved to the new directories
+      await api.updateConfig(updated);
+      setConfig(updated);
+      setStoragePaths(await api.getStoragePaths());
+    } catch (error) {
+      setStorageError(String(error));
+    }
+  };
+
+  const runExport = async () => {
+    setIsExporting(true);
+    setExportError(null);
+    setExportResult(null);
+    try {
+      const result = await api.exportReport(exportKind, exportFormat, exportPath.trim() || undefined);
+      setExportResult(
+        `Wrote ${(result.bytes / 1024).toFixed(1)} KB to ${result.path}` +
+          (result.template ? ` using ${result.template}` : "")
+      );
+    } catch (error) {
+      setExportError(String(error));
+    } finally {
+      setIsExporting(false);
+    }
+  };
+
+  const saveServiceNames = async (serviceNames: ServiceNameOverride[]) => {
+    if (!config) return;
+    const updated = { ...config, service_names: serviceNames };
+    await api.updateConfig(updated);
+    setConfig(updated);
+  };
+
+  const addServiceName = async () => {
+    if (!config) return;
+    const entry: ServiceNameOverride = {
+      port: Number(namePort),
+      protocol: nameProtocol || null,
+      name: nameLabel.trim(),
+    };
+    const others = config.service_names.filter((n) => n.port !== entry.port || n.protocol !== entry.protocol);
+    await saveServiceNames([...others, entry].sort((a, b) => a.port - b.port));
+    setNamePort("");
+    setNameLabel("");
+  };
+
+  const saveAlertRules = async (rules: AlertRule[]) => {
+    if (!config) return;
+    const updated = { ...config, alerts: { ...config.alerts, rules } };
+    await api.updateConfig(updated);
+    setConfig(updated);
+  };
+
+  const addAlertRule = async () => {
+    if (!config) return;
+    const threshold = Number(alertThreshold);
+    const minutes = Number(alertMinutes) || 0;
+    const condition: AlertCondition =
+      alertKind === "cpu_above"
+        ? { kind: "cpu_above", percent: threshold, minutes }
+        : alertKind === "memory_above"
+          ? { kind: "memory_above", bytes: Math.round(threshold * 1024 ** 3), minutes }
+          : { kind: alertKind };
+    const rule: AlertRule = {
+      id: crypto.randomUUID(),
+      name: alertName.trim() || describeCondition(condition),
+      enabled: true,
+      service_id: alertService.trim() || null,
+      condition,
+    };
+    await saveAlertRules([...config.alerts.rules, rule]);
+    setAlertName("");
+    setAlertService("");
+  };
+
+  const saveWebhooks = async (webhooks: WebhookConfig[]) => {
+    if (!config) return;
+    const updated = { ...config, webhooks };
+    await api.updateConfig(updated);
+    setConfig(updated);
+  };
+
+  const addWebhook = async () => {
+    if (!config) return;
+    const webhook: WebhookConfig = {
+      id: crypto.randomUUID(),
+      name: webhookName.trim() || webhookUrl.trim(),
+      url: webhookUrl.trim(),
+      secret: webhookSecret || null,
+      enabled: true,
+      events: webhookEventFilter,
+    };
+    await saveWebhooks([...config.webhooks, webhook]);
+    setWebhookName("");
+    setWebhookUrl("");
+    setWebhookSecret("");
+    setWebhookEventFilter([]);
+  };
+
+  const sendTestWebhook = async (id: string) => {
+    try {
+      await api.testWebhook(id);
+      setWebhookResult({ id, message: "Test event delivered" });
+    } catch (error) {
+      setWebhookResult({ id, message: String(error) });
+    }
+  };
+
+  const updateNmap = async (changes: Partial<NmapConfig>) => {
+    if (!config) return;
+    const updated = { ...config, nmap: { ...config.nmap, ...changes } };
+    await api.updateConfig(updated);
+    setConfig(updated);
+    setNmapStatus(await api.getNmapStatus());
+  };
+
+  const updateOllama = async (changes: Partial<Config["ollama"]>) => {
+    if (!config) return;
+    const updated = { ...config, ollama: { ...config.ollama, ...changes } };
+    await api.updateConfig(updated);
+    setConfig(updated);
+    api.getTaskModels().then(setTaskModels).catch(() => setTaskModels([]));
+  };
+
+  const runDiagnostics = async () => {
+    setIsDiagnosing(true);
+    setDiagnosticsError(null);
+    try {
+      setDiagnostics(await api.getLlmDiagnostics());
+    } catch (error) {
+      setDiagnosticsError(String(error));
+    } finally {
+      setIsDiagnosing(false);
+    }
+  };
+
+  const updateTaskTimeout = (key: keyof Config["ollama"]["task_timeouts"], value: string) => {
+    if (!config) return;
+    const seconds = Number(value);
+    updateOllama({
+      task_timeouts: { ...config.ollama.task_timeouts, [key]: value.trim() && seconds > 0 ? Math.round(seconds) : null },
+    });
+  };
+
+  const selectPrompt = (kind: PromptKind) => {
+    setPromptKind(kind);
+    setPromptError(null);
+    setPromptDraft(promptTemplates.find((t) => t.kind === kind)?.content ?? "");
+  };
+
+  const savePrompt = async (reset: boolean) => {
+    setPromptError(null);
+    try {
+      const saved = reset
+        ? await api.resetPromptTemplate(promptKind)
+        : await api.updatePromptTemplate(promptKind, promptDraft);
+      setPromptTemplates((current) => current.map((t) => (t.kind === saved.kind ? saved : t)));
+      setPromptDraft(saved.content);
+    } catch (error) {
+      setPromptError(String(error));
+    }
+  };
+
+  const addKnowledgeEntry = async () => {
+    setKnowledgeError(null);
+    try {
+      await api.addProcessKnowledgeEntry({
+        name: knowledgeDraft.name,
+        patterns: knowledgeDraft.patterns.split(","),
+        explanation: knowledgeDraft.explanation,
+        safety: knowledgeDraft.safety,
+        category: knowledgeDraft.category,
+      });
+      setKnowledgeEntries(await api.listProcessKnowledgeEntries());
+      setKnowledgeDraft({ ...knowledgeDraft, name: "", patterns: "", explanation: "" });
+    } catch (error) {
+      setKnowledgeError(String(error));
+    }
+  };
+
+  const updateHashReputation = async (changes: Partial<HashReputationConfig>) => {
+    if (!config) return;
+    const updated = { ...config, hash_reputation: { ...config.hash_reputation, ...changes } };
+    await api.updateConfig(updated);
+    setConfig(updated);
+  };
+
+  const updateCloudLlm = async (changes: Partial<CloudLlmConfig>) => {
+    if (!config) return;
+    const updated = { ...config, cloud_llm: { ...config.cloud_llm, ...changes } };
+    await api.updateConfig(updated);
+    setConfig(updated);
+  };
+
+  // Sending prompts to a cloud provider needs an explicit confirmation
+  const toggleCloudLlm = async () => {
+    if (!config) return;
+    if (!config.cloud_llm.enabled) {
+      const provider = config.cloud_llm.provider === "openai" ? "OpenAI" : "Anthropic";
+      const confirmed = confirm(
+        `Analyses, explanations and chat messages will be sent to ${provider} instead of Ollama. ` +
+          "Passwords, keys, email and IP addresses are masked first, but service names, log lines and paths leave this machine. " +
+          "Every request is recorded in the audit log. Continue?"
+      );
+      if (!confirmed) return;
+    }
+    // The drafts may not be saved yet when the button is clicked right after typing
+    await updateCloudLlm({
+      enabled: !config.cloud_llm.enabled,
+      api_key: cloudDraft.api_key.trim() || null,
+      model: cloudDraft.model.trim(),
+    });
+  };
+
+  const toggleNotification = async (key: keyof NotificationConfig) => {
+    if (!config) return;
+    const updated = {
+      ...config,
+      notifications: { ...config.notifications, [key]: !config.notifications[key] },
+    };
+    await api.updateConfig(updated);
+    setConfig(updated);
+  };
+
+  const togglePowermetrics = async () => {
+    if (!config) return;
+    const updated = {
+      ...config,
+      powermetrics: { ...config.powermetrics, enabled: !config.powermetrics.enabled },
+    };
+    await api.updateConfig(updated);
+    setConfig(updated);
+    // sudo fails within a moment if the sudoers rule is missing
+    setTimeout(() => api.getPowermetricsStatus().then(setPowermetricsStatus).catch(() => {}), 2000);
+  };
+
+  const togglePortWatch = async () => {
+    if (!config) return;
+    const updated = {
+      ...config,
+      port_watch: { ...config.port_watch, enabled: !config.port_watch.enabled },
+    };
+    await api.updateConfig(updated);
+    setConfig(updated);
+  };
+
+  const startPairing = async () => {
+    setViewerError(null);
+    try {
+      setPairing(await api.startRemotePairing());
+      setViewerStatus(await api.getRemoteViewerStatus());
+    } catch (error) {
+      setViewerError(String(error));
+    }
+  };
+
+  const stopViewer = async () => {
+    await api.stopRemoteViewer();
+    setPairing(null);
+    setViewerStatus(await api.getRemoteViewerStatus());
+  };
+
+  const revokeDevice = async (deviceId: string) => {
+    setViewerError(null);
+    try {
+      await api.revokeRemoteDevice(deviceId);
+      if (pairing?.device_id === deviceId) setPairing(null);
+      setViewerStatus(await api.getRemoteViewerStatus());
+    } catch (error) {
+      setViewerError(String(error));
+    }
+  };
 
   return (
     <div className="space-y-6">
       {/* Header */}
-      <div>
-        <h1 className="text-3xl font-bold tracking-tight">Settings</h1>
-        <p className="text-muted-foreground">
-          Configure your preferences and security settings
-        </p>
+      <div className="flex items-center justify-between">
+        <div>
+          <h1 className="text-3xl font-bold tracking-tight">Settings</h1>
+          <p className="text-muted-foreground">
+            Configure your preferences and security settings
+          </p>
+        </div>
+        <Button variant="outline" onClick={() => navigate("/setup")}>
+          <Wrench className="mr-2 h-4 w-4" />
+          Run Setup
+        </Button>
       </div>
 
       {/* Appearance */}
@@ -136,10 +573,78 @@ export function Settings() {
                 Enabled
               </Button>
             </div>
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">Port Watch</p>
+                <p className="text-sm text-muted-foreground">
+                  Alert when new listeners appear, with a notification for publicly bound ports
+                </p>
+              </div>
+              <Button variant="outline" onClick={togglePortWatch} disabled={!config}>
+                {config?.port_watch.enabled ? "Enabled" : "Disabled"}
+              </Button>
+            </div>
           </div>
         </CardContent>
       </Card>
 
+      {/* Notifications */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <Bell className="h-5 w-5" />
+            Notifications
+          </CardTitle>
+          <CardDescription>Desktop notifications, also while the window is minimized or hidden</CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="space-y-4">
+            {notificationOptions.map((option) => (
+              <div key={option.key} className="flex items-center justify-between">
+                <div>
+                  <p className="font-medium">{option.label}</p>
+                  <p className="text-sm text-muted-foreground">{option.description}</p>
+                </div>
+                <Button variant="outline" onClick={() => toggleNotification(option.key)} disabled={!config}>
+                  {config?.notifications[option.key] ? "Enabled" : "Disabled"}
+                </Button>
+              </div>
+            ))}
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Apple GPU */}
+      {powermetricsStatus?.supported && (
+        <Card>
+          <CardHeader>
+            <CardTitle className="flex items-center gap-2">
+              <MonitorSpeaker className="h-5 w-5" />
+              Apple GPU
+            </CardTitle>
+            <CardDescription>
+              GPU utilization, frequency and power, and the energy impact of each service, from{" "}
+              <code>powermetrics</code>, which needs root. Add
+              <code> %admin ALL=(root) NOPASSWD: /usr/bin/powermetrics</code> with <code>sudo visudo</code> first.
+            </CardDescription>
+          </CardHeader>
+          <CardContent>
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">GPU sampler</p>
+                <p className="text-sm text-muted-foreground">
+                  {powermetricsStatus.last_error ??
+                    (powermetricsStatus.running ? "Running" : "Not running")}
+                </p>
+              </div>
+              <Button variant="outline" onClick={togglePowermetrics} disabled={!config}>
+                {config?.powermetrics.enabled ? "Enabled" : "Disabled"}
+              </Button>
+            </div>
+          </CardContent>
+        </Card>
+      )}
+
       {/* Ollama Integration */}
       <Card>
         <CardHeader>
@@ -157,34 +662,1041 @@ export function Settings() {
               <div>
                 <p className="font-medium">Enable Ollama</p>
                 <p className="text-sm text-muted-foreground">
-                  Use local LLM for intelligent log analysis
+                  Use local LLM for intelligent log analysis. When disabled, no AI request leaves the app.
                 </p>
               </div>
-              <Button variant="outline">
-                Disabled
+              <Button
+                variant="outline"
+                onClick={() => config && updateOllama({ enabled: !config.ollama.enabled })}
+                disabled={!config}
+              >
+                {config?.ollama.enabled ? "Enabled" : "Disabled"}
               </Button>
             </div>
             <div>
               <label className="text-sm font-medium">Endpoint</label>
               <input
                 type="text"
-                value="http://localhost:11434"
-                disabled
-                className="mt-1 block w-full rounded-md border bg-muted px-3 py-2 text-sm"
+                value={ollamaDraft.endpoint}
+                onChange={(e) => setOllamaDraft({ ...ollamaDraft, endpoint: e.target.value })}
+                className="mt-1 block w-full rounded-md border bg-background px-3 py-2 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+            </div>
+            <div className="grid gap-4 sm:grid-cols-[1fr_10rem]">
+              <div>
+                <label className="text-sm font-medium">Model</label>
+                <input
+                  type="text"
+                  value={ollamaDraft.model}
+                  onChange={(e) => setOllamaDraft({ ...ollamaDraft, model: e.target.value })}
+                  className="mt-1 block w-full rounded-md border bg-background px-3 py-2 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                />
+              </div>
+              <div>
+                <label className="text-sm font-medium">Timeout (seconds)</label>
+                <input
+                  type="number"
+                  min={1}
+                  value={ollamaDraft.timeout_seconds}
+                  onChange={(e) => setOllamaDraft({ ...ollamaDraft, timeout_seconds: e.target.value })}
+                  className="mt-1 block w-full rounded-md border bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                />
+              </div>
+            </div>
+            <div className="flex justify-end">
+              <Button
+                variant="outline"
+                disabled={!config || !ollamaDraft.endpoint.trim() || !ollamaDraft.model.trim()}
+                onClick={() =>
+                  updateOllama({
+                    endpoint: ollamaDraft.endpoint.trim(),
+                    model: ollamaDraft.model.trim(),
+                    timeout_seconds: Math.max(1, Number(ollamaDraft.timeout_seconds) || 30),
+                  })
+                }
+              >
+                Apply
+              </Button>
+            </div>
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">Pick installed models</p>
+                <p className="text-sm text-muted-foreground">
+                  When the configured model is missing or times out, try other installed models: the smallest for
+                  explanations, the largest for analyses
+                </p>
+              </div>
+              <Button
+                variant="outline"
+                onClick={() => config && updateOllama({ auto_select_models: !config.ollama.auto_select_models })}
+                disabled={!config}
+              >
+                {config?.ollama.auto_select_models ? "Enabled" : "Disabled"}
+              </Button>
+            </div>
+            <div className="space-y-2">
+              <p className="font-medium">Models per task</p>
+              {taskOptions.map((option) => {
+                const chain = taskModels.find((t) => t.task === option.task);
+                return (
+                  <div key={option.key} className="grid items-center gap-2 sm:grid-cols-[12rem_1fr_8rem]">
+                    <span className="text-sm">{option.label}</span>
+                    <span className="truncate font-mono text-xs text-muted-foreground" title={chain?.models.join(" → ")}>
+                      {chain ? chain.models.join(" → ") : "Ollama not reachable"}
+                    </span>
+                    <input
+                      key={`${option.key}-${config?.ollama.task_timeouts[option.key] ?? ""}`}
+                      type="number"
+                      min={1}
+                      placeholder={`${config?.ollama.timeout_seconds ?? 30} s`}
+                      defaultValue={config?.ollama.task_timeouts[option.key] ?? ""}
+                      onBlur={(e) => updateTaskTimeout(option.key, e.target.value)}
+                      disabled={!config}
+                      title="Timeout in seconds; empty uses the general timeout"
+                      className="rounded-md border bg-background px-3 py-1 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                    />
+                  </div>
+                );
+              })}
+            </div>
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">Response language</p>
+                <p className="text-sm text-muted-foreground">
+                  Language of analyses, recommendations, explanations and chat answers
+                </p>
+              </div>
+              <select
+                value={config?.ollama.language ?? "de"}
+                onChange={(e) => updateOllama({ language: e.target.value })}
+                disabled={!config}
+                className="h-10 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              >
+                <option value="de">Deutsch</option>
+                <option value="en">English</option>
+                <option value="fr">Français</option>
+                <option value="es">Español</option>
+                <option value="it">Italiano</option>
+                <option value="nl">Nederlands</option>
+                <option value="pl">Polski</option>
+                <option value="pt">Português</option>
+              </select>
+            </div>
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">Diagnostics</p>
+                <p className="text-sm text-muted-foreground">
+                  Checks the Ollama version, model sizes against free memory and times a short test generation
+                </p>
+              </div>
+              <Button variant="outline" onClick={runDiagnostics} disabled={isDiagnosing}>
+                <Stethoscope className={`h-4 w-4 mr-2 ${isDiagnosing ? "animate-pulse" : ""}`} />
+                {isDiagnosing ? "Running..." : "Run Diagnostics"}
+              </Button>
+            </div>
+            {diagnosticsError && <p className="text-sm text-destructive">{diagnosticsError}</p>}
+            {diagnostics && (
+              <div className="space-y-3 rounded-lg border p-3 text-sm">
+                <div className="grid gap-1 sm:grid-cols-2">
+                  <span>
+                    Ollama: {diagnostics.reachable ? `${diagnostics.version ?? "unknown version"} at ` : "not reachable at "}
+                    <span className="font-mono">{diagnostics.endpoint}</span>
+                  </span>
+                  <span>
+                    Memory: {gigabytes(diagnostics.memory_available_bytes)} free of {gigabytes(diagnostics.memory_total_bytes)}
+                    {diagnostics.vram_total_bytes !== null && `, GPU ${gigabytes(diagnostics.vram_total_bytes)}`}
+                  </span>
+                  {diagnostics.probe && (
+                    <span>
+                      Test generation ({diagnostics.probe.model}): {(diagnostics.probe.total_ms / 1000).toFixed(1)} s,
+                      loading {(diagnostics.probe.load_ms / 1000).toFixed(1)} s
+                      {diagnostics.probe.tokens_per_second !== null &&
+                        `, ${diagnostics.probe.tokens_per_second.toFixed(1)} tokens/s`}
+                    </span>
+                  )}
+                  <span>
+                    Queue: {diagnostics.queue.running} running, {diagnostics.queue.waiting} waiting
+                  </span>
+                </div>
+                {diagnostics.hints.length > 0 ? (
+                  <ul className="list-disc space-y-1 pl-5 text-muted-foreground">
+                    {diagnostics.hints.map((hint) => (
+                      <li key={hint}>{hint}</li>
+                    ))}
+                  </ul>
+                ) : (
+                  <p className="text-muted-foreground">No problems found.</p>
+                )}
+                {diagnostics.models.length > 0 && (
+                  <table className="w-full text-left">
+                    <thead className="text-xs text-muted-foreground">
+                      <tr>
+                        <th className="font-medium">Model</th>
+                        <th className="font-medium">Needs</th>
+                        <th className="font-medium">RAM</th>
+                        <th className="font-medium">GPU</th>
+                        <th className="font-medium">Loaded</th>
+                      </tr>
+                    </thead>
+                    <tbody>
+                      {diagnostics.models.map((model) => (
+                        <tr key={model.name}>
+                          <td className="font-mono">
+                            {model.name}
+                            {model.parameter_size && (
+                              <span className="ml-1 text-xs text-muted-foreground">{model.parameter_size}</span>
+                            )}
+                          </td>
+                          <td>{gigabytes(model.required_bytes)}</td>
+                          <td className={model.fits_in_ram ? "" : "text-destructive"}>
+                            {model.fits_in_ram ? "fits" : "too large"}
+                          </td>
+                          <td className={model.fits_in_vram === false ? "text-destructive" : ""}>
+                            {model.fits_in_vram === null ? "–" : model.fits_in_vram ? "fits" : "too large"}
+                          </td>
+                          <td>{model.loaded ? "yes" : ""}</td>
+                        </tr>
+                      ))}
+                    </tbody>
+                  </table>
+                )}
+              </div>
+            )}
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Prompt Templates */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <FileText className="h-5 w-5" />
+            Prompt Templates
+          </CardTitle>
+          <CardDescription>
+            Tune the prompts sent to the model. Changes are stored as files and used from the next request on.
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          {(() => {
+            const current = promptTemplates.find((t) => t.kind === promptKind);
+            return (
+              <div className="space-y-4">
+                <div className="flex items-center justify-between gap-2">
+                  <select
+                    value={promptKind}
+                    onChange={(e) => selectPrompt(e.target.value as PromptKind)}
+                    className="h-10 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                  >
+                    {(Object.keys(promptLabels) as PromptKind[]).map((kind) => (
+                      <option key={kind} value={kind}>
+                        {promptLabels[kind]}
+                      </option>
+                    ))}
+                  </select>
+                  <span className="text-sm text-muted-foreground">{current?.customized ? "Customized" : "Built-in"}</span>
+                </div>
+                <textarea
+                  value={promptDraft}
+                  onChange={(e) => setPromptDraft(e.target.value)}
+                  rows={12}
+                  className="block w-full rounded-md border bg-background p-3 font-mono text-xs focus:outline-none focus:ring-2 focus:ring-ring"
+                />
+                {current && (
+                  <p className="text-sm text-muted-foreground">
+                    Placeholders: <span className="font-mono">{current.placeholders.join(" ")}</span>. Stored in{" "}
+                    <span className="font-mono">{current.path}</span>.
+                  </p>
+                )}
+                {promptError && <p className="text-sm text-destructive">{promptError}</p>}
+                <div className="flex justify-end gap-2">
+                  <Button variant="outline" onClick={() => savePrompt(true)} disabled={!current?.customized}>
+                    Reset to built-in
+                  </Button>
+                  <Button onClick={() => savePrompt(false)} disabled={!current || promptDraft === current.content}>
+                    Save
+                  </Button>
+                </div>
+              </div>
+            );
+          })()}
+        </CardContent>
+      </Card>
+
+      {/* Process Knowledge */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <BookOpen className="h-5 w-5" />
+            Process Knowledge
+          </CardTitle>
+          <CardDescription>
+            Own explanations for processes; they are used before the built-in ones and without asking the model
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="space-y-4">
+            {knowledgeEntries.length > 0 && (
+              <div className="space-y-2">
+                {knowledgeEntries.map((entry) => (
+                  <div key={entry.name} className="rounded-lg border p-3 text-sm">
+                    <div className="flex items-center justify-between gap-2">
+                      <span className="font-medium">{entry.name}</span>
+                      <span className="text-xs text-muted-foreground">
+                        {safetyLabels[entry.safety]} · {entry.category}
+                      </span>
+                    </div>
+                    <p className="font-mono text-xs text-muted-foreground">{entry.patterns.join(", ")}</p>
+                    <p className="text-muted-foreground">{entry.explanation}</p>
+                  </div>
+                ))}
+              </div>
+            )}
+            <div className="flex flex-wrap gap-2">
+              <input
+                type="text"
+                placeholder="Name"
+                value={knowledgeDraft.name}
+                onChange={(e) => setKnowledgeDraft({ ...knowledgeDraft, name: e.target.value })}
+                className="h-10 w-40 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
               />
+              <input
+                type="text"
+                placeholder="Patterns, e.g. *backup-agent*, rsyncd"
+                value={knowledgeDraft.patterns}
+                onChange={(e) => setKnowledgeDraft({ ...knowledgeDraft, patterns: e.target.value })}
+                className="h-10 min-w-48 flex-1 rounded-md border bg-background px-3 font-mono text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <select
+                value={knowledgeDraft.safety}
+                onChange={(e) => setKnowledgeDraft({ ...knowledgeDraft, safety: e.target.value as ProcessSafety })}
+                className="h-10 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              >
+                {(Object.keys(safetyLabels) as ProcessSafety[]).map((safety) => (
+                  <option key={safety} value={safety}>
+                    {safetyLabels[safety]}
+                  </option>
+                ))}
+              </select>
+              <select
+                value={knowledgeDraft.category}
+                onChange={(e) => setKnowledgeDraft({ ...knowledgeDraft, category: e.target.value as ProcessCategory })}
+                className="h-10 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              >
+                {categoryOptions.map((category) => (
+                  <option key={category} value={category}>
+                    {category}
+                  </option>
+                ))}
+              </select>
             </div>
+            <div className="flex gap-2">
+              <input
+                type="text"
+                placeholder="What the process does"
+                value={knowledgeDraft.explanation}
+                onChange={(e) => setKnowledgeDraft({ ...knowledgeDraft, explanation: e.target.value })}
+                className="h-10 flex-1 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <Button
+                variant="outline"
+                onClick={addKnowledgeEntry}
+                disabled={!knowledgeDraft.name.trim() || !knowledgeDraft.patterns.trim() || !knowledgeDraft.explanation.trim()}
+              >
+                Add
+              </Button>
+            </div>
+            {knowledgeError && <p className="text-sm text-destructive">{knowledgeError}</p>}
+            <p className="text-sm text-muted-foreground">
+              Patterns match the whole process name, ignoring case; <span className="font-mono">*</span> matches
+              anything. An entry with an existing name replaces it.
+            </p>
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Storage */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <HardDrive className="h-5 w-5" />
+            Storage
+          </CardTitle>
+          <CardDescription>
+            {storagePaths?.portable
+              ? "Portable mode: all state lives next to the executable"
+              : "Where settings, reports and logs are stored. Leave empty for the default."}
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="space-y-4">
             <div>
-              <label className="text-sm font-medium">Model</label>
+              <label className="text-sm font-medium">Config directory</label>
+              <input
+                type="text"
+                placeholder={storagePaths?.config_dir ?? "Default"}
+                value={storageDraft.config_dir ?? ""}
+                onChange={(e) => setStorageDraft({ ...storageDraft, config_dir: e.target.value || null })}
+                className="mt-1 block w-full rounded-md border bg-background px-3 py-2 font-mono text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+            </div>
+            <div>
+              <label className="text-sm font-medium">Data directory</label>
+              <input
+                type="text"
+                placeholder={storagePaths?.data_dir ?? "Default"}
+                value={storageDraft.data_dir ?? ""}
+                onChange={(e) => setStorageDraft({ ...storageDraft, data_dir: e.target.value || null })}
+                className="mt-1 block w-full rounded-md border bg-background px-3 py-2 font-mono text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+            </div>
+            <div>
+              <label className="text-sm font-medium">Log directory</label>
+              <input
+                type="text"
+                placeholder={storagePaths?.log_dir ?? "Default"}
+                value={storageDraft.log_dir ?? ""}
+                onChange={(e) => setStorageDraft({ ...storageDraft, log_dir: e.target.value || null })}
+                className="mt-1 block w-full rounded-md border bg-background px-3 py-2 font-mono text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+            </div>
+            {storageError && <p className="text-sm text-destructive">{storageError}</p>}
+            <div className="flex items-center justify-between">
+              <p className="text-sm text-muted-foreground">
+                Existing files are moved when a directory changes. Start with --portable to keep everything next to the app.
+              </p>
+              <Button variant="outline" onClick={applyStorage} disabled={!config}>
+                Apply
+              </Button>
+            </div>
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Port Scanner */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <Radar className="h-5 w-5" />
+            Port Scanner
+          </CardTitle>
+          <CardDescription>
+            {nmapStatus?.installed
+              ? `Scans can use ${nmapStatus.version ?? "nmap"} instead of the built-in scanner`
+              : "Install nmap for service and version detection; the built-in scanner is used until then"}
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="space-y-4">
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">Use nmap</p>
+                <p className="text-sm text-muted-foreground">
+                  TCP connect scan without scripts; falls back to the built-in scanner if nmap fails
+                </p>
+              </div>
+              <Button
+                variant="outline"
+                onClick={() => config && updateNmap({ enabled: !config.nmap.enabled })}
+                disabled={!config || !nmapStatus?.installed}
+              >
+                {config?.nmap.enabled ? "Enabled" : "Disabled"}
+              </Button>
+            </div>
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">Version detection</p>
+                <p className="text-sm text-muted-foreground">Identify product and version behind open ports</p>
+              </div>
+              <Button
+                variant="outline"
+                onClick={() => config && updateNmap({ version_detection: !config.nmap.version_detection })}
+                disabled={!config?.nmap.enabled}
+              >
+                {config?.nmap.version_detection ? "Enabled" : "Disabled"}
+              </Button>
+            </div>
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">OS detection</p>
+                <p className="text-sm text-muted-foreground">
+                  Guess the operating system of scanned hosts. Needs root, otherwise scans fall back.
+                </p>
+              </div>
+              <Button
+                variant="outline"
+                onClick={() => config && updateNmap({ os_detection: !config.nmap.os_detection })}
+                disabled={!config?.nmap.enabled}
+              >
+                {config?.nmap.os_detection ? "Enabled" : "Disabled"}
+              </Button>
+            </div>
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">Timing</p>
+                <p className="text-sm text-muted-foreground">Higher is faster but less reliable on slow networks</p>
+              </div>
+              <select
+                value={config?.nmap.timing ?? 3}
+                onChange={(e) => updateNmap({ timing: Number(e.target.value) })}
+                disabled={!config?.nmap.enabled}
+                className="h-10 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              >
+                <option value={1}>Sneaky (T1)</option>
+                <option value={2}>Polite (T2)</option>
+                <option value={3}>Normal (T3)</option>
+                <option value={4}>Aggressive (T4)</option>
+              </select>
+            </div>
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Hash Reputation */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <FileSearch className="h-5 w-5" />
+            Hash Reputation
+          </CardTitle>
+          <CardDescription>
+            Check the SHA-256 of executables behind listening ports against a malware database. Only the hash is sent.
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="space-y-4">
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">Look up hashes</p>
+                <p className="text-sm text-muted-foreground">
+                  During security scans and in process explanations; needs an API key
+                </p>
+              </div>
+              <Button
+                variant="outline"
+                onClick={() => config && updateHashReputation({ enabled: !config.hash_reputation.enabled })}
+                disabled={!config}
+              >
+                {config?.hash_reputation.enabled ? "Enabled" : "Disabled"}
+              </Button>
+            </div>
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">Provider</p>
+                <p className="text-sm text-muted-foreground">
+                  The free VirusTotal API allows 4 lookups per minute; new hashes beyond that wait for the next scan
+                </p>
+              </div>
+              <select
+                value={config?.hash_reputation.provider ?? "malwarebazaar"}
+                onChange={(e) => updateHashReputation({ provider: e.target.value as HashReputationConfig["provider"] })}
+                className="h-10 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              >
+                <option value="malwarebazaar">MalwareBazaar</option>
+                <option value="virustotal">VirusTotal</option>
+              </select>
+            </div>
+            <div className="flex items-center justify-between gap-4">
+              <div>
+                <p className="font-medium">API key</p>
+                <p className="text-sm text-muted-foreground">
+                  {config?.hash_reputation.provider === "virustotal"
+                    ? "From your VirusTotal profile"
+                    : "Auth-Key from auth.abuse.ch"}
+                </p>
+              </div>
+              <input
+                type="password"
+                value={reputationKey}
+                onChange={(e) => setReputationKey(e.target.value)}
+                onBlur={() => updateHashReputation({ api_key: reputationKey.trim() || null })}
+                className="h-10 w-64 rounded-md border bg-background px-3 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+            </div>
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Cloud AI */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <Bot className="h-5 w-5" />
+            Cloud AI
+          </CardTitle>
+          <CardDescription>
+            Use Anthropic or OpenAI when this machine can't run a local model. Off by default; data leaves the machine.
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="space-y-4">
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">Send AI requests to the cloud</p>
+                <p className="text-sm text-muted-foreground">
+                  Replaces Ollama for all generations; similarity search and line grouping keep using local embeddings
+                </p>
+              </div>
+              <Button
+                variant={config?.cloud_llm.enabled ? "destructive" : "outline"}
+                onClick={toggleCloudLlm}
+                disabled={!config || (!config.cloud_llm.enabled && !cloudDraft.api_key.trim())}
+              >
+                {config?.cloud_llm.enabled ? "Enabled" : "Disabled"}
+              </Button>
+            </div>
+            <div className="flex items-center justify-between">
+              <p className="font-medium">Provider</p>
+              <select
+                value={config?.cloud_llm.provider ?? "anthropic"}
+                onChange={(e) => updateCloudLlm({ provider: e.target.value as CloudLlmConfig["provider"] })}
+                className="h-10 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              >
+                <option value="anthropic">Anthropic</option>
+                <option value="openai">OpenAI</option>
+              </select>
+            </div>
+            <div className="flex items-center justify-between gap-4">
+              <p className="font-medium">API key</p>
+              <input
+                type="password"
+                value={cloudDraft.api_key}
+                onChange={(e) => setCloudDraft({ ...cloudDraft, api_key: e.target.value })}
+                onBlur={() => updateCloudLlm({ api_key: cloudDraft.api_key.trim() || null })}
+                className="h-10 w-64 rounded-md border bg-background px-3 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+            </div>
+            <div className="flex items-center justify-between gap-4">
+              <div>
+                <p className="font-medium">Model</p>
+                <p className="text-sm text-muted-foreground">Empty for the provider's default</p>
+              </div>
+              <input
+                type="text"
+                value={cloudDraft.model}
+                onChange={(e) => setCloudDraft({ ...cloudDraft, model: e.target.value })}
+                onBlur={() => updateCloudLlm({ model: cloudDraft.model.trim() })}
+                className="h-10 w-64 rounded-md border bg-background px-3 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+            </div>
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Port Names */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <Tag className="h-5 w-5" />
+            Port Names
+          </CardTitle>
+          <CardDescription>
+            Ports are labeled with their IANA service name (5432 → postgresql). Names set here take precedence.
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="space-y-4">
+            {config && config.service_names.length > 0 && (
+              <div className="flex flex-wrap gap-2">
+                {config.service_names.map((entry) => (
+                  <span
+                    key={`${entry.port}-${entry.protocol ?? "any"}`}
+                    className="flex items-center gap-1 rounded-md border px-2 py-1 font-mono text-sm"
+                  >
+                    {entry.port}
+                    {entry.protocol && `/${entry.protocol}`} → {entry.name}
+                    <button
+                      title="Remove"
+                      className="text-muted-foreground hover:text-foreground"
+                      onClick={() => saveServiceNames(config.service_names.filter((n) => n !== entry))}
+                    >
+                      <X className="h-3 w-3" />
+                    </button>
+                  </span>
+                ))}
+              </div>
+            )}
+            <div className="flex flex-wrap gap-2">
+              <input
+                type="number"
+                placeholder="Port (3000)"
+                value={namePort}
+                onChange={(e) => setNamePort(e.target.value)}
+                className="h-10 w-32 rounded-md border bg-background px-3 font-mono text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <select
+                value={nameProtocol}
+                onChange={(e) => setNameProtocol(e.target.value as Protocol | "")}
+                className="h-10 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              >
+                <option value="">TCP + UDP</option>
+                <option value="tcp">TCP</option>
+                <option value="udp">UDP</option>
+              </select>
+              <input
+                type="text"
+                placeholder="Name (frontend)"
+                value={nameLabel}
+                onChange={(e) => setNameLabel(e.target.value)}
+                className="h-10 min-w-48 flex-1 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <Button variant="outline" onClick={addServiceName} disabled={!config || !namePort || !nameLabel.trim()}>
+                Add
+              </Button>
+            </div>
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Alerts */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <BellRing className="h-5 w-5" />
+            Alerts
+          </CardTitle>
+          <CardDescription>
+            Rules are checked every {config?.alerts.interval_seconds ?? 30}s. Fired alerts appear on the dashboard and in the audit log.
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="space-y-4">
+            {config?.alerts.rules.map((rule) => (
+              <div key={rule.id} className="flex items-center justify-between rounded-md border p-3">
+                <div>
+                  <p className="font-medium">{rule.name}</p>
+                  <p className="text-sm text-muted-foreground">
+                    {describeCondition(rule.condition)}
+                    {rule.service_id ? ` • ${rule.service_id}` : " • all services / system"}
+                  </p>
+                </div>
+                <div className="flex items-center gap-2">
+                  <Button
+                    variant="outline"
+                    onClick={() =>
+                      saveAlertRules(
+                        config.alerts.rules.map((r) => (r.id === rule.id ? { ...r, enabled: !r.enabled } : r))
+                      )
+                    }
+                  >
+                    {rule.enabled ? "Enabled" : "Disabled"}
+                  </Button>
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    title="Remove"
+                    onClick={() => saveAlertRules(config.alerts.rules.filter((r) => r.id !== rule.id))}
+                  >
+                    <X className="h-4 w-4" />
+                  </Button>
+                </div>
+              </div>
+            ))}
+            <div className="flex flex-wrap gap-2">
               <input
                 type="text"
-                value="mistral:7b-instruct"
-                disabled
-                className="mt-1 block w-full rounded-md border bg-muted px-3 py-2 text-sm"
+                placeholder="Name (optional)"
+                value={alertName}
+                onChange={(e) => setAlertName(e.target.value)}
+                className="h-10 min-w-40 flex-1 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
               />
+              <select
+                value={alertKind}
+                onChange={(e) => {
+                  const kind = e.target.value as AlertCondition["kind"];
+                  setAlertKind(kind);
+                  setAlertThreshold(kind === "memory_above" ? "2" : "80");
+                }}
+                className="h-10 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              >
+                {alertKinds.map((kind) => (
+                  <option key={kind.value} value={kind.value}>
+                    {kind.label}
+                  </option>
+                ))}
+              </select>
+              {(alertKind === "cpu_above" || alertKind === "memory_above") && (
+                <>
+                  <input
+                    type="number"
+                    min={0}
+                    step={alertKind === "memory_above" ? 0.5 : 5}
+                    title={alertKind === "memory_above" ? "GB" : "Percent"}
+                    value={alertThreshold}
+                    onChange={(e) => setAlertThreshold(e.target.value)}
+                    className="h-10 w-24 rounded-md border bg-background px-3 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                  />
+                  <input
+                    type="number"
+                    min={0}
+                    title="Minutes"
+                    placeholder="Minutes"
+                    value={alertMinutes}
+                    onChange={(e) => setAlertMinutes(e.target.value)}
+                    className="h-10 w-24 rounded-md border bg-background px-3 font-mono text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+                  />
+                </>
+              )}
+              <input
+                type="text"
+                placeholder="Service ID (all)"
+                value={alertService}
+                onChange={(e) => setAlertService(e.target.value)}
+                className="h-10 w-48 rounded-md border bg-background px-3 font-mono text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <Button
+                variant="outline"
+                onClick={addAlertRule}
+                disabled={
+                  !config ||
+                  ((alertKind === "cpu_above" || alertKind === "memory_above") && !(Number(alertThreshold) > 0))
+                }
+              >
+                Add
+              </Button>
             </div>
           </div>
         </CardContent>
       </Card>
+
+      {/* Webhooks */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <Webhook className="h-5 w-5" />
+            Webhooks
+          </CardTitle>
+          <CardDescription>
+            POST status changes, alerts and security findings as JSON to your own automation. With a secret, the body is
+            signed with HMAC-SHA256 in the X-Network-Manager-Signature header.
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="space-y-4">
+            {config?.webhooks.map((webhook) => (
+              <div key={webhook.id} className="rounded-md border p-3">
+                <div className="flex items-center justify-between">
+                  <div className="min-w-0">
+                    <p className="font-medium">{webhook.name}</p>
+                    <p className="truncate font-mono text-sm text-muted-foreground">{webhook.url}</p>
+                    <p className="text-xs text-muted-foreground">
+                      {webhook.events.length === 0
+                        ? "All events"
+                        : webhookEvents
+                            .filter((e) => webhook.events.includes(e.value))
+                            .map((e) => e.label)
+                            .join(", ")}
+                      {webhook.secret ? " • signed" : ""}
+                    </p>
+                  </div>
+                  <div className="flex items-center gap-2">
+                    <Button variant="outline" size="sm" onClick={() => sendTestWebhook(webhook.id)}>
+                      Test
+                    </Button>
+                    <Button
+                      variant="outline"
+                      onClick={() =>
+                        saveWebhooks(
+                          config.webhooks.map((w) => (w.id === webhook.id ? { ...w, enabled: !w.enabled } : w))
+                        )
+                      }
+                    >
+                      {webhook.enabled ? "Enabled" : "Disabled"}
+                    </Button>
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      title="Remove"
+                      onClick={() => saveWebhooks(config.webhooks.filter((w) => w.id !== webhook.id))}
+                    >
+                      <X className="h-4 w-4" />
+                    </Button>
+                  </div>
+                </div>
+                {webhookResult?.id === webhook.id && (
+                  <p className="mt-2 text-sm text-muted-foreground">{webhookResult.message}</p>
+                )}
+              </div>
+            ))}
+            <div className="flex flex-wrap gap-2">
+              <input
+                type="text"
+                placeholder="Name (optional)"
+                value={webhookName}
+                onChange={(e) => setWebhookName(e.target.value)}
+                className="h-10 w-40 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <input
+                type="url"
+                placeholder="https://example.com/hook"
+                value={webhookUrl}
+                onChange={(e) => setWebhookUrl(e.target.value)}
+                className="h-10 min-w-60 flex-1 rounded-md border bg-background px-3 font-mono text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <input
+                type="password"
+                placeholder="Secret (optional)"
+                value={webhookSecret}
+                onChange={(e) => setWebhookSecret(e.target.value)}
+                className="h-10 w-44 rounded-md border bg-background px-3 text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <Button variant="outline" onClick={addWebhook} disabled={!config || !/^https?:\/\//.test(webhookUrl.trim())}>
+                Add
+              </Button>
+            </div>
+            <div className="flex flex-wrap items-center gap-2 text-sm">
+              <span className="text-muted-foreground">Events (none = all):</span>
+              {webhookEvents.map((event) => (
+                <Button
+                  key={event.value}
+                  size="sm"
+                  variant={webhookEventFilter.includes(event.value) ? "secondary" : "outline"}
+                  onClick={() =>
+                    setWebhookEventFilter((current) =>
+                      current.includes(event.value)
+                        ? current.filter((v) => v !== event.value)
+                        : [...current, event.value]
+                    )
+                  }
+                >
+                  {event.label}
+                </Button>
+              ))}
+            </div>
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Export */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <Download className="h-5 w-5" />
+            Export
+          </CardTitle>
+          <CardDescription>
+            Save the audit log, security report, inventory or resource usage as a file
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="space-y-4">
+            <div className="flex flex-wrap gap-2">
+              <select
+                value={exportKind}
+                onChange={(e) => setExportKind(e.target.value as ExportKind)}
+                className="h-10 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              >
+                <option value="audit">Audit log</option>
+                <option value="security">Security report</option>
+                <option value="inventory">Inventory</option>
+                <option value="metrics">Metrics</option>
+              </select>
+              <select
+                value={exportFormat}
+                onChange={(e) => setExportFormat(e.target.value as ExportFormat)}
+                className="h-10 rounded-md border bg-background px-3 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+              >
+                <option value="json">JSON</option>
+                <option value="csv">CSV</option>
+                <option value="markdown">Markdown</option>
+                <option value="html">HTML</option>
+              </select>
+              <input
+                type="text"
+                placeholder="File or directory (default: exports folder)"
+                value={exportPath}
+                onChange={(e) => setExportPath(e.target.value)}
+                className="h-10 min-w-64 flex-1 rounded-md border bg-background px-3 font-mono text-sm placeholder:text-muted-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+              />
+              <Button variant="outline" onClick={runExport} disabled={isExporting}>
+                {isExporting ? "Exporting..." : "Export"}
+              </Button>
+            </div>
+            {exportError && <p className="text-sm text-destructive">{exportError}</p>}
+            {exportResult && <p className="break-all text-sm">{exportResult}</p>}
+            {templateDir && (
+              <p className="text-sm text-muted-foreground">
+                Markdown and HTML use the templates in <span className="font-mono">{templateDir}</span> when present
+                (<span className="font-mono">security.html</span>, <span className="font-mono">default.md</span>, ...).
+              </p>
+            )}
+          </div>
+        </CardContent>
+      </Card>
+
+      {/* Remote Viewer */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <Smartphone className="h-5 w-5" />
+            Remote Viewer
+          </CardTitle>
+          <CardDescription>
+            Watch services and stats from a phone or second machine, without control rights
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="space-y-4">
+            <div className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">
+                  {viewerStatus?.running ? `Running on port ${viewerStatus.port}` : "Stopped"}
+                </p>
+                <p className="text-sm text-muted-foreground">
+                  {viewerStatus?.paired_devices ?? 0} paired device(s)
+                </p>
+              </div>
+              <div className="flex gap-2">
+                <Button variant="outline" onClick={startPairing}>
+                  Pair Device
+                </Button>
+                {viewerStatus?.running && (
+                  <Button variant="outline" onClick={stopViewer}>
+                    Stop & Unpair All
+                  </Button>
+                )}
+              </div>
+            </div>
+            {viewerError && <p className="text-sm text-destructive">{viewerError}</p>}
+            {viewerStatus && viewerStatus.devices.length > 0 && (
+              <div className="space-y-2">
+                {viewerStatus.devices.map((device) => (
+                  <div key={device.id} className="flex items-center justify-between rounded-lg border p-3">
+                    <div className="min-w-0">
+                      <p className="truncate text-sm font-medium">
+                        {device.pending ? "Waiting for scan" : device.user_agent ?? "Unknown device"}
+                      </p>
+                      <p className="text-xs text-muted-foreground">
+                        {device.pending ? "Code expires" : "Session expires"}{" "}
+                        {new Date(device.expires_at).toLocaleString()}
+                        {device.last_seen && ` · last seen ${new Date(device.last_seen).toLocaleString()}`}
+                      </p>
+                    </div>
+                    <Button variant="outline" size="sm" onClick={() => revokeDevice(device.id)}>
+                      Revoke
+                    </Button>
+                  </div>
+                ))}
+              </div>
+            )}
+            {pairing && (
+              <div className="flex items-center gap-4">
+                <div
+                  className="h-40 w-40 shrink-0"
+                  dangerouslySetInnerHTML={{ __html: pairing.qr_svg }}
+                />
+                <div className="min-w-0">
+                  <p className="text-sm text-muted-foreground">
+                    Scan the code or open this URL on a device in the same network. The link
+                    works once and expires {new Date(pairing.expires_at).toLocaleTimeString()}:
+                  </p>
+                  <p className="mt-1 break-all font-mono text-xs">{pairing.url}</p>
+                </div>
+              </div>
+            )}
+          </div>
+        </CardContent>
+      </Card>
     </div>
   );
 }
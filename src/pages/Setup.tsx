@@ -0,0 +1,241 @@
+import { useEffect, useState } from "react";
+import { useNavigate } from "react-router-dom";
+import { listen } from "@tauri-apps/api/event";
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "../components/ui/card";
+import { Button } from "../components/ui/button";
+import { Badge } from "../components/ui/badge";
+import * as api from "../lib/tauri/commands";
+import type { BootstrapStatus, PullProgress, ToolStatus } from "../lib/tauri/types";
+import { Bot, Camera, CheckCircle2, Copy, RefreshCw, Wrench, XCircle } from "lucide-react";
+
+function formatBytes(bytes: number): string {
+  if (bytes >= 1024 ** 3) return `${(bytes / 1024 ** 3).toFixed(1)} GB`;
+  return `${Math.round(bytes / 1024 ** 2)} MB`;
+}
+
+function ToolRow({ tool }: { tool: ToolStatus }) {
+  const ok = tool.installed && tool.running !== false;
+  return (
+    <div className="rounded-md border p-3">
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-2">
+          {ok ? (
+            <CheckCircle2 className="h-4 w-4 text-green-500" />
+          ) : (
+            <XCircle className="h-4 w-4 text-muted-foreground" />
+          )}
+          <span className="font-medium">{tool.tool}</span>
+          {tool.version && <span className="text-xs text-muted-foreground">{tool.version}</span>}
+        </div>
+        {!tool.installed ? (
+          <Badge variant="outline">Not installed</Badge>
+        ) : tool.running === false ? (
+          <Badge variant="secondary">Not running</Badge>
+        ) : (
+          <Badge>Ready</Badge>
+        )}
+      </div>
+      <p className="mt-1 text-sm text-muted-foreground">{tool.purpose}</p>
+      {!tool.installed && (
+        <div className="mt-2 space-y-1">
+          {tool.install_commands.map((command) => (
+            <div key={command} className="flex items-center gap-2">
+              <code className="flex-1 rounded bg-muted px-2 py-1 text-xs">{command}</code>
+              <Button
+                variant="ghost"
+                size="sm"
+                title="Copy"
+                onClick={() => navigator.clipboard.writeText(command).catch(() => {})}
+              >
+                <Copy className="h-4 w-4" />
+              </Button>
+            </div>
+          ))}
+          <a href={tool.docs_url} target="_blank" rel="noreferrer" className="text-xs text-primary underline">
+            Installation guide
+          </a>
+        </div>
+      )}
+    </div>
+  );
+}
+
+export function Setup() {
+  const navigate = useNavigate();
+  const [status, setStatus] = useState<BootstrapStatus | null>(null);
+  const [isChecking, setIsChecking] = useState(false);
+  const [pullProgress, setPullProgress] = useState<PullProgress | null>(null);
+  const [isPulling, setIsPulling] = useState(false);
+  const [isSnapshotting, setIsSnapshotting] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = async () => {
+    setIsChecking(true);
+    try {
+      setStatus(await api.getBootstrapStatus());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsChecking(false);
+    }
+  };
+
+  useEffect(() => {
+    refresh();
+  }, []);
+
+  const handlePull = async () => {
+    setIsPulling(true);
+    setError(null);
+    const unlisten = await listen<PullProgress>("model-pull-progress", (event) => {
+      setPullProgress(event.payload);
+    });
+    try {
+      await api.pullOllamaModel();
+      await refresh();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      unlisten();
+      setIsPulling(false);
+      setPullProgress(null);
+    }
+  };
+
+  const handleBaseline = async () => {
+    setIsSnapshotting(true);
+    setError(null);
+    try {
+      const baseline = await api.createBaselineSnapshot();
+      setStatus((current) => (current ? { ...current, baseline } : current));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsSnapshotting(false);
+    }
+  };
+
+  const handleFinish = async () => {
+    await api.completeBootstrap().catch(() => {});
+    navigate("/");
+  };
+
+  const ollama = status?.tools.find((tool) => tool.tool === "ollama");
+  const percent =
+    pullProgress?.total && pullProgress.completed != null
+      ? Math.round((pullProgress.completed / pullProgress.total) * 100)
+      : null;
+
+  return (
+    <div className="mx-auto max-w-3xl space-y-6">
+      {/* Header */}
+      <div className="flex items-center justify-between">
+        <div>
+          <h1 className="text-3xl font-bold tracking-tight">Setup</h1>
+          <p className="text-muted-foreground">
+            Optional tools, the AI model and a first snapshot of this machine
+          </p>
+        </div>
+        <Button variant="outline" onClick={refresh} disabled={isChecking}>
+          <RefreshCw className={`mr-2 h-4 w-4 ${isChecking ? "animate-spin" : ""}`} />
+          Check again
+        </Button>
+      </div>
+      {error && <p className="text-sm text-destructive">{error}</p>}
+
+      {/* Tools */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <Wrench className="h-5 w-5" />
+            Tools
+          </CardTitle>
+          <CardDescription>
+            Everything works without them, but discovery and analysis are more complete when they are installed
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-2">
+          {!status ? (
+            <p className="text-sm text-muted-foreground">Checking...</p>
+          ) : (
+            status.tools.map((tool) => <ToolRow key={tool.tool} tool={tool} />)
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Model */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <Bot className="h-5 w-5" />
+            AI Model
+          </CardTitle>
+          <CardDescription>Log analysis and process explanations run locally through Ollama</CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="flex items-center justify-between">
+            <div>
+              <p className="font-mono text-sm">{status?.model ?? "-"}</p>
+              <p className="text-sm text-muted-foreground">
+                {status?.model_installed
+                  ? "Downloaded"
+                  : ollama?.running
+                    ? "Not downloaded yet"
+                    : "Start Ollama to download the model"}
+              </p>
+            </div>
+            <Button onClick={handlePull} disabled={isPulling || !ollama?.running || status?.model_installed}>
+              {isPulling ? "Downloading..." : "Download"}
+            </Button>
+          </div>
+          {pullProgress && (
+            <div className="space-y-1">
+              <div className="h-2 overflow-hidden rounded bg-muted">
+                <div className="h-full bg-primary transition-all" style={{ width: `${percent ?? 0}%` }} />
+              </div>
+              <p className="text-xs text-muted-foreground">
+                {pullProgress.status}
+                {percent != null &&
+                  ` – ${formatBytes(pullProgress.completed ?? 0)} of ${formatBytes(pullProgress.total ?? 0)}`}
+              </p>
+            </div>
+          )}
+        </CardContent>
+      </Card>
+
+      {/* Baseline */}
+      <Card>
+        <CardHeader>
+          <CardTitle className="flex items-center gap-2">
+            <Camera className="h-5 w-5" />
+            Baseline
+          </CardTitle>
+          <CardDescription>
+            Records today's services, open ports and findings; the weekly digest reports changes against it
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <div className="flex items-center justify-between">
+            <p className="text-sm text-muted-foreground">
+              {status?.baseline
+                ? `${status.baseline.services} services, ${status.baseline.ports} ports, ${status.baseline.security_issues} findings on ${new Date(status.baseline.created_at).toLocaleString()}`
+                : "No baseline yet"}
+            </p>
+            <Button variant="outline" onClick={handleBaseline} disabled={isSnapshotting}>
+              {isSnapshotting ? "Recording..." : status?.baseline ? "Record again" : "Record"}
+            </Button>
+          </div>
+        </CardContent>
+      </Card>
+
+      <div className="flex justify-end gap-2">
+        <Button variant="ghost" onClick={handleFinish}>
+          Skip
+        </Button>
+        <Button onClick={handleFinish} disabled={isPulling || isSnapshotting}>
+          Finish
+        </Button>
+      </div>
+    </div>
+  );
+}
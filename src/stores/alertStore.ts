@@ -0,0 +1,35 @@
+import { create } from "zustand";
+import type { Alert } from "../lib/tauri/types";
+import * as api from "../lib/tauri/commands";
+
+// Alerts kept in memory; older ones are in the history database
+const MAX_ALERTS = 100;
+
+interface AlertState {
+  alerts: Alert[];
+  error: string | null;
+
+  // Actions
+  fetchAlerts: () => Promise<void>;
+  addAlert: (alert: Alert) => void;
+}
+
+export const useAlertStore = create<AlertState>((set) => ({
+  alerts: [],
+  error: null,
+
+  fetchAlerts: async () => {
+    try {
+      const alerts = await api.getAlertHistory(MAX_ALERTS);
+      set({ alerts, error: null });
+    } catch (error) {
+      set({ error: String(error) });
+    }
+  },
+
+  addAlert: (alert) => {
+    set((state) => ({
+      alerts: [alert, ...state.alerts.filter((a) => a.id !== alert.id)].slice(0, MAX_ALERTS),
+    }));
+  },
+}));
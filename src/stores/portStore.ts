@@ -1,22 +1,33 @@
 import { create } from "zustand";
-import type { PortInfo } from "../lib/tauri/types";
+import type { ImpactPreview, PortInfo, TunnelStatus } from "../lib/tauri/types";
 import * as api from "../lib/tauri/commands";
+import { runConfirmed } from "./serviceStore";
 
 interface PortState {
   ports: PortInfo[];
   isLoading: boolean;
   error: string | null;
+  /** Impact of the last free_port call that was skipped in dry-run mode */
+  dryRunPreview: ImpactPreview | null;
+  tunnels: TunnelStatus[];
 
   // Actions
   fetchPortUsage: () => Promise<void>;
-  scanPorts: (start: number, end: number) => Promise<void>;
+  scanPorts: (start: number, end: number, target?: string, ports?: number[]) => Promise<void>;
   findFreePorts: (count: number) => Promise<number[]>;
+  freePort: (port: number) => Promise<void>;
+  fetchTunnels: () => Promise<void>;
+  startTunnel: (id: string) => Promise<void>;
+  stopTunnel: (id: string) => Promise<void>;
+  removeTunnel: (id: string) => Promise<void>;
 }
 
-export const usePortStore = create<PortState>((set) => ({
+export const usePortStore = create<PortState>((set, get) => ({
   ports: [],
   isLoading: false,
   error: null,
+  dryRunPreview: null,
+  tunnels: [],
 
   fetchPortUsage: async () => {
     set({ isLoading: true, error: null });
@@ -28,10 +39,10 @@ export const usePortStore = create<PortState>((set) => ({
     }
   },
 
-  scanPorts: async (start, end) => {
+  scanPorts: async (start, end, target, portList) => {
     set({ isLoading: true, error: null });
     try {
-      const ports = await api.scanPorts(start, end);
+      const ports = await api.scanPorts(start, end, false, target, portList);
       set({ ports, isLoading: false });
     } catch (error) {
       set({ error: String(error), isLoading: false });
@@ -46,4 +57,53 @@ export const usePortStore = create<PortState>((set) => ({
       return [];
     }
   },
+
+  freePort: async (port) => {
+    try {
+      const outcome = await runConfirmed((token) => api.freePort(port, token));
+      set({ dryRunPreview: outcome?.status === "dry_run" ? outcome.preview : null });
+      if (outcome?.status === "executed") {
+        await get().fetchPortUsage();
+      }
+    } catch (error) {
+      set({ error: String(error) });
+    }
+  },
+
+  fetchTunnels: async () => {
+    try {
+      set({ tunnels: await api.listTunnels() });
+    } catch (error) {
+      set({ error: String(error) });
+    }
+  },
+
+  // Further state changes arrive as TunnelStateChanged events
+  startTunnel: async (id) => {
+    try {
+      const status = await api.startTunnel(id);
+      set((state) => ({ tunnels: state.tunnels.map((t) => (t.spec.id === id ? status : t)) }));
+    } catch (error) {
+      set({ error: String(error) });
+    }
+  },
+
+  stopTunnel: async (id) => {
+    try {
+      const status = await api.stopTunnel(id);
+      set((state) => ({ tunnels: state.tunnels.map((t) => (t.spec.id === id ? status : t)) }));
+      await get().fetchPortUsage();
+    } catch (error) {
+      set({ error: String(error) });
+    }
+  },
+
+  removeTunnel: async (id) => {
+    try {
+      await api.removeTunnel(id);
+      set((state) => ({ tunnels: state.tunnels.filter((t) => t.spec.id !== id) }));
+    } catch (error) {
+      set({ error: String(error) });
+    }
+  },
 }));
@@ -1,14 +1,18 @@
 import { create } from "zustand";
-import type { Service } from "../lib/tauri/types";
+import type { ActionOutcome, ImpactPreview, ProviderWarning, Service } from "../lib/tauri/types";
 import * as api from "../lib/tauri/commands";
 
 interface ServiceState {
   services: Service[];
+  /** Providers that returned incomplete data during the last discovery */
+  warnings: ProviderWarning[];
   selectedService: Service | null;
   isLoading: boolean;
   error: string | null;
   autoRefreshInterval: number | null;
   refreshIntervalMs: number;
+  /** Impact of the last destructive action that was skipped in dry-run mode */
+  dryRunPreview: ImpactPreview | null;
 
   // Actions
   fetchServices: () => Promise<void>;
@@ -23,13 +27,43 @@ interface ServiceState {
   setRefreshInterval: (intervalMs: number) => void;
 }
 
+function describeImpact(preview: ImpactPreview): string {
+  const lines = [`${preview.target_name ?? preview.target} wirklich beenden?`];
+  if (preview.ports_freed.length > 0) {
+    lines.push(`Freigegebene Ports: ${preview.ports_freed.join(", ")}`);
+  }
+  if (preview.dependents_affected.length > 0) {
+    lines.push(`Betroffen: ${preview.dependents_affected.join(", ")}`);
+  }
+  return lines.join("\n");
+}
+
+/**
+ * Run a destructive command, asking the user to confirm the impact preview
+ * when the backend requests it. Returns the final outcome, or null if cancelled.
+ */
+export async function runConfirmed(
+  run: (confirmationToken?: string) => Promise<ActionOutcome>
+): Promise<ActionOutcome | null> {
+  const outcome = await run();
+  if (outcome.status !== "confirmation_required") {
+    return outcome;
+  }
+  if (!window.confirm(describeImpact(outcome.preview))) {
+    return null;
+  }
+  return run(outcome.token);
+}
+
 export const useServiceStore = create<ServiceState>((set, get) => ({
   services: [],
+  warnings: [],
   selectedService: null,
   isLoading: false,
   error: null,
   autoRefreshInterval: null,
   refreshIntervalMs: 3000, // Default 3 seconds
+  dryRunPreview: null,
 
   fetchServices: async () => {
     // Don't set loading state during auto-refresh to avoid flickering
@@ -38,8 +72,8 @@ export const useServiceStore = create<ServiceState>((set, get) => ({
       set({ isLoading: true, error: null });
     }
     try {
-      const services = await api.discoverServices();
-      set({ services, isLoading: false });
+      const { services, warnings } = await api.discoverServices();
+      set({ services, warnings, isLoading: false });
     } catch (error) {
       set({ error: String(error), isLoading: false });
     }
@@ -91,7 +125,8 @@ export const useServiceStore = create<ServiceState>((set, get) => ({
 
   stopService: async (serviceId) => {
     try {
-      await api.stopService(serviceId);
+      const outcome = await runConfirmed((token) => api.stopService(serviceId, token));
+      set({ dryRunPreview: outcome?.status === "dry_run" ? outcome.preview : null });
       await get().fetchServices();
     } catch (error) {
       set({ error: String(error) });
@@ -111,7 +146,9 @@ export const useServiceStore = create<ServiceState>((set, get) => ({
     try {
       const service = get().services.find(s => s.id === serviceId);
       if (service?.pid) {
-        await api.killProcess(service.pid);
+        const pid = service.pid;
+        const outcome = await runConfirmed((token) => api.killProcess(pid, token));
+        set({ dryRunPreview: outcome?.status === "dry_run" ? outcome.preview : null });
         await get().fetchServices();
       }
     } catch (error) {